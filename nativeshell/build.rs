@@ -4,21 +4,46 @@ fn main() {
     #[cfg(target_os = "windows")]
     {
         windows::build!(
+            Windows::ApplicationModel::DataTransfer::{
+                DataTransferManager, DataRequestedEventArgs, DataRequest, DataPackage,
+                DataPackagePropertySet,
+            },
+            Windows::Foundation::{TypedEventHandler, Uri, EventRegistrationToken},
             Windows::Win32::Graphics::Dwm:: {
-                DwmExtendFrameIntoClientArea, DwmSetWindowAttribute, DwmFlush,
+                DwmExtendFrameIntoClientArea, DwmSetWindowAttribute, DwmGetWindowAttribute, DwmFlush,
                 DWMWINDOWATTRIBUTE, DWMNCRENDERINGPOLICY,
             },
             Windows::Win32::Graphics::Dxgi::{
                 IDXGIDevice, IDXGIFactory, IDXGIFactory2, IDXGISwapChain1, IDXGIAdapter,
-                DXGI_SWAP_CHAIN_DESC1, DXGI_SWAP_CHAIN_FULLSCREEN_DESC, DXGI_PRESENT_PARAMETERS
+                DXGI_SWAP_CHAIN_DESC1, DXGI_SWAP_CHAIN_FULLSCREEN_DESC, DXGI_PRESENT_PARAMETERS,
+                CreateDXGIFactory1, IDXGIFactory1, IDXGIAdapter1, IDXGIOutput, IDXGIOutput1,
+                IDXGIOutputDuplication, IDXGIResource, DXGI_OUTDUPL_FRAME_INFO, DXGI_ERROR_WAIT_TIMEOUT,
+                DXGI_ERROR_ACCESS_LOST, DXGI_ERROR_NOT_FOUND, IDXGIOutput6, DXGI_OUTPUT_DESC1,
+                DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020,
+            },
+            Windows::Win32::Graphics::Direct3D11::{
+                D3D11CreateDevice, ID3D11Device, ID3D11DeviceContext, ID3D11Texture2D,
+                D3D11_TEXTURE2D_DESC, D3D11_MAPPED_SUBRESOURCE, D3D11_SUBRESOURCE_DATA, D3D11_SDK_VERSION,
+                D3D_DRIVER_TYPE_UNKNOWN, D3D11_USAGE_STAGING, D3D11_CPU_ACCESS_READ, D3D11_MAP_READ,
             },
             Windows::Win32::Graphics::Gdi::{
                 EnumDisplayMonitors, ClientToScreen, ScreenToClient, CreateSolidBrush, GetDC, ReleaseDC,
                 CreateDIBSection, DeleteObject, RedrawWindow, GetDCEx, ExcludeClipRect,
                 FillRect, PAINTSTRUCT, BeginPaint, EndPaint, BI_RGB, DIB_RGB_COLORS,
+                CreateRectRgn, SetWindowRgn, CreateDCW, DeleteDC, CreateCompatibleDC,
+                CreateCompatibleBitmap, SelectObject, StretchBlt, SetStretchBltMode, HALFTONE, SRCCOPY,
+            },
+            Windows::Win32::UI::ColorSystem::GetICMProfileW,
+            Windows::Win32::Globalization::{
+                GetUserDefaultLocaleName, GetLocaleInfoEx,
             },
+            Windows::Win32::Media::Audio::DirectMusic::IPropertyStore,
+            Windows::Win32::Media::Multimedia::{
+                sndPlaySoundW, SND_ALIAS, SND_ASYNC, SND_NODEFAULT,
+            },
+            Windows::Win32::Storage::Xps::{PrintWindow, PRINT_WINDOW_FLAGS},
             Windows::Win32::Storage::StructuredStorage::{
-                IStream, STREAM_SEEK, STREAM_SEEK_END,
+                IStream, STREAM_SEEK, STREAM_SEEK_END, PROPVARIANT,
             },
             Windows::Win32::System::Com::{
                 CoInitializeEx, CoInitializeSecurity, CoUninitialize, COINIT,
@@ -30,32 +55,49 @@ fn main() {
                 DROPEFFECT_COPY, DROPEFFECT_MOVE, DROPEFFECT_LINK, DROPEFFECT_NONE,
             },
             Windows::Win32::System::DataExchange::{
-                RegisterClipboardFormatW, GetClipboardFormatNameW
+                RegisterClipboardFormatW, GetClipboardFormatNameW, COPYDATASTRUCT,
+                OpenClipboard, EmptyClipboard, SetClipboardData, CloseClipboard,
             },
             Windows::Win32::System::Diagnostics::Debug::{
                 IsDebuggerPresent, FlashWindowEx, GetLastError, FormatMessageW, FACILITY_CODE, FACILITY_WIN32,
                 FORMAT_MESSAGE_FROM_SYSTEM, FORMAT_MESSAGE_ALLOCATE_BUFFER, FORMAT_MESSAGE_IGNORE_INSERTS,
+                MessageBeep, SetUnhandledExceptionFilter, EXCEPTION_POINTERS,
             },
             Windows::Win32::System::Memory::{
                 GlobalSize, GlobalAlloc, GlobalFree, GlobalLock, GlobalUnlock, LocalFree,
+                GMEM_MOVEABLE,
+            },
+            Windows::Win32::System::PropertiesSystem::{PROPERTYKEY, SHGetPropertyStoreForWindow},
+            Windows::Win32::System::Registry::{
+                RegGetValueW, RegSetKeyValueW, RegDeleteKeyValueW, HKEY_CURRENT_USER, HKEY_CLASSES_ROOT,
+                HKEY_LOCAL_MACHINE, RRF_RT_DWORD, RRF_RT_REG_SZ, REG_SZ,
+            },
+            Windows::Win32::System::Shutdown::{
+                ShutdownBlockReasonCreate, ShutdownBlockReasonDestroy,
             },
             Windows::Win32::System::SystemServices::{
                 // Methods
-                LoadLibraryW, MsgWaitForMultipleObjects,
+                LoadLibraryW, MsgWaitForMultipleObjects, SetThreadExecutionState, GetSystemPowerStatus,
+                SYSTEM_POWER_STATUS, QueryFullProcessImageNameW,
                 FreeLibrary, GetProcAddress, GetModuleHandleW,
                 // Constants
                 S_OK, S_FALSE, E_NOINTERFACE, E_NOTIMPL,
+                ES_CONTINUOUS, ES_SYSTEM_REQUIRED, ES_DISPLAY_REQUIRED,
                 TRUE, FALSE,
                 BOOL,
                 DRAGDROP_S_CANCEL, DRAGDROP_S_DROP, DRAGDROP_S_USEDEFAULTCURSORS,
-                CLIPBOARD_FORMATS, CF_HDROP,
+                CLIPBOARD_FORMATS, CF_HDROP, CF_UNICODETEXT,
             },
             Windows::Win32::System::Threading::{
                 CreateEventW, SetEvent, WaitForSingleObject,
-                GetCurrentThreadId
+                GetCurrentThreadId, CreateMutexW, OpenProcess,
+                PROCESS_QUERY_LIMITED_INFORMATION,
             },
             Windows::Win32::System::WindowsProgramming::{
-                FORMAT_MESSAGE_MAX_WIDTH_MASK, CloseHandle
+                FORMAT_MESSAGE_MAX_WIDTH_MASK, CloseHandle, GetTickCount
+            },
+            Windows::Win32::UI::Accessibility::{
+                HIGHCONTRASTW, HCF_HIGHCONTRASTON,
             },
             Windows::Win32::UI::Controls:: {
                 WM_MOUSELEAVE,
@@ -67,11 +109,22 @@ fn main() {
             Windows::Win32::UI::KeyboardAndMouseInput::{
                 SetFocus, EnableWindow, IsWindowEnabled, SetActiveWindow, ReleaseCapture, SetCapture,
                 GetCapture, GetAsyncKeyState, GetKeyboardState, GetKeyState, TrackMouseEvent, ToUnicode,
-                TME_LEAVE,
+                TME_LEAVE, TME_NONCLIENT, GetLastInputInfo, LASTINPUTINFO,
+            },
+            Windows::Win32::UI::PointerInput::{
+                GetPointerType, GetPointerPenInfo, POINTER_PEN_INFO,
             },
             Windows::Win32::UI::Shell::{
                 SetWindowSubclass, RemoveWindowSubclass, DefSubclassProc, IDropTargetHelper, IDragSourceHelper,
                 DragQueryFileW, DROPFILES, SHCreateMemStream, SHDRAGIMAGE,
+                IDataTransferManagerInterop,
+                ShellExecuteW, ILCreateFromPathW, ILFree, SHOpenFolderAndSelectItems,
+                SHFileOperationW, SHFILEOPSTRUCTW, FO_DELETE, FOF_ALLOWUNDO, FOF_NOCONFIRMATION,
+                FOF_NOERRORUI, FOF_SILENT,
+                ICustomDestinationList, DestinationList, IShellLinkW, ShellLink, IObjectArray,
+                IObjectCollection, EnumerableObjectCollection, KNOWNDESTCATEGORY,
+                SetCurrentProcessExplicitAppUserModelID, AssocQueryStringW, ASSOCSTR_EXECUTABLE,
+                ASSOCSTR_FRIENDLYAPPNAME, ASSOCF_IS_PROTOCOL,
             },
             Windows::Win32::UI::WindowsAndMessaging::{
                 // Messages
@@ -82,26 +135,34 @@ fn main() {
                 WM_MBUTTONUP, WM_XBUTTONUP,
                 WM_TIMER, WM_MENUCOMMAND, WM_COMMAND, WM_USER, WM_CANCELMODE, WM_MENUSELECT,
                 WM_CHANGEUISTATE, WM_UPDATEUISTATE, WM_KEYDOWN, WM_KEYUP, WM_SYSKEYUP, WM_SETFOCUS, WM_DWMCOMPOSITIONCHANGED,
-                WM_NCLBUTTONDOWN, WM_ERASEBKGND, WM_ENTERSIZEMOVE, WM_EXITSIZEMOVE,
+                WM_NCLBUTTONDOWN, WM_NCLBUTTONUP, WM_NCLBUTTONDBLCLK, WM_NCMOUSELEAVE, WM_ERASEBKGND, WM_ENTERSIZEMOVE, WM_EXITSIZEMOVE,
                 WM_QUERYUISTATE, WM_SYSCOMMAND, GWL_EXSTYLE, GWL_STYLE, GWL_HWNDPARENT, GWL_USERDATA, GWLP_USERDATA,
-                WS_EX_LAYOUTRTL, MK_LBUTTON, SW_SHOW, SW_HIDE, SWP_NOZORDER, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE,
-                SWP_FRAMECHANGED,
+                WS_EX_LAYOUTRTL, WS_EX_LAYERED, LWA_ALPHA, SetLayeredWindowAttributes, MK_LBUTTON, MK_RBUTTON, MK_MBUTTON, SW_SHOW, SW_SHOWNORMAL, SW_HIDE, SWP_NOZORDER, SWP_NOACTIVATE, SWP_NOMOVE, SWP_NOSIZE,
+                SWP_FRAMECHANGED, SWP_SHOWWINDOW, SWP_HIDEWINDOW, WS_CHILD, WS_CLIPSIBLINGS, WS_VISIBLE, WS_EX_NOACTIVATE,
                 // Methods
                 GetSystemMenu, EnableMenuItem, CreatePopupMenu, DestroyMenu, AppendMenuW,
                 TrackPopupMenuEx, InsertMenuItemW, RemoveMenu, SetMenuItemInfoW, SetMenuInfo, GetMenuInfo,
                 GetMenuItemInfoW, GetCursorPos, EndMenu, GetSubMenu, GetMenuItemCount, HiliteMenuItem,
                 RegisterClassW, UnregisterClassW, PostMessageW, SendMessageW,
-                GetMessageW, PeekMessageW, TranslateMessage, DispatchMessageW, DestroyWindow, CreateWindowExW,
+                GetMessageW, PeekMessageW, GetQueueStatus, TranslateMessage, DispatchMessageW, DestroyWindow, CreateWindowExW,
                 DefWindowProcW, SetWindowLongW, GetWindowLongW, ShowWindow, SetProcessDPIAware,
                 SetWindowPos, GetWindowRect, GetClientRect, SetParent, GetParent, MoveWindow, SetForegroundWindow,
                 SetTimer, SetWindowsHookExW, UnhookWindowsHookEx, CallNextHookEx, FindWindowW, SetWindowTextW,
-                GetGUIThreadInfo, WindowFromPoint, LoadCursorW,
+                GetGUIThreadInfo, WindowFromPoint, LoadCursorW, FindWindowExW, AllowSetForegroundWindow,
+                GetWindowThreadProcessId, IsIconic, SW_RESTORE, WM_COPYDATA, WM_QUERYENDSESSION, WM_SYSCOLORCHANGE,
+                WM_POWERBROADCAST, PBT_APMPOWERSTATUSCHANGE,
+                EnumWindows, IsWindowVisible, GetWindowTextW, GetWindow, GW_OWNER,
+                WS_EX_TOOLWINDOW,
+                GetSysColor, COLOR_HIGHLIGHT, COLOR_HIGHLIGHTTEXT, COLOR_WINDOW, COLOR_WINDOWTEXT,
+                COLOR_BTNFACE, COLOR_BTNTEXT, COLOR_GRAYTEXT, COLOR_HOTLIGHT,
                 // Structures
-                CREATESTRUCTW, MSG, WINDOWPOS, NCCALCSIZE_PARAMS,
+                CREATESTRUCTW, MSG, WINDOWPOS, NCCALCSIZE_PARAMS, KBDLLHOOKSTRUCT, MSLLHOOKSTRUCT,
                 // Constants
                 TRACK_POPUP_MENU_FLAGS, WINDOW_LONG_PTR_INDEX,
-                VK_SHIFT, WNDCLASS_STYLES, IDC_ARROW, SC_CLOSE, HTCAPTION, HTTOPLEFT,
+                VK_SHIFT, VK_CONTROL, VK_MENU, VK_LWIN, VK_RWIN, VK_LBUTTON, VK_RBUTTON, VK_MBUTTON,
+                WNDCLASS_STYLES, IDC_ARROW, SC_CLOSE, HTCAPTION, HTTOPLEFT,
                 HTTOPRIGHT, HTTOP, HTBOTTOMLEFT, HTBOTTOMRIGHT, HTBOTTOM, HTLEFT, HTRIGHT, HTCLIENT, HTTRANSPARENT,
+                HTMAXBUTTON,
                 MSGF_MENU, VK_DOWN, VK_RIGHT, VK_LEFT, MIM_MENUDATA, MIM_STYLE, MFT_SEPARATOR, MFT_STRING,
                 MFS_ENABLED, MFS_DISABLED, MFS_CHECKED, MFT_RADIOCHECK, MIIM_FTYPE, MIIM_ID, MIIM_STATE, MIIM_STRING,  MIIM_SUBMENU,
                 MF_BYCOMMAND, MF_DISABLED, MF_GRAYED, MF_POPUP, MF_MOUSESELECT, MF_ENABLED,
@@ -109,14 +170,21 @@ fn main() {
                 WS_MAXIMIZEBOX, WS_MINIMIZEBOX,
                 WS_EX_NOREDIRECTIONBITMAP, WS_EX_APPWINDOW,
                 CS_HREDRAW, CS_VREDRAW,
-                WH_MSGFILTER,
+                WH_MSGFILTER, WH_KEYBOARD_LL, WH_MOUSE_LL,
+                WM_SYSKEYDOWN, WM_MOUSEWHEEL, WM_MOUSEHWHEEL, WM_XBUTTONDOWN,
+                WM_WININICHANGE, SystemParametersInfoW, GetSystemMetrics, SPI_GETKEYBOARDPREF,
+                SPI_GETCLIENTAREAANIMATION, SPI_GETHIGHCONTRAST, SM_CXCURSOR,
+                WM_POINTERENTER, WM_POINTERLEAVE, WM_POINTERDOWN, WM_POINTERUP, WM_POINTERUPDATE,
+                PT_PEN, PEN_FLAG_BARREL, PEN_FLAG_INVERTED, PEN_FLAG_ERASER, PEN_MASK_PRESSURE,
+                PEN_MASK_ROTATION, PEN_MASK_TILT_X, PEN_MASK_TILT_Y,
                 TPM_LEFTALIGN, TPM_TOPALIGN, TPM_VERTICAL, TPM_RETURNCMD,
+                MessageBoxW, MB_ICONERROR, MB_OK, MB_YESNO, MB_YESNOCANCEL, MB_TASKMODAL, IDYES, IDNO,
             },
         );
     }
 
     #[cfg(target_os = "linux")]
-    {
+    if std::env::var("CARGO_FEATURE_MOCK").is_err() {
         cargo_emit::rustc_link_lib! {
             "flutter_linux_gtk",
         };
@@ -0,0 +1,135 @@
+use std::rc::Rc;
+
+use crate::{
+    shell::{BinaryMessengerReply, Context, EngineHandle, EngineManager},
+    Error, Result,
+};
+
+// Counterpart of Dart BasicMessageChannel with BinaryCodec. Messages are passed
+// as raw bytes without going through Value, which is useful for bulk data (images,
+// audio buffers, etc).
+pub struct BinaryMessageChannel {
+    context: Rc<Context>,
+    sender: BinaryMessageSender,
+}
+
+impl BinaryMessageChannel {
+    pub fn new<F>(
+        context: Rc<Context>,
+        engine_handle: EngineHandle,
+        channel_name: &str,
+        callback: F,
+    ) -> Self
+    where
+        F: Fn(&[u8], BinaryMessageReply) + 'static,
+    {
+        Self::new_with_engine_manager(
+            context.clone(),
+            engine_handle,
+            channel_name,
+            callback,
+            &context.engine_manager.borrow(),
+        )
+    }
+
+    pub fn new_with_engine_manager<F>(
+        context: Rc<Context>,
+        engine_handle: EngineHandle,
+        channel_name: &str,
+        callback: F,
+        engine_manager: &EngineManager,
+    ) -> Self
+    where
+        F: Fn(&[u8], BinaryMessageReply) + 'static,
+    {
+        let res = BinaryMessageChannel {
+            context: context.clone(),
+            sender: BinaryMessageSender {
+                context,
+                engine_handle,
+                channel_name: channel_name.into(),
+            },
+        };
+
+        let engine = engine_manager.get_engine(engine_handle);
+        if let Some(engine) = engine {
+            engine
+                .binary_messenger()
+                .register_channel_handler(channel_name, move |data, reply| {
+                    callback(data, BinaryMessageReply { reply });
+                });
+        }
+        res
+    }
+
+    pub fn sender(&self) -> &BinaryMessageSender {
+        &self.sender
+    }
+}
+
+//
+//
+//
+
+#[derive(Clone)]
+pub struct BinaryMessageSender {
+    context: Rc<Context>,
+    engine_handle: EngineHandle,
+    channel_name: String,
+}
+
+impl BinaryMessageSender {
+    // Reply data is only valid for the duration of callback
+    pub fn send_message<F>(&self, message: &[u8], reply: F) -> Result<()>
+    where
+        F: FnOnce(&[u8]) + 'static,
+    {
+        let engine_manager = self.context.engine_manager.borrow();
+        let engine = engine_manager.get_engine(self.engine_handle);
+        if let Some(engine) = engine {
+            engine
+                .binary_messenger()
+                .send_message(&self.channel_name, message, reply)
+        } else {
+            Err(Error::InvalidEngineHandle)
+        }
+    }
+
+    pub fn post_message(&self, message: &[u8]) -> Result<()> {
+        let engine_manager = self.context.engine_manager.borrow();
+        let engine = engine_manager.get_engine(self.engine_handle);
+        if let Some(engine) = engine {
+            engine
+                .binary_messenger()
+                .post_message(&self.channel_name, message)
+        } else {
+            Err(Error::InvalidEngineHandle)
+        }
+    }
+}
+
+//
+//
+//
+
+pub struct BinaryMessageReply {
+    reply: BinaryMessengerReply,
+}
+
+impl BinaryMessageReply {
+    pub fn send(self, data: &[u8]) {
+        self.reply.send(data);
+    }
+}
+
+impl Drop for BinaryMessageChannel {
+    fn drop(&mut self) {
+        let engine_manager = self.context.engine_manager.borrow();
+        let engine = engine_manager.get_engine(self.sender.engine_handle);
+        if let Some(engine) = engine {
+            engine
+                .binary_messenger()
+                .unregister_channel_handler(&self.sender.channel_name);
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use std::{marker::PhantomData, rc::Rc};
+
+use log::warn;
+
+use crate::{
+    shell::{Context, EngineHandle, EngineManager},
+    Error, Result,
+};
+
+use super::{MethodCallError, MethodCallResult, MethodCodec};
+
+// Implemented by Rust side of Flutter EventChannel. Dart invokes on_listen when first
+// subscriber starts listening to the stream and on_cancel when last subscriber cancels
+// the subscription.
+pub trait EventStreamHandler<V> {
+    fn on_listen(&self, arguments: V, sink: EventSink<V>) -> MethodCallResult<V>;
+    fn on_cancel(&self, arguments: V) -> MethodCallResult<V>;
+}
+
+impl<V, H> EventStreamHandler<V> for Box<H>
+where
+    H: EventStreamHandler<V> + ?Sized,
+{
+    fn on_listen(&self, arguments: V, sink: EventSink<V>) -> MethodCallResult<V> {
+        self.as_ref().on_listen(arguments, sink)
+    }
+
+    fn on_cancel(&self, arguments: V) -> MethodCallResult<V> {
+        self.as_ref().on_cancel(arguments)
+    }
+}
+
+pub struct EventChannel<V>
+where
+    V: 'static,
+{
+    context: Rc<Context>,
+    engine_handle: EngineHandle,
+    channel_name: String,
+    _data: PhantomData<V>,
+}
+
+impl<V> EventChannel<V> {
+    pub fn new<H>(
+        context: Rc<Context>,
+        engine_handle: EngineHandle,
+        channel_name: &str,
+        codec: &'static dyn MethodCodec<V>,
+        handler: H,
+    ) -> Self
+    where
+        H: EventStreamHandler<V> + 'static,
+    {
+        Self::new_with_engine_manager(
+            context.clone(),
+            engine_handle,
+            channel_name,
+            codec,
+            handler,
+            &context.engine_manager.borrow(),
+        )
+    }
+
+    pub fn new_with_engine_manager<H>(
+        context: Rc<Context>,
+        engine_handle: EngineHandle,
+        channel_name: &str,
+        codec: &'static dyn MethodCodec<V>,
+        handler: H,
+        engine_manager: &EngineManager,
+    ) -> Self
+    where
+        H: EventStreamHandler<V> + 'static,
+    {
+        let engine = engine_manager.get_engine(engine_handle);
+        if let Some(engine) = engine {
+            let sink = EventSink {
+                context: context.clone(),
+                engine_handle,
+                channel_name: channel_name.into(),
+                codec,
+            };
+            let channel = channel_name.to_owned();
+            engine
+                .binary_messenger()
+                .register_channel_handler(channel_name, move |data, reply| {
+                    // Empty reply completes Dart call with MissingPluginException
+                    let call = match codec.decode_method_call(data) {
+                        Some(call) => call,
+                        None => {
+                            warn!("Malformed event channel call on {}", channel);
+                            reply.send(&[]);
+                            return;
+                        }
+                    };
+                    let result = match call.method.as_str() {
+                        "listen" => handler.on_listen(call.args, sink.clone()),
+                        "cancel" => handler.on_cancel(call.args),
+                        _ => {
+                            reply.send(&[]);
+                            return;
+                        }
+                    };
+                    reply.send(&codec.encode_method_call_result(&result));
+                });
+        }
+
+        EventChannel {
+            context,
+            engine_handle,
+            channel_name: channel_name.into(),
+            _data: PhantomData,
+        }
+    }
+}
+
+impl<V> Drop for EventChannel<V> {
+    fn drop(&mut self) {
+        let engine_manager = self.context.engine_manager.borrow();
+        let engine = engine_manager.get_engine(self.engine_handle);
+        if let Some(engine) = engine {
+            engine
+                .binary_messenger()
+                .unregister_channel_handler(&self.channel_name);
+        }
+    }
+}
+
+//
+//
+//
+
+// Cloneable sink for pushing events to Dart stream
+pub struct EventSink<V>
+where
+    V: 'static,
+{
+    context: Rc<Context>,
+    engine_handle: EngineHandle,
+    channel_name: String,
+    codec: &'static dyn MethodCodec<V>,
+}
+
+// Derived Clone would require V: Clone
+impl<V> Clone for EventSink<V> {
+    fn clone(&self) -> Self {
+        Self {
+            context: self.context.clone(),
+            engine_handle: self.engine_handle,
+            channel_name: self.channel_name.clone(),
+            codec: self.codec,
+        }
+    }
+}
+
+impl<V> EventSink<V> {
+    pub fn engine_handle(&self) -> EngineHandle {
+        self.engine_handle
+    }
+
+    pub fn send(&self, event: &V) -> Result<()> {
+        self.post(&self.codec.encode_success_envelope(event))
+    }
+
+    pub fn send_error(&self, error: &MethodCallError<V>) -> Result<()> {
+        self.post(&self.codec.encode_error_envelope(
+            &error.code,
+            error.message.as_deref(),
+            &error.details,
+        ))
+    }
+
+    // Closes the stream on Dart side
+    pub fn end_of_stream(&self) -> Result<()> {
+        self.post(&[])
+    }
+
+    fn post(&self, message: &[u8]) -> Result<()> {
+        let engine_manager = self.context.engine_manager.borrow();
+        let engine = engine_manager.get_engine(self.engine_handle);
+        if let Some(engine) = engine {
+            engine
+                .binary_messenger()
+                .post_message(&self.channel_name, message)
+        } else {
+            Err(Error::InvalidEngineHandle)
+        }
+    }
+}
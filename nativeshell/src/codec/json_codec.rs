@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+
+use log::error;
+use serde_json::{Map, Number};
+
+use super::{MessageCodec, MethodCall, MethodCallError, MethodCallResult, MethodCodec, Value};
+
+// Codec compatible with Flutter JSONMessageCodec and JSONMethodCodec. Typed lists
+// are encoded as plain JSON arrays and map keys are converted to strings, as
+// JSON has no representation for either.
+pub struct JsonMethodCodec;
+
+impl MessageCodec<Value> for JsonMethodCodec {
+    fn encode_message(&self, v: &Value) -> Vec<u8> {
+        serde_json::to_vec(&value_to_json(v)).unwrap()
+    }
+
+    fn decode_message(&self, buf: &[u8]) -> Option<Value> {
+        if buf.is_empty() {
+            return None;
+        }
+        match serde_json::from_slice::<serde_json::Value>(buf) {
+            Ok(json) => Some(json_to_value(json)),
+            Err(err) => {
+                error!("Invalid JSON message: {}", err);
+                None
+            }
+        }
+    }
+}
+
+impl MethodCodec<Value> for JsonMethodCodec {
+    fn encode_method_call(&self, v: &MethodCall<Value>) -> Vec<u8> {
+        let mut map = Map::new();
+        map.insert("method".into(), serde_json::Value::String(v.method.clone()));
+        map.insert("args".into(), value_to_json(&v.args));
+        serde_json::to_vec(&serde_json::Value::Object(map)).unwrap()
+    }
+
+    fn decode_method_call(&self, buf: &[u8]) -> Option<MethodCall<Value>> {
+        let json = serde_json::from_slice::<serde_json::Value>(buf).ok();
+        if let Some(serde_json::Value::Object(mut map)) = json {
+            if let Some(serde_json::Value::String(method)) = map.remove("method") {
+                let args = map.remove("args").map(json_to_value).unwrap_or(Value::Null);
+                return Some(MethodCall { method, args });
+            }
+        }
+        error!("Invalid method call");
+        None
+    }
+
+    fn encode_success_envelope(&self, result: &Value) -> Vec<u8> {
+        serde_json::to_vec(&serde_json::Value::Array(vec![value_to_json(result)])).unwrap()
+    }
+
+    fn encode_error_envelope(&self, code: &str, message: Option<&str>, v: &Value) -> Vec<u8> {
+        serde_json::to_vec(&serde_json::Value::Array(vec![
+            serde_json::Value::String(code.into()),
+            message
+                .map(|m| serde_json::Value::String(m.into()))
+                .unwrap_or(serde_json::Value::Null),
+            value_to_json(v),
+        ]))
+        .unwrap()
+    }
+
+    fn decode_envelope(&self, buf: &[u8]) -> Option<MethodCallResult<Value>> {
+        let json = serde_json::from_slice::<serde_json::Value>(buf).ok();
+        match json {
+            Some(serde_json::Value::Array(list)) if list.len() == 1 => {
+                let result = list.into_iter().next().unwrap();
+                Some(MethodCallResult::Ok(json_to_value(result)))
+            }
+            Some(serde_json::Value::Array(list)) if list.len() == 3 => {
+                let mut list = list.into_iter();
+                let code = list.next().unwrap();
+                let message = list.next().unwrap();
+                let details = list.next().unwrap();
+                Some(MethodCallResult::Err(MethodCallError {
+                    code: match code {
+                        serde_json::Value::String(s) => s,
+                        _ => "".into(),
+                    },
+                    message: match message {
+                        serde_json::Value::String(msg) => Some(msg),
+                        _ => None,
+                    },
+                    details: json_to_value(details),
+                }))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn list_to_json<T: Into<serde_json::Value> + Copy>(list: &[T]) -> serde_json::Value {
+    serde_json::Value::Array(list.iter().map(|v| (*v).into()).collect())
+}
+
+fn key_to_string(key: &Value) -> String {
+    match key {
+        Value::String(s) => s.clone(),
+        key => value_to_json(key).to_string(),
+    }
+}
+
+pub fn value_to_json(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Null => serde_json::Value::Null,
+        Value::Bool(v) => serde_json::Value::Bool(*v),
+        Value::I64(v) => (*v).into(),
+        Value::F64(v) => Number::from_f64(*v)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null),
+        Value::String(v) => serde_json::Value::String(v.clone()),
+        Value::U8List(v) => list_to_json(v),
+        Value::I32List(v) => list_to_json(v),
+        Value::I64List(v) => list_to_json(v),
+        Value::F64List(v) => list_to_json(v),
+        Value::F32List(v) => list_to_json(v),
+        Value::List(v) => serde_json::Value::Array(v.iter().map(value_to_json).collect()),
+        Value::Map(v) => serde_json::Value::Object(
+            v.iter()
+                .map(|(k, v)| (key_to_string(k), value_to_json(v)))
+                .collect(),
+        ),
+    }
+}
+
+pub fn json_to_value(json: serde_json::Value) -> Value {
+    match json {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(v) => Value::Bool(v),
+        serde_json::Value::Number(v) => match v.as_i64() {
+            Some(v) => Value::I64(v),
+            None => Value::F64(v.as_f64().unwrap_or(0.0)),
+        },
+        serde_json::Value::String(v) => Value::String(v),
+        serde_json::Value::Array(v) => Value::List(v.into_iter().map(json_to_value).collect()),
+        serde_json::Value::Object(v) => Value::Map(
+            v.into_iter()
+                .map(|(k, v)| (Value::String(k), json_to_value(v)))
+                .collect::<HashMap<_, _>>(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use velcro::hash_map;
+
+    use super::*;
+
+    #[test]
+    fn test_method_call() {
+        let codec = JsonMethodCodec;
+        let call = MethodCall {
+            method: "setTitle".into(),
+            args: Value::Map(hash_map! {
+                "title".into(): "Window".into(),
+                "size".into(): Value::List(vec![300i64.into(), 200.5.into()]),
+                "visible".into(): true.into(),
+                "parent".into(): Value::Null,
+            }),
+        };
+        let encoded = codec.encode_method_call(&call);
+        let decoded = codec.decode_method_call(&encoded).unwrap();
+        assert_eq!(decoded.method, call.method);
+        assert_eq!(decoded.args, call.args);
+
+        // Missing arguments are decoded as null
+        let decoded = codec.decode_method_call(br#"{"method":"hide"}"#).unwrap();
+        assert_eq!(decoded.method, "hide");
+        assert_eq!(decoded.args, Value::Null);
+    }
+
+    #[test]
+    fn test_envelope() {
+        let codec = JsonMethodCodec;
+        let value = Value::List(vec!["Hello".into(), 10i64.into()]);
+        let encoded = codec.encode_success_envelope(&value);
+        match codec.decode_envelope(&encoded) {
+            Some(Ok(result)) => assert_eq!(result, value),
+            _ => panic!("Expected success envelope"),
+        }
+
+        let encoded = codec.encode_error_envelope("code", Some("message"), &5i64.into());
+        match codec.decode_envelope(&encoded) {
+            Some(Err(error)) => {
+                assert_eq!(error.code, "code");
+                assert_eq!(error.message.as_deref(), Some("message"));
+                assert_eq!(error.details, Value::I64(5));
+            }
+            _ => panic!("Expected error envelope"),
+        }
+
+        let encoded = codec.encode_error_envelope("code", None, &Value::Null);
+        match codec.decode_envelope(&encoded) {
+            Some(Err(error)) => {
+                assert_eq!(error.code, "code");
+                assert_eq!(error.message, None);
+                assert_eq!(error.details, Value::Null);
+            }
+            _ => panic!("Expected error envelope"),
+        }
+    }
+
+    #[test]
+    fn test_lossy_values() {
+        let codec = JsonMethodCodec;
+        let value = Value::Map(hash_map! {
+            Value::I64(1): Value::I32List(vec![1, 2]),
+            "nan".into(): f64::NAN.into(),
+        });
+        let encoded = codec.encode_message(&value);
+        assert_eq!(
+            codec.decode_message(&encoded),
+            Some(Value::Map(hash_map! {
+                "1".into(): Value::List(vec![1i64.into(), 2i64.into()]),
+                "nan".into(): Value::Null,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        let codec = JsonMethodCodec;
+        assert_eq!(codec.decode_message(b""), None);
+        assert_eq!(codec.decode_message(b"{\"a\":"), None);
+        assert_eq!(codec.decode_message(&[0xff, 0xfe]), None);
+
+        assert!(codec.decode_method_call(b"").is_none());
+        assert!(codec.decode_method_call(b"[1, 2]").is_none());
+        assert!(codec.decode_method_call(br#"{"args":1}"#).is_none());
+        assert!(codec.decode_method_call(br#"{"method":5}"#).is_none());
+
+        assert!(codec.decode_envelope(b"").is_none());
+        assert!(codec.decode_envelope(b"[]").is_none());
+        assert!(codec.decode_envelope(b"[1, 2]").is_none());
+        assert!(codec.decode_envelope(b"{\"result\":1}").is_none());
+        assert!(codec.decode_envelope(b"[1, 2").is_none());
+    }
+}
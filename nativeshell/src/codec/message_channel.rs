@@ -5,7 +5,7 @@ use crate::{
     Error, Result,
 };
 
-use super::MessageCodec;
+use super::{MessageCodec, Value, ValueRef};
 
 pub struct MessageChannel<V>
 where
@@ -49,12 +49,7 @@ impl<V> MessageChannel<V> {
     {
         let res = MessageChannel {
             context: context.clone(),
-            sender: MessageSender {
-                context,
-                engine_handle,
-                channel_name: channel_name.into(),
-                codec,
-            },
+            sender: MessageSender::new(context, engine_handle, channel_name, codec),
         };
 
         let engine = engine_manager.get_engine(engine_handle);
@@ -92,18 +87,44 @@ where
 }
 
 impl<V> MessageSender<V> {
+    pub fn new(
+        context: Rc<Context>,
+        engine_handle: EngineHandle,
+        channel_name: &str,
+        codec: &'static dyn MessageCodec<V>,
+    ) -> Self {
+        Self {
+            context,
+            engine_handle,
+            channel_name: channel_name.into(),
+            codec,
+        }
+    }
+
     pub fn send_message<F>(&self, message: &V, reply: F) -> Result<()>
     where
         F: FnOnce(V) + 'static,
     {
         let encoded = self.codec.encode_message(message);
+        self.send_encoded(&encoded, reply)
+    }
+
+    pub fn post_message(&self, message: &V) -> Result<()> {
+        let encoded = self.codec.encode_message(message);
+        self.post_encoded(&encoded)
+    }
+
+    fn send_encoded<F>(&self, encoded: &[u8], reply: F) -> Result<()>
+    where
+        F: FnOnce(V) + 'static,
+    {
         let engine_manager = self.context.engine_manager.borrow();
         let engine = engine_manager.get_engine(self.engine_handle);
         if let Some(engine) = engine {
             let codec = self.codec;
             engine
                 .binary_messenger()
-                .send_message(&self.channel_name, &encoded, move |message| {
+                .send_message(&self.channel_name, encoded, move |message| {
                     let message = codec.decode_message(message).unwrap();
                     reply(message);
                 })
@@ -112,20 +133,36 @@ impl<V> MessageSender<V> {
         }
     }
 
-    pub fn post_message(&self, message: &V) -> Result<()> {
-        let encoded = self.codec.encode_message(message);
+    fn post_encoded(&self, encoded: &[u8]) -> Result<()> {
         let engine_manager = self.context.engine_manager.borrow();
         let engine = engine_manager.get_engine(self.engine_handle);
         if let Some(engine) = engine {
             engine
                 .binary_messenger()
-                .post_message(&self.channel_name, &encoded)
+                .post_message(&self.channel_name, encoded)
         } else {
             Err(Error::InvalidEngineHandle)
         }
     }
 }
 
+impl MessageSender<Value> {
+    // Same as send_message, but the message is borrowed; Large buffers can be
+    // sent without copying them into Value first
+    pub fn send_message_ref<F>(&self, message: &ValueRef, reply: F) -> Result<()>
+    where
+        F: FnOnce(Value) + 'static,
+    {
+        let encoded = self.codec.encode_message_ref(message);
+        self.send_encoded(&encoded, reply)
+    }
+
+    pub fn post_message_ref(&self, message: &ValueRef) -> Result<()> {
+        let encoded = self.codec.encode_message_ref(message);
+        self.post_encoded(&encoded)
+    }
+}
+
 //
 //
 //
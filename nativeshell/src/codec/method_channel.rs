@@ -1,11 +1,19 @@
-use std::rc::Rc;
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+    time::Duration,
+};
 
 use crate::{
-    shell::{BinaryMessengerReply, Context, EngineHandle, EngineManager},
+    shell::{BinaryMessengerReply, Context, EngineHandle, EngineManager, ScheduledCallback},
+    util::trace,
     Error, Result,
 };
 
-use super::{MethodCall, MethodCallError, MethodCallResult, MethodCodec};
+use super::{
+    value::{to_value, Value, ValueRef},
+    MethodCall, MethodCallError, MethodCallResult, MethodCodec,
+};
 
 pub struct MethodChannel<V>
 where
@@ -49,21 +57,18 @@ impl<V> MethodChannel<V> {
     {
         let res = MethodChannel {
             context: context.clone(),
-            invoker: MethodInvoker {
-                context,
-                engine_handle,
-                channel_name: channel_name.into(),
-                codec,
-            },
+            invoker: MethodInvoker::new(context, engine_handle, channel_name, codec),
         };
 
         let engine = engine_manager.get_engine(engine_handle);
         if let Some(engine) = engine {
             let codec = codec;
+            let channel = channel_name.to_owned();
             engine
                 .binary_messenger()
                 .register_channel_handler(channel_name, move |data, reply| {
                     let message = codec.decode_method_call(data).unwrap();
+                    let _span = trace::method_call(&channel, &message.method);
                     let reply = MethodCallReply { reply, codec };
                     callback(message, reply);
                 });
@@ -93,18 +98,39 @@ where
 }
 
 impl<V> MethodInvoker<V> {
+    pub fn new(
+        context: Rc<Context>,
+        engine_handle: EngineHandle,
+        channel_name: &str,
+        codec: &'static dyn MethodCodec<V>,
+    ) -> Self {
+        Self {
+            context,
+            engine_handle,
+            channel_name: channel_name.into(),
+            codec,
+        }
+    }
+
     pub fn call_method<F>(&self, method: String, args: V, reply: F) -> Result<()>
     where
         F: FnOnce(MethodCallResult<V>) + 'static,
     {
         let encoded = self.codec.encode_method_call(&MethodCall { method, args });
+        self.send_encoded(&encoded, reply)
+    }
+
+    fn send_encoded<F>(&self, encoded: &[u8], reply: F) -> Result<()>
+    where
+        F: FnOnce(MethodCallResult<V>) + 'static,
+    {
         let engine_manager = self.context.engine_manager.borrow();
         let engine = engine_manager.get_engine(self.engine_handle);
         if let Some(engine) = engine {
             let codec = self.codec;
             engine
                 .binary_messenger()
-                .send_message(&self.channel_name, &encoded, move |message| {
+                .send_message(&self.channel_name, encoded, move |message| {
                     let message = codec.decode_envelope(message).unwrap();
                     reply(message);
                 })
@@ -114,6 +140,162 @@ impl<V> MethodInvoker<V> {
     }
 }
 
+impl<V> MethodInvoker<V>
+where
+    V: Default + 'static,
+{
+    // Same as call_method, but the reply is invoked with "timeout" error if
+    // there is no response within given time, and with "no-reply" error if
+    // the call is dropped without response (i.e. engine was shut down).
+    // Returned handle can be used to cancel the call, in which case the reply
+    // is not invoked at all.
+    pub fn call_method_with_timeout<F>(
+        &self,
+        method: String,
+        args: V,
+        timeout: Option<Duration>,
+        reply: F,
+    ) -> Result<PendingMethodCall<V>>
+    where
+        F: FnOnce(MethodCallResult<V>) + 'static,
+    {
+        let pending = PendingMethodCall {
+            state: Rc::new(PendingCallState {
+                reply: RefCell::new(Some(Box::new(reply))),
+                timeout: RefCell::new(None),
+            }),
+        };
+        let guard = ReplyGuard {
+            state: Some(pending.state.clone()),
+            context: Rc::downgrade(&self.context),
+        };
+        if let Err(error) = self.call_method(method, args, move |result| guard.complete(result)) {
+            pending.cancel();
+            return Err(error);
+        }
+        if let Some(timeout) = timeout {
+            let state = Rc::downgrade(&pending.state);
+            let callback = self.context.run_loop.borrow().schedule(timeout, move || {
+                if let Some(state) = state.upgrade() {
+                    // the callback is being executed, no need to unschedule it
+                    if let Some(mut callback) = state.timeout.borrow_mut().take() {
+                        callback.detach();
+                    }
+                    state.complete(Err(MethodCallError::from_code_message(
+                        "timeout",
+                        "Method call timed out",
+                    )));
+                }
+            });
+            pending.state.timeout.replace(Some(callback));
+        }
+        Ok(pending)
+    }
+}
+
+struct PendingCallState<V> {
+    reply: RefCell<Option<Box<dyn FnOnce(MethodCallResult<V>)>>>,
+    timeout: RefCell<Option<ScheduledCallback>>,
+}
+
+impl<V> PendingCallState<V> {
+    fn complete(&self, result: MethodCallResult<V>) {
+        self.timeout.borrow_mut().take();
+        let reply = self.reply.borrow_mut().take();
+        if let Some(reply) = reply {
+            reply(result);
+        }
+    }
+}
+
+// Reports "no-reply" error if dropped before the response arrives
+struct ReplyGuard<V>
+where
+    V: Default + 'static,
+{
+    state: Option<Rc<PendingCallState<V>>>,
+    context: Weak<Context>,
+}
+
+impl<V> ReplyGuard<V>
+where
+    V: Default + 'static,
+{
+    fn complete(mut self, result: MethodCallResult<V>) {
+        if let Some(state) = self.state.take() {
+            state.complete(result);
+        }
+    }
+}
+
+impl<V> Drop for ReplyGuard<V>
+where
+    V: Default + 'static,
+{
+    fn drop(&mut self) {
+        if let (Some(state), Some(context)) = (self.state.take(), self.context.upgrade()) {
+            // This might happen during engine shutdown, so defer the reply
+            context
+                .run_loop
+                .borrow()
+                .schedule_now(move || {
+                    state.complete(Err(MethodCallError::from_code_message(
+                        "no-reply",
+                        "Method call was dropped without reply",
+                    )));
+                })
+                .detach();
+        }
+    }
+}
+
+// Handle for pending method call returned from call_method_with_timeout.
+// Dropping the handle does not cancel the call.
+pub struct PendingMethodCall<V> {
+    state: Rc<PendingCallState<V>>,
+}
+
+impl<V> Clone for PendingMethodCall<V> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<V> PendingMethodCall<V> {
+    // Cancels the call; Reply callback will not be invoked
+    pub fn cancel(&self) {
+        self.state.timeout.borrow_mut().take();
+        self.state.reply.borrow_mut().take();
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.state.reply.borrow().is_some()
+    }
+}
+
+impl MethodInvoker<Value> {
+    // Same as call_method, but arguments can be any serde serializable type
+    pub fn call_method_with_args<T, F>(&self, method: String, args: &T, reply: F) -> Result<()>
+    where
+        T: serde::Serialize,
+        F: FnOnce(MethodCallResult<Value>) + 'static,
+    {
+        self.call_method(method, to_value(args)?, reply)
+    }
+
+    // Same as call_method, but arguments are borrowed; Large buffers can be
+    // passed without copying them into Value first
+    pub fn call_method_ref<F>(&self, method: &str, args: &ValueRef, reply: F) -> Result<()>
+    where
+        F: FnOnce(MethodCallResult<Value>) + 'static,
+    {
+        let encoded = self.codec.encode_method_call_ref(method, args);
+        self.send_encoded(&encoded, reply)
+    }
+}
+
 //
 //
 //
@@ -136,6 +318,16 @@ impl<V> MethodCallReply<V> {
         self.send(MethodCallResult::Ok(value))
     }
 
+    // Encodes borrowed value straight into the reply without building owned
+    // value first
+    pub fn send_ok_ref(self, value: &ValueRef)
+    where
+        V: From<Value>,
+    {
+        let encoded = self.codec.encode_success_envelope_ref(value);
+        self.reply.send(&encoded);
+    }
+
     pub fn send_error(self, code: &str, message: Option<&str>, details: V) {
         self.send(MethodCallResult::Err(MethodCallError {
             code: code.into(),
@@ -145,6 +337,25 @@ impl<V> MethodCallReply<V> {
     }
 }
 
+impl MethodCallReply<Value> {
+    // Replies with Uint8List; Meant for large binary results (images, file contents)
+    pub fn send_bytes(self, bytes: &[u8]) {
+        self.send_ok_ref(&ValueRef::U8List(bytes))
+    }
+
+    // Replies with serialized result; Errors are converted to error envelopes
+    pub fn send_result<T, E>(self, result: std::result::Result<T, E>)
+    where
+        T: serde::Serialize,
+        E: Into<Error>,
+    {
+        let result = result
+            .map_err(|e| MethodCallError::from_error(e.into()))
+            .and_then(|v| to_value(v).map_err(|e| Error::from(e).into()));
+        self.send(result);
+    }
+}
+
 impl<V> Drop for MethodChannel<V> {
     fn drop(&mut self) {
         let engine_manager = self.context.engine_manager.borrow();
@@ -1,13 +1,20 @@
 use crate::Error;
 
-pub use self::value::Value;
+use self::value::{from_value_owned, ValueError};
+pub use self::value::{Value, ValueArena, ValueRef};
 
 pub mod value;
 
+mod binary_message_channel;
+mod event_channel;
+mod json_codec;
 mod message_channel;
 mod method_channel;
 mod standard_codec;
 
+pub use binary_message_channel::*;
+pub use event_channel::*;
+pub use json_codec::*;
 pub use message_channel::*;
 pub use method_channel::*;
 pub use standard_codec::*;
@@ -17,7 +24,17 @@ pub struct MethodCall<V> {
     pub args: V,
 }
 
-type MethodCallResult<V> = Result<V, MethodCallError<V>>;
+impl MethodCall<Value> {
+    // Deserializes arguments into any serde deserializable type
+    pub fn args_as<T>(&self) -> Result<T, ValueError>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        from_value_owned(&self.args)
+    }
+}
+
+pub type MethodCallResult<V> = Result<V, MethodCallError<V>>;
 
 #[derive(Debug, Clone)]
 pub struct MethodCallError<V> {
@@ -27,6 +44,14 @@ pub struct MethodCallError<V> {
 }
 
 impl<V> MethodCallError<V> {
+    pub fn new(code: &str, message: Option<&str>, details: V) -> Self {
+        Self {
+            code: code.into(),
+            message: message.map(|m| m.into()),
+            details,
+        }
+    }
+
     pub fn from_code_message(code: &str, message: &str) -> Self
     where
         V: Default,
@@ -39,28 +64,62 @@ impl<V> MethodCallError<V> {
     }
 }
 
+// Errors are reported to Dart as PlatformException with stable error code
 impl<V> From<Error> for MethodCallError<V>
 where
     V: Default,
 {
     fn from(e: Error) -> Self {
         Self {
-            code: format!("{:?}", e),
+            code: e.code().into(),
             message: Some(format!("{}", e)),
             details: Default::default(),
         }
     }
 }
 
+impl MethodCallError<Value> {
+    // Like From<Error>, but platform errors also carry details where available
+    pub fn from_error(e: Error) -> Self {
+        let details = match &e {
+            Error::Platform(error) => error.details(),
+            _ => Value::Null,
+        };
+        Self {
+            details,
+            ..e.into()
+        }
+    }
+}
+
 pub trait MessageCodec<V>: Send + Sync {
     /// Methods for plain messages
     fn encode_message(&self, v: &V) -> Vec<u8>;
     fn decode_message(&self, buf: &[u8]) -> Option<V>;
+
+    // Encodes borrowed value; Codecs can override this to avoid copying the
+    // data into owned value first
+    fn encode_message_ref(&self, v: &ValueRef) -> Vec<u8>
+    where
+        V: From<Value>,
+    {
+        self.encode_message(&v.to_value().into())
+    }
 }
 
 pub trait MethodCodec<V>: Send + Sync {
     fn decode_method_call(&self, buf: &[u8]) -> Option<MethodCall<V>>;
     fn encode_success_envelope(&self, v: &V) -> Vec<u8>;
+
+    // Encodes borrowed result; Codecs can override this to write buffers
+    // directly into the envelope
+    fn encode_success_envelope_ref(&self, v: &ValueRef) -> Vec<u8>
+    where
+        V: From<Value>,
+    {
+        self.encode_success_envelope(&v.to_value().into())
+    }
+
     fn encode_error_envelope(&self, code: &str, message: Option<&str>, details: &V) -> Vec<u8>;
 
     fn encode_method_call_result(&self, response: &MethodCallResult<V>) -> Vec<u8> {
@@ -74,5 +133,14 @@ pub trait MethodCodec<V>: Send + Sync {
 
     /// Methods for calling into dart
     fn encode_method_call(&self, v: &MethodCall<V>) -> Vec<u8>;
+    fn encode_method_call_ref(&self, method: &str, args: &ValueRef) -> Vec<u8>
+    where
+        V: From<Value>,
+    {
+        self.encode_method_call(&MethodCall {
+            method: method.into(),
+            args: args.to_value().into(),
+        })
+    }
     fn decode_envelope(&self, buf: &[u8]) -> Option<MethodCallResult<V>>;
 }
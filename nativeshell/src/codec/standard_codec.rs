@@ -1,10 +1,13 @@
-use std::{collections::HashMap, slice};
+use std::{collections::HashMap, convert::TryFrom};
 
 use log::error;
 
 // Based on code from flutter-rs
 
-use super::{MessageCodec, MethodCall, MethodCallResult, MethodCodec, MethodCallError, Value};
+use super::{
+    value::ArenaNode, MessageCodec, MethodCall, MethodCallError, MethodCallResult, MethodCodec,
+    Value, ValueArena, ValueRef,
+};
 
 const VALUE_NULL: u8 = 0;
 const VALUE_TRUE: u8 = 1;
@@ -20,6 +23,7 @@ const VALUE_INT64LIST: u8 = 10;
 const VALUE_FLOAT64LIST: u8 = 11;
 const VALUE_LIST: u8 = 12;
 const VALUE_MAP: u8 = 13;
+const VALUE_FLOAT32LIST: u8 = 14;
 
 pub struct StandardMethodCodec;
 #[derive(Debug)]
@@ -39,6 +43,12 @@ impl MessageCodec<Value> for StandardMethodCodec {
         let mut reader = Reader::new(buf);
         StandardMethodCodec::read_value(&mut reader).ok()
     }
+
+    fn encode_message_ref(&self, v: &ValueRef) -> Vec<u8> {
+        let mut writer = Writer::new(Vec::new());
+        StandardMethodCodec::write_value_ref(&mut writer, v);
+        writer.0
+    }
 }
 
 impl MethodCodec<Value> for StandardMethodCodec {
@@ -50,10 +60,18 @@ impl MethodCodec<Value> for StandardMethodCodec {
         writer.0
     }
 
+    fn encode_method_call_ref(&self, method: &str, args: &ValueRef) -> Vec<u8> {
+        let mut writer = Writer::new(Vec::new());
+
+        StandardMethodCodec::write_string(&mut writer, method);
+        StandardMethodCodec::write_value_ref(&mut writer, args);
+        writer.0
+    }
+
     fn decode_method_call(&self, buf: &[u8]) -> Option<MethodCall<Value>> {
         let mut reader = Reader::new(buf);
-        let method: Value = StandardMethodCodec::read_value(&mut reader).unwrap();
-        let args: Value = StandardMethodCodec::read_value(&mut reader).unwrap();
+        let method: Value = StandardMethodCodec::read_value(&mut reader).ok()?;
+        let args: Value = StandardMethodCodec::read_value(&mut reader).ok()?;
 
         if let Value::String(method) = method {
             return Some(MethodCall { method, args });
@@ -69,6 +87,17 @@ impl MethodCodec<Value> for StandardMethodCodec {
         writer.0
     }
 
+    fn encode_success_envelope_ref(&self, result: &ValueRef) -> Vec<u8> {
+        let mut writer = Writer::new(Vec::new());
+        // Envelope byte, type and size; Avoids reallocation for large buffers
+        if let ValueRef::U8List(list) = result {
+            writer.reserve(list.len() + 8);
+        }
+        writer.write_u8(0);
+        StandardMethodCodec::write_value_ref(&mut writer, result);
+        writer.0
+    }
+
     fn encode_error_envelope(&self, code: &str, message: Option<&str>, v: &Value) -> Vec<u8> {
         let mut writer = Writer::new(Vec::new());
         writer.write_u8(1);
@@ -85,14 +114,14 @@ impl MethodCodec<Value> for StandardMethodCodec {
 
     fn decode_envelope(&self, buf: &[u8]) -> Option<MethodCallResult<Value>> {
         let mut reader = Reader::new(buf);
-        let n = reader.read_u8();
+        let n = reader.read_u8().ok()?;
         if n == 0 {
-            let ret = StandardMethodCodec::read_value(&mut reader).unwrap();
+            let ret = StandardMethodCodec::read_value(&mut reader).ok()?;
             Some(MethodCallResult::Ok(ret))
         } else if n == 1 {
-            let code = StandardMethodCodec::read_value(&mut reader).unwrap();
-            let message = StandardMethodCodec::read_value(&mut reader).unwrap();
-            let details = StandardMethodCodec::read_value(&mut reader).unwrap();
+            let code = StandardMethodCodec::read_value(&mut reader).ok()?;
+            let message = StandardMethodCodec::read_value(&mut reader).ok()?;
+            let details = StandardMethodCodec::read_value(&mut reader).ok()?;
             Some(MethodCallResult::Err(MethodCallError {
                 code: match code {
                     Value::String(s) => s,
@@ -111,46 +140,149 @@ impl MethodCodec<Value> for StandardMethodCodec {
 }
 
 impl StandardMethodCodec {
+    // Decodes message into arena; Unlike decode_message this doesn't copy
+    // strings and typed lists and doesn't allocate map for every map value
+    pub fn decode_message_in_arena<'a>(&self, buf: &'a [u8]) -> Option<ValueArena<'a>> {
+        let mut reader = Reader::new(buf);
+        let mut arena = ValueArena::new();
+        let root = Self::read_node(&mut reader, &mut arena).ok()?;
+        arena.set_root(root);
+        Some(arena)
+    }
+
+    fn read_node<'a>(
+        reader: &mut Reader<'a>,
+        arena: &mut ValueArena<'a>,
+    ) -> Result<ArenaNode<'a>, DecodeError> {
+        if reader.ended() {
+            return Err(DecodeError::Ended);
+        }
+
+        let t = reader.read_u8()?;
+        Ok(match t {
+            VALUE_NULL => ArenaNode::Null,
+            VALUE_FALSE => ArenaNode::Bool(false),
+            VALUE_TRUE => ArenaNode::Bool(true),
+            VALUE_INT32 => ArenaNode::I64(reader.read_i32()?.into()),
+            VALUE_INT64 => ArenaNode::I64(reader.read_i64()?),
+            VALUE_LARGEINT => {
+                let len = reader.read_size()?;
+                let string = reader.read_str(len)?;
+                match parse_large_int(string) {
+                    Some(value) => ArenaNode::I64(value),
+                    None => ArenaNode::String(string),
+                }
+            }
+            VALUE_FLOAT64 => {
+                reader.align_to(8);
+                ArenaNode::F64(reader.read_f64()?)
+            }
+            VALUE_STRING => {
+                let len = reader.read_size()?;
+                ArenaNode::String(reader.read_str(len)?)
+            }
+            VALUE_UINT8LIST => {
+                let len = reader.read_size()?;
+                ArenaNode::U8List(reader.read_slice(len, 1)?)
+            }
+            VALUE_INT32LIST => {
+                let len = reader.read_size()?;
+                ArenaNode::I32List(reader.read_slice(len, 4)?)
+            }
+            VALUE_INT64LIST => {
+                let len = reader.read_size()?;
+                ArenaNode::I64List(reader.read_slice(len, 8)?)
+            }
+            VALUE_FLOAT64LIST => {
+                let len = reader.read_size()?;
+                ArenaNode::F64List(reader.read_slice(len, 8)?)
+            }
+            VALUE_FLOAT32LIST => {
+                let len = reader.read_size()?;
+                ArenaNode::F32List(reader.read_slice(len, 4)?)
+            }
+            VALUE_LIST => {
+                let len = reader.read_size()?;
+                // Every item takes at least one byte
+                if len > reader.remaining() {
+                    return Err(DecodeError::Ended);
+                }
+                let start = arena.reserve_nodes(len);
+                for i in 0..len {
+                    let item = Self::read_node(reader, arena)?;
+                    arena.set_node(start + i, item);
+                }
+                ArenaNode::List(start, len)
+            }
+            VALUE_MAP => {
+                let len = reader.read_size()?;
+                if len * 2 > reader.remaining() {
+                    return Err(DecodeError::Ended);
+                }
+                let start = arena.reserve_nodes(len * 2);
+                for i in 0..len * 2 {
+                    let item = Self::read_node(reader, arena)?;
+                    arena.set_node(start + i, item);
+                }
+                ArenaNode::Map(start, len)
+            }
+            _ => ArenaNode::Null,
+        })
+    }
+
     fn read_value(reader: &mut Reader) -> Result<Value, DecodeError> {
         if reader.ended() {
             return Err(DecodeError::Ended);
         }
 
-        let t = reader.read_u8();
+        let t = reader.read_u8()?;
         Ok(match t {
             VALUE_NULL => Value::Null,
             VALUE_FALSE => Value::Bool(false),
             VALUE_TRUE => Value::Bool(true),
-            VALUE_INT32 => Value::I64(reader.read_i32().into()),
-            VALUE_INT64 => Value::I64(reader.read_i64()),
-            VALUE_LARGEINT => panic!("Not implemented"),
+            VALUE_INT32 => Value::I64(reader.read_i32()?.into()),
+            VALUE_INT64 => Value::I64(reader.read_i64()?),
+            // Large integers are encoded as hexadecimal string; Keep the string
+            // if the value doesn't fit into i64
+            VALUE_LARGEINT => {
+                let len = reader.read_size()?;
+                let string = reader.read_string(len)?;
+                match parse_large_int(&string) {
+                    Some(value) => Value::I64(value),
+                    None => Value::String(string),
+                }
+            }
             VALUE_FLOAT64 => {
                 reader.align_to(8);
-                Value::F64(reader.read_f64())
+                Value::F64(reader.read_f64()?)
             }
             VALUE_STRING => {
-                let len = reader.read_size();
-                Value::String(reader.read_string(len))
+                let len = reader.read_size()?;
+                Value::String(reader.read_string(len)?)
             }
             VALUE_UINT8LIST => {
-                let len = reader.read_size();
-                Value::U8List(reader.read_u8_list(len))
+                let len = reader.read_size()?;
+                Value::U8List(reader.read_u8_list(len)?)
             }
             VALUE_INT32LIST => {
-                let len = reader.read_size();
-                Value::I32List(reader.read_i32_list(len))
+                let len = reader.read_size()?;
+                Value::I32List(reader.read_i32_list(len)?)
             }
             VALUE_INT64LIST => {
-                let len = reader.read_size();
-                Value::I64List(reader.read_i64_list(len))
+                let len = reader.read_size()?;
+                Value::I64List(reader.read_i64_list(len)?)
             }
             VALUE_FLOAT64LIST => {
-                let len = reader.read_size();
-                Value::F64List(reader.read_f64_list(len))
+                let len = reader.read_size()?;
+                Value::F64List(reader.read_f64_list(len)?)
+            }
+            VALUE_FLOAT32LIST => {
+                let len = reader.read_size()?;
+                Value::F32List(reader.read_f32_list(len)?)
             }
             VALUE_LIST => {
-                let len = reader.read_size();
-                let mut list = Vec::new();
+                let len = reader.read_size()?;
+                let mut list = Vec::with_capacity(len.min(reader.remaining()));
                 for _ in 0..len {
                     if let Ok(e) = Self::read_value(reader) {
                         list.push(e);
@@ -161,8 +293,8 @@ impl StandardMethodCodec {
                 Value::List(list)
             }
             VALUE_MAP => {
-                let len = reader.read_size();
-                let mut map = HashMap::new();
+                let len = reader.read_size()?;
+                let mut map = HashMap::with_capacity(len.min(reader.remaining()));
                 for _ in 0..len {
                     let k = Self::read_value(reader);
                     let v = Self::read_value(reader);
@@ -185,14 +317,17 @@ impl StandardMethodCodec {
         writer.write_string(s);
     }
     fn write_value(writer: &mut Writer, v: &Value) {
+        Self::write_value_ref(writer, &v.into());
+    }
+    fn write_value_ref(writer: &mut Writer, v: &ValueRef) {
         match v {
-            Value::Null => {
+            ValueRef::Null => {
                 writer.write_u8(VALUE_NULL);
             }
-            Value::Bool(v) => {
+            ValueRef::Bool(v) => {
                 writer.write_u8(if *v { VALUE_TRUE } else { VALUE_FALSE });
             }
-            Value::I64(n) => {
+            ValueRef::I64(n) => {
                 if -0x7fffffff - 1 <= *n && *n <= 0x7fffffff {
                     writer.write_u8(VALUE_INT32);
                     writer.write_i32(*n as i32);
@@ -201,54 +336,78 @@ impl StandardMethodCodec {
                     writer.write_i64(*n);
                 }
             }
-            Value::F64(n) => {
+            ValueRef::F64(n) => {
                 writer.write_u8(VALUE_FLOAT64);
                 writer.align_to(8);
                 writer.write_f64(*n);
             }
-            Value::String(s) => {
+            ValueRef::String(s) => {
                 Self::write_string(writer, s);
             }
-            Value::U8List(list) => {
+            ValueRef::U8List(list) => {
                 writer.write_u8(VALUE_UINT8LIST);
                 writer.write_size(list.len());
-                writer.align_to(1);
-                for n in list {
-                    writer.write_u8(*n);
-                }
+                writer.write_u8_list(list);
             }
-            Value::I32List(list) => {
+            ValueRef::I32List(list) => {
                 writer.write_u8(VALUE_INT32LIST);
                 writer.write_size(list.len());
                 writer.align_to(4);
-                for n in list {
+                writer.reserve(list.len() * 4);
+                for n in list.iter() {
                     writer.write_i32(*n);
                 }
             }
-            Value::I64List(list) => {
+            ValueRef::I64List(list) => {
                 writer.write_u8(VALUE_INT64LIST);
                 writer.write_size(list.len());
                 writer.align_to(8);
-                for n in list {
+                writer.reserve(list.len() * 8);
+                for n in list.iter() {
                     writer.write_i64(*n);
                 }
             }
-            Value::F64List(list) => {
+            ValueRef::F64List(list) => {
                 writer.write_u8(VALUE_FLOAT64LIST);
                 writer.write_size(list.len());
                 writer.align_to(8);
-                for n in list {
+                writer.reserve(list.len() * 8);
+                for n in list.iter() {
                     writer.write_f64(*n);
                 }
             }
-            Value::List(list) => {
+            ValueRef::F32List(list) => {
+                writer.write_u8(VALUE_FLOAT32LIST);
+                writer.write_size(list.len());
+                writer.align_to(4);
+                writer.reserve(list.len() * 4);
+                for n in list.iter() {
+                    writer.write_u32(n.to_bits());
+                }
+            }
+            ValueRef::List(list) => {
+                writer.write_u8(VALUE_LIST);
+                writer.write_size(list.len());
+                list.iter().for_each(|v| {
+                    Self::write_value_ref(writer, v);
+                });
+            }
+            ValueRef::Map(map) => {
+                writer.write_u8(VALUE_MAP);
+                writer.write_size(map.len());
+                map.iter().for_each(|(k, v)| {
+                    Self::write_value_ref(writer, k);
+                    Self::write_value_ref(writer, v);
+                });
+            }
+            ValueRef::Value(Value::List(list)) => {
                 writer.write_u8(VALUE_LIST);
                 writer.write_size(list.len());
                 list.iter().for_each(|v| {
                     Self::write_value(writer, v);
                 });
             }
-            Value::Map(map) => {
+            ValueRef::Value(Value::Map(map)) => {
                 writer.write_u8(VALUE_MAP);
                 writer.write_size(map.len());
                 map.iter().for_each(|(k, v)| {
@@ -256,6 +415,9 @@ impl StandardMethodCodec {
                     Self::write_value(writer, v);
                 });
             }
+            ValueRef::Value(v) => {
+                Self::write_value(writer, v);
+            }
         }
     }
 }
@@ -266,9 +428,15 @@ impl Writer {
     fn new(v: Vec<u8>) -> Self {
         Writer(v)
     }
+    fn reserve(&mut self, additional: usize) {
+        self.0.reserve(additional);
+    }
     fn write_u8(&mut self, n: u8) {
         self.0.push(n);
     }
+    fn write_u8_list(&mut self, list: &[u8]) {
+        self.0.extend_from_slice(list);
+    }
     fn write_u16(&mut self, n: u16) {
         self.0.extend_from_slice(&n.to_ne_bytes());
     }
@@ -325,90 +493,96 @@ impl<'a> Reader<'a> {
     fn new(buf: &'a [u8]) -> Self {
         Reader { buf, pos: 0 }
     }
-    fn read_u8(&mut self) -> u8 {
-        let n = self.buf[self.pos];
-        self.pos += 1;
-        n
-    }
-    fn read_u16(&mut self) -> u16 {
-        self.pos += 2;
-        let s = &self.buf[self.pos - 2..self.pos];
-        u16::from_ne_bytes(clone_into_array(s))
-    }
-    fn read_u32(&mut self) -> u32 {
-        self.pos += 4;
-        let s = &self.buf[self.pos - 4..self.pos];
-        u32::from_ne_bytes(clone_into_array(s))
-    }
-    fn read_i32(&mut self) -> i32 {
-        self.pos += 4;
-        let s = &self.buf[self.pos - 4..self.pos];
-        i32::from_ne_bytes(clone_into_array(s))
-    }
-    fn read_u64(&mut self) -> u64 {
-        self.pos += 8;
-        let s = &self.buf[self.pos - 8..self.pos];
-        u64::from_ne_bytes(clone_into_array(s))
-    }
-    fn read_i64(&mut self) -> i64 {
-        self.pos += 8;
-        let s = &self.buf[self.pos - 8..self.pos];
-        i64::from_ne_bytes(clone_into_array(s))
-    }
-    fn read_f64(&mut self) -> f64 {
-        let n = self.read_u64();
-        f64::from_bits(n)
-    }
-    fn read_size(&mut self) -> usize {
-        let n = self.read_u8();
-        match n {
-            254 => self.read_u16() as usize,
-            255 => self.read_u32() as usize,
-            _ => n as usize,
-        }
+    // Fails instead of panicking when the message is truncated
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        let end = self.pos.checked_add(len).ok_or(DecodeError::Invalid)?;
+        let res = self.buf.get(self.pos..end).ok_or(DecodeError::Ended)?;
+        self.pos = end;
+        Ok(res)
     }
-    fn read_string(&mut self, len: usize) -> String {
-        unsafe {
-            if len == 0 {
-                String::from("")
-            } else {
-                let v = slice::from_raw_parts(&self.buf[self.pos], len);
-                self.pos += len;
-                String::from_utf8_lossy(v).to_owned().to_string()
-            }
-        }
+    fn read_u8(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.read_bytes(1)?[0])
     }
-    fn read_u8_list(&mut self, len: usize) -> Vec<u8> {
-        let mut v = Vec::with_capacity(len);
-        for _ in 0..len {
-            v.push(self.read_u8());
-        }
-        v
+    fn read_u16(&mut self) -> Result<u16, DecodeError> {
+        Ok(u16::from_ne_bytes(clone_into_array(self.read_bytes(2)?)))
     }
-    fn read_i32_list(&mut self, len: usize) -> Vec<i32> {
-        let mut v = Vec::with_capacity(len);
-        self.align_to(4);
-        for _ in 0..len {
-            v.push(self.read_i32());
-        }
-        v
+    fn read_u32(&mut self) -> Result<u32, DecodeError> {
+        Ok(u32::from_ne_bytes(clone_into_array(self.read_bytes(4)?)))
     }
-    fn read_i64_list(&mut self, len: usize) -> Vec<i64> {
-        let mut v = Vec::with_capacity(len);
-        self.align_to(8);
-        for _ in 0..len {
-            v.push(self.read_i64());
-        }
-        v
-    }
-    fn read_f64_list(&mut self, len: usize) -> Vec<f64> {
-        let mut v = Vec::with_capacity(len);
-        self.align_to(8);
-        for _ in 0..len {
-            let n = self.read_u64();
-            v.push(f64::from_bits(n));
-        }
-        v
+    fn read_i32(&mut self) -> Result<i32, DecodeError> {
+        Ok(i32::from_ne_bytes(clone_into_array(self.read_bytes(4)?)))
+    }
+    fn read_u64(&mut self) -> Result<u64, DecodeError> {
+        Ok(u64::from_ne_bytes(clone_into_array(self.read_bytes(8)?)))
+    }
+    fn read_i64(&mut self) -> Result<i64, DecodeError> {
+        Ok(i64::from_ne_bytes(clone_into_array(self.read_bytes(8)?)))
+    }
+    fn read_f64(&mut self) -> Result<f64, DecodeError> {
+        Ok(f64::from_bits(self.read_u64()?))
+    }
+    fn read_size(&mut self) -> Result<usize, DecodeError> {
+        let n = self.read_u8()?;
+        Ok(match n {
+            254 => self.read_u16()? as usize,
+            255 => self.read_u32()? as usize,
+            _ => n as usize,
+        })
+    }
+    // Strings are copied directly from the buffer; Map keys in particular
+    // are short and decoded often, so avoid intermediate allocations
+    fn read_string(&mut self, len: usize) -> Result<String, DecodeError> {
+        let v = self.read_bytes(len)?;
+        Ok(match std::str::from_utf8(v) {
+            Ok(s) => s.to_owned(),
+            Err(_) => String::from_utf8_lossy(v).into_owned(),
+        })
+    }
+    // Borrowed string can't be lossy converted, so invalid UTF-8 is an error
+    fn read_str(&mut self, len: usize) -> Result<&'a str, DecodeError> {
+        std::str::from_utf8(self.read_bytes(len)?).map_err(|_| DecodeError::Invalid)
+    }
+    // Returns aligned slice for typed list of given length and element size
+    fn read_slice(&mut self, len: usize, element_size: usize) -> Result<&'a [u8], DecodeError> {
+        self.align_to(element_size);
+        let size = len.checked_mul(element_size).ok_or(DecodeError::Invalid)?;
+        self.read_bytes(size)
+    }
+    fn read_u8_list(&mut self, len: usize) -> Result<Vec<u8>, DecodeError> {
+        Ok(self.read_slice(len, 1)?.to_vec())
+    }
+    fn read_i32_list(&mut self, len: usize) -> Result<Vec<i32>, DecodeError> {
+        Ok(self
+            .read_slice(len, 4)?
+            .chunks_exact(4)
+            .map(|c| i32::from_ne_bytes(clone_into_array(c)))
+            .collect())
+    }
+    fn read_i64_list(&mut self, len: usize) -> Result<Vec<i64>, DecodeError> {
+        Ok(self
+            .read_slice(len, 8)?
+            .chunks_exact(8)
+            .map(|c| i64::from_ne_bytes(clone_into_array(c)))
+            .collect())
+    }
+    fn read_f32_list(&mut self, len: usize) -> Result<Vec<f32>, DecodeError> {
+        Ok(self
+            .read_slice(len, 4)?
+            .chunks_exact(4)
+            .map(|c| f32::from_bits(u32::from_ne_bytes(clone_into_array(c))))
+            .collect())
+    }
+    fn read_f64_list(&mut self, len: usize) -> Result<Vec<f64>, DecodeError> {
+        Ok(self
+            .read_slice(len, 8)?
+            .chunks_exact(8)
+            .map(|c| f64::from_bits(u64::from_ne_bytes(clone_into_array(c))))
+            .collect())
+    }
+    // Upper bound for number of elements that can still be decoded; Used to
+    // preallocate collections without trusting the length from the message
+    fn remaining(&self) -> usize {
+        self.buf.len().saturating_sub(self.pos)
     }
     fn ended(&self) -> bool {
         self.pos >= self.buf.len()
@@ -421,6 +595,17 @@ impl<'a> Reader<'a> {
     }
 }
 
+fn parse_large_int(string: &str) -> Option<i64> {
+    let (negative, digits) = match string.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, string),
+    };
+    let digits = digits.trim_start_matches("0x");
+    let value = i128::from_str_radix(digits, 16).ok()?;
+    let value = if negative { -value } else { value };
+    i64::try_from(value).ok()
+}
+
 fn clone_into_array<A, T>(slice: &[T]) -> A
 where
     A: Sized + Default + AsMut<[T]>,
@@ -430,3 +615,155 @@ where
     <A as AsMut<[T]>>::as_mut(&mut a).clone_from_slice(slice);
     a
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    use serde::Deserialize;
+    use velcro::hash_map;
+
+    use super::*;
+
+    // Counts allocations made by current thread
+    struct CountingAllocator;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            ALLOCATIONS.try_with(|a| a.set(a.get() + 1)).ok();
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn count_allocations<F: FnOnce()>(f: F) -> usize {
+        let before = ALLOCATIONS.with(|a| a.get());
+        f();
+        ALLOCATIONS.with(|a| a.get()) - before
+    }
+
+    #[derive(Deserialize, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    struct Frame<'a> {
+        window_handle: i64,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        title: &'a str,
+    }
+
+    #[test]
+    fn test_decode_in_arena() {
+        let codec = StandardMethodCodec;
+        let value = Value::Map(hash_map! {
+            "windowHandle".into(): 5i64.into(),
+            "x".into(): 10.0.into(),
+            "y".into(): 20.0.into(),
+            "width".into(): 300.0.into(),
+            "height".into(): 200.5.into(),
+            "title".into(): "Window".into(),
+        });
+        let message = codec.encode_message(&value);
+
+        let value_allocations = count_allocations(|| {
+            codec.decode_message(&message).unwrap();
+        });
+        let arena_allocations = count_allocations(|| {
+            let arena = codec.decode_message_in_arena(&message).unwrap();
+            let frame: Frame = arena.deserialize().unwrap();
+            assert_eq!(frame.title, "Window");
+        });
+        // Map nodes are the only allocation
+        assert_eq!(arena_allocations, 1);
+        assert!(value_allocations > 6);
+
+        let arena = codec.decode_message_in_arena(&message).unwrap();
+        assert_eq!(arena.to_value(), value);
+        assert_eq!(
+            arena.deserialize::<Frame>().unwrap(),
+            Frame {
+                window_handle: 5,
+                x: 10.0,
+                y: 20.0,
+                width: 300.0,
+                height: 200.5,
+                title: "Window",
+            }
+        );
+        assert_eq!(arena.root().get("title").unwrap().as_str(), Some("Window"));
+    }
+
+    #[test]
+    fn test_truncated_message() {
+        let codec = StandardMethodCodec;
+        let value = Value::List(vec!["Hello".into(), Value::I32List(vec![1, 2, 3])]);
+        let message = codec.encode_message(&value);
+        for len in 0..message.len() {
+            assert!(codec.decode_message(&message[..len]).is_none());
+            assert!(codec.decode_message_in_arena(&message[..len]).is_none());
+        }
+        assert!(codec.decode_message(&[VALUE_LIST, 200]).is_none());
+        assert!(codec.decode_message_in_arena(&[VALUE_LIST, 200]).is_none());
+        assert!(codec.decode_envelope(&[0, VALUE_STRING, 10]).is_none());
+    }
+
+    #[test]
+    fn test_large_int() {
+        let codec = StandardMethodCodec;
+        let message = |string: &str| {
+            let mut message = vec![VALUE_LARGEINT, string.len() as u8];
+            message.extend_from_slice(string.as_bytes());
+            message
+        };
+        let cases = [
+            ("7fffffffffffffff", Value::I64(i64::MAX)),
+            ("-8000000000000000", Value::I64(i64::MIN)),
+            ("-0x1f", Value::I64(-31)),
+            // Doesn't fit into i64
+            ("10000000000000000", "10000000000000000".into()),
+        ];
+        for (string, expected) in cases.iter() {
+            let message = message(string);
+            let value = codec.decode_message(&message).unwrap();
+            assert_eq!(&value, expected);
+            let arena = codec.decode_message_in_arena(&message).unwrap();
+            assert_eq!(&arena.to_value(), expected);
+            // Decoded value is written back as regular int or string
+            let encoded = codec.encode_message(&value);
+            assert_eq!(&codec.decode_message(&encoded).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_f32_list() {
+        let codec = StandardMethodCodec;
+        let list = vec![1.5f32, -2.0, f32::MAX, f32::MIN_POSITIVE];
+        // Strings of different length shift start of the list data
+        for prefix in ["", "a", "ab", "abc"].iter() {
+            let value = Value::List(vec![(*prefix).into(), Value::F32List(list.clone())]);
+            let message = codec.encode_message(&value);
+            let data_len = list.len() * 4;
+            let offset = message.len() - data_len;
+            assert_eq!(offset % 4, 0);
+            assert_eq!(&message[offset..offset + 4], &1.5f32.to_ne_bytes());
+
+            assert_eq!(codec.decode_message(&message).unwrap(), value);
+            let arena = codec.decode_message_in_arena(&message).unwrap();
+            assert_eq!(arena.to_value(), value);
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use std::{collections::HashMap, convert::TryInto};
+
+use serde::de::{
+    value::{BorrowedStrDeserializer, MapAccessDeserializer, MapDeserializer, SeqDeserializer},
+    IntoDeserializer,
+};
+
+use super::{Value, ValueError};
+
+type Result<T> = std::result::Result<T, ValueError>;
+
+// Value decoded without copying the message. Strings and typed lists point
+// into the message buffer and items of all lists and maps are stored in single
+// vector, so decoding needs one allocation regardless of number of map keys.
+// Meant for messages that are deserialized and dropped right away.
+pub struct ValueArena<'a> {
+    nodes: Vec<ArenaNode<'a>>,
+    root: ArenaNode<'a>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum ArenaNode<'a> {
+    Null,
+    Bool(bool),
+    I64(i64),
+    F64(f64),
+    String(&'a str),
+    U8List(&'a [u8]),
+    // Typed lists are kept as native endian bytes
+    I32List(&'a [u8]),
+    I64List(&'a [u8]),
+    F32List(&'a [u8]),
+    F64List(&'a [u8]),
+    // First node and item count; Map entries take two nodes (key and value)
+    List(usize, usize),
+    Map(usize, usize),
+}
+
+impl<'a> ValueArena<'a> {
+    pub(crate) fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            root: ArenaNode::Null,
+        }
+    }
+
+    // Reserves consecutive nodes for items of a list or map; Returns index of
+    // the first node
+    pub(crate) fn reserve_nodes(&mut self, count: usize) -> usize {
+        let start = self.nodes.len();
+        self.nodes.resize(start + count, ArenaNode::Null);
+        start
+    }
+
+    pub(crate) fn set_node(&mut self, index: usize, node: ArenaNode<'a>) {
+        self.nodes[index] = node;
+    }
+
+    pub(crate) fn set_root(&mut self, node: ArenaNode<'a>) {
+        self.root = node;
+    }
+
+    pub fn root(&self) -> ArenaValue<'_, 'a> {
+        ArenaValue {
+            arena: self,
+            node: self.root,
+        }
+    }
+
+    // Creates owned copy of the value
+    pub fn to_value(&self) -> Value {
+        self.root().to_value()
+    }
+
+    pub fn deserialize<T>(&self) -> Result<T>
+    where
+        T: serde::de::Deserialize<'a>,
+    {
+        T::deserialize(self.root())
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct ArenaValue<'r, 'a> {
+    arena: &'r ValueArena<'a>,
+    node: ArenaNode<'a>,
+}
+
+impl<'r, 'a> ArenaValue<'r, 'a> {
+    fn with_node(&self, node: &ArenaNode<'a>) -> Self {
+        Self {
+            arena: self.arena,
+            node: *node,
+        }
+    }
+
+    fn items(&self, start: usize, len: usize) -> impl Iterator<Item = ArenaValue<'r, 'a>> {
+        let this = *self;
+        self.arena.nodes[start..start + len]
+            .iter()
+            .map(move |node| this.with_node(node))
+    }
+
+    fn entries(
+        &self,
+        start: usize,
+        len: usize,
+    ) -> impl Iterator<Item = (ArenaValue<'r, 'a>, ArenaValue<'r, 'a>)> {
+        let this = *self;
+        self.arena.nodes[start..start + len * 2]
+            .chunks_exact(2)
+            .map(move |entry| (this.with_node(&entry[0]), this.with_node(&entry[1])))
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self.node, ArenaNode::Null)
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.node {
+            ArenaNode::I64(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&'a str> {
+        match self.node {
+            ArenaNode::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    // Value for given string key if this is a map
+    pub fn get(&self, key: &str) -> Option<ArenaValue<'r, 'a>> {
+        match self.node {
+            ArenaNode::Map(start, len) => self
+                .entries(start, len)
+                .find(|(k, _)| k.as_str() == Some(key))
+                .map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn to_value(&self) -> Value {
+        match self.node {
+            ArenaNode::Null => Value::Null,
+            ArenaNode::Bool(v) => Value::Bool(v),
+            ArenaNode::I64(v) => Value::I64(v),
+            ArenaNode::F64(v) => Value::F64(v),
+            ArenaNode::String(v) => Value::String(v.into()),
+            ArenaNode::U8List(v) => Value::U8List(v.into()),
+            ArenaNode::I32List(v) => Value::I32List(i32_items(v).collect()),
+            ArenaNode::I64List(v) => Value::I64List(i64_items(v).collect()),
+            ArenaNode::F32List(v) => Value::F32List(f32_items(v).collect()),
+            ArenaNode::F64List(v) => Value::F64List(f64_items(v).collect()),
+            ArenaNode::List(start, len) => {
+                Value::List(self.items(start, len).map(|v| v.to_value()).collect())
+            }
+            ArenaNode::Map(start, len) => Value::Map(
+                self.entries(start, len)
+                    .map(|(k, v)| (k.to_value(), v.to_value()))
+                    .collect::<HashMap<_, _>>(),
+            ),
+        }
+    }
+}
+
+fn i32_items(bytes: &[u8]) -> impl Iterator<Item = i32> + '_ {
+    bytes
+        .chunks_exact(4)
+        .map(|c| i32::from_ne_bytes(c.try_into().unwrap()))
+}
+
+fn i64_items(bytes: &[u8]) -> impl Iterator<Item = i64> + '_ {
+    bytes
+        .chunks_exact(8)
+        .map(|c| i64::from_ne_bytes(c.try_into().unwrap()))
+}
+
+fn f32_items(bytes: &[u8]) -> impl Iterator<Item = f32> + '_ {
+    bytes
+        .chunks_exact(4)
+        .map(|c| f32::from_ne_bytes(c.try_into().unwrap()))
+}
+
+fn f64_items(bytes: &[u8]) -> impl Iterator<Item = f64> + '_ {
+    bytes
+        .chunks_exact(8)
+        .map(|c| f64::from_ne_bytes(c.try_into().unwrap()))
+}
+
+impl<'r, 'a> IntoDeserializer<'a, ValueError> for ArenaValue<'r, 'a> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+// Strings are passed to visitor as borrowed, so struct field names and
+// borrowed string fields don't need to be allocated
+impl<'r, 'de> serde::de::Deserializer<'de> for ArenaValue<'r, 'de> {
+    type Error = ValueError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        match self.node {
+            ArenaNode::Null => visitor.visit_unit(),
+            ArenaNode::Bool(b) => visitor.visit_bool(b),
+            ArenaNode::I64(i) => visitor.visit_i64(i),
+            ArenaNode::F64(f) => visitor.visit_f64(f),
+            ArenaNode::String(s) => visitor.visit_borrowed_str(s),
+            ArenaNode::U8List(s) => visitor.visit_borrowed_bytes(s),
+            ArenaNode::I32List(s) => visitor.visit_seq(SeqDeserializer::new(i32_items(s))),
+            ArenaNode::I64List(s) => visitor.visit_seq(SeqDeserializer::new(i64_items(s))),
+            ArenaNode::F32List(s) => visitor.visit_seq(SeqDeserializer::new(f32_items(s))),
+            ArenaNode::F64List(s) => visitor.visit_seq(SeqDeserializer::new(f64_items(s))),
+            ArenaNode::List(start, len) => {
+                visitor.visit_seq(SeqDeserializer::new(self.items(start, len)))
+            }
+            ArenaNode::Map(start, len) => {
+                visitor.visit_map(MapDeserializer::new(self.entries(start, len)))
+            }
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        match self.node {
+            ArenaNode::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        match self.node {
+            ArenaNode::String(s) => visitor.visit_enum(BorrowedStrDeserializer::new(s)),
+            ArenaNode::Map(start, 1) => visitor.visit_enum(MapAccessDeserializer::new(
+                MapDeserializer::new(self.entries(start, 1)),
+            )),
+            _ => Err(ValueError::WrongType),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool
+        i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes byte_buf unit unit_struct
+        seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
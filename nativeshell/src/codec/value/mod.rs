@@ -1,18 +1,25 @@
+mod arena;
 mod deserializer;
 mod serializer;
+mod value_ref;
 
 use std::{convert::TryFrom, f64::NAN, fmt};
 
 use std::collections::HashMap;
 use std::hash::Hash;
 
+pub(crate) use self::arena::ArenaNode;
+pub use self::arena::{ArenaValue, ValueArena};
 pub use self::deserializer::{from_value, from_value_owned};
 pub use self::serializer::to_value;
+pub use self::value_ref::ValueRef;
 
 #[derive(Clone, Debug)]
 pub enum Value {
     Null,
     Bool(bool),
+    // Integers of any size sent from Dart; Standard codec decodes LARGEINT that
+    // doesn't fit into i64 as Value::String with the hexadecimal representation
     I64(i64),
     F64(f64),
     String(String),
@@ -20,6 +27,7 @@ pub enum Value {
     I32List(Vec<i32>),
     I64List(Vec<i64>),
     F64List(Vec<f64>),
+    F32List(Vec<f32>),
     List(Vec<Value>),
     Map(HashMap<Value, Value>),
 }
@@ -51,6 +59,7 @@ impl_from!(Value::U8List, Vec<u8>);
 impl_from!(Value::I32List, Vec<i32>);
 impl_from!(Value::I64List, Vec<i64>);
 impl_from!(Value::F64List, Vec<f64>);
+impl_from!(Value::F32List, Vec<f32>);
 impl_from!(Value::List, Vec<Value>);
 impl_from!(Value::Map, HashMap<Value, Value>);
 
@@ -77,6 +86,7 @@ impl PartialEq for Value {
             (Value::I32List(a), Value::I32List(b)) => a.eq(b),
             (Value::I64List(a), Value::I64List(b)) => a.eq(b),
             (Value::F64List(a), Value::F64List(b)) => a.eq(b),
+            (Value::F32List(a), Value::F32List(b)) => a.eq(b),
             (Value::List(a), Value::List(b)) => a.eq(b),
             (Value::Map(a), Value::Map(b)) => eq_map(a, b),
             (_, _) => false,
@@ -112,6 +122,7 @@ impl std::hash::Hash for Value {
             Value::I32List(v) => v.hash(state),
             Value::I64List(v) => v.hash(state),
             Value::F64List(v) => v.iter().for_each(|x| hash_f64(*x, state)),
+            Value::F32List(v) => v.iter().for_each(|x| hash_f64(*x as f64, state)),
             Value::List(v) => v.hash(state),
             Value::Map(v) => hash_map(v, state),
         }
@@ -172,6 +183,7 @@ impl serde::Serialize for Value {
             Value::I32List(vec) => vec.serialize(serializer),
             Value::I64List(vec) => vec.serialize(serializer),
             Value::F64List(vec) => vec.serialize(serializer),
+            Value::F32List(vec) => vec.serialize(serializer),
             Value::List(vec) => vec.serialize(serializer),
             Value::Map(map) => {
                 use serde::ser::SerializeMap;
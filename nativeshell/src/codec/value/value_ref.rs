@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+
+use super::Value;
+
+// Borrowed counterpart of Value. Can be used to encode existing buffers and
+// strings without copying them into Value tree first. Owned values can be
+// embedded using ValueRef::Value.
+#[derive(Clone, Debug)]
+pub enum ValueRef<'a> {
+    Null,
+    Bool(bool),
+    I64(i64),
+    F64(f64),
+    String(&'a str),
+    U8List(&'a [u8]),
+    I32List(&'a [i32]),
+    I64List(&'a [i64]),
+    F64List(&'a [f64]),
+    F32List(&'a [f32]),
+    List(Vec<ValueRef<'a>>),
+    Map(Vec<(ValueRef<'a>, ValueRef<'a>)>),
+    Value(&'a Value),
+}
+
+impl<'a> ValueRef<'a> {
+    // Creates owned copy of the value
+    pub fn to_value(&self) -> Value {
+        match self {
+            ValueRef::Null => Value::Null,
+            ValueRef::Bool(v) => Value::Bool(*v),
+            ValueRef::I64(v) => Value::I64(*v),
+            ValueRef::F64(v) => Value::F64(*v),
+            ValueRef::String(v) => Value::String((*v).into()),
+            ValueRef::U8List(v) => Value::U8List(v.to_vec()),
+            ValueRef::I32List(v) => Value::I32List(v.to_vec()),
+            ValueRef::I64List(v) => Value::I64List(v.to_vec()),
+            ValueRef::F64List(v) => Value::F64List(v.to_vec()),
+            ValueRef::F32List(v) => Value::F32List(v.to_vec()),
+            ValueRef::List(v) => Value::List(v.iter().map(|v| v.to_value()).collect()),
+            ValueRef::Map(v) => Value::Map(
+                v.iter()
+                    .map(|(k, v)| (k.to_value(), v.to_value()))
+                    .collect::<HashMap<_, _>>(),
+            ),
+            ValueRef::Value(v) => (*v).clone(),
+        }
+    }
+}
+
+// Scalars and typed lists are mapped to respective variants, collections are
+// wrapped in ValueRef::Value
+impl<'a> From<&'a Value> for ValueRef<'a> {
+    fn from(value: &'a Value) -> Self {
+        match value {
+            Value::Null => ValueRef::Null,
+            Value::Bool(v) => ValueRef::Bool(*v),
+            Value::I64(v) => ValueRef::I64(*v),
+            Value::F64(v) => ValueRef::F64(*v),
+            Value::String(v) => ValueRef::String(v),
+            Value::U8List(v) => ValueRef::U8List(v),
+            Value::I32List(v) => ValueRef::I32List(v),
+            Value::I64List(v) => ValueRef::I64List(v),
+            Value::F64List(v) => ValueRef::F64List(v),
+            Value::F32List(v) => ValueRef::F32List(v),
+            Value::List(_) | Value::Map(_) => ValueRef::Value(value),
+        }
+    }
+}
+
+macro_rules! impl_from_ref {
+    ($variant:path, $for_type:ty) => {
+        impl<'a> From<$for_type> for ValueRef<'a> {
+            fn from(v: $for_type) -> ValueRef<'a> {
+                $variant(v)
+            }
+        }
+    };
+}
+
+impl_from_ref!(ValueRef::Bool, bool);
+impl_from_ref!(ValueRef::I64, i64);
+impl_from_ref!(ValueRef::F64, f64);
+impl_from_ref!(ValueRef::String, &'a str);
+impl_from_ref!(ValueRef::U8List, &'a [u8]);
+impl_from_ref!(ValueRef::I32List, &'a [i32]);
+impl_from_ref!(ValueRef::I64List, &'a [i64]);
+impl_from_ref!(ValueRef::F64List, &'a [f64]);
+impl_from_ref!(ValueRef::F32List, &'a [f32]);
+impl_from_ref!(ValueRef::List, Vec<ValueRef<'a>>);
+impl_from_ref!(ValueRef::Map, Vec<(ValueRef<'a>, ValueRef<'a>)>);
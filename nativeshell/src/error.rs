@@ -8,6 +8,13 @@ pub enum Error {
     Platform(PlatformError),
     Value(ValueError),
     InvalidMenuHandle,
+    InvalidPixelBufferSize,
+    UnknownPlatformViewType,
+    InvalidPlatformViewHandle,
+    InvalidWindowHandle,
+    WindowClosed,
+    InstanceAlreadyRunning,
+    InvalidPluginName,
 }
 
 impl Display for Error {
@@ -21,6 +28,49 @@ impl Display for Error {
             Error::InvalidMenuHandle => {
                 write!(f, "Provided menu handle does not match any known menu")
             }
+            Error::InvalidPixelBufferSize => {
+                write!(f, "Pixel buffer size does not match dimensions and format")
+            }
+            Error::UnknownPlatformViewType => {
+                write!(
+                    f,
+                    "No factory is registered for requested platform view type"
+                )
+            }
+            Error::InvalidPlatformViewHandle => {
+                write!(f, "Provided handle does not match any platform view")
+            }
+            Error::InvalidWindowHandle => {
+                write!(f, "Provided handle does not match any window")
+            }
+            Error::WindowClosed => {
+                write!(f, "Target window was closed before replying")
+            }
+            Error::InstanceAlreadyRunning => {
+                write!(f, "Another instance of the application is already running")
+            }
+            Error::InvalidPluginName => {
+                write!(f, "Plugin name must not contain NUL characters")
+            }
+        }
+    }
+}
+
+impl Error {
+    // Stable error code reported to Dart
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::InvalidEngineHandle => "invalid-engine-handle",
+            Error::Platform(error) => error.code(),
+            Error::Value(_) => "invalid-value",
+            Error::InvalidMenuHandle => "invalid-menu-handle",
+            Error::InvalidPixelBufferSize => "invalid-pixel-buffer-size",
+            Error::UnknownPlatformViewType => "unknown-platform-view-type",
+            Error::InvalidPlatformViewHandle => "invalid-platform-view-handle",
+            Error::InvalidWindowHandle => "invalid-window-handle",
+            Error::WindowClosed => "window-closed",
+            Error::InstanceAlreadyRunning => "instance-already-running",
+            Error::InvalidPluginName => "invalid-plugin-name",
         }
     }
 }
@@ -0,0 +1,95 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    codec::{value::to_value, MethodCall, MethodCallError, MethodCallReply, Value},
+    util::OkLog,
+    Result,
+};
+
+use super::{
+    api_constants::*, api_model::AccessibilitySettings,
+    platform::accessibility::PlatformAccessibility, Context, EngineHandle, WindowMethodCallResult,
+};
+
+#[cfg_attr(feature = "mock", allow(dead_code))]
+pub struct AccessibilityManager {
+    context: Rc<Context>,
+    platform_accessibility: PlatformAccessibility,
+    last_settings: RefCell<Option<AccessibilitySettings>>,
+}
+
+impl AccessibilityManager {
+    pub(super) fn new(context: Rc<Context>) -> Self {
+        let context_copy = context.clone();
+        context
+            .message_manager
+            .borrow_mut()
+            .register_method_handler(channel::ACCESSIBILITY, move |value, reply, engine| {
+                context_copy
+                    .accessibility_manager
+                    .borrow()
+                    .on_method_call(value, reply, engine);
+            });
+
+        Self {
+            context: context.clone(),
+            platform_accessibility: PlatformAccessibility::new(context),
+            last_settings: RefCell::new(None),
+        }
+    }
+
+    pub fn get_settings(&self) -> Result<AccessibilitySettings> {
+        let settings = self.platform_accessibility.get_settings()?;
+        self.last_settings.replace(Some(settings.clone()));
+        Ok(settings)
+    }
+
+    // Called by platform when system settings (possibly) changed; Engines are only
+    // notified if accessibility settings actually differ from last known values
+    #[cfg_attr(feature = "mock", allow(dead_code))]
+    pub(crate) fn settings_changed(&self) {
+        let previous = self.last_settings.borrow().clone();
+        let settings = match self.get_settings().ok_log() {
+            Some(settings) => settings,
+            None => return,
+        };
+        if previous.as_ref() == Some(&settings) {
+            return;
+        }
+        let engines = self.context.engine_manager.borrow().get_all_engines();
+        let message_manager = self.context.message_manager.borrow();
+        for engine in engines {
+            if let Some(invoker) =
+                message_manager.get_method_invoker(engine, channel::ACCESSIBILITY)
+            {
+                invoker
+                    .call_method(
+                        method::accessibility::SETTINGS_CHANGED.into(),
+                        to_value(&settings).unwrap(),
+                        |_| {},
+                    )
+                    .ok_log();
+            }
+        }
+    }
+
+    fn map_result<T>(result: Result<T>) -> WindowMethodCallResult
+    where
+        T: serde::Serialize,
+    {
+        result
+            .map(|v| to_value(v).unwrap())
+            .map_err(MethodCallError::from_error)
+    }
+
+    fn on_method_call(
+        &self,
+        call: MethodCall<Value>,
+        reply: MethodCallReply<Value>,
+        _engine: EngineHandle,
+    ) {
+        if call.method.as_str() == method::accessibility::GET_SETTINGS {
+            reply.send(Self::map_result(self.get_settings()));
+        }
+    }
+}
@@ -8,10 +8,62 @@ pub(crate) mod channel {
         pub const WINDOW_MANAGER: &str = ".window.window-manager";
         pub const DROP_TARGET: &str = ".window.drop-target";
         pub const DRAG_SOURCE: &str = ".window.drag-source";
+        pub const TOUCH_BAR: &str = ".window.touch-bar";
+        pub const TABLET: &str = ".window.tablet";
+        pub const QUIT: &str = ".window.quit";
     }
 
     // Flutter channel for mananing platform menus
     pub const MENU_MANAGER: &str = "nativeshell/menu-manager";
+
+    // Flutter channel for system-wide (global) event monitoring
+    pub const EVENT_TAP: &str = "nativeshell/event-tap";
+
+    // Flutter channel for querying accessibility related system preferences
+    pub const ACCESSIBILITY: &str = "nativeshell/accessibility";
+
+    // Flutter channel for querying system appearance (dark mode, accent color)
+    pub const SYSTEM_THEME: &str = "nativeshell/system-theme";
+
+    // Flutter channel for querying display geometry and color capabilities
+    pub const DISPLAY: &str = "nativeshell/display";
+
+    // Flutter channel for querying battery and power source status
+    pub const POWER: &str = "nativeshell/power";
+
+    // Flutter channel for enumerating windows of other applications
+    pub const WINDOW_LIST: &str = "nativeshell/window-list";
+
+    // Flutter channel for querying regional preferences (locale, time format)
+    pub const LOCALE: &str = "nativeshell/locale";
+
+    // Flutter channel for querying user activity (idle time)
+    pub const USER_ACTIVITY: &str = "nativeshell/user-activity";
+
+    // Flutter channel for publish/subscribe messaging between engines
+    pub const MESSAGE_BUS: &str = "nativeshell/message-bus";
+
+    // Flutter channel for transferring large payloads in chunks
+    pub const TRANSFER: &str = "nativeshell/transfer";
+
+    // Flutter channel for application wide events
+    pub const APPLICATION: &str = "nativeshell/application";
+
+    // Flutter channel for opening URLs and files with default applications
+    pub const WORKSPACE: &str = "nativeshell/workspace";
+
+    // Flutter channel for system sounds and haptic feedback
+    pub const FEEDBACK: &str = "nativeshell/feedback";
+
+    // Flutter channel for querying and requesting privacy permissions
+    pub const PERMISSION: &str = "nativeshell/permission";
+
+    // Flutter channel for application lifecycle state (StringCodec)
+    pub const FLUTTER_LIFECYCLE: &str = "flutter/lifecycle";
+
+    // Not handled on Dart side; Reply (null) confirms that all messages sent
+    // to the engine before have been processed
+    pub const ENGINE_FLUSH: &str = "nativeshell/engine-flush";
 }
 
 pub const CURRENT_API_VERSION: i32 = 1;
@@ -33,6 +85,10 @@ pub(crate) mod method {
         // Request to show the window (may be delayed until window itself calls readyToShow)
         pub const SHOW: &str = "Window.show";
 
+        // Shows window next to screen rect (i.e. tray icon); Argument is
+        // ShowPopoverRequest
+        pub const SHOW_POPOVER: &str = "Window.showPopover";
+
         // Request to show the window modally (will return result after window closes)
         pub const SHOW_MODAL: &str = "Window.showModal";
 
@@ -42,6 +98,9 @@ pub(crate) mod method {
         // Hide the window
         pub const HIDE: &str = "Window.hide";
 
+        // Brings window to front (i.e. before showing quit confirmation prompt)
+        pub const ACTIVATE: &str = "Window.activate";
+
         // Close the window; This will terminate the isolate
         pub const CLOSE: &str = "Window.close";
 
@@ -53,19 +112,84 @@ pub(crate) mod method {
         pub const GET_GEOMETRY: &str = "Window.getGeometry";
         pub const SUPPORTED_GEOMETRY: &str = "Window.supportedGeometry";
 
+        // Argument is WindowGeometryRequest; Returns WindowGeometryUpdate with
+        // resulting geometry, saving separate getGeometry call
+        pub const UPDATE_GEOMETRY: &str = "Window.updateGeometry";
+
         pub const SET_STYLE: &str = "Window.setStyle";
         pub const SET_TITLE: &str = "Window.setTitle";
+
+        // Secondary title line; Shown on MacOS 11+ and in GtkHeaderBar on Linux
+        pub const SET_SUBTITLE: &str = "Window.setSubtitle";
+
+        // Path of document represented by the window or null; MacOS shows proxy
+        // icon in title bar, other platforms ignore it
+        pub const SET_REPRESENTED_FILE: &str = "Window.setRepresentedFile";
+
+        // Unsaved changes indicator; Dot in close button on MacOS, asterisk in
+        // window title on other platforms
+        pub const SET_DOCUMENT_EDITED: &str = "Window.setDocumentEdited";
+
+        // Forces light or dark window chrome regardless of system setting;
+        // Argument is WindowAppearance
+        pub const SET_APPEARANCE: &str = "Window.setAppearance";
+
+        // Material shown behind transparent window content (Windows 11);
+        // Argument is WindowBackdrop
+        pub const SET_BACKDROP: &str = "Window.setBackdrop";
+        pub const SET_WINDOW_TYPE: &str = "Window.setWindowType";
+        // Argument is optional DropFilter; null accepts everything
+        pub const SET_DROP_FILTER: &str = "Window.setDropFilter";
         pub const PERFORM_WINDOW_DRAG: &str = "Window.performWindowDrag";
 
         pub const SHOW_POPUP_MENU: &str = "Window.showPopupMenu";
         pub const HIDE_POPUP_MENU: &str = "Window.hidePopupMenu";
 
+        // Shows system share UI; Argument is SharePickerRequest
+        pub const SHOW_SHARE_PICKER: &str = "Window.showSharePicker";
+
+        // MacOS only; Shows (or updates) Quick Look panel for given files;
+        // Argument is QuickLookRequest
+        pub const SHOW_QUICK_LOOK: &str = "Window.showQuickLook";
+        // Argument is index of previewed item
+        pub const SET_QUICK_LOOK_INDEX: &str = "Window.setQuickLookIndex";
+        pub const HIDE_QUICK_LOOK: &str = "Window.hideQuickLook";
+
         // Windows only
         pub const SHOW_SYSTEM_MENU: &str = "Window.showSystemMenu";
 
+        // Windows only; Rect (or null) of maximize button drawn by Dart in window
+        // without title bar. Enables Snap Layouts on Windows 11.
+        pub const SET_MAXIMIZE_BUTTON_RECT: &str = "Window.setMaximizeButtonRect";
+
+        // Windows only; Argument is AppUserModelID string (or null to use process
+        // id). Windows with different ids are grouped separately on taskbar.
+        pub const SET_APP_USER_MODEL_ID: &str = "Window.setAppUserModelId";
+
+        // MacOS only; Whether window is on currently active Space (desktop)
+        pub const IS_ON_ACTIVE_SPACE: &str = "Window.isOnActiveSpace";
+
+        // MacOS only; Moves window from other Space to the active one
+        pub const MOVE_TO_ACTIVE_SPACE: &str = "Window.moveToActiveSpace";
+
         // MacOS only - associates given menu with current windon; The menu will
         // be displayed  when window gets active
         pub const SET_WINDOW_MENU: &str = "Window.setWindowMenu";
+
+        // MacOS only - sets (or clears) the Touch Bar shown while window is active
+        pub const SET_TOUCH_BAR: &str = "Window.setTouchBar";
+
+        // Native views embedded in window content; Views are created by factories
+        // registered with PlatformViewManager
+        pub const CREATE_PLATFORM_VIEW: &str = "Window.createPlatformView";
+        pub const UPDATE_PLATFORM_VIEW: &str = "Window.updatePlatformView";
+        pub const DISPOSE_PLATFORM_VIEW: &str = "Window.disposePlatformView";
+
+        // Additional Flutter views, each running own engine, embedded in window
+        // content; Returns engine handle, which identifies the view
+        pub const ATTACH_ENGINE_VIEW: &str = "Window.attachEngineView";
+        pub const UPDATE_ENGINE_VIEW: &str = "Window.updateEngineView";
+        pub const DETACH_ENGINE_VIEW: &str = "Window.detachEngineView";
     }
 
     pub mod drop_target {
@@ -79,11 +203,251 @@ pub(crate) mod method {
         pub const DRAG_SESSION_ENDED: &str = "DragSource.dragSessionEnded";
     }
 
+    pub mod touch_bar {
+        // Invoked when user interacts with touch bar item
+        pub const ON_ACTION: &str = "TouchBar.onAction";
+    }
+
+    pub mod tablet {
+        // Pen proximity, contact and movement within window
+        pub const ON_EVENT: &str = "Tablet.onEvent";
+    }
+
+    pub mod quit {
+        // Asks window whether application may quit; Argument is
+        // TerminationReason, returns false to cancel
+        pub const CONFIRM: &str = "Quit.confirm";
+    }
+
     pub mod menu {
         pub const CREATE_OR_UPDATE: &str = "Menu.createOrUpdate";
         pub const DESTROY: &str = "Menu.destroy";
         pub const ON_ACTION: &str = "Menu.onAction";
         pub const SET_APP_MENU: &str = "Menu.setAppMenu";
+
+        // Activates next (previous) window in window menu order
+        pub const SELECT_NEXT_WINDOW: &str = "Menu.selectNextWindow";
+        pub const SELECT_PREVIOUS_WINDOW: &str = "Menu.selectPreviousWindow";
+
+        // Brings all application windows in front of other applications
+        pub const BRING_ALL_TO_FRONT: &str = "Menu.bringAllToFront";
+
+        // Reports accelerator conflicts to engine that owns the menu
+        pub const ON_DIAGNOSTIC: &str = "Menu.onDiagnostic";
+    }
+
+    pub mod event_tap {
+        // Returns whether the application is allowed to monitor global events
+        pub const GET_PERMISSION: &str = "EventTap.getPermission";
+
+        // Asks user for permission (if applicable); May show system prompt
+        pub const REQUEST_PERMISSION: &str = "EventTap.requestPermission";
+
+        // Starts delivering global events to calling engine
+        pub const START: &str = "EventTap.start";
+        pub const STOP: &str = "EventTap.stop";
+
+        // Delivered to engines that started the event tap
+        pub const ON_EVENT: &str = "EventTap.onEvent";
+    }
+
+    pub mod system_theme {
+        pub const GET_SETTINGS: &str = "SystemTheme.getSettings";
+
+        // Rust -> Dart; Argument is SystemThemeSettings
+        pub const SETTINGS_CHANGED: &str = "SystemTheme.settingsChanged";
+    }
+
+    pub mod accessibility {
+        pub const GET_SETTINGS: &str = "Accessibility.getSettings";
+
+        // Delivered to all engines when any of the settings changes
+        pub const SETTINGS_CHANGED: &str = "Accessibility.settingsChanged";
+    }
+
+    pub mod display {
+        // Returns list of DisplayInfo
+        pub const GET_DISPLAYS: &str = "Display.getDisplays";
+
+        // Delivered to all engines with list of DisplayInfo when displays are
+        // added, removed or their configuration (including color space) changes
+        pub const DISPLAYS_CHANGED: &str = "Display.displaysChanged";
+    }
+
+    pub mod power {
+        // Returns PowerStatus
+        pub const GET_STATUS: &str = "Power.getStatus";
+
+        // Delivered to all engines with PowerStatus when power source, battery
+        // level or low power mode changes
+        pub const STATUS_CHANGED: &str = "Power.statusChanged";
+    }
+
+    pub mod window_list {
+        // WindowListRequest -> Vec<ExternalWindow>; Windows of current
+        // application are not included
+        pub const GET_WINDOWS: &str = "WindowList.getWindows";
+    }
+
+    pub mod locale {
+        pub const GET_SETTINGS: &str = "Locale.getSettings";
+
+        // Rust -> Dart; Argument is LocaleSettings
+        pub const SETTINGS_CHANGED: &str = "Locale.settingsChanged";
+    }
+
+    pub mod user_activity {
+        // Returns seconds since last user input
+        pub const GET_IDLE_TIME: &str = "UserActivity.getIdleTime";
+    }
+
+    pub mod application {
+        // Delivered to all engines when application was launched again while
+        // single instance was enforced; Argument is InstanceActivation
+        pub const ON_SECOND_INSTANCE: &str = "Application.onSecondInstance";
+
+        // Dart -> Rust; Engine is ready to handle open requests. Requests received
+        // before first engine is ready (i.e. during launch) are then delivered
+        pub const READY: &str = "Application.ready";
+
+        // Rust -> Dart; Delivered to ready engines with list of URLs
+        pub const OPEN_URLS: &str = "Application.openUrls";
+
+        // Rust -> Dart; Delivered to ready engines with list of file paths
+        pub const OPEN_FILES: &str = "Application.openFiles";
+
+        // Rust -> Dart; Delivered to ready engines with JumpListActivation
+        pub const OPEN_JUMP_LIST_ITEM: &str = "Application.openJumpListItem";
+
+        // Rust -> Dart; Delivered to ready engines with TerminationReason when
+        // application or user session is about to end; Returns false to cancel
+        pub const SHOULD_TERMINATE: &str = "Application.shouldTerminate";
+
+        // Dart -> Rust; Argument is SetPreventSuddenTerminationRequest
+        pub const SET_PREVENT_SUDDEN_TERMINATION: &str = "Application.setPreventSuddenTermination";
+
+        // Dart -> Rust; Returns whether application starts when user logs in
+        pub const GET_LAUNCH_AT_LOGIN: &str = "Application.getLaunchAtLogin";
+
+        // Dart -> Rust; Argument is SetLaunchAtLoginRequest
+        pub const SET_LAUNCH_AT_LOGIN: &str = "Application.setLaunchAtLogin";
+
+        // Dart -> Rust; Returns whether application was started at login with
+        // start hidden flag
+        pub const STARTED_HIDDEN: &str = "Application.startedHidden";
+
+        // Dart -> Rust; Ends launch feedback for application without windows
+        pub const NOTIFY_STARTUP_COMPLETE: &str = "Application.notifyStartupComplete";
+
+        // Dart -> Rust; Argument is JumpList (Windows only)
+        pub const SET_JUMP_LIST: &str = "Application.setJumpList";
+
+        // Dart -> Rust; Argument is AppUserModelID string (Windows only)
+        pub const SET_APP_USER_MODEL_ID: &str = "Application.setAppUserModelId";
+
+        // Dart -> Rust; Argument is UserActivity (or null to stop publishing)
+        pub const SET_USER_ACTIVITY: &str = "Application.setUserActivity";
+
+        // Rust -> Dart; Delivered to ready engines with UserActivity continued
+        // from another device (MacOS only)
+        pub const CONTINUE_USER_ACTIVITY: &str = "Application.continueUserActivity";
+
+        // Dart -> Rust; Returns ActivationPolicy
+        pub const GET_ACTIVATION_POLICY: &str = "Application.getActivationPolicy";
+
+        // Dart -> Rust; Argument is ActivationPolicy
+        pub const SET_ACTIVATION_POLICY: &str = "Application.setActivationPolicy";
+
+        // Dart -> Rust; Asks windows and engines for confirmation and quits
+        // application if all agree. Returns false if quit was cancelled
+        pub const REQUEST_QUIT: &str = "Application.requestQuit";
+
+        // Dart -> Rust; Hides all application windows (macOS only)
+        pub const HIDE: &str = "Application.hide";
+
+        // Dart -> Rust; Shows windows of hidden application
+        pub const UNHIDE: &str = "Application.unhide";
+
+        // Dart -> Rust; Hides windows of all other applications
+        pub const HIDE_OTHER_APPLICATIONS: &str = "Application.hideOtherApplications";
+
+        // Rust -> Dart; Delivered to all engines after application was hidden
+        pub const ON_HIDE: &str = "Application.onHide";
+
+        // Rust -> Dart; Delivered to all engines after application was unhidden
+        pub const ON_UNHIDE: &str = "Application.onUnhide";
+    }
+
+    pub mod workspace {
+        // Argument is URL
+        pub const OPEN_URL: &str = "Workspace.openUrl";
+
+        // Argument is path of file or directory
+        pub const OPEN_PATH: &str = "Workspace.openPath";
+
+        // Argument is path of file or directory
+        pub const REVEAL_IN_FILE_MANAGER: &str = "Workspace.revealInFileManager";
+
+        // Argument is list of paths; Returns list of locations in trash (null
+        // when not known)
+        pub const TRASH_ITEMS: &str = "Workspace.trashItems";
+
+        // Argument is HandlerTarget; Returns DefaultHandler or null when there
+        // is no application registered
+        pub const GET_DEFAULT_HANDLER: &str = "Workspace.getDefaultHandler";
+
+        // Argument is HandlerTarget; Asks system to make this application the
+        // default handler. Depending on platform this may show confirmation
+        // prompt or open system settings instead.
+        pub const SET_AS_DEFAULT_HANDLER: &str = "Workspace.setAsDefaultHandler";
+    }
+
+    pub mod feedback {
+        pub const BEEP: &str = "Feedback.beep";
+
+        // Argument is name of system sound (NSSound name on macOS, sound alias
+        // on Windows, freedesktop sound theme event id on Linux)
+        pub const PLAY_SYSTEM_SOUND: &str = "Feedback.playSystemSound";
+
+        // Argument is HapticFeedbackPattern (macOS only)
+        pub const PERFORM_HAPTIC_FEEDBACK: &str = "Feedback.performHapticFeedback";
+    }
+
+    pub mod permission {
+        // Argument is PermissionKind; Returns PermissionStatus
+        pub const GET_STATUS: &str = "Permission.getStatus";
+
+        // Argument is PermissionKind; May show system prompt. Returns
+        // PermissionStatus once user responds (camera and microphone) or
+        // immediately otherwise
+        pub const REQUEST: &str = "Permission.request";
+    }
+
+    pub mod message_bus {
+        // Starts or stops delivering messages for given topic to calling engine
+        pub const SUBSCRIBE: &str = "MessageBus.subscribe";
+        pub const UNSUBSCRIBE: &str = "MessageBus.unsubscribe";
+
+        // Delivers message to all other engines subscribed to the topic
+        pub const PUBLISH: &str = "MessageBus.publish";
+
+        // Delivered to subscribed engines
+        pub const ON_MESSAGE: &str = "MessageBus.onMessage";
+    }
+
+    pub mod transfer {
+        // Dart -> Rust; Begin returns transfer identifier. Reply to chunk is
+        // delayed until the receiver processed the chunk
+        pub const BEGIN: &str = "Transfer.begin";
+        pub const CHUNK: &str = "Transfer.chunk";
+        pub const END: &str = "Transfer.end";
+        pub const CANCEL: &str = "Transfer.cancel";
+
+        // Rust -> Dart; Next chunk is sent after Dart replied to previous one
+        pub const ON_BEGIN: &str = "Transfer.onBegin";
+        pub const ON_CHUNK: &str = "Transfer.onChunk";
+        pub const ON_END: &str = "Transfer.onEnd";
+        pub const ON_CANCEL: &str = "Transfer.onCancel";
     }
 
     pub mod menu_bar {
@@ -107,12 +471,33 @@ pub(crate) mod event {
 
         // Delivered when window is actually closed
         pub const CLOSE: &str = "event:Window.close";
+
+        // Delivered when pointer entered or left window content area. Events always come
+        // in pairs, spurious platform enter/leave notifications are filtered out
+        pub const POINTER_ENTERED: &str = "event:Window.pointerEntered";
+        pub const POINTER_EXITED: &str = "event:Window.pointerExited";
+
+        // Delivered when user starts dragging title bar proxy icon of represented
+        // file (MacOS only); Argument is the file path
+        pub const REPRESENTED_FILE_DRAGGED: &str = "event:Window.representedFileDragged";
+
+        // Delivered when user switched to another Space (MacOS only); Argument
+        // is whether the window is on the newly active Space
+        pub const ACTIVE_SPACE_CHANGED: &str = "event:Window.activeSpaceChanged";
+
+        // Delivered when window could not be shown after it was ready to show;
+        // Argument is the error (code, message and details)
+        pub const SHOW_FAILED: &str = "event:Window.showFailed";
     }
 }
 
 pub(crate) mod drag_data {
+    // Only files are used outside of platform drag data adapters
+    #[cfg_attr(feature = "mock", allow(dead_code))]
     pub mod key {
         pub const FILES: &str = "drag-data:internal:files";
         pub const URLS: &str = "drag-data:internal:urls";
+        pub const TEXT: &str = "drag-data:internal:text";
+        pub const HTML: &str = "drag-data:internal:html";
     }
 }
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use crate::codec::Value;
 
-use super::{MenuHandle, Point, Rect, Size};
+use super::{EngineHandle, MenuHandle, PlatformViewHandle, Point, Rect, Size};
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -104,6 +104,12 @@ pub struct PopupMenuRequest {
     // Windows only, first item will be pre-selected; Use during keyboard navigation
     // in menubar
     pub preselect_first: bool,
+
+    // Menu opened from keyboard (context menu key or accessibility action);
+    // Menu is placed below the rect of focused element instead of at position
+    // and first item is pre-selected on all platforms
+    #[serde(default)]
+    pub keyboard_anchor: Option<Rect>,
 }
 
 #[derive(serde::Serialize, Debug, Clone)]
@@ -146,6 +152,15 @@ impl Default for WindowGeometryFlags {
     }
 }
 
+// Result of setting geometry and reading it back in single call
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowGeometryUpdate {
+    // Fields of request that were applied
+    pub applied: WindowGeometryFlags,
+    pub geometry: WindowGeometry,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct DragData {
@@ -173,6 +188,41 @@ pub struct DragResult {
     pub effect: DragEffect,
 }
 
+// File types accepted by window drop targets; Drags that don't match are
+// rejected natively without consulting Dart code
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DropFilter {
+    // Case insensitive, without leading dot
+    pub extensions: Vec<String>,
+    // i.e. "image/png" or "image/*"; Linux and Windows only
+    pub mime_types: Vec<String>,
+    // Uniform type identifiers, i.e. "public.image"; macOS only
+    pub utis: Vec<String>,
+    // Whether drags without files (text, URLs) are accepted
+    pub allow_non_file_data: bool,
+}
+
+// Serialized as single key map, i.e. {"urlScheme": "mailto"}
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum HandlerTarget {
+    UrlScheme(String),
+    // Without leading dot
+    FileExtension(String),
+}
+
+// Application registered as default handler for URL scheme or file type
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DefaultHandler {
+    pub name: String,
+    // Bundle identifier (macOS), executable path (Windows) or desktop file id
+    // (Linux)
+    pub id: String,
+    pub is_current_application: bool,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ImageData {
@@ -215,6 +265,145 @@ pub struct WindowStyle {
     pub can_minimize: bool,
     pub can_maximize: bool,
     pub can_full_screen: bool,
+    // Linux only; Regular frame uses client-side GtkHeaderBar instead of window
+    // manager decorations
+    #[serde(default)]
+    pub header_bar: bool,
+}
+
+// Applied when window is created, before the window is shown for the first time
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WindowOptions {
+    pub title: Option<String>,
+    pub style: Option<WindowStyle>,
+    pub geometry: Option<WindowGeometryRequest>,
+    // Show window as soon as first frame is rendered
+    pub visible: bool,
+    // Show window as modal for its parent as soon as first frame is rendered
+    pub modal: bool,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum PopoverEdge {
+    // Menu bar on macOS, top panel on Linux
+    #[default]
+    Below,
+    // Taskbar at bottom of screen on Windows
+    Above,
+}
+
+// Shows window horizontally centered next to anchor (i.e. frame of tray icon)
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ShowPopoverRequest {
+    // In screen coordinates
+    pub anchor: Rect,
+    #[serde(default)]
+    pub edge: PopoverEdge,
+    // Hide window when it loses focus
+    #[serde(default)]
+    pub transient: bool,
+}
+
+// Content of window that is not backed by Flutter engine
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NativeWindowContent {
+    // 0xAARRGGBB; Alpha requires window without frame
+    pub background_color: u32,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SharePickerRequest {
+    pub text: Option<String>,
+    pub urls: Vec<String>,
+    // Paths of shared files
+    pub files: Vec<String>,
+    // Windows only; Shown in share UI, which requires a title
+    pub title: Option<String>,
+    // Picker is shown next to this rect (in window coordinates) on macOS
+    pub anchor: Rect,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickLookItem {
+    pub path: String,
+    // Position of item in window coordinates; Panel zooms from this rect
+    pub rect: Option<Rect>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QuickLookRequest {
+    pub items: Vec<QuickLookItem>,
+    pub current_index: usize,
+}
+
+// Force touch trackpad feedback; Matches NSHapticFeedbackPattern
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum HapticFeedbackPattern {
+    Generic,
+    // Dragged object snapped to guide or another object
+    Alignment,
+    // Value crossed discrete level (i.e. slider detent)
+    LevelChange,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum PermissionKind {
+    ScreenRecording,
+    // Required for monitoring key events and controlling other applications
+    Accessibility,
+    InputMonitoring,
+    Camera,
+    Microphone,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum PermissionStatus {
+    Granted,
+    Denied,
+    // User has not been asked yet
+    NotDetermined,
+    // Blocked by system policy (i.e. parental controls or MDM); User can not
+    // change this
+    Restricted,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum WindowAppearance {
+    // Follows system setting
+    System,
+    Light,
+    Dark,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum WindowBackdrop {
+    None,
+    Mica,
+    Acrylic,
+}
+
+// Hint for window manager on how to treat the window (_NET_WM_WINDOW_TYPE)
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum WindowType {
+    Normal,
+    Dialog,
+    Splash,
+    Dock,
+    Tooltip,
+    Notification,
 }
 
 //
@@ -240,7 +429,7 @@ pub enum MenuRole {
     Services,
 }
 
-#[derive(serde::Serialize, serde::Deserialize, Default, Debug)]
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Accelerator {
     pub label: String,
@@ -250,6 +439,51 @@ pub struct Accelerator {
     pub control: bool,
 }
 
+impl Accelerator {
+    // Parses description such as "Ctrl+Alt+Delete"; Meta key can also be
+    // written as Cmd, Win or Super
+    pub fn parse(description: &str) -> Self {
+        let mut res = Accelerator::default();
+        let mut parts: Vec<&str> = description.split('+').collect();
+        res.label = parts.pop().unwrap_or_default().into();
+        for modifier in parts {
+            match modifier {
+                "Ctrl" => res.control = true,
+                "Alt" => res.alt = true,
+                "Shift" => res.shift = true,
+                "Meta" | "Cmd" | "Win" | "Super" => res.meta = true,
+                _ => {}
+            }
+        }
+        res
+    }
+
+    pub fn description(&self) -> String {
+        let mut res = String::new();
+        for (pressed, name) in [
+            (self.control, "Ctrl+"),
+            (self.alt, "Alt+"),
+            (self.shift, "Shift+"),
+            (self.meta, "Meta+"),
+        ] {
+            if pressed {
+                res.push_str(name);
+            }
+        }
+        res.push_str(&self.label);
+        res
+    }
+
+    // Labels are compared case insensitively (Dart uses upper case labels)
+    pub fn same_as(&self, other: &Accelerator) -> bool {
+        self.label.to_lowercase() == other.label.to_lowercase()
+            && self.alt == other.alt
+            && self.shift == other.shift
+            && self.meta == other.meta
+            && self.control == other.control
+    }
+}
+
 #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub enum CheckStatus {
@@ -266,7 +500,7 @@ impl Default for CheckStatus {
     }
 }
 
-#[derive(serde::Serialize, serde::Deserialize, Default, Debug)]
+#[derive(serde::Serialize, serde::Deserialize, Default, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct MenuItem {
     pub id: i64,
@@ -285,12 +519,34 @@ impl PartialEq for MenuItem {
     }
 }
 
-#[derive(serde::Deserialize, Default, Debug)]
+#[derive(serde::Deserialize, Default, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Menu {
     pub role: Option<MenuRole>,
     pub items: Vec<MenuItem>,
 }
+#[derive(serde::Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum MenuDiagnosticKind {
+    // Multiple items in same menu tree use the accelerator
+    DuplicateAccelerator,
+    // Accelerator is handled by the system and may never reach the application
+    SystemShortcut,
+}
+
+#[derive(serde::Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MenuDiagnostic {
+    pub kind: MenuDiagnosticKind,
+    // Root of the menu tree
+    pub menu: MenuHandle,
+    pub accelerator: Accelerator,
+    // Affected items as paths of titles (i.e. "File > Save")
+    pub items: Vec<String>,
+    pub system_shortcut: Option<String>,
+    pub message: String,
+}
+
 #[derive(serde::Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct MenuCreateRequest {
@@ -315,3 +571,577 @@ pub struct MenuAction {
 pub struct SetMenuRequest {
     pub handle: Option<MenuHandle>,
 }
+
+//
+// Touch Bar
+//
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum TouchBarItemType {
+    Button,
+    Slider,
+    Scrubber,
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TouchBarItem {
+    pub id: i64,
+    pub item_type: TouchBarItemType,
+    pub title: String,
+    pub enabled: bool,
+
+    // Slider only
+    pub min_value: f64,
+    pub max_value: f64,
+    pub value: f64,
+
+    // Scrubber only
+    pub scrubber_items: Vec<String>,
+    pub selected_index: Option<i64>,
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TouchBar {
+    pub items: Vec<TouchBarItem>,
+}
+
+#[derive(serde::Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SetTouchBarRequest {
+    pub touch_bar: Option<TouchBar>,
+}
+
+#[derive(serde::Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TouchBarAction {
+    pub id: i64,
+
+    // Slider value or selected scrubber index
+    pub value: Option<f64>,
+    pub selected_index: Option<i64>,
+}
+
+//
+// Event Tap
+//
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum EventTapPermission {
+    Granted,
+    Denied,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EventTapRequest {
+    pub key_events: bool,
+    pub mouse_events: bool,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum EventTapEventType {
+    KeyDown,
+    KeyUp,
+    MouseDown,
+    MouseUp,
+    MouseMove,
+    ScrollWheel,
+}
+
+#[derive(serde::Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EventTapEvent {
+    pub event_type: EventTapEventType,
+
+    // Platform specific key code (virtual key code on Windows, kVK on macOS)
+    pub key_code: Option<i64>,
+
+    // 0 - primary, 1 - secondary, 2 - middle, other values are platform specific
+    pub button: Option<i64>,
+
+    // Screen position in logical coordinates (top left origin)
+    pub position: Option<Point>,
+
+    pub scroll_delta: Option<Point>,
+}
+
+impl EventTapEvent {
+    pub fn is_key_event(&self) -> bool {
+        matches!(
+            self.event_type,
+            EventTapEventType::KeyDown | EventTapEventType::KeyUp
+        )
+    }
+}
+
+//
+// Accessibility
+//
+
+#[derive(serde::Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessibilitySettings {
+    pub full_keyboard_access: bool,
+    pub reduce_motion: bool,
+    pub reduce_transparency: bool,
+    pub high_contrast: bool,
+
+    // Cursor scale; 1.0 is the default cursor size
+    pub cursor_size: f64,
+}
+
+impl Default for AccessibilitySettings {
+    fn default() -> Self {
+        Self {
+            full_keyboard_access: false,
+            reduce_motion: false,
+            reduce_transparency: false,
+            high_contrast: false,
+            cursor_size: 1.0,
+        }
+    }
+}
+
+//
+// System theme
+//
+
+#[derive(serde::Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemThemeSettings {
+    pub dark_mode: bool,
+
+    // Colors are 0xAARRGGBB; None if not available on current system
+    pub accent_color: Option<u32>,
+    // Background color of selected text
+    pub highlight_color: Option<u32>,
+
+    // Name of active high contrast theme; None if high contrast is off or not
+    // exposed as theme (macOS)
+    pub high_contrast_theme: Option<String>,
+    // Shell (taskbar, start menu) uses dark theme; Only differs from dark_mode
+    // on Windows
+    pub system_dark_mode: bool,
+    // Windows only
+    pub system_colors: Option<SystemColors>,
+}
+
+// Windows system colors (GetSysColor), which define high contrast palette
+#[derive(serde::Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemColors {
+    pub window: u32,
+    pub window_text: u32,
+    pub highlight: u32,
+    pub highlight_text: u32,
+    pub button_face: u32,
+    pub button_text: u32,
+    pub gray_text: u32,
+    pub hotlight: u32,
+}
+
+//
+// Displays
+//
+
+#[derive(serde::Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayInfo {
+    // Matches ScreenCaptureSource::Display; 0 is the main (primary) display
+    pub index: usize,
+    // Logical coordinates, top left origin
+    pub frame: Rect,
+    pub scale_factor: f64,
+
+    // Localized name of display color space (i.e. "Display P3"), if known
+    pub color_space: Option<String>,
+    #[serde(with = "serde_bytes")]
+    pub icc_profile: Option<Vec<u8>>,
+
+    // Largest color component value display can show, where 1.0 is SDR white;
+    // Greater than 1.0 for HDR (EDR) capable displays
+    pub max_edr_headroom: f64,
+    // Currently available headroom; Changes with display brightness on macOS
+    // without change notification
+    pub current_edr_headroom: f64,
+    pub hdr: bool,
+}
+
+//
+// Power
+//
+
+#[derive(serde::Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum PowerSource {
+    Ac,
+    Battery,
+    Unknown,
+}
+
+#[derive(serde::Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerStatus {
+    pub power_source: PowerSource,
+    // False for desktop computers without battery
+    pub has_battery: bool,
+    // 0.0 to 1.0; None if there is no battery or the level is not known
+    pub battery_level: Option<f64>,
+    pub charging: bool,
+    // Low Power Mode (macOS), battery saver (Windows) or power saver profile
+    // (Linux)
+    pub low_power_mode: bool,
+}
+
+//
+// Window list
+//
+
+#[derive(serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowListRequest {
+    // Maximum thumbnail size in pixels (aspect ratio is preserved); Thumbnails
+    // are not captured when not set
+    pub thumbnail_size: Option<Size>,
+}
+
+// Top level window of another application
+#[derive(serde::Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalWindow {
+    // CGWindowID (macOS), HWND (Windows) or X11 window
+    pub id: i64,
+    // Not available on macOS without screen recording permission
+    pub title: Option<String>,
+    pub owner_name: String,
+    pub owner_pid: i64,
+    // Logical coordinates, top left origin
+    pub frame: Rect,
+    pub thumbnail: Option<ImageData>,
+}
+
+//
+// Locale
+//
+
+#[derive(serde::Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum MeasurementSystem {
+    Metric,
+    // United States customary units
+    Us,
+    // Metric with imperial units for some quantities
+    Uk,
+}
+
+#[derive(serde::Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LocaleSettings {
+    // BCP 47 language tag (i.e. "en-US")
+    pub locale: String,
+    // ISO 3166 country code, if known
+    pub region: Option<String>,
+    // 1 (Monday) to 7 (Sunday)
+    pub first_day_of_week: u32,
+    pub uses_24_hour_clock: bool,
+    pub measurement_system: MeasurementSystem,
+}
+
+//
+// Tablet
+//
+
+#[derive(serde::Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum TabletEventType {
+    ProximityEnter,
+    ProximityLeave,
+    Down,
+    Move,
+    Up,
+}
+
+#[derive(serde::Serialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum TabletPointerType {
+    Unknown,
+    Pen,
+    Eraser,
+    Cursor,
+}
+
+#[derive(serde::Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TabletEvent {
+    pub event_type: TabletEventType,
+    pub pointer_type: TabletPointerType,
+
+    // Identifies the tool for the duration of its proximity
+    pub device_id: i64,
+
+    // Position in window content coordinates; Not available for proximity events
+    // on some platforms
+    pub position: Option<Point>,
+
+    // 0.0 - 1.0
+    pub pressure: f64,
+
+    // -1.0 - 1.0 for each axis; positive values tilt right and towards the user
+    pub tilt: Point,
+
+    // Rotation in degrees
+    pub rotation: f64,
+
+    // Barrel buttons (1 - lower button, 2 - upper button)
+    pub buttons: i64,
+}
+
+//
+// Pointer state
+//
+
+#[derive(serde::Serialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyboardModifiers {
+    pub shift: bool,
+    pub control: bool,
+    pub alt: bool,
+    // Command key on macOS, Windows key on Windows, Super on Linux
+    pub meta: bool,
+}
+
+#[derive(serde::Serialize, Debug, Clone, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PointerState {
+    // Last known pointer position in window content coordinates; None if no pointer
+    // event was delivered to window yet
+    pub position: Option<Point>,
+
+    // Pressed mouse buttons (1 - primary, 2 - secondary, 4 - middle); Other bits are
+    // platform specific
+    pub buttons: i64,
+
+    pub modifiers: KeyboardModifiers,
+}
+
+//
+// Message bus
+//
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageBusSubscribeRequest {
+    pub topic: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageBusMessage {
+    pub topic: String,
+    pub message: Value,
+
+    // Engine that published the message; None if published from Rust
+    pub source_engine: Option<i64>,
+}
+
+//
+// Transfer
+//
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferBegin {
+    // Only present for transfers initiated by Rust
+    pub transfer_id: Option<i64>,
+
+    // Identifies the receiver
+    pub name: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferEnd {
+    pub transfer_id: i64,
+}
+
+//
+// Platform views
+//
+
+#[derive(serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePlatformViewRequest {
+    pub view_type: String,
+    pub params: Value,
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdatePlatformViewRequest {
+    pub handle: PlatformViewHandle,
+
+    // View frame in logical coordinates relative to window content
+    pub frame: Rect,
+
+    // Visible portion of the view in window coordinates; Parts of the view
+    // outside of clip rect are not shown and do not receive input
+    pub clip: Rect,
+
+    pub visible: bool,
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DisposePlatformViewRequest {
+    pub handle: PlatformViewHandle,
+}
+
+//
+// Engine views
+//
+
+#[derive(serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachEngineViewRequest {
+    // Available to Dart code of the view through WindowState.initData
+    pub init_data: Value,
+    pub entrypoint: Option<String>,
+    #[serde(default)]
+    pub entrypoint_args: Vec<String>,
+
+    // In logical coordinates relative to window content
+    pub frame: Rect,
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateEngineViewRequest {
+    pub engine: EngineHandle,
+    pub frame: Rect,
+    pub visible: bool,
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DetachEngineViewRequest {
+    pub engine: EngineHandle,
+}
+
+//
+// Application
+//
+
+// Arguments of application launch that was redirected to running instance
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceActivation {
+    // Command line arguments without executable path
+    pub arguments: Vec<String>,
+    pub working_directory: String,
+}
+
+impl InstanceActivation {
+    pub fn current() -> Self {
+        Self {
+            arguments: std::env::args().skip(1).collect(),
+            working_directory: std::env::current_dir()
+                .map(|dir| dir.to_string_lossy().into())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+// Activity that can be continued on another device (Handoff)
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct UserActivity {
+    // Must be listed in NSUserActivityTypes of Info.plist
+    pub activity_type: String,
+    pub title: Option<String>,
+    // Opaque to other applications; Encoded with standard codec
+    pub payload: Value,
+    // Opened in browser when continued on device without the application
+    pub webpage_url: Option<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum TerminationReason {
+    // Application was asked to quit (i.e. from application menu or dock)
+    Quit,
+    // User is logging out or the system is shutting down
+    SessionEnd,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SetPreventSuddenTerminationRequest {
+    pub prevent: bool,
+    // Shown to user when logout or shutdown is blocked (Windows, Linux)
+    pub reason: Option<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SetLaunchAtLoginRequest {
+    pub enabled: bool,
+    // Application started at login should not show any window (i.e. tray utility)
+    pub start_hidden: bool,
+}
+
+// Whether application appears in Dock and application switcher (macOS) or in
+// taskbar (Windows, Linux)
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum ActivationPolicy {
+    Regular,
+    // No Dock icon or menu bar, windows can still be activated (tray utility)
+    Accessory,
+    // Application can not be activated; Same as accessory on Windows and Linux
+    Prohibited,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct JumpListItem {
+    // Reported to application when the item is activated
+    pub id: String,
+    pub title: String,
+    // Shown as tooltip
+    pub description: Option<String>,
+    // Additional command line arguments of launched instance
+    pub arguments: Vec<String>,
+    // Path of .ico, .exe or .dll file; Application icon is used if not set
+    pub icon_path: Option<String>,
+    pub icon_index: i32,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct JumpListCategory {
+    pub title: String,
+    pub items: Vec<JumpListItem>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct JumpList {
+    pub tasks: Vec<JumpListItem>,
+    pub categories: Vec<JumpListCategory>,
+    // Categories maintained by the system
+    pub show_recent: bool,
+    pub show_frequent: bool,
+}
+
+// Jump list item that launched (or activated) the application
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct JumpListActivation {
+    pub id: String,
+    pub arguments: Vec<String>,
+}
@@ -0,0 +1,713 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashSet, VecDeque},
+    path::Path,
+    rc::{Rc, Weak},
+};
+
+use crate::{
+    codec::{
+        value::to_value, MethodCall, MethodCallError, MethodCallReply, MethodCallResult, Value,
+    },
+    util::OkLog,
+    Error, Result,
+};
+
+use super::{
+    api_constants::*,
+    api_model::{
+        ActivationPolicy, InstanceActivation, JumpList, JumpListActivation,
+        SetLaunchAtLoginRequest, SetPreventSuddenTerminationRequest, TerminationReason,
+        UserActivity,
+    },
+    platform::{application::PlatformApplication, single_instance::PlatformSingleInstance},
+    Context, EngineHandle, WindowHandle,
+};
+
+// Passed to application launched at login when start hidden flag is set
+// (Windows, Linux)
+pub(crate) const START_HIDDEN_ARGUMENT: &str = "--start-hidden";
+
+// Passed as "--jump-list-item=<id>" followed by item arguments to application
+// launched from jump list (Windows)
+pub(crate) const JUMP_LIST_ITEM_ARGUMENT: &str = "--jump-list-item";
+
+pub struct ApplicationManager {
+    context: Rc<Context>,
+    platform_application: PlatformApplication,
+    single_instance: RefCell<Option<PlatformSingleInstance>>,
+
+    // Engines that signalled they are ready to handle open requests
+    ready_engines: RefCell<HashSet<EngineHandle>>,
+
+    // Open requests (method and argument) received before any engine was ready
+    pending_requests: RefCell<Vec<(&'static str, Value)>>,
+
+    activation_policy: Cell<ActivationPolicy>,
+
+    quit_in_progress: Cell<bool>,
+}
+
+// Collects replies of engines asked whether application may terminate
+struct TerminationReplies {
+    remaining: usize,
+    allowed: bool,
+    reply: Option<Box<dyn FnOnce(bool)>>,
+}
+
+impl ApplicationManager {
+    pub(super) fn new(context: Rc<Context>) -> Self {
+        let context_copy = context.clone();
+        context
+            .message_manager
+            .borrow_mut()
+            .register_method_handler(channel::APPLICATION, move |value, reply, engine| {
+                context_copy
+                    .application_manager
+                    .borrow()
+                    .on_method_call(value, reply, engine);
+            });
+
+        Self {
+            context: context.clone(),
+            platform_application: PlatformApplication::new(context),
+            single_instance: RefCell::new(None),
+            ready_engines: RefCell::new(HashSet::new()),
+            pending_requests: RefCell::new(Vec::new()),
+            activation_policy: Cell::new(ActivationPolicy::Regular),
+            quit_in_progress: Cell::new(false),
+        }
+    }
+
+    // Fails with InstanceAlreadyRunning if activation was forwarded to another
+    // running instance
+    pub(super) fn acquire_single_instance(&self) -> Result<()> {
+        let context = Rc::downgrade(&self.context);
+        let instance = PlatformSingleInstance::acquire(
+            &self.context,
+            &self.instance_id(),
+            &InstanceActivation::current(),
+            Box::new(move |activation| Self::on_second_instance(&context, activation)),
+        )?;
+        match instance {
+            Some(instance) => {
+                self.single_instance.replace(Some(instance));
+                Ok(())
+            }
+            None => Err(Error::InstanceAlreadyRunning),
+        }
+    }
+
+    // URLs and files passed on command line by the shell (Windows, Linux)
+    pub(super) fn process_launch_arguments(&self) {
+        self.process_arguments(&InstanceActivation::current());
+    }
+
+    fn process_arguments(&self, activation: &InstanceActivation) {
+        if let Some(activation) = Self::jump_list_activation(&activation.arguments) {
+            self.deliver_request(
+                method::application::OPEN_JUMP_LIST_ITEM,
+                to_value(&activation).unwrap(),
+            );
+        }
+        let urls = self.urls_from_arguments(&activation.arguments);
+        if !urls.is_empty() {
+            self.open_urls(urls);
+        }
+        if self.context.options.file_arguments {
+            let files = Self::files_from_arguments(activation);
+            if !files.is_empty() {
+                self.open_files(files);
+            }
+        }
+    }
+
+    // Whether the application is registered to start when user logs in
+    pub fn launch_at_login(&self) -> Result<bool> {
+        self.platform_application
+            .launch_at_login(&self.instance_id())
+            .map_err(|e| e.into())
+    }
+
+    // Registers the application to start when user logs in; Application started
+    // hidden should not show any window until activated (i.e. tray utility)
+    pub fn set_launch_at_login(&self, enabled: bool, start_hidden: bool) -> Result<()> {
+        self.platform_application
+            .set_launch_at_login(&self.instance_id(), enabled, start_hidden)
+            .map_err(|e| e.into())
+    }
+
+    // Whether the application was started at login with start hidden flag
+    pub fn started_hidden(&self) -> bool {
+        std::env::args().any(|argument| argument == START_HIDDEN_ARGUMENT)
+            || self.platform_application.started_hidden()
+    }
+
+    // Ends launch feedback (busy cursor) for applications that do not show any
+    // window on startup; Otherwise it ends when first window is shown
+    pub fn notify_startup_complete(&self) {
+        self.platform_application.notify_startup_complete();
+    }
+
+    // Replaces application jump list (taskbar menu on Windows)
+    pub fn set_jump_list(&self, jump_list: &JumpList) -> Result<()> {
+        self.platform_application
+            .set_jump_list(jump_list)
+            .map_err(|e| e.into())
+    }
+
+    // Explicit AppUserModelID of the process (Windows); Determines taskbar grouping
+    // and identity used by jump list and notifications. Should be called before
+    // any window is shown.
+    pub fn set_app_user_model_id(&self, id: &str) -> Result<()> {
+        self.platform_application
+            .set_app_user_model_id(id)
+            .map_err(|e| e.into())
+    }
+
+    // Publishes activity that can be continued on user's other devices (Handoff);
+    // None stops publishing current activity
+    pub fn set_user_activity(&self, activity: Option<UserActivity>) -> Result<()> {
+        self.platform_application
+            .set_user_activity(activity)
+            .map_err(|e| e.into())
+    }
+
+    pub fn activation_policy(&self) -> ActivationPolicy {
+        self.activation_policy.get()
+    }
+
+    // Tray utilities can hide Dock (taskbar) icon while no window is open and
+    // switch back to regular policy when showing a window. On Windows and Linux
+    // the policy is applied to each window, including windows created later.
+    pub fn set_activation_policy(&self, policy: ActivationPolicy) -> Result<()> {
+        self.platform_application.set_activation_policy(policy)?;
+        self.activation_policy.set(policy);
+        let window_manager = self.context.window_manager.borrow();
+        for handle in window_manager.get_all_windows() {
+            if let Some(window) = window_manager.get_window(handle) {
+                window.set_activation_policy(policy).ok_log();
+            }
+        }
+        Ok(())
+    }
+
+    // Hides all windows until application is activated again (macOS only)
+    pub fn hide_application(&self) -> Result<()> {
+        self.platform_application
+            .hide_application()
+            .map_err(|e| e.into())
+    }
+
+    pub fn unhide_application(&self) -> Result<()> {
+        self.platform_application
+            .unhide_application()
+            .map_err(|e| e.into())
+    }
+
+    pub fn hide_other_applications(&self) -> Result<()> {
+        self.platform_application
+            .hide_other_applications()
+            .map_err(|e| e.into())
+    }
+
+    fn instance_id(&self) -> String {
+        let namespace = &self.context.options.app_namespace;
+        if !namespace.is_empty() {
+            return namespace.clone();
+        }
+        std::env::current_exe()
+            .ok()
+            .as_deref()
+            .and_then(Path::file_stem)
+            .map(|name| name.to_string_lossy().into())
+            .unwrap_or_else(|| "nativeshell".into())
+    }
+
+    fn urls_from_arguments(&self, arguments: &[String]) -> Vec<String> {
+        let schemes = &self.context.options.url_schemes;
+        arguments
+            .iter()
+            .filter(|argument| {
+                argument
+                    .split_once(':')
+                    .map(|(scheme, _)| schemes.iter().any(|s| s.eq_ignore_ascii_case(scheme)))
+                    .unwrap_or(false)
+            })
+            .cloned()
+            .collect()
+    }
+
+    fn jump_list_activation(arguments: &[String]) -> Option<JumpListActivation> {
+        let prefix = format!("{}=", JUMP_LIST_ITEM_ARGUMENT);
+        let position = arguments.iter().position(|a| a.starts_with(&prefix))?;
+        Some(JumpListActivation {
+            id: arguments[position][prefix.len()..].into(),
+            arguments: arguments[position + 1..].to_vec(),
+        })
+    }
+
+    // Arguments that are paths to existing files; Relative paths are resolved
+    // against working directory of launched instance
+    fn files_from_arguments(activation: &InstanceActivation) -> Vec<String> {
+        let working_directory = Path::new(&activation.working_directory);
+        activation
+            .arguments
+            .iter()
+            .filter(|argument| !argument.starts_with('-'))
+            .map(|argument| working_directory.join(argument))
+            .filter(|path| path.is_file())
+            .map(|path| path.to_string_lossy().into())
+            .collect()
+    }
+
+    fn on_second_instance(context: &Weak<Context>, activation: InstanceActivation) {
+        let context = match context.upgrade() {
+            Some(context) => context,
+            None => return,
+        };
+        let manager = context.application_manager.borrow();
+        context
+            .window_manager
+            .borrow()
+            .activate_main_window()
+            .ok_log();
+        manager.invoke_on_all_engines(
+            method::application::ON_SECOND_INSTANCE,
+            to_value(&activation).unwrap(),
+        );
+        manager.process_arguments(&activation);
+    }
+
+    // Called by platform (or for command line arguments) with URLs that the
+    // application was asked to open
+    pub(crate) fn open_urls(&self, urls: Vec<String>) {
+        self.deliver_request(method::application::OPEN_URLS, to_value(&urls).unwrap());
+    }
+
+    // Called by platform (or for command line arguments) with paths of documents
+    // that the application was asked to open
+    pub(crate) fn open_files(&self, files: Vec<String>) {
+        self.deliver_request(method::application::OPEN_FILES, to_value(&files).unwrap());
+    }
+
+    // Called by platform with activity handed off from another device
+    #[allow(dead_code)] // only used on macOS
+    pub(crate) fn continue_user_activity(&self, activity: UserActivity) {
+        self.deliver_request(
+            method::application::CONTINUE_USER_ACTIVITY,
+            to_value(&activity).unwrap(),
+        );
+    }
+
+    // Called by platform after application was hidden or unhidden (also when
+    // triggered by system, i.e. from Dock menu)
+    #[allow(dead_code)] // only used on macOS
+    pub(crate) fn hidden_changed(&self, hidden: bool) {
+        let method = if hidden {
+            method::application::ON_HIDE
+        } else {
+            method::application::ON_UNHIDE
+        };
+        self.invoke_on_all_engines(method, Value::Null);
+    }
+
+    // Requests are queued until an engine is ready
+    fn deliver_request(&self, method: &'static str, argument: Value) {
+        let engines = self.ready_engines();
+        if engines.is_empty() {
+            self.pending_requests.borrow_mut().push((method, argument));
+            return;
+        }
+        for engine in engines {
+            self.invoke_on_engine(engine, method, argument.clone());
+        }
+    }
+
+    // Asks windows and engines whether application may quit and terminates it if
+    // all agree; Reply is invoked with false when quit was cancelled.
+    pub fn request_quit<F>(&self, reply: F)
+    where
+        F: FnOnce(bool) + 'static,
+    {
+        if self.quit_in_progress.replace(true) {
+            reply(false);
+            return;
+        }
+        let context = self.context.clone();
+        self.should_terminate(TerminationReason::Quit, move |allowed| {
+            let manager = context.application_manager.borrow();
+            manager.quit_in_progress.set(false);
+            reply(allowed);
+            if allowed {
+                manager.terminate();
+            }
+        });
+    }
+
+    // NSApplication exits the process (without asking again); Elsewhere the run
+    // loop is stopped, so that application returns from RunLoop::run
+    fn terminate(&self) {
+        self.platform_application.terminate();
+        self.context.run_loop.borrow().stop();
+    }
+
+    // Called by platform when application or user session is about to end. Open
+    // windows are asked first, then ready engines; Reply is invoked once all
+    // replied (possibly before this method returns).
+    pub(crate) fn should_terminate<F>(&self, reason: TerminationReason, reply: F)
+    where
+        F: FnOnce(bool) + 'static,
+    {
+        let windows = self.context.window_manager.borrow().get_all_windows();
+        let context = self.context.clone();
+        Self::confirm_quit(
+            self.context.clone(),
+            windows.into(),
+            reason,
+            Box::new(move |allowed| {
+                if allowed {
+                    let manager = context.application_manager.borrow();
+                    manager.ask_engines_to_terminate(reason, reply);
+                } else {
+                    reply(false);
+                }
+            }),
+        );
+    }
+
+    // Windows are asked one after another, so that only one confirmation prompt
+    // (i.e. for unsaved changes) is shown at a time. Stops at first window that
+    // refuses.
+    fn confirm_quit(
+        context: Rc<Context>,
+        mut windows: VecDeque<WindowHandle>,
+        reason: TerminationReason,
+        done: Box<dyn FnOnce(bool)>,
+    ) {
+        let window = match windows.pop_front() {
+            Some(window) => window,
+            None => {
+                done(true);
+                return;
+            }
+        };
+        let invoker = context.window_method_channel.borrow().get_method_invoker(
+            &context.window_manager.borrow(),
+            window,
+            channel::win::QUIT,
+        );
+        let context_copy = context.clone();
+        let next: Box<dyn FnOnce(bool)> = Box::new(move |allowed| {
+            if allowed {
+                Self::confirm_quit(context_copy, windows, reason, done);
+            } else {
+                done(false);
+            }
+        });
+        // Native windows have no Dart side to ask
+        let invoker = match invoker {
+            Some(invoker) => invoker,
+            None => {
+                next(true);
+                return;
+            }
+        };
+        let next = Rc::new(RefCell::new(Some(next)));
+        let next_copy = next.clone();
+        let res = invoker.call_method(
+            method::quit::CONFIRM,
+            to_value(reason).unwrap(),
+            move |result| {
+                // Windows without handler (or closed in the meanwhile) do not
+                // prevent termination
+                let allowed = !matches!(result, Ok(Value::Bool(false)));
+                let next = next_copy.borrow_mut().take();
+                if let Some(next) = next {
+                    next(allowed);
+                }
+            },
+        );
+        if res.is_err() {
+            let next = next.borrow_mut().take();
+            if let Some(next) = next {
+                next(true);
+            }
+        }
+    }
+
+    fn ask_engines_to_terminate<F>(&self, reason: TerminationReason, reply: F)
+    where
+        F: FnOnce(bool) + 'static,
+    {
+        let engines = self.ready_engines();
+        if engines.is_empty() {
+            reply(true);
+            return;
+        }
+        let replies = Rc::new(RefCell::new(TerminationReplies {
+            remaining: engines.len(),
+            allowed: true,
+            reply: Some(Box::new(reply)),
+        }));
+        let argument = to_value(reason).unwrap();
+        for engine in engines {
+            let invoker = self
+                .context
+                .message_manager
+                .borrow()
+                .get_method_invoker(engine, channel::APPLICATION);
+            let replies_copy = replies.clone();
+            let res = invoker.map(|invoker| {
+                invoker.call_method(
+                    method::application::SHOULD_TERMINATE.into(),
+                    argument.clone(),
+                    move |result: MethodCallResult<Value>| {
+                        // Engines that fail to reply do not prevent termination
+                        let allowed = !matches!(result, Ok(Value::Bool(false)));
+                        Self::termination_replied(&replies_copy, allowed);
+                    },
+                )
+            });
+            if !matches!(res, Some(Ok(()))) {
+                Self::termination_replied(&replies, true);
+            }
+        }
+    }
+
+    fn termination_replied(replies: &Rc<RefCell<TerminationReplies>>, allowed: bool) {
+        let reply = {
+            let mut replies = replies.borrow_mut();
+            replies.allowed &= allowed;
+            replies.remaining -= 1;
+            if replies.remaining > 0 {
+                return;
+            }
+            replies.reply.take().map(|reply| (reply, replies.allowed))
+        };
+        if let Some((reply, allowed)) = reply {
+            reply(allowed);
+        }
+    }
+
+    fn ready_engines(&self) -> Vec<EngineHandle> {
+        let ready_engines = self.ready_engines.borrow();
+        self.context
+            .engine_manager
+            .borrow()
+            .get_all_engines()
+            .into_iter()
+            .filter(|engine| ready_engines.contains(engine))
+            .collect()
+    }
+
+    fn engine_ready(&self, engine: EngineHandle) {
+        self.ready_engines.borrow_mut().insert(engine);
+        let requests: Vec<_> = self.pending_requests.borrow_mut().drain(..).collect();
+        for (method, argument) in requests {
+            self.deliver_request(method, argument);
+        }
+    }
+
+    fn invoke_on_engine(&self, engine: EngineHandle, method: &str, argument: Value) {
+        let message_manager = self.context.message_manager.borrow();
+        if let Some(invoker) = message_manager.get_method_invoker(engine, channel::APPLICATION) {
+            invoker
+                .call_method(method.into(), argument, |_| {})
+                .ok_log();
+        }
+    }
+
+    fn invoke_on_all_engines(&self, method: &str, argument: Value) {
+        let engines = self.context.engine_manager.borrow().get_all_engines();
+        for engine in engines {
+            self.invoke_on_engine(engine, method, argument.clone());
+        }
+    }
+
+    fn on_method_call(
+        &self,
+        call: MethodCall<Value>,
+        reply: MethodCallReply<Value>,
+        engine: EngineHandle,
+    ) {
+        match call.method.as_str() {
+            method::application::READY => {
+                self.engine_ready(engine);
+                reply.send(Ok(Value::Null));
+            }
+            method::application::SET_PREVENT_SUDDEN_TERMINATION => {
+                let res = call
+                    .args_as::<SetPreventSuddenTerminationRequest>()
+                    .map_err(Error::from)
+                    .and_then(|request| {
+                        self.platform_application
+                            .set_prevent_sudden_termination(
+                                request.prevent,
+                                request.reason.as_deref(),
+                            )
+                            .map_err(Error::from)
+                    });
+                reply.send(
+                    res.map(|_| Value::Null)
+                        .map_err(MethodCallError::from_error),
+                );
+            }
+            method::application::GET_LAUNCH_AT_LOGIN => {
+                reply.send(
+                    self.launch_at_login()
+                        .map(Value::Bool)
+                        .map_err(MethodCallError::from_error),
+                );
+            }
+            method::application::SET_LAUNCH_AT_LOGIN => {
+                let res = call
+                    .args_as::<SetLaunchAtLoginRequest>()
+                    .map_err(Error::from)
+                    .and_then(|request| {
+                        self.set_launch_at_login(request.enabled, request.start_hidden)
+                    });
+                reply.send(
+                    res.map(|_| Value::Null)
+                        .map_err(MethodCallError::from_error),
+                );
+            }
+            method::application::STARTED_HIDDEN => {
+                reply.send(Ok(Value::Bool(self.started_hidden())));
+            }
+            method::application::NOTIFY_STARTUP_COMPLETE => {
+                self.notify_startup_complete();
+                reply.send(Ok(Value::Null));
+            }
+            method::application::SET_JUMP_LIST => {
+                let res = call
+                    .args_as::<JumpList>()
+                    .map_err(Error::from)
+                    .and_then(|jump_list| self.set_jump_list(&jump_list));
+                reply.send(
+                    res.map(|_| Value::Null)
+                        .map_err(MethodCallError::from_error),
+                );
+            }
+            method::application::SET_APP_USER_MODEL_ID => {
+                let res = call
+                    .args_as::<String>()
+                    .map_err(Error::from)
+                    .and_then(|id| self.set_app_user_model_id(&id));
+                reply.send(
+                    res.map(|_| Value::Null)
+                        .map_err(MethodCallError::from_error),
+                );
+            }
+            method::application::SET_USER_ACTIVITY => {
+                let res = call
+                    .args_as::<Option<UserActivity>>()
+                    .map_err(Error::from)
+                    .and_then(|activity| self.set_user_activity(activity));
+                reply.send(
+                    res.map(|_| Value::Null)
+                        .map_err(MethodCallError::from_error),
+                );
+            }
+            method::application::HIDE => {
+                let res = self.hide_application();
+                reply.send(
+                    res.map(|_| Value::Null)
+                        .map_err(MethodCallError::from_error),
+                );
+            }
+            method::application::UNHIDE => {
+                let res = self.unhide_application();
+                reply.send(
+                    res.map(|_| Value::Null)
+                        .map_err(MethodCallError::from_error),
+                );
+            }
+            method::application::HIDE_OTHER_APPLICATIONS => {
+                let res = self.hide_other_applications();
+                reply.send(
+                    res.map(|_| Value::Null)
+                        .map_err(MethodCallError::from_error),
+                );
+            }
+            method::application::REQUEST_QUIT => {
+                self.request_quit(move |allowed| {
+                    reply.send(Ok(Value::Bool(allowed)));
+                });
+            }
+            method::application::GET_ACTIVATION_POLICY => {
+                reply.send(Ok(to_value(self.activation_policy()).unwrap()));
+            }
+            method::application::SET_ACTIVATION_POLICY => {
+                let res = call
+                    .args_as::<ActivationPolicy>()
+                    .map_err(Error::from)
+                    .and_then(|policy| self.set_activation_policy(policy));
+                reply.send(
+                    res.map(|_| Value::Null)
+                        .map_err(MethodCallError::from_error),
+                );
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use velcro::hash_map;
+
+    use crate::{
+        codec::{MessageCodec, StandardMethodCodec, Value},
+        shell::{
+            api_constants::*,
+            api_model::TerminationReason,
+            test_util::{create_window, mock_context, window_engine},
+        },
+    };
+
+    #[test]
+    fn test_windows_confirm_quit_one_at_a_time() {
+        let context = mock_context();
+        let engines: Vec<_> = (0..2)
+            .map(|_| window_engine(&context, create_window(&context, None)))
+            .collect();
+        let engine_manager = context.engine_manager.borrow();
+        let sent_messages = |index: usize| {
+            let engine = engine_manager.get_engine(engines[index]).unwrap();
+            engine.platform_engine.take_sent_messages().len()
+        };
+        let codec: &dyn MessageCodec<Value> = &StandardMethodCodec;
+        let reply = |index: usize, allowed: bool| {
+            let engine = engine_manager.get_engine(engines[index]).unwrap();
+            let reply = codec.encode_message(&Value::Map(hash_map! {
+                "result".into(): allowed.into(),
+            }));
+            engine
+                .platform_engine
+                .simulate_reply(channel::DISPATCHER, &reply)
+        };
+        sent_messages(0);
+        sent_messages(1);
+
+        let result = Rc::new(RefCell::new(None));
+        let result_copy = result.clone();
+        context.application_manager.borrow().should_terminate(
+            TerminationReason::Quit,
+            move |allowed| {
+                result_copy.replace(Some(allowed));
+            },
+        );
+
+        assert_eq!(sent_messages(0), 1);
+        assert_eq!(sent_messages(1), 0);
+        assert!(reply(0, true));
+        assert!(result.borrow().is_none());
+
+        assert_eq!(sent_messages(1), 1);
+        assert!(reply(1, false));
+        assert_eq!(result.take(), Some(false));
+    }
+}
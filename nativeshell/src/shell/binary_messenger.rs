@@ -1,6 +1,20 @@
+use std::{cell::RefCell, rc::Rc};
+
 use super::platform::binary_messenger::PlatformBinaryMessenger;
 use crate::Result;
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum MessageDirection {
+    ToDart,
+    FromDart,
+    // Reply to message received from Dart
+    ReplyToDart,
+    // Reply to message sent to Dart
+    ReplyFromDart,
+}
+
+pub(super) type MessageObserver = dyn Fn(MessageDirection, &str, &[u8]);
+
 pub struct BinaryMessengerReply {
     sent: bool,
     callback: Option<Box<dyn FnOnce(&[u8])>>,
@@ -22,24 +36,66 @@ impl BinaryMessengerReply {
         let callback = self.callback.take().unwrap();
         callback(message);
     }
+
+    // Reply that reports the message to observer before delivering it
+    fn observed<F>(mut self, observer: F) -> Self
+    where
+        F: FnOnce(&[u8]) + 'static,
+    {
+        self.sent = true;
+        let callback = self.callback.take().unwrap();
+        BinaryMessengerReply::new(move |message| {
+            observer(message);
+            callback(message);
+        })
+    }
 }
 
 pub struct BinaryMessenger {
     messenger: PlatformBinaryMessenger,
+    observer: Rc<RefCell<Option<Rc<MessageObserver>>>>,
 }
 
 impl BinaryMessenger {
     pub fn new(messenger_impl: PlatformBinaryMessenger) -> Self {
         BinaryMessenger {
             messenger: messenger_impl,
+            observer: Rc::new(RefCell::new(None)),
         }
     }
 
+    // Observer sees all traffic going through this messenger
+    pub(super) fn set_observer(&self, observer: Rc<MessageObserver>) {
+        self.observer.replace(Some(observer));
+    }
+
+    fn observer(&self) -> Option<Rc<MessageObserver>> {
+        self.observer.borrow().clone()
+    }
+
     pub fn register_channel_handler<F>(&self, channel: &str, callback: F)
     where
         F: Fn(&[u8], BinaryMessengerReply) + 'static,
     {
-        self.messenger.register_channel_handler(channel, callback);
+        // Observer is looked up for each message, so that handlers registered
+        // before the observer was set are observed as well
+        let observer = self.observer.clone();
+        let channel_name = channel.to_owned();
+        self.messenger
+            .register_channel_handler(channel, move |message, reply| {
+                let observer = observer.borrow().clone();
+                match observer {
+                    Some(observer) => {
+                        observer(MessageDirection::FromDart, &channel_name, message);
+                        let channel = channel_name.clone();
+                        let reply = reply.observed(move |reply| {
+                            observer(MessageDirection::ReplyToDart, &channel, reply)
+                        });
+                        callback(message, reply);
+                    }
+                    None => callback(message, reply),
+                }
+            });
     }
 
     pub fn unregister_channel_handler(&self, channel: &str) {
@@ -50,16 +106,32 @@ impl BinaryMessenger {
     where
         F: FnOnce(&[u8]) + 'static,
     {
-        self.messenger
-            .send_message(channel, message, reply_callback)
-            .map_err(|e| e.into())
+        match self.observer() {
+            Some(observer) => {
+                let reply_observer = observer.clone();
+                let channel_name = channel.to_owned();
+                self.messenger
+                    .send_message(channel, message, move |reply| {
+                        reply_observer(MessageDirection::ReplyFromDart, &channel_name, reply);
+                        reply_callback(reply);
+                    })?;
+                observer(MessageDirection::ToDart, channel, message);
+                Ok(())
+            }
+            None => self
+                .messenger
+                .send_message(channel, message, reply_callback)
+                .map_err(|e| e.into()),
+        }
     }
 
     // like "send_message" but wihtout reply
     pub fn post_message(&self, channel: &str, message: &[u8]) -> Result<()> {
-        self.messenger
-            .post_message(channel, message)
-            .map_err(|e| e.into())
+        self.messenger.post_message(channel, message)?;
+        if let Some(observer) = self.observer() {
+            observer(MessageDirection::ToDart, channel, message);
+        }
+        Ok(())
     }
 }
 
@@ -1,10 +1,17 @@
 use std::rc::Rc;
 
+use log::warn;
+
 use crate::{util::LateRefCell, Error, Result};
 
 use super::{
+    api_model::MenuDiagnostic,
     platform::{drag_data::DragDataAdapter, init::init_platform},
-    EngineManager, MenuManager, MessageManager, RunLoop, WindowManager, WindowMethodChannel,
+    AccessibilityManager, ApplicationManager, DisplayManager, EngineManager, EventTapManager,
+    FeedbackManager, FlutterEngine, LocaleManager, MenuManager, MessageBusManager, MessageManager,
+    PermissionManager, PlatformViewManager, PowerManager, RunLoop, SystemThemeManager,
+    TransferManager, UserActivityManager, WindowListManager, WindowManager, WindowMethodChannel,
+    WorkspaceManager,
 };
 
 pub struct ContextOptions {
@@ -12,6 +19,36 @@ pub struct ContextOptions {
 
     pub on_last_engine_removed: Box<dyn Fn(Rc<Context>)>,
     pub custom_drag_data_adapters: Vec<Box<dyn DragDataAdapter>>,
+
+    // Invoked for every newly created engine before it is launched; Can be used
+    // to register native desktop plugins through FlutterEngine::register_plugin
+    pub register_plugins: Box<dyn Fn(&FlutterEngine)>,
+
+    // Number of launched engines kept ready for new windows, which then show
+    // content almost immediately. Pooled engines use default EngineOptions and
+    // are only supported on platforms that can attach engine to a window.
+    pub engine_pool_size: usize,
+
+    // When set, launching the application again forwards command line arguments
+    // to the running instance and activates its main window; Context::new then
+    // fails with Error::InstanceAlreadyRunning and the process should exit.
+    // Instances are identified by app_namespace (or executable name).
+    pub single_instance: bool,
+
+    // Custom URL schemes (i.e. "myapp") registered for the application. MacOS
+    // delivers URLs through Apple Events (schemes are declared in Info.plist),
+    // on other platforms URLs are passed as command line arguments.
+    pub url_schemes: Vec<String>,
+
+    // Treat command line arguments that are paths to existing files as requests
+    // to open these files (Windows, Linux; i.e. when opening associated documents
+    // from file manager). MacOS always delivers open requests through Apple Events.
+    pub file_arguments: bool,
+
+    // Invoked for duplicate menu accelerators and accelerators that collide with
+    // system shortcuts, after menu is created or updated. Diagnostics for menus
+    // created from Dart are also reported to Dart (Menu.diagnosticEvent).
+    pub on_menu_diagnostic: Box<dyn Fn(&MenuDiagnostic)>,
 }
 
 impl Default for ContextOptions {
@@ -20,6 +57,12 @@ impl Default for ContextOptions {
             app_namespace: Default::default(),
             on_last_engine_removed: Box::new(|context| context.run_loop.borrow().stop()),
             custom_drag_data_adapters: Vec::new(),
+            register_plugins: Box::new(|_| {}),
+            engine_pool_size: 0,
+            single_instance: false,
+            url_schemes: Vec::new(),
+            file_arguments: false,
+            on_menu_diagnostic: Box::new(|diagnostic| warn!("{}", diagnostic.message)),
         }
     }
 }
@@ -32,6 +75,21 @@ pub struct Context {
     pub window_method_channel: LateRefCell<WindowMethodChannel>,
     pub window_manager: LateRefCell<WindowManager>,
     pub menu_manager: LateRefCell<MenuManager>,
+    pub event_tap_manager: LateRefCell<EventTapManager>,
+    pub accessibility_manager: LateRefCell<AccessibilityManager>,
+    pub message_bus_manager: LateRefCell<MessageBusManager>,
+    pub transfer_manager: LateRefCell<TransferManager>,
+    pub platform_view_manager: LateRefCell<PlatformViewManager>,
+    pub application_manager: LateRefCell<ApplicationManager>,
+    pub system_theme_manager: LateRefCell<SystemThemeManager>,
+    pub workspace_manager: LateRefCell<WorkspaceManager>,
+    pub display_manager: LateRefCell<DisplayManager>,
+    pub power_manager: LateRefCell<PowerManager>,
+    pub locale_manager: LateRefCell<LocaleManager>,
+    pub user_activity_manager: LateRefCell<UserActivityManager>,
+    pub feedback_manager: LateRefCell<FeedbackManager>,
+    pub permission_manager: LateRefCell<PermissionManager>,
+    pub window_list_manager: LateRefCell<WindowListManager>,
 }
 
 impl Context {
@@ -44,6 +102,21 @@ impl Context {
             window_method_channel: LateRefCell::new(),
             window_manager: LateRefCell::new(),
             menu_manager: LateRefCell::new(),
+            event_tap_manager: LateRefCell::new(),
+            accessibility_manager: LateRefCell::new(),
+            message_bus_manager: LateRefCell::new(),
+            transfer_manager: LateRefCell::new(),
+            platform_view_manager: LateRefCell::new(),
+            application_manager: LateRefCell::new(),
+            system_theme_manager: LateRefCell::new(),
+            workspace_manager: LateRefCell::new(),
+            display_manager: LateRefCell::new(),
+            power_manager: LateRefCell::new(),
+            locale_manager: LateRefCell::new(),
+            user_activity_manager: LateRefCell::new(),
+            feedback_manager: LateRefCell::new(),
+            permission_manager: LateRefCell::new(),
+            window_list_manager: LateRefCell::new(),
         });
         res.initialize(res.clone())?;
         Ok(res)
@@ -58,6 +131,33 @@ impl Context {
             .set(WindowMethodChannel::new(context.clone()));
         self.window_manager.set(WindowManager::new(context.clone()));
         self.menu_manager.set(MenuManager::new(context.clone()));
+        self.event_tap_manager
+            .set(EventTapManager::new(context.clone()));
+        self.accessibility_manager
+            .set(AccessibilityManager::new(context.clone()));
+        self.message_bus_manager
+            .set(MessageBusManager::new(context.clone()));
+        self.transfer_manager
+            .set(TransferManager::new(context.clone()));
+        self.platform_view_manager.set(PlatformViewManager::new());
+        self.application_manager
+            .set(ApplicationManager::new(context.clone()));
+        self.system_theme_manager
+            .set(SystemThemeManager::new(context.clone()));
+        self.workspace_manager
+            .set(WorkspaceManager::new(context.clone()));
+        self.display_manager
+            .set(DisplayManager::new(context.clone()));
+        self.power_manager.set(PowerManager::new(context.clone()));
+        self.locale_manager.set(LocaleManager::new(context.clone()));
+        self.user_activity_manager
+            .set(UserActivityManager::new(context.clone()));
+        self.feedback_manager
+            .set(FeedbackManager::new(context.clone()));
+        self.permission_manager
+            .set(PermissionManager::new(context.clone()));
+        self.window_list_manager
+            .set(WindowListManager::new(context.clone()));
 
         #[cfg(debug_assertions)]
         {
@@ -66,6 +166,13 @@ impl Context {
 
         init_platform(context).map_err(|e| Error::from(e))?;
 
+        if self.options.single_instance {
+            self.application_manager
+                .borrow()
+                .acquire_single_instance()?;
+        }
+        self.application_manager.borrow().process_launch_arguments();
+
         Ok(())
     }
 
@@ -0,0 +1,147 @@
+use std::{
+    any::Any,
+    backtrace::Backtrace,
+    fs::OpenOptions,
+    io::Write,
+    panic::{self, Location},
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use log::error;
+
+use super::platform::{crash_handler as platform, workspace};
+
+pub struct CrashHandlerOptions {
+    // Title of the crash dialog
+    pub title: String,
+
+    // When set, crash report (message and backtrace) is appended to this file
+    // and the crash dialog offers to open it
+    pub log_file: Option<PathBuf>,
+
+    // Also report uncaught Objective-C exceptions (macOS) and unhandled
+    // structured exceptions (Windows)
+    pub handle_exceptions: bool,
+}
+
+impl Default for CrashHandlerOptions {
+    fn default() -> Self {
+        Self {
+            title: "Application Error".into(),
+            log_file: None,
+            handle_exceptions: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CrashDialogResponse {
+    Quit,
+    CopyDetails,
+    OpenLogFile,
+}
+
+lazy_static! {
+    static ref OPTIONS: Mutex<Option<CrashHandlerOptions>> = Mutex::new(None);
+}
+
+static CRASHED: AtomicBool = AtomicBool::new(false);
+
+// Installs panic hook that presents native crash dialog and aborts the process
+// when main thread panics. Panics in callbacks from native code would otherwise
+// abort without any feedback. Should be called early in main(), before creating
+// the Context. Panics on other threads are only passed to the previous hook and
+// unwind as usual. Note that main thread panics abort even when caught by
+// catch_unwind.
+pub fn install_crash_handler(options: CrashHandlerOptions) {
+    let handle_exceptions = options.handle_exceptions;
+    *OPTIONS.lock().unwrap() = Some(options);
+
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        previous(info);
+        if !is_main_thread() {
+            return;
+        }
+        handle_crash(
+            panic_message(info.payload(), info.location()),
+            Backtrace::force_capture().to_string(),
+        );
+    }));
+
+    if handle_exceptions {
+        platform::install_exception_handler(handle_crash);
+    }
+}
+
+fn is_main_thread() -> bool {
+    thread::current().name() == Some("main")
+}
+
+fn panic_message(payload: &(dyn Any + Send), location: Option<&Location>) -> String {
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Unknown panic".into());
+    match location {
+        Some(location) => format!("{} ({})", message, location),
+        None => message,
+    }
+}
+
+fn write_log(path: &Path, details: &str) -> std::io::Result<()> {
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(
+        file,
+        "--- Crash (pid {}, time {}) ---\n{}\n",
+        process::id(),
+        time,
+        details
+    )
+}
+
+fn handle_crash(message: String, backtrace: String) {
+    // Crash while handling another crash (i.e. in the dialog itself)
+    if CRASHED.swap(true, Ordering::SeqCst) {
+        process::abort();
+    }
+    error!("Crashed: {}", message);
+
+    let options = OPTIONS.lock().unwrap_or_else(|e| e.into_inner());
+    let options = match options.as_ref() {
+        Some(options) => options,
+        None => process::abort(),
+    };
+    let details = format!("{}\n\nBacktrace:\n{}", message, backtrace);
+    let log_file = options
+        .log_file
+        .as_ref()
+        .filter(|path| write_log(path, &details).is_ok());
+
+    // Native UI can only be presented from main thread
+    if is_main_thread() {
+        let response = platform::show_crash_dialog(&options.title, &message, log_file.is_some());
+        match (response, log_file) {
+            (Ok(CrashDialogResponse::CopyDetails), _) => {
+                platform::copy_to_clipboard(&details).ok();
+            }
+            (Ok(CrashDialogResponse::OpenLogFile), Some(log_file)) => {
+                workspace::open_path(&log_file.to_string_lossy()).ok();
+            }
+            _ => {}
+        }
+    }
+    process::abort();
+}
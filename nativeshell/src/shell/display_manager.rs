@@ -0,0 +1,86 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    codec::{value::to_value, MethodCall, MethodCallReply, Value},
+    util::OkLog,
+    Result,
+};
+
+use super::{
+    api_constants::*, api_model::DisplayInfo, platform::display_info::PlatformDisplayInfo, Context,
+    EngineHandle,
+};
+
+// Display geometry and color capabilities (color space, ICC profile, HDR
+// headroom), so that color critical applications can adapt rendering
+#[cfg_attr(feature = "mock", allow(dead_code))]
+pub struct DisplayManager {
+    context: Rc<Context>,
+    platform_display_info: PlatformDisplayInfo,
+    last_displays: RefCell<Option<Vec<DisplayInfo>>>,
+}
+
+impl DisplayManager {
+    pub(super) fn new(context: Rc<Context>) -> Self {
+        let context_copy = context.clone();
+        context
+            .message_manager
+            .borrow_mut()
+            .register_method_handler(channel::DISPLAY, move |value, reply, engine| {
+                context_copy
+                    .display_manager
+                    .borrow()
+                    .on_method_call(value, reply, engine);
+            });
+
+        Self {
+            context: context.clone(),
+            platform_display_info: PlatformDisplayInfo::new(context),
+            last_displays: RefCell::new(None),
+        }
+    }
+
+    pub fn get_displays(&self) -> Result<Vec<DisplayInfo>> {
+        let displays = self.platform_display_info.get_displays()?;
+        self.last_displays.replace(Some(displays.clone()));
+        Ok(displays)
+    }
+
+    // Called by platform when display configuration (possibly) changed; Engines
+    // are only notified if display information differs from last known values
+    #[cfg_attr(feature = "mock", allow(dead_code))]
+    pub(crate) fn displays_changed(&self) {
+        let previous = self.last_displays.borrow().clone();
+        let displays = match self.get_displays().ok_log() {
+            Some(displays) => displays,
+            None => return,
+        };
+        if previous.as_ref() == Some(&displays) {
+            return;
+        }
+        let engines = self.context.engine_manager.borrow().get_all_engines();
+        let message_manager = self.context.message_manager.borrow();
+        for engine in engines {
+            if let Some(invoker) = message_manager.get_method_invoker(engine, channel::DISPLAY) {
+                invoker
+                    .call_method(
+                        method::display::DISPLAYS_CHANGED.into(),
+                        to_value(&displays).unwrap(),
+                        |_| {},
+                    )
+                    .ok_log();
+            }
+        }
+    }
+
+    fn on_method_call(
+        &self,
+        call: MethodCall<Value>,
+        reply: MethodCallReply<Value>,
+        _engine: EngineHandle,
+    ) {
+        if call.method.as_str() == method::display::GET_DISPLAYS {
+            reply.send_result(self.get_displays());
+        }
+    }
+}
@@ -1,19 +1,125 @@
-use super::{platform::engine::PlatformEngine, BinaryMessenger};
-use crate::Result;
+use std::{
+    env,
+    ffi::{c_void, CString, OsString},
+};
+
+use super::{
+    platform::{engine::PlatformEngine, native_view::NativeViewType},
+    BinaryMessenger,
+};
+use crate::{Error, Result};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EngineOptions {
+    // Name of top level Dart function to run instead of main; The function must
+    // be annotated with @pragma('vm:entry-point')
+    pub entrypoint: Option<String>,
+
+    // Passed to the entrypoint as List<String> argument
+    pub entrypoint_args: Vec<String>,
+
+    // Engine and Dart VM switches, i.e. "observatory-port=8181",
+    // "disable-service-auth-codes" or "trace-skia". Switches are only honored by
+    // debug and profile engine builds. Dart VM is shared by all engines in the
+    // process, so VM flags only take effect for the first engine launched.
+    pub switches: Vec<String>,
+}
+
+impl EngineOptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_entrypoint(mut self, entrypoint: &str) -> Self {
+        self.entrypoint = Some(entrypoint.into());
+        self
+    }
+
+    pub fn with_entrypoint_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.entrypoint_args = args.into_iter().map(|a| a.into()).collect();
+        self
+    }
+
+    pub fn with_switch(mut self, switch: &str) -> Self {
+        self.switches.push(switch.into());
+        self
+    }
+}
+
+// Desktop embedders read switches from FLUTTER_ENGINE_SWITCHES and
+// FLUTTER_ENGINE_SWITCH_<n> environment variables when the engine is run.
+// The variables are set until the engine is launched and then restored.
+struct EngineSwitches {
+    previous: Vec<(String, Option<OsString>)>,
+}
+
+impl EngineSwitches {
+    fn apply(switches: &[String]) -> Option<Self> {
+        if switches.is_empty() {
+            return None;
+        }
+        let mut vars = vec![(
+            "FLUTTER_ENGINE_SWITCHES".to_string(),
+            switches.len().to_string(),
+        )];
+        vars.extend(switches.iter().enumerate().map(|(i, switch)| {
+            (
+                format!("FLUTTER_ENGINE_SWITCH_{}", i + 1),
+                switch.trim_start_matches("--").to_string(),
+            )
+        }));
+        let previous = vars
+            .into_iter()
+            .map(|(name, value)| {
+                let previous = env::var_os(&name);
+                env::set_var(&name, value);
+                (name, previous)
+            })
+            .collect();
+        Some(Self { previous })
+    }
+}
+
+impl Drop for EngineSwitches {
+    fn drop(&mut self) {
+        for (name, value) in self.previous.drain(..) {
+            match value {
+                Some(value) => env::set_var(&name, value),
+                None => env::remove_var(&name),
+            }
+        }
+    }
+}
 
 pub struct FlutterEngine {
     pub(super) platform_engine: PlatformEngine,
     binary_messenger: Option<BinaryMessenger>,
+    switches: Option<EngineSwitches>,
 }
 
 impl FlutterEngine {
-    pub fn create() -> Self {
-        let platform_engine = PlatformEngine::new();
+    pub fn create(options: &EngineOptions) -> Self {
+        let switches = EngineSwitches::apply(&options.switches);
+        Self::new(PlatformEngine::new(options), switches)
+    }
 
+    // Engine without window; Dart code runs as soon as the engine is launched
+    // and the engine can be later attached to a window.
+    pub fn create_headless(options: &EngineOptions) -> Self {
+        let switches = EngineSwitches::apply(&options.switches);
+        Self::new(PlatformEngine::new_headless(options), switches)
+    }
+
+    fn new(platform_engine: PlatformEngine, switches: Option<EngineSwitches>) -> Self {
         let messenger = BinaryMessenger::new(platform_engine.new_binary_messenger());
         FlutterEngine {
             platform_engine,
             binary_messenger: Some(messenger),
+            switches,
         }
     }
 
@@ -21,12 +127,44 @@ impl FlutterEngine {
         self.binary_messenger.as_ref().unwrap()
     }
 
+    // Invokes the callback with the desktop embedder's own plugin registrar for
+    // given plugin, which can be passed to native plugin registration function
+    // (i.e. XXXPluginRegisterWithRegistrar on Windows,
+    // xxx_plugin_register_with_registrar on Linux or
+    // [XXXPlugin registerWithRegistrar:] on macOS). Registrar is
+    // FlutterDesktopPluginRegistrarRef, FlPluginRegistrar* and
+    // NSObject<FlutterPluginRegistrar>* respectively and is only guaranteed to be
+    // valid for the duration of the callback. This only hands out the raw
+    // embedder registrar; nativeshell doesn't wrap the plugin API itself.
+    pub fn register_plugin<F>(&self, plugin_name: &str, register: F) -> Result<()>
+    where
+        F: FnOnce(*mut c_void),
+    {
+        let plugin_name = CString::new(plugin_name).map_err(|_| Error::InvalidPluginName)?;
+        self.platform_engine
+            .with_plugin_registrar(&plugin_name, register)
+            .map_err(|e| e.into())
+    }
+
+    pub(super) fn attach_view(&mut self) -> Result<()> {
+        self.platform_engine.attach_view().map_err(|e| e.into())
+    }
+
+    // Flutter view of the engine, to be embedded as a region of a window that
+    // has its own content; Must be added to the window before launching
+    pub(super) fn embedded_view(&self) -> Result<NativeViewType> {
+        self.platform_engine.embedded_view().map_err(|e| e.into())
+    }
+
     pub fn launch(&mut self) -> Result<()> {
-        self.platform_engine.launch().map_err(|e| e.into())
+        let res = self.platform_engine.launch().map_err(|e| e.into());
+        self.switches.take();
+        res
     }
 
     pub fn shut_down(&mut self) -> Result<()> {
         self.binary_messenger.take();
+        self.switches.take();
         self.platform_engine.shut_down().map_err(|e| e.into())
     }
 }
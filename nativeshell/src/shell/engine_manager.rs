@@ -2,18 +2,43 @@ use std::{
     cell::{Ref, RefCell},
     collections::HashMap,
     rc::Rc,
+    time::Duration,
 };
 
-use super::{Context, FlutterEngine};
-use crate::{Error, Result};
+use super::{api_constants::*, Context, EngineOptions, FlutterEngine, MessageDirection};
+use crate::{util::OkLog, Error, Result};
 
-#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct EngineHandle(pub i64);
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum EngineEvent {
+    Created,
+    // Dart has rendered first frame and the window is ready to be shown
+    FirstFrame,
+    // Dart isolate has been restarted (hot restart during development); Dart side
+    // state of the engine is gone and should be rebuilt
+    HotRestart,
+    Destroyed,
+}
+
+type EngineListener = dyn Fn(EngineHandle, EngineEvent);
+
+// Arguments are engine, direction, channel and raw message
+type TrafficObserver = dyn Fn(EngineHandle, MessageDirection, &str, &[u8]);
+type TrafficObservers = Rc<RefCell<Vec<(i64, Rc<TrafficObserver>)>>>;
+
+// How long to wait for pending messages during graceful shutdown
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(1);
+
 pub struct EngineManager {
     context: Rc<Context>,
     engines: HashMap<EngineHandle, Box<RefCell<FlutterEngine>>>,
     next_handle: EngineHandle,
+    listeners: Vec<(i64, Rc<EngineListener>)>,
+    next_listener_id: i64,
+    // Shared with binary messengers of all engines
+    traffic_observers: TrafficObservers,
 }
 
 impl EngineManager {
@@ -22,18 +47,128 @@ impl EngineManager {
             context,
             engines: HashMap::new(),
             next_handle: EngineHandle(1),
+            listeners: Vec::new(),
+            next_listener_id: 1,
+            traffic_observers: Rc::new(RefCell::new(Vec::new())),
+        }
+    }
+
+    // Registers listener for engine lifecycle events; Returns identifier that
+    // can be used to remove the listener. Listeners are invoked on next run loop
+    // turn after the event.
+    pub fn add_listener<F>(&mut self, listener: F) -> i64
+    where
+        F: Fn(EngineHandle, EngineEvent) + 'static,
+    {
+        let id = self.next_listener_id;
+        self.next_listener_id += 1;
+        self.listeners.push((id, Rc::new(listener)));
+        id
+    }
+
+    pub fn remove_listener(&mut self, id: i64) {
+        self.listeners.retain(|(listener_id, _)| *listener_id != id);
+    }
+
+    // Registers observer for all platform channel messages and replies of all
+    // engines, including engines created before this call; Unlike listeners
+    // the observer is invoked synchronously.
+    pub fn add_traffic_observer<F>(&mut self, observer: F) -> i64
+    where
+        F: Fn(EngineHandle, MessageDirection, &str, &[u8]) + 'static,
+    {
+        let id = self.next_listener_id;
+        self.next_listener_id += 1;
+        let first = self.traffic_observers.borrow().is_empty();
+        self.traffic_observers
+            .borrow_mut()
+            .push((id, Rc::new(observer)));
+        if first {
+            for (handle, engine) in &self.engines {
+                self.observe_traffic(*handle, &engine.borrow());
+            }
+        }
+        id
+    }
+
+    pub fn remove_traffic_observer(&mut self, id: i64) {
+        self.traffic_observers
+            .borrow_mut()
+            .retain(|(observer_id, _)| *observer_id != id);
+    }
+
+    fn observe_traffic(&self, handle: EngineHandle, engine: &FlutterEngine) {
+        if self.traffic_observers.borrow().is_empty() {
+            return;
+        }
+        let observers = self.traffic_observers.clone();
+        engine
+            .binary_messenger()
+            .set_observer(Rc::new(move |direction, channel, message| {
+                // observers may be added or removed from within observer
+                let observers: Vec<Rc<TrafficObserver>> = observers
+                    .borrow()
+                    .iter()
+                    .map(|(_, observer)| observer.clone())
+                    .collect();
+                for observer in observers {
+                    observer(handle, direction, channel, message);
+                }
+            }));
+    }
+
+    pub(super) fn notify_listeners(&self, handle: EngineHandle, event: EngineEvent) {
+        if self.listeners.is_empty() {
+            return;
         }
+        let context = self.context.clone();
+        self.context
+            .run_loop
+            .borrow()
+            .schedule_now(move || {
+                let listeners: Vec<Rc<EngineListener>> = context
+                    .engine_manager
+                    .borrow()
+                    .listeners
+                    .iter()
+                    .map(|(_, listener)| listener.clone())
+                    .collect();
+                for listener in listeners {
+                    listener(handle, event);
+                }
+            })
+            .detach();
+    }
+
+    pub fn create_engine(&mut self, options: &EngineOptions) -> EngineHandle {
+        self.add_engine(FlutterEngine::create(options))
     }
 
-    pub fn create_engine(&mut self) -> EngineHandle {
-        let engine = FlutterEngine::create();
+    // Creates and launches engine without window. Engine can be attached to
+    // a window through WindowManager::create_window_for_engine. On Dart side
+    // WindowManager.initialize() completes once the engine has been attached.
+    pub fn create_headless_engine(&mut self, options: &EngineOptions) -> Result<EngineHandle> {
+        let handle = self.add_engine(FlutterEngine::create_headless(options));
+        let res = self.launch_engine(handle);
+        if res.is_err() {
+            if let Some(engine) = self.engines.remove(&handle) {
+                engine.borrow_mut().shut_down().ok_log();
+            }
+        }
+        res.map(|_| handle)
+    }
+
+    fn add_engine(&mut self, engine: FlutterEngine) -> EngineHandle {
         let handle = self.next_handle;
         self.next_handle.0 += 1;
+        self.observe_traffic(handle, &engine);
+        (self.context.options.register_plugins)(&engine);
         self.engines.insert(handle, Box::new(RefCell::new(engine)));
         self.context
             .message_manager
             .borrow_mut()
             .engine_created(self, handle);
+        self.notify_listeners(handle, EngineEvent::Created);
         handle
     }
 
@@ -45,6 +180,14 @@ impl EngineManager {
             .ok_or(Error::InvalidEngineHandle)
     }
 
+    pub(super) fn attach_view(&self, handle: EngineHandle) -> Result<()> {
+        self.engines
+            .get(&handle)
+            .map(|engine| engine.borrow_mut().attach_view())
+            .transpose()?
+            .ok_or(Error::InvalidEngineHandle)
+    }
+
     pub fn get_engine(&self, handle: EngineHandle) -> Option<Ref<FlutterEngine>> {
         self.engines.get(&handle).map(|a| a.borrow())
     }
@@ -52,8 +195,17 @@ impl EngineManager {
     pub fn remove_engine(&mut self, handle: EngineHandle) -> Result<()> {
         let entry = self.engines.remove(&handle);
         if let Some(entry) = entry {
+            self.context
+                .transfer_manager
+                .borrow()
+                .engine_removed(handle);
+            self.context
+                .message_bus_manager
+                .borrow()
+                .engine_removed(handle);
             let mut engine = entry.borrow_mut();
             engine.shut_down()?;
+            self.notify_listeners(handle, EngineEvent::Destroyed);
         }
         if self.engines.is_empty() {
             (self.context.options.on_last_engine_removed)(self.context.clone());
@@ -61,6 +213,66 @@ impl EngineManager {
         Ok(())
     }
 
+    // Waits until messages already sent to the engine have been processed (or
+    // timeout expires) and then removes the engine.
+    pub fn shut_down_engine<F>(&self, handle: EngineHandle, on_done: F)
+    where
+        F: FnOnce(Result<()>) + 'static,
+    {
+        let engine = match self.engines.get(&handle) {
+            Some(engine) => engine,
+            None => {
+                on_done(Err(Error::InvalidEngineHandle));
+                return;
+            }
+        };
+
+        // Invoked either by flush reply or by timeout, whichever comes first
+        let context = self.context.clone();
+        let remove = Rc::new(RefCell::new(Some(move || {
+            let res = context.engine_manager.borrow_mut().remove_engine(handle);
+            on_done(res);
+        })));
+
+        let remove_copy = remove.clone();
+        let mut timeout = self
+            .context
+            .run_loop
+            .borrow()
+            .schedule(FLUSH_TIMEOUT, move || {
+                if let Some(remove) = remove_copy.borrow_mut().take() {
+                    remove();
+                }
+            });
+
+        let context = self.context.clone();
+        let remove_copy = remove.clone();
+        let res = engine.borrow().binary_messenger().send_message(
+            channel::ENGINE_FLUSH,
+            &[],
+            move |_| {
+                timeout.cancel();
+                // Reply may come from within engine callback; Engine must not be
+                // removed until the callback returns
+                context
+                    .run_loop
+                    .borrow()
+                    .schedule_now(move || {
+                        if let Some(remove) = remove_copy.borrow_mut().take() {
+                            remove();
+                        }
+                    })
+                    .detach();
+            },
+        );
+        if res.is_err() {
+            let remove = remove.borrow_mut().take();
+            if let Some(remove) = remove {
+                self.context.run_loop.borrow().schedule_now(remove).detach();
+            }
+        }
+    }
+
     pub fn get_all_engines(&self) -> Vec<EngineHandle> {
         self.engines.keys().cloned().collect()
     }
@@ -0,0 +1,149 @@
+use std::{collections::HashMap, rc::Rc};
+
+use crate::{
+    codec::{value::to_value, MethodCall, MethodCallError, MethodCallReply, Value},
+    util::OkLog,
+    Error, Result,
+};
+
+use super::{
+    api_constants::*,
+    api_model::{EventTapEvent, EventTapPermission, EventTapRequest},
+    platform::event_tap::PlatformEventTap,
+    Context, EngineHandle, WindowMethodCallResult,
+};
+
+// Monitors keyboard and mouse events outside of application windows. Nothing
+// is monitored until an engine explicitly starts the event tap.
+pub struct EventTapManager {
+    context: Rc<Context>,
+    platform_event_tap: PlatformEventTap,
+    requests: HashMap<EngineHandle, EventTapRequest>,
+}
+
+impl EventTapManager {
+    pub(super) fn new(context: Rc<Context>) -> Self {
+        let context_copy = context.clone();
+        context
+            .message_manager
+            .borrow_mut()
+            .register_method_handler(channel::EVENT_TAP, move |value, reply, engine| {
+                context_copy
+                    .event_tap_manager
+                    .borrow_mut()
+                    .on_method_call(value, reply, engine);
+            });
+
+        Self {
+            context: context.clone(),
+            platform_event_tap: PlatformEventTap::new(context),
+            requests: HashMap::new(),
+        }
+    }
+
+    pub fn permission(&self) -> EventTapPermission {
+        self.platform_event_tap.permission()
+    }
+
+    pub fn request_permission(&self) -> Result<EventTapPermission> {
+        self.platform_event_tap
+            .request_permission()
+            .map_err(|e| e.into())
+    }
+
+    fn update_platform_event_tap(&self) -> Result<()> {
+        if self.requests.is_empty() {
+            return self.platform_event_tap.stop().map_err(|e| e.into());
+        }
+        let combined = self
+            .requests
+            .values()
+            .fold(EventTapRequest::default(), |acc, r| EventTapRequest {
+                key_events: acc.key_events || r.key_events,
+                mouse_events: acc.mouse_events || r.mouse_events,
+            });
+        self.platform_event_tap
+            .start(&combined)
+            .map_err(|e| e.into())
+    }
+
+    fn start(&mut self, request: EventTapRequest, engine: EngineHandle) -> Result<()> {
+        let previous = self.requests.insert(engine, request);
+        let res = self.update_platform_event_tap();
+        if res.is_err() {
+            match previous {
+                Some(previous) => self.requests.insert(engine, previous),
+                None => self.requests.remove(&engine),
+            };
+        }
+        res
+    }
+
+    fn stop(&mut self, engine: EngineHandle) -> Result<()> {
+        if self.requests.remove(&engine).is_some() {
+            self.update_platform_event_tap()
+        } else {
+            Ok(())
+        }
+    }
+
+    #[allow(dead_code)] // not used on linux
+    pub(crate) fn on_event(&self, event: EventTapEvent) {
+        let message_manager = self.context.message_manager.borrow();
+        for (engine, request) in &self.requests {
+            let wanted = if event.is_key_event() {
+                request.key_events
+            } else {
+                request.mouse_events
+            };
+            if !wanted {
+                continue;
+            }
+            if let Some(invoker) = message_manager.get_method_invoker(*engine, channel::EVENT_TAP) {
+                invoker
+                    .call_method(
+                        method::event_tap::ON_EVENT.into(),
+                        to_value(&event).unwrap(),
+                        |_| {},
+                    )
+                    .ok_log();
+            }
+        }
+    }
+
+    fn map_result<T>(result: Result<T>) -> WindowMethodCallResult
+    where
+        T: serde::Serialize,
+    {
+        result
+            .map(|v| to_value(v).unwrap())
+            .map_err(MethodCallError::from_error)
+    }
+
+    fn on_method_call(
+        &mut self,
+        call: MethodCall<Value>,
+        reply: MethodCallReply<Value>,
+        engine: EngineHandle,
+    ) {
+        match call.method.as_str() {
+            method::event_tap::GET_PERMISSION => {
+                reply.send(Self::map_result(Ok(self.permission())));
+            }
+            method::event_tap::REQUEST_PERMISSION => {
+                reply.send(Self::map_result(self.request_permission()));
+            }
+            method::event_tap::START => {
+                let res = call
+                    .args_as::<EventTapRequest>()
+                    .map_err(Error::from)
+                    .and_then(|request| self.start(request, engine));
+                reply.send(Self::map_result(res));
+            }
+            method::event_tap::STOP => {
+                reply.send(Self::map_result(self.stop(engine)));
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use std::rc::Rc;
+
+use crate::{
+    codec::{value::from_value, MethodCall, MethodCallError, MethodCallReply, Value},
+    Result,
+};
+
+use super::{
+    api_constants::*, api_model::HapticFeedbackPattern, platform::feedback, Context, EngineHandle,
+};
+
+// Audible and haptic feedback for user actions
+pub struct FeedbackManager {}
+
+impl FeedbackManager {
+    pub(super) fn new(context: Rc<Context>) -> Self {
+        let context_copy = context.clone();
+        context
+            .message_manager
+            .borrow_mut()
+            .register_method_handler(channel::FEEDBACK, move |value, reply, engine| {
+                context_copy
+                    .feedback_manager
+                    .borrow()
+                    .on_method_call(value, reply, engine);
+            });
+        Self {}
+    }
+
+    // Default alert sound
+    pub fn beep(&self) -> Result<()> {
+        feedback::beep().map_err(|e| e.into())
+    }
+
+    // Name is platform specific, i.e. "Glass" (macOS), "SystemAsterisk" (Windows)
+    // or "message-new-instant" (Linux sound theme); Playback is asynchronous
+    pub fn play_system_sound(&self, name: &str) -> Result<()> {
+        feedback::play_system_sound(name).map_err(|e| e.into())
+    }
+
+    // Only available on macOS with force touch trackpad; Silently ignored when
+    // there is no feedback device
+    pub fn perform_haptic_feedback(&self, pattern: HapticFeedbackPattern) -> Result<()> {
+        feedback::perform_haptic_feedback(pattern).map_err(|e| e.into())
+    }
+
+    fn on_method_call(
+        &self,
+        call: MethodCall<Value>,
+        reply: MethodCallReply<Value>,
+        _engine: EngineHandle,
+    ) {
+        let res = match call.method.as_str() {
+            method::feedback::BEEP => self.beep(),
+            method::feedback::PLAY_SYSTEM_SOUND => from_value::<String>(&call.args)
+                .map_err(|e| e.into())
+                .and_then(|name| self.play_system_sound(&name)),
+            method::feedback::PERFORM_HAPTIC_FEEDBACK => from_value(&call.args)
+                .map_err(|e| e.into())
+                .and_then(|pattern| self.perform_haptic_feedback(pattern)),
+            _ => return,
+        };
+        reply.send(
+            res.map(|_| Value::Null)
+                .map_err(MethodCallError::from_error),
+        );
+    }
+}
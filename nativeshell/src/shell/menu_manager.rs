@@ -1,24 +1,44 @@
-use std::{collections::HashMap, rc::Rc};
+use std::{
+    cell::Cell,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
 
 use crate::{
     codec::{
         value::{from_value, to_value},
-        MethodCall, MethodCallReply, MethodInvoker, Value,
+        MethodCall, MethodCallError, MethodCallReply, MethodInvoker, Value,
     },
-    util::OkLog,
+    util::{trace, OkLog},
     Error, Result,
 };
 
 use super::{
     api_constants::*,
-    api_model::{MenuAction, MenuCreateRequest, MenuDestroyRequest, SetMenuRequest},
+    api_model::{
+        Accelerator, CheckStatus, Menu, MenuAction, MenuCreateRequest, MenuDestroyRequest,
+        MenuDiagnostic, MenuDiagnosticKind, MenuItem, MenuItemRole, MenuRole, SetMenuRequest,
+    },
     platform::menu::{PlatformMenu, PlatformMenuManager},
-    Context, EngineHandle, WindowMethodCallResult,
+    Context, EngineHandle, Window, WindowHandle, WindowMethodCallResult,
 };
 
+// Ids of items appended to window menu; Dart allocates item ids from 1
+const WINDOW_SEPARATOR_ID: i64 = 0x4000_0000;
+const WINDOW_ITEM_ID_BASE: i64 = WINDOW_SEPARATOR_ID + 1;
+
+// Menus created by Dart report actions to their engine; Menus created from Rust
+// (i.e. application menu available before any engine is loaded) to a callback
+enum MenuOwner {
+    Engine(EngineHandle),
+    Rust(Rc<dyn Fn(i64)>),
+}
+
 struct MenuEntry {
-    engine: EngineHandle,
+    owner: MenuOwner,
     platform_menu: Rc<PlatformMenu>,
+    // Menu as provided by owner (without window list)
+    menu: Menu,
 }
 
 pub struct MenuManager {
@@ -26,6 +46,12 @@ pub struct MenuManager {
     platform_menu_map: HashMap<MenuHandle, MenuEntry>,
     platform_menu_manager: PlatformMenuManager,
     next_handle: MenuHandle,
+    // Windows listed in window menus, in item order
+    window_list: Vec<WindowHandle>,
+    window_menu_update_scheduled: Cell<bool>,
+    // Menus updated since accelerators were last checked
+    accelerator_check_pending: Vec<MenuHandle>,
+    accelerator_check_scheduled: Cell<bool>,
 }
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -49,6 +75,10 @@ impl MenuManager {
             platform_menu_map: HashMap::new(),
             platform_menu_manager: PlatformMenuManager::new(context),
             next_handle: MenuHandle(1),
+            window_list: Vec::new(),
+            window_menu_update_scheduled: Cell::new(false),
+            accelerator_check_pending: Vec::new(),
+            accelerator_check_scheduled: Cell::new(false),
         }
     }
 
@@ -63,46 +93,400 @@ impl MenuManager {
         &self.platform_menu_manager
     }
 
+    // Creates menu from Rust; Submenus must be created first and referenced by
+    // handle. Action callback receives id of selected item.
+    pub fn create_menu<F>(&mut self, menu: Menu, on_action: F) -> Result<MenuHandle>
+    where
+        F: Fn(i64) + 'static,
+    {
+        let handle = self.next_handle();
+        self.create_or_update(handle, menu, MenuOwner::Rust(Rc::new(on_action)))?;
+        Ok(handle)
+    }
+
+    pub fn update_menu(&mut self, handle: MenuHandle, menu: Menu) -> Result<()> {
+        match self.platform_menu_map.get(&handle) {
+            Some(MenuEntry {
+                owner: MenuOwner::Rust(on_action),
+                ..
+            }) => {
+                let owner = MenuOwner::Rust(on_action.clone());
+                self.create_or_update(handle, menu, owner)
+            }
+            _ => Err(Error::InvalidMenuHandle),
+        }
+    }
+
+    pub fn destroy_menu(&mut self, handle: MenuHandle) -> Result<()> {
+        self.platform_menu_map
+            .remove(&handle)
+            .ok_or(Error::InvalidMenuHandle)?;
+        trace::menu_destroyed(handle.0);
+        Ok(())
+    }
+
+    // Sets application wide menu (macOS menu bar, Linux global menu). Dart code can
+    // replace the menu later through Menu.setAsAppMenu.
+    pub fn set_app_menu(&self, handle: Option<MenuHandle>) -> Result<()> {
+        let menu = handle
+            .map(|handle| self.get_platform_menu(handle))
+            .transpose()?;
+        self.platform_menu_manager
+            .set_app_menu(menu)
+            .map_err(|e| e.into())
+    }
+
+    pub fn select_next_window(&self) -> Result<()> {
+        self.select_window(1)
+    }
+
+    pub fn select_previous_window(&self) -> Result<()> {
+        self.select_window(-1)
+    }
+
+    // Activates listed windows in order, active window last so that it stays on top
+    pub fn bring_all_to_front(&self) -> Result<()> {
+        let windows = self.listed_windows();
+        let (active, inactive): (Vec<_>, Vec<_>) =
+            windows.into_iter().partition(|window| window.is_active());
+        for window in inactive.iter().chain(active.iter()) {
+            window.activate()?;
+        }
+        Ok(())
+    }
+
+    fn select_window(&self, offset: isize) -> Result<()> {
+        let windows = self.listed_windows();
+        if windows.is_empty() {
+            return Ok(());
+        }
+        let index = match windows.iter().position(|window| window.is_active()) {
+            Some(active) => (active as isize + offset).rem_euclid(windows.len() as isize),
+            None => 0,
+        };
+        windows[index as usize].activate()
+    }
+
+    // Visible windows with title, in order of creation
+    fn listed_windows(&self) -> Vec<Rc<Window>> {
+        let window_manager = self.context.window_manager.borrow();
+        window_manager
+            .get_all_windows()
+            .into_iter()
+            .filter_map(|handle| window_manager.get_window(handle))
+            .filter(|window| window.is_visible() && !window.title().is_empty())
+            .collect()
+    }
+
+    // Platforms without native window list get window items appended to menus
+    // with window role
+    fn is_window_menu(&self, menu: &Menu) -> bool {
+        matches!(menu.role, Some(MenuRole::Window))
+            && !self.platform_menu_manager.has_native_window_list()
+    }
+
+    fn with_window_list(&mut self, mut menu: Menu) -> Menu {
+        if !self.is_window_menu(&menu) {
+            return menu;
+        }
+        let windows = self.listed_windows();
+        if !windows.is_empty() && !menu.items.is_empty() {
+            menu.items.push(MenuItem {
+                id: WINDOW_SEPARATOR_ID,
+                separator: true,
+                ..Default::default()
+            });
+        }
+        for (index, window) in windows.iter().enumerate() {
+            menu.items.push(MenuItem {
+                id: WINDOW_ITEM_ID_BASE + index as i64,
+                title: window.title(),
+                enabled: true,
+                check_status: if window.is_active() {
+                    CheckStatus::CheckOn
+                } else {
+                    CheckStatus::CheckOff
+                },
+                ..Default::default()
+            });
+        }
+        self.window_list = windows.iter().map(|window| window.window_handle).collect();
+        menu
+    }
+
+    // Called when window is shown, hidden, closed, activated or retitled;
+    // Window menus are updated once for all changes in current run loop turn
+    pub(crate) fn window_list_changed(&self) {
+        let has_window_menu = self
+            .platform_menu_map
+            .values()
+            .any(|entry| self.is_window_menu(&entry.menu));
+        if !has_window_menu || self.window_menu_update_scheduled.replace(true) {
+            return;
+        }
+        let context = self.context.clone();
+        self.context
+            .run_loop
+            .borrow()
+            .schedule_now(move || {
+                let mut menu_manager = context.menu_manager.borrow_mut();
+                menu_manager.window_menu_update_scheduled.set(false);
+                menu_manager.update_window_menus();
+            })
+            .detach();
+    }
+
+    fn update_window_menus(&mut self) {
+        let menus: Vec<_> = self
+            .platform_menu_map
+            .values()
+            .filter(|entry| self.is_window_menu(&entry.menu))
+            .map(|entry| (entry.platform_menu.clone(), entry.menu.clone()))
+            .collect();
+        for (platform_menu, menu) in menus {
+            let menu = self.with_window_list(menu);
+            platform_menu.update_from_menu(menu, self).ok_log();
+        }
+    }
+
+    // Role of item in menu or its submenus
+    fn item_role(&self, menu_handle: MenuHandle, id: i64) -> Option<MenuItemRole> {
+        let entry = self.platform_menu_map.get(&menu_handle)?;
+        entry.menu.items.iter().find_map(|item| {
+            if item.id == id {
+                item.role.clone()
+            } else {
+                item.submenu.and_then(|submenu| self.item_role(submenu, id))
+            }
+        })
+    }
+
+    fn next_handle(&mut self) -> MenuHandle {
+        let res = self.next_handle;
+        self.next_handle.0 += 1;
+        res
+    }
+
     fn on_create_or_update(
         &mut self,
         request: MenuCreateRequest,
         engine: EngineHandle,
     ) -> Result<MenuHandle> {
-        let handle = request.handle.unwrap_or_else(|| {
-            let res = self.next_handle;
-            self.next_handle.0 += 1;
-            res
-        });
+        let handle = request.handle.unwrap_or_else(|| self.next_handle());
+        self.create_or_update(handle, request.menu, MenuOwner::Engine(engine))?;
+        Ok(handle)
+    }
+
+    fn create_or_update(&mut self, handle: MenuHandle, menu: Menu, owner: MenuOwner) -> Result<()> {
         let entry = self.platform_menu_map.entry(handle);
         let context = self.context.clone();
-        let platform_menu = entry
-            .or_insert_with(|| {
-                let platform_menu = Rc::new(PlatformMenu::new(context, handle));
-                platform_menu.assign_weak_self(Rc::downgrade(&platform_menu));
-                MenuEntry {
-                    engine,
-                    platform_menu,
-                }
-            })
-            .platform_menu
-            .clone();
+        let entry = entry.or_insert_with(|| {
+            let platform_menu = Rc::new(PlatformMenu::new(context, handle));
+            platform_menu.assign_weak_self(Rc::downgrade(&platform_menu));
+            MenuEntry {
+                owner,
+                platform_menu,
+                menu: Menu::default(),
+            }
+        });
+        entry.menu = menu.clone();
+        let platform_menu = entry.platform_menu.clone();
+        let menu = self.with_window_list(menu);
         platform_menu
-            .update_from_menu(request.menu, self)
+            .update_from_menu(menu, self)
             .map_err(Error::from)?;
 
-        Ok(handle)
+        trace::menu_updated(handle.0);
+        self.schedule_accelerator_check(handle);
+        Ok(())
+    }
+
+    // Accelerators are checked once all menus updated in current run loop turn
+    // are in place (Dart creates submenus before their parents). Conflicts are
+    // reported to ContextOptions::on_menu_diagnostic and to the engine that owns
+    // the menu.
+    fn schedule_accelerator_check(&mut self, handle: MenuHandle) {
+        self.accelerator_check_pending.push(handle);
+        if self.accelerator_check_scheduled.replace(true) {
+            return;
+        }
+        let context = self.context.clone();
+        self.context
+            .run_loop
+            .borrow()
+            .schedule_now(move || {
+                let diagnostics = {
+                    let mut menu_manager = context.menu_manager.borrow_mut();
+                    menu_manager.accelerator_check_scheduled.set(false);
+                    menu_manager.check_accelerators()
+                };
+                for (invoker, diagnostic) in diagnostics {
+                    (context.options.on_menu_diagnostic)(&diagnostic);
+                    if let Some(invoker) = invoker {
+                        invoker
+                            .call_method(
+                                method::menu::ON_DIAGNOSTIC.into(),
+                                to_value(&diagnostic).unwrap(),
+                                |_| {},
+                            )
+                            .ok_log();
+                    }
+                }
+            })
+            .detach();
+    }
+
+    // Checks trees of root menus (menus that are not submenu of another menu)
+    // containing any of the updated menus
+    fn check_accelerators(&mut self) -> Vec<(Option<MethodInvoker<Value>>, MenuDiagnostic)> {
+        let pending: HashSet<MenuHandle> = self.accelerator_check_pending.drain(..).collect();
+        let submenus: HashSet<MenuHandle> = self
+            .platform_menu_map
+            .values()
+            .flat_map(|entry| entry.menu.items.iter().filter_map(|item| item.submenu))
+            .collect();
+        let system_shortcuts = self.platform_menu_manager.system_shortcuts();
+        let mut res = Vec::new();
+        for root in self.platform_menu_map.keys() {
+            if submenus.contains(root) {
+                continue;
+            }
+            let mut items = Vec::new();
+            let mut visited = HashSet::new();
+            self.collect_accelerators(*root, "", &mut items, &mut visited);
+            if visited.is_disjoint(&pending) {
+                continue;
+            }
+            for diagnostic in Self::accelerator_diagnostics(*root, &items, &system_shortcuts) {
+                res.push((self.invoker_for_menu(*root), diagnostic));
+            }
+        }
+        res
+    }
+
+    // Items with accelerator in menu and its submenus, with path of titles
+    fn collect_accelerators(
+        &self,
+        handle: MenuHandle,
+        prefix: &str,
+        items: &mut Vec<(String, Accelerator)>,
+        visited: &mut HashSet<MenuHandle>,
+    ) {
+        if !visited.insert(handle) {
+            return;
+        }
+        let entry = match self.platform_menu_map.get(&handle) {
+            Some(entry) => entry,
+            None => return,
+        };
+        for item in &entry.menu.items {
+            // Mnemonic markers (Windows and Linux)
+            let path = format!("{}{}", prefix, item.title.replace('&', ""));
+            if let Some(accelerator) = &item.accelerator {
+                items.push((path.clone(), accelerator.clone()));
+            }
+            if let Some(submenu) = item.submenu {
+                self.collect_accelerators(submenu, &format!("{} > ", path), items, visited);
+            }
+        }
+    }
+
+    fn accelerator_diagnostics(
+        menu: MenuHandle,
+        items: &[(String, Accelerator)],
+        system_shortcuts: &[(Accelerator, &'static str)],
+    ) -> Vec<MenuDiagnostic> {
+        let mut res = Vec::new();
+        let mut reported = vec![false; items.len()];
+        for (index, (path, accelerator)) in items.iter().enumerate() {
+            if !reported[index] {
+                let duplicates: Vec<usize> = (index..items.len())
+                    .filter(|i| items[*i].1.same_as(accelerator))
+                    .collect();
+                if duplicates.len() > 1 {
+                    let paths: Vec<String> = duplicates
+                        .iter()
+                        .map(|i| {
+                            reported[*i] = true;
+                            items[*i].0.clone()
+                        })
+                        .collect();
+                    res.push(MenuDiagnostic {
+                        kind: MenuDiagnosticKind::DuplicateAccelerator,
+                        menu,
+                        accelerator: accelerator.clone(),
+                        message: format!(
+                            "Accelerator {} is used by multiple menu items: {}",
+                            accelerator.description(),
+                            paths.join(", ")
+                        ),
+                        items: paths,
+                        system_shortcut: None,
+                    });
+                }
+            }
+            let system_shortcut = system_shortcuts
+                .iter()
+                .find(|shortcut| shortcut.0.same_as(accelerator));
+            if let Some((_, name)) = system_shortcut {
+                res.push(MenuDiagnostic {
+                    kind: MenuDiagnosticKind::SystemShortcut,
+                    menu,
+                    accelerator: accelerator.clone(),
+                    items: vec![path.clone()],
+                    system_shortcut: Some(name.to_string()),
+                    message: format!(
+                        "Accelerator {} of menu item {} collides with system shortcut ({})",
+                        accelerator.description(),
+                        path,
+                        name
+                    ),
+                });
+            }
+        }
+        res
     }
 
     fn invoker_for_menu(&self, menu_handle: MenuHandle) -> Option<MethodInvoker<Value>> {
-        self.platform_menu_map.get(&menu_handle).and_then(|e| {
-            self.context
+        match self.platform_menu_map.get(&menu_handle)?.owner {
+            MenuOwner::Engine(engine) => self
+                .context
                 .message_manager
                 .borrow()
-                .get_method_invoker(e.engine, channel::MENU_MANAGER)
-        })
+                .get_method_invoker(engine, channel::MENU_MANAGER),
+            MenuOwner::Rust(_) => None,
+        }
     }
 
     pub(crate) fn on_menu_action(&self, menu_handle: MenuHandle, id: i64) {
+        if id >= WINDOW_ITEM_ID_BASE {
+            let window = self.window_list.get((id - WINDOW_ITEM_ID_BASE) as usize);
+            if let Some(window) = window {
+                let window_manager = self.context.window_manager.borrow();
+                window_manager.activate_window(*window).ok_log();
+            }
+            return;
+        }
+        // Handled natively on macOS
+        if let Some(MenuItemRole::BringAllToFront) = self.item_role(menu_handle, id) {
+            self.bring_all_to_front().ok_log();
+            return;
+        }
+        let entry = self.platform_menu_map.get(&menu_handle);
+        if let Some(MenuEntry {
+            owner: MenuOwner::Rust(on_action),
+            ..
+        }) = entry
+        {
+            // Called while menu manager is borrowed; Callback may want to update menu
+            let on_action = on_action.clone();
+            self.context
+                .run_loop
+                .borrow()
+                .schedule_now(move || on_action(id))
+                .detach();
+            return;
+        }
         if let Some(invoker) = self.invoker_for_menu(menu_handle) {
             invoker
                 .call_method(
@@ -148,7 +532,9 @@ impl MenuManager {
     where
         T: serde::Serialize,
     {
-        result.map(|v| to_value(v).unwrap()).map_err(|e| e.into())
+        result
+            .map(|v| to_value(v).unwrap())
+            .map_err(MethodCallError::from_error)
     }
 
     fn on_method_call(
@@ -166,32 +552,323 @@ impl MenuManager {
             method::menu::DESTROY => {
                 let request: MenuDestroyRequest = from_value(&call.args).unwrap();
                 self.platform_menu_map.remove(&request.handle);
+                trace::menu_destroyed(request.handle.0);
                 reply.send_ok(Value::Null);
             }
             method::menu::SET_APP_MENU => {
                 let request: SetMenuRequest = from_value(&call.args).unwrap();
-                match request.handle {
-                    Some(handle) => {
-                        let menu = self.platform_menu_map.get(&handle);
-                        match menu {
-                            Some(menu) => reply.send(Self::map_result(
-                                self.platform_menu_manager
-                                    .set_app_menu(Some(menu.platform_menu.clone()))
-                                    .map_err(|e| e.into()),
-                            )),
-                            None => {
-                                reply.send(Self::map_result::<()>(Err(Error::InvalidMenuHandle)));
-                            }
-                        }
-                    }
-                    None => reply.send(Self::map_result(
-                        self.platform_menu_manager
-                            .set_app_menu(None)
-                            .map_err(|e| e.into()),
-                    )),
-                }
+                reply.send(Self::map_result(self.set_app_menu(request.handle)));
+            }
+            method::menu::SELECT_NEXT_WINDOW => {
+                reply.send(Self::map_result(self.select_next_window()));
+            }
+            method::menu::SELECT_PREVIOUS_WINDOW => {
+                reply.send(Self::map_result(self.select_previous_window()));
+            }
+            method::menu::BRING_ALL_TO_FRONT => {
+                reply.send(Self::map_result(self.bring_all_to_front()));
             }
             _ => {}
         };
     }
 }
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use velcro::hash_map;
+
+    use crate::{
+        codec::{MethodCall, MethodCallResult, MethodCodec, StandardMethodCodec, Value},
+        shell::{
+            api_constants::*,
+            api_model::{Accelerator, CheckStatus, Menu, MenuDiagnosticKind, MenuItem, MenuRole},
+            platform::recorder::{take_operations, MockTarget},
+            test_util::{mock_context, mock_context_with, platform_window},
+            ContextOptions, EngineOptions, WindowBuilder,
+        },
+    };
+
+    #[test]
+    fn test_create_menu_and_select_item() {
+        let context = mock_context();
+        let engine = context
+            .engine_manager
+            .borrow_mut()
+            .create_engine(&EngineOptions::default());
+        context
+            .engine_manager
+            .borrow_mut()
+            .launch_engine(engine)
+            .unwrap();
+        take_operations();
+
+        let item = Value::Map(hash_map! {
+            "id".into(): 10i64.into(),
+            "title".into(): "Item".into(),
+            "enabled".into(): true.into(),
+            "separator".into(): false.into(),
+            "checkStatus".into(): "none".into(),
+        });
+        let call = MethodCall {
+            method: method::menu::CREATE_OR_UPDATE.into(),
+            args: Value::Map(hash_map! {
+                "menu".into(): Value::Map(hash_map! {
+                    "items".into(): vec![item].into(),
+                }),
+            }),
+        };
+        let codec = &StandardMethodCodec;
+        let result = Rc::new(RefCell::new(None));
+        let result_copy = result.clone();
+        let engine_manager = context.engine_manager.borrow();
+        let platform_engine = &engine_manager.get_engine(engine).unwrap().platform_engine;
+        assert!(platform_engine.simulate_message(
+            channel::MENU_MANAGER,
+            &codec.encode_method_call(&call),
+            move |reply| {
+                result_copy.replace(codec.decode_envelope(reply));
+            },
+        ));
+
+        let handle = match result.borrow_mut().take() {
+            Some(MethodCallResult::Ok(Value::I64(handle))) => handle,
+            other => panic!("Unexpected result {:?}", other),
+        };
+        let operations = take_operations();
+        assert_eq!(operations.len(), 1);
+        assert_eq!(operations[0].target, MockTarget::Menu(handle));
+        assert_eq!(operations[0].name, "update_from_menu");
+
+        context
+            .menu_manager
+            .borrow()
+            .get_platform_menu(super::MenuHandle(handle))
+            .unwrap()
+            .simulate_item_selected(10);
+        let sent = platform_engine.take_sent_messages();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, channel::MENU_MANAGER);
+        let call = codec.decode_method_call(&sent[0].1).unwrap();
+        assert_eq!(call.method, method::menu::ON_ACTION);
+    }
+
+    #[test]
+    fn test_app_menu_from_rust() {
+        let context = mock_context();
+
+        let menu = || Menu {
+            role: None,
+            items: vec![MenuItem {
+                id: 1,
+                title: "Quit".into(),
+                enabled: true,
+                ..Default::default()
+            }],
+        };
+        let handle = context
+            .menu_manager
+            .borrow_mut()
+            .create_menu(menu(), |_| {})
+            .unwrap();
+        context
+            .menu_manager
+            .borrow()
+            .set_app_menu(Some(handle))
+            .unwrap();
+
+        let operations: Vec<_> = take_operations()
+            .into_iter()
+            .map(|o| (o.target, o.name))
+            .collect();
+        assert_eq!(
+            operations,
+            vec![
+                (MockTarget::Menu(handle.0), "update_from_menu"),
+                (MockTarget::MenuManager, "set_app_menu"),
+            ]
+        );
+
+        context
+            .menu_manager
+            .borrow_mut()
+            .update_menu(handle, menu())
+            .unwrap();
+        context
+            .menu_manager
+            .borrow_mut()
+            .destroy_menu(handle)
+            .unwrap();
+        assert!(context
+            .menu_manager
+            .borrow_mut()
+            .update_menu(handle, menu())
+            .is_err());
+    }
+
+    #[test]
+    fn test_accelerator_conflicts() {
+        let diagnostics = Rc::new(RefCell::new(Vec::new()));
+        let diagnostics_copy = diagnostics.clone();
+        let context = mock_context_with(ContextOptions {
+            on_menu_diagnostic: Box::new(move |diagnostic| {
+                diagnostics_copy.borrow_mut().push(diagnostic.clone());
+            }),
+            ..Default::default()
+        });
+
+        let item = |id, title: &str, accelerator: &str| MenuItem {
+            id,
+            title: title.into(),
+            enabled: true,
+            accelerator: Some(Accelerator::parse(accelerator)),
+            ..Default::default()
+        };
+        let mut menu_manager = context.menu_manager.borrow_mut();
+        let file = menu_manager
+            .create_menu(
+                Menu {
+                    role: None,
+                    items: vec![item(1, "&Save", "Ctrl+S"), item(2, "Close", "Alt+F4")],
+                },
+                |_| {},
+            )
+            .unwrap();
+        let root = menu_manager
+            .create_menu(
+                Menu {
+                    role: None,
+                    items: vec![
+                        MenuItem {
+                            id: 3,
+                            title: "&File".into(),
+                            submenu: Some(file),
+                            ..Default::default()
+                        },
+                        item(4, "Share", "Ctrl+s"),
+                    ],
+                },
+                |_| {},
+            )
+            .unwrap();
+        drop(menu_manager);
+        context
+            .run_loop
+            .borrow()
+            .as_virtual()
+            .unwrap()
+            .run_until_idle();
+
+        // Submenu is only checked as part of the whole tree
+        let diagnostics: Vec<_> = diagnostics
+            .borrow()
+            .iter()
+            .map(|d| (d.kind, d.menu, d.items.clone()))
+            .collect();
+        assert_eq!(
+            diagnostics,
+            vec![
+                (
+                    MenuDiagnosticKind::DuplicateAccelerator,
+                    root,
+                    vec!["File > Save".into(), "Share".into()]
+                ),
+                (
+                    MenuDiagnosticKind::SystemShortcut,
+                    root,
+                    vec!["File > Close".into()]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_window_menu_lists_windows() {
+        let context = mock_context();
+        let run_loop = context.run_loop.borrow();
+        let run_loop = run_loop.as_virtual().unwrap();
+
+        let menu = context
+            .menu_manager
+            .borrow_mut()
+            .create_menu(
+                Menu {
+                    role: Some(MenuRole::Window),
+                    items: vec![MenuItem {
+                        id: 1,
+                        title: "Minimize".into(),
+                        enabled: true,
+                        ..Default::default()
+                    }],
+                },
+                |_| {},
+            )
+            .unwrap();
+
+        let window_manager = context.window_manager.borrow();
+        let windows: Vec<_> = ["First", "Second"]
+            .iter()
+            .map(|title| {
+                let handle = window_manager.open_window(WindowBuilder::new().with_title(title));
+                let platform_window = platform_window(&context, handle);
+                platform_window.ready_to_show().unwrap();
+                window_manager.show_window(handle).unwrap();
+                platform_window
+            })
+            .collect();
+        windows[1].simulate(|d| d.activation_changed(true));
+        run_loop.run_until_idle();
+
+        let platform_menu = context
+            .menu_manager
+            .borrow()
+            .get_platform_menu(menu)
+            .unwrap();
+        let items: Vec<_> = platform_menu
+            .menu()
+            .as_ref()
+            .unwrap()
+            .items
+            .iter()
+            .map(|item| {
+                (
+                    item.title.clone(),
+                    item.separator,
+                    item.check_status.clone(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            items,
+            vec![
+                ("Minimize".into(), false, CheckStatus::None),
+                ("".into(), true, CheckStatus::None),
+                ("First".into(), false, CheckStatus::CheckOff),
+                ("Second".into(), false, CheckStatus::CheckOn),
+            ]
+        );
+
+        take_operations();
+        let first_item = platform_menu.menu().as_ref().unwrap().items[2].id;
+        platform_menu.simulate_item_selected(first_item);
+        let operations: Vec<_> = take_operations()
+            .into_iter()
+            .map(|o| (o.target, o.name))
+            .collect();
+        assert_eq!(
+            operations,
+            vec![(MockTarget::Window(windows[0].mock_id()), "activate")]
+        );
+
+        context.menu_manager.borrow().select_next_window().unwrap();
+        let operations: Vec<_> = take_operations()
+            .into_iter()
+            .map(|o| (o.target, o.name))
+            .collect();
+        assert_eq!(
+            operations,
+            vec![(MockTarget::Window(windows[0].mock_id()), "activate")]
+        );
+    }
+}
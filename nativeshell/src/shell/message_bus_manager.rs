@@ -0,0 +1,189 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+use crate::{
+    codec::{value::to_value, MethodCall, MethodCallError, MethodCallReply, Value},
+    util::OkLog,
+    Error, Result,
+};
+
+use super::{
+    api_constants::*,
+    api_model::{MessageBusMessage, MessageBusSubscribeRequest},
+    Context, EngineHandle, WindowMethodCallResult,
+};
+
+type MessageBusListener = dyn Fn(&MessageBusMessage);
+
+// Publish/subscribe bus for messages between engines (and Rust code). Messages
+// published to a topic are delivered to every engine subscribed to the topic
+// except the publisher, as well as to Rust listeners of the topic.
+pub struct MessageBusManager {
+    context: Rc<Context>,
+    subscriptions: RefCell<HashMap<EngineHandle, HashSet<String>>>,
+    listeners: RefCell<HashMap<String, Vec<(i64, Rc<MessageBusListener>)>>>,
+    next_listener_id: Cell<i64>,
+}
+
+impl MessageBusManager {
+    pub(super) fn new(context: Rc<Context>) -> Self {
+        let context_copy = context.clone();
+        context
+            .message_manager
+            .borrow_mut()
+            .register_method_handler(channel::MESSAGE_BUS, move |value, reply, engine| {
+                context_copy
+                    .message_bus_manager
+                    .borrow()
+                    .on_method_call(value, reply, engine);
+            });
+
+        Self {
+            context,
+            subscriptions: RefCell::new(HashMap::new()),
+            listeners: RefCell::new(HashMap::new()),
+            next_listener_id: Cell::new(1),
+        }
+    }
+
+    // Publishes message from Rust; Delivered to all subscribed engines
+    pub fn publish(&self, topic: &str, message: Value) {
+        self.dispatch(MessageBusMessage {
+            topic: topic.into(),
+            message,
+            source_engine: None,
+        });
+    }
+
+    // Registers Rust listener for given topic; Returns identifier that can be
+    // used to remove the listener
+    pub fn add_listener<F>(&self, topic: &str, listener: F) -> i64
+    where
+        F: Fn(&MessageBusMessage) + 'static,
+    {
+        let id = self
+            .next_listener_id
+            .replace(self.next_listener_id.get() + 1);
+        self.listeners
+            .borrow_mut()
+            .entry(topic.into())
+            .or_default()
+            .push((id, Rc::new(listener)));
+        id
+    }
+
+    pub fn remove_listener(&self, id: i64) {
+        let mut listeners = self.listeners.borrow_mut();
+        for topic_listeners in listeners.values_mut() {
+            topic_listeners.retain(|(listener_id, _)| *listener_id != id);
+        }
+        listeners.retain(|_, topic_listeners| !topic_listeners.is_empty());
+    }
+
+    // Drops subscriptions of given engine
+    pub(crate) fn engine_removed(&self, engine: EngineHandle) {
+        self.subscriptions.borrow_mut().remove(&engine);
+    }
+
+    fn dispatch(&self, message: MessageBusMessage) {
+        let source_engine = message.source_engine.map(EngineHandle);
+        let engines: Vec<EngineHandle> = self
+            .subscriptions
+            .borrow()
+            .iter()
+            .filter(|(engine, topics)| {
+                Some(**engine) != source_engine && topics.contains(&message.topic)
+            })
+            .map(|(engine, _)| *engine)
+            .collect();
+
+        // Rust listeners are still notified if the message can't be converted
+        let value = if engines.is_empty() {
+            None
+        } else {
+            to_value(&message).ok_log()
+        };
+        if let Some(value) = value {
+            let message_manager = self.context.message_manager.borrow();
+            for engine in engines {
+                if let Some(invoker) =
+                    message_manager.get_method_invoker(engine, channel::MESSAGE_BUS)
+                {
+                    invoker
+                        .call_method(
+                            method::message_bus::ON_MESSAGE.into(),
+                            value.clone(),
+                            |_| {},
+                        )
+                        .ok_log();
+                }
+            }
+        }
+
+        // Listeners may add or remove listeners
+        let listeners: Vec<Rc<MessageBusListener>> = self
+            .listeners
+            .borrow()
+            .get(&message.topic)
+            .map(|l| l.iter().map(|(_, listener)| listener.clone()).collect())
+            .unwrap_or_default();
+        for listener in listeners {
+            listener(&message);
+        }
+    }
+
+    fn map_result<T>(result: Result<T>) -> WindowMethodCallResult
+    where
+        T: serde::Serialize,
+    {
+        result
+            .map(|v| to_value(v).unwrap())
+            .map_err(MethodCallError::from_error)
+    }
+
+    fn on_method_call(
+        &self,
+        call: MethodCall<Value>,
+        reply: MethodCallReply<Value>,
+        engine: EngineHandle,
+    ) {
+        match call.method.as_str() {
+            method::message_bus::SUBSCRIBE => {
+                let res = call.args_as::<MessageBusSubscribeRequest>().map(|r| {
+                    self.subscriptions
+                        .borrow_mut()
+                        .entry(engine)
+                        .or_default()
+                        .insert(r.topic);
+                });
+                reply.send(Self::map_result(res.map_err(Error::from)));
+            }
+            method::message_bus::UNSUBSCRIBE => {
+                let res = call.args_as::<MessageBusSubscribeRequest>().map(|r| {
+                    let mut subscriptions = self.subscriptions.borrow_mut();
+                    if let Some(topics) = subscriptions.get_mut(&engine) {
+                        topics.remove(&r.topic);
+                        if topics.is_empty() {
+                            subscriptions.remove(&engine);
+                        }
+                    }
+                });
+                reply.send(Self::map_result(res.map_err(Error::from)));
+            }
+            method::message_bus::PUBLISH => {
+                let res = call.args_as::<MessageBusMessage>().map(|mut message| {
+                    message.source_engine = Some(engine.0);
+                    self.dispatch(message);
+                });
+                reply.send(Self::map_result(res.map_err(Error::from)));
+            }
+            _ => reply.send(Err(MethodCallError::from_code_message(
+                "not-implemented",
+                &format!("Unknown message bus method {}", call.method),
+            ))),
+        }
+    }
+}
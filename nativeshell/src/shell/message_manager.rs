@@ -1,23 +1,37 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{cell::RefCell, collections::HashMap, future::Future, rc::Rc};
 
 use crate::codec::{
-    MessageChannel, MessageReply, MessageSender, MethodCall, MethodCallReply, MethodChannel,
-    MethodInvoker, StandardMethodCodec, Value,
+    BinaryMessageChannel, BinaryMessageReply, BinaryMessageSender, EventChannel,
+    EventStreamHandler, MessageChannel, MessageCodec, MessageReply, MessageSender, MethodCall,
+    MethodCallReply, MethodCallResult, MethodChannel, MethodCodec, MethodInvoker,
+    StandardMethodCodec, Value,
 };
 
-use super::{Context, EngineHandle, EngineManager};
+use super::{
+    Context, EngineHandle, EngineManager, ThreadSafeMessageSender, ThreadSafeMethodInvoker,
+};
 
 type MessageCallback = dyn Fn(Value, MessageReply<Value>, EngineHandle);
+type BinaryMessageCallback = dyn Fn(&[u8], BinaryMessageReply, EngineHandle);
 type MethodCallback = dyn Fn(MethodCall<Value>, MethodCallReply<Value>, EngineHandle);
+type EventStreamHandlerFactory = dyn Fn(EngineHandle) -> Box<dyn EventStreamHandler<Value>>;
 
 pub struct MessageManager {
     context: Rc<Context>,
 
     message_channels: HashMap<EngineHandle, HashMap<String, MessageChannel<Value>>>,
     message_handlers: Rc<RefCell<HashMap<String, Box<MessageCallback>>>>,
+    message_codecs: HashMap<String, &'static dyn MessageCodec<Value>>,
+
+    binary_message_channels: HashMap<EngineHandle, HashMap<String, BinaryMessageChannel>>,
+    binary_message_handlers: Rc<RefCell<HashMap<String, Box<BinaryMessageCallback>>>>,
 
     method_channels: HashMap<EngineHandle, HashMap<String, MethodChannel<Value>>>,
     method_handlers: Rc<RefCell<HashMap<String, Box<MethodCallback>>>>,
+    method_codecs: HashMap<String, &'static dyn MethodCodec<Value>>,
+
+    event_channels: HashMap<EngineHandle, HashMap<String, EventChannel<Value>>>,
+    event_handler_factories: HashMap<String, Box<EventStreamHandlerFactory>>,
 }
 
 impl MessageManager {
@@ -26,31 +40,82 @@ impl MessageManager {
             context,
             message_channels: HashMap::new(),
             message_handlers: Rc::new(RefCell::new(HashMap::new())),
+            message_codecs: HashMap::new(),
+            binary_message_channels: HashMap::new(),
+            binary_message_handlers: Rc::new(RefCell::new(HashMap::new())),
             method_channels: HashMap::new(),
             method_handlers: Rc::new(RefCell::new(HashMap::new())),
+            method_codecs: HashMap::new(),
+            event_channels: HashMap::new(),
+            event_handler_factories: HashMap::new(),
         }
     }
 
     pub fn register_message_handler<F>(&mut self, channel: &str, callback: F)
     where
         F: Fn(Value, MessageReply<Value>, EngineHandle) + 'static,
+    {
+        self.register_message_handler_with_codec(channel, &StandardMethodCodec, callback);
+    }
+
+    // Registers message handler that uses specified codec (i.e. JsonMethodCodec)
+    // instead of the default StandardMethodCodec
+    pub fn register_message_handler_with_codec<F>(
+        &mut self,
+        channel: &str,
+        codec: &'static dyn MessageCodec<Value>,
+        callback: F,
+    ) where
+        F: Fn(Value, MessageReply<Value>, EngineHandle) + 'static,
     {
         let context = self.context.clone();
-        if !self
+        self.message_codecs.insert(channel.into(), codec);
+        if self
             .message_handlers
             .as_ref()
             .borrow()
             .contains_key(channel)
+        {
+            // codec might have changed; remove existing channels first
+            for entry in self.message_channels.values_mut() {
+                entry.remove(channel);
+            }
+        }
+
+        // register handlers on engines
+        let manager = context.engine_manager.borrow();
+        let engines = manager.get_all_engines();
+        for engine in engines {
+            self.register_message_channel_for_engine(&manager, engine, channel);
+        }
+
+        self.message_handlers
+            .as_ref()
+            .borrow_mut()
+            .insert(channel.into(), Box::new(callback));
+    }
+
+    // Like register_message_handler, but message data is passed directly without decoding
+    pub fn register_binary_message_handler<F>(&mut self, channel: &str, callback: F)
+    where
+        F: Fn(&[u8], BinaryMessageReply, EngineHandle) + 'static,
+    {
+        let context = self.context.clone();
+        if !self
+            .binary_message_handlers
+            .as_ref()
+            .borrow()
+            .contains_key(channel)
         {
             // register handlers on engines
             let manager = context.engine_manager.borrow();
             let engines = manager.get_all_engines();
             for engine in engines {
-                self.register_message_channel_for_engine(&manager, engine, channel);
+                self.register_binary_message_channel_for_engine(&manager, engine, channel);
             }
         }
 
-        self.message_handlers
+        self.binary_message_handlers
             .as_ref()
             .borrow_mut()
             .insert(channel.into(), Box::new(callback));
@@ -59,39 +124,125 @@ impl MessageManager {
     pub fn register_method_handler<F>(&mut self, channel: &str, callback: F)
     where
         F: Fn(MethodCall<Value>, MethodCallReply<Value>, EngineHandle) + 'static,
+    {
+        self.register_method_handler_with_codec(channel, &StandardMethodCodec, callback);
+    }
+
+    // Registers method handler that uses specified codec (i.e. JsonMethodCodec)
+    // instead of the default StandardMethodCodec
+    pub fn register_method_handler_with_codec<F>(
+        &mut self,
+        channel: &str,
+        codec: &'static dyn MethodCodec<Value>,
+        callback: F,
+    ) where
+        F: Fn(MethodCall<Value>, MethodCallReply<Value>, EngineHandle) + 'static,
     {
         let context = self.context.clone();
-        if !self.method_handlers.as_ref().borrow().contains_key(channel) {
-            // register handlers on engines
-            let manager = context.engine_manager.borrow();
-            let engines = manager.get_all_engines();
-            for engine in engines {
-                self.register_method_channel_for_engine(&manager, engine, channel);
+        self.method_codecs.insert(channel.into(), codec);
+        if self.method_handlers.as_ref().borrow().contains_key(channel) {
+            // codec might have changed; remove existing channels first
+            for entry in self.method_channels.values_mut() {
+                entry.remove(channel);
             }
         }
 
+        // register handlers on engines
+        let manager = context.engine_manager.borrow();
+        let engines = manager.get_all_engines();
+        for engine in engines {
+            self.register_method_channel_for_engine(&manager, engine, channel);
+        }
+
         self.method_handlers
             .as_ref()
             .borrow_mut()
             .insert(channel.into(), Box::new(callback));
     }
 
+    // Same as register_method_handler, except the callback returns a future
+    // resolving to method call result. The future is driven on main run loop
+    // and reply is sent once it completes.
+    pub fn register_async_method_handler<F, Fut>(&mut self, channel: &str, callback: F)
+    where
+        F: Fn(MethodCall<Value>, EngineHandle) -> Fut + 'static,
+        Fut: Future<Output = MethodCallResult<Value>> + 'static,
+    {
+        let context = Rc::downgrade(&self.context);
+        self.register_method_handler(channel, move |call, reply, engine| {
+            if let Some(context) = context.upgrade() {
+                let future = callback(call, engine);
+                context
+                    .run_loop
+                    .borrow()
+                    .spawn(async move {
+                        reply.send(future.await);
+                    })
+                    .detach();
+            }
+        });
+    }
+
+    // Registers Rust side of Flutter EventChannel. Factory is called for every engine to
+    // create stream handler for that engine.
+    pub fn register_event_stream_handler<F, H>(&mut self, channel: &str, factory: F)
+    where
+        F: Fn(EngineHandle) -> H + 'static,
+        H: EventStreamHandler<Value> + 'static,
+    {
+        // dropping existing channel would unregister the new engine handler
+        self.unregister_event_stream_handler(channel);
+
+        self.event_handler_factories.insert(
+            channel.into(),
+            Box::new(move |engine| Box::new(factory(engine))),
+        );
+
+        let context = self.context.clone();
+        let manager = context.engine_manager.borrow();
+        let engines = manager.get_all_engines();
+        for engine in engines {
+            self.register_event_channel_for_engine(&manager, engine, channel);
+        }
+    }
+
     pub fn unregister_message_handler(&mut self, channel: &str) {
         self.message_handlers.as_ref().borrow_mut().remove(channel);
+        self.message_codecs.remove(channel);
 
         for entry in self.message_channels.values_mut() {
             entry.remove(channel);
         }
     }
 
+    pub fn unregister_binary_message_handler(&mut self, channel: &str) {
+        self.binary_message_handlers
+            .as_ref()
+            .borrow_mut()
+            .remove(channel);
+
+        for entry in self.binary_message_channels.values_mut() {
+            entry.remove(channel);
+        }
+    }
+
     pub fn unregister_method_handler(&mut self, channel: &str) {
         self.method_handlers.as_ref().borrow_mut().remove(channel);
+        self.method_codecs.remove(channel);
 
         for entry in self.method_channels.values_mut() {
             entry.remove(channel);
         }
     }
 
+    pub fn unregister_event_stream_handler(&mut self, channel: &str) {
+        self.event_handler_factories.remove(channel);
+
+        for entry in self.event_channels.values_mut() {
+            entry.remove(channel);
+        }
+    }
+
     pub fn get_message_sender(
         &self,
         engine: EngineHandle,
@@ -103,6 +254,17 @@ impl MessageManager {
             .map(|e| e.sender().clone())
     }
 
+    pub fn get_binary_message_sender(
+        &self,
+        engine: EngineHandle,
+        channel: &str,
+    ) -> Option<BinaryMessageSender> {
+        self.binary_message_channels
+            .get(&engine)
+            .and_then(|e| e.get(channel))
+            .map(|e| e.sender().clone())
+    }
+
     pub fn get_method_invoker(
         &self,
         engine: EngineHandle,
@@ -114,6 +276,32 @@ impl MessageManager {
             .map(|e| e.invoker().clone())
     }
 
+    // Codec registered for the channel, or StandardMethodCodec if there is none
+    pub fn get_method_codec(&self, channel: &str) -> &'static dyn MethodCodec<Value> {
+        self.method_codecs
+            .get(channel)
+            .cloned()
+            .unwrap_or(&StandardMethodCodec)
+    }
+
+    // Returns invoker that can be used to call methods from any thread
+    pub fn get_thread_safe_method_invoker(
+        &self,
+        engine: EngineHandle,
+        channel: &str,
+    ) -> ThreadSafeMethodInvoker {
+        ThreadSafeMethodInvoker::new(&self.context, engine, channel)
+    }
+
+    // Returns sender that can be used to send messages from any thread
+    pub fn get_thread_safe_message_sender(
+        &self,
+        engine: EngineHandle,
+        channel: &str,
+    ) -> ThreadSafeMessageSender {
+        ThreadSafeMessageSender::new(&self.context, engine, channel)
+    }
+
     pub(super) fn engine_created(&mut self, engine_manager: &EngineManager, engine: EngineHandle) {
         let message_keys: Vec<String> = self
             .message_handlers
@@ -126,6 +314,17 @@ impl MessageManager {
             self.register_message_channel_for_engine(engine_manager, engine, &channel);
         }
 
+        let binary_message_keys: Vec<String> = self
+            .binary_message_handlers
+            .as_ref()
+            .borrow()
+            .keys()
+            .map(|s| s.into())
+            .collect();
+        for channel in binary_message_keys {
+            self.register_binary_message_channel_for_engine(engine_manager, engine, &channel);
+        }
+
         let method_keys: Vec<String> = self
             .method_handlers
             .as_ref()
@@ -137,6 +336,11 @@ impl MessageManager {
         for channel in method_keys {
             self.register_method_channel_for_engine(engine_manager, engine, &channel);
         }
+
+        let event_keys: Vec<String> = self.event_handler_factories.keys().cloned().collect();
+        for channel in event_keys {
+            self.register_event_channel_for_engine(engine_manager, engine, &channel);
+        }
     }
 
     fn on_message(
@@ -152,6 +356,19 @@ impl MessageManager {
         }
     }
 
+    fn on_binary_message(
+        handlers: Rc<RefCell<HashMap<String, Box<BinaryMessageCallback>>>>,
+        data: &[u8],
+        channel: &str,
+        reply: BinaryMessageReply,
+        engine: EngineHandle,
+    ) {
+        let handlers = handlers.as_ref().borrow();
+        if let Some(handler) = handlers.get(channel) {
+            handler(data, reply, engine);
+        }
+    }
+
     fn on_method(
         handlers: Rc<RefCell<HashMap<String, Box<MethodCallback>>>>,
         call: MethodCall<Value>,
@@ -173,11 +390,16 @@ impl MessageManager {
     ) {
         let channel_str = String::from(channel);
         let handlers = self.message_handlers.clone();
+        let codec = self
+            .message_codecs
+            .get(channel)
+            .cloned()
+            .unwrap_or(&StandardMethodCodec);
         let message_channel = MessageChannel::new_with_engine_manager(
             self.context.clone(),
             engine,
             channel,
-            &StandardMethodCodec,
+            codec,
             move |value, reply| {
                 Self::on_message(handlers.clone(), value, &channel_str, reply, engine);
             },
@@ -188,6 +410,29 @@ impl MessageManager {
         entry.insert(channel.into(), message_channel);
     }
 
+    fn register_binary_message_channel_for_engine(
+        &mut self,
+        engine_manager: &EngineManager,
+        engine: EngineHandle,
+        channel: &str,
+    ) {
+        let channel_str = String::from(channel);
+        let handlers = self.binary_message_handlers.clone();
+        let message_channel = BinaryMessageChannel::new_with_engine_manager(
+            self.context.clone(),
+            engine,
+            channel,
+            move |data, reply| {
+                Self::on_binary_message(handlers.clone(), data, &channel_str, reply, engine);
+            },
+            engine_manager,
+        );
+        self.binary_message_channels
+            .entry(engine)
+            .or_default()
+            .insert(channel.into(), message_channel);
+    }
+
     fn register_method_channel_for_engine(
         &mut self,
         engine_manager: &EngineManager,
@@ -196,11 +441,12 @@ impl MessageManager {
     ) {
         let channel_str = String::from(channel);
         let handlers = self.method_handlers.clone();
+        let codec = self.get_method_codec(channel);
         let method_channel = MethodChannel::new_with_engine_manager(
             self.context.clone(),
             engine,
             channel,
-            &StandardMethodCodec,
+            codec,
             move |call, reply| {
                 Self::on_method(handlers.clone(), call, &channel_str, reply, engine);
             },
@@ -210,4 +456,28 @@ impl MessageManager {
         let entry = map.or_insert_with(HashMap::new);
         entry.insert(channel.into(), method_channel);
     }
+
+    fn register_event_channel_for_engine(
+        &mut self,
+        engine_manager: &EngineManager,
+        engine: EngineHandle,
+        channel: &str,
+    ) {
+        let handler = match self.event_handler_factories.get(channel) {
+            Some(factory) => factory(engine),
+            None => return,
+        };
+        let event_channel = EventChannel::new_with_engine_manager(
+            self.context.clone(),
+            engine,
+            channel,
+            &StandardMethodCodec,
+            handler,
+            engine_manager,
+        );
+        self.event_channels
+            .entry(engine)
+            .or_default()
+            .insert(channel.into(), event_channel);
+    }
 }
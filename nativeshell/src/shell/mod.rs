@@ -1,31 +1,86 @@
+mod accessibility_manager;
 mod api_constants;
+mod application_manager;
 mod binary_messenger;
 mod bundle;
 mod context;
+mod crash_handler;
+mod display_manager;
 mod engine;
 mod engine_manager;
+mod event_tap_manager;
+mod feedback_manager;
 mod geometry;
+mod locale_manager;
 mod menu_manager;
+mod message_bus_manager;
 mod message_manager;
 mod observatory;
+mod permission_manager;
+mod platform_view_manager;
+mod power;
+mod power_manager;
+mod render_layer;
 mod run_loop;
+mod run_loop_instrumentation;
+mod screen_capture;
+mod system_theme_manager;
+mod test_driver;
+mod texture;
+mod thread_safe_sender;
+mod transfer_manager;
+mod user_activity_manager;
+mod virtual_run_loop;
+mod watchdog;
 mod window;
+mod window_builder;
+mod window_list_manager;
 mod window_manager;
 mod window_method_channel;
+mod workspace_manager;
 
+pub use accessibility_manager::*;
+pub use application_manager::*;
 pub use binary_messenger::*;
 pub use bundle::*;
 pub use context::*;
+pub use crash_handler::*;
+pub use display_manager::*;
 pub use engine::*;
 pub use engine_manager::*;
+pub use event_tap_manager::*;
+pub use feedback_manager::*;
 pub use geometry::*;
+pub use locale_manager::*;
 pub use menu_manager::*;
+pub use message_bus_manager::*;
 pub use message_manager::*;
 pub use observatory::*;
+pub use permission_manager::*;
+pub use platform_view_manager::*;
+pub use power::*;
+pub use power_manager::*;
+pub use render_layer::*;
 pub use run_loop::*;
+pub use run_loop_instrumentation::*;
+pub use screen_capture::*;
+pub use system_theme_manager::*;
+pub use test_driver::*;
+pub use texture::*;
+pub use thread_safe_sender::*;
+pub use transfer_manager::*;
+pub use user_activity_manager::*;
+pub use virtual_run_loop::*;
+pub use watchdog::*;
 pub use window::*;
+pub use window_builder::*;
+pub use window_list_manager::*;
 pub use window_manager::*;
 pub use window_method_channel::*;
+pub use workspace_manager::*;
 
 pub mod api_model;
 pub mod platform;
+
+#[cfg(all(test, feature = "mock"))]
+mod test_util;
@@ -0,0 +1,87 @@
+use std::rc::{Rc, Weak};
+
+use crate::{
+    codec::{value::from_value, MethodCall, MethodCallReply, Value},
+    util::Capsule,
+    Error, Result,
+};
+
+use super::{
+    api_constants::*,
+    api_model::{PermissionKind, PermissionStatus},
+    platform::permission,
+    Context, EngineHandle,
+};
+
+// Privacy permissions required by capture and input monitoring features.
+// Platforms without a permission model report everything as granted.
+pub struct PermissionManager {
+    context: Weak<Context>,
+}
+
+impl PermissionManager {
+    pub(super) fn new(context: Rc<Context>) -> Self {
+        let context_copy = context.clone();
+        context
+            .message_manager
+            .borrow_mut()
+            .register_method_handler(channel::PERMISSION, move |value, reply, engine| {
+                context_copy
+                    .permission_manager
+                    .borrow()
+                    .on_method_call(value, reply, engine);
+            });
+        Self {
+            context: Rc::downgrade(&context),
+        }
+    }
+
+    pub fn status(&self, kind: PermissionKind) -> Result<PermissionStatus> {
+        permission::status(kind).map_err(|e| e.into())
+    }
+
+    // Prompts user if the permission has not been determined yet; Callback is
+    // invoked on main thread. Screen recording and input monitoring changes
+    // only take effect after application restart on macOS.
+    pub fn request<F>(&self, kind: PermissionKind, on_done: F)
+    where
+        F: FnOnce(Result<PermissionStatus>) + 'static,
+    {
+        let context = match self.context.upgrade() {
+            Some(context) => context,
+            None => return,
+        };
+        let run_loop = context.run_loop.borrow();
+        let sender = run_loop.new_sender();
+        let mut on_done = Capsule::new_with_sender(on_done, run_loop.new_sender());
+        permission::request(
+            kind,
+            Box::new(move |res| {
+                sender.send(move || {
+                    if let Some(on_done) = on_done.take() {
+                        on_done(res.map_err(|e| e.into()));
+                    }
+                });
+            }),
+        );
+    }
+
+    fn on_method_call(
+        &self,
+        call: MethodCall<Value>,
+        reply: MethodCallReply<Value>,
+        _engine: EngineHandle,
+    ) {
+        let kind = from_value::<PermissionKind>(&call.args);
+        match call.method.as_str() {
+            method::permission::GET_STATUS => {
+                reply.send_result(kind.map_err(|e| e.into()).and_then(|k| self.status(k)));
+            }
+            method::permission::REQUEST => match kind {
+                Ok(kind) => self.request(kind, move |res| reply.send_result(res)),
+                Err(err) => reply.send_result::<(), Error>(Err(err.into())),
+            },
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+
+use glib::SignalHandlerId;
+use gtk::SettingsExt;
+
+use crate::shell::{api_model::AccessibilitySettings, Context};
+
+use super::error::{PlatformError, PlatformResult};
+
+pub struct PlatformAccessibility {
+    context: Weak<Context>,
+    notify_handlers: RefCell<Vec<SignalHandlerId>>,
+}
+
+// Default cursor size used by GTK when gtk-cursor-theme-size is not set
+const DEFAULT_CURSOR_SIZE: f64 = 24.0;
+
+impl PlatformAccessibility {
+    pub fn new(context: Rc<Context>) -> Self {
+        Self {
+            context: Rc::downgrade(&context),
+            notify_handlers: RefCell::new(Vec::new()),
+        }
+    }
+
+    // GTK is not initialized when this object is created, so start observing
+    // settings changes on first query
+    fn observe_settings(&self, settings: &gtk::Settings) {
+        let mut handlers = self.notify_handlers.borrow_mut();
+        if !handlers.is_empty() {
+            return;
+        }
+        let context = self.context.clone();
+        let on_change = Rc::new(move || {
+            if let Some(context) = context.upgrade() {
+                context.accessibility_manager.borrow().settings_changed();
+            }
+        });
+        let on_change_copy = on_change.clone();
+        handlers.push(
+            settings.connect_property_gtk_enable_animations_notify(move |_| on_change_copy()),
+        );
+        let on_change_copy = on_change.clone();
+        handlers.push(settings.connect_property_gtk_theme_name_notify(move |_| on_change_copy()));
+        handlers.push(settings.connect_property_gtk_cursor_theme_size_notify(move |_| on_change()));
+    }
+
+    pub fn get_settings(&self) -> PlatformResult<AccessibilitySettings> {
+        let settings = gtk::Settings::get_default().ok_or(PlatformError::UnknownError)?;
+        self.observe_settings(&settings);
+
+        let theme_name = settings
+            .get_property_gtk_theme_name()
+            .map(|s| s.to_string())
+            .unwrap_or_default();
+        let cursor_size = settings.get_property_gtk_cursor_theme_size();
+
+        Ok(AccessibilitySettings {
+            full_keyboard_access: false,
+            reduce_motion: !settings.get_property_gtk_enable_animations(),
+            reduce_transparency: false,
+            high_contrast: theme_name.contains("HighContrast"),
+            cursor_size: if cursor_size > 0 {
+                cursor_size as f64 / DEFAULT_CURSOR_SIZE
+            } else {
+                1.0
+            },
+        })
+    }
+}
@@ -0,0 +1,318 @@
+use std::{
+    cell::Cell,
+    rc::{Rc, Weak},
+};
+
+use gio::{DBusConnection, DBusSignalFlags, FileCreateFlags, FileExt, SignalSubscriptionId};
+use glib::{ToVariant, Variant};
+use log::warn;
+
+use crate::shell::{
+    api_model::{ActivationPolicy, JumpList, TerminationReason, UserActivity},
+    Context, START_HIDDEN_ARGUMENT,
+};
+
+use super::{
+    error::{PlatformError, PlatformResult},
+    utils::{application_id, dbus_call, dbus_child_value, dbus_object_path},
+};
+
+const SESSION_MANAGER: &str = "org.gnome.SessionManager";
+const SESSION_MANAGER_PATH: &str = "/org/gnome/SessionManager";
+const CLIENT_PRIVATE: &str = "org.gnome.SessionManager.ClientPrivate";
+
+// GsmInhibitorFlag
+const INHIBIT_LOGOUT: u32 = 1;
+
+// URLs and files are passed to the application as command line arguments.
+// Application is registered as session manager client to be asked whether
+// the session may end.
+pub struct PlatformApplication {
+    connection: Option<DBusConnection>,
+    client: Option<SessionClient>,
+    inhibit_cookie: Cell<Option<u32>>,
+}
+
+struct SessionClient {
+    path: String,
+    subscription: Option<SignalSubscriptionId>,
+}
+
+impl PlatformApplication {
+    pub fn new(context: Rc<Context>) -> Self {
+        let connection = gio::bus_get_sync(gio::BusType::Session, None::<&gio::Cancellable>)
+            .map_err(|e| warn!("Could not connect to session bus: {}", e))
+            .ok();
+        let client = connection
+            .as_ref()
+            .and_then(|connection| SessionClient::register(connection, Rc::downgrade(&context)));
+        Self {
+            connection,
+            client,
+            inhibit_cookie: Cell::new(None),
+        }
+    }
+
+    // Inhibits logout while set; Reason is shown by the session manager
+    pub fn set_prevent_sudden_termination(
+        &self,
+        prevent: bool,
+        reason: Option<&str>,
+    ) -> PlatformResult<()> {
+        let connection = match &self.connection {
+            Some(connection) => connection,
+            None => return Ok(()),
+        };
+        if let Some(cookie) = self.inhibit_cookie.take() {
+            call(
+                connection,
+                SESSION_MANAGER_PATH,
+                SESSION_MANAGER,
+                "Uninhibit",
+                &[cookie.to_variant()],
+            )?;
+        }
+        if prevent {
+            let res = call(
+                connection,
+                SESSION_MANAGER_PATH,
+                SESSION_MANAGER,
+                "Inhibit",
+                &[
+                    application_id().to_variant(),
+                    0u32.to_variant(),
+                    reason.unwrap_or("").to_variant(),
+                    INHIBIT_LOGOUT.to_variant(),
+                ],
+            )?;
+            self.inhibit_cookie
+                .set(dbus_child_value(&res, 0).get::<u32>());
+        }
+        Ok(())
+    }
+
+    // Application is started at login through XDG autostart entry
+    pub fn launch_at_login(&self, id: &str) -> PlatformResult<bool> {
+        Ok(autostart_file(id)?.query_exists(None::<&gio::Cancellable>))
+    }
+
+    pub fn set_launch_at_login(
+        &self,
+        id: &str,
+        enabled: bool,
+        start_hidden: bool,
+    ) -> PlatformResult<()> {
+        let file = autostart_file(id)?;
+        if !enabled {
+            if file.query_exists(None::<&gio::Cancellable>) {
+                file.delete(None::<&gio::Cancellable>).map_err(glib_error)?;
+            }
+            return Ok(());
+        }
+        if let Some(parent) = file.get_parent() {
+            if !parent.query_exists(None::<&gio::Cancellable>) {
+                parent
+                    .make_directory_with_parents(None::<&gio::Cancellable>)
+                    .map_err(glib_error)?;
+            }
+        }
+        let executable = std::env::current_exe().map_err(|e| PlatformError::GLibError {
+            message: e.to_string(),
+        })?;
+        let mut exec = exec_argument(&executable.to_string_lossy());
+        if start_hidden {
+            exec.push(' ');
+            exec.push_str(START_HIDDEN_ARGUMENT);
+        }
+        let contents = format!(
+            "[Desktop Entry]\nType=Application\nName={}\nExec={}\nX-GNOME-Autostart-enabled=true\n",
+            id, exec
+        );
+        file.replace_contents(
+            contents.as_bytes(),
+            None,
+            false,
+            FileCreateFlags::NONE,
+            None::<&gio::Cancellable>,
+        )
+        .map_err(glib_error)?;
+        Ok(())
+    }
+
+    // Start hidden flag is passed as command line argument
+    pub fn started_hidden(&self) -> bool {
+        false
+    }
+
+    pub fn notify_startup_complete(&self) {
+        gdk::notify_startup_complete();
+    }
+
+    // Jump lists are Windows only
+    pub fn set_jump_list(&self, _jump_list: &JumpList) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    // AppUserModelID is Windows only
+    pub fn set_app_user_model_id(&self, _id: &str) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    // Handoff is MacOS only
+    pub fn set_user_activity(&self, _activity: Option<UserActivity>) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    // Taskbar visibility is set on individual windows
+    pub fn set_activation_policy(&self, _policy: ActivationPolicy) -> PlatformResult<()> {
+        Ok(())
+    }
+
+    // Application manager stops the run loop
+    pub fn terminate(&self) {}
+
+    // Hiding application is macOS only
+    pub fn hide_application(&self) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn unhide_application(&self) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn hide_other_applications(&self) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+}
+
+fn autostart_file(id: &str) -> PlatformResult<gio::File> {
+    let config_dir = glib::get_user_config_dir().ok_or(PlatformError::UnknownError)?;
+    Ok(gio::File::new_for_path(
+        config_dir.join("autostart").join(format!("{}.desktop", id)),
+    ))
+}
+
+// Quoted as required by desktop entry Exec key; Backslashes are then escaped
+// again as for any desktop entry string value
+fn exec_argument(argument: &str) -> String {
+    let mut res = String::from("\"");
+    for c in argument.chars() {
+        if matches!(c, '"' | '`' | '$' | '\\') {
+            res.push('\\');
+        }
+        res.push(c);
+    }
+    res.push('"');
+    res.replace('\\', "\\\\")
+}
+
+fn glib_error(error: glib::Error) -> PlatformError {
+    PlatformError::GLibError {
+        message: error.to_string(),
+    }
+}
+
+impl Drop for PlatformApplication {
+    fn drop(&mut self) {
+        if let (Some(connection), Some(mut client)) = (&self.connection, self.client.take()) {
+            if let Some(subscription) = client.subscription.take() {
+                connection.signal_unsubscribe(subscription);
+            }
+            let path = dbus_object_path(&client.path);
+            call(
+                connection,
+                SESSION_MANAGER_PATH,
+                SESSION_MANAGER,
+                "UnregisterClient",
+                &[path],
+            )
+            .ok();
+        }
+    }
+}
+
+impl SessionClient {
+    fn register(connection: &DBusConnection, context: Weak<Context>) -> Option<Self> {
+        let startup_id = std::env::var("DESKTOP_AUTOSTART_ID").unwrap_or_default();
+        let res = call(
+            connection,
+            SESSION_MANAGER_PATH,
+            SESSION_MANAGER,
+            "RegisterClient",
+            &[application_id().to_variant(), startup_id.to_variant()],
+        )
+        .ok()?;
+        let path: String = dbus_child_value(&res, 0).get_str()?.into();
+
+        let subscription = connection.signal_subscribe(
+            Some(SESSION_MANAGER),
+            Some(CLIENT_PRIVATE),
+            None,
+            Some(&path),
+            None,
+            DBusSignalFlags::NONE,
+            move |connection, _sender, path, _interface, signal, _parameters| {
+                Self::on_signal(&context, connection, path, signal);
+            },
+        );
+        Some(Self {
+            path,
+            subscription: Some(subscription),
+        })
+    }
+
+    fn on_signal(context: &Weak<Context>, connection: &DBusConnection, path: &str, signal: &str) {
+        let context = match context.upgrade() {
+            Some(context) => context,
+            None => return,
+        };
+        match signal {
+            "QueryEndSession" => {
+                let connection = connection.clone();
+                let path = path.to_string();
+                context
+                    .application_manager
+                    .borrow()
+                    .should_terminate(TerminationReason::SessionEnd, move |allowed| {
+                        end_session_response(&connection, &path, allowed)
+                    });
+            }
+            "EndSession" => end_session_response(connection, path, true),
+            "Stop" => context.run_loop.borrow().stop(),
+            _ => {}
+        }
+    }
+}
+
+fn end_session_response(connection: &DBusConnection, path: &str, allowed: bool) {
+    let reason = if allowed {
+        ""
+    } else {
+        "Cancelled by application"
+    };
+    call(
+        connection,
+        path,
+        CLIENT_PRIVATE,
+        "EndSessionResponse",
+        &[allowed.to_variant(), reason.to_variant()],
+    )
+    .ok();
+}
+
+fn call(
+    connection: &DBusConnection,
+    path: &str,
+    interface: &str,
+    method: &str,
+    arguments: &[Variant],
+) -> PlatformResult<Variant> {
+    dbus_call(
+        connection,
+        SESSION_MANAGER,
+        path,
+        interface,
+        method,
+        arguments,
+    )
+}
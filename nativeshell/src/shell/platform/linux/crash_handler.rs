@@ -0,0 +1,52 @@
+use gtk::{DialogExt, MessageType, ResponseType};
+
+use crate::shell::CrashDialogResponse;
+
+use super::error::{PlatformError, PlatformResult};
+
+// There is no equivalent of uncaught exception handler; Fatal signals are left
+// to the default handler
+#[allow(unused_variables)]
+pub fn install_exception_handler(handler: fn(String, String)) {}
+
+// Requires GTK to be initialized (crash happened after Context was created)
+pub fn show_crash_dialog(
+    title: &str,
+    message: &str,
+    can_open_log_file: bool,
+) -> PlatformResult<CrashDialogResponse> {
+    if !gtk::is_initialized_main_thread() {
+        return Err(PlatformError::NotImplemented);
+    }
+    let dialog = gtk::MessageDialogBuilder::new()
+        .message_type(MessageType::Error)
+        .text(title)
+        .secondary_text(message)
+        .modal(true)
+        .build();
+    let mut responses = vec![CrashDialogResponse::Quit, CrashDialogResponse::CopyDetails];
+    if can_open_log_file {
+        responses.push(CrashDialogResponse::OpenLogFile);
+    }
+    for (index, response) in responses.iter().enumerate() {
+        let title = match response {
+            CrashDialogResponse::Quit => "Quit",
+            CrashDialogResponse::CopyDetails => "Copy Details and Quit",
+            CrashDialogResponse::OpenLogFile => "Open Log and Quit",
+        };
+        dialog.add_button(title, ResponseType::Other(index as u16));
+    }
+    let res = match dialog.run() {
+        ResponseType::Other(index) => responses.get(index as usize).cloned(),
+        _ => None,
+    };
+    Ok(res.unwrap_or(CrashDialogResponse::Quit))
+}
+
+pub fn copy_to_clipboard(text: &str) -> PlatformResult<()> {
+    let clipboard = gtk::Clipboard::get(&gdk::SELECTION_CLIPBOARD);
+    clipboard.set_text(text);
+    // Hand contents over to clipboard manager, process is about to exit
+    clipboard.store();
+    Ok(())
+}
@@ -0,0 +1,492 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    rc::{Rc, Weak},
+};
+
+use gio::{DBusConnection, DBusMethodInvocation, DBusNodeInfo, RegistrationId};
+use glib::Cast;
+use glib::{translate::ToGlibPtr, ToVariant, Variant};
+use gtk::{GtkWindowExt, WidgetExt};
+
+use crate::shell::{
+    api_model::{CheckStatus, MenuItem},
+    Context, MenuHandle, ScheduledCallback,
+};
+
+use super::{
+    error::{PlatformError, PlatformResult},
+    menu::PlatformMenu,
+    utils::{
+        dbus_array, dbus_call, dbus_child_value, dbus_children, dbus_dict_entry, dbus_object_path,
+        dbus_tuple, dbus_variant, get_session_type, symbol, SessionType,
+    },
+};
+
+const MENU_PATH: &str = "/MenuBar";
+const MENU_INTERFACE: &str = "com.canonical.dbusmenu";
+
+const REGISTRAR: &str = "com.canonical.AppMenu.Registrar";
+const REGISTRAR_PATH: &str = "/com/canonical/AppMenu/Registrar";
+
+const MENU_INTERFACE_XML: &str = r#"
+<node>
+  <interface name="com.canonical.dbusmenu">
+    <property name="Version" type="u" access="read"/>
+    <property name="TextDirection" type="s" access="read"/>
+    <property name="Status" type="s" access="read"/>
+    <property name="IconThemePath" type="as" access="read"/>
+    <method name="GetLayout">
+      <arg type="i" name="parentId" direction="in"/>
+      <arg type="i" name="recursionDepth" direction="in"/>
+      <arg type="as" name="propertyNames" direction="in"/>
+      <arg type="u" name="revision" direction="out"/>
+      <arg type="(ia{sv}av)" name="layout" direction="out"/>
+    </method>
+    <method name="GetGroupProperties">
+      <arg type="ai" name="ids" direction="in"/>
+      <arg type="as" name="propertyNames" direction="in"/>
+      <arg type="a(ia{sv})" name="properties" direction="out"/>
+    </method>
+    <method name="GetProperty">
+      <arg type="i" name="id" direction="in"/>
+      <arg type="s" name="name" direction="in"/>
+      <arg type="v" name="value" direction="out"/>
+    </method>
+    <method name="Event">
+      <arg type="i" name="id" direction="in"/>
+      <arg type="s" name="eventId" direction="in"/>
+      <arg type="v" name="data" direction="in"/>
+      <arg type="u" name="timestamp" direction="in"/>
+    </method>
+    <method name="EventGroup">
+      <arg type="a(isvu)" name="events" direction="in"/>
+      <arg type="ai" name="idErrors" direction="out"/>
+    </method>
+    <method name="AboutToShow">
+      <arg type="i" name="id" direction="in"/>
+      <arg type="b" name="needUpdate" direction="out"/>
+    </method>
+    <method name="AboutToShowGroup">
+      <arg type="ai" name="ids" direction="in"/>
+      <arg type="ai" name="updatesNeeded" direction="out"/>
+      <arg type="ai" name="idErrors" direction="out"/>
+    </method>
+    <signal name="LayoutUpdated">
+      <arg type="u" name="revision"/>
+      <arg type="i" name="parent"/>
+    </signal>
+  </interface>
+</node>
+"#;
+
+type GdkX11WindowGetXid = unsafe extern "C" fn(*mut gdk_sys::GdkWindow) -> std::os::raw::c_ulong;
+
+thread_local! {
+    // D-Bus callbacks must be Send + Sync, so they find exported menu through here
+    static CURRENT: RefCell<Weak<DBusMenu>> = const { RefCell::new(Weak::new()) };
+}
+
+// Application menu exported on session bus through com.canonical.dbusmenu;
+// Desktops with global menu (KDE, Unity) find it through window properties
+// or AppMenu registrar (X11 only).
+pub struct DBusMenu {
+    context: Rc<Context>,
+    connection: DBusConnection,
+    registration: RefCell<Option<RegistrationId>>,
+    menu: RefCell<Rc<PlatformMenu>>,
+    revision: Cell<u32>,
+    // (menu, item id) -> D-Bus item id; 0 is root
+    ids: RefCell<HashMap<(MenuHandle, i64), i32>>,
+    items: RefCell<HashMap<i32, (MenuHandle, MenuItem)>>,
+    exported_menus: RefCell<HashSet<MenuHandle>>,
+    update_handle: RefCell<Option<ScheduledCallback>>,
+}
+
+impl DBusMenu {
+    pub fn new(context: Rc<Context>, menu: Rc<PlatformMenu>) -> PlatformResult<Rc<Self>> {
+        let connection = gio::bus_get_sync(gio::BusType::Session, None::<&gio::Cancellable>)
+            .map_err(glib_error)?;
+        let res = Rc::new(Self {
+            context,
+            connection,
+            registration: RefCell::new(None),
+            menu: RefCell::new(menu),
+            revision: Cell::new(1),
+            ids: RefCell::new(HashMap::new()),
+            items: RefCell::new(HashMap::new()),
+            exported_menus: RefCell::new(HashSet::new()),
+            update_handle: RefCell::new(None),
+        });
+        res.register()?;
+        CURRENT.with(|current| current.replace(Rc::downgrade(&res)));
+        for window in gtk::Window::list_toplevels() {
+            if let Ok(window) = window.downcast::<gtk::Window>() {
+                res.register_window(&window);
+            }
+        }
+        Ok(res)
+    }
+
+    fn current() -> Option<Rc<DBusMenu>> {
+        CURRENT.with(|current| current.borrow().upgrade())
+    }
+
+    fn register(&self) -> PlatformResult<()> {
+        let info = DBusNodeInfo::new_for_xml(MENU_INTERFACE_XML).map_err(glib_error)?;
+        let interface = info
+            .lookup_interface(MENU_INTERFACE)
+            .ok_or(PlatformError::UnknownError)?;
+        let registration = self
+            .connection
+            .register_object(
+                MENU_PATH,
+                &interface,
+                |_, _, _, _, method, parameters, invocation| {
+                    if let Some(menu) = Self::current() {
+                        menu.on_method_call(method, &parameters, &invocation);
+                    }
+                },
+                |_, _, _, _, property| match property {
+                    "Version" => 3u32.to_variant(),
+                    "TextDirection" => "ltr".to_variant(),
+                    "Status" => "normal".to_variant(),
+                    _ => dbus_array("s", &[]),
+                },
+                |_, _, _, _, _, _| false,
+            )
+            .map_err(glib_error)?;
+        self.registration.borrow_mut().replace(registration);
+        Ok(())
+    }
+
+    pub fn set_menu(&self, menu: Rc<PlatformMenu>) {
+        *self.menu.borrow_mut() = menu;
+        self.exported_menus.borrow_mut().clear();
+        self.schedule_layout_update();
+    }
+
+    // Called after any menu is updated
+    pub fn menu_changed(menu: MenuHandle) {
+        if let Some(current) = Self::current() {
+            if current.exported_menus.borrow().contains(&menu) {
+                current.schedule_layout_update();
+            }
+        }
+    }
+
+    // Called when new window is realized
+    pub fn window_created(window: &gtk::Window) {
+        if let Some(current) = Self::current() {
+            current.register_window(window);
+        }
+    }
+
+    fn schedule_layout_update(&self) {
+        let callback = self.context.run_loop.borrow().schedule_now(|| {
+            if let Some(current) = Self::current() {
+                current.layout_updated();
+            }
+        });
+        self.update_handle.borrow_mut().replace(callback);
+    }
+
+    fn layout_updated(&self) {
+        self.revision.set(self.revision.get() + 1);
+        self.connection
+            .emit_signal(
+                None,
+                MENU_PATH,
+                MENU_INTERFACE,
+                "LayoutUpdated",
+                Some(&dbus_tuple(&[
+                    self.revision.get().to_variant(),
+                    0.to_variant(),
+                ])),
+            )
+            .ok();
+    }
+
+    fn register_window(&self, window: &gtk::Window) {
+        let gdk_window = match window.get_window() {
+            Some(gdk_window) => gdk_window,
+            None => return,
+        };
+        if window.get_window_type() != gtk::WindowType::Toplevel
+            || get_session_type() != SessionType::X11
+        {
+            return;
+        }
+        // KDE
+        if let Some(name) = self.connection.get_unique_name() {
+            set_string_property(&gdk_window, b"_KDE_NET_WM_APPMENU_SERVICE_NAME\0", &name);
+            set_string_property(&gdk_window, b"_KDE_NET_WM_APPMENU_OBJECT_PATH\0", MENU_PATH);
+        }
+        // Unity and other desktops running registrar service; May not be present
+        if let Some(xid) = unsafe { x11_window_id(&gdk_window) } {
+            dbus_call(
+                &self.connection,
+                REGISTRAR,
+                REGISTRAR_PATH,
+                REGISTRAR,
+                "RegisterWindow",
+                &[(xid as u32).to_variant(), dbus_object_path(MENU_PATH)],
+            )
+            .ok();
+        }
+    }
+
+    fn on_method_call(
+        &self,
+        method: &str,
+        parameters: &Variant,
+        invocation: &DBusMethodInvocation,
+    ) {
+        let res = match method {
+            "GetLayout" => {
+                let parent = dbus_child_value(parameters, 0).get::<i32>().unwrap_or(0);
+                let depth = dbus_child_value(parameters, 1).get::<i32>().unwrap_or(-1);
+                let layout = self.layout(parent, depth);
+                dbus_tuple(&[self.revision.get().to_variant(), layout])
+            }
+            "GetGroupProperties" => {
+                let mut ids: Vec<i32> = dbus_children(&dbus_child_value(parameters, 0))
+                    .iter()
+                    .filter_map(|id| id.get::<i32>())
+                    .collect();
+                if ids.is_empty() {
+                    ids = self.items.borrow().keys().cloned().collect();
+                }
+                let properties: Vec<_> = ids
+                    .iter()
+                    .filter_map(|id| {
+                        let items = self.items.borrow();
+                        let (_, item) = items.get(id)?;
+                        Some(dbus_tuple(&[id.to_variant(), self.item_properties(item)]))
+                    })
+                    .collect();
+                dbus_tuple(&[dbus_array("(ia{sv})", &properties)])
+            }
+            "GetProperty" => {
+                let id = dbus_child_value(parameters, 0).get::<i32>().unwrap_or(0);
+                let name = dbus_child_value(parameters, 1)
+                    .get::<String>()
+                    .unwrap_or_default();
+                let value = self.items.borrow().get(&id).and_then(|(_, item)| {
+                    Self::properties(item)
+                        .into_iter()
+                        .find(|p| p.0 == name)
+                        .map(|p| p.1)
+                });
+                match value {
+                    Some(value) => dbus_tuple(&[dbus_variant(&value)]),
+                    None => {
+                        invocation.return_dbus_error(
+                            "com.canonical.dbusmenu.UnknownProperty",
+                            "Unknown property",
+                        );
+                        return;
+                    }
+                }
+            }
+            "Event" => {
+                self.on_event(parameters);
+                dbus_tuple(&[])
+            }
+            "EventGroup" => {
+                for event in dbus_children(&dbus_child_value(parameters, 0)) {
+                    self.on_event(&event);
+                }
+                dbus_tuple(&[dbus_array("i", &[])])
+            }
+            "AboutToShow" => dbus_tuple(&[false.to_variant()]),
+            "AboutToShowGroup" => dbus_tuple(&[dbus_array("i", &[]), dbus_array("i", &[])]),
+            _ => {
+                invocation.return_dbus_error(
+                    "org.freedesktop.DBus.Error.UnknownMethod",
+                    "Unknown method",
+                );
+                return;
+            }
+        };
+        invocation.return_value(Some(&res));
+    }
+
+    // Event is (isvu) tuple
+    fn on_event(&self, event: &Variant) {
+        let id = dbus_child_value(event, 0).get::<i32>().unwrap_or(0);
+        let event_id = dbus_child_value(event, 1)
+            .get::<String>()
+            .unwrap_or_default();
+        if event_id != "clicked" {
+            return;
+        }
+        let item = self.items.borrow().get(&id).cloned();
+        if let Some((menu, item)) = item {
+            if item.submenu.is_none() {
+                self.context
+                    .menu_manager
+                    .borrow()
+                    .on_menu_action(menu, item.id);
+            }
+        }
+    }
+
+    // Layout of item with given id as (ia{sv}av)
+    fn layout(&self, id: i32, depth: i32) -> Variant {
+        let (properties, submenu) = if id == 0 {
+            let properties = vec![("children-display", "submenu".to_variant())];
+            (properties, Some(self.menu.borrow().handle))
+        } else {
+            match self.items.borrow().get(&id) {
+                Some((_, item)) => (Self::properties(item), item.submenu),
+                None => (Vec::new(), None),
+            }
+        };
+        let children = match submenu {
+            Some(submenu) if depth != 0 => self
+                .menu_items(submenu)
+                .iter()
+                .map(|child| dbus_variant(&self.layout(*child, depth - 1)))
+                .collect(),
+            _ => Vec::new(),
+        };
+        dbus_tuple(&[
+            id.to_variant(),
+            Self::properties_dict(properties),
+            dbus_array("v", &children),
+        ])
+    }
+
+    // Assigns D-Bus ids to items of given menu
+    fn menu_items(&self, menu: MenuHandle) -> Vec<i32> {
+        let platform_menu = if self.menu.borrow().handle == menu {
+            Some(self.menu.borrow().clone())
+        } else {
+            self.context
+                .menu_manager
+                .borrow()
+                .get_platform_menu(menu)
+                .ok()
+        };
+        let platform_menu = match platform_menu {
+            Some(platform_menu) => platform_menu,
+            None => return Vec::new(),
+        };
+        self.exported_menus.borrow_mut().insert(menu);
+        let items = platform_menu.previous_menu.borrow().items.clone();
+        items
+            .into_iter()
+            .map(|item| {
+                let mut ids = self.ids.borrow_mut();
+                let next_id = ids.len() as i32 + 1;
+                let id = *ids.entry((menu, item.id)).or_insert(next_id);
+                self.items.borrow_mut().insert(id, (menu, item));
+                id
+            })
+            .collect()
+    }
+
+    fn item_properties(&self, item: &MenuItem) -> Variant {
+        Self::properties_dict(Self::properties(item))
+    }
+
+    fn properties_dict(properties: Vec<(&str, Variant)>) -> Variant {
+        let entries: Vec<_> = properties
+            .iter()
+            .map(|(name, value)| dbus_dict_entry(&name.to_variant(), &dbus_variant(value)))
+            .collect();
+        dbus_array("{sv}", &entries)
+    }
+
+    fn properties(item: &MenuItem) -> Vec<(&'static str, Variant)> {
+        if item.separator {
+            return vec![("type", "separator".to_variant())];
+        }
+        let mut res = vec![
+            (
+                "label",
+                PlatformMenu::convert_mnemonics(&item.title).to_variant(),
+            ),
+            ("enabled", item.enabled.to_variant()),
+        ];
+        match item.check_status {
+            CheckStatus::None => {}
+            CheckStatus::CheckOn | CheckStatus::CheckOff => {
+                res.push(("toggle-type", "checkmark".to_variant()));
+            }
+            CheckStatus::RadioOn | CheckStatus::RadioOff => {
+                res.push(("toggle-type", "radio".to_variant()));
+            }
+        }
+        match item.check_status {
+            CheckStatus::CheckOn | CheckStatus::RadioOn => {
+                res.push(("toggle-state", 1.to_variant()));
+            }
+            CheckStatus::CheckOff | CheckStatus::RadioOff => {
+                res.push(("toggle-state", 0.to_variant()));
+            }
+            CheckStatus::None => {}
+        }
+        if item.submenu.is_some() {
+            res.push(("children-display", "submenu".to_variant()));
+        }
+        if let Some(accelerator) = &item.accelerator {
+            let mut keys = Vec::new();
+            if accelerator.control {
+                keys.push("Control".to_variant());
+            }
+            if accelerator.alt {
+                keys.push("Alt".to_variant());
+            }
+            if accelerator.shift {
+                keys.push("Shift".to_variant());
+            }
+            if accelerator.meta {
+                keys.push("Super".to_variant());
+            }
+            let key = PlatformMenu::accelerator_label_code(accelerator) as u32;
+            if let Some(key) = gdk::keyval_name(key) {
+                keys.push(key.as_str().to_variant());
+                res.push(("shortcut", dbus_array("as", &[dbus_array("s", &keys)])));
+            }
+        }
+        res
+    }
+}
+
+impl Drop for DBusMenu {
+    fn drop(&mut self) {
+        if let Some(registration) = self.registration.borrow_mut().take() {
+            self.connection.unregister_object(registration).ok();
+        }
+    }
+}
+
+fn glib_error(e: glib::Error) -> PlatformError {
+    PlatformError::GLibError {
+        message: e.to_string(),
+    }
+}
+
+fn set_string_property(window: &gdk::Window, name: &[u8], value: &str) {
+    let window: *mut gdk_sys::GdkWindow = window.to_glib_none().0;
+    unsafe {
+        let property = gdk_sys::gdk_atom_intern(name.as_ptr() as *const _, glib_sys::GFALSE);
+        gdk_sys::gdk_property_change(
+            window,
+            property,
+            gdk_sys::gdk_atom_intern(b"UTF8_STRING\0".as_ptr() as *const _, glib_sys::GFALSE),
+            8,
+            gdk_sys::GDK_PROP_MODE_REPLACE,
+            value.as_ptr(),
+            value.len() as i32,
+        );
+    }
+}
+
+// GDK X11 backend is loaded by GTK
+unsafe fn x11_window_id(window: &gdk::Window) -> Option<std::os::raw::c_ulong> {
+    let get_xid: GdkX11WindowGetXid = symbol(libc::RTLD_DEFAULT, b"gdk_x11_window_get_xid\0")?;
+    Some(get_xid(window.to_glib_none().0))
+}
@@ -0,0 +1,101 @@
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+
+use gdk::{Atom, Monitor};
+use glib::SignalHandlerId;
+
+use crate::shell::{api_model::DisplayInfo, Context, Rect};
+
+use super::error::{PlatformError, PlatformResult};
+
+// Upper bound for ICC profile property size
+const MAX_ICC_PROFILE_LENGTH: u64 = 16 * 1024 * 1024;
+
+pub struct PlatformDisplayInfo {
+    context: Weak<Context>,
+    monitors_changed_handler: RefCell<Option<SignalHandlerId>>,
+}
+
+// HDR output is not exposed by GDK; All displays are reported as SDR
+impl PlatformDisplayInfo {
+    pub fn new(context: Rc<Context>) -> Self {
+        Self {
+            context: Rc::downgrade(&context),
+            monitors_changed_handler: RefCell::new(None),
+        }
+    }
+
+    // GDK display is not open yet when this object is created, so start
+    // observing monitor changes on first query
+    fn observe_monitors(&self, screen: &gdk::Screen) {
+        let mut handler = self.monitors_changed_handler.borrow_mut();
+        if handler.is_some() {
+            return;
+        }
+        let context = self.context.clone();
+        handler.replace(screen.connect_monitors_changed(move |_| {
+            if let Some(context) = context.upgrade() {
+                context.display_manager.borrow().displays_changed();
+            }
+        }));
+    }
+
+    // Primary monitor comes first
+    pub fn get_displays(&self) -> PlatformResult<Vec<DisplayInfo>> {
+        let display = gdk::Display::get_default().ok_or(PlatformError::UnknownError)?;
+        self.observe_monitors(&display.get_default_screen());
+
+        let primary = display.get_primary_monitor();
+        let mut monitors: Vec<(i32, Monitor)> = (0..display.get_n_monitors())
+            .filter_map(|i| display.get_monitor(i).map(|m| (i, m)))
+            .collect();
+        monitors.sort_by_key(|(_, m)| Some(m) != primary.as_ref());
+
+        Ok(monitors
+            .iter()
+            .enumerate()
+            .map(|(index, (monitor_num, monitor))| {
+                let geometry = monitor.get_geometry();
+                DisplayInfo {
+                    index,
+                    frame: Rect::xywh(
+                        geometry.x as f64,
+                        geometry.y as f64,
+                        geometry.width as f64,
+                        geometry.height as f64,
+                    ),
+                    scale_factor: monitor.get_scale_factor() as f64,
+                    color_space: None,
+                    icc_profile: Self::icc_profile(&display, *monitor_num),
+                    max_edr_headroom: 1.0,
+                    current_edr_headroom: 1.0,
+                    hdr: false,
+                }
+            })
+            .collect())
+    }
+
+    // Color managers (i.e. colord) publish display profiles as root window
+    // properties on X11 ("ICC Profiles in X Specification"); Not available on
+    // Wayland
+    fn icc_profile(display: &gdk::Display, monitor_num: i32) -> Option<Vec<u8>> {
+        let root = display.get_default_screen().get_root_window()?;
+        let name = if monitor_num == 0 {
+            "_ICC_PROFILE".to_string()
+        } else {
+            format!("_ICC_PROFILE_{}", monitor_num)
+        };
+        gdk::property_get(
+            &root,
+            &Atom::intern(&name),
+            &Atom::intern("CARDINAL"),
+            0,
+            MAX_ICC_PROFILE_LENGTH,
+            0,
+        )
+        .map(|(_, _, data)| data)
+        .filter(|data| !data.is_empty())
+    }
+}
@@ -17,13 +17,17 @@ use crate::{
     codec::Value,
     shell::{
         api_model::{DragData, DragEffect, DragRequest, DraggingInfo, ImageData},
-        platform::drag_data::{FallThroughDragDataAdapter, UriListDataAdapter},
+        platform::{
+            drag_data::{FallThroughDragDataAdapter, TextDataAdapter, UriListDataAdapter},
+            DragError, WindowError,
+        },
         Context, PlatformWindowDelegate, Point,
     },
 };
 
 use super::{
     drag_data::{DragDataAdapter, DragDataSetter},
+    error::PlatformResult,
     window::PlatformWindow,
 };
 
@@ -47,6 +51,8 @@ impl DropContext {
             window,
             data_adapters: vec![
                 Box::new(UriListDataAdapter::new()),
+                Box::new(TextDataAdapter::plain()),
+                Box::new(TextDataAdapter::html()),
                 Box::new(FallThroughDragDataAdapter::new(&context.options)),
             ],
             current_data: RefCell::new(HashMap::new()),
@@ -274,7 +280,11 @@ impl DragContext {
         Self {
             context: context.clone(),
             window,
+            // Text adapters go first so that explicit text takes precedence over
+            // URIs serialized as text
             data_adapters: vec![
+                Box::new(TextDataAdapter::plain()),
+                Box::new(TextDataAdapter::html()),
                 Box::new(UriListDataAdapter::new()),
                 Box::new(FallThroughDragDataAdapter::new(&context.options)),
             ],
@@ -283,27 +293,32 @@ impl DragContext {
         }
     }
 
-    fn prepare_data(&self, request: &mut DragRequest) -> TargetList {
+    fn prepare_data(&self, request: &mut DragRequest) -> PlatformResult<TargetList> {
         let mut properties = &mut request.data.properties;
         let mut data = self.data.borrow_mut();
         data.clear();
 
         for a in &self.context.options.custom_drag_data_adapters {
-            data.append(&mut a.prepare_drag_data(&mut properties));
+            data.append(&mut a.prepare_drag_data(&mut properties)?);
         }
         for a in &self.data_adapters {
-            data.append(&mut a.prepare_drag_data(&mut properties));
+            data.append(&mut a.prepare_drag_data(&mut properties)?);
         }
 
         let targets = TargetList::new(&[]);
+        let mut added = Vec::<Atom>::new();
         data.iter().enumerate().all(|(index, source)| {
             for k in source.data_formats() {
-                targets.add(&k, 0, index as u32);
+                // first setter for given format wins
+                if !added.contains(&k) {
+                    targets.add(&k, 0, index as u32);
+                    added.push(k);
+                }
             }
             true
         });
 
-        targets
+        Ok(targets)
     }
 
     fn convert_effects_to_gtk(effects: &[DragEffect]) -> DragAction {
@@ -314,8 +329,12 @@ impl DragContext {
         res
     }
 
-    pub fn begin_drag<T: IsA<Widget>>(&self, mut request: DragRequest, widget: &T) {
-        let window = self.window.upgrade().unwrap();
+    pub fn begin_drag<T: IsA<Widget>>(
+        &self,
+        mut request: DragRequest,
+        widget: &T,
+    ) -> PlatformResult<()> {
+        let window = self.window.upgrade().ok_or(WindowError::WindowGone)?;
 
         let events = window.last_event.borrow();
         let drag_event = events
@@ -331,7 +350,7 @@ impl DragContext {
             .and_then(|e| e.get_button())
             .unwrap_or(0);
 
-        let targets = self.prepare_data(&mut request);
+        let targets = self.prepare_data(&mut request)?;
 
         let context = widget.drag_begin_with_coordinates(
             &targets,
@@ -347,7 +366,7 @@ impl DragContext {
             .unwrap_or((0.0, 0.0));
 
         if let Some(context) = context {
-            let surface = &Self::surface_from_image_data(request.image);
+            let surface = &Self::surface_from_image_data(request.image)?;
             let scale_factor = widget.get_scale_factor() as f64;
             surface.set_device_scale(
                 widget.get_scale_factor() as f64,
@@ -361,9 +380,10 @@ impl DragContext {
         }
 
         self.dragging.replace(true);
+        Ok(())
     }
 
-    fn surface_from_image_data(image: ImageData) -> ImageSurface {
+    fn surface_from_image_data(image: ImageData) -> PlatformResult<ImageSurface> {
         let mut data = image.data;
         for offset in (0..data.len()).step_by(4) {
             let (r, g, b, a) = (
@@ -384,7 +404,12 @@ impl DragContext {
             image.height,
             image.bytes_per_row,
         );
-        surface.unwrap()
+        surface.map_err(|e| {
+            DragError::InvalidImage {
+                message: e.to_string(),
+            }
+            .into()
+        })
     }
 
     pub fn get_data(&self, selection_data: &SelectionData, target_info: u32) {
@@ -9,9 +9,15 @@ use url::Url;
 
 use crate::{
     codec::{MessageCodec, StandardMethodCodec, Value},
-    shell::{api_constants::drag_data, ContextOptions},
+    shell::{
+        api_constants::drag_data,
+        platform::{extract_drag_string, extract_drag_string_list, DragError},
+        ContextOptions,
+    },
 };
 
+use super::error::PlatformResult;
+
 pub trait DragDataSetter {
     fn set(&self, selection_data: &SelectionData);
     fn data_formats(&self) -> Vec<Atom>;
@@ -24,7 +30,7 @@ pub trait DragDataAdapter {
     fn prepare_drag_data(
         &self,
         data_in: &mut HashMap<String, Value>,
-    ) -> Vec<Box<dyn DragDataSetter>>;
+    ) -> PlatformResult<Vec<Box<dyn DragDataSetter>>>;
 }
 
 pub(super) struct UriListDataAdapter {}
@@ -105,16 +111,16 @@ impl DragDataAdapter for UriListDataAdapter {
     fn prepare_drag_data(
         &self,
         data_in: &mut HashMap<String, Value>,
-    ) -> Vec<Box<dyn DragDataSetter>> {
+    ) -> PlatformResult<Vec<Box<dyn DragDataSetter>>> {
         let mut uris = Vec::<String>::new();
 
         let urls = data_in.remove(drag_data::key::URLS);
-        if let Some(mut urls) = extract_string_list(urls) {
+        if let Some(mut urls) = extract_string_list(drag_data::key::URLS, urls)? {
             uris.append(&mut urls);
         }
 
         let files = data_in.remove(drag_data::key::FILES);
-        if let Some(files) = extract_string_list(files) {
+        if let Some(files) = extract_string_list(drag_data::key::FILES, files)? {
             for file in files {
                 let uri = format!(
                     "file://{}",
@@ -126,7 +132,11 @@ impl DragDataAdapter for UriListDataAdapter {
             }
         }
 
-        vec![
+        if uris.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Ok(vec![
             Box::new(UriDragData {
                 uris: uris.clone(),
                 set_as_uris: true,
@@ -144,28 +154,14 @@ impl DragDataAdapter for UriListDataAdapter {
                     Atom::intern("text/plain"),
                 ],
             }),
-        ]
+        ])
     }
 }
 
-fn extract_string_list(value: Option<Value>) -> Option<Vec<String>> {
-    match value {
-        Some(value) => {
-            if let Value::List(list) = value {
-                let mut res = Vec::new();
-                for value in list {
-                    if let Value::String(value) = value {
-                        res.push(value)
-                    } else {
-                        panic!("Invalid value found in list: ${:?}", value);
-                    }
-                }
-                return Some(res);
-            }
-            panic!("Invalid value: {:?}, expected list of strings", value)
-        }
-        None => None,
-    }
+fn extract_string_list(key: &str, value: Option<Value>) -> Result<Option<Vec<String>>, DragError> {
+    value
+        .map(|value| extract_drag_string_list(key, value))
+        .transpose()
 }
 
 struct UriDragData {
@@ -198,6 +194,106 @@ impl DragDataSetter for UriDragData {
 //
 //
 
+pub(super) struct TextDataAdapter {
+    key: &'static str,
+    formats: Vec<Atom>,
+}
+
+impl TextDataAdapter {
+    pub fn plain() -> Self {
+        Self {
+            key: drag_data::key::TEXT,
+            formats: vec![
+                Atom::intern("UTF8_STRING"),
+                Atom::intern("COMPOUND_TEXT"),
+                Atom::intern("TEXT"),
+                Atom::intern("STRING"),
+                Atom::intern("text/plain;charset=utf-8"),
+                Atom::intern("text/plain"),
+            ],
+        }
+    }
+
+    pub fn html() -> Self {
+        Self {
+            key: drag_data::key::HTML,
+            formats: vec![Atom::intern("text/html")],
+        }
+    }
+
+    fn is_html(&self) -> bool {
+        self.key == drag_data::key::HTML
+    }
+
+    fn decode_html(data: &[u8]) -> String {
+        // Some applications (i.e. Firefox) provide text/html as UTF-16
+        if data.starts_with(&[0xFF, 0xFE]) {
+            let data: Vec<u16> = data[2..]
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .collect();
+            String::from_utf16_lossy(&data)
+        } else {
+            String::from_utf8_lossy(data).into()
+        }
+    }
+}
+
+impl DragDataAdapter for TextDataAdapter {
+    fn retrieve_drag_data(&self, data: &SelectionData, data_out: &mut HashMap<String, Value>) {
+        let text = if self.is_html() {
+            Some(Self::decode_html(&data.get_data()))
+        } else {
+            data.get_text().map(|s| s.into())
+        };
+        if let Some(text) = text {
+            data_out.insert(self.key.into(), Value::String(text));
+        }
+    }
+
+    fn data_formats(&self) -> Vec<Atom> {
+        self.formats.clone()
+    }
+
+    fn prepare_drag_data(
+        &self,
+        data_in: &mut HashMap<String, Value>,
+    ) -> PlatformResult<Vec<Box<dyn DragDataSetter>>> {
+        match data_in.remove(self.key) {
+            Some(text) => Ok(vec![Box::new(TextDragData {
+                text: extract_drag_string(self.key, text)?,
+                html: self.is_html(),
+                formats: self.formats.clone(),
+            })]),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+struct TextDragData {
+    text: String,
+    html: bool,
+    formats: Vec<Atom>,
+}
+
+impl DragDataSetter for TextDragData {
+    fn set(&self, selection_data: &SelectionData) {
+        if self.html {
+            selection_data.set(&self.formats[0], 8, self.text.as_bytes());
+        } else {
+            selection_data.set_text(&self.text);
+        }
+    }
+
+    fn data_formats(&self) -> Vec<Atom> {
+        self.formats.clone()
+    }
+}
+
+//
+//
+//
+
 pub struct FallThroughDragDataAdapter {
     format: Atom,
 }
@@ -217,8 +313,8 @@ impl DragDataAdapter for FallThroughDragDataAdapter {
     fn retrieve_drag_data(&self, data: &SelectionData, data_out: &mut HashMap<String, Value>) {
         let codec: &'static dyn MessageCodec<Value> = &StandardMethodCodec;
         let data = data.get_data();
-        let value = codec.decode_message(&data).unwrap();
-        if let Value::Map(value) = value {
+        let value = codec.decode_message(&data);
+        if let Some(Value::Map(value)) = value {
             for entry in value {
                 if let Value::String(key) = entry.0 {
                     data_out.insert(key, entry.1);
@@ -238,11 +334,11 @@ impl DragDataAdapter for FallThroughDragDataAdapter {
     fn prepare_drag_data(
         &self,
         data_in: &mut HashMap<String, Value>,
-    ) -> Vec<Box<dyn DragDataSetter>> {
-        vec![Box::new(FallthroughDragDataSetter {
+    ) -> PlatformResult<Vec<Box<dyn DragDataSetter>>> {
+        Ok(vec![Box::new(FallthroughDragDataSetter {
             values: take(data_in),
             format: self.format,
-        })]
+        })])
     }
 }
 
@@ -1,19 +1,23 @@
-use std::ffi::c_void;
+use std::ffi::{c_void, CStr};
 
-use glib::translate::ToGlibPtr;
+use glib::{translate::ToGlibPtr, Cast};
 use gtk::WidgetExt;
 
-use crate::shell::platform::key_interceptor::override_key_event;
+use log::warn;
+
+use crate::shell::{platform::key_interceptor::override_key_event, EngineOptions};
 
 use super::{
     binary_messenger::PlatformBinaryMessenger,
-    error::PlatformResult,
+    error::{PlatformError, PlatformResult},
     flutter::{self, EngineExt, ViewExt},
     flutter_sys,
+    native_view::NativeViewType,
 };
 
 pub struct PlatformEngine {
-    pub(super) view: flutter::View,
+    pub(super) view: Option<flutter::View>,
+    engine: flutter::Engine,
 }
 
 #[repr(C)]
@@ -30,27 +34,91 @@ struct _FlEngine {
 }
 
 impl PlatformEngine {
-    pub fn new() -> Self {
-        let project = flutter::DartProject::new();
+    pub fn new(options: &EngineOptions) -> Self {
+        let project = Self::new_project(options);
         let view = flutter::View::new(&project);
-        PlatformEngine { view }
+        let engine = view.get_engine();
+        PlatformEngine {
+            view: Some(view),
+            engine,
+        }
+    }
+
+    // Engine without view
+    pub fn new_headless(options: &EngineOptions) -> Self {
+        let project = Self::new_project(options);
+        PlatformEngine {
+            view: None,
+            engine: flutter::Engine::new_headless(&project),
+        }
+    }
+
+    fn new_project(options: &EngineOptions) -> flutter::DartProject {
+        let project = flutter::DartProject::new();
+        project.set_dart_entrypoint_arguments(&options.entrypoint_args);
+        if let Some(entrypoint) = &options.entrypoint {
+            // FlDartProject has no way to specify entrypoint
+            warn!(
+                "Custom entrypoint '{}' is not supported on Linux, running main instead",
+                entrypoint
+            );
+        }
+        project
+    }
+
+    // Engine created without view can not be attached to FlView
+    pub fn attach_view(&mut self) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    // FlView to be embedded in existing window; Engine is started when the view
+    // is realized, so it must be added to the window before launching
+    pub fn embedded_view(&self) -> PlatformResult<NativeViewType> {
+        self.view
+            .as_ref()
+            .map(|view| view.clone().upcast())
+            .ok_or(PlatformError::NotImplemented)
     }
 
     pub fn new_binary_messenger(&self) -> PlatformBinaryMessenger {
-        PlatformBinaryMessenger::new(self.view.get_engine().get_binary_messenger())
+        PlatformBinaryMessenger::new(self.engine.get_binary_messenger())
     }
 
     fn override_key_event(&self) {
-        let engine = self.view.get_engine();
-        let engine: *mut flutter_sys::FlEngine = engine.to_glib_none().0;
+        let engine: *mut flutter_sys::FlEngine = self.engine.to_glib_none().0;
         let engine = engine as *mut u8;
         let api = unsafe { engine.add(std::mem::size_of::<_FlEngine>()) } as *mut c_void;
         override_key_event(api);
     }
 
+    // Registrar is FlPluginRegistrar*
+    pub fn with_plugin_registrar<F>(&self, plugin_name: &CStr, f: F) -> PlatformResult<()>
+    where
+        F: FnOnce(*mut c_void),
+    {
+        let registry: *mut flutter_sys::FlView = match &self.view {
+            Some(view) => view.to_glib_none().0,
+            None => return Err(PlatformError::NotImplemented),
+        };
+        unsafe {
+            let registrar = flutter_sys::fl_plugin_registry_get_registrar_for_plugin(
+                registry as *mut _,
+                plugin_name.as_ptr(),
+            );
+            f(registrar as *mut c_void);
+            gobject_sys::g_object_unref(registrar as *mut _);
+        }
+        Ok(())
+    }
+
     pub fn launch(&mut self) -> PlatformResult<()> {
-        // This assumes the view has already been added to GtkWindow
-        self.view.realize();
+        match &self.view {
+            // This assumes the view has already been added to GtkWindow
+            Some(view) => view.realize(),
+            None => self.engine.start().map_err(|e| PlatformError::GLibError {
+                message: e.to_string(),
+            })?,
+        }
         self.override_key_event();
         Ok(())
     }
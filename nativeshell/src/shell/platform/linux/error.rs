@@ -1,10 +1,17 @@
 use std::fmt::Display;
 
+use crate::{
+    codec::Value,
+    shell::platform::{DragError, WindowError},
+};
+
 #[derive(Debug, Clone)]
 pub enum PlatformError {
     NotImplemented,
     UnknownError,
     GLibError { message: String },
+    Window(WindowError),
+    Drag(DragError),
 }
 
 pub type PlatformResult<T> = Result<T, PlatformError>;
@@ -21,8 +28,44 @@ impl Display for PlatformError {
             PlatformError::GLibError { message } => {
                 write!(f, "GLibError: {}", message)
             }
+            PlatformError::Window(error) => error.fmt(f),
+            PlatformError::Drag(error) => error.fmt(f),
+        }
+    }
+}
+
+impl PlatformError {
+    // Stable error code reported to Dart
+    pub fn code(&self) -> &'static str {
+        match self {
+            PlatformError::NotImplemented => "not-implemented",
+            PlatformError::UnknownError => "unknown-error",
+            PlatformError::GLibError { .. } => "glib-error",
+            PlatformError::Window(error) => error.code(),
+            PlatformError::Drag(error) => error.code(),
+        }
+    }
+
+    pub fn details(&self) -> Value {
+        match self {
+            PlatformError::GLibError { message } => message.as_str().into(),
+            PlatformError::Window(error) => error.details(),
+            PlatformError::Drag(error) => error.details(),
+            _ => Value::Null,
         }
     }
 }
 
 impl std::error::Error for PlatformError {}
+
+impl From<WindowError> for PlatformError {
+    fn from(error: WindowError) -> Self {
+        PlatformError::Window(error)
+    }
+}
+
+impl From<DragError> for PlatformError {
+    fn from(error: DragError) -> Self {
+        PlatformError::Drag(error)
+    }
+}
@@ -0,0 +1,35 @@
+use std::rc::Rc;
+
+use crate::shell::{
+    api_model::{EventTapPermission, EventTapRequest},
+    Context,
+};
+
+use super::error::{PlatformError, PlatformResult};
+
+// Global event monitoring is not possible on Wayland and would require XRecord
+// on X11; Not supported for now.
+pub struct PlatformEventTap {}
+
+#[allow(unused_variables)]
+impl PlatformEventTap {
+    pub fn new(context: Rc<Context>) -> Self {
+        Self {}
+    }
+
+    pub fn permission(&self) -> EventTapPermission {
+        EventTapPermission::Denied
+    }
+
+    pub fn request_permission(&self) -> PlatformResult<EventTapPermission> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn start(&self, request: &EventTapRequest) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn stop(&self) -> PlatformResult<()> {
+        Ok(())
+    }
+}
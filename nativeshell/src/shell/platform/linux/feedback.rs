@@ -0,0 +1,61 @@
+use std::{
+    ffi::CString,
+    os::raw::{c_char, c_int, c_void},
+};
+
+use crate::shell::api_model::HapticFeedbackPattern;
+
+use super::{
+    error::{PlatformError, PlatformResult},
+    utils::symbol,
+};
+
+type CaGtkContextGet = unsafe extern "C" fn() -> *mut c_void;
+type CaContextPlay = unsafe extern "C" fn(*mut c_void, u32, ...) -> c_int;
+
+pub fn beep() -> PlatformResult<()> {
+    let display = gdk::Display::get_default().ok_or(PlatformError::UnknownError)?;
+    display.beep();
+    Ok(())
+}
+
+// Sounds are played through libcanberra, which is loaded at runtime so that it
+// is not a hard dependency
+pub fn play_system_sound(name: &str) -> PlatformResult<()> {
+    let name = CString::new(name).map_err(|_| PlatformError::UnknownError)?;
+    unsafe {
+        // Library is kept loaded; Context returned by ca_gtk_context_get is shared
+        let library = libc::dlopen(
+            b"libcanberra-gtk3.so.0\0".as_ptr() as *const c_char,
+            libc::RTLD_LAZY,
+        );
+        if library.is_null() {
+            return Err(PlatformError::NotImplemented);
+        }
+        let context_get: CaGtkContextGet =
+            symbol(library, b"ca_gtk_context_get\0").ok_or(PlatformError::NotImplemented)?;
+        let play: CaContextPlay =
+            symbol(library, b"ca_context_play\0").ok_or(PlatformError::NotImplemented)?;
+        let context = context_get();
+        if context.is_null() {
+            return Err(PlatformError::UnknownError);
+        }
+        let res = play(
+            context,
+            0,
+            b"event.id\0".as_ptr() as *const c_char,
+            name.as_ptr(),
+            std::ptr::null::<c_char>(),
+        );
+        if res == 0 {
+            Ok(())
+        } else {
+            Err(PlatformError::UnknownError)
+        }
+    }
+}
+
+#[allow(unused_variables)]
+pub fn perform_haptic_feedback(pattern: HapticFeedbackPattern) -> PlatformResult<()> {
+    Err(PlatformError::NotImplemented)
+}
@@ -1,6 +1,6 @@
 #![allow(clippy::from_over_into)]
 
-use std::{mem::ManuallyDrop, os::raw::c_char};
+use std::{ffi::CString, mem::ManuallyDrop, os::raw::c_char, ptr};
 
 use super::flutter_sys;
 use glib::{glib_wrapper, Object};
@@ -24,6 +24,22 @@ impl DartProject {
     pub fn new() -> DartProject {
         unsafe { Object::from_glib_none(flutter_sys::fl_dart_project_new()).unsafe_cast() }
     }
+
+    pub fn set_dart_entrypoint_arguments(&self, args: &[String]) {
+        // Arguments are copied by the project
+        let args: Vec<CString> = args
+            .iter()
+            .map(|a| CString::new(a.as_str()).unwrap())
+            .collect();
+        let mut argv: Vec<*mut c_char> = args.iter().map(|a| a.as_ptr() as *mut _).collect();
+        argv.push(ptr::null_mut());
+        unsafe {
+            flutter_sys::fl_dart_project_set_dart_entrypoint_arguments(
+                self.to_glib_none().0,
+                argv.as_mut_ptr(),
+            );
+        }
+    }
 }
 
 glib_wrapper! {
@@ -68,8 +84,20 @@ glib_wrapper! {
     }
 }
 
+impl Engine {
+    pub fn new_headless<P: IsA<DartProject>>(project: &P) -> Engine {
+        unsafe {
+            Object::from_glib_full(flutter_sys::fl_engine_new_headless(
+                project.as_ref().to_glib_none().0,
+            ))
+            .unsafe_cast()
+        }
+    }
+}
+
 pub trait EngineExt: 'static {
     fn get_binary_messenger(&self) -> BinaryMessenger;
+    fn start(&self) -> Result<(), glib::Error>;
 }
 
 impl<O: IsA<Engine>> EngineExt for O {
@@ -81,6 +109,18 @@ impl<O: IsA<Engine>> EngineExt for O {
             .unsafe_cast()
         }
     }
+
+    fn start(&self) -> Result<(), glib::Error> {
+        unsafe {
+            let mut error = std::ptr::null_mut();
+            let res = flutter_sys::fl_engine_start(self.as_ref().to_glib_none().0, &mut error);
+            if res == glib_sys::GFALSE {
+                Err(from_glib_full(error))
+            } else {
+                Ok(())
+            }
+        }
+    }
 }
 
 glib_wrapper! {
@@ -76,12 +76,72 @@ pub type FlBinaryMessengerMessageHandler = Option<
     ),
 >;
 
+#[repr(C)]
+pub struct FlPixelBufferTextureClass {
+    pub parent_class: GObjectClass,
+    pub copy_pixels: Option<
+        unsafe extern "C" fn(
+            texture: *mut GObject,
+            buffer: *mut *const u8,
+            width: *mut u32,
+            height: *mut u32,
+            error: *mut *mut glib_sys::GError,
+        ) -> glib_sys::gboolean,
+    >,
+}
+
+#[repr(C)]
+pub struct FlTextureGLClass {
+    pub parent_class: GObjectClass,
+    pub populate: Option<
+        unsafe extern "C" fn(
+            texture: *mut GObject,
+            target: *mut u32,
+            name: *mut u32,
+            width: *mut u32,
+            height: *mut u32,
+            error: *mut *mut glib_sys::GError,
+        ) -> glib_sys::gboolean,
+    >,
+}
+
 extern "C" {
     pub fn fl_dart_project_new() -> *mut GObject;
+    pub fn fl_dart_project_set_dart_entrypoint_arguments(
+        project: *mut FlDartProject,
+        argv: *mut *mut c_char,
+    );
+    pub fn fl_plugin_registry_get_registrar_for_plugin(
+        registry: *mut GObject,
+        name: *const c_char,
+    ) -> *mut GObject;
+
+    pub fn fl_plugin_registrar_get_texture_registrar(registrar: *mut GObject) -> *mut GObject;
+
+    pub fn fl_pixel_buffer_texture_get_type() -> glib_sys::GType;
+    pub fn fl_texture_gl_get_type() -> glib_sys::GType;
+    pub fn fl_texture_get_id(texture: *mut GObject) -> i64;
+    pub fn fl_texture_registrar_register_texture(
+        registrar: *mut GObject,
+        texture: *mut GObject,
+    ) -> glib_sys::gboolean;
+    pub fn fl_texture_registrar_mark_texture_frame_available(
+        registrar: *mut GObject,
+        texture: *mut GObject,
+    ) -> glib_sys::gboolean;
+    pub fn fl_texture_registrar_unregister_texture(
+        registrar: *mut GObject,
+        texture: *mut GObject,
+    ) -> glib_sys::gboolean;
 
     pub fn fl_view_new(project: *mut FlDartProject) -> *mut GtkWidget;
     pub fn fl_view_get_engine(view: *mut FlView) -> *mut GObject;
 
+    pub fn fl_engine_new_headless(project: *mut FlDartProject) -> *mut GObject;
+    pub fn fl_engine_start(
+        engine: *mut FlEngine,
+        error: *mut *mut glib_sys::GError,
+    ) -> glib_sys::gboolean;
     pub fn fl_engine_get_binary_messenger(engine: *mut FlEngine) -> *mut GObject;
 
     pub fn fl_binary_messenger_set_message_handler_on_channel(
@@ -0,0 +1,130 @@
+use std::{
+    ffi::CStr,
+    rc::{Rc, Weak},
+};
+
+use gio::{SettingsExt, SettingsSchemaSource};
+
+use crate::shell::{
+    api_model::{LocaleSettings, MeasurementSystem},
+    Context,
+};
+
+use super::error::PlatformResult;
+
+// glibc locale items; Not exported by libc crate
+const NL_TIME_WEEK_1STDAY: libc::nl_item = 0x20066;
+const NL_TIME_FIRST_WEEKDAY: libc::nl_item = 0x20068;
+const NL_MEASUREMENT_MEASUREMENT: libc::nl_item = 0xB0000;
+
+// Week origin dates reported by NL_TIME_WEEK_1STDAY
+const WEEK_ORIGIN_SUNDAY: usize = 19971130;
+
+// Desktop setting that overrides time format of the locale (GNOME)
+const INTERFACE_SCHEMA: &str = "org.gnome.desktop.interface";
+const CLOCK_FORMAT_KEY: &str = "clock-format";
+
+// Process locale doesn't change while running, only desktop clock format
+// setting is observed
+pub struct PlatformLocale {
+    interface_settings: Option<gio::Settings>,
+}
+
+impl PlatformLocale {
+    pub fn new(context: Rc<Context>) -> Self {
+        let interface_settings = Self::interface_settings();
+        if let Some(settings) = &interface_settings {
+            let context: Weak<Context> = Rc::downgrade(&context);
+            settings.connect_changed(move |_, key| {
+                if key == CLOCK_FORMAT_KEY {
+                    if let Some(context) = context.upgrade() {
+                        context.locale_manager.borrow().settings_changed();
+                    }
+                }
+            });
+        }
+        Self { interface_settings }
+    }
+
+    // Creating settings for missing schema would abort
+    fn interface_settings() -> Option<gio::Settings> {
+        let schema = SettingsSchemaSource::get_default()?.lookup(INTERFACE_SCHEMA, true)?;
+        if schema.has_key(CLOCK_FORMAT_KEY) {
+            Some(gio::Settings::new(INTERFACE_SCHEMA))
+        } else {
+            None
+        }
+    }
+
+    pub fn get_settings(&self) -> PlatformResult<LocaleSettings> {
+        // Locale is set from environment during GTK initialization
+        let name = unsafe {
+            let name = libc::setlocale(libc::LC_TIME, std::ptr::null());
+            if name.is_null() {
+                String::new()
+            } else {
+                CStr::from_ptr(name).to_string_lossy().into_owned()
+            }
+        };
+        // language[_territory][.codeset][@modifier]
+        let name = name.split(['.', '@']).next().unwrap_or_default();
+        let (locale, region) = match name {
+            "" | "C" | "POSIX" => ("und".into(), None),
+            name => (
+                name.replace('_', "-"),
+                name.split_once('_').map(|(_, region)| region.into()),
+            ),
+        };
+
+        let uses_24_hour_clock = match self
+            .interface_settings
+            .as_ref()
+            .and_then(|s| s.get_string(CLOCK_FORMAT_KEY))
+            .as_deref()
+        {
+            Some("12h") => false,
+            Some("24h") => true,
+            _ => {
+                let format = langinfo_string(libc::T_FMT);
+                !["%I", "%l", "%r", "%p"].iter().any(|f| format.contains(f))
+            }
+        };
+
+        let (first_day_of_week, measurement) = unsafe {
+            let week_origin = libc::nl_langinfo(NL_TIME_WEEK_1STDAY) as usize;
+            let first_weekday = *libc::nl_langinfo(NL_TIME_FIRST_WEEKDAY) as u32;
+            let measurement = *libc::nl_langinfo(NL_MEASUREMENT_MEASUREMENT) as u8;
+            // Day relative to week origin (1 is the origin); 0 is Sunday
+            let origin = if week_origin == WEEK_ORIGIN_SUNDAY {
+                0
+            } else {
+                1
+            };
+            let day = (origin + first_weekday.max(1) - 1) % 7;
+            (if day == 0 { 7 } else { day }, measurement)
+        };
+
+        Ok(LocaleSettings {
+            locale,
+            region,
+            first_day_of_week,
+            uses_24_hour_clock,
+            measurement_system: if measurement == 2 {
+                MeasurementSystem::Us
+            } else {
+                MeasurementSystem::Metric
+            },
+        })
+    }
+}
+
+fn langinfo_string(item: libc::nl_item) -> String {
+    unsafe {
+        let value = libc::nl_langinfo(item);
+        if value.is_null() {
+            String::new()
+        } else {
+            CStr::from_ptr(value).to_string_lossy().into_owned()
+        }
+    }
+}
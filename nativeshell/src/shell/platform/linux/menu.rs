@@ -22,7 +22,8 @@ use crate::{
 };
 
 use super::{
-    error::{PlatformError, PlatformResult},
+    dbus_menu::DBusMenu,
+    error::PlatformResult,
     menu_item::{
         check_menu_item_set_checked, create_check_menu_item, create_radio_menu_item,
         radio_menu_item_set_checked,
@@ -31,10 +32,10 @@ use super::{
 
 pub struct PlatformMenu {
     context: Rc<Context>,
-    handle: MenuHandle,
+    pub(super) handle: MenuHandle,
     weak_self: LateRefCell<Weak<PlatformMenu>>,
     pub(super) menu: gtk::Menu,
-    previous_menu: RefCell<Menu>,
+    pub(super) previous_menu: RefCell<Menu>,
     id_to_menu_item: RefCell<HashMap<i64, gtk::MenuItem>>,
     item_selected: Cell<bool>,
     on_selection_done: RefCell<Option<Box<dyn FnOnce(bool)>>>,
@@ -126,12 +127,8 @@ impl PlatformMenu {
 
         for (i, d) in diff.iter().enumerate() {
             match d {
-                DiffResult::Remove(_) => {
-                    panic!("Should have been already removed.")
-                }
-                DiffResult::Keep(_, _) => {
-                    // nothing
-                }
+                // removed items were already handled above
+                DiffResult::Remove(_) | DiffResult::Keep(_, _) => {}
                 DiffResult::Update(old, new) => {
                     let item = self
                         .id_to_menu_item
@@ -166,6 +163,8 @@ impl PlatformMenu {
         // and menu cut off
         self.resize_menu_if_needed();
 
+        DBusMenu::menu_changed(self.handle);
+
         Ok(())
     }
 
@@ -192,18 +191,14 @@ impl PlatformMenu {
         let res = if menu_item.separator {
             gtk::SeparatorMenuItem::new().upcast::<gtk::MenuItem>()
         } else {
-            let res = if menu_item.check_status == CheckStatus::None {
-                gtk::MenuItem::new()
-            } else if menu_item.check_status == CheckStatus::CheckOn
-                || menu_item.check_status == CheckStatus::CheckOff
-            {
-                create_check_menu_item().upcast::<gtk::MenuItem>()
-            } else if menu_item.check_status == CheckStatus::RadioOn
-                || menu_item.check_status == CheckStatus::RadioOff
-            {
-                create_radio_menu_item().upcast::<gtk::MenuItem>()
-            } else {
-                panic!("Invalid item check status")
+            let res = match menu_item.check_status {
+                CheckStatus::None => gtk::MenuItem::new(),
+                CheckStatus::CheckOn | CheckStatus::CheckOff => {
+                    create_check_menu_item().upcast::<gtk::MenuItem>()
+                }
+                CheckStatus::RadioOn | CheckStatus::RadioOff => {
+                    create_radio_menu_item().upcast::<gtk::MenuItem>()
+                }
             };
             let weak = self.weak_self.borrow().clone();
             res.connect_activate(move |item| {
@@ -262,7 +257,7 @@ impl PlatformMenu {
     }
 
     // Convert & mnemonics to _
-    fn convert_mnemonics(title: &str) -> String {
+    pub(super) fn convert_mnemonics(title: &str) -> String {
         let mut res = String::new();
         let mut mnemonic = false;
         for c in title.chars() {
@@ -289,7 +284,7 @@ impl PlatformMenu {
         res
     }
 
-    fn accelerator_label_code(accelerator: &Accelerator) -> i32 {
+    pub(super) fn accelerator_label_code(accelerator: &Accelerator) -> i32 {
         let label = accelerator.label.to_lowercase();
         let value = match label.as_str() {
             // these must match label descriptions from accelerators.dart
@@ -454,14 +449,55 @@ impl PlatformMenu {
     }
 }
 
-pub struct PlatformMenuManager {}
+pub struct PlatformMenuManager {
+    context: Rc<Context>,
+    dbus_menu: RefCell<Option<Rc<DBusMenu>>>,
+}
 
 impl PlatformMenuManager {
-    pub fn new(_context: Rc<Context>) -> Self {
-        Self {}
+    pub fn new(context: Rc<Context>) -> Self {
+        Self {
+            context,
+            dbus_menu: RefCell::new(None),
+        }
+    }
+
+    pub fn has_native_window_list(&self) -> bool {
+        false
+    }
+
+    // Common defaults of GNOME and KDE; Actual shortcuts depend on desktop
+    // environment and user configuration
+    pub fn system_shortcuts(&self) -> Vec<(Accelerator, &'static str)> {
+        [
+            ("Alt+Tab", "Switch applications"),
+            ("Alt+F2", "Run command"),
+            ("Alt+F4", "Close window"),
+            ("Alt+Space", "Window menu"),
+            ("Ctrl+Alt+Delete", "Log out"),
+            ("Ctrl+Alt+Left Arrow", "Switch to workspace on the left"),
+            ("Ctrl+Alt+Right Arrow", "Switch to workspace on the right"),
+            ("Super+Tab", "Switch applications"),
+            ("Super+L", "Lock screen"),
+        ]
+        .iter()
+        .map(|(accelerator, name)| (Accelerator::parse(accelerator), *name))
+        .collect()
     }
 
-    pub fn set_app_menu(&self, _menu: Option<Rc<PlatformMenu>>) -> PlatformResult<()> {
-        Err(PlatformError::NotImplemented)
+    // Application menu is exported for desktops with global menu bar; Applications
+    // should still show menu bar in window when there is none
+    pub fn set_app_menu(&self, menu: Option<Rc<PlatformMenu>>) -> PlatformResult<()> {
+        let mut dbus_menu = self.dbus_menu.borrow_mut();
+        match (menu, dbus_menu.as_ref()) {
+            (Some(menu), Some(dbus_menu)) => dbus_menu.set_menu(menu),
+            (Some(menu), None) => {
+                dbus_menu.replace(DBusMenu::new(self.context.clone(), menu)?);
+            }
+            (None, _) => {
+                dbus_menu.take();
+            }
+        }
+        Ok(())
     }
 }
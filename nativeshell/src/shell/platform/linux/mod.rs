@@ -1,18 +1,38 @@
 extern crate gtk;
 
+pub mod accessibility;
+pub mod application;
 pub mod binary_messenger;
+pub mod crash_handler;
+pub mod dbus_menu;
+pub mod display_info;
 pub mod drag_context;
 pub mod drag_data;
 pub mod engine;
 pub mod error;
+pub mod event_tap;
+pub mod feedback;
 pub mod flutter;
 pub mod flutter_sys;
 pub mod init;
 pub mod key_event;
+pub mod locale;
 pub mod menu;
 pub mod menu_item;
+pub mod native_view;
+pub mod permission;
+pub mod power;
+pub mod render_layer;
 pub mod run_loop;
+pub mod screen_capture;
+pub mod single_instance;
+pub mod size_widget;
+pub mod system_theme;
+pub mod tablet;
+pub mod texture;
+pub mod user_activity;
 pub mod utils;
 pub mod window;
+pub mod window_list;
 pub mod window_menu;
-pub mod size_widget;
\ No newline at end of file
+pub mod workspace;
@@ -0,0 +1,61 @@
+use std::rc::Rc;
+
+use gtk::{Align, ContainerExt, Fixed, FixedExt, OverlayExt, Widget, WidgetExt};
+
+use crate::shell::Rect;
+
+use super::{error::PlatformResult, window::PlatformWindow};
+
+pub type NativeViewType = Widget;
+
+pub struct PlatformNativeView {
+    window: Rc<PlatformWindow>,
+    // Has own GdkWindow, which clips the view and receives its input; Positioned
+    // within overlay using margins
+    container: Fixed,
+    view: Widget,
+}
+
+impl PlatformNativeView {
+    pub fn new(window: Rc<PlatformWindow>, view: NativeViewType) -> PlatformResult<Self> {
+        let container = Fixed::new();
+        container.set_has_window(true);
+        container.set_halign(Align::Start);
+        container.set_valign(Align::Start);
+        container.put(&view, 0, 0);
+        view.show();
+        window.overlay.borrow().add_overlay(&container);
+        Ok(Self {
+            window,
+            container,
+            view,
+        })
+    }
+
+    pub fn update(&self, frame: &Rect, clip: &Rect, visible: bool) -> PlatformResult<()> {
+        self.container.set_margin_start(clip.x.round() as i32);
+        self.container.set_margin_top(clip.y.round() as i32);
+        self.container
+            .set_size_request(clip.width.round() as i32, clip.height.round() as i32);
+        self.container.move_(
+            &self.view,
+            (frame.x - clip.x).round() as i32,
+            (frame.y - clip.y).round() as i32,
+        );
+        self.view
+            .set_size_request(frame.width.round() as i32, frame.height.round() as i32);
+        if visible {
+            self.container.show();
+        } else {
+            self.container.hide();
+        }
+        Ok(())
+    }
+}
+
+impl Drop for PlatformNativeView {
+    fn drop(&mut self) {
+        self.container.remove(&self.view);
+        self.window.overlay.borrow().remove(&self.container);
+    }
+}
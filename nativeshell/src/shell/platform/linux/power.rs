@@ -0,0 +1,228 @@
+use std::rc::{Rc, Weak};
+
+use gio::{DBusConnection, DBusSignalFlags, SignalSubscriptionId};
+use glib::{ToVariant, Variant};
+
+use crate::shell::{
+    api_model::{PowerSource, PowerStatus},
+    ActivityOptions, Context, SleepPreventionKind,
+};
+
+use super::{
+    error::{PlatformError, PlatformResult},
+    utils::{application_id, dbus_call, dbus_child_value},
+};
+
+const SCREEN_SAVER: &str = "org.freedesktop.ScreenSaver";
+const SCREEN_SAVER_PATH: &str = "/org/freedesktop/ScreenSaver";
+
+const PROPERTIES: &str = "org.freedesktop.DBus.Properties";
+const UPOWER: &str = "org.freedesktop.UPower";
+const UPOWER_PATH: &str = "/org/freedesktop/UPower";
+const UPOWER_DEVICE: &str = "org.freedesktop.UPower.Device";
+// Composite device that represents all batteries
+const UPOWER_DISPLAY_DEVICE_PATH: &str = "/org/freedesktop/UPower/devices/DisplayDevice";
+const POWER_PROFILES: &str = "net.hadess.PowerProfiles";
+const POWER_PROFILES_PATH: &str = "/net/hadess/PowerProfiles";
+
+// UPower device state
+const DEVICE_STATE_CHARGING: u32 = 1;
+const DEVICE_STATE_PENDING_CHARGE: u32 = 5;
+
+// Screen saver inhibition also prevents idle suspend on common desktops, so it
+// is used for both kinds
+pub struct PlatformSleepGuard {
+    connection: DBusConnection,
+    cookie: u32,
+}
+
+impl PlatformSleepGuard {
+    pub fn new(_kind: SleepPreventionKind, reason: &str) -> PlatformResult<Self> {
+        let connection = gio::bus_get_sync(gio::BusType::Session, None::<&gio::Cancellable>)
+            .map_err(|e| PlatformError::GLibError {
+                message: e.to_string(),
+            })?;
+        let res = dbus_call(
+            &connection,
+            SCREEN_SAVER,
+            SCREEN_SAVER_PATH,
+            SCREEN_SAVER,
+            "Inhibit",
+            &[application_id().to_variant(), reason.to_variant()],
+        )?;
+        let cookie = dbus_child_value(&res, 0)
+            .get::<u32>()
+            .ok_or(PlatformError::UnknownError)?;
+        Ok(Self { connection, cookie })
+    }
+}
+
+impl Drop for PlatformSleepGuard {
+    fn drop(&mut self) {
+        dbus_call(
+            &self.connection,
+            SCREEN_SAVER,
+            SCREEN_SAVER_PATH,
+            SCREEN_SAVER,
+            "UnInhibit",
+            &[self.cookie.to_variant()],
+        )
+        .ok();
+    }
+}
+
+// No App Nap equivalent; Only idle sleep prevention has an effect
+pub struct PlatformActivityGuard {
+    _sleep_guard: Option<PlatformSleepGuard>,
+}
+
+impl PlatformActivityGuard {
+    pub fn new(options: ActivityOptions, reason: &str) -> PlatformResult<Self> {
+        let sleep_guard = if options.prevent_idle_sleep {
+            Some(PlatformSleepGuard::new(
+                SleepPreventionKind::System,
+                reason,
+            )?)
+        } else {
+            None
+        };
+        Ok(Self {
+            _sleep_guard: sleep_guard,
+        })
+    }
+}
+
+// Battery status from UPower and power saver profile from power-profiles-daemon
+// (both on system bus)
+pub struct PlatformPowerSource {
+    connection: Option<DBusConnection>,
+    subscriptions: Vec<SignalSubscriptionId>,
+}
+
+impl PlatformPowerSource {
+    pub fn new(context: Rc<Context>) -> Self {
+        let connection = gio::bus_get_sync(gio::BusType::System, None::<&gio::Cancellable>).ok();
+        let subscriptions = match &connection {
+            Some(connection) => [UPOWER, POWER_PROFILES]
+                .iter()
+                .map(|sender| Self::subscribe(connection, sender, Rc::downgrade(&context)))
+                .collect(),
+            None => Vec::new(),
+        };
+        Self {
+            connection,
+            subscriptions,
+        }
+    }
+
+    fn subscribe(
+        connection: &DBusConnection,
+        sender: &str,
+        context: Weak<Context>,
+    ) -> SignalSubscriptionId {
+        connection.signal_subscribe(
+            Some(sender),
+            Some(PROPERTIES),
+            Some("PropertiesChanged"),
+            None,
+            None,
+            DBusSignalFlags::NONE,
+            move |_connection, _sender, _path, _interface, _signal, _parameters| {
+                if let Some(context) = context.upgrade() {
+                    context.power_manager.borrow().status_changed();
+                }
+            },
+        )
+    }
+
+    fn property(
+        &self,
+        destination: &str,
+        path: &str,
+        interface: &str,
+        name: &str,
+    ) -> Option<Variant> {
+        let connection = self.connection.as_ref()?;
+        let res = dbus_call(
+            connection,
+            destination,
+            path,
+            PROPERTIES,
+            "Get",
+            &[interface.to_variant(), name.to_variant()],
+        )
+        .ok()?;
+        // Result is (v); Variant is container with single child
+        Some(dbus_child_value(&dbus_child_value(&res, 0), 0))
+    }
+
+    pub fn get_status(&self) -> PlatformResult<PowerStatus> {
+        if self.connection.is_none() {
+            return Err(PlatformError::UnknownError);
+        }
+        let on_battery = self
+            .property(UPOWER, UPOWER_PATH, UPOWER, "OnBattery")
+            .and_then(|v| v.get::<bool>());
+        let has_battery = self
+            .property(
+                UPOWER,
+                UPOWER_DISPLAY_DEVICE_PATH,
+                UPOWER_DEVICE,
+                "IsPresent",
+            )
+            .and_then(|v| v.get::<bool>())
+            .unwrap_or(false);
+        let (battery_level, charging) = if has_battery {
+            let percentage = self
+                .property(
+                    UPOWER,
+                    UPOWER_DISPLAY_DEVICE_PATH,
+                    UPOWER_DEVICE,
+                    "Percentage",
+                )
+                .and_then(|v| v.get::<f64>());
+            let state = self
+                .property(UPOWER, UPOWER_DISPLAY_DEVICE_PATH, UPOWER_DEVICE, "State")
+                .and_then(|v| v.get::<u32>());
+            (
+                percentage.map(|p| (p / 100.0).min(1.0)),
+                matches!(
+                    state,
+                    Some(DEVICE_STATE_CHARGING) | Some(DEVICE_STATE_PENDING_CHARGE)
+                ),
+            )
+        } else {
+            (None, false)
+        };
+        let low_power_mode = self
+            .property(
+                POWER_PROFILES,
+                POWER_PROFILES_PATH,
+                POWER_PROFILES,
+                "ActiveProfile",
+            )
+            .map(|v| v.get_str() == Some("power-saver"))
+            .unwrap_or(false);
+        Ok(PowerStatus {
+            power_source: match on_battery {
+                Some(true) => PowerSource::Battery,
+                Some(false) => PowerSource::Ac,
+                None => PowerSource::Unknown,
+            },
+            has_battery,
+            battery_level,
+            charging,
+            low_power_mode,
+        })
+    }
+}
+
+impl Drop for PlatformPowerSource {
+    fn drop(&mut self) {
+        if let Some(connection) = &self.connection {
+            for subscription in self.subscriptions.drain(..) {
+                connection.signal_unsubscribe(subscription);
+            }
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::rc::Rc;
+
+use gdk::WindowExt;
+use glib::translate::ToGlibPtr;
+use gtk::{ContainerExt, DrawingArea, OverlayExt, WidgetExt};
+
+use crate::shell::{RenderLayerPosition, RenderLayerResizeCallback};
+
+use super::{
+    error::{PlatformError, PlatformResult},
+    window::PlatformWindow,
+};
+
+pub struct PlatformRenderLayer {
+    window: Rc<PlatformWindow>,
+    area: DrawingArea,
+}
+
+impl PlatformRenderLayer {
+    pub fn new(
+        window: Rc<PlatformWindow>,
+        position: RenderLayerPosition,
+        on_resize: Box<RenderLayerResizeCallback>,
+    ) -> PlatformResult<Self> {
+        // Overlay children are always stacked above the main child
+        if position == RenderLayerPosition::BelowContent {
+            return Err(PlatformError::NotImplemented);
+        }
+        let area = DrawingArea::new();
+        area.connect_size_allocate(move |area, allocation| {
+            let scale = area.get_scale_factor();
+            on_resize(
+                (allocation.width * scale) as usize,
+                (allocation.height * scale) as usize,
+                scale as f64,
+            );
+        });
+        let overlay = window.overlay.borrow();
+        overlay.add_overlay(&area);
+        overlay.set_overlay_pass_through(&area, true);
+        area.show();
+        area.realize();
+        if let Some(gdk_window) = area.get_window() {
+            // Own X11 window / Wayland subsurface that can be used as render target
+            gdk_window.ensure_native();
+            gdk_window.set_pass_through(true);
+        }
+        drop(overlay);
+        Ok(Self { window, area })
+    }
+
+    pub fn native_handle(&self) -> usize {
+        let gdk_window: *mut gdk_sys::GdkWindow = self.area.get_window().to_glib_none().0;
+        gdk_window as usize
+    }
+}
+
+impl Drop for PlatformRenderLayer {
+    fn drop(&mut self) {
+        self.window.overlay.borrow().remove(&self.area);
+    }
+}
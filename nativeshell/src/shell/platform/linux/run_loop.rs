@@ -1,18 +1,28 @@
 use std::{
     cell::{Cell, RefCell},
     collections::HashMap,
+    os::unix::io::RawFd,
     rc::Rc,
     time::Duration,
 };
 
-use glib::{source_remove, timeout_add_local, Continue, MainContext, SourceId};
+use glib::{
+    source_remove, timeout_add_local, translate::ToGlibPtr, unix_fd_add_local, Continue,
+    IOCondition, MainContext, SourceId,
+};
+
+use crate::shell::TaskPriority;
 
 pub type HandleType = usize;
 pub const INVALID_HANDLE: HandleType = 0;
 
+// File descriptor; Callback is invoked when it becomes readable (or is closed)
+pub type EventSourceType = RawFd;
+
 pub struct PlatformRunLoop {
     next_handle: Cell<HandleType>,
     timers: Rc<RefCell<HashMap<HandleType, SourceId>>>,
+    event_sources: RefCell<HashMap<HandleType, SourceId>>,
 }
 
 #[allow(unused_variables)]
@@ -21,6 +31,7 @@ impl PlatformRunLoop {
         Self {
             next_handle: Cell::new(INVALID_HANDLE + 1),
             timers: Rc::new(RefCell::new(HashMap::new())),
+            event_sources: RefCell::new(HashMap::new()),
         }
     }
 
@@ -38,7 +49,7 @@ impl PlatformRunLoop {
     }
 
     #[must_use]
-    pub fn schedule<F>(&self, in_time: Duration, callback: F) -> HandleType
+    pub fn schedule<F>(&self, in_time: Duration, priority: TaskPriority, callback: F) -> HandleType
     where
         F: FnOnce() + 'static,
     {
@@ -55,10 +66,47 @@ impl PlatformRunLoop {
             f();
             Continue(false)
         });
+        Self::set_priority(&source_id, priority);
         self.timers.borrow_mut().insert(handle, source_id);
         handle
     }
 
+    // Input events are dispatched with default priority; Idle sources are only
+    // dispatched when no sources with higher priority (including redraw) are ready
+    fn set_priority(source_id: &SourceId, priority: TaskPriority) {
+        let priority = match priority {
+            TaskPriority::UserInteractive => glib_sys::G_PRIORITY_HIGH,
+            TaskPriority::Default => return,
+            TaskPriority::Idle => glib_sys::G_PRIORITY_DEFAULT_IDLE,
+        };
+        if let Some(source) = MainContext::default().find_source_by_id(source_id) {
+            unsafe {
+                glib_sys::g_source_set_priority(source.to_glib_none().0, priority);
+            }
+        }
+    }
+
+    pub fn add_event_source<F>(&self, source: EventSourceType, mut callback: F) -> HandleType
+    where
+        F: FnMut() + 'static,
+    {
+        let handle = self.next_handle();
+        let condition = IOCondition::IN | IOCondition::HUP | IOCondition::ERR;
+        let source_id = unix_fd_add_local(source, condition, move |_, _| {
+            callback();
+            Continue(true)
+        });
+        self.event_sources.borrow_mut().insert(handle, source_id);
+        handle
+    }
+
+    pub fn remove_event_source(&self, handle: HandleType) {
+        let source = self.event_sources.borrow_mut().remove(&handle);
+        if let Some(source) = source {
+            source_remove(source);
+        }
+    }
+
     pub fn run(&self) {
         gtk::main();
     }
@@ -0,0 +1,86 @@
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+
+use glib::SignalHandlerId;
+use gtk::{SettingsExt, StyleContextExt, WidgetExt};
+
+use crate::shell::{api_model::SystemThemeSettings, Context};
+
+use super::error::{PlatformError, PlatformResult};
+
+pub struct PlatformSystemTheme {
+    context: Weak<Context>,
+    notify_handlers: RefCell<Vec<SignalHandlerId>>,
+}
+
+impl PlatformSystemTheme {
+    pub fn new(context: Rc<Context>) -> Self {
+        Self {
+            context: Rc::downgrade(&context),
+            notify_handlers: RefCell::new(Vec::new()),
+        }
+    }
+
+    // GTK is not initialized when this object is created, so start observing
+    // settings changes on first query
+    fn observe_settings(&self, settings: &gtk::Settings) {
+        let mut handlers = self.notify_handlers.borrow_mut();
+        if !handlers.is_empty() {
+            return;
+        }
+        let context = self.context.clone();
+        let on_change = Rc::new(move || {
+            if let Some(context) = context.upgrade() {
+                context.system_theme_manager.borrow().settings_changed();
+            }
+        });
+        let on_change_copy = on_change.clone();
+        handlers.push(settings.connect_property_gtk_theme_name_notify(move |_| on_change_copy()));
+        handlers.push(
+            settings
+                .connect_property_gtk_application_prefer_dark_theme_notify(move |_| on_change()),
+        );
+    }
+
+    pub fn get_settings(&self) -> PlatformResult<SystemThemeSettings> {
+        let settings = gtk::Settings::get_default().ok_or(PlatformError::UnknownError)?;
+        self.observe_settings(&settings);
+
+        let theme_name = settings
+            .get_property_gtk_theme_name()
+            .map(|s| s.to_lowercase())
+            .unwrap_or_default();
+        let dark_mode = settings.get_property_gtk_application_prefer_dark_theme()
+            || theme_name.ends_with("-dark")
+            || theme_name.ends_with(":dark");
+
+        // GTK has no accent color; Selection color of the theme is used instead
+        let style_context = gtk::Entry::new().get_style_context();
+        let selection_color = style_context
+            .lookup_color("theme_selected_bg_color")
+            .map(|color| {
+                let component = |c: f64| (c.clamp(0.0, 1.0) * 255.0).round() as u32;
+                component(color.alpha) << 24
+                    | component(color.red) << 16
+                    | component(color.green) << 8
+                    | component(color.blue)
+            });
+
+        // i.e. HighContrast and HighContrastInverse shipped with GNOME
+        let high_contrast_theme = settings
+            .get_property_gtk_theme_name()
+            .map(|s| s.to_string())
+            .filter(|_| theme_name.starts_with("highcontrast"));
+
+        Ok(SystemThemeSettings {
+            dark_mode,
+            accent_color: selection_color,
+            highlight_color: selection_color,
+            high_contrast_theme,
+            system_dark_mode: dark_mode,
+            system_colors: None,
+        })
+    }
+}
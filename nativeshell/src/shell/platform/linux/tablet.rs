@@ -0,0 +1,60 @@
+use gdk::{AxisUse, Event, EventType, InputSource, ModifierType, WindowExt};
+use glib::ObjectType;
+
+use crate::shell::{
+    api_model::{TabletEvent, TabletEventType, TabletPointerType},
+    Point,
+};
+
+// Converts GDK event to tablet event if the source device is a tablet tool.
+// Position is converted to be relative to given window.
+pub(super) fn tablet_event_for_event(event: &Event, window: &gdk::Window) -> Option<TabletEvent> {
+    let device = event.get_source_device()?;
+    let pointer_type = match device.get_source() {
+        InputSource::Pen => TabletPointerType::Pen,
+        InputSource::Eraser => TabletPointerType::Eraser,
+        InputSource::Cursor => TabletPointerType::Cursor,
+        _ => return None,
+    };
+
+    let event_type = match event.get_event_type() {
+        EventType::ProximityIn => TabletEventType::ProximityEnter,
+        EventType::ProximityOut => TabletEventType::ProximityLeave,
+        EventType::ButtonPress if event.get_button() == Some(1) => TabletEventType::Down,
+        EventType::ButtonRelease if event.get_button() == Some(1) => TabletEventType::Up,
+        // barrel button press and release are reported through buttons
+        EventType::MotionNotify | EventType::ButtonPress | EventType::ButtonRelease => {
+            TabletEventType::Move
+        }
+        _ => return None,
+    };
+
+    let position = event.get_root_coords().map(|(x, y)| {
+        let (_, win_x, win_y) = window.get_origin();
+        Point::xy(x - win_x as f64, y - win_y as f64)
+    });
+
+    let mut buttons = 0;
+    if let Some(state) = event.get_state() {
+        if state.contains(ModifierType::BUTTON2_MASK) {
+            buttons |= 1;
+        }
+        if state.contains(ModifierType::BUTTON3_MASK) {
+            buttons |= 2;
+        }
+    }
+
+    // Axes are normalized by GDK; rotation is reported as fraction of full turn
+    let axis = |axis_use: AxisUse| event.get_axis(axis_use).unwrap_or(0.0);
+
+    Some(TabletEvent {
+        event_type,
+        pointer_type,
+        device_id: device.as_ptr() as i64,
+        position,
+        pressure: axis(AxisUse::Pressure),
+        tilt: Point::xy(axis(AxisUse::Xtilt), axis(AxisUse::Ytilt)),
+        rotation: axis(AxisUse::Rotation) * 360.0,
+        buttons,
+    })
+}
@@ -0,0 +1,268 @@
+use std::{
+    ffi::{CStr, CString},
+    mem, ptr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex, Once,
+    },
+};
+
+use gobject_sys::GObject;
+
+use crate::shell::{GpuSurfaceKind, GpuSurfaceProvider, SharedPixelBuffer};
+
+use super::{
+    engine::PlatformEngine,
+    error::{PlatformError, PlatformResult},
+    flutter_sys,
+};
+
+// Textures are registered through registrar of internal plugin
+const PLUGIN_NAME: &CStr = unsafe { CStr::from_bytes_with_nul_unchecked(b"nativeshell_texture\0") };
+
+pub struct PlatformTexture {
+    registrar: *mut GObject,
+    texture: *mut GObject,
+    id: i64,
+    state: *mut TextureState,
+}
+
+enum TextureState {
+    PixelBuffer {
+        buffer: SharedPixelBuffer,
+        // Copy of the buffer handed to the engine; Must stay valid until next callback
+        front: Mutex<Vec<u8>>,
+    },
+    GpuSurface {
+        provider: Box<GpuSurfaceProvider>,
+    },
+}
+
+const STATE_KEY: &[u8] = b"nativeshell-texture-state\0";
+
+impl PlatformTexture {
+    pub fn new(engine: &PlatformEngine, buffer: SharedPixelBuffer) -> PlatformResult<Self> {
+        let state = TextureState::PixelBuffer {
+            buffer,
+            front: Mutex::new(Vec::new()),
+        };
+        Self::register(engine, pixel_buffer_texture_get_type(), state)
+    }
+
+    pub fn new_gpu_surface(
+        engine: &PlatformEngine,
+        kind: GpuSurfaceKind,
+        provider: Box<GpuSurfaceProvider>,
+    ) -> PlatformResult<Self> {
+        if kind != GpuSurfaceKind::GlTexture {
+            return Err(PlatformError::NotImplemented);
+        }
+        let state = TextureState::GpuSurface { provider };
+        Self::register(engine, gl_texture_get_type(), state)
+    }
+
+    fn register(
+        engine: &PlatformEngine,
+        texture_type: glib_sys::GType,
+        state: TextureState,
+    ) -> PlatformResult<Self> {
+        let mut registrar = ptr::null_mut();
+        engine.with_plugin_registrar(PLUGIN_NAME, |plugin_registrar| unsafe {
+            registrar =
+                flutter_sys::fl_plugin_registrar_get_texture_registrar(plugin_registrar as *mut _);
+            gobject_sys::g_object_ref(registrar);
+        })?;
+        let state = Box::into_raw(Box::new(state));
+        unsafe {
+            let texture = gobject_sys::g_object_new(texture_type, ptr::null());
+            gobject_sys::g_object_set_data(
+                texture,
+                STATE_KEY.as_ptr() as *const _,
+                state as *mut _,
+            );
+            let res = Self {
+                registrar,
+                texture,
+                id: flutter_sys::fl_texture_get_id(texture),
+                state,
+            };
+            if flutter_sys::fl_texture_registrar_register_texture(registrar, texture)
+                == glib_sys::GFALSE
+            {
+                return Err(PlatformError::UnknownError);
+            }
+            Ok(res)
+        }
+    }
+
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    pub fn mark_frame_available(&self) -> PlatformResult<()> {
+        let res = unsafe {
+            flutter_sys::fl_texture_registrar_mark_texture_frame_available(
+                self.registrar,
+                self.texture,
+            )
+        };
+        if res == glib_sys::GFALSE {
+            Err(PlatformError::UnknownError)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl Drop for PlatformTexture {
+    fn drop(&mut self) {
+        unsafe {
+            flutter_sys::fl_texture_registrar_unregister_texture(self.registrar, self.texture);
+            // texture may outlive PlatformTexture if still referenced by engine
+            gobject_sys::g_object_set_data(
+                self.texture,
+                STATE_KEY.as_ptr() as *const _,
+                ptr::null_mut(),
+            );
+            gobject_sys::g_object_unref(self.texture);
+            gobject_sys::g_object_unref(self.registrar);
+            drop(Box::from_raw(self.state));
+        }
+    }
+}
+
+static PIXEL_BUFFER_TEXTURE_TYPE: AtomicUsize = AtomicUsize::new(0);
+static PIXEL_BUFFER_TEXTURE_TYPE_INIT: Once = Once::new();
+
+static GL_TEXTURE_TYPE: AtomicUsize = AtomicUsize::new(0);
+static GL_TEXTURE_TYPE_INIT: Once = Once::new();
+
+// Subclass of FlPixelBufferTexture that copies pixels from TextureState
+fn pixel_buffer_texture_get_type() -> glib_sys::GType {
+    register_type(
+        &PIXEL_BUFFER_TEXTURE_TYPE,
+        &PIXEL_BUFFER_TEXTURE_TYPE_INIT,
+        || unsafe { flutter_sys::fl_pixel_buffer_texture_get_type() },
+        "NativeShellPixelBufferTexture",
+        pixel_buffer_texture_class_init,
+    )
+}
+
+// Subclass of FlTextureGL that populates texture from GpuSurfaceProvider
+fn gl_texture_get_type() -> glib_sys::GType {
+    register_type(
+        &GL_TEXTURE_TYPE,
+        &GL_TEXTURE_TYPE_INIT,
+        || unsafe { flutter_sys::fl_texture_gl_get_type() },
+        "NativeShellGLTexture",
+        gl_texture_class_init,
+    )
+}
+
+fn register_type<F>(
+    texture_type: &AtomicUsize,
+    init: &Once,
+    parent: F,
+    name: &str,
+    class_init: unsafe extern "C" fn(glib_sys::gpointer, glib_sys::gpointer),
+) -> glib_sys::GType
+where
+    F: FnOnce() -> glib_sys::GType,
+{
+    init.call_once(|| unsafe {
+        let parent = parent();
+        let mut query: gobject_sys::GTypeQuery = mem::zeroed();
+        gobject_sys::g_type_query(parent, &mut query);
+        let name = CString::new(name).unwrap();
+        let res = gobject_sys::g_type_register_static_simple(
+            parent,
+            name.as_ptr(),
+            query.class_size,
+            Some(class_init),
+            query.instance_size,
+            None,
+            0,
+        );
+        texture_type.store(res, Ordering::SeqCst);
+    });
+    texture_type.load(Ordering::SeqCst)
+}
+
+unsafe extern "C" fn pixel_buffer_texture_class_init(
+    klass: glib_sys::gpointer,
+    _data: glib_sys::gpointer,
+) {
+    let klass = klass as *mut flutter_sys::FlPixelBufferTextureClass;
+    (*klass).copy_pixels = Some(texture_copy_pixels);
+}
+
+unsafe extern "C" fn gl_texture_class_init(klass: glib_sys::gpointer, _data: glib_sys::gpointer) {
+    let klass = klass as *mut flutter_sys::FlTextureGLClass;
+    (*klass).populate = Some(texture_populate);
+}
+
+// Invoked on raster thread
+unsafe extern "C" fn texture_copy_pixels(
+    texture: *mut GObject,
+    buffer: *mut *const u8,
+    width: *mut u32,
+    height: *mut u32,
+    _error: *mut *mut glib_sys::GError,
+) -> glib_sys::gboolean {
+    // Single transparent pixel for textures without content
+    static EMPTY: [u8; 4] = [0, 0, 0, 0];
+    *buffer = EMPTY.as_ptr();
+    *width = 1;
+    *height = 1;
+
+    let state = gobject_sys::g_object_get_data(texture, STATE_KEY.as_ptr() as *const _)
+        as *const TextureState;
+    if let Some(TextureState::PixelBuffer {
+        buffer: pixels,
+        front,
+    }) = state.as_ref()
+    {
+        let mut front = front.lock().unwrap();
+        {
+            let pixels = pixels.lock().unwrap();
+            if pixels.data.is_empty() {
+                return glib_sys::GTRUE;
+            }
+            front.clear();
+            front.extend_from_slice(&pixels.data);
+            *width = pixels.width as u32;
+            *height = pixels.height as u32;
+        }
+        *buffer = front.as_ptr();
+    }
+    glib_sys::GTRUE
+}
+
+const GL_TEXTURE_2D: u32 = 0x0DE1;
+
+// Invoked on raster thread with engine GL context current
+unsafe extern "C" fn texture_populate(
+    texture: *mut GObject,
+    target: *mut u32,
+    name: *mut u32,
+    width: *mut u32,
+    height: *mut u32,
+    _error: *mut *mut glib_sys::GError,
+) -> glib_sys::gboolean {
+    let state = gobject_sys::g_object_get_data(texture, STATE_KEY.as_ptr() as *const _)
+        as *const TextureState;
+    let surface = match state.as_ref() {
+        Some(TextureState::GpuSurface { provider }) => provider(0, 0),
+        _ => None,
+    };
+    match surface {
+        Some(surface) => {
+            *target = GL_TEXTURE_2D;
+            *name = surface.handle as u32;
+            *width = surface.width as u32;
+            *height = surface.height as u32;
+            glib_sys::GTRUE
+        }
+        None => glib_sys::GFALSE,
+    }
+}
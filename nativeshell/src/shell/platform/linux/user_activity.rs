@@ -0,0 +1,100 @@
+use std::{
+    os::raw::{c_char, c_int, c_ulong, c_void},
+    time::Duration,
+};
+
+use super::{
+    error::{PlatformError, PlatformResult},
+    utils::{dbus_call, dbus_child_value, get_session_type, symbol, SessionType},
+};
+
+const IDLE_MONITOR: &str = "org.gnome.Mutter.IdleMonitor";
+const IDLE_MONITOR_PATH: &str = "/org/gnome/Mutter/IdleMonitor/Core";
+
+#[repr(C)]
+struct XScreenSaverInfo {
+    window: c_ulong,
+    state: c_int,
+    kind: c_int,
+    til_or_since: c_ulong,
+    idle: c_ulong,
+    event_mask: c_ulong,
+}
+
+type XScreenSaverAllocInfo = unsafe extern "C" fn() -> *mut XScreenSaverInfo;
+type XScreenSaverQueryInfo =
+    unsafe extern "C" fn(*mut c_void, c_ulong, *mut XScreenSaverInfo) -> c_int;
+type XDefaultRootWindow = unsafe extern "C" fn(*mut c_void) -> c_ulong;
+type XFree = unsafe extern "C" fn(*mut c_void) -> c_int;
+type GdkX11DisplayGetXDisplay = unsafe extern "C" fn(*mut gdk_sys::GdkDisplay) -> *mut c_void;
+
+// Time since last input; Uses screen saver extension on X11 and Mutter idle
+// monitor on Wayland (GNOME only)
+pub fn idle_time() -> PlatformResult<Duration> {
+    if get_session_type() == SessionType::X11 {
+        if let Some(idle_time) = unsafe { x11_idle_time() } {
+            return Ok(idle_time);
+        }
+    }
+    mutter_idle_time()
+}
+
+fn mutter_idle_time() -> PlatformResult<Duration> {
+    let connection =
+        gio::bus_get_sync(gio::BusType::Session, None::<&gio::Cancellable>).map_err(|e| {
+            PlatformError::GLibError {
+                message: e.to_string(),
+            }
+        })?;
+    let res = dbus_call(
+        &connection,
+        IDLE_MONITOR,
+        IDLE_MONITOR_PATH,
+        IDLE_MONITOR,
+        "GetIdletime",
+        &[],
+    )?;
+    let idle = dbus_child_value(&res, 0)
+        .get::<u64>()
+        .ok_or(PlatformError::UnknownError)?;
+    Ok(Duration::from_millis(idle))
+}
+
+// libXss is loaded at runtime so that it is not required on Wayland
+unsafe fn x11_idle_time() -> Option<Duration> {
+    let display = gdk_sys::gdk_display_get_default();
+    if display.is_null() {
+        return None;
+    }
+    let xss = libc::dlopen(b"libXss.so.1\0".as_ptr() as *const c_char, libc::RTLD_LAZY);
+    if xss.is_null() {
+        return None;
+    }
+    let res = x11_query_idle(xss, display);
+    libc::dlclose(xss);
+    res
+}
+
+unsafe fn x11_query_idle(xss: *mut c_void, display: *mut gdk_sys::GdkDisplay) -> Option<Duration> {
+    // Xlib and GDK X11 backend are already loaded by GTK
+    let get_xdisplay: GdkX11DisplayGetXDisplay =
+        symbol(libc::RTLD_DEFAULT, b"gdk_x11_display_get_xdisplay\0")?;
+    let default_root_window: XDefaultRootWindow =
+        symbol(libc::RTLD_DEFAULT, b"XDefaultRootWindow\0")?;
+    let free: XFree = symbol(libc::RTLD_DEFAULT, b"XFree\0")?;
+    let alloc_info: XScreenSaverAllocInfo = symbol(xss, b"XScreenSaverAllocInfo\0")?;
+    let query_info: XScreenSaverQueryInfo = symbol(xss, b"XScreenSaverQueryInfo\0")?;
+
+    let xdisplay = get_xdisplay(display);
+    let info = alloc_info();
+    if xdisplay.is_null() || info.is_null() {
+        return None;
+    }
+    let res = if query_info(xdisplay, default_root_window(xdisplay), info) != 0 {
+        Some(Duration::from_millis((*info).idle as u64))
+    } else {
+        None
+    };
+    free(info as *mut c_void);
+    res
+}
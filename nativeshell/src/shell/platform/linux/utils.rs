@@ -1,5 +1,16 @@
+use std::{
+    ffi::CString,
+    os::raw::{c_char, c_void},
+};
+
 use gdk::{Event, EventType, Window, WindowExt};
-use glib::translate::{FromGlibPtrFull, ToGlibPtr, ToGlibPtrMut};
+use gio::{DBusCallFlags, DBusConnection};
+use glib::{
+    translate::{FromGlibPtrFull, FromGlibPtrNone, ToGlibPtr, ToGlibPtrMut},
+    Variant,
+};
+
+use super::error::{PlatformError, PlatformResult};
 
 #[derive(PartialEq)]
 pub(super) enum SessionType {
@@ -15,34 +26,96 @@ pub(super) fn get_session_type() -> SessionType {
     }
 }
 
-pub(super) fn synthetize_button_up(event: &Event) -> Event {
+// Returns None if event is not a button press
+pub(super) fn synthetize_button_up(event: &Event) -> Option<Event> {
     if event.get_event_type() != EventType::ButtonPress {
-        panic!("Invalid event type");
+        return None;
     }
     let mut event = event.clone();
     let e: *mut gdk_sys::GdkEvent = event.to_glib_none_mut().0;
     let e = unsafe { &mut *e };
     e.type_ = gdk_sys::GDK_BUTTON_RELEASE;
-    event
+    Some(event)
 }
 
-pub(super) fn synthetize_leave_event_from_motion(event: &Event) -> Event {
+// Returns None if event is not a motion event or lacks window, coordinates or device
+pub(super) fn synthetize_leave_event_from_motion(event: &Event) -> Option<Event> {
     if event.get_event_type() != EventType::MotionNotify {
-        panic!("Invalid event type");
+        return None;
     }
+    let window = event.get_window()?;
+    let coords = event.get_coords()?;
+    let root_coords = event.get_root_coords()?;
+    let device = event.get_device()?;
+
     let mut res = Event::new(EventType::LeaveNotify);
     let e: *mut gdk_sys::GdkEvent = res.to_glib_none_mut().0;
     let e = unsafe { &mut *e };
-    e.crossing.window = event.get_window().unwrap().to_glib_full();
-    e.crossing.subwindow = event.get_window().unwrap().to_glib_full();
+    e.crossing.window = window.to_glib_full();
+    e.crossing.subwindow = window.to_glib_full();
     e.crossing.send_event = 1;
-    e.crossing.x = event.get_coords().unwrap().0;
-    e.crossing.y = event.get_coords().unwrap().1;
-    e.crossing.x_root = event.get_root_coords().unwrap().0;
-    e.crossing.y_root = event.get_root_coords().unwrap().1;
+    e.crossing.x = coords.0;
+    e.crossing.y = coords.1;
+    e.crossing.x_root = root_coords.0;
+    e.crossing.y_root = root_coords.1;
 
     unsafe {
-        gdk_sys::gdk_event_set_device(e, event.get_device().unwrap().to_glib_none().0);
+        gdk_sys::gdk_event_set_device(e, device.to_glib_none().0);
+    }
+    Some(res)
+}
+
+// Button, motion or key event targeting given window as if sent by the system;
+// Coordinates are relative to the window
+pub(super) fn synthetize_event(
+    window: &Window,
+    event_type: EventType,
+    position: (f64, f64),
+    button: u32,
+    keyval: u32,
+) -> Event {
+    let mut res = Event::new(event_type);
+    let e: *mut gdk_sys::GdkEvent = res.to_glib_none_mut().0;
+    let e = unsafe { &mut *e };
+    let (_, win_x, win_y) = window.get_origin();
+    let root = (position.0 + win_x as f64, position.1 + win_y as f64);
+    let time = (glib::get_monotonic_time() / 1000) as u32;
+    let seat = window.get_display().get_default_seat();
+    let device = match event_type {
+        EventType::KeyPress | EventType::KeyRelease => seat.and_then(|s| s.get_keyboard()),
+        _ => seat.and_then(|s| s.get_pointer()),
+    };
+    unsafe {
+        match event_type {
+            EventType::ButtonPress | EventType::ButtonRelease => {
+                e.button.window = window.to_glib_full();
+                e.button.send_event = 1;
+                e.button.time = time;
+                e.button.x = position.0;
+                e.button.y = position.1;
+                e.button.x_root = root.0;
+                e.button.y_root = root.1;
+                e.button.button = button;
+            }
+            EventType::MotionNotify => {
+                e.motion.window = window.to_glib_full();
+                e.motion.send_event = 1;
+                e.motion.time = time;
+                e.motion.x = position.0;
+                e.motion.y = position.1;
+                e.motion.x_root = root.0;
+                e.motion.y_root = root.1;
+            }
+            _ => {
+                e.key.window = window.to_glib_full();
+                e.key.send_event = 1;
+                e.key.time = time;
+                e.key.keyval = keyval;
+            }
+        }
+        if let Some(device) = device {
+            gdk_sys::gdk_event_set_device(e, device.to_glib_none().0);
+        }
     }
     res
 }
@@ -69,3 +142,116 @@ pub(super) fn translate_event_to_window(event: &Event, win: &Window) -> Event {
     }
     event
 }
+
+// Identifies application to session services
+pub(super) fn application_id() -> String {
+    glib::get_prgname().unwrap_or_else(|| "nativeshell".into())
+}
+
+// Synchronous method call on session bus object; Arguments are passed as tuple
+pub(super) fn dbus_call(
+    connection: &DBusConnection,
+    destination: &str,
+    path: &str,
+    interface: &str,
+    method: &str,
+    arguments: &[Variant],
+) -> PlatformResult<Variant> {
+    connection
+        .call_sync(
+            Some(destination),
+            path,
+            interface,
+            method,
+            Some(&dbus_tuple(arguments)),
+            None,
+            DBusCallFlags::NONE,
+            -1,
+            None::<&gio::Cancellable>,
+        )
+        .map_err(|e| PlatformError::GLibError {
+            message: e.to_string(),
+        })
+}
+
+// Variant tuples and object paths are not supported by glib bindings
+pub(super) fn dbus_tuple(children: &[Variant]) -> Variant {
+    let children: Vec<*mut glib_sys::GVariant> =
+        children.iter().map(|c| c.to_glib_none().0).collect();
+    unsafe {
+        Variant::from_glib_none(glib_sys::g_variant_new_tuple(
+            children.as_ptr(),
+            children.len(),
+        ))
+    }
+}
+
+pub(super) fn dbus_object_path(path: &str) -> Variant {
+    unsafe { Variant::from_glib_none(glib_sys::g_variant_new_object_path(path.to_glib_none().0)) }
+}
+
+pub(super) fn dbus_string_array(strings: &[String]) -> Variant {
+    let strings: Vec<CString> = strings
+        .iter()
+        .map(|s| CString::new(s.as_str()).unwrap_or_default())
+        .collect();
+    let pointers: Vec<*const c_char> = strings.iter().map(|s| s.as_ptr()).collect();
+    unsafe {
+        Variant::from_glib_none(glib_sys::g_variant_new_strv(
+            pointers.as_ptr(),
+            pointers.len() as isize,
+        ))
+    }
+}
+
+pub(super) fn dbus_child_value(variant: &Variant, index: usize) -> Variant {
+    unsafe {
+        Variant::from_glib_full(glib_sys::g_variant_get_child_value(
+            variant.to_glib_none().0,
+            index,
+        ))
+    }
+}
+
+pub(super) fn dbus_children(variant: &Variant) -> Vec<Variant> {
+    let count = unsafe { glib_sys::g_variant_n_children(variant.to_glib_none().0) };
+    (0..count).map(|i| dbus_child_value(variant, i)).collect()
+}
+
+// Array with given element type (i.e. "i" or "{sv}"); Type is needed for empty arrays
+pub(super) fn dbus_array(element_type: &str, children: &[Variant]) -> Variant {
+    let element_type = CString::new(element_type).unwrap();
+    let children: Vec<*mut glib_sys::GVariant> =
+        children.iter().map(|c| c.to_glib_none().0).collect();
+    unsafe {
+        Variant::from_glib_none(glib_sys::g_variant_new_array(
+            element_type.as_ptr() as *const _,
+            children.as_ptr(),
+            children.len(),
+        ))
+    }
+}
+
+// Boxes value as "v"
+pub(super) fn dbus_variant(value: &Variant) -> Variant {
+    unsafe { Variant::from_glib_none(glib_sys::g_variant_new_variant(value.to_glib_none().0)) }
+}
+
+pub(super) fn dbus_dict_entry(key: &Variant, value: &Variant) -> Variant {
+    unsafe {
+        Variant::from_glib_none(glib_sys::g_variant_new_dict_entry(
+            key.to_glib_none().0,
+            value.to_glib_none().0,
+        ))
+    }
+}
+
+// Name must be null terminated
+pub(super) unsafe fn symbol<T: Copy>(library: *mut c_void, name: &[u8]) -> Option<T> {
+    let symbol = libc::dlsym(library, name.as_ptr() as *const c_char);
+    if symbol.is_null() {
+        None
+    } else {
+        Some(std::mem::transmute_copy(&symbol))
+    }
+}
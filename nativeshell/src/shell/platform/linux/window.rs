@@ -5,33 +5,45 @@ use std::{
     time::Duration,
 };
 
-use gdk::{Event, EventType, WMDecoration, WMFunction, WindowExt};
-use glib::{Cast, ObjectExt};
+use gdk::{
+    CrossingMode, Event, EventCrossing, EventMask, EventType, ModifierType, NotifyType,
+    WMDecoration, WMFunction, WindowExt, WindowState,
+};
+use glib::{translate::ToGlibPtr, Cast, ObjectExt};
 use gtk::{
-    propagate_event, ContainerExt, EventBox, GtkWindowExt, Inhibit, Overlay, OverlayExt, Widget,
-    WidgetExt,
+    prelude::WidgetExtManual, propagate_event, ContainerExt, EventBox, GtkWindowExt, HeaderBarExt,
+    Inhibit, Overlay, OverlayExt, Widget, WidgetExt,
 };
 
 use crate::{
     codec::Value,
     shell::{
         api_model::{
-            DragEffect, DragRequest, PopupMenuRequest, PopupMenuResponse, WindowFrame,
-            WindowGeometry, WindowGeometryFlags, WindowGeometryRequest, WindowStyle,
+            ActivationPolicy, DragEffect, DragRequest, KeyboardModifiers, NativeWindowContent,
+            PointerState, PopupMenuRequest, PopupMenuResponse, QuickLookRequest,
+            SharePickerRequest, TouchBar, WindowAppearance, WindowBackdrop, WindowFrame,
+            WindowGeometry, WindowGeometryFlags, WindowGeometryRequest, WindowStyle, WindowType,
         },
-        Context, ISize, PlatformWindowDelegate, Point, ScheduledCallback, Size,
+        platform::WindowError,
+        Context, ISize, PlatformWindowDelegate, Point, Rect, ScheduledCallback, Size,
+        SyntheticEvent, SyntheticMouseButton,
     },
     util::{LateRefCell, OkLog},
 };
 
 use super::{
+    dbus_menu::DBusMenu,
     drag_context::{DragContext, DropContext},
     engine::PlatformEngine,
     error::{PlatformError, PlatformResult},
     flutter::View,
     menu::PlatformMenu,
     size_widget::{create_size_widget, size_widget_set_min_size},
-    utils::{get_session_type, synthetize_button_up, translate_event_to_window, SessionType},
+    tablet::tablet_event_for_event,
+    utils::{
+        get_session_type, synthetize_button_up, synthetize_event, translate_event_to_window,
+        SessionType,
+    },
     window_menu::WindowMenu,
 };
 
@@ -58,8 +70,11 @@ pub struct PlatformWindow {
     modal_close_callback: RefCell<Option<Box<dyn FnOnce(PlatformResult<Value>)>>>,
     size_widget: Widget,
     pub(super) view: LateRefCell<View>,
+    pub(super) overlay: LateRefCell<Overlay>,
     ready_to_show: Cell<bool>,
     show_when_ready: Cell<bool>,
+    // Window without Flutter view; Shown without waiting for first frame
+    native: Cell<bool>,
     pending_first_frame: Cell<bool>,
     last_geometry_request: RefCell<Option<WindowGeometryRequest>>,
     last_window_style: RefCell<Option<WindowStyle>>,
@@ -69,6 +84,9 @@ pub struct PlatformWindow {
     pub(super) window_menu: LateRefCell<WindowMenu>,
     pub(super) drop_context: LateRefCell<DropContext>,
     drag_context: LateRefCell<DragContext>,
+    header_bar: gtk::HeaderBar,
+    title: RefCell<String>,
+    document_edited: Cell<bool>,
 }
 
 impl PlatformWindow {
@@ -88,8 +106,10 @@ impl PlatformWindow {
             parent,
             size_widget: create_size_widget(),
             view: LateRefCell::new(),
+            overlay: LateRefCell::new(),
             ready_to_show: Cell::new(false),
             show_when_ready: Cell::new(false),
+            native: Cell::new(false),
             pending_first_frame: Cell::new(true),
             last_geometry_request: RefCell::new(None),
             last_window_style: RefCell::new(None),
@@ -99,11 +119,17 @@ impl PlatformWindow {
             window_menu: LateRefCell::new(),
             drop_context: LateRefCell::new(),
             drag_context: LateRefCell::new(),
+            header_bar: gtk::HeaderBar::new(),
+            title: RefCell::new(String::new()),
+            document_edited: Cell::new(false),
         }
     }
 
     pub fn on_first_frame(&self) {
         self.window.set_opacity(1.0);
+        if let Some(delegate) = self.delegate.upgrade() {
+            delegate.visibility_changed(true);
+        }
     }
 
     pub fn assign_weak_self(&self, weak: Weak<PlatformWindow>, engine: &PlatformEngine) {
@@ -117,11 +143,22 @@ impl PlatformWindow {
         overlay.add(&self.size_widget);
         overlay.add_overlay(&self.window_menu.borrow().menu_bar_container);
 
-        self.view.set(engine.view.clone());
+        self.view.set(engine.view.clone().unwrap());
         overlay.add_overlay(&self.view.borrow().clone());
 
         self.view.borrow().grab_focus();
 
+        // native views are added as overlays above flutter view
+        self.overlay.set(overlay);
+
+        // Titlebar can only be set before window is realized (which also starts
+        // the engine), so header bar is always present and shown only for
+        // client-side decorated windows
+        self.header_bar.set_show_close_button(true);
+        self.window.set_titlebar(Some(&self.header_bar));
+        self.window
+            .set_decorated(get_session_type() == SessionType::Wayland);
+
         self.window.realize();
         unsafe {
             self.window
@@ -129,6 +166,14 @@ impl PlatformWindow {
                 .unwrap()
                 .set_data("nativeshell_platform_window", weak);
         }
+        if get_session_type() == SessionType::X11 {
+            // Undecorated GTK window has no window manager decorations either
+            self.window
+                .get_window()
+                .unwrap()
+                .set_decorations(WMDecoration::ALL);
+        }
+        DBusMenu::window_created(&self.window);
 
         // by default make window resizable, non resizable window need size
         // specified
@@ -136,6 +181,9 @@ impl PlatformWindow {
 
         self.schedule_first_frame_notification();
 
+        self.window
+            .add_events(EventMask::PROXIMITY_IN_MASK | EventMask::PROXIMITY_OUT_MASK);
+
         let weak = self.weak_self.borrow().clone();
         let weak_clone = weak.clone();
         self.window.connect_delete_event(move |_, _| {
@@ -147,6 +195,8 @@ impl PlatformWindow {
             }
         });
 
+        self.connect_state_events();
+
         self.drop_context
             .set(DropContext::new(self.context.clone(), weak.clone()));
         self.drag_context
@@ -154,6 +204,96 @@ impl PlatformWindow {
         self.connect_drag_drop_events();
     }
 
+    pub fn assign_weak_self_native(
+        &self,
+        weak: Weak<PlatformWindow>,
+        content: &NativeWindowContent,
+    ) {
+        self.weak_self.set(weak.clone());
+        self.native.set(true);
+
+        self.window_menu.set(WindowMenu::new(weak.clone()));
+
+        let overlay = Overlay::new();
+        self.window.add(&overlay);
+        overlay.add(&self.size_widget);
+
+        let color = content.background_color;
+        let component = |shift: u32| ((color >> shift) & 0xFF) as f64 / 255.0;
+        let (red, green, blue, alpha) = (component(16), component(8), component(0), component(24));
+        if alpha < 1.0 {
+            let visual = self.window.get_screen().and_then(|s| s.get_rgba_visual());
+            if let Some(visual) = visual {
+                self.window.set_visual(Some(&visual));
+                self.window.set_app_paintable(true);
+            }
+        }
+        let area = gtk::DrawingArea::new();
+        area.connect_draw(move |_, cr| {
+            cr.set_operator(cairo::Operator::Source);
+            cr.set_source_rgba(red, green, blue, alpha);
+            cr.paint();
+            Inhibit(true)
+        });
+        overlay.add_overlay(&area);
+        overlay.show_all();
+        self.overlay.set(overlay);
+
+        self.header_bar.set_show_close_button(true);
+        self.window.set_titlebar(Some(&self.header_bar));
+        self.window
+            .set_decorated(get_session_type() == SessionType::Wayland);
+
+        // Platform window is not attached to GDK window, so events are not
+        // routed through on_event (there is no Flutter view to forward them to)
+        self.window.realize();
+        self.window.set_resizable(true);
+
+        let weak_clone = weak.clone();
+        self.window.connect_delete_event(move |_, _| {
+            let s = weak_clone.upgrade();
+            if let Some(s) = s {
+                s.on_delete()
+            } else {
+                Inhibit(false)
+            }
+        });
+
+        self.connect_state_events();
+
+        self.drop_context
+            .set(DropContext::new(self.context.clone(), weak.clone()));
+        self.drag_context
+            .set(DragContext::new(self.context.clone(), weak));
+    }
+
+    fn connect_state_events(&self) {
+        let delegate = self.delegate.clone();
+        self.window.connect_focus_in_event(move |_, _| {
+            if let Some(delegate) = delegate.upgrade() {
+                delegate.activation_changed(true);
+            }
+            Inhibit(false)
+        });
+        let delegate = self.delegate.clone();
+        self.window.connect_focus_out_event(move |_, _| {
+            if let Some(delegate) = delegate.upgrade() {
+                delegate.activation_changed(false);
+            }
+            Inhibit(false)
+        });
+        let delegate = self.delegate.clone();
+        self.window.connect_window_state_event(move |_, event| {
+            if let Some(delegate) = delegate.upgrade() {
+                let iconified = event
+                    .get_new_window_state()
+                    .contains(WindowState::ICONIFIED);
+                delegate.occlusion_changed(iconified);
+            }
+            Inhibit(false)
+        });
+    }
+
     fn connect_drag_drop_events(&self) {
         if let Some(event_box) = self.get_event_box() {
             self.drop_context.borrow().register(&event_box);
@@ -233,6 +373,8 @@ impl PlatformWindow {
                 if let Some(callback) = callback {
                     callback(Ok(Value::Null));
                 }
+                // Retained events keep references to window resources
+                self.last_event.borrow_mut().clear();
                 delegate.will_close();
                 return Inhibit(false);
             } else {
@@ -294,6 +436,9 @@ impl PlatformWindow {
     pub fn show(&self) -> PlatformResult<()> {
         if self.ready_to_show.get() {
             self.window.show();
+            if let Some(delegate) = self.delegate.upgrade() {
+                delegate.visibility_changed(true);
+            }
             Ok(())
         } else {
             self.show_when_ready.set(true);
@@ -301,6 +446,20 @@ impl PlatformWindow {
         }
     }
 
+    // Brings window to front and focuses it (restores it if minimized)
+    pub fn activate(&self) -> PlatformResult<()> {
+        self.window
+            .present_with_time(gdk_sys::GDK_CURRENT_TIME as u32);
+        Ok(())
+    }
+
+    pub fn set_activation_policy(&self, policy: ActivationPolicy) -> PlatformResult<()> {
+        let skip = policy != ActivationPolicy::Regular;
+        self.window.set_skip_taskbar_hint(skip);
+        self.window.set_skip_pager_hint(skip);
+        Ok(())
+    }
+
     pub fn hide(&self) -> PlatformResult<()> {
         if self.ready_to_show.get() {
             self.window.hide();
@@ -315,7 +474,11 @@ impl PlatformWindow {
 
     pub fn ready_to_show(&self) -> PlatformResult<()> {
         self.ready_to_show.set(true);
-        if self.show_when_ready.get() {
+        if self.show_when_ready.get() && self.native.get() {
+            self.window.show();
+            self.on_first_frame();
+            Ok(())
+        } else if self.show_when_ready.get() {
             self.window.show(); // otherwise complains about size allocation in show_all
             self.window.set_opacity(0.0);
             self.window.show_all();
@@ -338,11 +501,52 @@ impl PlatformWindow {
                 .insert(event.get_event_type(), event.clone());
         }
 
+        self.handle_tablet_event(event);
+        self.update_pointer_inside(event);
+
         if self.window_menu.borrow().should_forward_event(&event) {
             self.propagate_event(event);
         }
     }
 
+    // Only crossing events of toplevel window are considered, crossing between child windows
+    // and crossings caused by grabs (i.e. popup menu) do not change pointer state
+    fn update_pointer_inside(&self, event: &Event) {
+        let inside = match event.get_event_type() {
+            EventType::MotionNotify => true,
+            EventType::EnterNotify | EventType::LeaveNotify => {
+                let crossing = match event.downcast_ref::<EventCrossing>() {
+                    Some(crossing) => crossing,
+                    None => return,
+                };
+                if event.get_window() != self.window.get_window()
+                    || crossing.get_detail() == NotifyType::Inferior
+                    || crossing.get_mode() == CrossingMode::Grab
+                    || crossing.get_mode() == CrossingMode::GtkGrab
+                {
+                    return;
+                }
+                event.get_event_type() == EventType::EnterNotify
+            }
+            _ => return,
+        };
+        if let Some(delegate) = self.delegate.upgrade() {
+            if inside {
+                delegate.pointer_entered();
+            } else {
+                delegate.pointer_exited();
+            }
+        }
+    }
+
+    fn handle_tablet_event(&self, event: &Event) {
+        let view_window = self.view.borrow().get_window();
+        let tablet_event = view_window.and_then(|window| tablet_event_for_event(event, &window));
+        if let (Some(tablet_event), Some(delegate)) = (tablet_event, self.delegate.upgrade()) {
+            delegate.tablet_event(tablet_event);
+        }
+    }
+
     pub(super) fn propagate_event(&self, event: &mut Event) {
         let event_box = self.get_event_box();
         if let Some(event_box) = event_box {
@@ -497,15 +701,127 @@ impl PlatformWindow {
     }
 
     pub fn set_title(&self, title: String) -> PlatformResult<()> {
-        self.window.set_title(&title);
+        self.title.replace(title);
+        self.update_title();
         Ok(())
     }
 
+    // Only visible in header bar
+    pub fn set_subtitle(&self, subtitle: String) -> PlatformResult<()> {
+        let subtitle = Some(subtitle.as_str()).filter(|s| !s.is_empty());
+        self.header_bar.set_subtitle(subtitle);
+        Ok(())
+    }
+
+    // There is no proxy icon on Linux
+    pub fn set_represented_file(&self, _path: Option<String>) -> PlatformResult<()> {
+        Ok(())
+    }
+
+    pub fn set_document_edited(&self, edited: bool) -> PlatformResult<()> {
+        self.document_edited.set(edited);
+        self.update_title();
+        Ok(())
+    }
+
+    // There is no system share UI
+    pub fn show_share_picker(&self, _request: SharePickerRequest) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    // Quick Look is MacOS only
+    pub fn show_quick_look(&self, _request: QuickLookRequest) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn set_quick_look_index(&self, _index: usize) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn hide_quick_look(&self) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    // GTK theme variant is global; Window manager uses this property to pick
+    // decoration variant of individual window (X11 only)
+    pub fn set_appearance(&self, appearance: WindowAppearance) -> PlatformResult<()> {
+        self.window.realize();
+        let window = self.window.get_window().unwrap();
+        let window: *mut gdk_sys::GdkWindow = window.to_glib_none().0;
+        unsafe {
+            let property = gdk_sys::gdk_atom_intern(
+                b"_GTK_THEME_VARIANT\0".as_ptr() as *const _,
+                glib_sys::GFALSE,
+            );
+            let variant: &[u8] = match appearance {
+                WindowAppearance::System => {
+                    gdk_sys::gdk_property_delete(window, property);
+                    return Ok(());
+                }
+                WindowAppearance::Light => b"light",
+                WindowAppearance::Dark => b"dark",
+            };
+            gdk_sys::gdk_property_change(
+                window,
+                property,
+                gdk_sys::gdk_atom_intern(b"UTF8_STRING\0".as_ptr() as *const _, glib_sys::GFALSE),
+                8,
+                gdk_sys::GDK_PROP_MODE_REPLACE,
+                variant.as_ptr(),
+                variant.len() as i32,
+            );
+        }
+        Ok(())
+    }
+
+    pub fn set_backdrop(&self, backdrop: WindowBackdrop) -> PlatformResult<()> {
+        match backdrop {
+            WindowBackdrop::None => Ok(()),
+            // Backdrop materials are Windows only
+            _ => Err(PlatformError::NotImplemented),
+        }
+    }
+
+    // Most window managers only read the type when window is mapped
+    pub fn set_window_type(&self, window_type: WindowType) -> PlatformResult<()> {
+        self.window.set_type_hint(match window_type {
+            WindowType::Normal => gdk::WindowTypeHint::Normal,
+            WindowType::Dialog => gdk::WindowTypeHint::Dialog,
+            WindowType::Splash => gdk::WindowTypeHint::Splashscreen,
+            WindowType::Dock => gdk::WindowTypeHint::Dock,
+            WindowType::Tooltip => gdk::WindowTypeHint::Tooltip,
+            WindowType::Notification => gdk::WindowTypeHint::Notification,
+        });
+        Ok(())
+    }
+
+    // GNOME convention for unsaved documents
+    fn update_title(&self) {
+        let title = self.title.borrow();
+        let title = if self.document_edited.get() {
+            format!("*{}", title)
+        } else {
+            title.clone()
+        };
+        self.window.set_title(&title);
+        self.header_bar.set_title(Some(&title));
+    }
+
     pub fn set_style(&self, style: WindowStyle) -> PlatformResult<()> {
         self.last_window_style.borrow_mut().replace(style.clone());
 
         self.window.realize();
 
+        // Header bar is drawn by GTK as client-side decoration
+        let client_side = style.header_bar && style.frame == WindowFrame::Regular;
+        self.window
+            .set_decorated(client_side && style.frame == WindowFrame::Regular);
+        if client_side {
+            self.window.set_resizable(style.can_resize);
+            self.window.set_deletable(style.can_close);
+            return Ok(());
+        }
+
         let window = self.window.get_window().unwrap();
         match style.frame {
             WindowFrame::Regular => {
@@ -543,11 +859,14 @@ impl PlatformWindow {
     pub fn perform_window_drag(&self) -> PlatformResult<()> {
         if let Some(event) = self.last_event.borrow().get(&EventType::ButtonPress) {
             if let (Some(coords), Some(button)) = (event.get_root_coords(), event.get_button()) {
+                let window = self.window.get_window().ok_or(WindowError::NotRealized)?;
+
                 // release event will get eaten, we need to synthetize it otherwise flutter keeps waiting for it
-                let mut release = synthetize_button_up(event);
-                gtk::main_do_event(&mut release);
+                if let Some(mut release) = synthetize_button_up(event) {
+                    gtk::main_do_event(&mut release);
+                }
 
-                self.window.get_window().unwrap().begin_move_drag(
+                window.begin_move_drag(
                     button as i32,
                     coords.0 as i32,
                     coords.1 as i32,
@@ -560,18 +879,123 @@ impl PlatformWindow {
     }
 
     pub fn begin_drag_session(&self, request: DragRequest) -> PlatformResult<()> {
+        let event_box = self.get_event_box().ok_or(WindowError::NotRealized)?;
+
         // relase event will get eaten
         if let Some(event) = self.last_event.borrow().get(&EventType::ButtonPress) {
-            let mut release = synthetize_button_up(event);
-            gtk::main_do_event(&mut release);
+            if let Some(mut release) = synthetize_button_up(event) {
+                gtk::main_do_event(&mut release);
+            }
         }
 
-        self.drag_context
-            .borrow()
-            .begin_drag(request, self.get_event_box().as_ref().unwrap());
+        self.drag_context.borrow().begin_drag(request, &event_box)
+    }
+
+    // Dispatched through gtk main loop so that event handlers and workarounds of this
+    // window see the event same as they would see real input
+    pub fn inject_event(&self, event: &SyntheticEvent) -> PlatformResult<()> {
+        let event_box = self.get_event_box().ok_or(WindowError::NotRealized)?;
+        let window = event_box.get_window().ok_or(WindowError::NotRealized)?;
+        let button_number = |button: &SyntheticMouseButton| match button {
+            SyntheticMouseButton::Left => 1,
+            SyntheticMouseButton::Middle => 2,
+            SyntheticMouseButton::Right => 3,
+        };
+        let mut event = match event {
+            SyntheticEvent::MouseDown { position, button } => synthetize_event(
+                &window,
+                EventType::ButtonPress,
+                (position.x, position.y),
+                button_number(button),
+                0,
+            ),
+            SyntheticEvent::MouseUp { position, button } => synthetize_event(
+                &window,
+                EventType::ButtonRelease,
+                (position.x, position.y),
+                button_number(button),
+                0,
+            ),
+            SyntheticEvent::MouseMove { position } => synthetize_event(
+                &window,
+                EventType::MotionNotify,
+                (position.x, position.y),
+                0,
+                0,
+            ),
+            SyntheticEvent::KeyDown { key_code, .. } => synthetize_event(
+                &window,
+                EventType::KeyPress,
+                (0.0, 0.0),
+                0,
+                *key_code as u32,
+            ),
+            SyntheticEvent::KeyUp { key_code, .. } => synthetize_event(
+                &window,
+                EventType::KeyRelease,
+                (0.0, 0.0),
+                0,
+                *key_code as u32,
+            ),
+        };
+        gtk::main_do_event(&mut event);
         Ok(())
     }
 
+    // Derived from most recent input event; GDK event state does not include the
+    // button being pressed or released by the event itself
+    pub fn pointer_state(&self) -> PointerState {
+        let last_event = self
+            .last_event
+            .borrow()
+            .values()
+            .max_by_key(|e| e.get_time())
+            .cloned();
+        let last_event = match last_event {
+            Some(event) => event,
+            None => return Default::default(),
+        };
+        let state = last_event.get_state().unwrap_or_else(ModifierType::empty);
+        let button_bit = |button| match button {
+            1 => 1,
+            3 => 2,
+            2 => 4,
+            _ => 0,
+        };
+        let masks = [
+            (1, ModifierType::BUTTON1_MASK),
+            (2, ModifierType::BUTTON2_MASK),
+            (3, ModifierType::BUTTON3_MASK),
+        ];
+        let mut buttons = 0;
+        for (button, mask) in masks.iter() {
+            if state.contains(*mask) {
+                buttons |= button_bit(*button);
+            }
+        }
+        match (last_event.get_event_type(), last_event.get_button()) {
+            (EventType::ButtonPress, Some(button)) => buttons |= button_bit(button),
+            (EventType::ButtonRelease, Some(button)) => buttons &= !button_bit(button),
+            _ => {}
+        }
+        let view_window = self.view.borrow().get_window();
+        let position = view_window.and_then(|window| {
+            let (x, y) = last_event.get_root_coords()?;
+            let (_, win_x, win_y) = window.get_origin();
+            Some(Point::xy(x - win_x as f64, y - win_y as f64))
+        });
+        PointerState {
+            position,
+            buttons,
+            modifiers: KeyboardModifiers {
+                shift: state.contains(ModifierType::SHIFT_MASK),
+                control: state.contains(ModifierType::CONTROL_MASK),
+                alt: state.contains(ModifierType::MOD1_MASK),
+                meta: state.contains(ModifierType::SUPER_MASK),
+            },
+        }
+    }
+
     pub fn set_pending_effect(&self, effect: DragEffect) {
         self.drop_context.borrow().set_pending_effect(effect);
     }
@@ -593,7 +1017,30 @@ impl PlatformWindow {
         Err(PlatformError::NotImplemented)
     }
 
+    // Only used for Snap Layouts on Windows
+    pub fn set_maximize_button_rect(&self, _rect: Option<Rect>) -> PlatformResult<()> {
+        Ok(())
+    }
+
+    // AppUserModelID is Windows only
+    pub fn set_app_user_model_id(&self, _id: Option<String>) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    // Spaces are MacOS only
+    pub fn is_on_active_space(&self) -> PlatformResult<bool> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn move_to_active_space(&self) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
     pub fn set_window_menu(&self, _menu: Option<Rc<PlatformMenu>>) -> PlatformResult<()> {
         Err(PlatformError::NotImplemented)
     }
+
+    pub fn set_touch_bar(&self, _touch_bar: Option<TouchBar>) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
 }
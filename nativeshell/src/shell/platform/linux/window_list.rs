@@ -0,0 +1,173 @@
+use std::{mem::size_of, os::raw::c_ulong};
+
+use cairo::{Format, ImageSurface};
+use gdk::{prelude::GdkContextExt, Atom, WindowExt};
+use glib::translate::{FromGlibPtrFull, ToGlibPtr};
+
+use crate::shell::{
+    api_model::{ExternalWindow, ImageData, WindowListRequest},
+    Rect, Size,
+};
+
+use super::{
+    error::{PlatformError, PlatformResult},
+    utils::{get_session_type, symbol, SessionType},
+};
+
+type GdkX11WindowForeignNewForDisplay =
+    unsafe extern "C" fn(*mut gdk_sys::GdkDisplay, c_ulong) -> *mut gdk_sys::GdkWindow;
+
+// Upper bound for window property size
+const MAX_PROPERTY_LENGTH: u64 = 1024 * 1024;
+
+// Format 32 properties are returned as array of longs; ATOM properties are
+// converted to GdkAtom values
+fn property_longs(window: &gdk::Window, name: &str, type_: &str) -> Vec<c_ulong> {
+    let data = gdk::property_get(
+        window,
+        &Atom::intern(name),
+        &Atom::intern(type_),
+        0,
+        MAX_PROPERTY_LENGTH,
+        0,
+    );
+    match data {
+        Some((_, 32, data)) => data
+            .chunks_exact(size_of::<c_ulong>())
+            .map(|c| {
+                let mut bytes = [0u8; size_of::<c_ulong>()];
+                bytes.copy_from_slice(c);
+                c_ulong::from_ne_bytes(bytes)
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn property_string(window: &gdk::Window, name: &str, type_: &str) -> Option<String> {
+    gdk::property_get(
+        window,
+        &Atom::intern(name),
+        &Atom::intern(type_),
+        0,
+        MAX_PROPERTY_LENGTH,
+        0,
+    )
+    .map(|(_, _, data)| String::from_utf8_lossy(&data).into_owned())
+    .filter(|s| !s.is_empty())
+}
+
+// Docks, desktop and panels are excluded
+fn is_normal_window(window: &gdk::Window) -> bool {
+    let normal = unsafe { Atom::intern("_NET_WM_WINDOW_TYPE_NORMAL").value() } as c_ulong;
+    let types = property_longs(window, "_NET_WM_WINDOW_TYPE", "ATOM");
+    types.is_empty() || types.contains(&normal)
+}
+
+fn process_name(pid: i64) -> String {
+    std::fs::read_to_string(format!("/proc/{}/comm", pid))
+        .map(|name| name.trim_end().into())
+        .unwrap_or_default()
+}
+
+// Lists windows managed by EWMH compliant window manager; Wayland compositors
+// don't allow clients to enumerate other windows
+pub fn get_windows(request: &WindowListRequest) -> PlatformResult<Vec<ExternalWindow>> {
+    if get_session_type() != SessionType::X11 {
+        return Err(PlatformError::NotImplemented);
+    }
+    let display = gdk::Display::get_default().ok_or(PlatformError::UnknownError)?;
+    let root = display
+        .get_default_screen()
+        .get_root_window()
+        .ok_or(PlatformError::UnknownError)?;
+    let foreign_new: GdkX11WindowForeignNewForDisplay = unsafe {
+        symbol(
+            libc::RTLD_DEFAULT,
+            b"gdk_x11_window_foreign_new_for_display\0",
+        )
+    }
+    .ok_or(PlatformError::UnknownError)?;
+
+    let current_pid = std::process::id() as i64;
+    let mut res = Vec::new();
+    // Stacking order is bottom to top
+    for xid in property_longs(&root, "_NET_CLIENT_LIST_STACKING", "WINDOW")
+        .into_iter()
+        .rev()
+    {
+        let window: gdk::Window = unsafe {
+            let window = foreign_new(display.to_glib_none().0, xid);
+            if window.is_null() {
+                continue;
+            }
+            gdk::Window::from_glib_full(window)
+        };
+        let owner_pid = property_longs(&window, "_NET_WM_PID", "CARDINAL")
+            .first()
+            .map(|pid| *pid as i64)
+            .unwrap_or(0);
+        if owner_pid == current_pid || !is_normal_window(&window) {
+            continue;
+        }
+        let frame = window.get_frame_extents();
+        res.push(ExternalWindow {
+            id: xid as i64,
+            title: property_string(&window, "_NET_WM_NAME", "UTF8_STRING")
+                .or_else(|| property_string(&window, "WM_NAME", "STRING")),
+            owner_name: process_name(owner_pid),
+            owner_pid,
+            frame: Rect::xywh(
+                frame.x as f64,
+                frame.y as f64,
+                frame.width as f64,
+                frame.height as f64,
+            ),
+            thumbnail: request
+                .thumbnail_size
+                .as_ref()
+                .and_then(|size| thumbnail(&window, size)),
+        });
+    }
+    Ok(res)
+}
+
+fn thumbnail(window: &gdk::Window, max_size: &Size) -> Option<ImageData> {
+    let (width, height) = (window.get_width() as f64, window.get_height() as f64);
+    if width <= 0.0 || height <= 0.0 {
+        return None;
+    }
+    let scale = (max_size.width / width)
+        .min(max_size.height / height)
+        .min(1.0);
+    let width = ((width * scale).round() as i32).max(1);
+    let height = ((height * scale).round() as i32).max(1);
+    let mut surface = ImageSurface::create(Format::Rgb24, width, height).ok()?;
+    {
+        // Window may have been destroyed or unmapped in the meanwhile
+        gdk::error_trap_push();
+        let cr = cairo::Context::new(&surface);
+        cr.scale(scale, scale);
+        cr.set_source_window(window, 0.0, 0.0);
+        cr.paint();
+        if gdk::error_trap_pop() != 0 {
+            return None;
+        }
+    }
+    surface.flush();
+    let stride = surface.get_stride() as usize;
+    let surface_data = surface.get_data().ok()?;
+    let mut data = Vec::with_capacity((width * height * 4) as usize);
+    for row in surface_data.chunks(stride).take(height as usize) {
+        // Native endian 0xXXRRGGBB
+        for pixel in row[..width as usize * 4].chunks_exact(4) {
+            data.extend_from_slice(&[pixel[2], pixel[1], pixel[0], 255]);
+        }
+    }
+    Some(ImageData {
+        width,
+        height,
+        bytes_per_row: width * 4,
+        data,
+    })
+}
@@ -105,8 +105,9 @@ impl WindowMenu {
                         self.pointer_inside_item_rect.replace(true);
                         return true;
                     } else if self.pointer_inside_item_rect.replace(false) {
-                        let mut leave = synthetize_leave_event_from_motion(event);
-                        window.propagate_event(&mut leave);
+                        if let Some(mut leave) = synthetize_leave_event_from_motion(event) {
+                            window.propagate_event(&mut leave);
+                        }
                     }
                 }
             }
@@ -149,8 +150,9 @@ impl WindowMenu {
 
         if let Some(event) = last_button_event {
             // menu was shown
-            let mut release = synthetize_button_up(event);
-            gtk::main_do_event(&mut release);
+            if let Some(mut release) = synthetize_button_up(event) {
+                gtk::main_do_event(&mut release);
+            }
         }
 
         // event to make Gtk happy
@@ -185,32 +187,43 @@ impl WindowMenu {
                 Gravity::NorthWest,
                 trigger_event,
             );
-
-            // enabled during keyboard navigation; this also takes care of enabling
-            // mnemonics
-            if request.preselect_first {
-                for item in menu.menu.get_children() {
-                    if item.is_sensitive() {
-                        item.mnemonic_activate(true);
-                        break;
-                    }
-                }
-            }
         } else {
-            menu.menu.popup_at_rect(
-                &window.view.borrow().get_window().unwrap(),
-                &Rectangle {
+            // Keyboard invoked menu is anchored to focused element so that Gtk
+            // can flip it above the element when there is no space below
+            let rect = match request.keyboard_anchor.as_ref() {
+                Some(anchor) => Rectangle {
+                    x: anchor.x as i32,
+                    y: anchor.y as i32,
+                    width: anchor.width as i32,
+                    height: anchor.height as i32,
+                },
+                None => Rectangle {
                     x: request.position.x as i32,
                     y: request.position.y as i32,
                     width: 0,
                     height: 0,
                 },
+            };
+            menu.menu.popup_at_rect(
+                &window.view.borrow().get_window().unwrap(),
+                &rect,
                 Gravity::SouthWest,
                 Gravity::NorthWest,
                 trigger_event,
             );
         }
 
+        // enabled during keyboard navigation; this also takes care of enabling
+        // mnemonics
+        if request.preselect_first {
+            for item in menu.menu.get_children() {
+                if item.is_sensitive() {
+                    item.mnemonic_activate(true);
+                    break;
+                }
+            }
+        }
+
         // close menu notification
 
         let win_weak = self.window.clone();
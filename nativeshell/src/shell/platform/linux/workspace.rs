@@ -0,0 +1,120 @@
+use gio::{AppInfo, AppInfoExt, AppLaunchContext, DesktopAppInfo, FileExt};
+use glib::ToVariant;
+
+use crate::shell::api_model::{DefaultHandler, DropFilter, HandlerTarget};
+
+use super::{
+    error::{PlatformError, PlatformResult},
+    utils::{application_id, dbus_call, dbus_string_array},
+};
+
+const FILE_MANAGER: &str = "org.freedesktop.FileManager1";
+const FILE_MANAGER_PATH: &str = "/org/freedesktop/FileManager1";
+
+pub fn open_url(url: &str) -> PlatformResult<()> {
+    AppInfo::launch_default_for_uri(url, None::<&AppLaunchContext>).map_err(|e| {
+        PlatformError::GLibError {
+            message: e.to_string(),
+        }
+    })
+}
+
+// Opens file in default application or directory in file manager
+pub fn open_path(path: &str) -> PlatformResult<()> {
+    open_url(&file_uri(path))
+}
+
+// Falls back to opening parent directory when file manager doesn't implement
+// the FileManager1 interface
+pub fn reveal_in_file_manager(path: &str) -> PlatformResult<()> {
+    let shown = gio::bus_get_sync(gio::BusType::Session, None::<&gio::Cancellable>)
+        .ok()
+        .and_then(|connection| {
+            dbus_call(
+                &connection,
+                FILE_MANAGER,
+                FILE_MANAGER_PATH,
+                FILE_MANAGER,
+                "ShowItems",
+                &[dbus_string_array(&[file_uri(path)]), "".to_variant()],
+            )
+            .ok()
+        })
+        .is_some();
+    if shown {
+        return Ok(());
+    }
+    match std::path::Path::new(path).parent() {
+        Some(parent) => open_path(&parent.to_string_lossy()),
+        None => open_path(path),
+    }
+}
+
+// Trash location is not reported by gio
+pub fn trash_items(paths: &[String]) -> PlatformResult<Vec<Option<String>>> {
+    for path in paths {
+        gio::File::new_for_path(path)
+            .trash(None::<&gio::Cancellable>)
+            .map_err(|e| PlatformError::GLibError {
+                message: e.to_string(),
+            })?;
+    }
+    Ok(vec![None; paths.len()])
+}
+
+// Content types on Linux are MIME types; "type/*" matches whole media type
+pub fn file_conforms_to(path: &str, filter: &DropFilter) -> bool {
+    if filter.mime_types.is_empty() {
+        return false;
+    }
+    let (content_type, _) = gio::content_type_guess(Some(path), &[]);
+    filter
+        .mime_types
+        .iter()
+        .any(|m| gio::content_type_is_a(&content_type, m))
+}
+
+// URL schemes are registered as "x-scheme-handler/<scheme>" content types
+fn content_type(target: &HandlerTarget) -> String {
+    match target {
+        HandlerTarget::UrlScheme(scheme) => format!("x-scheme-handler/{}", scheme),
+        HandlerTarget::FileExtension(extension) => {
+            gio::content_type_guess(Some(&format!("file.{}", extension)), &[])
+                .0
+                .into()
+        }
+    }
+}
+
+fn own_desktop_id() -> String {
+    format!("{}.desktop", application_id())
+}
+
+pub fn default_handler(target: &HandlerTarget) -> PlatformResult<Option<DefaultHandler>> {
+    let app_info = AppInfo::get_default_for_type(&content_type(target), false);
+    Ok(app_info.map(|app_info| {
+        let id: String = app_info.get_id().map(|id| id.into()).unwrap_or_default();
+        DefaultHandler {
+            name: app_info
+                .get_display_name()
+                .map(|name| name.into())
+                .unwrap_or_default(),
+            is_current_application: id == own_desktop_id(),
+            id,
+        }
+    }))
+}
+
+// Requires desktop file named after program name to be installed
+pub fn set_as_default_handler(target: &HandlerTarget) -> PlatformResult<()> {
+    let app_info = DesktopAppInfo::new(&own_desktop_id()).ok_or(PlatformError::UnknownError)?;
+    app_info
+        .set_as_default_for_type(&content_type(target))
+        .map_err(|e| PlatformError::GLibError {
+            message: e.to_string(),
+        })
+}
+
+fn file_uri(path: &str) -> String {
+    gio::File::new_for_path(path).get_uri().into()
+}
@@ -0,0 +1,114 @@
+use std::rc::{Rc, Weak};
+
+use block::ConcreteBlock;
+use cocoa::{
+    appkit::NSApp,
+    base::{id, nil, BOOL, YES},
+};
+use objc::rc::StrongPtr;
+
+use crate::shell::{api_model::AccessibilitySettings, Context};
+
+use super::{error::PlatformResult, utils::to_nsstring};
+
+#[link(name = "AppKit", kind = "framework")]
+extern "C" {
+    static NSWorkspaceAccessibilityDisplayOptionsDidChangeNotification: id;
+    static NSApplicationDidBecomeActiveNotification: id;
+}
+
+pub struct PlatformAccessibility {
+    workspace_observer: StrongPtr,
+    application_observer: StrongPtr,
+}
+
+impl PlatformAccessibility {
+    pub fn new(context: Rc<Context>) -> Self {
+        unsafe {
+            let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+            let workspace_center: id = msg_send![workspace, notificationCenter];
+            let workspace_observer = Self::observe(
+                workspace_center,
+                NSWorkspaceAccessibilityDisplayOptionsDidChangeNotification,
+                Rc::downgrade(&context),
+            );
+
+            // There is no notification for full keyboard access or cursor size changes;
+            // These can only be changed in System Preferences, so check again when
+            // application gets activated
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let application_observer = Self::observe(
+                center,
+                NSApplicationDidBecomeActiveNotification,
+                Rc::downgrade(&context),
+            );
+
+            Self {
+                workspace_observer,
+                application_observer,
+            }
+        }
+    }
+
+    unsafe fn observe(center: id, name: id, context: Weak<Context>) -> StrongPtr {
+        let block = ConcreteBlock::new(move |_notification: id| {
+            if let Some(context) = context.upgrade() {
+                context.accessibility_manager.borrow().settings_changed();
+            }
+        });
+        let block = block.copy();
+        let observer: id = msg_send![center,
+            addObserverForName: name
+            object: nil
+            queue: nil
+            usingBlock: &*block
+        ];
+        StrongPtr::retain(observer)
+    }
+
+    pub fn get_settings(&self) -> PlatformResult<AccessibilitySettings> {
+        unsafe {
+            let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+            let reduce_motion: BOOL = msg_send![workspace, accessibilityDisplayShouldReduceMotion];
+            let reduce_transparency: BOOL =
+                msg_send![workspace, accessibilityDisplayShouldReduceTransparency];
+            let high_contrast: BOOL =
+                msg_send![workspace, accessibilityDisplayShouldIncreaseContrast];
+            let full_keyboard_access: BOOL = msg_send![NSApp(), isFullKeyboardAccessEnabled];
+
+            Ok(AccessibilitySettings {
+                full_keyboard_access: full_keyboard_access == YES,
+                reduce_motion: reduce_motion == YES,
+                reduce_transparency: reduce_transparency == YES,
+                high_contrast: high_contrast == YES,
+                cursor_size: Self::cursor_size(),
+            })
+        }
+    }
+
+    unsafe fn cursor_size() -> f64 {
+        let defaults: id = msg_send![class!(NSUserDefaults), standardUserDefaults];
+        let domain: id = msg_send![defaults,
+            persistentDomainForName: *to_nsstring("com.apple.universalaccess")];
+        if domain == nil {
+            return 1.0;
+        }
+        let size: id = msg_send![domain, objectForKey: *to_nsstring("mouseDriverCursorSize")];
+        if size == nil {
+            return 1.0;
+        }
+        msg_send![size, doubleValue]
+    }
+}
+
+impl Drop for PlatformAccessibility {
+    fn drop(&mut self) {
+        unsafe {
+            let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+            let workspace_center: id = msg_send![workspace, notificationCenter];
+            let () = msg_send![workspace_center, removeObserver: *self.workspace_observer];
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let () = msg_send![center, removeObserver: *self.application_observer];
+        }
+    }
+}
@@ -0,0 +1,642 @@
+use std::{
+    cell::{Cell, RefCell},
+    ffi::c_void,
+    rc::{Rc, Weak},
+};
+
+use cocoa::{
+    appkit::NSApp,
+    base::{id, nil, BOOL, NO, YES},
+    foundation::{NSInteger, NSUInteger},
+};
+use log::warn;
+use objc::{
+    declare::ClassDecl,
+    rc::StrongPtr,
+    runtime::{Class, Object, Sel},
+};
+
+use crate::{
+    codec::{MessageCodec, StandardMethodCodec, Value},
+    shell::{
+        api_model::{ActivationPolicy, JumpList, TerminationReason, UserActivity},
+        Context,
+    },
+};
+
+use super::{
+    error::{PlatformError, PlatformResult},
+    utils::{from_nsstring, superclass, to_nsdata, to_nsstring},
+};
+
+// 'GURL'
+const K_INTERNET_EVENT_CLASS: u32 = 0x4755524C;
+const K_AE_GET_URL: u32 = 0x4755524C;
+// 'aevt'
+const K_CORE_EVENT_CLASS: u32 = 0x61657674;
+// 'odoc'
+const K_AE_OPEN_DOCUMENTS: u32 = 0x6F646F63;
+// '----'
+const KEY_DIRECT_OBJECT: u32 = 0x2D2D2D2D;
+// 'furl'
+const TYPE_FILE_URL: u32 = 0x6675726C;
+// 'why?'; Present when quitting because of logout, restart or shutdown
+const K_AE_QUIT_REASON: u32 = 0x7768793F;
+
+// 'prdt'
+const KEY_AE_PROP_DATA: u32 = 0x70726474;
+// 'lgit'; Property data of open application event when launched as login item
+const KEY_AE_LAUNCHED_AS_LOG_IN_ITEM: u32 = 0x6C676974;
+
+// SMAppServiceStatus
+const SM_APP_SERVICE_STATUS_ENABLED: NSInteger = 1;
+const SM_APP_SERVICE_STATUS_REQUIRES_APPROVAL: NSInteger = 2;
+
+// Login items can not be launched with arguments, so the flag is kept in defaults
+const START_HIDDEN_KEY: &str = "NativeShellStartHidden";
+
+// User info key of encoded user activity payload
+const USER_ACTIVITY_PAYLOAD_KEY: &str = "NativeShellPayload";
+
+// NSApplicationTerminateReply
+const NS_TERMINATE_CANCEL: NSUInteger = 0;
+const NS_TERMINATE_NOW: NSUInteger = 1;
+const NS_TERMINATE_LATER: NSUInteger = 2;
+
+// Handles Apple Events sent to the application; Handler is installed before
+// application finishes launching, so that URL that launched the application
+// is not lost. NSApplication replaces open documents handler in finishLaunching,
+// so that one is installed again when application will finish launching.
+// The handler is also application delegate (unless there already is one) to
+// ask Dart whether application should terminate.
+pub struct PlatformApplication {
+    handler: StrongPtr,
+    prevent_sudden_termination: Cell<bool>,
+    user_activity: RefCell<Option<StrongPtr>>,
+}
+
+impl PlatformApplication {
+    pub fn new(context: Rc<Context>) -> Self {
+        unsafe {
+            let handler: id = msg_send![EVENT_HANDLER_CLASS.0, new];
+            let handler = StrongPtr::new(handler);
+            let state = Box::new(Rc::downgrade(&context));
+            (**handler).set_ivar("imState", Box::into_raw(state) as *mut c_void);
+
+            let manager: id = msg_send![class!(NSAppleEventManager), sharedAppleEventManager];
+            let () = msg_send![manager,
+                setEventHandler: *handler
+                andSelector: sel!(handleGetURLEvent:withReplyEvent:)
+                forEventClass: K_INTERNET_EVENT_CLASS
+                andEventID: K_AE_GET_URL
+            ];
+            install_open_documents_handler(*handler);
+
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let () = msg_send![center,
+                addObserver: *handler
+                selector: sel!(applicationWillFinishLaunching:)
+                name: NSApplicationWillFinishLaunchingNotification
+                object: nil
+            ];
+            let () = msg_send![center,
+                addObserver: *handler
+                selector: sel!(applicationDidFinishLaunching:)
+                name: NSApplicationDidFinishLaunchingNotification
+                object: nil
+            ];
+            // Selectors differ from delegate methods, which NSApplication would
+            // register for the same notifications when the handler is delegate
+            let () = msg_send![center,
+                addObserver: *handler
+                selector: sel!(handleDidHide:)
+                name: NSApplicationDidHideNotification
+                object: nil
+            ];
+            let () = msg_send![center,
+                addObserver: *handler
+                selector: sel!(handleDidUnhide:)
+                name: NSApplicationDidUnhideNotification
+                object: nil
+            ];
+
+            let app: id = msg_send![class!(NSApplication), sharedApplication];
+            let delegate: id = msg_send![app, delegate];
+            if delegate == nil {
+                let () = msg_send![app, setDelegate: *handler];
+            } else {
+                warn!(
+                    "Application delegate already set; termination requests will not be delivered"
+                );
+            }
+            Self {
+                handler,
+                prevent_sudden_termination: Cell::new(false),
+                user_activity: RefCell::new(None),
+            }
+        }
+    }
+
+    // Sudden termination is counted by NSProcessInfo, so only toggle it on change
+    pub fn set_prevent_sudden_termination(
+        &self,
+        prevent: bool,
+        _reason: Option<&str>,
+    ) -> PlatformResult<()> {
+        if self.prevent_sudden_termination.replace(prevent) != prevent {
+            unsafe {
+                let process_info: id = msg_send![class!(NSProcessInfo), processInfo];
+                if prevent {
+                    let () = msg_send![process_info, disableSuddenTermination];
+                } else {
+                    let () = msg_send![process_info, enableSuddenTermination];
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Application is registered as login item through SMAppService, which is
+    // only available on macOS 13 and later
+    pub fn launch_at_login(&self, _id: &str) -> PlatformResult<bool> {
+        unsafe {
+            let service = Self::main_app_service()?;
+            let status: NSInteger = msg_send![service, status];
+            // Registered login item may still wait for user approval
+            Ok(status == SM_APP_SERVICE_STATUS_ENABLED
+                || status == SM_APP_SERVICE_STATUS_REQUIRES_APPROVAL)
+        }
+    }
+
+    pub fn set_launch_at_login(
+        &self,
+        _id: &str,
+        enabled: bool,
+        start_hidden: bool,
+    ) -> PlatformResult<()> {
+        unsafe {
+            let service = Self::main_app_service()?;
+            let mut error: id = nil;
+            let res: BOOL = if enabled {
+                msg_send![service, registerAndReturnError: &mut error]
+            } else {
+                msg_send![service, unregisterAndReturnError: &mut error]
+            };
+            if res != YES {
+                if error != nil {
+                    let description: id = msg_send![error, localizedDescription];
+                    warn!(
+                        "Could not update login item: {}",
+                        from_nsstring(description)
+                    );
+                }
+                return Err(PlatformError::UnknownError);
+            }
+            let defaults: id = msg_send![class!(NSUserDefaults), standardUserDefaults];
+            let () = msg_send![defaults,
+                setBool: (enabled && start_hidden) as BOOL
+                forKey: *to_nsstring(START_HIDDEN_KEY)
+            ];
+            Ok(())
+        }
+    }
+
+    // Only known after application finished launching
+    pub fn started_hidden(&self) -> bool {
+        unsafe {
+            let launched_at_login: BOOL = *(**self.handler).get_ivar("imLaunchedAtLogin");
+            let defaults: id = msg_send![class!(NSUserDefaults), standardUserDefaults];
+            let start_hidden: BOOL =
+                msg_send![defaults, boolForKey: *to_nsstring(START_HIDDEN_KEY)];
+            launched_at_login == YES && start_hidden == YES
+        }
+    }
+
+    // Dock icon stops bouncing once application finishes launching
+    pub fn notify_startup_complete(&self) {}
+
+    // Jump lists are Windows only
+    pub fn set_jump_list(&self, _jump_list: &JumpList) -> PlatformResult<()> {
+        Err(PlatformError::NotAvailable)
+    }
+
+    // AppUserModelID is Windows only
+    pub fn set_app_user_model_id(&self, _id: &str) -> PlatformResult<()> {
+        Err(PlatformError::NotAvailable)
+    }
+
+    // Activity is only advertised while it is current and not invalidated
+    pub fn set_user_activity(&self, activity: Option<UserActivity>) -> PlatformResult<()> {
+        unsafe {
+            if let Some(previous) = self.user_activity.borrow_mut().take() {
+                let () = msg_send![*previous, invalidate];
+            }
+            let activity = match activity {
+                Some(activity) => activity,
+                None => return Ok(()),
+            };
+            let user_activity: id = msg_send![class!(NSUserActivity), alloc];
+            let user_activity: id = msg_send![user_activity,
+                initWithActivityType: *to_nsstring(&activity.activity_type)
+            ];
+            let user_activity = StrongPtr::new(user_activity);
+            if let Some(title) = &activity.title {
+                let () = msg_send![*user_activity, setTitle: *to_nsstring(title)];
+            }
+            if let Some(webpage_url) = &activity.webpage_url {
+                let url: id = msg_send![class!(NSURL), URLWithString: *to_nsstring(webpage_url)];
+                let () = msg_send![*user_activity, setWebpageURL: url];
+            }
+            let codec: &'static dyn MessageCodec<Value> = &StandardMethodCodec;
+            let payload = to_nsdata(&codec.encode_message(&activity.payload));
+            let user_info: id = msg_send![class!(NSDictionary),
+                dictionaryWithObject: *payload
+                forKey: *to_nsstring(USER_ACTIVITY_PAYLOAD_KEY)
+            ];
+            let () = msg_send![*user_activity, setUserInfo: user_info];
+            let () = msg_send![*user_activity, becomeCurrent];
+            self.user_activity.replace(Some(user_activity));
+        }
+        Ok(())
+    }
+
+    // Switching to regular policy shows Dock icon and menu bar; Switching to
+    // accessory hides Dock icon but keeps windows on screen
+    pub fn set_activation_policy(&self, policy: ActivationPolicy) -> PlatformResult<()> {
+        let policy: NSInteger = match policy {
+            ActivationPolicy::Regular => 0,
+            ActivationPolicy::Accessory => 1,
+            ActivationPolicy::Prohibited => 2,
+        };
+        let res: BOOL = unsafe { msg_send![NSApp(), setActivationPolicy: policy] };
+        if res == YES {
+            Ok(())
+        } else {
+            Err(PlatformError::UnknownError)
+        }
+    }
+
+    // Termination was already confirmed by application manager, so delegate
+    // does not ask again
+    pub fn terminate(&self) {
+        unsafe {
+            (**self.handler).set_ivar("imTerminationConfirmed", YES);
+            let () = msg_send![NSApp(), terminate: nil];
+        }
+    }
+
+    pub fn hide_application(&self) -> PlatformResult<()> {
+        unsafe {
+            let () = msg_send![NSApp(), hide: nil];
+        }
+        Ok(())
+    }
+
+    pub fn unhide_application(&self) -> PlatformResult<()> {
+        unsafe {
+            let () = msg_send![NSApp(), unhide: nil];
+        }
+        Ok(())
+    }
+
+    pub fn hide_other_applications(&self) -> PlatformResult<()> {
+        unsafe {
+            let () = msg_send![NSApp(), hideOtherApplications: nil];
+        }
+        Ok(())
+    }
+
+    unsafe fn main_app_service() -> PlatformResult<id> {
+        let class = Class::get("SMAppService").ok_or(PlatformError::NotAvailable)?;
+        Ok(msg_send![class, mainAppService])
+    }
+}
+
+unsafe fn install_open_documents_handler(handler: id) {
+    let manager: id = msg_send![class!(NSAppleEventManager), sharedAppleEventManager];
+    let () = msg_send![manager,
+        setEventHandler: handler
+        andSelector: sel!(handleOpenDocumentsEvent:withReplyEvent:)
+        forEventClass: K_CORE_EVENT_CLASS
+        andEventID: K_AE_OPEN_DOCUMENTS
+    ];
+}
+
+#[link(name = "AppKit", kind = "framework")]
+extern "C" {
+    static NSApplicationWillFinishLaunchingNotification: id;
+    static NSApplicationDidFinishLaunchingNotification: id;
+    static NSApplicationDidHideNotification: id;
+    static NSApplicationDidUnhideNotification: id;
+}
+
+#[link(name = "ServiceManagement", kind = "framework")]
+extern "C" {}
+
+impl Drop for PlatformApplication {
+    fn drop(&mut self) {
+        unsafe {
+            let manager: id = msg_send![class!(NSAppleEventManager), sharedAppleEventManager];
+            let () = msg_send![manager,
+                removeEventHandlerForEventClass: K_INTERNET_EVENT_CLASS
+                andEventID: K_AE_GET_URL
+            ];
+            let () = msg_send![manager,
+                removeEventHandlerForEventClass: K_CORE_EVENT_CLASS
+                andEventID: K_AE_OPEN_DOCUMENTS
+            ];
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let () = msg_send![center, removeObserver: *self.handler];
+
+            let app: id = msg_send![class!(NSApplication), sharedApplication];
+            let delegate: id = msg_send![app, delegate];
+            if delegate == *self.handler {
+                let () = msg_send![app, setDelegate: nil];
+            }
+        }
+    }
+}
+
+struct EventHandlerClass(*const Class);
+unsafe impl Sync for EventHandlerClass {}
+
+lazy_static! {
+    static ref EVENT_HANDLER_CLASS: EventHandlerClass = unsafe {
+        let superclass = class!(NSObject);
+        let mut decl = ClassDecl::new("IMAppleEventHandler", superclass).unwrap();
+
+        decl.add_ivar::<*mut c_void>("imState");
+        decl.add_ivar::<BOOL>("imLaunchedAtLogin");
+        decl.add_ivar::<BOOL>("imTerminationConfirmed");
+
+        decl.add_method(sel!(dealloc), dealloc as extern "C" fn(&Object, Sel));
+        decl.add_method(
+            sel!(handleGetURLEvent:withReplyEvent:),
+            handle_get_url_event as extern "C" fn(&Object, Sel, id, id),
+        );
+        decl.add_method(
+            sel!(handleOpenDocumentsEvent:withReplyEvent:),
+            handle_open_documents_event as extern "C" fn(&Object, Sel, id, id),
+        );
+        decl.add_method(
+            sel!(applicationWillFinishLaunching:),
+            application_will_finish_launching as extern "C" fn(&Object, Sel, id),
+        );
+        decl.add_method(
+            sel!(applicationDidFinishLaunching:),
+            application_did_finish_launching as extern "C" fn(&mut Object, Sel, id),
+        );
+        decl.add_method(
+            sel!(handleDidHide:),
+            handle_did_hide as extern "C" fn(&Object, Sel, id),
+        );
+        decl.add_method(
+            sel!(handleDidUnhide:),
+            handle_did_unhide as extern "C" fn(&Object, Sel, id),
+        );
+        decl.add_method(
+            sel!(application:continueUserActivity:restorationHandler:),
+            application_continue_user_activity as extern "C" fn(&Object, Sel, id, id, id) -> BOOL,
+        );
+        decl.add_method(
+            sel!(applicationShouldTerminate:),
+            application_should_terminate as extern "C" fn(&Object, Sel, id) -> NSUInteger,
+        );
+
+        EventHandlerClass(decl.register())
+    };
+}
+
+extern "C" fn dealloc(this: &Object, _sel: Sel) {
+    unsafe {
+        let state_ptr: *mut c_void = *this.get_ivar("imState");
+        if !state_ptr.is_null() {
+            drop(Box::from_raw(state_ptr as *mut Weak<Context>));
+        }
+
+        let superclass = superclass(this);
+        let () = msg_send![super(this, superclass), dealloc];
+    }
+}
+
+extern "C" fn handle_get_url_event(this: &Object, _sel: Sel, event: id, _reply: id) {
+    let context = unsafe {
+        let state_ptr: *mut c_void = *this.get_ivar("imState");
+        (*(state_ptr as *const Weak<Context>)).upgrade()
+    };
+    let url = unsafe {
+        let descriptor: id = msg_send![event, paramDescriptorForKeyword: KEY_DIRECT_OBJECT];
+        if descriptor == nil {
+            return;
+        }
+        let url: id = msg_send![descriptor, stringValue];
+        if url == nil {
+            return;
+        }
+        from_nsstring(url)
+    };
+    if let Some(context) = context {
+        context.application_manager.borrow().open_urls(vec![url]);
+    }
+}
+
+extern "C" fn handle_open_documents_event(this: &Object, _sel: Sel, event: id, _reply: id) {
+    let context = unsafe {
+        let state_ptr: *mut c_void = *this.get_ivar("imState");
+        (*(state_ptr as *const Weak<Context>)).upgrade()
+    };
+    let mut files = Vec::new();
+    unsafe {
+        // List of file references; Items are coerced to file URLs
+        let descriptor: id = msg_send![event, paramDescriptorForKeyword: KEY_DIRECT_OBJECT];
+        if descriptor == nil {
+            return;
+        }
+        let count: isize = msg_send![descriptor, numberOfItems];
+        for i in 1..=count {
+            let item: id = msg_send![descriptor, descriptorAtIndex: i];
+            if item == nil {
+                continue;
+            }
+            let item: id = msg_send![item, coerceToDescriptorType: TYPE_FILE_URL];
+            if item == nil {
+                continue;
+            }
+            let string: id = msg_send![item, stringValue];
+            if string == nil {
+                continue;
+            }
+            let url: id = msg_send![class!(NSURL), URLWithString: string];
+            if url == nil {
+                continue;
+            }
+            let path: id = msg_send![url, path];
+            if path != nil {
+                files.push(from_nsstring(path));
+            }
+        }
+    }
+    if let Some(context) = context {
+        if !files.is_empty() {
+            context.application_manager.borrow().open_files(files);
+        }
+    }
+}
+
+extern "C" fn application_will_finish_launching(this: &Object, _sel: Sel, _notification: id) {
+    unsafe {
+        install_open_documents_handler(this as *const Object as id);
+    }
+}
+
+// Open application event is being handled when the notification is posted
+extern "C" fn application_did_finish_launching(this: &mut Object, _sel: Sel, _notification: id) {
+    unsafe {
+        let manager: id = msg_send![class!(NSAppleEventManager), sharedAppleEventManager];
+        let event: id = msg_send![manager, currentAppleEvent];
+        let mut launched_at_login = NO;
+        if event != nil {
+            let data: id = msg_send![event, paramDescriptorForKeyword: KEY_AE_PROP_DATA];
+            if data != nil {
+                let value: u32 = msg_send![data, enumCodeValue];
+                if value == KEY_AE_LAUNCHED_AS_LOG_IN_ITEM {
+                    launched_at_login = YES;
+                }
+            }
+        }
+        this.set_ivar("imLaunchedAtLogin", launched_at_login);
+    }
+}
+
+fn hidden_changed(this: &Object, hidden: bool) {
+    let context = unsafe {
+        let state_ptr: *mut c_void = *this.get_ivar("imState");
+        (*(state_ptr as *const Weak<Context>)).upgrade()
+    };
+    if let Some(context) = context {
+        context.application_manager.borrow().hidden_changed(hidden);
+    }
+}
+
+extern "C" fn handle_did_hide(this: &Object, _sel: Sel, _notification: id) {
+    hidden_changed(this, true);
+}
+
+extern "C" fn handle_did_unhide(this: &Object, _sel: Sel, _notification: id) {
+    hidden_changed(this, false);
+}
+
+// Only called when the handler is application delegate
+extern "C" fn application_continue_user_activity(
+    this: &Object,
+    _sel: Sel,
+    _app: id,
+    user_activity: id,
+    _restoration_handler: id,
+) -> BOOL {
+    let context = unsafe {
+        let state_ptr: *mut c_void = *this.get_ivar("imState");
+        (*(state_ptr as *const Weak<Context>)).upgrade()
+    };
+    let context = match context {
+        Some(context) => context,
+        None => return NO,
+    };
+    let activity = unsafe { user_activity_from(user_activity) };
+    context
+        .application_manager
+        .borrow()
+        .continue_user_activity(activity);
+    YES
+}
+
+unsafe fn user_activity_from(user_activity: id) -> UserActivity {
+    let activity_type: id = msg_send![user_activity, activityType];
+    let title: id = msg_send![user_activity, title];
+    let webpage_url: id = msg_send![user_activity, webpageURL];
+    let webpage_url: id = if webpage_url != nil {
+        msg_send![webpage_url, absoluteString]
+    } else {
+        nil
+    };
+    // Activities from browser or other applications have no payload
+    let mut payload = Value::Null;
+    let user_info: id = msg_send![user_activity, userInfo];
+    if user_info != nil {
+        let data: id = msg_send![user_info, objectForKey: *to_nsstring(USER_ACTIVITY_PAYLOAD_KEY)];
+        if data != nil {
+            let bytes: *const u8 = msg_send![data, bytes];
+            let length: usize = msg_send![data, length];
+            let codec: &'static dyn MessageCodec<Value> = &StandardMethodCodec;
+            payload = codec
+                .decode_message(std::slice::from_raw_parts(bytes, length))
+                .unwrap_or(Value::Null);
+        }
+    }
+    let optional_string = |string: id| {
+        if string != nil {
+            Some(from_nsstring(string))
+        } else {
+            None
+        }
+    };
+    UserActivity {
+        activity_type: from_nsstring(activity_type),
+        title: optional_string(title),
+        payload,
+        webpage_url: optional_string(webpage_url),
+    }
+}
+
+extern "C" fn application_should_terminate(this: &Object, _sel: Sel, app: id) -> NSUInteger {
+    let context = unsafe {
+        let state_ptr: *mut c_void = *this.get_ivar("imState");
+        (*(state_ptr as *const Weak<Context>)).upgrade()
+    };
+    let context = match context {
+        Some(context) => context,
+        None => return NS_TERMINATE_NOW,
+    };
+    let confirmed: BOOL = unsafe { *this.get_ivar("imTerminationConfirmed") };
+    if confirmed == YES {
+        return NS_TERMINATE_NOW;
+    }
+
+    // Reply may come before should_terminate returns, in which case it is
+    // returned directly instead of replying later
+    let returned = Rc::new(Cell::new(false));
+    let immediate_reply = Rc::new(Cell::new(None));
+    let returned_copy = returned.clone();
+    let immediate_reply_copy = immediate_reply.clone();
+    let app = unsafe { StrongPtr::retain(app) };
+    context.application_manager.borrow().should_terminate(
+        unsafe { termination_reason() },
+        move |allowed| {
+            if returned_copy.get() {
+                unsafe {
+                    let () = msg_send![*app, replyToApplicationShouldTerminate: allowed as BOOL];
+                }
+            } else {
+                immediate_reply_copy.set(Some(allowed));
+            }
+        },
+    );
+    returned.set(true);
+    match immediate_reply.get() {
+        Some(true) => NS_TERMINATE_NOW,
+        Some(false) => NS_TERMINATE_CANCEL,
+        None => NS_TERMINATE_LATER,
+    }
+}
+
+unsafe fn termination_reason() -> TerminationReason {
+    let manager: id = msg_send![class!(NSAppleEventManager), sharedAppleEventManager];
+    let event: id = msg_send![manager, currentAppleEvent];
+    if event != nil {
+        let reason: id = msg_send![event, attributeDescriptorForKeyword: K_AE_QUIT_REASON];
+        if reason != nil {
+            return TerminationReason::SessionEnd;
+        }
+    }
+    TerminationReason::Quit
+}
@@ -0,0 +1,106 @@
+use std::sync::Mutex;
+
+use cocoa::{
+    base::{id, nil, BOOL, NO},
+    foundation::{NSArray, NSInteger, NSUInteger},
+};
+use objc::rc::StrongPtr;
+
+use crate::shell::CrashDialogResponse;
+
+use super::{
+    error::{PlatformError, PlatformResult},
+    utils::{from_nsstring, to_nsstring},
+};
+
+#[link(name = "Foundation", kind = "framework")]
+extern "C" {
+    fn NSSetUncaughtExceptionHandler(handler: extern "C" fn(id));
+}
+
+const NS_ALERT_STYLE_CRITICAL: NSUInteger = 2;
+
+// NSAlertFirstButtonReturn
+const FIRST_BUTTON: NSInteger = 1000;
+
+lazy_static! {
+    static ref EXCEPTION_HANDLER: Mutex<Option<fn(String, String)>> = Mutex::new(None);
+}
+
+extern "C" fn on_uncaught_exception(exception: id) {
+    let (message, backtrace) = unsafe {
+        let name: id = msg_send![exception, name];
+        let reason: id = msg_send![exception, reason];
+        let message = format!(
+            "Uncaught exception {}: {}",
+            from_nsstring(name),
+            if reason != nil {
+                from_nsstring(reason)
+            } else {
+                String::new()
+            }
+        );
+        let symbols: id = msg_send![exception, callStackSymbols];
+        let backtrace = (0..NSArray::count(symbols))
+            .map(|i| from_nsstring(NSArray::objectAtIndex(symbols, i)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        (message, backtrace)
+    };
+    let handler = *EXCEPTION_HANDLER.lock().unwrap();
+    if let Some(handler) = handler {
+        handler(message, backtrace);
+    }
+}
+
+// Only exceptions not caught by AppKit reach the handler; AppKit logs and
+// ignores exceptions thrown during event dispatch.
+pub fn install_exception_handler(handler: fn(String, String)) {
+    *EXCEPTION_HANDLER.lock().unwrap() = Some(handler);
+    unsafe { NSSetUncaughtExceptionHandler(on_uncaught_exception) };
+}
+
+pub fn show_crash_dialog(
+    title: &str,
+    message: &str,
+    can_open_log_file: bool,
+) -> PlatformResult<CrashDialogResponse> {
+    unsafe {
+        let alert = StrongPtr::new(msg_send![class!(NSAlert), new]);
+        let () = msg_send![*alert, setAlertStyle: NS_ALERT_STYLE_CRITICAL];
+        let () = msg_send![*alert, setMessageText: *to_nsstring(title)];
+        let () = msg_send![*alert, setInformativeText: *to_nsstring(message)];
+        let mut responses = vec![CrashDialogResponse::Quit, CrashDialogResponse::CopyDetails];
+        if can_open_log_file {
+            responses.push(CrashDialogResponse::OpenLogFile);
+        }
+        for response in &responses {
+            let title = match response {
+                CrashDialogResponse::Quit => "Quit",
+                CrashDialogResponse::CopyDetails => "Copy Details and Quit",
+                CrashDialogResponse::OpenLogFile => "Open Log and Quit",
+            };
+            let () = msg_send![*alert, addButtonWithTitle: *to_nsstring(title)];
+        }
+        let res: NSInteger = msg_send![*alert, runModal];
+        Ok(responses
+            .get((res - FIRST_BUTTON) as usize)
+            .cloned()
+            .unwrap_or(CrashDialogResponse::Quit))
+    }
+}
+
+pub fn copy_to_clipboard(text: &str) -> PlatformResult<()> {
+    unsafe {
+        let pasteboard: id = msg_send![class!(NSPasteboard), generalPasteboard];
+        let _: NSInteger = msg_send![pasteboard, clearContents];
+        let text = to_nsstring(text);
+        let string_type = to_nsstring("public.utf8-plain-text"); // NSPasteboardTypeString
+        let res: BOOL = msg_send![pasteboard, setString: *text forType: *string_type];
+        if res == NO {
+            Err(PlatformError::UnknownError)
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use std::rc::{Rc, Weak};
+
+use block::ConcreteBlock;
+use cocoa::{
+    appkit::NSScreen,
+    base::{id, nil, BOOL, YES},
+    foundation::{NSArray, NSRect},
+};
+use objc::rc::StrongPtr;
+
+use crate::shell::{api_model::DisplayInfo, Context, Rect};
+
+use super::{
+    error::PlatformResult,
+    utils::{from_nsdata, from_nsstring},
+};
+
+#[link(name = "AppKit", kind = "framework")]
+extern "C" {
+    static NSApplicationDidChangeScreenParametersNotification: id;
+    static NSScreenColorSpaceDidChangeNotification: id;
+}
+
+pub struct PlatformDisplayInfo {
+    observers: Vec<StrongPtr>,
+}
+
+impl PlatformDisplayInfo {
+    pub fn new(context: Rc<Context>) -> Self {
+        unsafe {
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let observers = vec![
+                Self::observe(
+                    center,
+                    NSApplicationDidChangeScreenParametersNotification,
+                    Rc::downgrade(&context),
+                ),
+                Self::observe(
+                    center,
+                    NSScreenColorSpaceDidChangeNotification,
+                    Rc::downgrade(&context),
+                ),
+            ];
+            Self { observers }
+        }
+    }
+
+    unsafe fn observe(center: id, name: id, context: Weak<Context>) -> StrongPtr {
+        let block = ConcreteBlock::new(move |_notification: id| {
+            if let Some(context) = context.upgrade() {
+                context.display_manager.borrow().displays_changed();
+            }
+        });
+        let block = block.copy();
+        let observer: id = msg_send![center,
+            addObserverForName: name
+            object: nil
+            queue: nil
+            usingBlock: &*block
+        ];
+        StrongPtr::retain(observer)
+    }
+
+    // First screen is the main display, same as in CGDisplay::active_displays
+    pub fn get_displays(&self) -> PlatformResult<Vec<DisplayInfo>> {
+        unsafe {
+            let screens = NSScreen::screens(nil);
+            let count = screens.count();
+            let main_height = if count > 0 {
+                NSScreen::frame(screens.objectAtIndex(0)).size.height
+            } else {
+                0.0
+            };
+            Ok((0..count)
+                .map(|index| Self::display_info(screens.objectAtIndex(index), index, main_height))
+                .collect())
+        }
+    }
+
+    unsafe fn display_info(screen: id, index: u64, main_height: f64) -> DisplayInfo {
+        let frame: NSRect = NSScreen::frame(screen);
+        let color_space: id = msg_send![screen, colorSpace];
+        let (color_space_name, icc_profile) = if color_space != nil {
+            let name: id = msg_send![color_space, localizedName];
+            let profile: id = msg_send![color_space, ICCProfileData];
+            (
+                if name != nil {
+                    Some(from_nsstring(name))
+                } else {
+                    None
+                },
+                if profile != nil {
+                    Some(from_nsdata(profile))
+                } else {
+                    None
+                },
+            )
+        } else {
+            (None, None)
+        };
+        let current_headroom: f64 =
+            msg_send![screen, maximumExtendedDynamicRangeColorComponentValue];
+        // Only available on macOS 10.15 and later
+        let responds: BOOL = msg_send![screen,
+            respondsToSelector: sel!(maximumPotentialExtendedDynamicRangeColorComponentValue)];
+        let max_headroom: f64 = if responds == YES {
+            msg_send![
+                screen,
+                maximumPotentialExtendedDynamicRangeColorComponentValue
+            ]
+        } else {
+            current_headroom
+        };
+        DisplayInfo {
+            index: index as usize,
+            frame: Rect::xywh(
+                frame.origin.x,
+                main_height - (frame.origin.y + frame.size.height),
+                frame.size.width,
+                frame.size.height,
+            ),
+            scale_factor: NSScreen::backingScaleFactor(screen),
+            color_space: color_space_name,
+            icc_profile,
+            max_edr_headroom: max_headroom,
+            current_edr_headroom: current_headroom,
+            hdr: max_headroom > 1.0,
+        }
+    }
+}
+
+impl Drop for PlatformDisplayInfo {
+    fn drop(&mut self) {
+        unsafe {
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            for observer in &self.observers {
+                let () = msg_send![center, removeObserver: **observer];
+            }
+        }
+    }
+}
@@ -18,8 +18,9 @@ use crate::shell::{
 use super::{
     drag_data::{
         DragDataAdapter, FallThroughDragDataAdapter, FilesDragDataAdapter, PasteboardItems,
-        UrlsDragDataAdapter,
+        TextDragDataAdapter, UrlsDragDataAdapter,
     },
+    error::PlatformResult,
     utils::{array_with_objects, ns_image_from},
     window::PlatformWindow,
 };
@@ -52,6 +53,7 @@ impl DragContext {
             data_adapters: vec![
                 Box::new(FilesDragDataAdapter::new()),
                 Box::new(UrlsDragDataAdapter::new()),
+                Box::new(TextDragDataAdapter::new()),
                 Box::new(FallThroughDragDataAdapter::new(&context.options)),
             ],
             allowed_operations: Cell::new(NSDragOperationNone),
@@ -154,18 +156,24 @@ impl DragContext {
         })
     }
 
-    pub unsafe fn start_drag(&self, request: DragRequest, view: id, source: id, event: id) {
+    pub unsafe fn start_drag(
+        &self,
+        request: DragRequest,
+        view: id,
+        source: id,
+        event: id,
+    ) -> PlatformResult<()> {
         let mut pasteboard_items = PasteboardItems::new();
 
         let mut data = request.data.properties;
 
         for adapter in &self.context.options.custom_drag_data_adapters {
             pasteboard_items.reset_index();
-            adapter.prepare_drag_data(&mut data, &mut pasteboard_items);
+            adapter.prepare_drag_data(&mut data, &mut pasteboard_items)?;
         }
         for adapter in &self.data_adapters {
             pasteboard_items.reset_index();
-            adapter.prepare_drag_data(&mut data, &mut pasteboard_items);
+            adapter.prepare_drag_data(&mut data, &mut pasteboard_items)?;
         }
 
         let mut first = true;
@@ -199,6 +207,7 @@ impl DragContext {
             event:event
             source:source
         ];
+        Ok(())
     }
 
     pub fn source_operation_mask_for_dragging_context(
@@ -2,17 +2,24 @@ use std::{collections::HashMap, ffi::CStr};
 
 use cocoa::{
     base::{id, nil},
-    foundation::NSArray,
+    foundation::{NSArray, NSRange},
 };
 use log::warn;
 use objc::rc::StrongPtr;
 
 use crate::{
     codec::{MessageCodec, StandardMethodCodec, Value},
-    shell::{api_constants::drag_data, ContextOptions},
+    shell::{
+        api_constants::drag_data,
+        platform::{extract_drag_string, extract_drag_string_list},
+        ContextOptions,
+    },
 };
 
-use super::utils::{from_nsstring, to_nsdata, to_nsstring};
+use super::{
+    error::PlatformResult,
+    utils::{from_nsstring, to_nsdata, to_nsstring},
+};
 
 pub trait DragDataAdapter {
     // Retrieve data from given pasteboard
@@ -23,7 +30,7 @@ pub trait DragDataAdapter {
         &self,
         data_in: &mut HashMap<String, Value>,
         pasteboard_items: &mut PasteboardItems,
-    );
+    ) -> PlatformResult<()>;
 
     fn register_types(&self, types: &mut Vec<StrongPtr>);
 }
@@ -121,14 +128,14 @@ impl DragDataAdapter for FilesDragDataAdapter {
         &self,
         data_in: &mut HashMap<String, Value>,
         pasteboard_items: &mut PasteboardItems,
-    ) {
+    ) -> PlatformResult<()> {
         unsafe {
             let files = data_in.remove(drag_data::key::FILES);
 
             let file_url = to_nsstring("public.file-url"); // NSPasteboardTypeFileURL
 
             if let Some(files) = files {
-                let files = extract_string_list(files);
+                let files = extract_drag_string_list(drag_data::key::FILES, files)?;
                 for file in &files {
                     let item = pasteboard_items.next_item();
                     let url: id = msg_send![class!(NSURL), fileURLWithPath: *to_nsstring(file)];
@@ -137,6 +144,7 @@ impl DragDataAdapter for FilesDragDataAdapter {
                 }
             }
         }
+        Ok(())
     }
 
     fn register_types(&self, types: &mut Vec<StrongPtr>) {
@@ -186,21 +194,29 @@ impl DragDataAdapter for UrlsDragDataAdapter {
         &self,
         data_in: &mut HashMap<String, Value>,
         pasteboard_items: &mut PasteboardItems,
-    ) {
+    ) -> PlatformResult<()> {
         unsafe {
             let urls = data_in.remove(drag_data::key::URLS);
 
             let url_type = to_nsstring("public.url"); // NSPasteboardTypeFileURL
+            let string_type = to_nsstring("public.utf8-plain-text"); // NSPasteboardTypeString
+
+            // Text editors only accept plain string; explicit text takes precedence
+            let has_text = data_in.contains_key(drag_data::key::TEXT);
 
             if let Some(urls) = urls {
-                let urls = extract_string_list(urls);
+                let urls = extract_drag_string_list(drag_data::key::URLS, urls)?;
                 for url in &urls {
                     let item = pasteboard_items.next_item();
                     let string = to_nsstring(url);
                     let () = msg_send![*item, setString:*string forType:*url_type];
+                    if !has_text {
+                        let () = msg_send![*item, setString:*string forType:*string_type];
+                    }
                 }
             }
         }
+        Ok(())
     }
 
     fn register_types(&self, types: &mut Vec<StrongPtr>) {
@@ -209,6 +225,92 @@ impl DragDataAdapter for UrlsDragDataAdapter {
     }
 }
 
+pub(super) struct TextDragDataAdapter {}
+
+impl TextDragDataAdapter {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    // Convert HTML to RTF for applications that don't read HTML directly
+    unsafe fn rtf_from_html(html: &str) -> Option<StrongPtr> {
+        let data = to_nsdata(html.as_bytes());
+        let string: id = msg_send![class!(NSAttributedString), alloc];
+        let string: id = msg_send![string, initWithHTML:*data documentAttributes:nil];
+        if string == nil {
+            return None;
+        }
+        let string = StrongPtr::new(string);
+        let length: usize = msg_send![*string, length];
+        let attributes: id = msg_send![class!(NSDictionary), dictionary];
+        let rtf: id = msg_send![*string,
+            RTFFromRange:NSRange::new(0, length as u64)
+            documentAttributes:attributes
+        ];
+        if rtf == nil {
+            None
+        } else {
+            Some(StrongPtr::retain(rtf))
+        }
+    }
+}
+
+impl DragDataAdapter for TextDragDataAdapter {
+    fn retrieve_drag_data(&self, pasteboard: id, data_out: &mut HashMap<String, Value>) {
+        unsafe {
+            let string_type = to_nsstring("public.utf8-plain-text"); // NSPasteboardTypeString
+            let html_type = to_nsstring("public.html"); // NSPasteboardTypeHTML
+
+            let string: id = msg_send![pasteboard, stringForType: *string_type];
+            if string != nil {
+                data_out.insert(drag_data::key::TEXT.into(), from_nsstring(string).into());
+            }
+            let html: id = msg_send![pasteboard, stringForType: *html_type];
+            if html != nil {
+                data_out.insert(drag_data::key::HTML.into(), from_nsstring(html).into());
+            }
+        }
+    }
+
+    fn prepare_drag_data(
+        &self,
+        data_in: &mut HashMap<String, Value>,
+        pasteboard_items: &mut PasteboardItems,
+    ) -> PlatformResult<()> {
+        let text = data_in.remove(drag_data::key::TEXT);
+        let html = data_in.remove(drag_data::key::HTML);
+        if text.is_none() && html.is_none() {
+            return Ok(());
+        }
+
+        // Text is attached to the first item so that it is dragged together with URLs
+        // or files, if any
+        let item = pasteboard_items.next_item();
+        unsafe {
+            if let Some(text) = text {
+                let text = extract_drag_string(drag_data::key::TEXT, text)?;
+                let string_type = to_nsstring("public.utf8-plain-text");
+                let () = msg_send![*item, setString:*to_nsstring(&text) forType:*string_type];
+            }
+            if let Some(html) = html {
+                let html = extract_drag_string(drag_data::key::HTML, html)?;
+                let html_type = to_nsstring("public.html");
+                let () = msg_send![*item, setString:*to_nsstring(&html) forType:*html_type];
+                if let Some(rtf) = Self::rtf_from_html(&html) {
+                    let rtf_type = to_nsstring("public.rtf"); // NSPasteboardTypeRTF
+                    let () = msg_send![*item, setData:*rtf forType:*rtf_type];
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn register_types(&self, types: &mut Vec<StrongPtr>) {
+        types.push(to_nsstring("public.utf8-plain-text"));
+        types.push(to_nsstring("public.html"));
+    }
+}
+
 pub(super) struct FallThroughDragDataAdapter {
     format: StrongPtr,
 }
@@ -233,8 +335,8 @@ impl DragDataAdapter for FallThroughDragDataAdapter {
                 let bytes: *const u8 = msg_send![data, bytes];
                 let length: usize = msg_send![data, length];
                 let data: &[u8] = std::slice::from_raw_parts(bytes, length);
-                let value = codec.decode_message(&data).unwrap();
-                if let Value::Map(value) = value {
+                let value = codec.decode_message(data);
+                if let Some(Value::Map(value)) = value {
                     for entry in value {
                         if let Value::String(key) = entry.0 {
                             data_out.insert(key, entry.1);
@@ -253,7 +355,7 @@ impl DragDataAdapter for FallThroughDragDataAdapter {
         &self,
         data_in: &mut HashMap<String, Value>,
         pasteboard_items: &mut PasteboardItems,
-    ) {
+    ) -> PlatformResult<()> {
         let codec: &'static dyn MessageCodec<Value> = &StandardMethodCodec;
         let mut map = HashMap::new();
         for e in data_in.drain() {
@@ -265,24 +367,10 @@ impl DragDataAdapter for FallThroughDragDataAdapter {
         unsafe {
             let () = msg_send![*item, setData:*data forType:*self.format];
         }
+        Ok(())
     }
 
     fn register_types(&self, types: &mut Vec<StrongPtr>) {
         types.push(self.format.clone());
     }
 }
-
-fn extract_string_list(value: Value) -> Vec<String> {
-    if let Value::List(list) = value {
-        let mut res = Vec::new();
-        for value in list {
-            if let Value::String(value) = value {
-                res.push(value)
-            } else {
-                panic!("Invalid value found in list: ${:?}", value);
-            }
-        }
-        return res;
-    }
-    panic!("Invalid value: {:?}, expected list of strings", value)
-}
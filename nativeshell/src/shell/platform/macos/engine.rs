@@ -1,26 +1,52 @@
-use std::ffi::c_void;
+use std::ffi::{c_void, CStr};
 
-use cocoa::base::{id, nil, BOOL, NO};
+use cocoa::{
+    base::{id, nil, BOOL, NO, YES},
+    foundation::NSArray,
+};
 use objc::rc::{autoreleasepool, StrongPtr};
 
-use crate::shell::platform::key_interceptor::override_key_event;
+use crate::shell::{platform::key_interceptor::override_key_event, EngineOptions};
 
 use super::{
     binary_messenger::PlatformBinaryMessenger,
     error::{PlatformError, PlatformResult},
+    native_view::NativeViewType,
+    utils::to_nsstring,
 };
 
 pub struct PlatformEngine {
-    handle: StrongPtr,
+    pub(super) handle: StrongPtr,
     pub(super) view_controller: StrongPtr,
+    entrypoint: Option<StrongPtr>,
+}
+
+fn new_dart_project(options: &EngineOptions) -> StrongPtr {
+    unsafe {
+        let project: id = msg_send![class!(FlutterDartProject), alloc];
+        let project = StrongPtr::new(msg_send![project, initWithPrecompiledDartBundle: nil]);
+        if !options.entrypoint_args.is_empty() {
+            let args: Vec<StrongPtr> = options
+                .entrypoint_args
+                .iter()
+                .map(|a| to_nsstring(a))
+                .collect();
+            let args: Vec<id> = args.iter().map(|a| **a).collect();
+            let args = NSArray::arrayWithObjects(nil, &args);
+            let () = msg_send![*project, setDartEntrypointArguments: args];
+        }
+        project
+    }
 }
 
 impl PlatformEngine {
-    pub fn new() -> Self {
+    pub fn new(options: &EngineOptions) -> Self {
         autoreleasepool(|| unsafe {
+            let project = new_dart_project(options);
             let class = class!(FlutterViewController);
             let view_controller: id = msg_send![class, alloc];
-            let view_controller = StrongPtr::new(msg_send![view_controller, initWithProject: nil]);
+            let view_controller =
+                StrongPtr::new(msg_send![view_controller, initWithProject: *project]);
             let () = msg_send![*view_controller, setMouseTrackingMode: 3]; // always track mouse
             let engine: id = msg_send![*view_controller, engine];
             let embedder_api: *mut c_void = msg_send![engine, embedderAPI];
@@ -28,10 +54,58 @@ impl PlatformEngine {
             Self {
                 handle: StrongPtr::retain(engine),
                 view_controller,
+                entrypoint: options.entrypoint.as_deref().map(to_nsstring),
             }
         })
     }
 
+    // Engine without view controller; View controller can be attached later
+    pub fn new_headless(options: &EngineOptions) -> Self {
+        autoreleasepool(|| unsafe {
+            let project = new_dart_project(options);
+            let name = to_nsstring("io.flutter");
+            let engine: id = msg_send![class!(FlutterEngine), alloc];
+            let engine = StrongPtr::new(msg_send![engine,
+                initWithName: *name
+                project: *project
+                allowHeadlessExecution: YES]);
+            let embedder_api: *mut c_void = msg_send![*engine, embedderAPI];
+            override_key_event(embedder_api);
+            Self {
+                handle: engine,
+                view_controller: StrongPtr::new(nil),
+                entrypoint: options.entrypoint.as_deref().map(to_nsstring),
+            }
+        })
+    }
+
+    pub fn attach_view(&mut self) -> PlatformResult<()> {
+        if !self.view_controller.is_null() {
+            return Err(PlatformError::NotAvailable);
+        }
+        autoreleasepool(|| unsafe {
+            let view_controller: id = msg_send![class!(FlutterViewController), alloc];
+            self.view_controller = StrongPtr::new(msg_send![view_controller,
+                initWithEngine: *self.handle
+                nibName: nil
+                bundle: nil]);
+            let () = msg_send![*self.view_controller, setMouseTrackingMode: 3];
+        });
+        Ok(())
+    }
+
+    // Flutter view to be embedded in existing window instead of becoming its
+    // content view
+    pub fn embedded_view(&self) -> PlatformResult<NativeViewType> {
+        if self.view_controller.is_null() {
+            return Err(PlatformError::NotAvailable);
+        }
+        unsafe {
+            let view: id = msg_send![*self.view_controller, view];
+            Ok(StrongPtr::retain(view))
+        }
+    }
+
     pub fn new_binary_messenger(&self) -> PlatformBinaryMessenger {
         autoreleasepool(|| unsafe {
             let messenger: id = msg_send![*self.handle, binaryMessenger];
@@ -39,9 +113,28 @@ impl PlatformEngine {
         })
     }
 
+    // Registrar is NSObject<FlutterPluginRegistrar>*
+    pub fn with_plugin_registrar<F>(&self, plugin_name: &CStr, f: F) -> PlatformResult<()>
+    where
+        F: FnOnce(*mut c_void),
+    {
+        autoreleasepool(|| unsafe {
+            let name: id = msg_send![class!(NSString), stringWithUTF8String: plugin_name.as_ptr()];
+            let registrar: id = msg_send![*self.handle, registrarForPlugin: name];
+            f(registrar as *mut c_void);
+        });
+        Ok(())
+    }
+
     pub fn launch(&mut self) -> PlatformResult<()> {
-        let res: BOOL =
-            autoreleasepool(|| unsafe { msg_send![*self.view_controller, launchEngine] });
+        let res: BOOL = autoreleasepool(|| unsafe {
+            if self.view_controller.is_null() || self.entrypoint.is_some() {
+                let entrypoint = self.entrypoint.as_ref().map(|e| **e).unwrap_or(nil);
+                msg_send![*self.handle, runWithEntrypoint: entrypoint]
+            } else {
+                msg_send![*self.view_controller, launchEngine]
+            }
+        });
         if res == NO {
             Err(PlatformError::LaunchEngineFailure)
         } else {
@@ -51,6 +144,13 @@ impl PlatformEngine {
 
     pub fn shut_down(&mut self) -> PlatformResult<()> {
         autoreleasepool(|| unsafe {
+            if !self.view_controller.is_null() {
+                let view: id = msg_send![*self.view_controller, view];
+                let window: id = msg_send![view, window];
+                if window != nil {
+                    let () = msg_send![window, setContentViewController: nil];
+                }
+            }
             let () = msg_send![*self.handle, shutDownEngine];
         });
         Ok(())
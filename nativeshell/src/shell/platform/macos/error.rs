@@ -1,5 +1,10 @@
 use std::fmt::Display;
 
+use crate::{
+    codec::Value,
+    shell::platform::{DragError, WindowError},
+};
+
 #[derive(Debug, Clone)]
 pub enum PlatformError {
     UnknownError,
@@ -7,6 +12,9 @@ pub enum PlatformError {
     SendMessageFailure { channel: String },
     NotAvailable,
     NoEventFound,
+    PermissionDenied,
+    Window(WindowError),
+    Drag(DragError),
 }
 
 pub type PlatformResult<T> = Result<T, PlatformError>;
@@ -32,8 +40,50 @@ impl Display for PlatformError {
             PlatformError::NotAvailable => {
                 write!(f, "Feature is not available")
             }
+            PlatformError::PermissionDenied => {
+                write!(f, "Permission denied")
+            }
+            PlatformError::Window(error) => error.fmt(f),
+            PlatformError::Drag(error) => error.fmt(f),
+        }
+    }
+}
+
+impl PlatformError {
+    // Stable error code reported to Dart
+    pub fn code(&self) -> &'static str {
+        match self {
+            PlatformError::UnknownError => "unknown-error",
+            PlatformError::LaunchEngineFailure => "launch-engine-failure",
+            PlatformError::SendMessageFailure { .. } => "send-message-failure",
+            PlatformError::NotAvailable => "not-available",
+            PlatformError::NoEventFound => "no-event-found",
+            PlatformError::PermissionDenied => "permission-denied",
+            PlatformError::Window(error) => error.code(),
+            PlatformError::Drag(error) => error.code(),
+        }
+    }
+
+    pub fn details(&self) -> Value {
+        match self {
+            PlatformError::SendMessageFailure { channel } => channel.as_str().into(),
+            PlatformError::Window(error) => error.details(),
+            PlatformError::Drag(error) => error.details(),
+            _ => Value::Null,
         }
     }
 }
 
 impl std::error::Error for PlatformError {}
+
+impl From<WindowError> for PlatformError {
+    fn from(error: WindowError) -> Self {
+        PlatformError::Window(error)
+    }
+}
+
+impl From<DragError> for PlatformError {
+    fn from(error: DragError) -> Self {
+        PlatformError::Drag(error)
+    }
+}
@@ -0,0 +1,206 @@
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+
+use block::ConcreteBlock;
+use cocoa::{
+    appkit::{NSEvent, NSEventType, NSScreen},
+    base::{id, nil},
+    foundation::{NSArray, NSInteger, NSPoint, NSRect},
+};
+use core_foundation::{
+    base::TCFType,
+    boolean::CFBoolean,
+    dictionary::{CFDictionary, CFDictionaryRef},
+    string::{CFString, CFStringRef},
+};
+use objc::rc::StrongPtr;
+
+use crate::shell::{
+    api_model::{EventTapEvent, EventTapEventType, EventTapPermission, EventTapRequest},
+    Context, Point,
+};
+
+use super::error::{PlatformError, PlatformResult};
+
+use NSEventType::{
+    NSKeyDown, NSKeyUp, NSLeftMouseDown, NSLeftMouseDragged, NSLeftMouseUp, NSMouseMoved,
+    NSOtherMouseDown, NSOtherMouseDragged, NSOtherMouseUp, NSRightMouseDown, NSRightMouseDragged,
+    NSRightMouseUp, NSScrollWheel,
+};
+
+#[link(name = "ApplicationServices", kind = "framework")]
+extern "C" {
+    static kAXTrustedCheckOptionPrompt: CFStringRef;
+    fn AXIsProcessTrusted() -> bool;
+    fn AXIsProcessTrustedWithOptions(options: CFDictionaryRef) -> bool;
+}
+
+pub struct PlatformEventTap {
+    context: Weak<Context>,
+    monitor: RefCell<Option<StrongPtr>>,
+}
+
+fn mask_for(types: &[NSEventType]) -> u64 {
+    types.iter().fold(0, |mask, t| mask | 1 << *t as u64)
+}
+
+impl PlatformEventTap {
+    pub fn new(context: Rc<Context>) -> Self {
+        Self {
+            context: Rc::downgrade(&context),
+            monitor: RefCell::new(None),
+        }
+    }
+
+    // Mouse events can be monitored freely, key events require the application
+    // to be trusted for accessibility
+    pub fn permission(&self) -> EventTapPermission {
+        if unsafe { AXIsProcessTrusted() } {
+            EventTapPermission::Granted
+        } else {
+            EventTapPermission::Denied
+        }
+    }
+
+    pub fn request_permission(&self) -> PlatformResult<EventTapPermission> {
+        let trusted = unsafe {
+            let key = CFString::wrap_under_get_rule(kAXTrustedCheckOptionPrompt);
+            let options = CFDictionary::from_CFType_pairs(&[(
+                key.as_CFType(),
+                CFBoolean::true_value().as_CFType(),
+            )]);
+            AXIsProcessTrustedWithOptions(options.as_concrete_TypeRef())
+        };
+        Ok(if trusted {
+            EventTapPermission::Granted
+        } else {
+            EventTapPermission::Denied
+        })
+    }
+
+    pub fn start(&self, request: &EventTapRequest) -> PlatformResult<()> {
+        self.stop()?;
+
+        if request.key_events && self.permission() != EventTapPermission::Granted {
+            return Err(PlatformError::PermissionDenied);
+        }
+
+        let mut mask = 0;
+        if request.key_events {
+            mask |= mask_for(&[NSKeyDown, NSKeyUp]);
+        }
+        if request.mouse_events {
+            mask |= mask_for(&[
+                NSLeftMouseDown,
+                NSLeftMouseUp,
+                NSRightMouseDown,
+                NSRightMouseUp,
+                NSOtherMouseDown,
+                NSOtherMouseUp,
+                NSMouseMoved,
+                NSLeftMouseDragged,
+                NSRightMouseDragged,
+                NSOtherMouseDragged,
+                NSScrollWheel,
+            ]);
+        }
+        if mask == 0 {
+            return Ok(());
+        }
+
+        let context = self.context.clone();
+        let handler = ConcreteBlock::new(move |event: id| {
+            if let (Some(context), Some(event)) = (context.upgrade(), convert_event(event)) {
+                context.event_tap_manager.borrow().on_event(event);
+            }
+        });
+        let handler = handler.copy();
+
+        let monitor = unsafe {
+            let monitor: id = msg_send![class!(NSEvent),
+                addGlobalMonitorForEventsMatchingMask: mask
+                handler: &*handler
+            ];
+            if monitor == nil {
+                return Err(PlatformError::UnknownError);
+            }
+            StrongPtr::retain(monitor)
+        };
+        self.monitor.replace(Some(monitor));
+        Ok(())
+    }
+
+    pub fn stop(&self) -> PlatformResult<()> {
+        if let Some(monitor) = self.monitor.borrow_mut().take() {
+            unsafe {
+                let () = msg_send![class!(NSEvent), removeMonitor: *monitor];
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for PlatformEventTap {
+    fn drop(&mut self) {
+        self.stop().ok();
+    }
+}
+
+// Global events have screen coordinates with bottom left origin
+unsafe fn screen_position(event: id) -> Point {
+    let location: NSPoint = event.locationInWindow();
+    let screens = NSScreen::screens(nil);
+    let height = if screens.count() > 0 {
+        let frame: NSRect = NSScreen::frame(screens.objectAtIndex(0));
+        frame.size.height
+    } else {
+        0.0
+    };
+    Point::xy(location.x, height - location.y)
+}
+
+fn convert_event(event: id) -> Option<EventTapEvent> {
+    unsafe {
+        let event_type = match event.eventType() {
+            NSKeyDown => EventTapEventType::KeyDown,
+            NSKeyUp => EventTapEventType::KeyUp,
+            NSLeftMouseDown | NSRightMouseDown | NSOtherMouseDown => EventTapEventType::MouseDown,
+            NSLeftMouseUp | NSRightMouseUp | NSOtherMouseUp => EventTapEventType::MouseUp,
+            NSMouseMoved | NSLeftMouseDragged | NSRightMouseDragged | NSOtherMouseDragged => {
+                EventTapEventType::MouseMove
+            }
+            NSScrollWheel => EventTapEventType::ScrollWheel,
+            _ => return None,
+        };
+        let key_code = match event_type {
+            EventTapEventType::KeyDown | EventTapEventType::KeyUp => Some(event.keyCode() as i64),
+            _ => None,
+        };
+        let button = match event_type {
+            EventTapEventType::MouseDown | EventTapEventType::MouseUp => {
+                let button: NSInteger = msg_send![event, buttonNumber];
+                Some(button)
+            }
+            _ => None,
+        };
+        let position = match event_type {
+            EventTapEventType::KeyDown | EventTapEventType::KeyUp => None,
+            _ => Some(screen_position(event)),
+        };
+        let scroll_delta = match event_type {
+            EventTapEventType::ScrollWheel => {
+                Some(Point::xy(event.scrollingDeltaX(), event.scrollingDeltaY()))
+            }
+            _ => None,
+        };
+        Some(EventTapEvent {
+            event_type,
+            key_code,
+            button,
+            position,
+            scroll_delta,
+        })
+    }
+}
@@ -0,0 +1,50 @@
+use cocoa::base::{id, nil, BOOL, YES};
+
+use crate::shell::api_model::HapticFeedbackPattern;
+
+use super::{
+    error::{PlatformError, PlatformResult},
+    utils::to_nsstring,
+};
+
+#[link(name = "AppKit", kind = "framework")]
+extern "C" {
+    fn NSBeep();
+}
+
+pub fn beep() -> PlatformResult<()> {
+    unsafe { NSBeep() };
+    Ok(())
+}
+
+pub fn play_system_sound(name: &str) -> PlatformResult<()> {
+    unsafe {
+        let sound: id = msg_send![class!(NSSound), soundNamed: *to_nsstring(name)];
+        if sound == nil {
+            return Err(PlatformError::NotAvailable);
+        }
+        let played: BOOL = msg_send![sound, play];
+        if played == YES {
+            Ok(())
+        } else {
+            Err(PlatformError::UnknownError)
+        }
+    }
+}
+
+pub fn perform_haptic_feedback(pattern: HapticFeedbackPattern) -> PlatformResult<()> {
+    // NSHapticFeedbackPattern
+    let pattern: isize = match pattern {
+        HapticFeedbackPattern::Generic => 0,
+        HapticFeedbackPattern::Alignment => 1,
+        HapticFeedbackPattern::LevelChange => 2,
+    };
+    unsafe {
+        let performer: id = msg_send![class!(NSHapticFeedbackManager), defaultPerformer];
+        let () = msg_send![performer,
+            performFeedbackPattern: pattern
+            performanceTime: 0isize // NSHapticFeedbackPerformanceTimeDefault
+        ];
+    }
+    Ok(())
+}
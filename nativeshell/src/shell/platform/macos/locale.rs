@@ -0,0 +1,106 @@
+use std::rc::Rc;
+
+use block::ConcreteBlock;
+use cocoa::base::{id, nil};
+use objc::rc::StrongPtr;
+
+use crate::shell::{
+    api_model::{LocaleSettings, MeasurementSystem},
+    Context,
+};
+
+use super::{
+    error::PlatformResult,
+    utils::{from_nsstring, to_nsstring},
+};
+
+#[link(name = "Foundation", kind = "framework")]
+extern "C" {
+    static NSCurrentLocaleDidChangeNotification: id;
+    static NSLocaleCountryCode: id;
+    static NSLocaleMeasurementSystem: id;
+}
+
+pub struct PlatformLocale {
+    observer: StrongPtr,
+}
+
+impl PlatformLocale {
+    pub fn new(context: Rc<Context>) -> Self {
+        let context = Rc::downgrade(&context);
+        unsafe {
+            // Also posted when user changes time format in system preferences
+            let block = ConcreteBlock::new(move |_notification: id| {
+                if let Some(context) = context.upgrade() {
+                    context.locale_manager.borrow().settings_changed();
+                }
+            });
+            let block = block.copy();
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let observer: id = msg_send![center,
+                addObserverForName: NSCurrentLocaleDidChangeNotification
+                object: nil
+                queue: nil
+                usingBlock: &*block
+            ];
+            Self {
+                observer: StrongPtr::retain(observer),
+            }
+        }
+    }
+
+    pub fn get_settings(&self) -> PlatformResult<LocaleSettings> {
+        unsafe {
+            let locale: id = msg_send![class!(NSLocale), autoupdatingCurrentLocale];
+            // May contain keywords (i.e. "en_US@rg=gbzzzz")
+            let identifier: id = msg_send![locale, localeIdentifier];
+            let region: id = msg_send![locale, objectForKey: NSLocaleCountryCode];
+            let measurement: id = msg_send![locale, objectForKey: NSLocaleMeasurementSystem];
+            let measurement_system = match measurement {
+                m if m == nil => MeasurementSystem::Metric,
+                m => match from_nsstring(m).as_str() {
+                    "U.S." => MeasurementSystem::Us,
+                    "U.K." => MeasurementSystem::Uk,
+                    _ => MeasurementSystem::Metric,
+                },
+            };
+
+            // 1 is Sunday
+            let calendar: id = msg_send![class!(NSCalendar), autoupdatingCurrentCalendar];
+            let first_weekday: usize = msg_send![calendar, firstWeekday];
+
+            // Preferred hour format of the locale, including user override
+            let format: id = msg_send![class!(NSDateFormatter),
+                dateFormatFromTemplate: *to_nsstring("j")
+                options: 0usize
+                locale: locale
+            ];
+            let uses_24_hour_clock = format == nil || !from_nsstring(format).contains('a');
+
+            Ok(LocaleSettings {
+                locale: from_nsstring(identifier)
+                    .split('@')
+                    .next()
+                    .unwrap_or_default()
+                    .replace('_', "-"),
+                region: if region != nil {
+                    Some(from_nsstring(region))
+                } else {
+                    None
+                },
+                first_day_of_week: ((first_weekday + 5) % 7 + 1) as u32,
+                uses_24_hour_clock,
+                measurement_system,
+            })
+        }
+    }
+}
+
+impl Drop for PlatformLocale {
+    fn drop(&mut self) {
+        unsafe {
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let () = msg_send![center, removeObserver: *self.observer];
+        }
+    }
+}
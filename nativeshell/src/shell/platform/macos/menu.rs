@@ -66,6 +66,32 @@ impl PlatformMenuManager {
         }
     }
 
+    // Cocoa lists windows in menu set as windowsMenu
+    pub fn has_native_window_list(&self) -> bool {
+        true
+    }
+
+    // Default system wide shortcuts (users can reassign these in Keyboard
+    // preferences)
+    pub fn system_shortcuts(&self) -> Vec<(Accelerator, &'static str)> {
+        [
+            ("Cmd+Tab", "Switch applications"),
+            ("Cmd+Space", "Spotlight"),
+            ("Ctrl+Space", "Select previous input source"),
+            ("Ctrl+Cmd+Space", "Emoji & Symbols"),
+            ("Ctrl+Cmd+Q", "Lock Screen"),
+            ("Alt+Cmd+Escape", "Force Quit"),
+            ("Shift+Cmd+3", "Screenshot"),
+            ("Shift+Cmd+4", "Screenshot of selected area"),
+            ("Shift+Cmd+5", "Screenshot and recording options"),
+            ("Ctrl+Up Arrow", "Mission Control"),
+            ("Ctrl+Down Arrow", "Application windows"),
+        ]
+        .iter()
+        .map(|(accelerator, name)| (Accelerator::parse(accelerator), *name))
+        .collect()
+    }
+
     fn update_menu(&self) {
         unsafe {
             let mut menu = self.app_menu.borrow().clone();
@@ -220,12 +246,8 @@ impl PlatformMenu {
 
         for (i, d) in diff.iter().enumerate() {
             match d {
-                DiffResult::Remove(_) => {
-                    panic!("Should have been already removed.")
-                }
-                DiffResult::Keep(_, _) => {
-                    // nothing
-                }
+                // removed items were already handled above
+                DiffResult::Remove(_) | DiffResult::Keep(_, _) => {}
                 DiffResult::Update(old, new) => {
                     let item = self
                         .id_to_menu_item
@@ -1,12 +1,33 @@
+pub mod accessibility;
+pub mod application;
 pub mod binary_messenger;
+pub mod bundle;
+pub mod crash_handler;
+pub mod display_info;
 mod drag_context;
 pub mod drag_data;
 pub mod engine;
 pub mod error;
+pub mod event_tap;
+pub mod feedback;
 pub mod init;
 pub mod key_event;
+pub mod locale;
 pub mod menu;
+pub mod native_view;
+pub mod permission;
+pub mod power;
+mod recent_input;
+pub mod render_layer;
 pub mod run_loop;
+pub mod screen_capture;
+pub mod single_instance;
+pub mod system_theme;
+mod tablet;
+pub mod texture;
+mod touch_bar;
+pub mod user_activity;
 mod utils;
 pub mod window;
-pub mod bundle;
\ No newline at end of file
+pub mod window_list;
+pub mod workspace;
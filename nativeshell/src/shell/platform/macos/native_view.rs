@@ -0,0 +1,86 @@
+use std::rc::Rc;
+
+use cocoa::{
+    appkit::{NSView, NSWindow},
+    base::{id, BOOL, NO, YES},
+    foundation::{NSPoint, NSRect, NSSize},
+};
+use objc::rc::StrongPtr;
+
+use crate::shell::Rect;
+
+use super::{error::PlatformResult, window::PlatformWindow};
+
+// NSView instance; Will be added as subview of window content view
+pub type NativeViewType = StrongPtr;
+
+pub struct PlatformNativeView {
+    window: Rc<PlatformWindow>,
+    // Clips the view; Frame matches clip rect
+    container: StrongPtr,
+    view: StrongPtr,
+}
+
+impl PlatformNativeView {
+    pub fn new(window: Rc<PlatformWindow>, view: NativeViewType) -> PlatformResult<Self> {
+        unsafe {
+            let container: id = msg_send![class!(NSView), alloc];
+            let container = StrongPtr::new(
+                container
+                    .initWithFrame_(NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(0.0, 0.0))),
+            );
+            container.setWantsLayer(YES);
+            let layer: id = msg_send![*container, layer];
+            let () = msg_send![layer, setMasksToBounds: YES];
+            let () = msg_send![*container, setHidden: YES];
+            container.addSubview_(*view);
+
+            // Subviews of flutter view are above flutter content and get mouse
+            // events through regular hit testing
+            let content_view = window.get_platform_window().contentView();
+            content_view.addSubview_(*container);
+
+            Ok(Self {
+                window,
+                container,
+                view,
+            })
+        }
+    }
+
+    pub fn update(&self, frame: &Rect, clip: &Rect, visible: bool) -> PlatformResult<()> {
+        unsafe {
+            let content_view = self.window.get_platform_window().contentView();
+            let flipped: BOOL = msg_send![content_view, isFlipped];
+            let content_height = content_view.bounds().size.height;
+            let clip_y = if flipped == NO {
+                content_height - clip.y2()
+            } else {
+                clip.y
+            };
+            let container_frame = NSRect::new(
+                NSPoint::new(clip.x, clip_y),
+                NSSize::new(clip.width, clip.height),
+            );
+            let () = msg_send![*self.container, setFrame: container_frame];
+            // container is not flipped
+            let view_frame = NSRect::new(
+                NSPoint::new(frame.x - clip.x, clip.y2() - frame.y2()),
+                NSSize::new(frame.width, frame.height),
+            );
+            let () = msg_send![*self.view, setFrame: view_frame];
+            let hidden = if visible { NO } else { YES };
+            let () = msg_send![*self.container, setHidden: hidden];
+        }
+        Ok(())
+    }
+}
+
+impl Drop for PlatformNativeView {
+    fn drop(&mut self) {
+        unsafe {
+            let () = msg_send![*self.container, removeFromSuperview];
+            let () = msg_send![*self.view, removeFromSuperview];
+        }
+    }
+}
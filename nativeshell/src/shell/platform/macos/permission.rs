@@ -0,0 +1,152 @@
+use std::sync::Mutex;
+
+use block::ConcreteBlock;
+use cocoa::base::id;
+use core_foundation::{
+    base::TCFType,
+    boolean::CFBoolean,
+    dictionary::{CFDictionary, CFDictionaryRef},
+    string::{CFString, CFStringRef},
+};
+use objc::runtime::{BOOL, YES};
+
+use crate::shell::api_model::{PermissionKind, PermissionStatus};
+
+use super::error::PlatformResult;
+
+type IOHIDRequestType = u32;
+type IOHIDAccessType = u32;
+
+const K_IOHID_REQUEST_TYPE_LISTEN_EVENT: IOHIDRequestType = 1;
+const K_IOHID_ACCESS_TYPE_GRANTED: IOHIDAccessType = 0;
+const K_IOHID_ACCESS_TYPE_DENIED: IOHIDAccessType = 1;
+
+// AVAuthorizationStatus
+const AV_AUTHORIZATION_STATUS_NOT_DETERMINED: isize = 0;
+const AV_AUTHORIZATION_STATUS_RESTRICTED: isize = 1;
+const AV_AUTHORIZATION_STATUS_DENIED: isize = 2;
+
+#[link(name = "ApplicationServices", kind = "framework")]
+extern "C" {
+    static kAXTrustedCheckOptionPrompt: CFStringRef;
+    fn AXIsProcessTrusted() -> bool;
+    fn AXIsProcessTrustedWithOptions(options: CFDictionaryRef) -> bool;
+}
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    fn CGPreflightScreenCaptureAccess() -> bool;
+    fn CGRequestScreenCaptureAccess() -> bool;
+}
+
+#[link(name = "IOKit", kind = "framework")]
+extern "C" {
+    fn IOHIDCheckAccess(request_type: IOHIDRequestType) -> IOHIDAccessType;
+    fn IOHIDRequestAccess(request_type: IOHIDRequestType) -> bool;
+}
+
+#[link(name = "AVFoundation", kind = "framework")]
+extern "C" {
+    static AVMediaTypeVideo: id;
+    static AVMediaTypeAudio: id;
+}
+
+fn granted_or_denied(granted: bool) -> PermissionStatus {
+    if granted {
+        PermissionStatus::Granted
+    } else {
+        PermissionStatus::Denied
+    }
+}
+
+fn media_type(kind: PermissionKind) -> Option<id> {
+    match kind {
+        PermissionKind::Camera => Some(unsafe { AVMediaTypeVideo }),
+        PermissionKind::Microphone => Some(unsafe { AVMediaTypeAudio }),
+        _ => None,
+    }
+}
+
+fn media_status(media_type: id) -> PermissionStatus {
+    let status: isize =
+        unsafe { msg_send![class!(AVCaptureDevice), authorizationStatusForMediaType: media_type] };
+    match status {
+        AV_AUTHORIZATION_STATUS_NOT_DETERMINED => PermissionStatus::NotDetermined,
+        AV_AUTHORIZATION_STATUS_RESTRICTED => PermissionStatus::Restricted,
+        AV_AUTHORIZATION_STATUS_DENIED => PermissionStatus::Denied,
+        _ => PermissionStatus::Granted,
+    }
+}
+
+// Screen recording permission can not be distinguished between denied and not
+// determined; Both are reported as denied.
+pub fn status(kind: PermissionKind) -> PlatformResult<PermissionStatus> {
+    Ok(match kind {
+        PermissionKind::ScreenRecording => {
+            granted_or_denied(unsafe { CGPreflightScreenCaptureAccess() })
+        }
+        PermissionKind::Accessibility => granted_or_denied(unsafe { AXIsProcessTrusted() }),
+        PermissionKind::InputMonitoring => {
+            match unsafe { IOHIDCheckAccess(K_IOHID_REQUEST_TYPE_LISTEN_EVENT) } {
+                K_IOHID_ACCESS_TYPE_GRANTED => PermissionStatus::Granted,
+                K_IOHID_ACCESS_TYPE_DENIED => PermissionStatus::Denied,
+                _ => PermissionStatus::NotDetermined,
+            }
+        }
+        PermissionKind::Camera | PermissionKind::Microphone => {
+            media_status(media_type(kind).unwrap())
+        }
+    })
+}
+
+// Screen recording, accessibility and input monitoring prompts direct user to
+// System Preferences and return immediately; Camera and microphone prompts
+// complete on arbitrary thread once user responds.
+pub fn request(
+    kind: PermissionKind,
+    callback: Box<dyn FnOnce(PlatformResult<PermissionStatus>) + Send>,
+) {
+    match kind {
+        PermissionKind::ScreenRecording => {
+            callback(Ok(granted_or_denied(unsafe {
+                CGRequestScreenCaptureAccess()
+            })));
+        }
+        PermissionKind::Accessibility => {
+            let trusted = unsafe {
+                let key = CFString::wrap_under_get_rule(kAXTrustedCheckOptionPrompt);
+                let options = CFDictionary::from_CFType_pairs(&[(
+                    key.as_CFType(),
+                    CFBoolean::true_value().as_CFType(),
+                )]);
+                AXIsProcessTrustedWithOptions(options.as_concrete_TypeRef())
+            };
+            callback(Ok(granted_or_denied(trusted)));
+        }
+        PermissionKind::InputMonitoring => {
+            callback(Ok(granted_or_denied(unsafe {
+                IOHIDRequestAccess(K_IOHID_REQUEST_TYPE_LISTEN_EVENT)
+            })));
+        }
+        PermissionKind::Camera | PermissionKind::Microphone => {
+            let media_type = media_type(kind).unwrap();
+            if media_status(media_type) != PermissionStatus::NotDetermined {
+                callback(status(kind));
+                return;
+            }
+            // Handler block must be Fn; Callback is consumed on first invocation
+            let callback = Mutex::new(Some(callback));
+            let handler = ConcreteBlock::new(move |granted: BOOL| {
+                if let Some(callback) = callback.lock().unwrap().take() {
+                    callback(Ok(granted_or_denied(granted == YES)));
+                }
+            });
+            let handler = handler.copy();
+            unsafe {
+                let () = msg_send![class!(AVCaptureDevice),
+                    requestAccessForMediaType: media_type
+                    completionHandler: &*handler];
+            }
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use std::{
+    ffi::c_void,
+    rc::{Rc, Weak},
+};
+
+use block::ConcreteBlock;
+use core_foundation::{
+    array::CFArrayRef,
+    base::{CFRelease, CFTypeRef, TCFType},
+    runloop::{kCFRunLoopCommonModes, CFRunLoop, CFRunLoopSource, CFRunLoopSourceRef},
+    string::{CFString, CFStringRef},
+};
+
+use cocoa::{
+    base::{id, nil, BOOL, NO, YES},
+    foundation::{NSArray, NSProcessInfo},
+};
+use objc::rc::StrongPtr;
+
+use crate::shell::{
+    api_model::{PowerSource, PowerStatus},
+    ActivityOptions, Context, SleepPreventionKind,
+};
+
+use super::{
+    error::{PlatformError, PlatformResult},
+    utils::{from_nsstring, to_nsstring},
+};
+
+type IOPMAssertionID = u32;
+type IOPMAssertionLevel = u32;
+type IOReturn = i32;
+
+const K_IOPM_ASSERTION_LEVEL_ON: IOPMAssertionLevel = 255;
+const K_IO_RETURN_SUCCESS: IOReturn = 0;
+
+// NSActivityOptions
+const NS_ACTIVITY_IDLE_SYSTEM_SLEEP_DISABLED: u64 = 1 << 20;
+const NS_ACTIVITY_USER_INITIATED_ALLOWING_IDLE_SYSTEM_SLEEP: u64 =
+    0x00FFFFFF & !NS_ACTIVITY_IDLE_SYSTEM_SLEEP_DISABLED;
+const NS_ACTIVITY_LATENCY_CRITICAL: u64 = 0xFF00000000;
+
+#[link(name = "IOKit", kind = "framework")]
+extern "C" {
+    fn IOPMAssertionCreateWithName(
+        assertion_type: CFStringRef,
+        assertion_level: IOPMAssertionLevel,
+        assertion_name: CFStringRef,
+        assertion_id: *mut IOPMAssertionID,
+    ) -> IOReturn;
+    fn IOPMAssertionRelease(assertion_id: IOPMAssertionID) -> IOReturn;
+
+    fn IOPSCopyPowerSourcesInfo() -> CFTypeRef;
+    fn IOPSCopyPowerSourcesList(blob: CFTypeRef) -> CFArrayRef;
+    fn IOPSGetPowerSourceDescription(blob: CFTypeRef, ps: CFTypeRef) -> id;
+    fn IOPSGetProvidingPowerSourceType(snapshot: CFTypeRef) -> CFStringRef;
+    fn IOPSNotificationCreateRunLoopSource(
+        callback: extern "C" fn(*mut c_void),
+        context: *mut c_void,
+    ) -> CFRunLoopSourceRef;
+}
+
+#[link(name = "Foundation", kind = "framework")]
+extern "C" {
+    static NSProcessInfoPowerStateDidChangeNotification: id;
+}
+
+pub struct PlatformSleepGuard {
+    assertion_id: IOPMAssertionID,
+}
+
+impl PlatformSleepGuard {
+    pub fn new(kind: SleepPreventionKind, reason: &str) -> PlatformResult<Self> {
+        let assertion_type = match kind {
+            SleepPreventionKind::System => "PreventUserIdleSystemSleep",
+            SleepPreventionKind::Display => "PreventUserIdleDisplaySleep",
+        };
+        let assertion_type = CFString::new(assertion_type);
+        let reason = CFString::new(reason);
+        let mut assertion_id: IOPMAssertionID = 0;
+        let res = unsafe {
+            IOPMAssertionCreateWithName(
+                assertion_type.as_concrete_TypeRef(),
+                K_IOPM_ASSERTION_LEVEL_ON,
+                reason.as_concrete_TypeRef(),
+                &mut assertion_id,
+            )
+        };
+        if res != K_IO_RETURN_SUCCESS {
+            return Err(PlatformError::UnknownError);
+        }
+        Ok(Self { assertion_id })
+    }
+}
+
+impl Drop for PlatformSleepGuard {
+    fn drop(&mut self) {
+        unsafe {
+            IOPMAssertionRelease(self.assertion_id);
+        }
+    }
+}
+
+pub struct PlatformActivityGuard {
+    activity: StrongPtr,
+}
+
+impl PlatformActivityGuard {
+    pub fn new(options: ActivityOptions, reason: &str) -> PlatformResult<Self> {
+        let mut activity_options = 0u64;
+        if options.user_initiated {
+            activity_options |= NS_ACTIVITY_USER_INITIATED_ALLOWING_IDLE_SYSTEM_SLEEP;
+        }
+        if options.latency_critical {
+            activity_options |= NS_ACTIVITY_LATENCY_CRITICAL;
+        }
+        if options.prevent_idle_sleep {
+            activity_options |= NS_ACTIVITY_IDLE_SYSTEM_SLEEP_DISABLED;
+        }
+        unsafe {
+            let process_info = NSProcessInfo::processInfo(nil);
+            let activity: id = msg_send![process_info,
+                beginActivityWithOptions: activity_options
+                reason: *to_nsstring(reason)
+            ];
+            if activity.is_null() {
+                return Err(PlatformError::UnknownError);
+            }
+            Ok(Self {
+                activity: StrongPtr::retain(activity),
+            })
+        }
+    }
+}
+
+impl Drop for PlatformActivityGuard {
+    fn drop(&mut self) {
+        unsafe {
+            let process_info = NSProcessInfo::processInfo(nil);
+            let () = msg_send![process_info, endActivity: *self.activity];
+        }
+    }
+}
+
+// Observes IOKit power source notifications and Low Power Mode changes
+pub struct PlatformPowerSource {
+    run_loop_source: CFRunLoopSource,
+    context: *mut Weak<Context>,
+    low_power_observer: StrongPtr,
+}
+
+impl PlatformPowerSource {
+    pub fn new(context: Rc<Context>) -> Self {
+        let weak_context = Box::into_raw(Box::new(Rc::downgrade(&context)));
+        let run_loop_source = unsafe {
+            CFRunLoopSource::wrap_under_create_rule(IOPSNotificationCreateRunLoopSource(
+                Self::on_power_source_changed,
+                weak_context as *mut c_void,
+            ))
+        };
+        CFRunLoop::get_main().add_source(&run_loop_source, unsafe { kCFRunLoopCommonModes });
+
+        // Notification is posted on arbitrary thread, so deliver it through
+        // main queue
+        let low_power_observer = unsafe {
+            let context = Rc::downgrade(&context);
+            let block = ConcreteBlock::new(move |_notification: id| {
+                if let Some(context) = context.upgrade() {
+                    context.power_manager.borrow().status_changed();
+                }
+            });
+            let block = block.copy();
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let queue: id = msg_send![class!(NSOperationQueue), mainQueue];
+            let observer: id = msg_send![center,
+                addObserverForName: NSProcessInfoPowerStateDidChangeNotification
+                object: nil
+                queue: queue
+                usingBlock: &*block
+            ];
+            StrongPtr::retain(observer)
+        };
+
+        Self {
+            run_loop_source,
+            context: weak_context,
+            low_power_observer,
+        }
+    }
+
+    extern "C" fn on_power_source_changed(context: *mut c_void) {
+        let context = unsafe { &*(context as *const Weak<Context>) };
+        if let Some(context) = context.upgrade() {
+            context.power_manager.borrow().status_changed();
+        }
+    }
+
+    pub fn get_status(&self) -> PlatformResult<PowerStatus> {
+        unsafe {
+            let snapshot = IOPSCopyPowerSourcesInfo();
+            if snapshot.is_null() {
+                return Err(PlatformError::NotAvailable);
+            }
+            let status = Self::status_from_snapshot(snapshot);
+            CFRelease(snapshot);
+            Ok(status)
+        }
+    }
+
+    unsafe fn status_from_snapshot(snapshot: CFTypeRef) -> PowerStatus {
+        let providing = IOPSGetProvidingPowerSourceType(snapshot);
+        let power_source = if providing.is_null() {
+            PowerSource::Unknown
+        } else {
+            match CFString::wrap_under_get_rule(providing)
+                .to_string()
+                .as_str()
+            {
+                "AC Power" => PowerSource::Ac,
+                "Battery Power" => PowerSource::Battery,
+                _ => PowerSource::Unknown,
+            }
+        };
+
+        let mut battery = None;
+        let list = IOPSCopyPowerSourcesList(snapshot);
+        if !list.is_null() {
+            let sources = list as id;
+            for i in 0..sources.count() {
+                let description =
+                    IOPSGetPowerSourceDescription(snapshot, sources.objectAtIndex(i) as CFTypeRef);
+                if description == nil {
+                    continue;
+                }
+                let source_type: id = msg_send![description, objectForKey: *to_nsstring("Type")];
+                if source_type != nil && from_nsstring(source_type) == "InternalBattery" {
+                    battery = Some(description);
+                    break;
+                }
+            }
+        }
+
+        let (battery_level, charging) = match battery {
+            Some(battery) => {
+                let current: id =
+                    msg_send![battery, objectForKey: *to_nsstring("Current Capacity")];
+                let max: id = msg_send![battery, objectForKey: *to_nsstring("Max Capacity")];
+                let charging: id = msg_send![battery, objectForKey: *to_nsstring("Is Charging")];
+                let level = if current != nil && max != nil {
+                    let current: f64 = msg_send![current, doubleValue];
+                    let max: f64 = msg_send![max, doubleValue];
+                    if max > 0.0 {
+                        Some((current / max).min(1.0))
+                    } else {
+                        None
+                    }
+                } else {
+                    None
+                };
+                let charging: BOOL = if charging != nil {
+                    msg_send![charging, boolValue]
+                } else {
+                    NO
+                };
+                (level, charging == YES)
+            }
+            None => (None, false),
+        };
+        if !list.is_null() {
+            CFRelease(list as CFTypeRef);
+        }
+
+        PowerStatus {
+            power_source,
+            has_battery: battery.is_some(),
+            battery_level,
+            charging,
+            low_power_mode: Self::low_power_mode(),
+        }
+    }
+
+    // Only available on macOS 12 and later
+    unsafe fn low_power_mode() -> bool {
+        let process_info = NSProcessInfo::processInfo(nil);
+        let responds: BOOL =
+            msg_send![process_info, respondsToSelector: sel!(isLowPowerModeEnabled)];
+        if responds != YES {
+            return false;
+        }
+        let enabled: BOOL = msg_send![process_info, isLowPowerModeEnabled];
+        enabled == YES
+    }
+}
+
+impl Drop for PlatformPowerSource {
+    fn drop(&mut self) {
+        CFRunLoop::get_main()
+            .remove_source(&self.run_loop_source, unsafe { kCFRunLoopCommonModes });
+        unsafe {
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let () = msg_send![center, removeObserver: *self.low_power_observer];
+            drop(Box::from_raw(self.context));
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use cocoa::{
+    appkit::{NSEvent, NSEventModifierFlags, NSEventType, NSView},
+    base::{id, nil},
+    foundation::{NSInteger, NSPoint},
+};
+use objc::rc::StrongPtr;
+
+use crate::shell::{
+    api_model::{KeyboardModifiers, PointerState},
+    Point,
+};
+
+use NSEventType::{
+    NSFlagsChanged, NSKeyDown, NSKeyUp, NSLeftMouseDown, NSLeftMouseDragged, NSLeftMouseUp,
+    NSMouseEntered, NSMouseExited, NSMouseMoved, NSOtherMouseDown, NSOtherMouseDragged,
+    NSOtherMouseUp, NSRightMouseDown, NSRightMouseDragged, NSRightMouseUp,
+};
+
+const LEFT_MOUSE_BUTTON: NSInteger = 0;
+const RIGHT_MOUSE_BUTTON: NSInteger = 1;
+
+// Tracks recent input of a window. Only the last mouse down event per button
+// and the last mouse event are retained; These are needed to start window drags
+// and drag sessions and to synthesize mouse events.
+pub(super) struct RecentInput {
+    state: RefCell<State>,
+}
+
+struct State {
+    mouse_down: HashMap<NSInteger, StrongPtr>,
+    // Bit per button number
+    pressed_buttons: u64,
+    last_mouse_event: Option<StrongPtr>,
+    // In window coordinates
+    location: Option<NSPoint>,
+    modifier_flags: NSEventModifierFlags,
+}
+
+impl RecentInput {
+    pub fn new() -> Self {
+        Self {
+            state: RefCell::new(State {
+                mouse_down: HashMap::new(),
+                pressed_buttons: 0,
+                last_mouse_event: None,
+                location: None,
+                modifier_flags: NSEventModifierFlags::empty(),
+            }),
+        }
+    }
+
+    pub fn record_event(&self, event: id) {
+        let mut state = self.state.borrow_mut();
+        unsafe {
+            match event.eventType() {
+                NSLeftMouseDown | NSRightMouseDown | NSOtherMouseDown => {
+                    let button = event.buttonNumber();
+                    state.pressed_buttons |= Self::button_mask(button);
+                    state.mouse_down.insert(button, StrongPtr::retain(event));
+                }
+                NSLeftMouseUp | NSRightMouseUp | NSOtherMouseUp => {
+                    state.pressed_buttons &= !Self::button_mask(event.buttonNumber());
+                }
+                NSMouseMoved | NSMouseEntered | NSMouseExited | NSLeftMouseDragged
+                | NSRightMouseDragged | NSOtherMouseDragged => {}
+                NSKeyDown | NSKeyUp | NSFlagsChanged => {
+                    state.modifier_flags = event.modifierFlags();
+                    return;
+                }
+                _ => return,
+            }
+            state.location = Some(event.locationInWindow());
+            state.modifier_flags = event.modifierFlags();
+            state.last_mouse_event = Some(StrongPtr::retain(event));
+        }
+    }
+
+    // Releases retained events; Called when window is closing
+    pub fn clear(&self) {
+        let mut state = self.state.borrow_mut();
+        state.mouse_down.clear();
+        state.pressed_buttons = 0;
+        state.last_mouse_event = None;
+        state.location = None;
+    }
+
+    pub fn last_left_mouse_down(&self) -> Option<StrongPtr> {
+        self.state
+            .borrow()
+            .mouse_down
+            .get(&LEFT_MOUSE_BUTTON)
+            .cloned()
+    }
+
+    // Most recent mouse down of left or right button that is still pressed
+    pub fn pressed_mouse_down(&self) -> Option<StrongPtr> {
+        let state = self.state.borrow();
+        [LEFT_MOUSE_BUTTON, RIGHT_MOUSE_BUTTON]
+            .iter()
+            .filter(|button| state.pressed_buttons & Self::button_mask(**button) != 0)
+            .filter_map(|button| state.mouse_down.get(button))
+            .max_by_key(|event| unsafe { event.eventNumber() })
+            .cloned()
+    }
+
+    pub fn last_mouse_event(&self) -> Option<StrongPtr> {
+        self.state.borrow().last_mouse_event.clone()
+    }
+
+    pub fn pointer_state(&self, view: id) -> PointerState {
+        let state = self.state.borrow();
+        let position = state.location.map(|location| unsafe {
+            let location: NSPoint = msg_send![view, convertPoint: location fromView: nil];
+            let height = NSView::frame(view).size.height;
+            Point::xy(location.x, height - location.y)
+        });
+        let flags = state.modifier_flags;
+        PointerState {
+            position,
+            buttons: state.pressed_buttons as i64,
+            modifiers: KeyboardModifiers {
+                shift: flags.contains(NSEventModifierFlags::NSShiftKeyMask),
+                control: flags.contains(NSEventModifierFlags::NSControlKeyMask),
+                alt: flags.contains(NSEventModifierFlags::NSAlternateKeyMask),
+                meta: flags.contains(NSEventModifierFlags::NSCommandKeyMask),
+            },
+        }
+    }
+
+    fn button_mask(button: NSInteger) -> u64 {
+        if (0..64).contains(&button) {
+            1 << button
+        } else {
+            0
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::rc::Rc;
+
+use block::ConcreteBlock;
+use cocoa::{
+    appkit::{NSView, NSWindow},
+    base::{id, nil, YES},
+    foundation::NSSize,
+};
+use objc::rc::StrongPtr;
+
+use crate::shell::{RenderLayerPosition, RenderLayerResizeCallback};
+
+use super::{
+    error::{PlatformError, PlatformResult},
+    window::PlatformWindow,
+};
+
+#[link(name = "AppKit", kind = "framework")]
+extern "C" {
+    static NSViewFrameDidChangeNotification: id;
+}
+
+const NS_VIEW_WIDTH_SIZABLE: u64 = 2;
+const NS_VIEW_HEIGHT_SIZABLE: u64 = 16;
+const NS_WINDOW_BELOW: i64 = -1;
+
+// Layer hosting view with CAMetalLayer that is resized with window content view
+pub struct PlatformRenderLayer {
+    view: StrongPtr,
+    layer: StrongPtr,
+    observer: StrongPtr,
+}
+
+impl PlatformRenderLayer {
+    pub fn new(
+        window: Rc<PlatformWindow>,
+        position: RenderLayerPosition,
+        on_resize: Box<RenderLayerResizeCallback>,
+    ) -> PlatformResult<Self> {
+        unsafe {
+            let layer: id = msg_send![class!(CAMetalLayer), layer];
+            if layer == nil {
+                return Err(PlatformError::NotAvailable);
+            }
+            let layer = StrongPtr::retain(layer);
+
+            let content_view = window.get_platform_window().contentView();
+            let view: id = msg_send![class!(NSView), alloc];
+            let view = StrongPtr::new(view.initWithFrame_(NSView::frame(content_view)));
+            // Layer must be set before wantsLayer to make the view layer hosting
+            let () = msg_send![*view, setLayer: *layer];
+            view.setWantsLayer(YES);
+            let () = msg_send![*view,
+                setAutoresizingMask: NS_VIEW_WIDTH_SIZABLE | NS_VIEW_HEIGHT_SIZABLE
+            ];
+            let () = msg_send![*view, setPostsFrameChangedNotifications: YES];
+
+            match position {
+                // Subviews of flutter view are drawn above flutter content
+                RenderLayerPosition::AboveContent => {
+                    let () = msg_send![*view, setFrame: content_view.bounds()];
+                    content_view.addSubview_(*view);
+                }
+                // Sibling of flutter view in window frame view
+                RenderLayerPosition::BelowContent => {
+                    let superview: id = msg_send![content_view, superview];
+                    let () = msg_send![superview,
+                        addSubview: *view
+                        positioned: NS_WINDOW_BELOW
+                        relativeTo: content_view
+                    ];
+                }
+            }
+
+            let view_ptr = *view;
+            let layer_ptr = *layer;
+            let update_size = move || {
+                let bounds = view_ptr.bounds();
+                let window: id = msg_send![view_ptr, window];
+                let scale = if window != nil {
+                    window.backingScaleFactor()
+                } else {
+                    1.0
+                };
+                let size = NSSize::new(bounds.size.width * scale, bounds.size.height * scale);
+                let () = msg_send![layer_ptr, setContentsScale: scale];
+                let () = msg_send![layer_ptr, setDrawableSize: size];
+                on_resize(size.width as usize, size.height as usize, scale);
+            };
+            update_size();
+
+            // Posted synchronously during live resize
+            let handler = ConcreteBlock::new(move |_notification: id| update_size());
+            let handler = handler.copy();
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let observer: id = msg_send![center,
+                addObserverForName: NSViewFrameDidChangeNotification
+                object: *view
+                queue: nil
+                usingBlock: &*handler
+            ];
+
+            Ok(Self {
+                view,
+                layer,
+                observer: StrongPtr::retain(observer),
+            })
+        }
+    }
+
+    pub fn native_handle(&self) -> usize {
+        *self.layer as usize
+    }
+}
+
+impl Drop for PlatformRenderLayer {
+    fn drop(&mut self) {
+        unsafe {
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let () = msg_send![center, removeObserver: *self.observer];
+            let () = msg_send![*self.view, removeFromSuperview];
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use std::{
     cell::{Cell, RefCell},
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
+    ffi::c_void,
+    os::unix::io::RawFd,
     rc::Rc,
     time::Duration,
 };
@@ -8,41 +10,136 @@ use std::{
 use block::ConcreteBlock;
 use cocoa::{
     appkit::{
-        NSApplication, NSApplicationActivationPolicy::NSApplicationActivationPolicyRegular,
+        NSApplication,
+        NSApplicationActivationPolicy::{
+            NSApplicationActivationPolicyProhibited, NSApplicationActivationPolicyRegular,
+        },
         NSEventType::NSApplicationDefined,
     },
     base::{id, nil, YES},
-    foundation::{NSPoint, NSRunLoop},
+    foundation::{NSInteger, NSPoint, NSRunLoop},
+};
+use core_foundation::{
+    base::{kCFAllocatorDefault, CFIndex, CFOptionFlags, TCFType},
+    filedescriptor::{
+        kCFFileDescriptorReadCallBack, CFFileDescriptor, CFFileDescriptorContext,
+        CFFileDescriptorEnableCallBacks, CFFileDescriptorRef,
+    },
+    runloop::{
+        kCFRunLoopBeforeWaiting, kCFRunLoopCommonModes, CFRunLoop, CFRunLoopActivity,
+        CFRunLoopObserver, CFRunLoopObserverContext, CFRunLoopObserverCreate,
+        CFRunLoopObserverInvalidate, CFRunLoopObserverRef, CFRunLoopSource, CFRunLoopWakeUp,
+    },
 };
-
 use dispatch::ffi::{
     dispatch_after_f, dispatch_async_f, dispatch_get_main_queue, dispatch_time, DISPATCH_TIME_NOW,
 };
 
+use crate::shell::TaskPriority;
+
 pub type HandleType = usize;
 pub const INVALID_HANDLE: HandleType = 0;
 
+// File descriptor; Callback is invoked when it becomes readable. Other
+// CFRunLoopSources (i.e. for mach ports) can be added directly to main CFRunLoop
+// in kCFRunLoopCommonModes.
+pub type EventSourceType = RawFd;
+
 type Callback = Box<dyn FnOnce()>;
 
 pub struct PlatformRunLoop {
     next_handle: Cell<HandleType>,
     callbacks: Rc<RefCell<HashMap<usize, Callback>>>,
+    event_sources: RefCell<HashMap<HandleType, (CFFileDescriptor, CFRunLoopSource)>>,
+    idle_state: Rc<IdleState>,
+    idle_observer: CFRunLoopObserver,
+}
+
+struct IdleState {
+    callbacks: Rc<RefCell<HashMap<usize, Callback>>>,
+    // Handles of idle callbacks that are due
+    pending: RefCell<VecDeque<HandleType>>,
+}
+
+impl IdleState {
+    // Invoked before run loop goes to sleep; Runs one idle callback and if there are
+    // more wakes up the run loop so that pending events are processed before next one.
+    fn run_next(&self) {
+        loop {
+            let handle = self.pending.borrow_mut().pop_front();
+            let handle = match handle {
+                Some(handle) => handle,
+                None => return,
+            };
+            let callback = self.callbacks.borrow_mut().remove(&handle);
+            if let Some(callback) = callback {
+                callback();
+                break;
+            }
+        }
+        if !self.pending.borrow().is_empty() {
+            unsafe { CFRunLoopWakeUp(CFRunLoop::get_main().as_concrete_TypeRef()) };
+        }
+    }
+}
+
+struct EventSourceData {
+    callback: RefCell<Box<dyn FnMut()>>,
 }
 
 struct CallbackData {
     handle: HandleType,
     callbacks: Rc<RefCell<HashMap<usize, Callback>>>,
+    // Set for idle callbacks, which are only queued when due
+    idle_state: Option<Rc<IdleState>>,
 }
 
 #[allow(unused_variables)]
 impl PlatformRunLoop {
     pub fn new() -> Self {
+        let callbacks = Rc::new(RefCell::new(HashMap::new()));
+        let idle_state = Rc::new(IdleState {
+            callbacks: callbacks.clone(),
+            pending: RefCell::new(VecDeque::new()),
+        });
+        let idle_observer = unsafe {
+            let mut context = CFRunLoopObserverContext {
+                version: 0,
+                info: Rc::as_ptr(&idle_state) as *mut c_void,
+                retain: None,
+                release: None,
+                copyDescription: None,
+            };
+            // run after other observers (i.e. core animation commit)
+            let observer = CFRunLoopObserver::wrap_under_create_rule(CFRunLoopObserverCreate(
+                kCFAllocatorDefault,
+                kCFRunLoopBeforeWaiting,
+                1,
+                CFIndex::MAX,
+                Self::on_idle,
+                &mut context,
+            ));
+            CFRunLoop::get_main().add_observer(&observer, kCFRunLoopCommonModes);
+            observer
+        };
         Self {
             next_handle: Cell::new(INVALID_HANDLE + 1),
-            callbacks: Rc::new(RefCell::new(HashMap::new())),
+            callbacks,
+            event_sources: RefCell::new(HashMap::new()),
+            idle_state,
+            idle_observer,
         }
     }
 
+    extern "C" fn on_idle(
+        _observer: CFRunLoopObserverRef,
+        _activity: CFRunLoopActivity,
+        info: *mut c_void,
+    ) {
+        let state = unsafe { &*(info as *const IdleState) };
+        state.run_next();
+    }
+
     fn next_handle(&self) -> HandleType {
         let r = self.next_handle.get();
         self.next_handle.replace(r + 1);
@@ -53,7 +150,9 @@ impl PlatformRunLoop {
         self.callbacks.borrow_mut().remove(&handle);
     }
 
-    pub fn schedule<F>(&self, in_time: Duration, callback: F) -> HandleType
+    // Main dispatch queue has no priorities; UserInteractive and Default callbacks
+    // are treated equally.
+    pub fn schedule<F>(&self, in_time: Duration, priority: TaskPriority, callback: F) -> HandleType
     where
         F: FnOnce() + 'static,
     {
@@ -62,14 +161,18 @@ impl PlatformRunLoop {
             .borrow_mut()
             .insert(handle, Box::new(callback));
 
+        let idle = priority == TaskPriority::Idle;
         let data = Box::new(CallbackData {
             handle,
             callbacks: self.callbacks.clone(),
+            idle_state: idle.then(|| self.idle_state.clone()),
         });
 
         let delta = in_time.as_nanos() as i64;
         unsafe {
-            if delta > 0 {
+            if idle && delta <= 0 {
+                self.idle_state.pending.borrow_mut().push_back(handle);
+            } else if delta > 0 {
                 dispatch_after_f(
                     dispatch_time(DISPATCH_TIME_NOW, delta),
                     dispatch_get_main_queue(),
@@ -96,17 +199,100 @@ impl PlatformRunLoop {
 
     extern "C" fn on_callback(user_data: *mut ::std::os::raw::c_void) {
         let data: Box<CallbackData> = unsafe { Box::from_raw(user_data as *mut _) };
+        if let Some(idle_state) = data.idle_state {
+            idle_state.pending.borrow_mut().push_back(data.handle);
+            return;
+        }
         let entry = data.callbacks.borrow_mut().remove(&data.handle);
         if let Some(entry) = entry {
             entry();
         }
     }
 
+    pub fn add_event_source<F>(&self, source: EventSourceType, callback: F) -> HandleType
+    where
+        F: FnMut() + 'static,
+    {
+        let data = Rc::new(EventSourceData {
+            callback: RefCell::new(Box::new(callback)),
+        });
+        let context = CFFileDescriptorContext {
+            version: 0,
+            info: Rc::into_raw(data) as *mut c_void,
+            retain: None,
+            release: Some(Self::on_event_source_release),
+            copyDescription: None,
+        };
+        // context is released by CFFileDescriptor (including failure)
+        let descriptor =
+            CFFileDescriptor::new(source, false, Self::on_event_source, Some(&context));
+        let descriptor = match descriptor {
+            Some(descriptor) => descriptor,
+            None => return INVALID_HANDLE,
+        };
+        let run_loop_source = match descriptor.to_run_loop_source(0) {
+            Some(source) => source,
+            None => {
+                descriptor.invalidate();
+                return INVALID_HANDLE;
+            }
+        };
+        descriptor.enable_callbacks(kCFFileDescriptorReadCallBack);
+        unsafe {
+            CFRunLoop::get_main().add_source(&run_loop_source, kCFRunLoopCommonModes);
+        }
+        let handle = self.next_handle();
+        self.event_sources
+            .borrow_mut()
+            .insert(handle, (descriptor, run_loop_source));
+        handle
+    }
+
+    pub fn remove_event_source(&self, handle: HandleType) {
+        let source = self.event_sources.borrow_mut().remove(&handle);
+        if let Some((descriptor, run_loop_source)) = source {
+            descriptor.invalidate();
+            unsafe {
+                CFRunLoop::get_main().remove_source(&run_loop_source, kCFRunLoopCommonModes);
+            }
+        }
+    }
+
+    extern "C" fn on_event_source(
+        descriptor: CFFileDescriptorRef,
+        _callback_types: CFOptionFlags,
+        info: *mut c_void,
+    ) {
+        // callbacks are one-shot and must be re-enabled after each invocation
+        unsafe {
+            CFFileDescriptorEnableCallBacks(descriptor, kCFFileDescriptorReadCallBack);
+        }
+        // keep data alive in case the callback removes the event source
+        let data = unsafe {
+            Rc::increment_strong_count(info as *const EventSourceData);
+            Rc::from_raw(info as *const EventSourceData)
+        };
+        let mut callback = data.callback.borrow_mut();
+        callback();
+    }
+
+    extern "C" fn on_event_source_release(info: *const c_void) {
+        unsafe { Rc::from_raw(info as *const EventSourceData) };
+    }
+
     pub fn run(&self) {
         unsafe {
             let app = NSApplication::sharedApplication(nil);
-            NSApplication::setActivationPolicy_(app, NSApplicationActivationPolicyRegular);
-            NSApplication::activateIgnoringOtherApps_(app, YES);
+            // Unbundled executables start with prohibited policy; Policy set by
+            // application before running is preserved
+            let policy: NSInteger = msg_send![app, activationPolicy];
+            if policy == NSApplicationActivationPolicyProhibited as NSInteger {
+                NSApplication::setActivationPolicy_(app, NSApplicationActivationPolicyRegular);
+            }
+            let policy: NSInteger = msg_send![app, activationPolicy];
+            if policy == NSApplicationActivationPolicyRegular as NSInteger {
+                NSApplication::activateIgnoringOtherApps_(app, YES);
+            }
             NSApplication::run(app);
         }
     }
@@ -138,6 +324,12 @@ impl PlatformRunLoop {
     }
 }
 
+impl Drop for PlatformRunLoop {
+    fn drop(&mut self) {
+        unsafe { CFRunLoopObserverInvalidate(self.idle_observer.as_concrete_TypeRef()) };
+    }
+}
+
 pub struct PlatformRunLoopSender {}
 
 struct SenderCallbackData {
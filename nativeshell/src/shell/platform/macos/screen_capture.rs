@@ -0,0 +1,170 @@
+use std::{
+    ffi::c_void,
+    os::raw::{c_char, c_double},
+    ptr, slice,
+    sync::Arc,
+};
+
+use block::{Block, ConcreteBlock};
+use core_foundation::{
+    base::{CFRelease, CFTypeRef, TCFType},
+    boolean::CFBoolean,
+    dictionary::{CFDictionary, CFDictionaryRef},
+    number::CFNumber,
+    string::{CFString, CFStringRef},
+};
+use core_graphics::display::{CGDirectDisplayID, CGDisplay};
+
+use crate::shell::{
+    ScreenCaptureFrame, ScreenCaptureOptions, ScreenCaptureSink, ScreenCaptureSource,
+};
+
+use super::error::{PlatformError, PlatformResult};
+
+type CGDisplayStreamRef = CFTypeRef;
+type IOSurfaceRef = CFTypeRef;
+type DispatchQueue = *mut c_void;
+type FrameHandler = Block<(i32, u64, IOSurfaceRef, CFTypeRef), ()>;
+
+const K_CG_DISPLAY_STREAM_FRAME_STATUS_FRAME_COMPLETE: i32 = 0;
+const K_PIXEL_FORMAT_BGRA: i32 = 0x42475241; // 'BGRA'
+const K_IO_SURFACE_LOCK_READ_ONLY: u32 = 1;
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    static kCGDisplayStreamMinimumFrameTime: CFStringRef;
+    static kCGDisplayStreamShowCursor: CFStringRef;
+
+    fn CGDisplayStreamCreateWithDispatchQueue(
+        display: CGDirectDisplayID,
+        output_width: usize,
+        output_height: usize,
+        pixel_format: i32,
+        properties: CFDictionaryRef,
+        queue: DispatchQueue,
+        handler: *const c_void,
+    ) -> CGDisplayStreamRef;
+    fn CGDisplayStreamStart(stream: CGDisplayStreamRef) -> i32;
+    fn CGDisplayStreamStop(stream: CGDisplayStreamRef) -> i32;
+}
+
+#[link(name = "IOSurface", kind = "framework")]
+extern "C" {
+    fn IOSurfaceLock(surface: IOSurfaceRef, options: u32, seed: *mut u32) -> i32;
+    fn IOSurfaceUnlock(surface: IOSurfaceRef, options: u32, seed: *mut u32) -> i32;
+    fn IOSurfaceGetBaseAddress(surface: IOSurfaceRef) -> *const c_void;
+    fn IOSurfaceGetBytesPerRow(surface: IOSurfaceRef) -> usize;
+    fn IOSurfaceGetWidth(surface: IOSurfaceRef) -> usize;
+    fn IOSurfaceGetHeight(surface: IOSurfaceRef) -> usize;
+}
+
+extern "C" {
+    fn dispatch_queue_create(label: *const c_char, attr: *mut c_void) -> DispatchQueue;
+    fn dispatch_release(object: DispatchQueue);
+}
+
+// CGDisplayStream delivering frames on a serial dispatch queue
+pub struct PlatformScreenCapture {
+    stream: CGDisplayStreamRef,
+    queue: DispatchQueue,
+}
+
+impl PlatformScreenCapture {
+    pub fn new(
+        source: ScreenCaptureSource,
+        options: ScreenCaptureOptions,
+        sink: Arc<ScreenCaptureSink>,
+    ) -> PlatformResult<Self> {
+        let ScreenCaptureSource::Display(index) = source;
+        // First display in the list is the main display
+        let display = CGDisplay::active_displays()
+            .ok()
+            .and_then(|displays| displays.get(index).cloned())
+            .map(CGDisplay::new)
+            .ok_or(PlatformError::NotAvailable)?;
+        let mode = display.display_mode().ok_or(PlatformError::NotAvailable)?;
+
+        let properties = unsafe {
+            CFDictionary::from_CFType_pairs(&[
+                (
+                    CFString::wrap_under_get_rule(kCGDisplayStreamMinimumFrameTime).as_CFType(),
+                    CFNumber::from(1.0 as c_double / options.max_frame_rate.max(1) as c_double)
+                        .as_CFType(),
+                ),
+                (
+                    CFString::wrap_under_get_rule(kCGDisplayStreamShowCursor).as_CFType(),
+                    CFBoolean::from(options.show_cursor).as_CFType(),
+                ),
+            ])
+        };
+
+        let handler = ConcreteBlock::new(
+            move |status: i32, _time: u64, surface: IOSurfaceRef, _update: CFTypeRef| {
+                if status != K_CG_DISPLAY_STREAM_FRAME_STATUS_FRAME_COMPLETE || surface.is_null() {
+                    return;
+                }
+                unsafe {
+                    IOSurfaceLock(surface, K_IO_SURFACE_LOCK_READ_ONLY, ptr::null_mut());
+                    let height = IOSurfaceGetHeight(surface);
+                    let bytes_per_row = IOSurfaceGetBytesPerRow(surface);
+                    let data = slice::from_raw_parts(
+                        IOSurfaceGetBaseAddress(surface) as *const u8,
+                        bytes_per_row * height,
+                    );
+                    sink(&ScreenCaptureFrame {
+                        width: IOSurfaceGetWidth(surface),
+                        height,
+                        bytes_per_row,
+                        data,
+                    });
+                    IOSurfaceUnlock(surface, K_IO_SURFACE_LOCK_READ_ONLY, ptr::null_mut());
+                }
+            },
+        );
+        let handler = handler.copy();
+
+        unsafe {
+            let queue = dispatch_queue_create(
+                b"nativeshell.screen_capture\0".as_ptr() as *const c_char,
+                ptr::null_mut(),
+            );
+            let stream = CGDisplayStreamCreateWithDispatchQueue(
+                display.id,
+                mode.pixel_width() as usize,
+                mode.pixel_height() as usize,
+                K_PIXEL_FORMAT_BGRA,
+                properties.as_concrete_TypeRef(),
+                queue,
+                &*handler as *const FrameHandler as *const c_void,
+            );
+            if stream.is_null() {
+                dispatch_release(queue);
+                return Err(PlatformError::NotAvailable);
+            }
+            Ok(Self { stream, queue })
+        }
+    }
+
+    // Fails when the application has no screen recording permission
+    pub fn start(&self) -> PlatformResult<()> {
+        match unsafe { CGDisplayStreamStart(self.stream) } {
+            0 => Ok(()),
+            _ => Err(PlatformError::UnknownError),
+        }
+    }
+
+    pub fn stop(&self) -> PlatformResult<()> {
+        unsafe { CGDisplayStreamStop(self.stream) };
+        Ok(())
+    }
+}
+
+impl Drop for PlatformScreenCapture {
+    fn drop(&mut self) {
+        unsafe {
+            CGDisplayStreamStop(self.stream);
+            CFRelease(self.stream);
+            dispatch_release(self.queue);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use std::{
+    fs,
+    io::{Read, Write},
+    os::unix::{
+        io::AsRawFd,
+        net::{UnixListener, UnixStream},
+    },
+    path::PathBuf,
+    rc::Rc,
+    time::Duration,
+};
+
+use cocoa::{
+    base::{id, nil},
+    foundation::{NSArray, NSUInteger},
+};
+use log::warn;
+
+use crate::shell::{api_model::InstanceActivation, Context, EventSource};
+
+use super::error::{PlatformError, PlatformResult};
+
+// First instance listens on unix socket in (per user) temporary directory;
+// Later instances send their activation through the socket
+pub struct PlatformSingleInstance {
+    path: PathBuf,
+    _event_source: EventSource,
+}
+
+impl PlatformSingleInstance {
+    // Returns None if another instance is running (activation was forwarded to it)
+    pub fn acquire(
+        context: &Rc<Context>,
+        id: &str,
+        activation: &InstanceActivation,
+        on_activation: Box<dyn Fn(InstanceActivation)>,
+    ) -> PlatformResult<Option<Self>> {
+        let path = std::env::temp_dir().join(format!("{}.instance", id));
+        if let Ok(mut stream) = UnixStream::connect(&path) {
+            let data = serde_json::to_vec(activation).unwrap();
+            stream
+                .write_all(&data)
+                .map_err(|_| PlatformError::UnknownError)?;
+            activate_other_instance();
+            return Ok(None);
+        }
+
+        // Stale socket left behind by instance that did not exit cleanly
+        fs::remove_file(&path).ok();
+        let listener = UnixListener::bind(&path).map_err(|_| PlatformError::UnknownError)?;
+        listener
+            .set_nonblocking(true)
+            .map_err(|_| PlatformError::UnknownError)?;
+        let fd = listener.as_raw_fd();
+        let event_source = context.run_loop.borrow().add_event_source(fd, move || {
+            while let Ok((stream, _)) = listener.accept() {
+                match read_activation(stream) {
+                    Some(activation) => on_activation(activation),
+                    None => warn!("Received malformed instance activation"),
+                }
+            }
+        });
+        Ok(Some(Self {
+            path,
+            _event_source: event_source,
+        }))
+    }
+}
+
+// Running instance can't reliably activate itself when launched from another
+// application, so activate it from here
+fn activate_other_instance() {
+    unsafe {
+        let bundle: id = msg_send![class!(NSBundle), mainBundle];
+        let identifier: id = msg_send![bundle, bundleIdentifier];
+        if identifier == nil {
+            return;
+        }
+        let current: id = msg_send![class!(NSRunningApplication), currentApplication];
+        let current_pid: i32 = msg_send![current, processIdentifier];
+        let applications: id = msg_send![
+            class!(NSRunningApplication),
+            runningApplicationsWithBundleIdentifier: identifier
+        ];
+        for i in 0..NSArray::count(applications) {
+            let application = NSArray::objectAtIndex(applications, i);
+            let pid: i32 = msg_send![application, processIdentifier];
+            if pid != current_pid {
+                // NSApplicationActivateIgnoringOtherApps
+                let options: NSUInteger = 1 << 1;
+                let () = msg_send![application, activateWithOptions: options];
+            }
+        }
+    }
+}
+
+fn read_activation(mut stream: UnixStream) -> Option<InstanceActivation> {
+    // Other instance closes the stream right after writing activation
+    stream.set_nonblocking(false).ok()?;
+    stream.set_read_timeout(Some(Duration::from_secs(1))).ok()?;
+    let mut data = Vec::new();
+    stream.read_to_end(&mut data).ok()?;
+    serde_json::from_slice(&data).ok()
+}
+
+impl Drop for PlatformSingleInstance {
+    fn drop(&mut self) {
+        fs::remove_file(&self.path).ok();
+    }
+}
@@ -0,0 +1,128 @@
+use std::rc::{Rc, Weak};
+
+use block::ConcreteBlock;
+use cocoa::{
+    appkit::CGFloat,
+    base::{id, nil, BOOL, YES},
+};
+use objc::rc::StrongPtr;
+
+use crate::shell::{api_model::SystemThemeSettings, Context};
+
+use super::{
+    error::PlatformResult,
+    utils::{from_nsstring, to_nsstring},
+};
+
+#[link(name = "AppKit", kind = "framework")]
+extern "C" {
+    static NSSystemColorsDidChangeNotification: id;
+}
+
+pub struct PlatformSystemTheme {
+    distributed_observer: StrongPtr,
+    colors_observer: StrongPtr,
+}
+
+impl PlatformSystemTheme {
+    pub fn new(context: Rc<Context>) -> Self {
+        unsafe {
+            // Posted when switching between light and dark appearance
+            let distributed_center: id =
+                msg_send![class!(NSDistributedNotificationCenter), defaultCenter];
+            let distributed_observer = Self::observe(
+                distributed_center,
+                *to_nsstring("AppleInterfaceThemeChangedNotification"),
+                Rc::downgrade(&context),
+            );
+
+            // Posted when accent or highlight color changes
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let colors_observer = Self::observe(
+                center,
+                NSSystemColorsDidChangeNotification,
+                Rc::downgrade(&context),
+            );
+
+            Self {
+                distributed_observer,
+                colors_observer,
+            }
+        }
+    }
+
+    unsafe fn observe(center: id, name: id, context: Weak<Context>) -> StrongPtr {
+        let block = ConcreteBlock::new(move |_notification: id| {
+            if let Some(context) = context.upgrade() {
+                context.system_theme_manager.borrow().settings_changed();
+            }
+        });
+        let block = block.copy();
+        let observer: id = msg_send![center,
+            addObserverForName: name
+            object: nil
+            queue: nil
+            usingBlock: &*block
+        ];
+        StrongPtr::retain(observer)
+    }
+
+    pub fn get_settings(&self) -> PlatformResult<SystemThemeSettings> {
+        unsafe {
+            let dark_mode = Self::dark_mode();
+            Ok(SystemThemeSettings {
+                dark_mode,
+                accent_color: Self::accent_color(),
+                highlight_color: Self::color_value(msg_send![
+                    class!(NSColor),
+                    selectedTextBackgroundColor
+                ]),
+                high_contrast_theme: None,
+                system_dark_mode: dark_mode,
+                system_colors: None,
+            })
+        }
+    }
+
+    // System setting, regardless of appearance of this application
+    unsafe fn dark_mode() -> bool {
+        let defaults: id = msg_send![class!(NSUserDefaults), standardUserDefaults];
+        let style: id = msg_send![defaults, stringForKey: *to_nsstring("AppleInterfaceStyle")];
+        style != nil && from_nsstring(style).eq_ignore_ascii_case("dark")
+    }
+
+    // Only available on macOS 10.14 and later
+    unsafe fn accent_color() -> Option<u32> {
+        let responds: BOOL =
+            msg_send![class!(NSColor), respondsToSelector: sel!(controlAccentColor)];
+        if responds != YES {
+            return None;
+        }
+        Self::color_value(msg_send![class!(NSColor), controlAccentColor])
+    }
+
+    unsafe fn color_value(color: id) -> Option<u32> {
+        let color_space: id = msg_send![class!(NSColorSpace), sRGBColorSpace];
+        let color: id = msg_send![color, colorUsingColorSpace: color_space];
+        if color == nil {
+            return None;
+        }
+        let (mut r, mut g, mut b, mut a): (CGFloat, CGFloat, CGFloat, CGFloat) =
+            (0.0, 0.0, 0.0, 0.0);
+        let () = msg_send![color, getRed: &mut r green: &mut g blue: &mut b alpha: &mut a];
+        let component = |c: CGFloat| (c.clamp(0.0, 1.0) * 255.0).round() as u32;
+        Some(component(a) << 24 | component(r) << 16 | component(g) << 8 | component(b))
+    }
+}
+
+impl Drop for PlatformSystemTheme {
+    fn drop(&mut self) {
+        unsafe {
+            let distributed_center: id =
+                msg_send![class!(NSDistributedNotificationCenter), defaultCenter];
+            let () = msg_send![distributed_center, removeObserver: *self.distributed_observer];
+            let center: id = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let () = msg_send![center, removeObserver: *self.colors_observer];
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use cocoa::{
+    appkit::{NSEvent, NSEventType, NSView},
+    base::{id, nil, BOOL, YES},
+    foundation::{NSPoint, NSUInteger},
+};
+
+use crate::shell::{
+    api_model::{TabletEvent, TabletEventType, TabletPointerType},
+    Point,
+};
+
+use NSEventType::{
+    NSLeftMouseDown, NSLeftMouseDragged, NSLeftMouseUp, NSMouseMoved, NSTabletPoint,
+    NSTabletProximity,
+};
+
+// NSEventSubtype
+const NS_TABLET_POINT_EVENT_SUBTYPE: i16 = 1;
+
+// NSPointingDeviceType
+const NS_PEN_POINTING_DEVICE: NSUInteger = 1;
+const NS_CURSOR_POINTING_DEVICE: NSUInteger = 2;
+const NS_ERASER_POINTING_DEVICE: NSUInteger = 3;
+
+// NSEventButtonMask
+const NS_PEN_LOWER_SIDE_MASK: NSUInteger = 2;
+const NS_PEN_UPPER_SIDE_MASK: NSUInteger = 4;
+
+// Converts tablet events delivered to window; Pointing device type is only
+// available in proximity events so it needs to be remembered for subsequent
+// point events.
+pub struct PlatformTablet {
+    pointer_types: RefCell<HashMap<NSUInteger, TabletPointerType>>,
+}
+
+impl PlatformTablet {
+    pub fn new() -> Self {
+        Self {
+            pointer_types: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn convert_event(&self, event: id, view: id) -> Option<TabletEvent> {
+        unsafe {
+            let event_type = event.eventType();
+            if event_type == NSTabletProximity {
+                return Some(self.convert_proximity_event(event));
+            }
+
+            let is_tablet_event = event_type == NSTabletPoint || {
+                let subtype: i16 = msg_send![event, subtype];
+                subtype == NS_TABLET_POINT_EVENT_SUBTYPE
+            };
+            if !is_tablet_event {
+                return None;
+            }
+
+            let event_type = match event_type {
+                NSLeftMouseDown => TabletEventType::Down,
+                NSLeftMouseUp => TabletEventType::Up,
+                NSLeftMouseDragged | NSMouseMoved | NSTabletPoint => TabletEventType::Move,
+                _ => return None,
+            };
+
+            let device_id: NSUInteger = msg_send![event, deviceID];
+            let pointer_type = self
+                .pointer_types
+                .borrow()
+                .get(&device_id)
+                .cloned()
+                .unwrap_or(TabletPointerType::Unknown);
+
+            let tilt: NSPoint = msg_send![event, tilt];
+            let rotation: f32 = msg_send![event, rotation];
+            let pressure: f32 = msg_send![event, pressure];
+            let button_mask: NSUInteger = msg_send![event, buttonMask];
+
+            let mut buttons = 0;
+            if button_mask & NS_PEN_LOWER_SIDE_MASK != 0 {
+                buttons |= 1;
+            }
+            if button_mask & NS_PEN_UPPER_SIDE_MASK != 0 {
+                buttons |= 2;
+            }
+
+            Some(TabletEvent {
+                event_type,
+                pointer_type,
+                device_id: device_id as i64,
+                position: Some(Self::position_in_view(event, view)),
+                pressure: pressure as f64,
+                // macOS y axis is flipped
+                tilt: Point::xy(tilt.x, -tilt.y),
+                rotation: rotation as f64,
+                buttons,
+            })
+        }
+    }
+
+    unsafe fn convert_proximity_event(&self, event: id) -> TabletEvent {
+        let device_id: NSUInteger = msg_send![event, deviceID];
+        let device_type: NSUInteger = msg_send![event, pointingDeviceType];
+        let entering: BOOL = msg_send![event, isEnteringProximity];
+
+        let pointer_type = match device_type {
+            NS_PEN_POINTING_DEVICE => TabletPointerType::Pen,
+            NS_CURSOR_POINTING_DEVICE => TabletPointerType::Cursor,
+            NS_ERASER_POINTING_DEVICE => TabletPointerType::Eraser,
+            _ => TabletPointerType::Unknown,
+        };
+
+        let event_type = if entering == YES {
+            self.pointer_types
+                .borrow_mut()
+                .insert(device_id, pointer_type);
+            TabletEventType::ProximityEnter
+        } else {
+            self.pointer_types.borrow_mut().remove(&device_id);
+            TabletEventType::ProximityLeave
+        };
+
+        TabletEvent {
+            event_type,
+            pointer_type,
+            device_id: device_id as i64,
+            position: None,
+            pressure: 0.0,
+            tilt: Point::xy(0.0, 0.0),
+            rotation: 0.0,
+            buttons: 0,
+        }
+    }
+
+    unsafe fn position_in_view(event: id, view: id) -> Point {
+        let location = event.locationInWindow();
+        let location: NSPoint = msg_send![view, convertPoint: location fromView: nil];
+        let height = NSView::frame(view).size.height;
+        Point::xy(location.x, height - location.y)
+    }
+}
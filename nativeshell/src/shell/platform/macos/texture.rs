@@ -0,0 +1,222 @@
+use std::{ffi::c_void, ptr};
+
+use cocoa::base::id;
+use core_foundation::{
+    base::{kCFAllocatorDefault, CFAllocatorRef, CFType, TCFType},
+    boolean::CFBoolean,
+    dictionary::{CFDictionary, CFDictionaryRef},
+    string::{CFString, CFStringRef},
+};
+use objc::{
+    declare::ClassDecl,
+    rc::StrongPtr,
+    runtime::{Class, Object, Sel},
+};
+
+use crate::shell::{GpuSurfaceKind, GpuSurfaceProvider, SharedPixelBuffer};
+
+use super::{
+    engine::PlatformEngine,
+    error::{PlatformError, PlatformResult},
+    utils::superclass,
+};
+
+type CVPixelBufferRef = *mut c_void;
+
+#[allow(non_upper_case_globals)]
+const kCVPixelFormatType_32BGRA: u32 = 0x42475241;
+
+#[link(name = "CoreVideo", kind = "framework")]
+extern "C" {
+    static kCVPixelBufferMetalCompatibilityKey: CFStringRef;
+    static kCVPixelBufferIOSurfacePropertiesKey: CFStringRef;
+
+    fn CVPixelBufferCreateWithIOSurface(
+        allocator: CFAllocatorRef,
+        surface: *mut c_void,
+        pixel_buffer_attributes: CFDictionaryRef,
+        pixel_buffer_out: *mut CVPixelBufferRef,
+    ) -> i32;
+    fn CVPixelBufferCreate(
+        allocator: CFAllocatorRef,
+        width: usize,
+        height: usize,
+        pixel_format_type: u32,
+        pixel_buffer_attributes: CFDictionaryRef,
+        pixel_buffer_out: *mut CVPixelBufferRef,
+    ) -> i32;
+    fn CVPixelBufferLockBaseAddress(pixel_buffer: CVPixelBufferRef, lock_flags: u64) -> i32;
+    fn CVPixelBufferUnlockBaseAddress(pixel_buffer: CVPixelBufferRef, unlock_flags: u64) -> i32;
+    fn CVPixelBufferGetBaseAddress(pixel_buffer: CVPixelBufferRef) -> *mut c_void;
+    fn CVPixelBufferGetBytesPerRow(pixel_buffer: CVPixelBufferRef) -> usize;
+}
+
+pub struct PlatformTexture {
+    engine: StrongPtr,
+    id: i64,
+}
+
+enum TextureState {
+    PixelBuffer(SharedPixelBuffer),
+    GpuSurface(Box<GpuSurfaceProvider>),
+}
+
+impl PlatformTexture {
+    pub fn new(engine: &PlatformEngine, buffer: SharedPixelBuffer) -> PlatformResult<Self> {
+        Self::register(engine, TextureState::PixelBuffer(buffer))
+    }
+
+    pub fn new_gpu_surface(
+        engine: &PlatformEngine,
+        kind: GpuSurfaceKind,
+        provider: Box<GpuSurfaceProvider>,
+    ) -> PlatformResult<Self> {
+        if kind != GpuSurfaceKind::IOSurface {
+            return Err(PlatformError::NotAvailable);
+        }
+        Self::register(engine, TextureState::GpuSurface(provider))
+    }
+
+    fn register(engine: &PlatformEngine, state: TextureState) -> PlatformResult<Self> {
+        unsafe {
+            let texture: id = msg_send![TEXTURE_CLASS.0, new];
+            let texture = StrongPtr::new(texture);
+            let state = Box::new(state);
+            (**texture).set_ivar("imState", Box::into_raw(state) as *mut c_void);
+            // FlutterEngine is FlutterTextureRegistry and retains the texture
+            let id: i64 = msg_send![*engine.handle, registerTexture: *texture];
+            if id == 0 {
+                return Err(PlatformError::UnknownError);
+            }
+            Ok(Self {
+                engine: engine.handle.clone(),
+                id,
+            })
+        }
+    }
+
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    pub fn mark_frame_available(&self) -> PlatformResult<()> {
+        unsafe {
+            let () = msg_send![*self.engine, textureFrameAvailable: self.id];
+        }
+        Ok(())
+    }
+}
+
+impl Drop for PlatformTexture {
+    fn drop(&mut self) {
+        unsafe {
+            let () = msg_send![*self.engine, unregisterTexture: self.id];
+        }
+    }
+}
+
+struct TextureClass(*const Class);
+unsafe impl Sync for TextureClass {}
+
+lazy_static! {
+    static ref TEXTURE_CLASS: TextureClass = unsafe {
+        let texture_superclass = class!(NSObject);
+        let mut decl = ClassDecl::new("IMPixelBufferTexture", texture_superclass).unwrap();
+
+        decl.add_ivar::<*mut c_void>("imState");
+
+        decl.add_method(sel!(dealloc), dealloc as extern "C" fn(&Object, Sel));
+        decl.add_method(
+            sel!(copyPixelBuffer),
+            copy_pixel_buffer as extern "C" fn(&Object, Sel) -> CVPixelBufferRef,
+        );
+
+        TextureClass(decl.register())
+    };
+}
+
+extern "C" fn dealloc(this: &Object, _sel: Sel) {
+    unsafe {
+        let state_ptr: *mut c_void = *this.get_ivar("imState");
+        if !state_ptr.is_null() {
+            drop(Box::from_raw(state_ptr as *mut TextureState));
+        }
+
+        let superclass = superclass(this);
+        let () = msg_send![super(this, superclass), dealloc];
+    }
+}
+
+// Invoked on raster thread; Returned pixel buffer is released by the engine
+extern "C" fn copy_pixel_buffer(this: &Object, _sel: Sel) -> CVPixelBufferRef {
+    let state = unsafe {
+        let state_ptr: *mut c_void = *this.get_ivar("imState");
+        &*(state_ptr as *const TextureState)
+    };
+    let buffer = match state {
+        TextureState::PixelBuffer(buffer) => buffer,
+        TextureState::GpuSurface(provider) => return wrap_surface(provider),
+    };
+    let pixels = buffer.lock().unwrap();
+    if pixels.data.is_empty() {
+        return ptr::null_mut();
+    }
+    unsafe {
+        let attributes = CFDictionary::<CFString, CFType>::from_CFType_pairs(&[
+            (
+                CFString::wrap_under_get_rule(kCVPixelBufferMetalCompatibilityKey),
+                CFBoolean::true_value().as_CFType(),
+            ),
+            (
+                CFString::wrap_under_get_rule(kCVPixelBufferIOSurfacePropertiesKey),
+                CFDictionary::<CFString, CFType>::from_CFType_pairs(&[]).as_CFType(),
+            ),
+        ]);
+        let mut res: CVPixelBufferRef = ptr::null_mut();
+        CVPixelBufferCreate(
+            kCFAllocatorDefault,
+            pixels.width,
+            pixels.height,
+            kCVPixelFormatType_32BGRA,
+            attributes.as_concrete_TypeRef(),
+            &mut res,
+        );
+        if res.is_null() {
+            return res;
+        }
+        CVPixelBufferLockBaseAddress(res, 0);
+        let base = CVPixelBufferGetBaseAddress(res) as *mut u8;
+        let bytes_per_row = CVPixelBufferGetBytesPerRow(res);
+        for (y, src_row) in pixels.data.chunks_exact(pixels.width * 4).enumerate() {
+            let dst_row =
+                std::slice::from_raw_parts_mut(base.add(y * bytes_per_row), src_row.len());
+            // RGBA -> BGRA
+            for (dst, src) in dst_row.chunks_exact_mut(4).zip(src_row.chunks_exact(4)) {
+                dst[0] = src[2];
+                dst[1] = src[1];
+                dst[2] = src[0];
+                dst[3] = src[3];
+            }
+        }
+        CVPixelBufferUnlockBaseAddress(res, 0);
+        res
+    }
+}
+
+// Wraps IOSurface provided by the application without copying pixels
+fn wrap_surface(provider: &GpuSurfaceProvider) -> CVPixelBufferRef {
+    let surface = match provider(0, 0) {
+        Some(surface) => surface,
+        None => return ptr::null_mut(),
+    };
+    let mut res: CVPixelBufferRef = ptr::null_mut();
+    unsafe {
+        CVPixelBufferCreateWithIOSurface(
+            kCFAllocatorDefault,
+            surface.handle as *mut c_void,
+            ptr::null(),
+            &mut res,
+        );
+    }
+    res
+}
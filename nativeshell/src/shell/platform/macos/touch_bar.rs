@@ -0,0 +1,285 @@
+use std::{
+    ffi::c_void,
+    rc::{Rc, Weak},
+};
+
+use cocoa::{
+    base::{id, nil, BOOL, NO, YES},
+    foundation::NSInteger,
+};
+use objc::{
+    declare::ClassDecl,
+    rc::{autoreleasepool, StrongPtr},
+    runtime::{Class, Object, Sel},
+};
+
+use crate::shell::api_model::{TouchBar, TouchBarAction, TouchBarItem, TouchBarItemType};
+
+use super::{
+    utils::{array_with_objects, superclass, to_nsstring},
+    window::PlatformWindow,
+};
+
+const SCRUBBER_ITEM_IDENTIFIER: &str = "IMScrubberTextItem";
+
+pub struct PlatformTouchBar {
+    window: Weak<PlatformWindow>,
+    pub(super) touch_bar: StrongPtr,
+    target: StrongPtr,
+
+    // controls (buttons, sliders, scrubbers) and the items they were created from
+    controls: Vec<(StrongPtr, TouchBarItem)>,
+}
+
+impl PlatformTouchBar {
+    pub fn new(window: Weak<PlatformWindow>, touch_bar: TouchBar) -> Rc<Self> {
+        autoreleasepool(|| unsafe {
+            let target: id = msg_send![TOUCH_BAR_TARGET_CLASS.0, new];
+            let target = StrongPtr::new(target);
+
+            let mut controls = Vec::new();
+            let mut items = Vec::new();
+            let mut identifiers = Vec::new();
+
+            for item in touch_bar.items {
+                let identifier = to_nsstring(&format!("nativeshell.touch-bar.{}", item.id));
+                let (touch_bar_item, control) = Self::create_item(&item, *identifier, *target);
+                items.push(touch_bar_item);
+                identifiers.push(identifier);
+                controls.push((control, item));
+            }
+
+            let ns_touch_bar: id = msg_send![class!(NSTouchBar), new];
+            let ns_touch_bar = StrongPtr::new(ns_touch_bar);
+            let items = array_with_objects(&items);
+            let items: id = msg_send![class!(NSSet), setWithArray: items];
+            let () = msg_send![*ns_touch_bar, setTemplateItems: items];
+            let () = msg_send![*ns_touch_bar, setDefaultItemIdentifiers: array_with_objects(&identifiers)];
+
+            let res = Rc::new(Self {
+                window,
+                touch_bar: ns_touch_bar,
+                target,
+                controls,
+            });
+
+            let state_ptr = Box::into_raw(Box::new(Rc::downgrade(&res))) as *mut c_void;
+            (**res.target).set_ivar("imState", state_ptr);
+
+            res
+        })
+    }
+
+    unsafe fn create_item(
+        item: &TouchBarItem,
+        identifier: id,
+        target: id,
+    ) -> (StrongPtr, StrongPtr) {
+        let enabled: BOOL = if item.enabled { YES } else { NO };
+        match item.item_type {
+            TouchBarItemType::Button => {
+                let touch_bar_item: id = msg_send![class!(NSCustomTouchBarItem), alloc];
+                let touch_bar_item =
+                    StrongPtr::new(msg_send![touch_bar_item, initWithIdentifier: identifier]);
+                let button: id = msg_send![class!(NSButton),
+                    buttonWithTitle: *to_nsstring(&item.title)
+                    target: target
+                    action: sel!(onAction:)
+                ];
+                let () = msg_send![button, setEnabled: enabled];
+                let () = msg_send![*touch_bar_item, setView: button];
+                (touch_bar_item, StrongPtr::retain(button))
+            }
+            TouchBarItemType::Slider => {
+                let touch_bar_item: id = msg_send![class!(NSSliderTouchBarItem), alloc];
+                let touch_bar_item =
+                    StrongPtr::new(msg_send![touch_bar_item, initWithIdentifier: identifier]);
+                let () = msg_send![*touch_bar_item, setLabel: *to_nsstring(&item.title)];
+                let slider: id = msg_send![*touch_bar_item, slider];
+                let () = msg_send![slider, setMinValue: item.min_value];
+                let () = msg_send![slider, setMaxValue: item.max_value];
+                let () = msg_send![slider, setDoubleValue: item.value];
+                let () = msg_send![slider, setEnabled: enabled];
+                let () = msg_send![slider, setTarget: target];
+                let () = msg_send![slider, setAction: sel!(onAction:)];
+                (touch_bar_item, StrongPtr::retain(slider))
+            }
+            TouchBarItemType::Scrubber => {
+                let touch_bar_item: id = msg_send![class!(NSCustomTouchBarItem), alloc];
+                let touch_bar_item =
+                    StrongPtr::new(msg_send![touch_bar_item, initWithIdentifier: identifier]);
+                let scrubber: id = msg_send![class!(NSScrubber), new];
+                let scrubber = StrongPtr::new(scrubber);
+                let () = msg_send![*scrubber, registerClass: class!(NSScrubberTextItemView)
+                    forItemIdentifier: *to_nsstring(SCRUBBER_ITEM_IDENTIFIER)];
+                let style: id = msg_send![class!(NSScrubberSelectionStyle), outlineOverlayStyle];
+                let () = msg_send![*scrubber, setSelectionOverlayStyle: style];
+                let () = msg_send![*scrubber, setShowsArrowButtons: YES];
+                let () = msg_send![*scrubber, setDataSource: target];
+                let () = msg_send![*scrubber, setDelegate: target];
+                if let Some(index) = item.selected_index {
+                    let () = msg_send![*scrubber, setSelectedIndex: index as NSInteger];
+                }
+                let () = msg_send![*touch_bar_item, setView: *scrubber];
+                (touch_bar_item, scrubber)
+            }
+        }
+    }
+
+    fn item_for_control(&self, control: id) -> Option<&TouchBarItem> {
+        self.controls
+            .iter()
+            .find(|(c, _)| **c == control)
+            .map(|(_, item)| item)
+    }
+
+    fn send_action(&self, action: TouchBarAction) {
+        if let Some(window) = self.window.upgrade() {
+            window.with_delegate(|delegate| delegate.touch_bar_action(action));
+        }
+    }
+
+    fn on_action(&self, sender: id) {
+        if let Some(item) = self.item_for_control(sender) {
+            let value = match item.item_type {
+                TouchBarItemType::Slider => {
+                    let value: f64 = unsafe { msg_send![sender, doubleValue] };
+                    Some(value)
+                }
+                _ => None,
+            };
+            self.send_action(TouchBarAction {
+                id: item.id,
+                value,
+                selected_index: None,
+            });
+        }
+    }
+
+    fn number_of_scrubber_items(&self, scrubber: id) -> NSInteger {
+        self.item_for_control(scrubber)
+            .map(|item| item.scrubber_items.len() as NSInteger)
+            .unwrap_or(0)
+    }
+
+    fn scrubber_view_for_item(&self, scrubber: id, index: NSInteger) -> id {
+        let title = self
+            .item_for_control(scrubber)
+            .and_then(|item| item.scrubber_items.get(index as usize));
+        unsafe {
+            let view: id = msg_send![scrubber,
+                makeItemWithIdentifier: *to_nsstring(SCRUBBER_ITEM_IDENTIFIER)
+                owner: nil
+            ];
+            if let Some(title) = title {
+                let () = msg_send![view, setTitle: *to_nsstring(title)];
+            }
+            view
+        }
+    }
+
+    fn scrubber_did_select_item(&self, scrubber: id, index: NSInteger) {
+        if let Some(item) = self.item_for_control(scrubber) {
+            self.send_action(TouchBarAction {
+                id: item.id,
+                value: None,
+                selected_index: Some(index),
+            });
+        }
+    }
+}
+
+struct TouchBarTargetClass(*const Class);
+unsafe impl Sync for TouchBarTargetClass {}
+
+lazy_static! {
+    static ref TOUCH_BAR_TARGET_CLASS: TouchBarTargetClass = unsafe {
+        let target_superclass = class!(NSObject);
+        let mut decl = ClassDecl::new("IMTouchBarTarget", target_superclass).unwrap();
+
+        decl.add_ivar::<*mut c_void>("imState");
+
+        decl.add_method(sel!(dealloc), dealloc as extern "C" fn(&Object, Sel));
+        decl.add_method(
+            sel!(onAction:),
+            on_action as extern "C" fn(&Object, Sel, id),
+        );
+        decl.add_method(
+            sel!(numberOfItemsForScrubber:),
+            number_of_items_for_scrubber as extern "C" fn(&Object, Sel, id) -> NSInteger,
+        );
+        decl.add_method(
+            sel!(scrubber:viewForItemAtIndex:),
+            scrubber_view_for_item_at_index as extern "C" fn(&Object, Sel, id, NSInteger) -> id,
+        );
+        decl.add_method(
+            sel!(scrubber:didSelectItemAtIndex:),
+            scrubber_did_select_item_at_index as extern "C" fn(&Object, Sel, id, NSInteger),
+        );
+
+        TouchBarTargetClass(decl.register())
+    };
+}
+
+fn with_state<F, R>(this: &Object, callback: F, default: R) -> R
+where
+    F: FnOnce(Rc<PlatformTouchBar>) -> R,
+{
+    let state_ptr = unsafe {
+        let state_ptr: *mut c_void = *this.get_ivar("imState");
+        if state_ptr.is_null() {
+            return default;
+        }
+        &mut *(state_ptr as *mut Weak<PlatformTouchBar>)
+    };
+    match state_ptr.upgrade() {
+        Some(upgraded) => callback(upgraded),
+        None => default,
+    }
+}
+
+extern "C" fn dealloc(this: &Object, _sel: Sel) {
+    unsafe {
+        let state_ptr: *mut c_void = *this.get_ivar("imState");
+        if !state_ptr.is_null() {
+            drop(Box::from_raw(state_ptr as *mut Weak<PlatformTouchBar>));
+        }
+
+        let superclass = superclass(this);
+        let () = msg_send![super(this, superclass), dealloc];
+    }
+}
+
+extern "C" fn on_action(this: &Object, _sel: Sel, sender: id) {
+    with_state(this, |state| state.on_action(sender), ())
+}
+
+extern "C" fn number_of_items_for_scrubber(this: &Object, _sel: Sel, scrubber: id) -> NSInteger {
+    with_state(this, |state| state.number_of_scrubber_items(scrubber), 0)
+}
+
+extern "C" fn scrubber_view_for_item_at_index(
+    this: &Object,
+    _sel: Sel,
+    scrubber: id,
+    index: NSInteger,
+) -> id {
+    with_state(
+        this,
+        |state| state.scrubber_view_for_item(scrubber, index),
+        nil,
+    )
+}
+
+extern "C" fn scrubber_did_select_item_at_index(
+    this: &Object,
+    _sel: Sel,
+    scrubber: id,
+    index: NSInteger,
+) {
+    with_state(
+        this,
+        |state| state.scrubber_did_select_item(scrubber, index),
+        (),
+    )
+}
@@ -0,0 +1,28 @@
+use std::time::Duration;
+
+use super::error::PlatformResult;
+
+type CGEventSourceStateID = i32;
+type CGEventType = u32;
+
+const K_CG_EVENT_SOURCE_STATE_HID_SYSTEM_STATE: CGEventSourceStateID = 1;
+const K_CG_ANY_INPUT_EVENT_TYPE: CGEventType = !0;
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    fn CGEventSourceSecondsSinceLastEventType(
+        state_id: CGEventSourceStateID,
+        event_type: CGEventType,
+    ) -> f64;
+}
+
+// Time since last input from any HID device
+pub fn idle_time() -> PlatformResult<Duration> {
+    let seconds = unsafe {
+        CGEventSourceSecondsSinceLastEventType(
+            K_CG_EVENT_SOURCE_STATE_HID_SYSTEM_STATE,
+            K_CG_ANY_INPUT_EVENT_TYPE,
+        )
+    };
+    Ok(Duration::from_secs_f64(seconds.max(0.0)))
+}
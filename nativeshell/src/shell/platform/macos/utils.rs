@@ -105,14 +105,17 @@ pub fn to_nsstring(string: &str) -> StrongPtr {
     }
 }
 
-// pub fn from_nsdata(data: id) -> Vec<u8> {
-//     unsafe {
-//         let bytes: *const u8 = msg_send![data, bytes];
-//         let length: usize = msg_send![data, length];
-//         let data: &[u8] = std::slice::from_raw_parts(bytes, length);
-//         data.into()
-//     }
-// }
+pub fn from_nsdata(data: id) -> Vec<u8> {
+    unsafe {
+        let bytes: *const u8 = msg_send![data, bytes];
+        let length: usize = msg_send![data, length];
+        if length == 0 {
+            return Vec::new();
+        }
+        let data: &[u8] = std::slice::from_raw_parts(bytes, length);
+        data.into()
+    }
+}
 
 pub fn to_nsdata(data: &[u8]) -> StrongPtr {
     unsafe {
@@ -1,22 +1,21 @@
 use std::{
     cell::{Cell, RefCell},
-    collections::HashMap,
     ffi::c_void,
     rc::{Rc, Weak},
-    time::Duration,
 };
 
+use cocoa::{
+    appkit::{NSApp, NSScreen, NSWindowTabbingMode},
+    foundation::NSProcessInfo,
+};
 use cocoa::{
     appkit::{
-        NSEvent, NSEventType, NSView, NSWindow, NSWindowCollectionBehavior, NSWindowStyleMask,
+        NSEvent, NSEventModifierFlags, NSEventType, NSView, NSWindow, NSWindowCollectionBehavior,
+        NSWindowStyleMask,
     },
     base::{id, nil, BOOL, NO, YES},
     foundation::{NSArray, NSInteger, NSPoint, NSRect, NSSize, NSString, NSUInteger},
 };
-use cocoa::{
-    appkit::{NSScreen, NSWindowTabbingMode},
-    foundation::NSProcessInfo,
-};
 
 use objc::{
     declare::ClassDecl,
@@ -28,18 +27,22 @@ use objc::{
 };
 
 use NSEventType::{
-    NSLeftMouseDown, NSLeftMouseUp, NSMouseEntered, NSMouseExited, NSMouseMoved, NSRightMouseDown,
-    NSRightMouseUp,
+    NSKeyDown, NSKeyUp, NSLeftMouseDown, NSLeftMouseDragged, NSLeftMouseUp, NSMouseEntered,
+    NSMouseExited, NSMouseMoved, NSOtherMouseDown, NSOtherMouseDragged, NSOtherMouseUp,
+    NSRightMouseDown, NSRightMouseDragged, NSRightMouseUp,
 };
 
 use crate::{
     codec::Value,
     shell::{
         api_model::{
-            DragEffect, DragRequest, PopupMenuRequest, PopupMenuResponse, WindowFrame,
-            WindowGeometry, WindowGeometryFlags, WindowGeometryRequest, WindowStyle,
+            ActivationPolicy, DragEffect, DragRequest, NativeWindowContent, PointerState,
+            PopupMenuRequest, PopupMenuResponse, QuickLookRequest, SharePickerRequest, TouchBar,
+            WindowAppearance, WindowBackdrop, WindowFrame, WindowGeometry, WindowGeometryFlags,
+            WindowGeometryRequest, WindowStyle, WindowType,
         },
-        Context, PlatformWindowDelegate, Point, Size,
+        platform::WindowError,
+        Context, PlatformWindowDelegate, Point, Rect, Size, SyntheticEvent, SyntheticMouseButton,
     },
     util::{LateRefCell, OkLog},
 };
@@ -49,11 +52,18 @@ use super::{
     engine::PlatformEngine,
     error::{PlatformError, PlatformResult},
     menu::PlatformMenu,
+    recent_input::RecentInput,
+    tablet::PlatformTablet,
+    touch_bar::PlatformTouchBar,
     utils::*,
 };
 
 pub type PlatformWindowType = StrongPtr;
 
+// NSRectEdge
+const NS_MIN_Y_EDGE: NSUInteger = 1;
+const NS_MAX_Y_EDGE: NSUInteger = 3;
+
 pub struct PlatformWindow {
     context: Rc<Context>,
     platform_window: PlatformWindowType,
@@ -64,16 +74,33 @@ pub struct PlatformWindow {
     modal_close_callback: RefCell<Option<Box<dyn FnOnce(PlatformResult<Value>)>>>,
     ready_to_show: Cell<bool>,
     show_when_ready: Cell<bool>,
+    // Window without Flutter view; Shown without waiting for first frame
+    native: Cell<bool>,
+    // Flutter layer observed while waiting for first frame of matching size
+    observed_layer: RefCell<Option<StrongPtr>>,
     drag_context: LateRefCell<DragContext>,
-    last_event: RefCell<HashMap<u64, StrongPtr>>,
+    recent_input: RecentInput,
     ignore_enter_leave_until: Cell<f64>,
+    touch_bar: RefCell<Option<Rc<PlatformTouchBar>>>,
+    tablet: PlatformTablet,
+    quick_look: RefCell<Option<QuickLookState>>,
+}
+
+struct QuickLookState {
+    // File URL and rect in window coordinates
+    items: Vec<(StrongPtr, Option<Rect>)>,
+    current_index: usize,
 }
 
 #[link(name = "AppKit", kind = "framework")]
 extern "C" {
     pub static NSPasteboardTypeFileURL: id;
+    static NSWorkspaceActiveSpaceDidChangeNotification: id;
 }
 
+#[link(name = "Quartz", kind = "framework")]
+extern "C" {}
+
 impl PlatformWindow {
     pub fn new(
         context: Rc<Context>,
@@ -104,6 +131,16 @@ impl PlatformWindow {
 
             window.setDelegate_(*platform_delegate);
 
+            // Space changes are only posted to workspace notification center
+            let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+            let workspace_center: id = msg_send![workspace, notificationCenter];
+            let () = msg_send![workspace_center,
+                addObserver: *platform_delegate
+                selector: sel!(activeSpaceDidChange:)
+                name: NSWorkspaceActiveSpaceDidChangeNotification
+                object: nil
+            ];
+
             Self {
                 context,
                 platform_window: window,
@@ -114,9 +151,14 @@ impl PlatformWindow {
                 modal_close_callback: RefCell::new(None),
                 ready_to_show: Cell::new(false),
                 show_when_ready: Cell::new(false),
-                last_event: RefCell::new(HashMap::new()),
+                native: Cell::new(false),
+                observed_layer: RefCell::new(None),
+                recent_input: RecentInput::new(),
                 drag_context: LateRefCell::new(),
                 ignore_enter_leave_until: Cell::new(0.0),
+                touch_bar: RefCell::new(None),
+                tablet: PlatformTablet::new(),
+                quick_look: RefCell::new(None),
             }
         })
     }
@@ -143,6 +185,52 @@ impl PlatformWindow {
         self.drag_context.set(drag_context);
     }
 
+    pub fn assign_weak_self_native(
+        &self,
+        weak: Weak<PlatformWindow>,
+        content: &NativeWindowContent,
+    ) {
+        self.weak_self.set(weak.clone());
+        self.native.set(true);
+
+        autoreleasepool(|| unsafe {
+            let state_ptr = Box::into_raw(Box::new(weak.clone())) as *mut c_void;
+            (**self.platform_delegate).set_ivar("imState", state_ptr);
+
+            let state_ptr = Box::into_raw(Box::new(weak.clone())) as *mut c_void;
+            (**self.platform_window).set_ivar("imState", state_ptr);
+
+            let color = content.background_color;
+            let component = |shift: u32| ((color >> shift) & 0xFF) as f64 / 255.0;
+            let alpha = component(24);
+            let ns_color: id = msg_send![class!(NSColor),
+                colorWithSRGBRed: component(16)
+                green: component(8)
+                blue: component(0)
+                alpha: alpha
+            ];
+
+            let frame = NSView::frame(self.platform_window.contentView());
+            let view: id = msg_send![class!(NSView), alloc];
+            let view = StrongPtr::new(NSView::initWithFrame_(view, frame));
+            let () = msg_send![*view, setWantsLayer: YES];
+            let layer: id = msg_send![*view, layer];
+            let cg_color: id = msg_send![ns_color, CGColor];
+            let () = msg_send![layer, setBackgroundColor: cg_color];
+            self.platform_window.setContentView_(*view);
+
+            if alpha < 1.0 {
+                let clear: id = msg_send![class!(NSColor), clearColor];
+                let () = msg_send![*self.platform_window, setOpaque: NO];
+                let () = msg_send![*self.platform_window, setBackgroundColor: clear];
+            }
+        });
+
+        // Native window doesn't accept drops, so drag types are not registered
+        self.drag_context
+            .set(DragContext::new(self.context.clone(), weak));
+    }
+
     pub fn get_platform_window(&self) -> PlatformWindowType {
         self.platform_window.clone()
     }
@@ -334,16 +422,7 @@ impl PlatformWindow {
 
     pub fn perform_window_drag(&self) -> PlatformResult<()> {
         unsafe {
-            let last_event = self
-                .last_event
-                .borrow()
-                .values()
-                .filter(|e| {
-                    let event_type = e.eventType();
-                    event_type == NSLeftMouseDown
-                })
-                .max_by_key(|x| x.eventNumber())
-                .cloned();
+            let last_event = self.recent_input.last_left_mouse_down();
             if let Some(last_event) = last_event {
                 let () = msg_send![*self.platform_window, performWindowDragWithEvent:*last_event];
                 Ok(())
@@ -407,31 +486,274 @@ impl PlatformWindow {
         Ok(())
     }
 
+    // Only available on macOS 11 and later
+    pub fn set_subtitle(&self, subtitle: String) -> PlatformResult<()> {
+        unsafe {
+            let responds: BOOL =
+                msg_send![*self.platform_window, respondsToSelector: sel!(setSubtitle:)];
+            if responds != YES {
+                return Err(PlatformError::NotAvailable);
+            }
+            let () = msg_send![*self.platform_window, setSubtitle: *to_nsstring(&subtitle)];
+        }
+        Ok(())
+    }
+
+    pub fn set_represented_file(&self, path: Option<String>) -> PlatformResult<()> {
+        // Empty string removes the proxy icon
+        let path = path.unwrap_or_default();
+        unsafe {
+            let () = msg_send![*self.platform_window, setRepresentedFilename: *to_nsstring(&path)];
+        }
+        Ok(())
+    }
+
+    pub fn set_document_edited(&self, edited: bool) -> PlatformResult<()> {
+        unsafe {
+            let () = msg_send![*self.platform_window, setDocumentEdited: edited as BOOL];
+        }
+        Ok(())
+    }
+
+    pub fn set_appearance(&self, appearance: WindowAppearance) -> PlatformResult<()> {
+        unsafe {
+            // nil appearance is inherited from application (system setting)
+            let appearance: id = match appearance {
+                WindowAppearance::System => nil,
+                WindowAppearance::Light => msg_send![class!(NSAppearance),
+                    appearanceNamed: *to_nsstring("NSAppearanceNameAqua")],
+                WindowAppearance::Dark => msg_send![class!(NSAppearance),
+                    appearanceNamed: *to_nsstring("NSAppearanceNameDarkAqua")],
+            };
+            let () = msg_send![*self.platform_window, setAppearance: appearance];
+        }
+        Ok(())
+    }
+
+    pub fn set_backdrop(&self, backdrop: WindowBackdrop) -> PlatformResult<()> {
+        match backdrop {
+            WindowBackdrop::None => Ok(()),
+            // Backdrop materials are Windows only
+            _ => Err(PlatformError::NotAvailable),
+        }
+    }
+
+    pub fn set_window_type(&self, window_type: WindowType) -> PlatformResult<()> {
+        match window_type {
+            WindowType::Normal => Ok(()),
+            // Window type hints are X11 only
+            _ => Err(PlatformError::NotAvailable),
+        }
+    }
+
+    pub fn show_share_picker(&self, request: SharePickerRequest) -> PlatformResult<()> {
+        unsafe {
+            let items: id = msg_send![class!(NSMutableArray), array];
+            if let Some(text) = &request.text {
+                let () = msg_send![items, addObject: *to_nsstring(text)];
+            }
+            for url in &request.urls {
+                let url: id = msg_send![class!(NSURL), URLWithString: *to_nsstring(url)];
+                if url != nil {
+                    let () = msg_send![items, addObject: url];
+                }
+            }
+            for file in &request.files {
+                let url: id = msg_send![class!(NSURL), fileURLWithPath: *to_nsstring(file)];
+                let () = msg_send![items, addObject: url];
+            }
+            let picker: id = msg_send![class!(NSSharingServicePicker), alloc];
+            let picker = StrongPtr::new(msg_send![picker, initWithItems: items]);
+            // Prefer showing the picker below the anchor
+            let content_view = self.platform_window.contentView();
+            let flipped: BOOL = msg_send![content_view, isFlipped];
+            let edge = if flipped == NO {
+                NS_MIN_Y_EDGE
+            } else {
+                NS_MAX_Y_EDGE
+            };
+            let () = msg_send![*picker,
+                showRelativeToRect: self.content_view_rect(&request.anchor)
+                ofView: content_view
+                preferredEdge: edge
+            ];
+        }
+        Ok(())
+    }
+
+    // Converts rect in window coordinates to content view rect
+    unsafe fn content_view_rect(&self, rect: &Rect) -> NSRect {
+        let content_view = self.platform_window.contentView();
+        let flipped: BOOL = msg_send![content_view, isFlipped];
+        if flipped == NO {
+            let y = content_view.bounds().size.height - rect.y2();
+            NSRect::new(
+                NSPoint::new(rect.x, y),
+                NSSize::new(rect.width, rect.height),
+            )
+        } else {
+            rect.into()
+        }
+    }
+
+    pub fn show_quick_look(&self, request: QuickLookRequest) -> PlatformResult<()> {
+        if request.items.is_empty() {
+            return self.hide_quick_look();
+        }
+        let items = request
+            .items
+            .into_iter()
+            .map(|item| unsafe {
+                let url: id = msg_send![class!(NSURL), fileURLWithPath: *to_nsstring(&item.path)];
+                (StrongPtr::retain(url), item.rect)
+            })
+            .collect();
+        self.quick_look.replace(Some(QuickLookState {
+            items,
+            current_index: request.current_index,
+        }));
+        unsafe {
+            let panel: id = msg_send![class!(QLPreviewPanel), sharedPreviewPanel];
+            if self.is_quick_look_controller(panel) {
+                let () = msg_send![panel, reloadData];
+                let () = msg_send![panel,
+                    setCurrentPreviewItemIndex: request.current_index as NSInteger];
+            } else {
+                // Panel looks for controller in responder chain of key window
+                self.platform_window.makeKeyWindow();
+                let () = msg_send![panel, updateController];
+            }
+            let () = msg_send![panel, makeKeyAndOrderFront: nil];
+        }
+        Ok(())
+    }
+
+    pub fn set_quick_look_index(&self, index: usize) -> PlatformResult<()> {
+        if let Some(state) = self.quick_look.borrow_mut().as_mut() {
+            state.current_index = index;
+        }
+        unsafe {
+            let panel: id = msg_send![class!(QLPreviewPanel), sharedPreviewPanel];
+            if self.is_quick_look_controller(panel) {
+                let () = msg_send![panel, setCurrentPreviewItemIndex: index as NSInteger];
+            }
+        }
+        Ok(())
+    }
+
+    pub fn hide_quick_look(&self) -> PlatformResult<()> {
+        unsafe {
+            let visible: BOOL = msg_send![class!(QLPreviewPanel), sharedPreviewPanelExists];
+            if visible == YES {
+                let panel: id = msg_send![class!(QLPreviewPanel), sharedPreviewPanel];
+                if self.is_quick_look_controller(panel) {
+                    let () = msg_send![panel, orderOut: nil];
+                }
+            }
+        }
+        self.quick_look.replace(None);
+        Ok(())
+    }
+
+    unsafe fn is_quick_look_controller(&self, panel: id) -> bool {
+        let controller: id = msg_send![panel, currentController];
+        let visible: BOOL = msg_send![panel, isVisible];
+        controller == *self.platform_window && visible == YES
+    }
+
+    fn begin_quick_look_control(&self, panel: id) {
+        let index = self.quick_look.borrow().as_ref().map(|s| s.current_index);
+        unsafe {
+            let window = *self.platform_window;
+            let () = msg_send![panel, setDataSource: window];
+            let () = msg_send![panel, setDelegate: window];
+            if let Some(index) = index {
+                let () = msg_send![panel, setCurrentPreviewItemIndex: index as NSInteger];
+            }
+        }
+    }
+
+    fn end_quick_look_control(&self, panel: id) {
+        unsafe {
+            let () = msg_send![panel, setDataSource: nil];
+            let () = msg_send![panel, setDelegate: nil];
+        }
+        self.quick_look.replace(None);
+    }
+
+    fn quick_look_item(&self, index: usize) -> id {
+        self.quick_look
+            .borrow()
+            .as_ref()
+            .and_then(|s| s.items.get(index).map(|i| *i.0))
+            .unwrap_or(nil)
+    }
+
+    fn quick_look_source_frame(&self, item: id) -> NSRect {
+        let rect = self.quick_look.borrow().as_ref().and_then(|s| {
+            s.items
+                .iter()
+                .find(|i| *i.0 == item)
+                .and_then(|i| i.1.clone())
+        });
+        match rect {
+            Some(rect) => unsafe {
+                let content_view = self.platform_window.contentView();
+                let rect = self.content_view_rect(&rect);
+                let rect: NSRect = msg_send![content_view, convertRect: rect toView: nil];
+                msg_send![*self.platform_window, convertRectToScreen: rect]
+            },
+            // Panel fades in without zoom animation
+            None => NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(0.0, 0.0)),
+        }
+    }
+
     pub fn is_modal(&self) -> bool {
         self.modal_close_callback.borrow().is_some()
     }
 
     unsafe fn actually_show(&self) {
-        if self.is_modal() {
-            let parent = self.parent_platform_window.as_ref().unwrap().clone().load();
-            let () = msg_send![*parent, beginSheet:*self.platform_window completionHandler:nil];
-        } else {
-            self.platform_window.makeKeyAndOrderFront_(nil);
+        // Modal window without parent (or with parent already released) is
+        // shown as regular window
+        let parent = self
+            .parent_platform_window
+            .as_ref()
+            .map(|parent| parent.load())
+            .filter(|parent| !parent.is_null());
+        match parent {
+            Some(parent) if self.is_modal() => {
+                let () = msg_send![*parent, beginSheet:*self.platform_window completionHandler:nil];
+            }
+            _ => self.platform_window.makeKeyAndOrderFront_(nil),
         }
     }
 
-    fn show_when_ready(weak_self: Weak<PlatformWindow>) {
+    fn show_when_ready(weak_self: Weak<PlatformWindow>) -> PlatformResult<()> {
         if let Some(s) = weak_self.upgrade() {
+            if s.native.get() {
+                unsafe { s.actually_show() };
+                if let Some(delegate) = s.delegate.upgrade() {
+                    delegate.visibility_changed(true);
+                }
+                return Ok(());
+            }
             autoreleasepool(|| unsafe {
                 let layer = NSWindow::contentView(*s.platform_window).layer();
                 let sublayers: id = msg_send![layer, sublayers];
+                if sublayers == nil || NSArray::count(sublayers) == 0 {
+                    return Err(WindowError::NotRealized.into());
+                }
                 let first = sublayers.objectAtIndex(0);
                 let contents: id = msg_send![first, contents];
                 if contents != nil {
                     // This makes assumptions about FlutterView internals :-/
                     let class: id = msg_send![contents, className];
                     if !class.isEqualToString("IOSurface") {
-                        panic!("Expected IOSurface content");
+                        s.stop_observing_layer();
+                        return Err(WindowError::UnexpectedContent {
+                            class: from_nsstring(class),
+                        }
+                        .into());
                     }
                     let scale = NSWindow::backingScaleFactor(*s.platform_window);
                     let content_size = NSView::frame(NSWindow::contentView(*s.platform_window));
@@ -446,36 +768,60 @@ impl PlatformWindow {
                     if actual_width == expected_width as NSInteger
                         && actual_height == expected_height as NSInteger
                     {
+                        s.stop_observing_layer();
                         s.actually_show();
                         if let Some(delegate) = s.delegate.upgrade() {
                             delegate.visibility_changed(true);
                         };
-                        return;
+                        return Ok(());
                     }
                 }
-                // wait until we have content generated (with proper size)
-                s.context
-                    .run_loop
-                    .borrow()
-                    .schedule(Duration::from_secs_f64(1.0 / 60.0), move || {
-                        Self::show_when_ready(weak_self)
-                    })
-                    .detach();
+                // wait until we have content generated (with proper size); Layer
+                // contents is replaced with every frame
+                s.observe_layer(first);
+                Ok(())
             })
+        } else {
+            Ok(())
+        }
+    }
+
+    unsafe fn observe_layer(&self, layer: id) {
+        if self.observed_layer.borrow().is_some() {
+            return;
+        }
+        let () = msg_send![layer,
+            addObserver: *self.platform_delegate
+            forKeyPath: *to_nsstring("contents")
+            options: 0 as NSUInteger
+            context: nil
+        ];
+        self.observed_layer.replace(Some(StrongPtr::retain(layer)));
+    }
+
+    fn stop_observing_layer(&self) {
+        let layer = self.observed_layer.borrow_mut().take();
+        if let Some(layer) = layer {
+            unsafe {
+                let () = msg_send![*layer,
+                    removeObserver: *self.platform_delegate
+                    forKeyPath: *to_nsstring("contents")
+                ];
+            }
         }
     }
 
     pub fn ready_to_show(&self) -> PlatformResult<()> {
         self.ready_to_show.set(true);
         if self.show_when_ready.get() {
-            Self::show_when_ready(self.weak_self.clone_value());
+            Self::show_when_ready(self.weak_self.clone_value())?;
         }
         Ok(())
     }
 
     pub fn show(&self) -> PlatformResult<()> {
         if self.ready_to_show.get() {
-            Self::show_when_ready(self.weak_self.clone_value());
+            Self::show_when_ready(self.weak_self.clone_value())?;
         } else {
             self.show_when_ready.set(true);
         }
@@ -511,7 +857,26 @@ impl PlatformWindow {
         Ok(())
     }
 
+    // Brings application and window to front (restores window if minimized)
+    pub fn activate(&self) -> PlatformResult<()> {
+        unsafe {
+            let () = msg_send![NSApp(), activateIgnoringOtherApps: YES];
+            let miniaturized: BOOL = msg_send![*self.platform_window, isMiniaturized];
+            if miniaturized == YES {
+                let () = msg_send![*self.platform_window, deminiaturize: nil];
+            }
+            self.platform_window.makeKeyAndOrderFront_(nil);
+        }
+        Ok(())
+    }
+
+    // Activation policy applies to entire application
+    pub fn set_activation_policy(&self, _policy: ActivationPolicy) -> PlatformResult<()> {
+        Ok(())
+    }
+
     pub fn hide(&self) -> PlatformResult<()> {
+        self.stop_observing_layer();
         if self.ready_to_show.get() {
             autoreleasepool(|| unsafe {
                 self.platform_window.orderOut_(nil);
@@ -526,20 +891,7 @@ impl PlatformWindow {
     }
 
     unsafe fn synthetize_mouse_up_event(&self) {
-        let last_event = self
-            .last_event
-            .borrow()
-            .values()
-            .filter(|e| {
-                let event_type = e.eventType();
-                event_type == NSLeftMouseDown
-                    || event_type == NSLeftMouseUp
-                    || event_type == NSRightMouseDown
-                    || event_type == NSRightMouseUp
-            })
-            .max_by_key(|x| x.eventNumber())
-            .cloned();
-
+        let last_event = self.recent_input.pressed_mouse_down();
         if let Some(event) = last_event {
             let opposite = match event.eventType() {
                 NSLeftMouseDown => NSLeftMouseUp,
@@ -564,18 +916,7 @@ impl PlatformWindow {
 
     pub(super) fn synthetize_mouse_move_if_needed(&self) {
         autoreleasepool(|| unsafe {
-            let last_event = self
-                .last_event
-                .borrow()
-                .values()
-                .filter(|e| {
-                    let event_type = e.eventType();
-                    event_type as i32 >= NSLeftMouseDown as i32
-                        && event_type as i32 <= NSMouseExited as i32
-                })
-                .max_by_key(|x| x.eventNumber())
-                .cloned();
-
+            let last_event = self.recent_input.last_mouse_event();
             if let Some(last_event) = last_event {
                 let location = NSEvent::mouseLocation(nil);
                 let window_frame = NSWindow::frame(*self.platform_window);
@@ -605,6 +946,43 @@ impl PlatformWindow {
                 }
             }
         });
+        // pointer may have left the window while mouse events were not delivered
+        self.update_pointer_inside();
+    }
+
+    // NSMouseEntered and NSMouseExited can not be relied on (they might be stale after
+    // showing popup menu), so instead check pointer location against content rect and
+    // window under the cursor.
+    pub(super) fn update_pointer_inside(&self) {
+        let inside = unsafe {
+            let location = NSEvent::mouseLocation(nil);
+            let window_number: NSInteger = msg_send![class!(NSWindow),
+                windowNumberAtPoint: location
+                belowWindowWithWindowNumber: 0 as NSInteger
+            ];
+            let own_window_number: NSInteger = msg_send![*self.platform_window, windowNumber];
+            let window_frame = NSWindow::frame(*self.platform_window);
+            let content_rect = self.platform_window.contentRectForFrameRect_(window_frame);
+            window_number == own_window_number
+                && location.x >= content_rect.origin.x
+                && location.x < content_rect.origin.x + content_rect.size.width
+                && location.y >= content_rect.origin.y
+                && location.y < content_rect.origin.y + content_rect.size.height
+        };
+        self.with_delegate(|delegate| {
+            if inside {
+                delegate.pointer_entered();
+            } else {
+                delegate.pointer_exited();
+            }
+        });
+    }
+
+    pub fn handle_tablet_event(&self, event: id) {
+        let view = unsafe { self.platform_window.contentView() };
+        if let Some(event) = self.tablet.convert_event(event, view) {
+            self.with_delegate(|delegate| delegate.tablet_event(event));
+        }
     }
 
     pub fn should_send_event(&self, event: StrongPtr) -> bool {
@@ -623,16 +1001,17 @@ impl PlatformWindow {
         true
     }
 
+    pub fn pointer_state(&self) -> PointerState {
+        let view = unsafe { self.platform_window.contentView() };
+        self.recent_input.pointer_state(view)
+    }
+
     pub fn set_pending_effect(&self, effect: DragEffect) {
         self.drag_context.borrow_mut().set_pending_effect(effect);
     }
 
     pub fn begin_drag_session(&self, request: DragRequest) -> PlatformResult<()> {
-        let last_down_event = self
-            .last_event
-            .borrow()
-            .get(&(NSLeftMouseDown as u64))
-            .cloned();
+        let last_down_event = self.recent_input.last_left_mouse_down();
         if let Some(last_down_event) = last_down_event {
             autoreleasepool(|| unsafe {
                 self.drag_context.borrow().start_drag(
@@ -640,16 +1019,106 @@ impl PlatformWindow {
                     self.platform_window.contentView(),
                     *self.platform_window,
                     *last_down_event,
-                );
+                )?;
 
                 self.synthetize_mouse_up_event();
-            });
-            Ok(())
+                Ok(())
+            })
         } else {
             Err(PlatformError::NoEventFound)
         }
     }
 
+    // Event goes through sendEvent: same as events from the window server, so it
+    // is seen by Flutter view as well as by recent input tracking
+    pub fn inject_event(&self, event: &SyntheticEvent) -> PlatformResult<()> {
+        autoreleasepool(|| unsafe {
+            let window_number: NSInteger = msg_send![*self.platform_window, windowNumber];
+            let event: id = match event {
+                SyntheticEvent::MouseDown { position, button } => {
+                    let event_type = match button {
+                        SyntheticMouseButton::Left => NSLeftMouseDown,
+                        SyntheticMouseButton::Right => NSRightMouseDown,
+                        SyntheticMouseButton::Middle => NSOtherMouseDown,
+                    };
+                    self.synthetic_mouse_event(event_type, position, window_number)
+                }
+                SyntheticEvent::MouseUp { position, button } => {
+                    let event_type = match button {
+                        SyntheticMouseButton::Left => NSLeftMouseUp,
+                        SyntheticMouseButton::Right => NSRightMouseUp,
+                        SyntheticMouseButton::Middle => NSOtherMouseUp,
+                    };
+                    self.synthetic_mouse_event(event_type, position, window_number)
+                }
+                SyntheticEvent::MouseMove { position } => {
+                    let pressed = self.recent_input.pressed_mouse_down();
+                    let event_type = match pressed.map(|e| e.eventType()) {
+                        Some(NSLeftMouseDown) => NSLeftMouseDragged,
+                        Some(NSRightMouseDown) => NSRightMouseDragged,
+                        Some(NSOtherMouseDown) => NSOtherMouseDragged,
+                        _ => NSMouseMoved,
+                    };
+                    self.synthetic_mouse_event(event_type, position, window_number)
+                }
+                SyntheticEvent::KeyDown {
+                    key_code,
+                    characters,
+                }
+                | SyntheticEvent::KeyUp {
+                    key_code,
+                    characters,
+                } => {
+                    let event_type = match event {
+                        SyntheticEvent::KeyDown { .. } => NSKeyDown,
+                        _ => NSKeyUp,
+                    };
+                    let characters = to_nsstring(characters);
+                    msg_send![class!(NSEvent), keyEventWithType: event_type
+                        location: NSPoint::new(0.0, 0.0)
+                        modifierFlags: NSEventModifierFlags::empty()
+                        timestamp: Self::system_uptime()
+                        windowNumber: window_number
+                        context: nil
+                        characters: *characters
+                        charactersIgnoringModifiers: *characters
+                        isARepeat: NO
+                        keyCode: *key_code as u16
+                    ]
+                }
+            };
+            let () = msg_send![*self.platform_window, sendEvent: event];
+        });
+        Ok(())
+    }
+
+    unsafe fn synthetic_mouse_event(
+        &self,
+        event_type: NSEventType,
+        position: &Point,
+        window_number: NSInteger,
+    ) -> id {
+        let content_view = self.platform_window.contentView();
+        let flipped: BOOL = msg_send![content_view, isFlipped];
+        let y = if flipped == YES {
+            position.y
+        } else {
+            NSView::frame(content_view).size.height - position.y
+        };
+        let location: NSPoint =
+            msg_send![content_view, convertPoint: NSPoint::new(position.x, y) toView: nil];
+        msg_send![class!(NSEvent), mouseEventWithType: event_type
+            location: location
+            modifierFlags: NSEventModifierFlags::empty()
+            timestamp: Self::system_uptime()
+            windowNumber: window_number
+            context: nil
+            eventNumber: 0 as NSInteger
+            clickCount: 1 as NSInteger
+            pressure: 1.0f32
+        ]
+    }
+
     fn system_uptime() -> f64 {
         unsafe {
             let info = NSProcessInfo::processInfo(nil);
@@ -671,7 +1140,14 @@ impl PlatformWindow {
             let menu = menu.menu.clone();
             let on_done = RefCell::new(Some(Box::new(on_done)));
             let weak = self.weak_self.clone_value();
+            let preselect_first = request.preselect_first;
+            let window_number: NSInteger = msg_send![*self.platform_window, windowNumber];
             let cb = move || {
+                if preselect_first {
+                    // There is no public API to highlight menu item; Down arrow
+                    // is processed by menu tracking loop once the menu is open
+                    Self::post_down_arrow_key(window_number);
+                }
                 let item_selected: BOOL = msg_send![*menu, popUpMenuPositioningItem:nil atLocation:position inView:view.clone()];
 
                 let on_done = on_done.take();
@@ -698,6 +1174,22 @@ impl PlatformWindow {
         }
     }
 
+    unsafe fn post_down_arrow_key(window_number: NSInteger) {
+        let characters = to_nsstring("\u{F701}"); // NSDownArrowFunctionKey
+        let event: id = msg_send![class!(NSEvent), keyEventWithType: NSKeyDown
+            location: NSPoint::new(0.0, 0.0)
+            modifierFlags: NSEventModifierFlags::empty()
+            timestamp: Self::system_uptime()
+            windowNumber: window_number
+            context: nil
+            characters: *characters
+            charactersIgnoringModifiers: *characters
+            isARepeat: NO
+            keyCode: 125u16 // kVK_DownArrow
+        ];
+        let () = msg_send![NSApp(), postEvent: event atStart: NO];
+    }
+
     pub fn hide_popup_menu(&self, menu: Rc<PlatformMenu>) -> PlatformResult<()> {
         unsafe {
             let () = msg_send![*menu.menu, cancelTracking];
@@ -710,6 +1202,40 @@ impl PlatformWindow {
         Err(PlatformError::NotAvailable)
     }
 
+    // Only used for Snap Layouts on Windows
+    pub fn set_maximize_button_rect(&self, _rect: Option<Rect>) -> PlatformResult<()> {
+        Ok(())
+    }
+
+    // AppUserModelID is Windows only
+    pub fn set_app_user_model_id(&self, _id: Option<String>) -> PlatformResult<()> {
+        Err(PlatformError::NotAvailable)
+    }
+
+    pub fn is_on_active_space(&self) -> PlatformResult<bool> {
+        let on_active_space: BOOL = unsafe { msg_send![*self.platform_window, isOnActiveSpace] };
+        Ok(on_active_space == YES)
+    }
+
+    // Visible window is moved when ordered front while having MoveToActiveSpace
+    // collection behavior; Hidden window will appear on active Space when shown
+    pub fn move_to_active_space(&self) -> PlatformResult<()> {
+        unsafe {
+            let window = *self.platform_window;
+            let collection_behavior = NSWindow::collectionBehavior(window);
+            NSWindow::setCollectionBehavior_(
+                window,
+                collection_behavior
+                    | NSWindowCollectionBehavior::NSWindowCollectionBehaviorMoveToActiveSpace,
+            );
+            if NSWindow::isVisible(window) == YES {
+                NSWindow::orderFront_(window, nil);
+            }
+            NSWindow::setCollectionBehavior_(window, collection_behavior);
+        }
+        Ok(())
+    }
+
     pub fn set_window_menu(&self, menu: Option<Rc<PlatformMenu>>) -> PlatformResult<()> {
         self.context
             .menu_manager
@@ -719,6 +1245,17 @@ impl PlatformWindow {
         Ok(())
     }
 
+    pub fn set_touch_bar(&self, touch_bar: Option<TouchBar>) -> PlatformResult<()> {
+        let touch_bar = touch_bar
+            .map(|touch_bar| PlatformTouchBar::new(self.weak_self.clone_value(), touch_bar));
+        unsafe {
+            let ns_touch_bar = touch_bar.as_ref().map(|t| *t.touch_bar).unwrap_or(nil);
+            let () = msg_send![*self.platform_window, setTouchBar: ns_touch_bar];
+        }
+        self.touch_bar.replace(touch_bar);
+        Ok(())
+    }
+
     pub(super) fn with_delegate<F>(&self, callback: F)
     where
         F: FnOnce(Rc<dyn PlatformWindowDelegate>),
@@ -730,6 +1267,12 @@ impl PlatformWindow {
     }
 }
 
+impl Drop for PlatformWindow {
+    fn drop(&mut self) {
+        self.stop_observing_layer();
+    }
+}
+
 struct WindowClass(*const Class);
 unsafe impl Sync for WindowClass {}
 struct WindowDelegateClass(*const Class);
@@ -791,6 +1334,37 @@ lazy_static! {
                 as extern "C" fn(&mut Object, Sel, id, NSPoint, NSDragOperation),
         );
 
+        // QLPreviewPanelController, QLPreviewPanelDataSource, QLPreviewPanelDelegate
+        decl.add_method(
+            sel!(acceptsPreviewPanelControl:),
+            accepts_preview_panel_control as extern "C" fn(&Object, Sel, id) -> BOOL,
+        );
+
+        decl.add_method(
+            sel!(beginPreviewPanelControl:),
+            begin_preview_panel_control as extern "C" fn(&Object, Sel, id),
+        );
+
+        decl.add_method(
+            sel!(endPreviewPanelControl:),
+            end_preview_panel_control as extern "C" fn(&Object, Sel, id),
+        );
+
+        decl.add_method(
+            sel!(numberOfPreviewItemsInPreviewPanel:),
+            number_of_preview_items as extern "C" fn(&Object, Sel, id) -> NSInteger,
+        );
+
+        decl.add_method(
+            sel!(previewPanel:previewItemAtIndex:),
+            preview_item_at_index as extern "C" fn(&Object, Sel, id, NSInteger) -> id,
+        );
+
+        decl.add_method(
+            sel!(previewPanel:sourceFrameOnScreenForPreviewItem:),
+            source_frame_for_preview_item as extern "C" fn(&Object, Sel, id, id) -> NSRect,
+        );
+
         decl.add_ivar::<*mut c_void>("imState");
 
         WindowClass(decl.register())
@@ -824,6 +1398,31 @@ lazy_static! {
             window_did_resign_key as extern "C" fn(&Object, Sel, id),
         );
 
+        decl.add_method(
+            sel!(windowDidChangeOcclusionState:),
+            window_did_change_occlusion_state as extern "C" fn(&Object, Sel, id),
+        );
+
+        decl.add_method(
+            sel!(observeValueForKeyPath:ofObject:change:context:),
+            observe_value_for_key_path as extern "C" fn(&Object, Sel, id, id, id, *mut c_void),
+        );
+
+        decl.add_method(
+            sel!(layerContentsDidChange:),
+            layer_contents_did_change as extern "C" fn(&Object, Sel, id),
+        );
+
+        decl.add_method(
+            sel!(activeSpaceDidChange:),
+            active_space_did_change as extern "C" fn(&Object, Sel, id),
+        );
+
+        decl.add_method(
+            sel!(window:shouldDragDocumentWithEvent:from:withPasteboard:),
+            window_should_drag_document as extern "C" fn(&Object, Sel, id, id, NSPoint, id) -> BOOL,
+        );
+
         decl.add_method(sel!(dealloc), dealloc as extern "C" fn(&Object, Sel));
 
         decl.add_ivar::<*mut c_void>("imState");
@@ -893,7 +1492,8 @@ extern "C" fn window_will_close(this: &Object, _: Sel, _: id) {
             for i in 0..child_windows.count() {
                 child_windows.objectAtIndex(i).close();
             }
-            let () = msg_send![*state.platform_window, setContentViewController: nil];
+            // View controller is removed when engine is shut down, which happens
+            // after pending messages are processed
         }
         state
             .context
@@ -901,32 +1501,112 @@ extern "C" fn window_will_close(this: &Object, _: Sel, _: id) {
             .borrow()
             .get_platform_menu_manager()
             .window_will_close(state.platform_window.clone());
+        state.recent_input.clear();
+        state.stop_observing_layer();
         delegate.will_close();
     });
 }
 
 extern "C" fn window_did_become_key(this: &Object, _: Sel, _: id) {
-    with_state_delegate(this, |state, _delegate| {
+    with_state_delegate(this, |state, delegate| {
         state
             .context
             .menu_manager
             .borrow()
             .get_platform_menu_manager()
             .window_did_become_active(state.platform_window.clone());
+        delegate.activation_changed(true);
     });
 }
 
 extern "C" fn window_did_resign_key(this: &Object, _: Sel, _: id) {
-    with_state_delegate(this, |state, _delegate| {
+    with_state_delegate(this, |state, delegate| {
         state
             .context
             .menu_manager
             .borrow()
             .get_platform_menu_manager()
             .window_did_resign_active(state.platform_window.clone());
+        delegate.activation_changed(false);
+    });
+}
+
+extern "C" fn window_did_change_occlusion_state(this: &Object, _: Sel, _: id) {
+    with_state_delegate(this, |state, delegate| {
+        let occlusion_state: NSUInteger =
+            unsafe { msg_send![*state.platform_window, occlusionState] };
+        // NSWindowOcclusionStateVisible
+        delegate.occlusion_changed(occlusion_state & (1 << 1) == 0);
+    });
+}
+
+extern "C" fn observe_value_for_key_path(
+    this: &Object,
+    _: Sel,
+    _key_path: id,
+    _object: id,
+    _change: id,
+    _context: *mut c_void,
+) {
+    // KVO notification is posted on thread that replaced layer contents
+    unsafe {
+        let () = msg_send![this,
+            performSelectorOnMainThread: sel!(layerContentsDidChange:)
+            withObject: nil
+            waitUntilDone: NO
+        ];
+    }
+}
+
+extern "C" fn layer_contents_did_change(this: &Object, _: Sel, _: id) {
+    with_state_delegate(this, |state, delegate| {
+        if state.observed_layer.borrow().is_some() {
+            // Show request has already been replied to
+            if let Err(error) = PlatformWindow::show_when_ready(Rc::downgrade(&state)) {
+                delegate.show_failed(error.into());
+            }
+        }
+    });
+}
+
+extern "C" fn active_space_did_change(this: &Object, _: Sel, _: id) {
+    with_state_delegate(this, |state, delegate| {
+        let on_active_space: BOOL = unsafe { msg_send![*state.platform_window, isOnActiveSpace] };
+        delegate.active_space_changed(on_active_space == YES);
     });
 }
 
+// Proxy icon drag is performed by AppKit (file URL is written to pasteboard)
+extern "C" fn window_should_drag_document(
+    this: &Object,
+    _: Sel,
+    _window: id,
+    _event: id,
+    _drag_image_location: NSPoint,
+    _pasteboard: id,
+) -> BOOL {
+    with_state_delegate(this, |state, delegate| {
+        let path: id = unsafe { msg_send![*state.platform_window, representedFilename] };
+        let path = from_nsstring(path);
+        if !path.is_empty() {
+            delegate.represented_file_dragged(path);
+        }
+    });
+    YES
+}
+
+fn is_pointer_event(event_type: NSEventType) -> bool {
+    matches!(
+        event_type,
+        NSMouseMoved
+            | NSMouseEntered
+            | NSMouseExited
+            | NSLeftMouseDragged
+            | NSRightMouseDragged
+            | NSOtherMouseDragged
+    )
+}
+
 extern "C" fn send_event(this: &mut Object, _: Sel, e: id) {
     unsafe {
         let event = StrongPtr::retain(e);
@@ -934,11 +1614,13 @@ extern "C" fn send_event(this: &mut Object, _: Sel, e: id) {
             this,
             move |state| {
                 let event_type = NSEvent::eventType(*event);
-                state
-                    .last_event
-                    .borrow_mut()
-                    .insert(event_type as u64, event.clone());
-                state.should_send_event(event)
+                state.recent_input.record_event(*event);
+                state.handle_tablet_event(*event);
+                let res = state.should_send_event(event);
+                if res && is_pointer_event(event_type) {
+                    state.update_pointer_inside();
+                }
+                res
             },
             || true,
         );
@@ -949,6 +1631,55 @@ extern "C" fn send_event(this: &mut Object, _: Sel, e: id) {
     }
 }
 
+extern "C" fn accepts_preview_panel_control(this: &Object, _: Sel, _panel: id) -> BOOL {
+    with_state_res(
+        this,
+        |state| {
+            if state.quick_look.borrow().is_some() {
+                YES
+            } else {
+                NO
+            }
+        },
+        || NO,
+    )
+}
+
+extern "C" fn begin_preview_panel_control(this: &Object, _: Sel, panel: id) {
+    with_state(this, |state| state.begin_quick_look_control(panel));
+}
+
+extern "C" fn end_preview_panel_control(this: &Object, _: Sel, panel: id) {
+    with_state(this, |state| state.end_quick_look_control(panel));
+}
+
+extern "C" fn number_of_preview_items(this: &Object, _: Sel, _panel: id) -> NSInteger {
+    with_state_res(
+        this,
+        |state| {
+            state
+                .quick_look
+                .borrow()
+                .as_ref()
+                .map(|s| s.items.len() as NSInteger)
+                .unwrap_or(0)
+        },
+        || 0,
+    )
+}
+
+extern "C" fn preview_item_at_index(this: &Object, _: Sel, _panel: id, index: NSInteger) -> id {
+    with_state_res(this, |state| state.quick_look_item(index as usize), || nil)
+}
+
+extern "C" fn source_frame_for_preview_item(this: &Object, _: Sel, _panel: id, item: id) -> NSRect {
+    with_state_res(
+        this,
+        |state| state.quick_look_source_frame(item),
+        || NSRect::new(NSPoint::new(0.0, 0.0), NSSize::new(0.0, 0.0)),
+    )
+}
+
 extern "C" fn dragging_entered(this: &mut Object, _: Sel, info: id) -> NSDragOperation {
     with_state_res(
         this,
@@ -1020,6 +1751,11 @@ extern "C" fn dealloc(this: &Object, _sel: Sel) {
     unsafe {
         Box::from_raw(state_ptr);
 
+        // Delegate observes active Space changes
+        let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+        let workspace_center: id = msg_send![workspace, notificationCenter];
+        let () = msg_send![workspace_center, removeObserver: this];
+
         let superclass = superclass(this);
         let () = msg_send![super(this, superclass), dealloc];
     }
@@ -0,0 +1,134 @@
+use cocoa::base::{id, nil};
+use core_foundation::{
+    base::TCFType,
+    dictionary::{CFDictionary, CFDictionaryRef},
+    string::CFStringRef,
+};
+use core_graphics::{
+    base::{kCGBitmapByteOrderDefault, kCGImageAlphaPremultipliedLast},
+    color_space::CGColorSpace,
+    context::CGContext,
+    geometry::{CGPoint, CGRect, CGSize},
+    window::*,
+};
+
+use crate::shell::{
+    api_model::{ExternalWindow, ImageData, PermissionKind, PermissionStatus, WindowListRequest},
+    Rect, Size,
+};
+
+use super::{
+    error::{PlatformError, PlatformResult},
+    permission,
+    utils::from_nsstring,
+};
+
+// Regular application windows; Menu bar, dock and overlays use higher layers
+const NORMAL_WINDOW_LAYER: i32 = 0;
+
+unsafe fn value_for_key(dict: id, key: CFStringRef) -> id {
+    msg_send![dict, objectForKey: key as id]
+}
+
+unsafe fn string_for_key(dict: id, key: CFStringRef) -> Option<String> {
+    let value = value_for_key(dict, key);
+    if value == nil {
+        None
+    } else {
+        Some(from_nsstring(value))
+    }
+}
+
+// Without screen recording permission window names are missing and captured
+// images only contain desktop background, so thumbnails are skipped
+pub fn get_windows(request: &WindowListRequest) -> PlatformResult<Vec<ExternalWindow>> {
+    let info = copy_window_info(
+        kCGWindowListOptionOnScreenOnly | kCGWindowListOptionExcludeDesktopElements,
+        kCGNullWindowID,
+    )
+    .ok_or(PlatformError::UnknownError)?;
+    let thumbnail_size = request.thumbnail_size.as_ref().filter(|_| {
+        permission::status(PermissionKind::ScreenRecording).ok() == Some(PermissionStatus::Granted)
+    });
+    let current_pid = std::process::id() as i64;
+    let mut res = Vec::new();
+    unsafe {
+        let info = info.as_concrete_TypeRef() as id;
+        let count: usize = msg_send![info, count];
+        for i in 0..count {
+            let window: id = msg_send![info, objectAtIndex: i];
+            let layer: i32 = msg_send![value_for_key(window, kCGWindowLayer), intValue];
+            let owner_pid: i64 = msg_send![value_for_key(window, kCGWindowOwnerPID), longLongValue];
+            if layer != NORMAL_WINDOW_LAYER || owner_pid == current_pid {
+                continue;
+            }
+            let window_id: CGWindowID =
+                msg_send![value_for_key(window, kCGWindowNumber), unsignedIntValue];
+            let bounds = CFDictionary::wrap_under_get_rule(
+                value_for_key(window, kCGWindowBounds) as CFDictionaryRef
+            );
+            let bounds = match CGRect::from_dict_representation(&bounds) {
+                Some(bounds) => bounds,
+                None => continue,
+            };
+            res.push(ExternalWindow {
+                id: window_id as i64,
+                title: string_for_key(window, kCGWindowName),
+                owner_name: string_for_key(window, kCGWindowOwnerName).unwrap_or_default(),
+                owner_pid,
+                // Window bounds use global display coordinates (top left
+                // origin)
+                frame: Rect::xywh(
+                    bounds.origin.x,
+                    bounds.origin.y,
+                    bounds.size.width,
+                    bounds.size.height,
+                ),
+                thumbnail: thumbnail_size.and_then(|size| thumbnail(window_id, &bounds, size)),
+            });
+        }
+    }
+    Ok(res)
+}
+
+fn thumbnail(window_id: CGWindowID, bounds: &CGRect, max_size: &Size) -> Option<ImageData> {
+    let image = create_image(
+        *bounds,
+        kCGWindowListOptionIncludingWindow,
+        window_id,
+        kCGWindowImageBoundsIgnoreFraming
+            | kCGWindowImageShouldBeOpaque
+            | kCGWindowImageNominalResolution,
+    )?;
+    let (width, height) = (image.width() as f64, image.height() as f64);
+    if width == 0.0 || height == 0.0 {
+        return None;
+    }
+    let scale = (max_size.width / width)
+        .min(max_size.height / height)
+        .min(1.0);
+    let width = ((width * scale).round() as usize).max(1);
+    let height = ((height * scale).round() as usize).max(1);
+    let mut context = CGContext::create_bitmap_context(
+        None,
+        width,
+        height,
+        8,
+        width * 4,
+        &CGColorSpace::create_device_rgb(),
+        kCGBitmapByteOrderDefault | kCGImageAlphaPremultipliedLast,
+    );
+    context.draw_image(
+        CGRect::new(
+            &CGPoint::new(0.0, 0.0),
+            &CGSize::new(width as f64, height as f64),
+        ),
+        &image,
+    );
+    Some(ImageData {
+        width: width as i32,
+        height: height as i32,
+        bytes_per_row: (width * 4) as i32,
+        data: context.data().to_vec(),
+    })
+}
@@ -0,0 +1,210 @@
+use cocoa::{
+    base::{id, nil, BOOL, YES},
+    foundation::NSArray,
+};
+use core_foundation::{
+    base::TCFType,
+    string::{CFString, CFStringRef},
+};
+
+use crate::shell::api_model::{DefaultHandler, DropFilter, HandlerTarget};
+
+use super::{
+    error::{PlatformError, PlatformResult},
+    utils::{from_nsstring, to_nsstring},
+};
+
+type LSRolesMask = u32;
+
+const K_LS_ROLES_ALL: LSRolesMask = 0xFFFFFFFF;
+
+#[link(name = "CoreServices", kind = "framework")]
+extern "C" {
+    static kUTTagClassFilenameExtension: CFStringRef;
+    fn UTTypeCreatePreferredIdentifierForTag(
+        tag_class: CFStringRef,
+        tag: CFStringRef,
+        conforming_to: CFStringRef,
+    ) -> CFStringRef;
+    fn LSCopyDefaultHandlerForURLScheme(scheme: CFStringRef) -> CFStringRef;
+    fn LSCopyDefaultRoleHandlerForContentType(
+        content_type: CFStringRef,
+        role: LSRolesMask,
+    ) -> CFStringRef;
+    fn LSSetDefaultHandlerForURLScheme(scheme: CFStringRef, bundle_id: CFStringRef) -> i32;
+    fn LSSetDefaultRoleHandlerForContentType(
+        content_type: CFStringRef,
+        role: LSRolesMask,
+        bundle_id: CFStringRef,
+    ) -> i32;
+}
+
+pub fn open_url(url: &str) -> PlatformResult<()> {
+    unsafe {
+        let url: id = msg_send![class!(NSURL), URLWithString: *to_nsstring(url)];
+        if url == nil {
+            return Err(PlatformError::UnknownError);
+        }
+        open(url)
+    }
+}
+
+// Opens file in default application or directory in Finder
+pub fn open_path(path: &str) -> PlatformResult<()> {
+    unsafe { open(msg_send![class!(NSURL), fileURLWithPath: *to_nsstring(path)]) }
+}
+
+pub fn reveal_in_file_manager(path: &str) -> PlatformResult<()> {
+    unsafe {
+        let url: id = msg_send![class!(NSURL), fileURLWithPath: *to_nsstring(path)];
+        let urls = NSArray::arrayWithObject(nil, url);
+        let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+        let () = msg_send![workspace, activateFileViewerSelectingURLs: urls];
+        Ok(())
+    }
+}
+
+pub fn trash_items(paths: &[String]) -> PlatformResult<Vec<Option<String>>> {
+    unsafe {
+        let file_manager: id = msg_send![class!(NSFileManager), defaultManager];
+        let mut res = Vec::new();
+        for path in paths {
+            let url: id = msg_send![class!(NSURL), fileURLWithPath: *to_nsstring(path)];
+            let mut trashed_url: id = nil;
+            let mut error: id = nil;
+            let trashed: BOOL = msg_send![file_manager,
+                trashItemAtURL: url
+                resultingItemURL: &mut trashed_url
+                error: &mut error
+            ];
+            if trashed != YES {
+                return Err(PlatformError::UnknownError);
+            }
+            if trashed_url != nil {
+                let path: id = msg_send![trashed_url, path];
+                res.push(Some(from_nsstring(path)));
+            } else {
+                res.push(None);
+            }
+        }
+        Ok(res)
+    }
+}
+
+// Whether type of file at path conforms to any of the filter UTIs
+pub fn file_conforms_to(path: &str, filter: &DropFilter) -> bool {
+    if filter.utis.is_empty() {
+        return false;
+    }
+    unsafe {
+        let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+        let file_type: id = msg_send![workspace, typeOfFile:*to_nsstring(path) error:nil];
+        if file_type == nil {
+            return false;
+        }
+        filter.utis.iter().any(|uti| {
+            let conforms: BOOL = msg_send![workspace,
+                type: file_type
+                conformsToType: *to_nsstring(uti)
+            ];
+            conforms == YES
+        })
+    }
+}
+
+fn uti_for_extension(extension: &str) -> CFString {
+    let extension = CFString::new(extension);
+    unsafe {
+        CFString::wrap_under_create_rule(UTTypeCreatePreferredIdentifierForTag(
+            kUTTagClassFilenameExtension,
+            extension.as_concrete_TypeRef(),
+            std::ptr::null(),
+        ))
+    }
+}
+
+fn own_bundle_identifier() -> Option<String> {
+    unsafe {
+        let bundle: id = msg_send![class!(NSBundle), mainBundle];
+        let identifier: id = msg_send![bundle, bundleIdentifier];
+        if identifier == nil {
+            None
+        } else {
+            Some(from_nsstring(identifier))
+        }
+    }
+}
+
+pub fn default_handler(target: &HandlerTarget) -> PlatformResult<Option<DefaultHandler>> {
+    let bundle_id = unsafe {
+        match target {
+            HandlerTarget::UrlScheme(scheme) => {
+                let scheme = CFString::new(scheme);
+                LSCopyDefaultHandlerForURLScheme(scheme.as_concrete_TypeRef())
+            }
+            HandlerTarget::FileExtension(extension) => LSCopyDefaultRoleHandlerForContentType(
+                uti_for_extension(extension).as_concrete_TypeRef(),
+                K_LS_ROLES_ALL,
+            ),
+        }
+    };
+    if bundle_id.is_null() {
+        return Ok(None);
+    }
+    let bundle_id = unsafe { CFString::wrap_under_create_rule(bundle_id) }.to_string();
+    let name = unsafe {
+        let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+        let url: id = msg_send![workspace,
+            URLForApplicationWithBundleIdentifier: *to_nsstring(&bundle_id)];
+        if url == nil {
+            // Registered application is no longer installed
+            return Ok(None);
+        }
+        let path: id = msg_send![url, path];
+        let file_manager: id = msg_send![class!(NSFileManager), defaultManager];
+        let name = from_nsstring(msg_send![file_manager, displayNameAtPath: path]);
+        name.strip_suffix(".app").map(|n| n.into()).unwrap_or(name)
+    };
+    Ok(Some(DefaultHandler {
+        name,
+        is_current_application: own_bundle_identifier()
+            .map(|own| own.eq_ignore_ascii_case(&bundle_id))
+            .unwrap_or(false),
+        id: bundle_id,
+    }))
+}
+
+// System asks user to confirm changing the default handler
+pub fn set_as_default_handler(target: &HandlerTarget) -> PlatformResult<()> {
+    let bundle_id = CFString::new(&own_bundle_identifier().ok_or(PlatformError::NotAvailable)?);
+    let status = unsafe {
+        match target {
+            HandlerTarget::UrlScheme(scheme) => {
+                let scheme = CFString::new(scheme);
+                LSSetDefaultHandlerForURLScheme(
+                    scheme.as_concrete_TypeRef(),
+                    bundle_id.as_concrete_TypeRef(),
+                )
+            }
+            HandlerTarget::FileExtension(extension) => LSSetDefaultRoleHandlerForContentType(
+                uti_for_extension(extension).as_concrete_TypeRef(),
+                K_LS_ROLES_ALL,
+                bundle_id.as_concrete_TypeRef(),
+            ),
+        }
+    };
+    match status {
+        0 => Ok(()),
+        _ => Err(PlatformError::UnknownError),
+    }
+}
+
+unsafe fn open(url: id) -> PlatformResult<()> {
+    let workspace: id = msg_send![class!(NSWorkspace), sharedWorkspace];
+    let res: BOOL = msg_send![workspace, openURL: url];
+    if res == YES {
+        Ok(())
+    } else {
+        Err(PlatformError::UnknownError)
+    }
+}
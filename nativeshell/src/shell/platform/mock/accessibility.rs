@@ -0,0 +1,18 @@
+use std::rc::Rc;
+
+use crate::shell::{api_model::AccessibilitySettings, Context};
+
+use super::error::{PlatformError, PlatformResult};
+
+pub struct PlatformAccessibility {}
+
+#[allow(unused_variables)]
+impl PlatformAccessibility {
+    pub fn new(context: Rc<Context>) -> Self {
+        Self {}
+    }
+
+    pub fn get_settings(&self) -> PlatformResult<AccessibilitySettings> {
+        Err(PlatformError::NotImplemented)
+    }
+}
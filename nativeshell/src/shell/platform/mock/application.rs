@@ -0,0 +1,74 @@
+use std::rc::Rc;
+
+use crate::shell::{
+    api_model::{ActivationPolicy, JumpList, UserActivity},
+    Context,
+};
+
+use super::error::{PlatformError, PlatformResult};
+
+pub struct PlatformApplication {}
+
+#[allow(unused_variables)]
+impl PlatformApplication {
+    pub fn new(context: Rc<Context>) -> Self {
+        Self {}
+    }
+
+    pub fn set_prevent_sudden_termination(
+        &self,
+        prevent: bool,
+        reason: Option<&str>,
+    ) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn launch_at_login(&self, id: &str) -> PlatformResult<bool> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn set_launch_at_login(
+        &self,
+        id: &str,
+        enabled: bool,
+        start_hidden: bool,
+    ) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn started_hidden(&self) -> bool {
+        false
+    }
+
+    pub fn notify_startup_complete(&self) {}
+
+    pub fn set_jump_list(&self, jump_list: &JumpList) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn set_app_user_model_id(&self, id: &str) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn set_user_activity(&self, activity: Option<UserActivity>) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn set_activation_policy(&self, policy: ActivationPolicy) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn terminate(&self) {}
+
+    pub fn hide_application(&self) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn unhide_application(&self) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn hide_other_applications(&self) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+}
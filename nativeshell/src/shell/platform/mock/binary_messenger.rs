@@ -0,0 +1,101 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crate::shell::BinaryMessengerReply;
+
+use super::{
+    error::{PlatformError, PlatformResult},
+    recorder::{record, MockTarget},
+};
+
+type Handler = Rc<dyn Fn(&[u8], BinaryMessengerReply)>;
+type ReplyCallback = Box<dyn FnOnce(&[u8])>;
+
+// Shared by engine and all its messengers; Stands in for the Dart side
+pub(super) struct MessengerState {
+    pub engine: i64,
+    pub running: bool,
+    handlers: HashMap<String, Handler>,
+    // Messages sent to Dart; Replies are delivered through PlatformEngine::reply
+    sent: Vec<(String, Vec<u8>)>,
+    pending_replies: Vec<(String, ReplyCallback)>,
+}
+
+impl MessengerState {
+    pub fn new(engine: i64) -> Self {
+        Self {
+            engine,
+            running: false,
+            handlers: HashMap::new(),
+            sent: Vec::new(),
+            pending_replies: Vec::new(),
+        }
+    }
+
+    pub fn handler(&self, channel: &str) -> Option<Handler> {
+        self.handlers.get(channel).cloned()
+    }
+
+    pub fn take_sent(&mut self) -> Vec<(String, Vec<u8>)> {
+        std::mem::take(&mut self.sent)
+    }
+
+    // Oldest reply callback waiting for response on given channel
+    pub fn take_pending_reply(&mut self, channel: &str) -> Option<ReplyCallback> {
+        let index = self.pending_replies.iter().position(|r| r.0 == channel)?;
+        Some(self.pending_replies.remove(index).1)
+    }
+}
+
+pub struct PlatformBinaryMessenger {
+    state: Rc<RefCell<MessengerState>>,
+}
+
+impl PlatformBinaryMessenger {
+    pub(super) fn new(state: Rc<RefCell<MessengerState>>) -> Self {
+        Self { state }
+    }
+
+    fn target(&self) -> MockTarget {
+        MockTarget::Messenger(self.state.borrow().engine)
+    }
+
+    pub fn register_channel_handler<F>(&self, channel: &str, callback: F)
+    where
+        F: Fn(&[u8], BinaryMessengerReply) + 'static,
+    {
+        self.state
+            .borrow_mut()
+            .handlers
+            .insert(channel.into(), Rc::new(callback));
+    }
+
+    pub fn unregister_channel_handler(&self, channel: &str) {
+        self.state.borrow_mut().handlers.remove(channel);
+    }
+
+    pub fn send_message<F>(&self, channel: &str, message: &[u8], reply: F) -> PlatformResult<()>
+    where
+        F: FnOnce(&[u8]) + 'static,
+    {
+        self.post_message(channel, message)?;
+        self.state
+            .borrow_mut()
+            .pending_replies
+            .push((channel.into(), Box::new(reply)));
+        Ok(())
+    }
+
+    pub fn post_message(&self, channel: &str, message: &[u8]) -> PlatformResult<()> {
+        if !self.state.borrow().running {
+            return Err(PlatformError::SendMessageFailure {
+                channel: channel.into(),
+            });
+        }
+        record(self.target(), "send_message", channel.into());
+        self.state
+            .borrow_mut()
+            .sent
+            .push((channel.into(), message.into()));
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+use crate::shell::CrashDialogResponse;
+
+use super::error::{PlatformError, PlatformResult};
+
+#[allow(unused_variables)]
+pub fn install_exception_handler(handler: fn(String, String)) {}
+
+#[allow(unused_variables)]
+pub fn show_crash_dialog(
+    title: &str,
+    message: &str,
+    can_open_log_file: bool,
+) -> PlatformResult<CrashDialogResponse> {
+    Err(PlatformError::NotImplemented)
+}
+
+#[allow(unused_variables)]
+pub fn copy_to_clipboard(text: &str) -> PlatformResult<()> {
+    Err(PlatformError::NotImplemented)
+}
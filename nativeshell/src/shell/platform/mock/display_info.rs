@@ -0,0 +1,18 @@
+use std::rc::Rc;
+
+use crate::shell::{api_model::DisplayInfo, Context};
+
+use super::error::{PlatformError, PlatformResult};
+
+pub struct PlatformDisplayInfo {}
+
+#[allow(unused_variables)]
+impl PlatformDisplayInfo {
+    pub fn new(context: Rc<Context>) -> Self {
+        Self {}
+    }
+
+    pub fn get_displays(&self) -> PlatformResult<Vec<DisplayInfo>> {
+        Err(PlatformError::NotImplemented)
+    }
+}
@@ -0,0 +1,116 @@
+use std::{
+    cell::{Cell, RefCell},
+    ffi::{c_void, CStr},
+    rc::Rc,
+};
+
+use crate::shell::{BinaryMessengerReply, EngineOptions};
+
+use super::{
+    binary_messenger::{MessengerState, PlatformBinaryMessenger},
+    error::{PlatformError, PlatformResult},
+    native_view::NativeViewType,
+    recorder::{record, MockTarget},
+};
+
+thread_local! {
+    static NEXT_ENGINE_ID: Cell<i64> = const { Cell::new(1) };
+}
+
+// Engine without Dart VM; Tests act as the Dart side by simulating messages
+// from Dart and inspecting messages sent to it
+pub struct PlatformEngine {
+    id: i64,
+    messenger_state: Rc<RefCell<MessengerState>>,
+}
+
+impl PlatformEngine {
+    pub fn new(options: &EngineOptions) -> Self {
+        let id = NEXT_ENGINE_ID.with(|next| next.replace(next.get() + 1));
+        record(MockTarget::Engine(id), "new", format!("{:?}", options));
+        PlatformEngine {
+            id,
+            messenger_state: Rc::new(RefCell::new(MessengerState::new(id))),
+        }
+    }
+
+    pub fn new_headless(options: &EngineOptions) -> Self {
+        Self::new(options)
+    }
+
+    // Identifies engine in recorded operations
+    pub fn mock_id(&self) -> i64 {
+        self.id
+    }
+
+    pub fn attach_view(&mut self) -> PlatformResult<()> {
+        record(MockTarget::Engine(self.id), "attach_view", String::new());
+        Ok(())
+    }
+
+    // Mock view is identified by id of its engine
+    pub fn embedded_view(&self) -> PlatformResult<NativeViewType> {
+        record(MockTarget::Engine(self.id), "embedded_view", String::new());
+        Ok(self.id)
+    }
+
+    pub fn new_binary_messenger(&self) -> PlatformBinaryMessenger {
+        PlatformBinaryMessenger::new(self.messenger_state.clone())
+    }
+
+    pub fn with_plugin_registrar<F>(&self, _plugin_name: &CStr, _f: F) -> PlatformResult<()>
+    where
+        F: FnOnce(*mut c_void),
+    {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn launch(&mut self) -> PlatformResult<()> {
+        record(MockTarget::Engine(self.id), "launch", String::new());
+        self.messenger_state.borrow_mut().running = true;
+        Ok(())
+    }
+
+    pub fn shut_down(&mut self) -> PlatformResult<()> {
+        record(MockTarget::Engine(self.id), "shut_down", String::new());
+        self.messenger_state.borrow_mut().running = false;
+        Ok(())
+    }
+
+    // Delivers message as if sent from Dart; Returns false if there is no
+    // handler registered for the channel
+    pub fn simulate_message<F>(&self, channel: &str, message: &[u8], reply: F) -> bool
+    where
+        F: FnOnce(&[u8]) + 'static,
+    {
+        let handler = self.messenger_state.borrow().handler(channel);
+        match handler {
+            Some(handler) => {
+                handler(message, BinaryMessengerReply::new(reply));
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Messages sent to Dart (channel, message) since last call
+    pub fn take_sent_messages(&self) -> Vec<(String, Vec<u8>)> {
+        self.messenger_state.borrow_mut().take_sent()
+    }
+
+    // Replies to oldest message sent to Dart on given channel that is still
+    // waiting for reply; Returns false if there is no such message
+    pub fn simulate_reply(&self, channel: &str, reply: &[u8]) -> bool {
+        let callback = self
+            .messenger_state
+            .borrow_mut()
+            .take_pending_reply(channel);
+        match callback {
+            Some(callback) => {
+                callback(reply);
+                true
+            }
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt::Display;
+
+use crate::{
+    codec::Value,
+    shell::platform::{DragError, WindowError},
+};
+
+#[derive(Debug, Clone)]
+pub enum PlatformError {
+    NotImplemented,
+    UnknownError,
+    SendMessageFailure { channel: String },
+    Window(WindowError),
+    Drag(DragError),
+}
+
+pub type PlatformResult<T> = Result<T, PlatformError>;
+
+impl Display for PlatformError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl PlatformError {
+    // Stable error code reported to Dart
+    pub fn code(&self) -> &'static str {
+        match self {
+            PlatformError::NotImplemented => "not-implemented",
+            PlatformError::UnknownError => "unknown-error",
+            PlatformError::SendMessageFailure { .. } => "send-message-failure",
+            PlatformError::Window(error) => error.code(),
+            PlatformError::Drag(error) => error.code(),
+        }
+    }
+
+    pub fn details(&self) -> Value {
+        match self {
+            PlatformError::SendMessageFailure { channel } => channel.as_str().into(),
+            PlatformError::Window(error) => error.details(),
+            PlatformError::Drag(error) => error.details(),
+            _ => Value::Null,
+        }
+    }
+}
+
+impl std::error::Error for PlatformError {}
+
+impl From<WindowError> for PlatformError {
+    fn from(error: WindowError) -> Self {
+        PlatformError::Window(error)
+    }
+}
+
+impl From<DragError> for PlatformError {
+    fn from(error: DragError) -> Self {
+        PlatformError::Drag(error)
+    }
+}
@@ -0,0 +1,17 @@
+use crate::shell::api_model::HapticFeedbackPattern;
+
+use super::error::{PlatformError, PlatformResult};
+
+pub fn beep() -> PlatformResult<()> {
+    Err(PlatformError::NotImplemented)
+}
+
+#[allow(unused_variables)]
+pub fn play_system_sound(name: &str) -> PlatformResult<()> {
+    Err(PlatformError::NotImplemented)
+}
+
+#[allow(unused_variables)]
+pub fn perform_haptic_feedback(pattern: HapticFeedbackPattern) -> PlatformResult<()> {
+    Err(PlatformError::NotImplemented)
+}
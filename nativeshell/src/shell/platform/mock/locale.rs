@@ -0,0 +1,18 @@
+use std::rc::Rc;
+
+use crate::shell::{api_model::LocaleSettings, Context};
+
+use super::error::{PlatformError, PlatformResult};
+
+pub struct PlatformLocale {}
+
+#[allow(unused_variables)]
+impl PlatformLocale {
+    pub fn new(context: Rc<Context>) -> Self {
+        Self {}
+    }
+
+    pub fn get_settings(&self) -> PlatformResult<LocaleSettings> {
+        Err(PlatformError::NotImplemented)
+    }
+}
@@ -0,0 +1,84 @@
+use std::{
+    cell::{Ref, RefCell},
+    rc::{Rc, Weak},
+};
+
+use crate::shell::{
+    api_model::{Accelerator, Menu},
+    Context, MenuHandle, MenuManager,
+};
+
+use super::{
+    error::PlatformResult,
+    recorder::{record, MockTarget},
+};
+
+pub struct PlatformMenu {
+    context: Rc<Context>,
+    handle: MenuHandle,
+    menu: RefCell<Option<Menu>>,
+}
+
+impl PlatformMenu {
+    pub fn new(context: Rc<Context>, handle: MenuHandle) -> Self {
+        Self {
+            context,
+            handle,
+            menu: RefCell::new(None),
+        }
+    }
+
+    pub fn assign_weak_self(&self, _weak: Weak<PlatformMenu>) {}
+
+    pub fn handle(&self) -> MenuHandle {
+        self.handle
+    }
+
+    // Menu from last update
+    pub fn menu(&self) -> Ref<'_, Option<Menu>> {
+        self.menu.borrow()
+    }
+
+    pub fn update_from_menu(&self, menu: Menu, _manager: &MenuManager) -> PlatformResult<()> {
+        record(
+            MockTarget::Menu(self.handle.0),
+            "update_from_menu",
+            format!("{:?}", menu),
+        );
+        self.menu.replace(Some(menu));
+        Ok(())
+    }
+
+    // Reports item selection as if the user picked the item
+    pub fn simulate_item_selected(&self, id: i64) {
+        self.context
+            .menu_manager
+            .borrow()
+            .on_menu_action(self.handle, id);
+    }
+}
+
+pub struct PlatformMenuManager {}
+
+impl PlatformMenuManager {
+    pub fn new(_context: Rc<Context>) -> Self {
+        Self {}
+    }
+
+    pub fn has_native_window_list(&self) -> bool {
+        false
+    }
+
+    pub fn system_shortcuts(&self) -> Vec<(Accelerator, &'static str)> {
+        vec![(Accelerator::parse("Alt+F4"), "Close window")]
+    }
+
+    pub fn set_app_menu(&self, menu: Option<Rc<PlatformMenu>>) -> PlatformResult<()> {
+        record(
+            MockTarget::MenuManager,
+            "set_app_menu",
+            format!("{:?}", menu.map(|m| m.handle)),
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+pub mod accessibility;
+pub mod application;
+pub mod binary_messenger;
+pub mod crash_handler;
+pub mod display_info;
+pub mod drag_data;
+pub mod engine;
+pub mod error;
+pub mod event_tap;
+pub mod feedback;
+pub mod init;
+pub mod key_event;
+pub mod locale;
+pub mod menu;
+pub mod native_view;
+pub mod permission;
+pub mod power;
+pub mod recorder;
+pub mod render_layer;
+pub mod run_loop;
+pub mod screen_capture;
+pub mod single_instance;
+pub mod system_theme;
+pub mod texture;
+pub mod user_activity;
+pub mod window;
+pub mod window_list;
+pub mod workspace;
@@ -0,0 +1,47 @@
+use std::rc::Rc;
+
+use crate::shell::Rect;
+
+use super::{
+    error::PlatformResult,
+    recorder::{record, MockTarget},
+    window::PlatformWindow,
+};
+
+// Opaque id of the view; Operations on the view are recorded on its window
+pub type NativeViewType = i64;
+
+pub struct PlatformNativeView {
+    window: Rc<PlatformWindow>,
+    view: NativeViewType,
+}
+
+impl PlatformNativeView {
+    pub fn new(window: Rc<PlatformWindow>, view: NativeViewType) -> PlatformResult<Self> {
+        record(
+            MockTarget::Window(window.mock_id()),
+            "add_native_view",
+            format!("{}", view),
+        );
+        Ok(Self { window, view })
+    }
+
+    pub fn update(&self, frame: &Rect, clip: &Rect, visible: bool) -> PlatformResult<()> {
+        record(
+            MockTarget::Window(self.window.mock_id()),
+            "update_native_view",
+            format!("{} {:?} {:?} {}", self.view, frame, clip, visible),
+        );
+        Ok(())
+    }
+}
+
+impl Drop for PlatformNativeView {
+    fn drop(&mut self) {
+        record(
+            MockTarget::Window(self.window.mock_id()),
+            "remove_native_view",
+            format!("{}", self.view),
+        );
+    }
+}
@@ -0,0 +1,16 @@
+use crate::shell::api_model::{PermissionKind, PermissionStatus};
+
+use super::error::{PlatformError, PlatformResult};
+
+#[allow(unused_variables)]
+pub fn status(kind: PermissionKind) -> PlatformResult<PermissionStatus> {
+    Err(PlatformError::NotImplemented)
+}
+
+#[allow(unused_variables)]
+pub fn request(
+    kind: PermissionKind,
+    callback: Box<dyn FnOnce(PlatformResult<PermissionStatus>) + Send>,
+) {
+    callback(Err(PlatformError::NotImplemented))
+}
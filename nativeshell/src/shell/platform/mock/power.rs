@@ -0,0 +1,36 @@
+use std::rc::Rc;
+
+use crate::shell::{api_model::PowerStatus, ActivityOptions, Context, SleepPreventionKind};
+
+use super::error::{PlatformError, PlatformResult};
+
+pub struct PlatformSleepGuard {}
+
+#[allow(unused_variables)]
+impl PlatformSleepGuard {
+    pub fn new(kind: SleepPreventionKind, reason: &str) -> PlatformResult<Self> {
+        Err(PlatformError::NotImplemented)
+    }
+}
+
+pub struct PlatformActivityGuard {}
+
+#[allow(unused_variables)]
+impl PlatformActivityGuard {
+    pub fn new(options: ActivityOptions, reason: &str) -> PlatformResult<Self> {
+        Err(PlatformError::NotImplemented)
+    }
+}
+
+pub struct PlatformPowerSource {}
+
+#[allow(unused_variables)]
+impl PlatformPowerSource {
+    pub fn new(context: Rc<Context>) -> Self {
+        Self {}
+    }
+
+    pub fn get_status(&self) -> PlatformResult<PowerStatus> {
+        Err(PlatformError::NotImplemented)
+    }
+}
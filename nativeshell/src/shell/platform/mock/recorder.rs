@@ -0,0 +1,49 @@
+use std::cell::RefCell;
+
+// Object the operation was performed on; Windows and engines are identified by
+// ids assigned by mock platform, menus by their handle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockTarget {
+    Engine(i64),
+    Window(isize),
+    Menu(i64),
+    MenuManager,
+    Messenger(i64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockOperation {
+    pub target: MockTarget,
+    pub name: &'static str,
+    // Debug representation of arguments; Empty if operation has none
+    pub args: String,
+}
+
+thread_local! {
+    static OPERATIONS: RefCell<Vec<MockOperation>> = const { RefCell::new(Vec::new()) };
+}
+
+pub(super) fn record(target: MockTarget, name: &'static str, args: String) {
+    OPERATIONS.with(|operations| {
+        operations
+            .borrow_mut()
+            .push(MockOperation { target, name, args });
+    });
+}
+
+// Returns operations recorded on current thread since last call
+pub fn take_operations() -> Vec<MockOperation> {
+    OPERATIONS.with(|operations| operations.take())
+}
+
+// Recorded operations (without removing them) with given target
+pub fn operations_for(target: MockTarget) -> Vec<MockOperation> {
+    OPERATIONS.with(|operations| {
+        operations
+            .borrow()
+            .iter()
+            .filter(|o| o.target == target)
+            .cloned()
+            .collect()
+    })
+}
@@ -0,0 +1,25 @@
+use std::rc::Rc;
+
+use crate::shell::{RenderLayerPosition, RenderLayerResizeCallback};
+
+use super::{
+    error::{PlatformError, PlatformResult},
+    window::PlatformWindow,
+};
+
+pub struct PlatformRenderLayer {}
+
+#[allow(unused_variables)]
+impl PlatformRenderLayer {
+    pub fn new(
+        window: Rc<PlatformWindow>,
+        position: RenderLayerPosition,
+        on_resize: Box<RenderLayerResizeCallback>,
+    ) -> PlatformResult<Self> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn native_handle(&self) -> usize {
+        0
+    }
+}
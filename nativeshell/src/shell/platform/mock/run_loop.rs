@@ -0,0 +1,8 @@
+// Mock platform has no native run loop; Tests drive virtual time instead
+pub use crate::shell::VirtualRunLoop as PlatformRunLoop;
+pub(in crate::shell) use crate::shell::VirtualRunLoopSender as PlatformRunLoopSender;
+
+pub type HandleType = usize;
+pub const INVALID_HANDLE: HandleType = 0;
+
+pub type EventSourceType = i64;
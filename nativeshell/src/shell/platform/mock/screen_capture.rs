@@ -0,0 +1,26 @@
+use std::sync::Arc;
+
+use crate::shell::{ScreenCaptureOptions, ScreenCaptureSink, ScreenCaptureSource};
+
+use super::error::{PlatformError, PlatformResult};
+
+pub struct PlatformScreenCapture {}
+
+#[allow(unused_variables)]
+impl PlatformScreenCapture {
+    pub fn new(
+        source: ScreenCaptureSource,
+        options: ScreenCaptureOptions,
+        sink: Arc<ScreenCaptureSink>,
+    ) -> PlatformResult<Self> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn start(&self) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn stop(&self) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+}
@@ -0,0 +1,19 @@
+use std::rc::Rc;
+
+use crate::shell::{api_model::InstanceActivation, Context};
+
+use super::error::{PlatformError, PlatformResult};
+
+pub struct PlatformSingleInstance {}
+
+#[allow(unused_variables)]
+impl PlatformSingleInstance {
+    pub fn acquire(
+        context: &Rc<Context>,
+        id: &str,
+        activation: &InstanceActivation,
+        on_activation: Box<dyn Fn(InstanceActivation)>,
+    ) -> PlatformResult<Option<Self>> {
+        Err(PlatformError::NotImplemented)
+    }
+}
@@ -0,0 +1,18 @@
+use std::rc::Rc;
+
+use crate::shell::{api_model::SystemThemeSettings, Context};
+
+use super::error::{PlatformError, PlatformResult};
+
+pub struct PlatformSystemTheme {}
+
+#[allow(unused_variables)]
+impl PlatformSystemTheme {
+    pub fn new(context: Rc<Context>) -> Self {
+        Self {}
+    }
+
+    pub fn get_settings(&self) -> PlatformResult<SystemThemeSettings> {
+        Err(PlatformError::NotImplemented)
+    }
+}
@@ -0,0 +1,31 @@
+use crate::shell::{GpuSurfaceKind, GpuSurfaceProvider, SharedPixelBuffer};
+
+use super::{
+    engine::PlatformEngine,
+    error::{PlatformError, PlatformResult},
+};
+
+pub struct PlatformTexture {}
+
+#[allow(unused_variables)]
+impl PlatformTexture {
+    pub fn new(engine: &PlatformEngine, buffer: SharedPixelBuffer) -> PlatformResult<Self> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn new_gpu_surface(
+        engine: &PlatformEngine,
+        kind: GpuSurfaceKind,
+        provider: Box<GpuSurfaceProvider>,
+    ) -> PlatformResult<Self> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn id(&self) -> i64 {
+        0
+    }
+
+    pub fn mark_frame_available(&self) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+}
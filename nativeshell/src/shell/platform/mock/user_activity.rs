@@ -0,0 +1,7 @@
+use std::time::Duration;
+
+use super::error::{PlatformError, PlatformResult};
+
+pub fn idle_time() -> PlatformResult<Duration> {
+    Err(PlatformError::NotImplemented)
+}
@@ -0,0 +1,424 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::{Rc, Weak},
+};
+
+use crate::{
+    codec::Value,
+    shell::{
+        api_model::{
+            ActivationPolicy, DragEffect, DragRequest, NativeWindowContent, PointerState,
+            PopupMenuRequest, PopupMenuResponse, QuickLookRequest, SharePickerRequest, TouchBar,
+            WindowAppearance, WindowBackdrop, WindowGeometry, WindowGeometryFlags,
+            WindowGeometryRequest, WindowStyle, WindowType,
+        },
+        Context, PlatformWindowDelegate, Rect, SyntheticEvent, SyntheticMouseButton,
+    },
+};
+
+use super::{
+    engine::PlatformEngine,
+    error::{PlatformError, PlatformResult},
+    menu::PlatformMenu,
+    recorder::{record, MockTarget},
+};
+
+// Mock id of the window
+pub type PlatformWindowType = isize;
+
+thread_local! {
+    static NEXT_WINDOW_ID: Cell<isize> = const { Cell::new(1) };
+}
+
+type ModalCallback = Box<dyn FnOnce(PlatformResult<Value>)>;
+type PopupMenuCallback = Box<dyn FnOnce(PlatformResult<PopupMenuResponse>)>;
+
+// Window that is never realized; Records all operations and keeps geometry and
+// visibility so that they can be queried back. Platform events are simulated
+// through `simulate`.
+pub struct PlatformWindow {
+    id: isize,
+    delegate: Weak<dyn PlatformWindowDelegate>,
+    geometry: RefCell<WindowGeometry>,
+    ready_to_show: Cell<bool>,
+    show_when_ready: Cell<bool>,
+    visible: Cell<bool>,
+    pointer_state: RefCell<PointerState>,
+    injected_events: RefCell<Vec<SyntheticEvent>>,
+    modal_close_callback: RefCell<Option<ModalCallback>>,
+    popup_menu_callback: RefCell<Option<PopupMenuCallback>>,
+}
+
+impl PlatformWindow {
+    pub fn new(
+        _context: Rc<Context>,
+        delegate: Weak<dyn PlatformWindowDelegate>,
+        parent: Option<Rc<PlatformWindow>>,
+    ) -> Self {
+        let id = NEXT_WINDOW_ID.with(|next| next.replace(next.get() + 1));
+        record(
+            MockTarget::Window(id),
+            "new",
+            format!("parent: {:?}", parent.map(|p| p.id)),
+        );
+        Self {
+            id,
+            delegate,
+            geometry: RefCell::new(WindowGeometry {
+                frame_origin: None,
+                frame_size: None,
+                content_origin: None,
+                content_size: None,
+                min_frame_size: None,
+                max_frame_size: None,
+                min_content_size: None,
+                max_content_size: None,
+            }),
+            ready_to_show: Cell::new(false),
+            show_when_ready: Cell::new(false),
+            visible: Cell::new(false),
+            pointer_state: RefCell::new(Default::default()),
+            injected_events: RefCell::new(Vec::new()),
+            modal_close_callback: RefCell::new(None),
+            popup_menu_callback: RefCell::new(None),
+        }
+    }
+
+    fn record(&self, name: &'static str, args: String) {
+        record(MockTarget::Window(self.id), name, args);
+    }
+
+    // Identifies window in recorded operations
+    pub fn mock_id(&self) -> isize {
+        self.id
+    }
+
+    pub fn assign_weak_self(&self, _weak: Weak<PlatformWindow>, engine: &PlatformEngine) {
+        self.record("assign_engine", format!("{}", engine.mock_id()));
+    }
+
+    pub fn assign_weak_self_native(
+        &self,
+        _weak: Weak<PlatformWindow>,
+        content: &NativeWindowContent,
+    ) {
+        self.record("assign_native_content", format!("{:?}", content));
+    }
+
+    pub fn get_platform_window(&self) -> PlatformWindowType {
+        self.id
+    }
+
+    // Invokes delegate as if platform reported an event
+    pub fn simulate<F>(&self, f: F)
+    where
+        F: FnOnce(&dyn PlatformWindowDelegate),
+    {
+        if let Some(delegate) = self.delegate.upgrade() {
+            f(delegate.as_ref());
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible.get()
+    }
+
+    pub fn set_pointer_state(&self, state: PointerState) {
+        self.pointer_state.replace(state);
+    }
+
+    // Events injected through test driver since last call
+    pub fn take_injected_events(&self) -> Vec<SyntheticEvent> {
+        self.injected_events.take()
+    }
+
+    // Completes pending popup menu; Returns false if no menu is shown
+    pub fn complete_popup_menu(&self, response: PopupMenuResponse) -> bool {
+        let callback = self.popup_menu_callback.borrow_mut().take();
+        match callback {
+            Some(callback) => {
+                callback(Ok(response));
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn set_visible(&self, visible: bool) {
+        if self.visible.replace(visible) != visible {
+            self.simulate(|d| d.visibility_changed(visible));
+        }
+    }
+
+    pub fn show(&self) -> PlatformResult<()> {
+        self.record("show", String::new());
+        if self.ready_to_show.get() {
+            self.set_visible(true);
+        } else {
+            self.show_when_ready.set(true);
+        }
+        Ok(())
+    }
+
+    pub fn ready_to_show(&self) -> PlatformResult<()> {
+        self.record("ready_to_show", String::new());
+        self.ready_to_show.set(true);
+        if self.show_when_ready.replace(false) {
+            self.set_visible(true);
+        }
+        Ok(())
+    }
+
+    pub fn close(&self) -> PlatformResult<()> {
+        self.record("close", String::new());
+        let callback = self.modal_close_callback.borrow_mut().take();
+        if let Some(callback) = callback {
+            callback(Ok(Value::Null));
+        }
+        self.set_visible(false);
+        self.simulate(|d| d.will_close());
+        Ok(())
+    }
+
+    pub fn close_with_result(&self, result: Value) -> PlatformResult<()> {
+        self.record("close_with_result", format!("{:?}", result));
+        let callback = self.modal_close_callback.borrow_mut().take();
+        if let Some(callback) = callback {
+            callback(Ok(result));
+        }
+        self.close()
+    }
+
+    pub fn hide(&self) -> PlatformResult<()> {
+        self.record("hide", String::new());
+        self.show_when_ready.set(false);
+        self.set_visible(false);
+        Ok(())
+    }
+
+    pub fn activate(&self) -> PlatformResult<()> {
+        self.record("activate", String::new());
+        Ok(())
+    }
+
+    pub fn set_activation_policy(&self, policy: ActivationPolicy) -> PlatformResult<()> {
+        self.record("set_activation_policy", format!("{:?}", policy));
+        Ok(())
+    }
+
+    pub fn show_modal<F>(&self, done_callback: F)
+    where
+        F: FnOnce(PlatformResult<Value>) + 'static,
+    {
+        self.modal_close_callback
+            .replace(Some(Box::new(done_callback)));
+        self.show().ok();
+    }
+
+    pub fn set_geometry(
+        &self,
+        geometry: WindowGeometryRequest,
+    ) -> PlatformResult<WindowGeometryFlags> {
+        self.record("set_geometry", format!("{:?}", geometry));
+        let geometry = geometry.filtered_by_preference();
+        let mut current = self.geometry.borrow_mut();
+        macro_rules! apply {
+            ($($field:ident),*) => {
+                $(if geometry.$field.is_some() {
+                    current.$field = geometry.$field.clone();
+                })*
+            };
+        }
+        apply!(
+            frame_origin,
+            frame_size,
+            content_origin,
+            content_size,
+            min_frame_size,
+            max_frame_size,
+            min_content_size,
+            max_content_size
+        );
+        self.supported_geometry()
+    }
+
+    pub fn get_geometry(&self) -> PlatformResult<WindowGeometry> {
+        Ok(self.geometry.borrow().clone())
+    }
+
+    pub fn supported_geometry(&self) -> PlatformResult<WindowGeometryFlags> {
+        Ok(WindowGeometryFlags {
+            frame_origin: true,
+            frame_size: true,
+            content_origin: true,
+            content_size: true,
+            min_frame_size: true,
+            max_frame_size: true,
+            min_content_size: true,
+            max_content_size: true,
+        })
+    }
+
+    pub fn set_title(&self, title: String) -> PlatformResult<()> {
+        self.record("set_title", title);
+        Ok(())
+    }
+
+    pub fn set_subtitle(&self, subtitle: String) -> PlatformResult<()> {
+        self.record("set_subtitle", subtitle);
+        Ok(())
+    }
+
+    pub fn set_represented_file(&self, path: Option<String>) -> PlatformResult<()> {
+        self.record("set_represented_file", format!("{:?}", path));
+        Ok(())
+    }
+
+    pub fn set_document_edited(&self, edited: bool) -> PlatformResult<()> {
+        self.record("set_document_edited", format!("{}", edited));
+        Ok(())
+    }
+
+    pub fn set_appearance(&self, appearance: WindowAppearance) -> PlatformResult<()> {
+        self.record("set_appearance", format!("{:?}", appearance));
+        Ok(())
+    }
+
+    pub fn set_backdrop(&self, backdrop: WindowBackdrop) -> PlatformResult<()> {
+        self.record("set_backdrop", format!("{:?}", backdrop));
+        Ok(())
+    }
+
+    pub fn set_window_type(&self, window_type: WindowType) -> PlatformResult<()> {
+        self.record("set_window_type", format!("{:?}", window_type));
+        Ok(())
+    }
+
+    pub fn set_style(&self, style: WindowStyle) -> PlatformResult<()> {
+        self.record("set_style", format!("{:?}", style));
+        Ok(())
+    }
+
+    pub fn perform_window_drag(&self) -> PlatformResult<()> {
+        self.record("perform_window_drag", String::new());
+        Ok(())
+    }
+
+    pub fn show_share_picker(&self, request: SharePickerRequest) -> PlatformResult<()> {
+        self.record("show_share_picker", format!("{:?}", request));
+        Ok(())
+    }
+
+    pub fn show_quick_look(&self, request: QuickLookRequest) -> PlatformResult<()> {
+        self.record("show_quick_look", format!("{:?}", request));
+        Ok(())
+    }
+
+    pub fn set_quick_look_index(&self, index: usize) -> PlatformResult<()> {
+        self.record("set_quick_look_index", format!("{}", index));
+        Ok(())
+    }
+
+    pub fn hide_quick_look(&self) -> PlatformResult<()> {
+        self.record("hide_quick_look", String::new());
+        Ok(())
+    }
+
+    // Drag session ends when test simulates drag_ended on delegate
+    pub fn begin_drag_session(&self, request: DragRequest) -> PlatformResult<()> {
+        self.record("begin_drag_session", format!("{:?}", request.data));
+        Ok(())
+    }
+
+    // Updates pointer state the way platform would; Keyboard events are only recorded
+    pub fn inject_event(&self, event: &SyntheticEvent) -> PlatformResult<()> {
+        self.record("inject_event", format!("{:?}", event));
+        self.injected_events.borrow_mut().push(event.clone());
+        let button_bit = |button: &SyntheticMouseButton| match button {
+            SyntheticMouseButton::Left => 1,
+            SyntheticMouseButton::Right => 2,
+            SyntheticMouseButton::Middle => 4,
+        };
+        let mut state = self.pointer_state.borrow_mut();
+        match event {
+            SyntheticEvent::MouseDown { position, button } => {
+                state.position = Some(position.clone());
+                state.buttons |= button_bit(button);
+            }
+            SyntheticEvent::MouseUp { position, button } => {
+                state.position = Some(position.clone());
+                state.buttons &= !button_bit(button);
+            }
+            SyntheticEvent::MouseMove { position } => {
+                state.position = Some(position.clone());
+            }
+            SyntheticEvent::KeyDown { .. } | SyntheticEvent::KeyUp { .. } => {}
+        }
+        Ok(())
+    }
+
+    pub fn pointer_state(&self) -> PointerState {
+        self.pointer_state.borrow().clone()
+    }
+
+    pub fn set_pending_effect(&self, effect: DragEffect) {
+        self.record("set_pending_effect", format!("{:?}", effect));
+    }
+
+    pub fn show_popup_menu<F>(&self, menu: Rc<PlatformMenu>, request: PopupMenuRequest, on_done: F)
+    where
+        F: FnOnce(PlatformResult<PopupMenuResponse>) + 'static,
+    {
+        self.record(
+            "show_popup_menu",
+            format!("{:?} at {:?}", menu.handle(), request.position),
+        );
+        let previous = self.popup_menu_callback.replace(Some(Box::new(on_done)));
+        if let Some(previous) = previous {
+            previous(Ok(PopupMenuResponse {
+                item_selected: false,
+            }));
+        }
+    }
+
+    pub fn hide_popup_menu(&self, menu: Rc<PlatformMenu>) -> PlatformResult<()> {
+        self.record("hide_popup_menu", format!("{:?}", menu.handle()));
+        self.complete_popup_menu(PopupMenuResponse {
+            item_selected: false,
+        });
+        Ok(())
+    }
+
+    pub fn show_system_menu(&self) -> PlatformResult<()> {
+        Err(PlatformError::NotImplemented)
+    }
+
+    pub fn set_maximize_button_rect(&self, rect: Option<Rect>) -> PlatformResult<()> {
+        self.record("set_maximize_button_rect", format!("{:?}", rect));
+        Ok(())
+    }
+
+    pub fn set_app_user_model_id(&self, id: Option<String>) -> PlatformResult<()> {
+        self.record("set_app_user_model_id", format!("{:?}", id));
+        Ok(())
+    }
+
+    pub fn is_on_active_space(&self) -> PlatformResult<bool> {
+        Ok(true)
+    }
+
+    pub fn move_to_active_space(&self) -> PlatformResult<()> {
+        self.record("move_to_active_space", String::new());
+        Ok(())
+    }
+
+    pub fn set_window_menu(&self, menu: Option<Rc<PlatformMenu>>) -> PlatformResult<()> {
+        self.record("set_window_menu", format!("{:?}", menu.map(|m| m.handle())));
+        Ok(())
+    }
+
+    pub fn set_touch_bar(&self, touch_bar: Option<TouchBar>) -> PlatformResult<()> {
+        self.record("set_touch_bar", format!("{}", touch_bar.is_some()));
+        Ok(())
+    }
+}
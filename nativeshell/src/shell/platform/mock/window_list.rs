@@ -0,0 +1,8 @@
+use crate::shell::api_model::{ExternalWindow, WindowListRequest};
+
+use super::error::{PlatformError, PlatformResult};
+
+#[allow(unused_variables)]
+pub fn get_windows(request: &WindowListRequest) -> PlatformResult<Vec<ExternalWindow>> {
+    Err(PlatformError::NotImplemented)
+}
@@ -0,0 +1,39 @@
+use crate::shell::api_model::{DefaultHandler, DropFilter, HandlerTarget};
+
+use super::error::{PlatformError, PlatformResult};
+
+#[allow(unused_variables)]
+pub fn open_url(url: &str) -> PlatformResult<()> {
+    Err(PlatformError::NotImplemented)
+}
+
+#[allow(unused_variables)]
+pub fn open_path(path: &str) -> PlatformResult<()> {
+    Err(PlatformError::NotImplemented)
+}
+
+#[allow(unused_variables)]
+pub fn reveal_in_file_manager(path: &str) -> PlatformResult<()> {
+    Err(PlatformError::NotImplemented)
+}
+
+#[allow(unused_variables)]
+pub fn trash_items(paths: &[String]) -> PlatformResult<Vec<Option<String>>> {
+    Err(PlatformError::NotImplemented)
+}
+
+// Only extensions are matched in mock
+#[allow(unused_variables)]
+pub fn file_conforms_to(path: &str, filter: &DropFilter) -> bool {
+    false
+}
+
+#[allow(unused_variables)]
+pub fn default_handler(target: &HandlerTarget) -> PlatformResult<Option<DefaultHandler>> {
+    Err(PlatformError::NotImplemented)
+}
+
+#[allow(unused_variables)]
+pub fn set_as_default_handler(target: &HandlerTarget) -> PlatformResult<()> {
+    Err(PlatformError::NotImplemented)
+}
@@ -1,18 +1,29 @@
 pub use self::platform_impl::*;
 
-// #[path = "null/mod.rs"]
-// mod platform_impl;
+// Headless implementation that records operations and can simulate platform
+// events; Used for unit tests on any OS without display server
+// (cargo test --no-default-features --features mock)
+#[cfg(feature = "mock")]
+#[path = "mock/mod.rs"]
+mod platform_impl;
 
-#[cfg(target_os = "macos")]
+#[cfg(all(target_os = "macos", not(feature = "mock")))]
 #[path = "macos/mod.rs"]
 mod platform_impl;
 
-#[cfg(target_os = "windows")]
+#[cfg(all(target_os = "windows", not(feature = "mock")))]
 #[path = "win32/mod.rs"]
 mod platform_impl;
 
-#[cfg(target_os = "linux")]
+#[cfg(all(target_os = "linux", not(feature = "mock"), feature = "gtk"))]
 #[path = "linux/mod.rs"]
 mod platform_impl;
 
-mod key_interceptor;
\ No newline at end of file
+#[cfg(all(target_os = "linux", not(feature = "mock"), not(feature = "gtk")))]
+compile_error!("Linux platform backend requires \"gtk\" feature");
+
+#[cfg(not(feature = "mock"))]
+mod key_interceptor;
+
+mod subsystem_error;
+pub use subsystem_error::*;
@@ -1,9 +0,0 @@
-pub mod binary_messenger;
-pub mod drag_data;
-pub mod engine;
-pub mod error;
-pub mod init;
-pub mod key_event;
-pub mod menu;
-pub mod run_loop;
-pub mod window;
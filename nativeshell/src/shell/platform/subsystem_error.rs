@@ -0,0 +1,124 @@
+use std::fmt::Display;
+
+use velcro::hash_map;
+
+use crate::codec::Value;
+
+// Errors shared by all platform implementations, grouped by subsystem. Each
+// platform PlatformError wraps these next to its OS specific variants.
+
+#[derive(Debug, Clone)]
+pub enum WindowError {
+    // Flutter view layer has contents of a class we don't know how to measure
+    UnexpectedContent { class: String },
+    // Window was released while operation was in progress
+    WindowGone,
+    // Native window or view has not been created yet
+    NotRealized,
+}
+
+impl Display for WindowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WindowError::UnexpectedContent { class } => {
+                write!(f, "Unexpected window content class {}", class)
+            }
+            WindowError::WindowGone => {
+                write!(f, "Window no longer exists")
+            }
+            WindowError::NotRealized => {
+                write!(f, "Window is not realized")
+            }
+        }
+    }
+}
+
+impl WindowError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            WindowError::UnexpectedContent { .. } => "window-unexpected-content",
+            WindowError::WindowGone => "window-gone",
+            WindowError::NotRealized => "window-not-realized",
+        }
+    }
+
+    pub fn details(&self) -> Value {
+        match self {
+            WindowError::UnexpectedContent { class } => class.as_str().into(),
+            _ => Value::Null,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum DragError {
+    // Value under drag data key can not be converted to platform format
+    InvalidData { key: String, message: String },
+    // Drag image can not be converted to platform image
+    InvalidImage { message: String },
+}
+
+impl Display for DragError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DragError::InvalidData { key, message } => {
+                write!(f, "Invalid drag data for key {}: {}", key, message)
+            }
+            DragError::InvalidImage { message } => {
+                write!(f, "Invalid drag image: {}", message)
+            }
+        }
+    }
+}
+
+impl DragError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            DragError::InvalidData { .. } => "drag-invalid-data",
+            DragError::InvalidImage { .. } => "drag-invalid-image",
+        }
+    }
+
+    pub fn details(&self) -> Value {
+        match self {
+            DragError::InvalidData { key, message } => Value::Map(hash_map! {
+                "key".into(): key.as_str().into(),
+                "message".into(): message.as_str().into(),
+            }),
+            DragError::InvalidImage { message } => message.as_str().into(),
+        }
+    }
+}
+
+// Convenience for drag data adapters; Expects list of strings under given key
+#[cfg(not(feature = "mock"))]
+pub(crate) fn extract_drag_string(key: &str, value: Value) -> Result<String, DragError> {
+    match value {
+        Value::String(value) => Ok(value),
+        value => Err(DragError::InvalidData {
+            key: key.into(),
+            message: format!("Expected string, found {:?}", value),
+        }),
+    }
+}
+
+#[cfg(not(feature = "mock"))]
+pub(crate) fn extract_drag_string_list(key: &str, value: Value) -> Result<Vec<String>, DragError> {
+    let invalid = |message: String| DragError::InvalidData {
+        key: key.into(),
+        message,
+    };
+    match value {
+        Value::List(list) => list
+            .into_iter()
+            .map(|value| match value {
+                Value::String(value) => Ok(value),
+                value => Err(invalid(format!("Expected string, found {:?}", value))),
+            })
+            .collect(),
+        value => Err(invalid(format!(
+            "Expected list of strings, found {:?}",
+            value
+        ))),
+    }
+}
@@ -0,0 +1,79 @@
+use std::{ffi::c_void, mem::size_of, rc::Rc};
+
+use crate::shell::{api_model::AccessibilitySettings, Context};
+
+use super::{
+    all_bindings::*,
+    error::{PlatformError, PlatformResult},
+    util::{get_user_registry_dword, HRESULT_FROM_WIN32},
+};
+
+pub struct PlatformAccessibility {}
+
+// Cursor size when "Make the mouse pointer bigger" is not used
+const DEFAULT_CURSOR_SIZE: f64 = 32.0;
+
+// Settings changes are reported through WM_SETTINGCHANGE, which is handled in
+// PlatformWindow.
+impl PlatformAccessibility {
+    pub fn new(_context: Rc<Context>) -> Self {
+        Self {}
+    }
+
+    pub fn get_settings(&self) -> PlatformResult<AccessibilitySettings> {
+        unsafe {
+            let mut keyboard_pref = BOOL(0);
+            Self::system_parameter(SPI_GETKEYBOARDPREF, 0, &mut keyboard_pref)?;
+
+            let mut client_area_animation = BOOL(1);
+            Self::system_parameter(SPI_GETCLIENTAREAANIMATION, 0, &mut client_area_animation)?;
+
+            let mut high_contrast = HIGHCONTRASTW {
+                cbSize: size_of::<HIGHCONTRASTW>() as u32,
+                ..Default::default()
+            };
+            Self::system_parameter(
+                SPI_GETHIGHCONTRAST,
+                high_contrast.cbSize,
+                &mut high_contrast,
+            )?;
+
+            Ok(AccessibilitySettings {
+                full_keyboard_access: keyboard_pref.as_bool(),
+                reduce_motion: !client_area_animation.as_bool(),
+                reduce_transparency: !Self::transparency_enabled(),
+                high_contrast: high_contrast.dwFlags.0 & HCF_HIGHCONTRASTON.0 != 0,
+                cursor_size: GetSystemMetrics(SM_CXCURSOR) as f64 / DEFAULT_CURSOR_SIZE,
+            })
+        }
+    }
+
+    unsafe fn system_parameter<T>(
+        action: SYSTEM_PARAMETERS_INFO_ACTION,
+        ui_param: u32,
+        value: &mut T,
+    ) -> PlatformResult<()> {
+        let res = SystemParametersInfoW(
+            action,
+            ui_param,
+            value as *mut T as *mut c_void,
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        );
+        if res.as_bool() {
+            Ok(())
+        } else {
+            Err(PlatformError::HResult(HRESULT_FROM_WIN32(GetLastError().0)))
+        }
+    }
+
+    // Transparency effects can be turned off in Settings -> Personalization -> Colors;
+    // There is no system parameter for it
+    fn transparency_enabled() -> bool {
+        get_user_registry_dword(
+            "Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize",
+            "EnableTransparency",
+        )
+        .unwrap_or(1)
+            != 0
+    }
+}
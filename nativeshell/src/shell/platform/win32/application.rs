@@ -0,0 +1,204 @@
+use std::{
+    cell::Cell,
+    rc::{Rc, Weak},
+};
+
+use crate::shell::{
+    api_model::{ActivationPolicy, JumpList, TerminationReason, UserActivity},
+    Context, START_HIDDEN_ARGUMENT,
+};
+
+use super::{
+    all_bindings::*,
+    bindings::Windows::Win32::System::Shutdown::{
+        ShutdownBlockReasonCreate, ShutdownBlockReasonDestroy,
+    },
+    error::{PlatformError, PlatformResult},
+    jump_list,
+    util::{to_utf16, HRESULTExt, HRESULT_FROM_WIN32},
+    window_adapter::WindowAdapter,
+};
+
+// Not included in generated bindings
+const ERROR_FILE_NOT_FOUND: i32 = 2;
+
+const RUN_KEY: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\Run";
+
+// URLs and files are passed to the application as command line arguments.
+// Hidden top-level window receives session end queries (message-only windows
+// don't get broadcast messages).
+pub struct PlatformApplication {
+    session_window: Box<SessionWindow>,
+}
+
+struct SessionWindow {
+    context: Weak<Context>,
+    hwnd: Cell<HWND>,
+    prevent_sudden_termination: Cell<bool>,
+}
+
+impl PlatformApplication {
+    pub fn new(context: Rc<Context>) -> Self {
+        let session_window = Box::new(SessionWindow {
+            context: Rc::downgrade(&context),
+            hwnd: Cell::new(HWND(0)),
+            prevent_sudden_termination: Cell::new(false),
+        });
+        let hwnd = session_window.create_window_custom(
+            "nativeshell_session",
+            WINDOW_STYLE(0),
+            WINDOW_EX_STYLE(0),
+        );
+        session_window.hwnd.set(hwnd);
+        Self { session_window }
+    }
+
+    // Blocks logout and shutdown; Reason is shown by the system while blocked
+    pub fn set_prevent_sudden_termination(
+        &self,
+        prevent: bool,
+        reason: Option<&str>,
+    ) -> PlatformResult<()> {
+        let hwnd = self.session_window.hwnd.get();
+        unsafe {
+            if prevent {
+                ShutdownBlockReasonCreate(hwnd, reason.unwrap_or(""));
+            } else if self.session_window.prevent_sudden_termination.get() {
+                ShutdownBlockReasonDestroy(hwnd);
+            }
+        }
+        self.session_window.prevent_sudden_termination.set(prevent);
+        Ok(())
+    }
+
+    // Application is started at login through value in user's Run registry key
+    pub fn launch_at_login(&self, id: &str) -> PlatformResult<bool> {
+        let mut key = to_utf16(RUN_KEY);
+        let mut value = to_utf16(id);
+        let status = unsafe {
+            RegGetValueW(
+                HKEY_CURRENT_USER,
+                PWSTR(key.as_mut_ptr()),
+                PWSTR(value.as_mut_ptr()),
+                RRF_RT_REG_SZ,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            )
+        };
+        Ok(status.0 == 0)
+    }
+
+    pub fn set_launch_at_login(
+        &self,
+        id: &str,
+        enabled: bool,
+        start_hidden: bool,
+    ) -> PlatformResult<()> {
+        let mut key = to_utf16(RUN_KEY);
+        let mut value = to_utf16(id);
+        if !enabled {
+            let status = unsafe {
+                RegDeleteKeyValueW(
+                    HKEY_CURRENT_USER,
+                    PWSTR(key.as_mut_ptr()),
+                    PWSTR(value.as_mut_ptr()),
+                )
+            };
+            return match status.0 {
+                0 | ERROR_FILE_NOT_FOUND => Ok(()),
+                status => Err(PlatformError::HResult(HRESULT_FROM_WIN32(status as u32))),
+            };
+        }
+        let executable = std::env::current_exe().map_err(|_| PlatformError::UnknownError)?;
+        let mut command = format!("\"{}\"", executable.to_string_lossy());
+        if start_hidden {
+            command.push(' ');
+            command.push_str(START_HIDDEN_ARGUMENT);
+        }
+        let command = to_utf16(&command);
+        let status = unsafe {
+            RegSetKeyValueW(
+                HKEY_CURRENT_USER,
+                PWSTR(key.as_mut_ptr()),
+                PWSTR(value.as_mut_ptr()),
+                REG_SZ.0,
+                command.as_ptr() as *const _,
+                (command.len() * std::mem::size_of::<u16>()) as u32,
+            )
+        };
+        match status.0 {
+            0 => Ok(()),
+            status => Err(PlatformError::HResult(HRESULT_FROM_WIN32(status as u32))),
+        }
+    }
+
+    // Start hidden flag is passed as command line argument
+    pub fn started_hidden(&self) -> bool {
+        false
+    }
+
+    // Starting cursor is reset by the system once message loop runs
+    pub fn notify_startup_complete(&self) {}
+
+    pub fn set_jump_list(&self, jump_list: &JumpList) -> PlatformResult<()> {
+        jump_list::set_jump_list(jump_list)
+    }
+
+    // Identifies the application for taskbar grouping, jump list and notifications;
+    // Should be set before any window is shown
+    pub fn set_app_user_model_id(&self, id: &str) -> PlatformResult<()> {
+        unsafe { SetCurrentProcessExplicitAppUserModelID(id).as_platform_result() }
+    }
+
+    pub fn set_user_activity(&self, _activity: Option<UserActivity>) -> PlatformResult<()> {
+        Err(PlatformError::NotAvailable)
+    }
+
+    // Taskbar visibility is set on individual windows
+    pub fn set_activation_policy(&self, _policy: ActivationPolicy) -> PlatformResult<()> {
+        Ok(())
+    }
+
+    // Application manager stops the run loop
+    pub fn terminate(&self) {}
+
+    // Hiding application is macOS only
+    pub fn hide_application(&self) -> PlatformResult<()> {
+        Err(PlatformError::NotAvailable)
+    }
+
+    pub fn unhide_application(&self) -> PlatformResult<()> {
+        Err(PlatformError::NotAvailable)
+    }
+
+    pub fn hide_other_applications(&self) -> PlatformResult<()> {
+        Err(PlatformError::NotAvailable)
+    }
+}
+
+impl Drop for PlatformApplication {
+    fn drop(&mut self) {
+        unsafe {
+            DestroyWindow(self.session_window.hwnd.get());
+        }
+    }
+}
+
+impl WindowAdapter for SessionWindow {
+    fn wnd_proc(&self, h_wnd: HWND, msg: u32, w_param: WPARAM, l_param: LPARAM) -> LRESULT {
+        if msg == WM_QUERYENDSESSION {
+            // The query must be answered synchronously; Dart is notified so that it
+            // can save data and clear the flag, but can not cancel by replying
+            if let Some(context) = self.context.upgrade() {
+                context
+                    .application_manager
+                    .borrow()
+                    .should_terminate(TerminationReason::SessionEnd, |_| {});
+            }
+            let allowed = !self.prevent_sudden_termination.get();
+            return LRESULT(allowed as i32);
+        }
+        self.default_wnd_proc(h_wnd, msg, w_param, l_param)
+    }
+}
@@ -0,0 +1,94 @@
+use std::{backtrace::Backtrace, sync::Mutex};
+
+use crate::shell::CrashDialogResponse;
+
+use super::{
+    all_bindings::*,
+    error::{PlatformError, PlatformResult},
+    util::HRESULT_FROM_WIN32,
+};
+
+const EXCEPTION_CONTINUE_SEARCH: i32 = 0;
+
+lazy_static! {
+    static ref EXCEPTION_HANDLER: Mutex<Option<fn(String, String)>> = Mutex::new(None);
+}
+
+unsafe extern "system" fn on_unhandled_exception(info: *mut EXCEPTION_POINTERS) -> i32 {
+    let record = &*(*info).ExceptionRecord;
+    let message = format!(
+        "Unhandled exception 0x{:08X} at {:?}",
+        record.ExceptionCode.0, record.ExceptionAddress
+    );
+    let handler = *EXCEPTION_HANDLER.lock().unwrap();
+    if let Some(handler) = handler {
+        handler(message, Backtrace::force_capture().to_string());
+    }
+    EXCEPTION_CONTINUE_SEARCH
+}
+
+// Structured exceptions (access violations, etc.) not handled by any frame;
+// Not invoked when a debugger is attached
+pub fn install_exception_handler(handler: fn(String, String)) {
+    *EXCEPTION_HANDLER.lock().unwrap() = Some(handler);
+    unsafe { SetUnhandledExceptionFilter(Some(on_unhandled_exception)) };
+}
+
+// Message box only offers fixed button sets, so the actions are described in
+// the text
+pub fn show_crash_dialog(
+    title: &str,
+    message: &str,
+    can_open_log_file: bool,
+) -> PlatformResult<CrashDialogResponse> {
+    let (text, style) = if can_open_log_file {
+        (
+            format!(
+                "{}\n\nSelect Yes to copy crash details to clipboard or No to open the log file.",
+                message
+            ),
+            MB_YESNOCANCEL,
+        )
+    } else {
+        (
+            format!("{}\n\nCopy crash details to clipboard?", message),
+            MB_YESNO,
+        )
+    };
+    let res = unsafe {
+        MessageBoxW(
+            HWND(0),
+            text.as_str(),
+            title,
+            style | MB_ICONERROR | MB_TASKMODAL,
+        )
+    };
+    Ok(match res {
+        IDYES => CrashDialogResponse::CopyDetails,
+        IDNO if can_open_log_file => CrashDialogResponse::OpenLogFile,
+        _ => CrashDialogResponse::Quit,
+    })
+}
+
+pub fn copy_to_clipboard(text: &str) -> PlatformResult<()> {
+    let text: Vec<u16> = text.encode_utf16().chain(std::iter::once(0)).collect();
+    unsafe {
+        if !OpenClipboard(HWND(0)).as_bool() {
+            return Err(PlatformError::HResult(HRESULT_FROM_WIN32(GetLastError().0)));
+        }
+        EmptyClipboard();
+        let global = GlobalAlloc(GMEM_MOVEABLE, text.len() * 2);
+        let global_data = GlobalLock(global);
+        std::ptr::copy_nonoverlapping(text.as_ptr(), global_data as *mut u16, text.len());
+        GlobalUnlock(global);
+        // Clipboard takes ownership of the memory
+        let res = SetClipboardData(CF_UNICODETEXT.0, HANDLE(global));
+        CloseClipboard();
+        if res.0 == 0 {
+            GlobalFree(global);
+            Err(PlatformError::UnknownError)
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use std::rc::Rc;
+
+use windows::Interface;
+
+use crate::shell::{api_model::DisplayInfo, Context};
+
+use super::{
+    all_bindings::*,
+    bindings::Windows::Win32::{
+        Graphics::Dxgi::*,
+        UI::{ColorSystem::GetICMProfileW, DisplayDevices::DEVMODEW},
+    },
+    display::Displays,
+    error::PlatformResult,
+};
+
+// Luminance of scRGB reference white (1.0), in nits
+const SCRGB_REFERENCE_WHITE: f64 = 80.0;
+
+// Display changes are reported through WM_DISPLAYCHANGE, which is handled in
+// PlatformWindow.
+pub struct PlatformDisplayInfo {}
+
+impl PlatformDisplayInfo {
+    pub fn new(_context: Rc<Context>) -> Self {
+        Self {}
+    }
+
+    // Outputs are enumerated in same order as for screen capture
+    pub fn get_displays(&self) -> PlatformResult<Vec<DisplayInfo>> {
+        let displays = Displays::get_displays();
+        let mut res = Vec::new();
+        for output in Self::outputs() {
+            let desc = match Self::output_desc(&output) {
+                Some(desc) => desc,
+                None => continue,
+            };
+            let display = displays
+                .displays
+                .iter()
+                .find(|d| d.handle == desc.Monitor.0);
+            let display = match display {
+                Some(display) => display,
+                None => continue,
+            };
+            let hdr = desc.ColorSpace == DXGI_COLOR_SPACE_RGB_FULL_G2084_NONE_P2020;
+            let headroom = if hdr {
+                (desc.MaxLuminance as f64 / SCRGB_REFERENCE_WHITE).max(1.0)
+            } else {
+                1.0
+            };
+            res.push(DisplayInfo {
+                index: res.len(),
+                frame: display.logical.clone(),
+                scale_factor: display.scale,
+                color_space: Some(if hdr { "BT.2100 PQ" } else { "sRGB" }.into()),
+                icc_profile: Self::icc_profile(&desc.DeviceName),
+                max_edr_headroom: headroom,
+                current_edr_headroom: headroom,
+                hdr,
+            });
+        }
+        Ok(res)
+    }
+
+    fn outputs() -> Vec<IDXGIOutput> {
+        let mut res = Vec::new();
+        let factory: IDXGIFactory1 = match unsafe { CreateDXGIFactory1() } {
+            Ok(factory) => factory,
+            Err(_) => return res,
+        };
+        let mut adapter_index = 0;
+        loop {
+            let mut adapter = None;
+            if unsafe { factory.EnumAdapters1(adapter_index, &mut adapter) }.is_err() {
+                return res;
+            }
+            if let Some(adapter) = adapter {
+                let mut output_index = 0;
+                loop {
+                    let mut output = None;
+                    if unsafe { adapter.EnumOutputs(output_index, &mut output) }.is_err() {
+                        break;
+                    }
+                    res.extend(output);
+                    output_index += 1;
+                }
+            }
+            adapter_index += 1;
+        }
+    }
+
+    // IDXGIOutput6 is available since Windows 10 1703
+    fn output_desc(output: &IDXGIOutput) -> Option<DXGI_OUTPUT_DESC1> {
+        let output: IDXGIOutput6 = output.cast().ok()?;
+        let mut desc: DXGI_OUTPUT_DESC1 = unsafe { std::mem::zeroed() };
+        unsafe { output.GetDesc1(&mut desc) }.ok().ok()?;
+        Some(desc)
+    }
+
+    // Profile associated with display device in color management settings
+    fn icc_profile(device_name: &[u16]) -> Option<Vec<u8>> {
+        let len = device_name.iter().position(|c| *c == 0)?;
+        let device_name = String::from_utf16_lossy(&device_name[..len]);
+        let path = unsafe {
+            let hdc = CreateDCW(
+                PWSTR::NULL,
+                device_name.as_str(),
+                PWSTR::NULL,
+                std::ptr::null::<DEVMODEW>(),
+            );
+            if hdc.0 == 0 {
+                return None;
+            }
+            let mut buf = [0u16; 260];
+            let mut size = buf.len() as u32;
+            let res = GetICMProfileW(hdc, &mut size, PWSTR(buf.as_mut_ptr()));
+            DeleteDC(hdc);
+            if !res.as_bool() {
+                return None;
+            }
+            let len = buf.iter().position(|c| *c == 0).unwrap_or(buf.len());
+            String::from_utf16_lossy(&buf[..len])
+        };
+        std::fs::read(path).ok()
+    }
+}
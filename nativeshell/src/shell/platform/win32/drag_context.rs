@@ -9,21 +9,23 @@ use windows::create_instance;
 use crate::{
     shell::{
         api_model::{DragData, DragEffect, DragRequest, DraggingInfo},
+        platform::WindowError,
         Context, IPoint,
     },
-    util::LateRefCell,
+    util::{LateRefCell, OkLog},
 };
 
 use super::{
     drag_com::{DataObject, DropSource, DropTarget, DropTargetDelegate},
     drag_data::{
-        DragDataAdapter, FallThroughDragDataAdapter, FilesDragDataAdapter, UrlsDragDataAdapter,
+        DragDataAdapter, FallThroughDragDataAdapter, FilesDragDataAdapter, TextDragDataAdapter,
+        UrlsDragDataAdapter,
     },
     drag_util::{
         convert_drag_effect, convert_drag_effects, convert_drop_effect_mask,
         create_dragimage_bitmap, CLSID_DragDropHelper,
     },
-    error::PlatformResult,
+    error::{PlatformError, PlatformResult},
     util::HRESULTExt,
     window::PlatformWindow,
 };
@@ -50,6 +52,7 @@ impl DragContext {
             data_adapters: vec![
                 Box::new(FilesDragDataAdapter::new()),
                 Box::new(UrlsDragDataAdapter::new()),
+                Box::new(TextDragDataAdapter::new()),
                 Box::new(FallThroughDragDataAdapter::new(&context.options)),
             ],
         }
@@ -73,31 +76,31 @@ impl DragContext {
         unsafe { RevokeDragDrop(window.hwnd()).as_platform_result() }
     }
 
-    pub fn begin_drag_session(&self, request: DragRequest) -> PlatformResult<()> {
+    pub fn begin_drag_session(&self, mut request: DragRequest) -> PlatformResult<()> {
+        // serialize now so that invalid data is reported to caller
+        let data = self.serialize_drag_data(std::mem::take(&mut request.data))?;
         let weak = self.weak_self.clone_value();
         self.context
             .run_loop
             .borrow()
             .schedule_now(move || {
                 if let Some(s) = weak.upgrade() {
-                    unsafe {
-                        s.start_drag_internal(request);
-                    }
+                    unsafe { s.start_drag_internal(request, data) }.ok_log();
                 }
             })
             .detach();
         Ok(())
     }
 
-    fn serialize_drag_data(&self, mut data: DragData) -> HashMap<u32, Vec<u8>> {
+    fn serialize_drag_data(&self, mut data: DragData) -> PlatformResult<HashMap<u32, Vec<u8>>> {
         let mut res = HashMap::new();
         for adapter in &self.context.options.custom_drag_data_adapters {
-            adapter.prepare_drag_data(&mut data.properties, &mut res);
+            adapter.prepare_drag_data(&mut data.properties, &mut res)?;
         }
         for adapter in &self.data_adapters {
-            adapter.prepare_drag_data(&mut data.properties, &mut res);
+            adapter.prepare_drag_data(&mut data.properties, &mut res)?;
         }
-        res
+        Ok(res)
     }
 
     fn deserialize_drag_data(&self, data: IDataObject) -> DragData {
@@ -114,12 +117,16 @@ impl DragContext {
         res
     }
 
-    unsafe fn start_drag_internal(&self, request: DragRequest) {
-        let window = self.window.upgrade().unwrap();
-        let data = self.serialize_drag_data(request.data);
+    unsafe fn start_drag_internal(
+        &self,
+        request: DragRequest,
+        data: HashMap<u32, Vec<u8>>,
+    ) -> PlatformResult<()> {
+        let window = self.window.upgrade().ok_or(WindowError::WindowGone)?;
         let data = Rc::new(RefCell::new(data));
         let data = DataObject::new(Rc::downgrade(&data));
-        let helper: IDragSourceHelper = create_instance(&CLSID_DragDropHelper).unwrap();
+        let helper: IDragSourceHelper = create_instance(&CLSID_DragDropHelper)
+            .map_err(|e| PlatformError::HResult(e.code().0))?;
         let hbitmap = create_dragimage_bitmap(&request.image);
         let image_start = window.local_to_global(request.rect.origin());
         let mut cursor_pos = POINT::default();
@@ -155,6 +162,7 @@ impl DragContext {
             }
             delegate.drag_ended(effect);
         }
+        Ok(())
     }
 }
 
@@ -4,13 +4,19 @@ use byte_slice_cast::AsByteSlice;
 use log::warn;
 use widestring::WideCString;
 
-use crate::{codec::Value, shell::ContextOptions};
+use crate::{
+    codec::Value,
+    shell::{
+        platform::{extract_drag_string, extract_drag_string_list, DragError},
+        ContextOptions,
+    },
+};
 use crate::{
     codec::{MessageCodec, StandardMethodCodec},
     shell::api_constants::*,
 };
 
-use super::{all_bindings::*, drag_util::DataUtil};
+use super::{all_bindings::*, drag_util::DataUtil, error::PlatformResult};
 
 pub trait DragDataAdapter {
     // Retrieve drag data from data object; This is called when receiving drop;
@@ -23,7 +29,7 @@ pub trait DragDataAdapter {
         &self,
         data_in: &mut HashMap<String, Value>,
         data_out: &mut HashMap<u32, Vec<u8>>,
-    );
+    ) -> PlatformResult<()>;
 }
 
 //
@@ -56,14 +62,13 @@ impl DragDataAdapter for FilesDragDataAdapter {
         &self,
         data_in: &mut HashMap<String, Value>,
         data_out: &mut HashMap<u32, Vec<u8>>,
-    ) {
+    ) -> PlatformResult<()> {
         let files = data_in.remove(drag_data::key::FILES);
         if let Some(files) = files {
-            data_out.insert(
-                CF_HDROP.0,
-                DataUtil::bundle_files(&extract_string_list(files)),
-            );
+            let files = extract_drag_string_list(drag_data::key::FILES, files)?;
+            data_out.insert(CF_HDROP.0, DataUtil::bundle_files(&files));
         }
+        Ok(())
     }
 }
 
@@ -102,21 +107,113 @@ impl DragDataAdapter for UrlsDragDataAdapter {
         &self,
         data_in: &mut HashMap<String, Value>,
         data_out: &mut HashMap<u32, Vec<u8>>,
-    ) {
+    ) -> PlatformResult<()> {
         let urls = data_in.remove(drag_data::key::URLS);
         if let Some(urls) = urls {
-            let strings = extract_string_list(urls);
+            let strings = extract_drag_string_list(drag_data::key::URLS, urls)?;
             if strings.len() > 1 {
                 warn!("Only one URL is supported in drag data on Windows");
             }
             if let Some(url) = strings.first() {
-                let url = WideCString::from_str(&url).unwrap();
-                let bytes = url.as_slice().as_byte_slice();
-                let mut data = Vec::from(bytes);
-                data.extend_from_slice(&[0, 0]);
+                let data = to_wide_bytes(drag_data::key::URLS, url)?;
+                // Editors only accept plain text; explicit text takes precedence
+                if !data_in.contains_key(drag_data::key::TEXT) {
+                    data_out.insert(CF_UNICODETEXT.0, data.clone());
+                }
                 data_out.insert(self.format_inet_url_w, data);
             }
         }
+        Ok(())
+    }
+}
+
+pub(super) struct TextDragDataAdapter {
+    format_html: u32,
+}
+
+impl TextDragDataAdapter {
+    pub fn new() -> Self {
+        Self {
+            format_html: register_format("HTML Format"),
+        }
+    }
+
+    // https://docs.microsoft.com/en-us/windows/win32/dataxchg/html-clipboard-format
+    fn bundle_html(fragment: &str) -> Vec<u8> {
+        const PREFIX: &str = "<html><body>\r\n<!--StartFragment-->";
+        const SUFFIX: &str = "<!--EndFragment-->\r\n</body></html>";
+
+        let header = |start_html, end_html, start_fragment, end_fragment| {
+            format!(
+                "Version:0.9\r\n\
+                 StartHTML:{:010}\r\n\
+                 EndHTML:{:010}\r\n\
+                 StartFragment:{:010}\r\n\
+                 EndFragment:{:010}\r\n",
+                start_html, end_html, start_fragment, end_fragment
+            )
+        };
+
+        // Offsets are zero padded so header length doesn't depend on them
+        let start_html = header(0, 0, 0, 0).len();
+        let start_fragment = start_html + PREFIX.len();
+        let end_fragment = start_fragment + fragment.len();
+        let end_html = end_fragment + SUFFIX.len();
+
+        let mut res = header(start_html, end_html, start_fragment, end_fragment);
+        res.push_str(PREFIX);
+        res.push_str(fragment);
+        res.push_str(SUFFIX);
+        let mut res = res.into_bytes();
+        res.push(0);
+        res
+    }
+
+    fn extract_html(buffer: &[u8]) -> Option<String> {
+        let len = buffer.iter().position(|b| *b == 0).unwrap_or(buffer.len());
+        let data = String::from_utf8_lossy(&buffer[..len]);
+        let offset = |name: &str| -> Option<usize> {
+            data.lines()
+                .find_map(|l| l.strip_prefix(name))
+                .and_then(|v| v.trim().parse().ok())
+        };
+        let start = offset("StartFragment:")?;
+        let end = offset("EndFragment:")?;
+        data.get(start..end).map(|s| s.into())
+    }
+}
+
+impl DragDataAdapter for TextDragDataAdapter {
+    fn retrieve_drag_data(&self, data: IDataObject, data_out: &mut HashMap<String, Value>) {
+        let text = DataUtil::get_data(data.clone(), CF_UNICODETEXT.0)
+            .map(|d| DataUtil::extract_string_w(&d))
+            .ok();
+        if let Some(text) = text {
+            data_out.insert(drag_data::key::TEXT.into(), text.into());
+        }
+        let html = DataUtil::get_data(data, self.format_html)
+            .ok()
+            .and_then(|d| Self::extract_html(&d));
+        if let Some(html) = html {
+            data_out.insert(drag_data::key::HTML.into(), html.into());
+        }
+    }
+
+    fn prepare_drag_data(
+        &self,
+        data_in: &mut HashMap<String, Value>,
+        data_out: &mut HashMap<u32, Vec<u8>>,
+    ) -> PlatformResult<()> {
+        if let Some(text) = data_in.remove(drag_data::key::TEXT) {
+            let text = extract_drag_string(drag_data::key::TEXT, text)?;
+            let data = to_wide_bytes(drag_data::key::TEXT, &text)?;
+            data_out.insert(CF_UNICODETEXT.0, data);
+        }
+        if let Some(html) = data_in.remove(drag_data::key::HTML) {
+            let html = extract_drag_string(drag_data::key::HTML, html)?;
+            data_out.insert(self.format_html, Self::bundle_html(&html));
+        }
+        Ok(())
     }
 }
 
@@ -141,8 +238,8 @@ impl DragDataAdapter for FallThroughDragDataAdapter {
 
         let data = DataUtil::get_data(data, self.format);
         if let Ok(data) = data {
-            let value = codec.decode_message(&data).unwrap();
-            if let Value::Map(value) = value {
+            let value = codec.decode_message(&data);
+            if let Some(Value::Map(value)) = value {
                 for entry in value {
                     if let Value::String(key) = entry.0 {
                         data_out.insert(key, entry.1);
@@ -160,7 +257,7 @@ impl DragDataAdapter for FallThroughDragDataAdapter {
         &self,
         data_in: &mut HashMap<String, Value>,
         data_out: &mut HashMap<u32, Vec<u8>>,
-    ) {
+    ) -> PlatformResult<()> {
         let codec: &'static dyn MessageCodec<Value> = &StandardMethodCodec;
         let mut map = HashMap::new();
         for e in data_in.drain() {
@@ -168,24 +265,21 @@ impl DragDataAdapter for FallThroughDragDataAdapter {
         }
         let data = codec.encode_message(&Value::Map(map));
         data_out.insert(self.format, data);
+        Ok(())
     }
 }
 
-fn register_format(name: &str) -> u32 {
-    unsafe { RegisterClipboardFormatW(name) }
+// Null terminated UTF-16 string
+fn to_wide_bytes(key: &str, string: &str) -> std::result::Result<Vec<u8>, DragError> {
+    let string = WideCString::from_str(string).map_err(|e| DragError::InvalidData {
+        key: key.into(),
+        message: e.to_string(),
+    })?;
+    let mut data = Vec::from(string.as_slice().as_byte_slice());
+    data.extend_from_slice(&[0, 0]);
+    Ok(data)
 }
 
-fn extract_string_list(value: Value) -> Vec<String> {
-    if let Value::List(list) = value {
-        let mut res = Vec::new();
-        for value in list {
-            if let Value::String(value) = value {
-                res.push(value)
-            } else {
-                panic!("Invalid value found in list: ${:?}", value);
-            }
-        }
-        return res;
-    }
-    panic!("Invalid value: {:?}, expected list of strings", value)
+fn register_format(name: &str) -> u32 {
+    unsafe { RegisterClipboardFormatW(name) }
 }
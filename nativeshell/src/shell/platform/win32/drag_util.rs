@@ -200,6 +200,15 @@ impl DataUtil {
         str.to_string_lossy()
     }
 
+    pub fn extract_string_w(buffer: &[u8]) -> String {
+        let data: Vec<u16> = buffer
+            .chunks_exact(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .take_while(|c| *c != 0)
+            .collect();
+        String::from_utf16_lossy(&data)
+    }
+
     pub fn extract_url(buffer: &[u8]) -> String {
         let str = CStr::from_bytes_with_nul(&buffer).unwrap();
         str.to_string_lossy().into()
@@ -1,23 +1,43 @@
-use std::{mem::size_of, ptr};
+use std::{
+    cell::Cell,
+    ffi::{CStr, CString},
+    mem::size_of,
+    os::raw::{c_char, c_void},
+    ptr,
+};
 
-use crate::shell::platform::key_interceptor::override_key_event;
+use crate::shell::{platform::key_interceptor::override_key_event, EngineOptions};
 
 use super::{
     binary_messenger::PlatformBinaryMessenger,
-    error::PlatformResult,
+    error::{PlatformError, PlatformResult},
     flutter_sys::{
         FlutterDesktopEngineCreate, FlutterDesktopEngineDestroy, FlutterDesktopEngineGetMessenger,
-        FlutterDesktopEngineProperties, FlutterDesktopEngineRef,
+        FlutterDesktopEngineGetPluginRegistrar, FlutterDesktopEngineProperties,
+        FlutterDesktopEngineRef, FlutterDesktopEngineRun, FlutterDesktopViewControllerCreate,
+        FlutterDesktopViewControllerGetView, FlutterDesktopViewGetHWND,
     },
+    native_view::NativeViewType,
     util::to_utf16,
 };
 
 pub struct PlatformEngine {
     pub(super) handle: FlutterDesktopEngineRef,
+    headless: bool,
+    entrypoint: Option<CString>,
+    running: Cell<bool>,
 }
 
 impl PlatformEngine {
-    pub fn new() -> Self {
+    pub fn new(options: &EngineOptions) -> Self {
+        // Arguments are copied by the engine
+        let args: Vec<CString> = options
+            .entrypoint_args
+            .iter()
+            .map(|a| CString::new(a.as_str()).unwrap())
+            .collect();
+        let mut argv: Vec<*const c_char> = args.iter().map(|a| a.as_ptr()).collect();
+
         let assets = to_utf16("data\\flutter_assets");
         let icu = to_utf16("data\\icudtl.dat");
         let aot = to_utf16("data\\app.so");
@@ -25,8 +45,8 @@ impl PlatformEngine {
             assets_path: assets.as_ptr(),
             icu_data_path: icu.as_ptr(),
             aot_library_path: aot.as_ptr(),
-            dart_entrypoint_argc: 0,
-            dart_entrypoint_argv: ptr::null_mut(),
+            dart_entrypoint_argc: argv.len() as i32,
+            dart_entrypoint_argv: argv.as_mut_ptr(),
         };
 
         let engine = unsafe { FlutterDesktopEngineCreate(&properties) };
@@ -36,7 +56,65 @@ impl PlatformEngine {
             // break in future;
             override_key_event((engine as *mut u8).add(2 * size_of::<isize>()) as *mut _);
         }
-        Self { handle: engine }
+        Self {
+            handle: engine,
+            headless: false,
+            entrypoint: options
+                .entrypoint
+                .as_ref()
+                .map(|e| CString::new(e.as_str()).unwrap()),
+            running: Cell::new(false),
+        }
+    }
+
+    // Engine without view; View can be attached later
+    pub fn new_headless(options: &EngineOptions) -> Self {
+        Self {
+            headless: true,
+            ..Self::new(options)
+        }
+    }
+
+    // Runs the engine with custom entrypoint, if any. Must be called before creating
+    // view controller, which would otherwise run the engine with default entrypoint.
+    pub(super) fn run_custom_entrypoint(&self) -> PlatformResult<()> {
+        match &self.entrypoint {
+            Some(entrypoint) if !self.running.get() => self.run(entrypoint.as_ptr()),
+            _ => Ok(()),
+        }
+    }
+
+    fn run(&self, entrypoint: *const c_char) -> PlatformResult<()> {
+        if unsafe { FlutterDesktopEngineRun(self.handle, entrypoint) } {
+            self.running.set(true);
+            Ok(())
+        } else {
+            Err(PlatformError::LaunchEngineFailure)
+        }
+    }
+
+    // View controller is created by the window and runs on existing engine
+    pub fn attach_view(&mut self) -> PlatformResult<()> {
+        if !self.headless {
+            return Err(PlatformError::NotAvailable);
+        }
+        self.headless = false;
+        Ok(())
+    }
+
+    // Creates view controller for the engine and returns its view, which can be
+    // embedded in existing window; This also runs the engine.
+    pub fn embedded_view(&self) -> PlatformResult<NativeViewType> {
+        if self.headless {
+            return Err(PlatformError::NotAvailable);
+        }
+        self.run_custom_entrypoint()?;
+        unsafe {
+            let controller = FlutterDesktopViewControllerCreate(100, 100, self.handle);
+            let view = FlutterDesktopViewControllerGetView(controller);
+            self.running.set(true);
+            Ok(FlutterDesktopViewGetHWND(view).0)
+        }
     }
 
     pub fn new_binary_messenger(&self) -> PlatformBinaryMessenger {
@@ -44,12 +122,28 @@ impl PlatformEngine {
         PlatformBinaryMessenger::from_handle(messenger)
     }
 
-    pub fn launch(&mut self) -> PlatformResult<()> {
-        // This is a bit inconsistent; On windows engine is unconditionally launched from controller
-        // unsafe { FlutterDesktopEngineRun(self.handle, ptr::null()); }
+    // Registrar is FlutterDesktopPluginRegistrarRef
+    pub fn with_plugin_registrar<F>(&self, plugin_name: &CStr, f: F) -> PlatformResult<()>
+    where
+        F: FnOnce(*mut c_void),
+    {
+        let registrar =
+            unsafe { FlutterDesktopEngineGetPluginRegistrar(self.handle, plugin_name.as_ptr()) };
+        f(registrar as *mut c_void);
         Ok(())
     }
 
+    pub fn launch(&mut self) -> PlatformResult<()> {
+        // This is a bit inconsistent; On windows engine is unconditionally launched from controller,
+        // unless there is no controller
+        if self.headless && !self.running.get() {
+            let entrypoint = self.entrypoint.as_ref().map(|e| e.as_ptr());
+            self.run(entrypoint.unwrap_or(ptr::null()))
+        } else {
+            Ok(())
+        }
+    }
+
     pub fn shut_down(&mut self) -> PlatformResult<()> {
         unsafe {
             FlutterDesktopEngineDestroy(self.handle);
@@ -1,6 +1,11 @@
 use super::util::hresult_description;
 use std::fmt::Display;
 
+use crate::{
+    codec::Value,
+    shell::platform::{DragError, WindowError},
+};
+
 #[derive(Debug, Clone)]
 pub enum PlatformError {
     UnknownError,
@@ -8,6 +13,8 @@ pub enum PlatformError {
     SendMessageFailure { channel: String },
     HResult(u32),
     NotAvailable,
+    Window(WindowError),
+    Drag(DragError),
 }
 
 pub type PlatformResult<T> = Result<T, PlatformError>;
@@ -35,8 +42,47 @@ impl Display for PlatformError {
             PlatformError::NotAvailable => {
                 write!(f, "Feature is not available")
             }
+            PlatformError::Window(error) => error.fmt(f),
+            PlatformError::Drag(error) => error.fmt(f),
+        }
+    }
+}
+
+impl PlatformError {
+    // Stable error code reported to Dart
+    pub fn code(&self) -> &'static str {
+        match self {
+            PlatformError::UnknownError => "unknown-error",
+            PlatformError::LaunchEngineFailure => "launch-engine-failure",
+            PlatformError::SendMessageFailure { .. } => "send-message-failure",
+            PlatformError::HResult(_) => "hresult",
+            PlatformError::NotAvailable => "not-available",
+            PlatformError::Window(error) => error.code(),
+            PlatformError::Drag(error) => error.code(),
+        }
+    }
+
+    pub fn details(&self) -> Value {
+        match self {
+            PlatformError::SendMessageFailure { channel } => channel.as_str().into(),
+            PlatformError::HResult(hresult) => (*hresult as i64).into(),
+            PlatformError::Window(error) => error.details(),
+            PlatformError::Drag(error) => error.details(),
+            _ => Value::Null,
         }
     }
 }
 
 impl std::error::Error for PlatformError {}
+
+impl From<WindowError> for PlatformError {
+    fn from(error: WindowError) -> Self {
+        PlatformError::Window(error)
+    }
+}
+
+impl From<DragError> for PlatformError {
+    fn from(error: DragError) -> Self {
+        PlatformError::Drag(error)
+    }
+}
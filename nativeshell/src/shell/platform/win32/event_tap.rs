@@ -0,0 +1,175 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::{Rc, Weak},
+};
+
+use crate::shell::{
+    api_model::{EventTapEvent, EventTapEventType, EventTapPermission, EventTapRequest},
+    Context, IPoint, Point,
+};
+
+use super::{
+    all_bindings::*,
+    display::Displays,
+    error::{PlatformError, PlatformResult},
+    util::{HIWORD, HRESULT_FROM_WIN32},
+};
+
+// Low level hooks don't carry any user data so the state needs to be global
+thread_local! {
+    static CONTEXT: RefCell<Weak<Context>> = const { RefCell::new(Weak::new()) };
+}
+
+pub struct PlatformEventTap {
+    context: Weak<Context>,
+    keyboard_hook: Cell<HHOOK>,
+    mouse_hook: Cell<HHOOK>,
+}
+
+const WHEEL_DELTA: f64 = 120.0;
+
+impl PlatformEventTap {
+    pub fn new(context: Rc<Context>) -> Self {
+        Self {
+            context: Rc::downgrade(&context),
+            keyboard_hook: Cell::new(HHOOK(0)),
+            mouse_hook: Cell::new(HHOOK(0)),
+        }
+    }
+
+    // Low level hooks don't require any special permission on Windows
+    pub fn permission(&self) -> EventTapPermission {
+        EventTapPermission::Granted
+    }
+
+    pub fn request_permission(&self) -> PlatformResult<EventTapPermission> {
+        Ok(self.permission())
+    }
+
+    pub fn start(&self, request: &EventTapRequest) -> PlatformResult<()> {
+        self.stop()?;
+
+        CONTEXT.with(|c| c.replace(self.context.clone()));
+
+        unsafe {
+            let module = GetModuleHandleW(PWSTR::default());
+            if request.key_events {
+                let hook = SetWindowsHookExW(WH_KEYBOARD_LL, Some(keyboard_hook_proc), module, 0);
+                if hook.0 == 0 {
+                    return Err(PlatformError::HResult(HRESULT_FROM_WIN32(GetLastError().0)));
+                }
+                self.keyboard_hook.set(hook);
+            }
+            if request.mouse_events {
+                let hook = SetWindowsHookExW(WH_MOUSE_LL, Some(mouse_hook_proc), module, 0);
+                if hook.0 == 0 {
+                    let error = HRESULT_FROM_WIN32(GetLastError().0);
+                    self.stop()?;
+                    return Err(PlatformError::HResult(error));
+                }
+                self.mouse_hook.set(hook);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn stop(&self) -> PlatformResult<()> {
+        unsafe {
+            let hook = self.keyboard_hook.replace(HHOOK(0));
+            if hook.0 != 0 {
+                UnhookWindowsHookEx(hook);
+            }
+            let hook = self.mouse_hook.replace(HHOOK(0));
+            if hook.0 != 0 {
+                UnhookWindowsHookEx(hook);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Drop for PlatformEventTap {
+    fn drop(&mut self) {
+        self.stop().ok();
+    }
+}
+
+fn dispatch_event(event: EventTapEvent) {
+    let context = CONTEXT.with(|c| c.borrow().upgrade());
+    if let Some(context) = context {
+        context.event_tap_manager.borrow().on_event(event);
+    }
+}
+
+extern "system" fn keyboard_hook_proc(code: i32, w_param: WPARAM, l_param: LPARAM) -> LRESULT {
+    unsafe {
+        if code >= 0 {
+            let info = &*(l_param.0 as *const KBDLLHOOKSTRUCT);
+            let event_type = match w_param.0 as u32 {
+                WM_KEYDOWN | WM_SYSKEYDOWN => Some(EventTapEventType::KeyDown),
+                WM_KEYUP | WM_SYSKEYUP => Some(EventTapEventType::KeyUp),
+                _ => None,
+            };
+            if let Some(event_type) = event_type {
+                dispatch_event(EventTapEvent {
+                    event_type,
+                    key_code: Some(info.vkCode as i64),
+                    button: None,
+                    position: None,
+                    scroll_delta: None,
+                });
+            }
+        }
+        CallNextHookEx(HHOOK(0), code, w_param, l_param)
+    }
+}
+
+extern "system" fn mouse_hook_proc(code: i32, w_param: WPARAM, l_param: LPARAM) -> LRESULT {
+    unsafe {
+        if code >= 0 {
+            let info = &*(l_param.0 as *const MSLLHOOKSTRUCT);
+            let mouse_data = HIWORD(info.mouseData.0);
+            let wheel_delta = mouse_data as i16 as f64 / WHEEL_DELTA;
+            let (event_type, button, scroll_delta) = match w_param.0 as u32 {
+                WM_LBUTTONDOWN => (EventTapEventType::MouseDown, Some(0), None),
+                WM_RBUTTONDOWN => (EventTapEventType::MouseDown, Some(1), None),
+                WM_MBUTTONDOWN => (EventTapEventType::MouseDown, Some(2), None),
+                WM_XBUTTONDOWN => (
+                    EventTapEventType::MouseDown,
+                    Some(2 + mouse_data as i64),
+                    None,
+                ),
+                WM_LBUTTONUP => (EventTapEventType::MouseUp, Some(0), None),
+                WM_RBUTTONUP => (EventTapEventType::MouseUp, Some(1), None),
+                WM_MBUTTONUP => (EventTapEventType::MouseUp, Some(2), None),
+                WM_XBUTTONUP => (
+                    EventTapEventType::MouseUp,
+                    Some(2 + mouse_data as i64),
+                    None,
+                ),
+                WM_MOUSEMOVE => (EventTapEventType::MouseMove, None, None),
+                WM_MOUSEWHEEL => (
+                    EventTapEventType::ScrollWheel,
+                    None,
+                    Some(Point::xy(0.0, wheel_delta)),
+                ),
+                WM_MOUSEHWHEEL => (
+                    EventTapEventType::ScrollWheel,
+                    None,
+                    Some(Point::xy(wheel_delta, 0.0)),
+                ),
+                _ => return CallNextHookEx(HHOOK(0), code, w_param, l_param),
+            };
+            let position = Displays::get_displays()
+                .convert_physical_to_logical(&IPoint::xy(info.pt.x, info.pt.y));
+            dispatch_event(EventTapEvent {
+                event_type,
+                key_code: None,
+                button,
+                position,
+                scroll_delta,
+            });
+        }
+        CallNextHookEx(HHOOK(0), code, w_param, l_param)
+    }
+}
@@ -0,0 +1,35 @@
+use crate::shell::api_model::HapticFeedbackPattern;
+
+use super::{
+    all_bindings::*,
+    bindings::Windows::Win32::Media::Multimedia::{
+        sndPlaySoundW, SND_ALIAS, SND_ASYNC, SND_NODEFAULT,
+    },
+    error::{PlatformError, PlatformResult},
+};
+
+// Not included in generated bindings
+const MB_OK: u32 = 0;
+
+pub fn beep() -> PlatformResult<()> {
+    if unsafe { MessageBeep(MB_OK) }.as_bool() {
+        Ok(())
+    } else {
+        Err(PlatformError::UnknownError)
+    }
+}
+
+// Name is sound alias from registry (i.e. "SystemAsterisk", "SystemNotification")
+pub fn play_system_sound(name: &str) -> PlatformResult<()> {
+    let played = unsafe { sndPlaySoundW(name, SND_ALIAS as u32 | SND_ASYNC | SND_NODEFAULT) };
+    if played.as_bool() {
+        Ok(())
+    } else {
+        Err(PlatformError::NotAvailable)
+    }
+}
+
+#[allow(unused_variables)]
+pub fn perform_haptic_feedback(pattern: HapticFeedbackPattern) -> PlatformResult<()> {
+    Err(PlatformError::NotAvailable)
+}
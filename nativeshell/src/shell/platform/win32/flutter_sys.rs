@@ -141,9 +141,7 @@ extern "C" {
     ) -> FlutterDesktopViewRef;
 }
 extern "C" {
-    pub fn FlutterDesktopViewControllerForceRedraw(
-        controller: FlutterDesktopViewControllerRef,
-    );
+    pub fn FlutterDesktopViewControllerForceRedraw(controller: FlutterDesktopViewControllerRef);
 }
 extern "C" {
     pub fn FlutterDesktopViewControllerEnableDirectComposition(
@@ -232,3 +230,106 @@ extern "C" {
 extern "C" {
     pub fn FlutterDesktopResyncOutputStreams();
 }
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct FlutterDesktopTextureRegistrar {
+    _unused: [u8; 0],
+}
+pub type FlutterDesktopTextureRegistrarRef = *mut FlutterDesktopTextureRegistrar;
+pub type FlutterDesktopTextureType = ::std::os::raw::c_int;
+#[allow(non_upper_case_globals)]
+pub const kFlutterDesktopPixelBufferTexture: FlutterDesktopTextureType = 0;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct FlutterDesktopPixelBuffer {
+    pub buffer: *const u8,
+    pub width: size_t,
+    pub height: size_t,
+}
+pub type FlutterDesktopPixelBufferTextureCallback = ::std::option::Option<
+    unsafe extern "C" fn(
+        width: size_t,
+        height: size_t,
+        user_data: *mut ::std::os::raw::c_void,
+    ) -> *const FlutterDesktopPixelBuffer,
+>;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct FlutterDesktopPixelBufferTextureConfig {
+    pub callback: FlutterDesktopPixelBufferTextureCallback,
+    pub user_data: *mut ::std::os::raw::c_void,
+}
+pub type FlutterDesktopGpuSurfaceType = ::std::os::raw::c_int;
+#[allow(non_upper_case_globals)]
+pub const kFlutterDesktopGpuSurfaceTypeDxgiSharedHandle: FlutterDesktopGpuSurfaceType = 1;
+#[allow(non_upper_case_globals)]
+pub const kFlutterDesktopGpuSurfaceTypeD3d11Texture2D: FlutterDesktopGpuSurfaceType = 2;
+pub type FlutterDesktopPixelFormat = ::std::os::raw::c_int;
+#[allow(non_upper_case_globals)]
+pub const kFlutterDesktopPixelFormatBGRA8888: FlutterDesktopPixelFormat = 2;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct FlutterDesktopGpuSurfaceDescriptor {
+    pub struct_size: size_t,
+    pub handle: *mut ::std::os::raw::c_void,
+    pub width: size_t,
+    pub height: size_t,
+    pub visible_width: size_t,
+    pub visible_height: size_t,
+    pub format: FlutterDesktopPixelFormat,
+    pub release_callback:
+        ::std::option::Option<unsafe extern "C" fn(release_context: *mut ::std::os::raw::c_void)>,
+    pub release_context: *mut ::std::os::raw::c_void,
+}
+pub type FlutterDesktopGpuSurfaceTextureCallback = ::std::option::Option<
+    unsafe extern "C" fn(
+        width: size_t,
+        height: size_t,
+        user_data: *mut ::std::os::raw::c_void,
+    ) -> *const FlutterDesktopGpuSurfaceDescriptor,
+>;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct FlutterDesktopGpuSurfaceTextureConfig {
+    pub struct_size: size_t,
+    pub type_: FlutterDesktopGpuSurfaceType,
+    pub callback: FlutterDesktopGpuSurfaceTextureCallback,
+    pub user_data: *mut ::std::os::raw::c_void,
+}
+#[allow(non_upper_case_globals)]
+pub const kFlutterDesktopGpuSurfaceTexture: FlutterDesktopTextureType = 1;
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub union FlutterDesktopTextureInfoConfig {
+    pub pixel_buffer_config: FlutterDesktopPixelBufferTextureConfig,
+    pub gpu_surface_config: FlutterDesktopGpuSurfaceTextureConfig,
+}
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct FlutterDesktopTextureInfo {
+    pub type_: FlutterDesktopTextureType,
+    pub config: FlutterDesktopTextureInfoConfig,
+}
+extern "C" {
+    pub fn FlutterDesktopRegistrarGetTextureRegistrar(
+        registrar: FlutterDesktopPluginRegistrarRef,
+    ) -> FlutterDesktopTextureRegistrarRef;
+}
+extern "C" {
+    pub fn FlutterDesktopTextureRegistrarRegisterExternalTexture(
+        texture_registrar: FlutterDesktopTextureRegistrarRef,
+        info: *const FlutterDesktopTextureInfo,
+    ) -> i64;
+}
+extern "C" {
+    pub fn FlutterDesktopTextureRegistrarUnregisterExternalTexture(
+        texture_registrar: FlutterDesktopTextureRegistrarRef,
+        texture_id: i64,
+    ) -> bool;
+}
+extern "C" {
+    pub fn FlutterDesktopTextureRegistrarMarkExternalTextureFrameAvailable(
+        texture_registrar: FlutterDesktopTextureRegistrarRef,
+        texture_id: i64,
+    ) -> bool;
+}
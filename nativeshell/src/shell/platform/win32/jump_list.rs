@@ -0,0 +1,164 @@
+use crate::shell::{
+    api_model::{JumpList, JumpListItem},
+    JUMP_LIST_ITEM_ARGUMENT,
+};
+
+use super::{
+    all_bindings::*,
+    bindings::Windows::Win32::{
+        Media::Audio::DirectMusic::IPropertyStore, System::PropertiesSystem::PROPERTYKEY,
+    },
+    error::{PlatformError, PlatformResult},
+    util::{set_string_property, HRESULTExt},
+};
+
+// Not included in generated bindings
+const KDC_FREQUENT: KNOWNDESTCATEGORY = KNOWNDESTCATEGORY(1);
+const KDC_RECENT: KNOWNDESTCATEGORY = KNOWNDESTCATEGORY(2);
+const PKEY_TITLE: PROPERTYKEY = PROPERTYKEY {
+    fmtid: Guid::from_values(
+        0xF29F85E0,
+        0x4FF9,
+        0x1068,
+        [0xAB, 0x91, 0x08, 0x00, 0x2B, 0x27, 0xB3, 0xD9],
+    ),
+    pid: 2,
+};
+
+// Jump list items are shell links launching the application executable;
+// Activation is delivered through command line arguments (and forwarded
+// to running instance if single instance is enforced)
+pub(super) fn set_jump_list(jump_list: &JumpList) -> PlatformResult<()> {
+    let list: ICustomDestinationList = create_instance(&DestinationList).map_err(error)?;
+    let mut min_slots = 0u32;
+    let removed: IObjectArray = unsafe { list.BeginList(&mut min_slots) }.map_err(error)?;
+    match append_items(&list, jump_list, &removed_arguments(&removed)) {
+        Ok(()) => unsafe { list.CommitList() }.as_platform_result(),
+        Err(err) => {
+            unsafe { list.AbortList() }.ok_log();
+            Err(err)
+        }
+    }
+}
+
+fn error(e: windows::Error) -> PlatformError {
+    PlatformError::HResult(e.code().0)
+}
+
+fn append_items(
+    list: &ICustomDestinationList,
+    jump_list: &JumpList,
+    removed: &[String],
+) -> PlatformResult<()> {
+    for category in &jump_list.categories {
+        // Adding item removed by user from jump list fails whole category
+        let items: Vec<_> = category
+            .items
+            .iter()
+            .filter(|item| !removed.contains(&item_arguments(item)))
+            .collect();
+        if items.is_empty() {
+            continue;
+        }
+        let items = create_collection(&items)?;
+        unsafe { list.AppendCategory(category.title.as_str(), items) }.as_platform_result()?;
+    }
+    if jump_list.show_frequent {
+        unsafe { list.AppendKnownCategory(KDC_FREQUENT) }.as_platform_result()?;
+    }
+    if jump_list.show_recent {
+        unsafe { list.AppendKnownCategory(KDC_RECENT) }.as_platform_result()?;
+    }
+    if !jump_list.tasks.is_empty() {
+        let tasks: Vec<_> = jump_list.tasks.iter().collect();
+        let tasks = create_collection(&tasks)?;
+        unsafe { list.AddUserTasks(tasks) }.as_platform_result()?;
+    }
+    Ok(())
+}
+
+// Arguments of links that user removed from jump list
+fn removed_arguments(removed: &IObjectArray) -> Vec<String> {
+    let mut count = 0u32;
+    if !unsafe { removed.GetCount(&mut count) }.ok_log() {
+        return Vec::new();
+    }
+    (0..count)
+        .filter_map(|i| {
+            let link: IShellLinkW = unsafe { removed.GetAt(i) }.ok()?;
+            let mut buf = [0u16; 1024];
+            unsafe { link.GetArguments(PWSTR(buf.as_mut_ptr()), buf.len() as i32) }
+                .ok()
+                .ok()?;
+            let len = buf.iter().position(|c| *c == 0).unwrap_or(buf.len());
+            Some(String::from_utf16_lossy(&buf[..len]))
+        })
+        .collect()
+}
+
+fn create_collection(items: &[&JumpListItem]) -> PlatformResult<IObjectArray> {
+    let collection: IObjectCollection =
+        create_instance(&EnumerableObjectCollection).map_err(error)?;
+    for item in items {
+        let link = create_link(item)?;
+        unsafe { collection.AddObject(link) }.as_platform_result()?;
+    }
+    collection.cast().map_err(error)
+}
+
+fn create_link(item: &JumpListItem) -> PlatformResult<IShellLinkW> {
+    let executable = std::env::current_exe().map_err(|_| PlatformError::UnknownError)?;
+    let link: IShellLinkW = create_instance(&ShellLink).map_err(error)?;
+    unsafe {
+        link.SetPath(&*executable.to_string_lossy())
+            .as_platform_result()?;
+        link.SetArguments(item_arguments(item).as_str())
+            .as_platform_result()?;
+        if let Some(description) = &item.description {
+            link.SetDescription(description.as_str())
+                .as_platform_result()?;
+        }
+        if let Some(icon_path) = &item.icon_path {
+            link.SetIconLocation(icon_path.as_str(), item.icon_index)
+                .as_platform_result()?;
+        }
+    }
+
+    // Title is only shown when set as link property
+    let store: IPropertyStore = link.cast().map_err(error)?;
+    set_string_property(&store, &PKEY_TITLE, Some(&item.title))?;
+    Ok(link)
+}
+
+fn item_arguments(item: &JumpListItem) -> String {
+    let id = format!("{}={}", JUMP_LIST_ITEM_ARGUMENT, item.id);
+    std::iter::once(&id)
+        .chain(item.arguments.iter())
+        .map(|argument| quote_argument(argument))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Quotes argument to be parsed back by CommandLineToArgvW
+fn quote_argument(argument: &str) -> String {
+    if !argument.is_empty() && !argument.contains(&[' ', '\t', '"'][..]) {
+        return argument.into();
+    }
+    let mut res = String::from('"');
+    let mut backslashes = 0;
+    for c in argument.chars() {
+        match c {
+            '\\' => {
+                backslashes += 1;
+                continue;
+            }
+            '"' => res.push_str(&"\\".repeat(backslashes * 2 + 1)),
+            _ => res.push_str(&"\\".repeat(backslashes)),
+        }
+        backslashes = 0;
+        res.push(c);
+    }
+    res.push_str(&"\\".repeat(backslashes * 2));
+    res.push('"');
+    res
+}
@@ -0,0 +1,73 @@
+use std::rc::Rc;
+
+use crate::shell::{
+    api_model::{LocaleSettings, MeasurementSystem},
+    Context,
+};
+
+use super::{
+    all_bindings::*,
+    bindings::Windows::Win32::Globalization::{GetLocaleInfoEx, GetUserDefaultLocaleName},
+    error::PlatformResult,
+};
+
+// Not included in generated bindings
+const LOCALE_NAME_MAX_LENGTH: usize = 85;
+const LOCALE_IMEASURE: u32 = 0x0000000D;
+const LOCALE_STIMEFORMAT: u32 = 0x00001003;
+const LOCALE_IFIRSTDAYOFWEEK: u32 = 0x0000100C;
+const LOCALE_SISO3166CTRYNAME: u32 = 0x0000005A;
+
+// Changes are reported through WM_SETTINGCHANGE received by windows
+pub struct PlatformLocale {}
+
+impl PlatformLocale {
+    pub fn new(_context: Rc<Context>) -> Self {
+        Self {}
+    }
+
+    pub fn get_settings(&self) -> PlatformResult<LocaleSettings> {
+        let mut name = [0u16; LOCALE_NAME_MAX_LENGTH];
+        let len = unsafe { GetUserDefaultLocaleName(PWSTR(name.as_mut_ptr()), name.len() as i32) };
+        let locale = String::from_utf16_lossy(&name[..(len.max(1) - 1) as usize]);
+
+        // 0 is Monday
+        let first_day_of_week = get_locale_info(LOCALE_IFIRSTDAYOFWEEK)
+            .and_then(|v| v.parse::<u32>().ok())
+            .map(|v| v % 7 + 1)
+            .unwrap_or(1);
+        let measurement_system = match get_locale_info(LOCALE_IMEASURE).as_deref() {
+            Some("1") => MeasurementSystem::Us,
+            _ => MeasurementSystem::Metric,
+        };
+        let uses_24_hour_clock = get_locale_info(LOCALE_STIMEFORMAT)
+            .map(|format| format.contains('H'))
+            .unwrap_or(true);
+
+        Ok(LocaleSettings {
+            locale,
+            region: get_locale_info(LOCALE_SISO3166CTRYNAME),
+            first_day_of_week,
+            uses_24_hour_clock,
+            measurement_system,
+        })
+    }
+}
+
+// Value for user default locale, including user overrides
+fn get_locale_info(lc_type: u32) -> Option<String> {
+    let mut buffer = [0u16; 128];
+    let len = unsafe {
+        GetLocaleInfoEx(
+            PWSTR::NULL,
+            lc_type,
+            PWSTR(buffer.as_mut_ptr()),
+            buffer.len() as i32,
+        )
+    };
+    if len > 0 {
+        Some(String::from_utf16_lossy(&buffer[..(len - 1) as usize]))
+    } else {
+        None
+    }
+}
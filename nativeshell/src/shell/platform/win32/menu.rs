@@ -11,7 +11,7 @@ use super::{
 
 use crate::{
     shell::{
-        api_model::{CheckStatus, Menu, MenuItem},
+        api_model::{Accelerator, CheckStatus, Menu, MenuItem},
         Context, MenuHandle, MenuManager,
     },
     util::{update_diff, DiffResult},
@@ -34,6 +34,32 @@ impl PlatformMenuManager {
     pub fn set_app_menu(&self, _menu: Option<Rc<PlatformMenu>>) -> PlatformResult<()> {
         Err(PlatformError::NotAvailable)
     }
+
+    pub fn has_native_window_list(&self) -> bool {
+        false
+    }
+
+    // Shortcuts handled by the system before they reach the application
+    pub fn system_shortcuts(&self) -> Vec<(Accelerator, &'static str)> {
+        [
+            ("Alt+Tab", "Switch windows"),
+            ("Alt+F4", "Close window"),
+            ("Alt+Space", "Window menu"),
+            ("Ctrl+Alt+Delete", "Security options"),
+            ("Win+Tab", "Task View"),
+            ("Win+D", "Show desktop"),
+            ("Win+E", "File Explorer"),
+            ("Win+L", "Lock"),
+            ("Win+R", "Run"),
+            ("Win+Up Arrow", "Maximize window"),
+            ("Win+Down Arrow", "Minimize window"),
+            ("Win+Left Arrow", "Snap window left"),
+            ("Win+Right Arrow", "Snap window right"),
+        ]
+        .iter()
+        .map(|(accelerator, name)| (Accelerator::parse(accelerator), *name))
+        .collect()
+    }
 }
 
 impl PlatformMenu {
@@ -135,12 +161,8 @@ impl PlatformMenu {
 
         for (i, d) in diff.iter().enumerate() {
             match d {
-                DiffResult::Remove(_) => {
-                    panic!("Should have been already removed.")
-                }
-                DiffResult::Keep(_, _) => {
-                    // nothing
-                }
+                // removed items were already handled above
+                DiffResult::Remove(_) | DiffResult::Keep(_, _) => {}
                 DiffResult::Update(old, new) => {
                     let title = to_utf16(&self.title_for_item(&new));
                     let mut info = Self::get_menu_item_info(new, &title, manager);
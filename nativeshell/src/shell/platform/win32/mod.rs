@@ -1,5 +1,9 @@
+pub mod accessibility;
+pub mod application;
 pub mod binary_messenger;
+pub mod crash_handler;
 pub mod display;
+pub mod display_info;
 pub mod dpi;
 pub mod drag_com;
 pub mod drag_context;
@@ -8,18 +12,42 @@ pub mod drag_util;
 pub mod dxgi_hook;
 pub mod engine;
 pub mod error;
+pub mod event_tap;
+pub mod feedback;
 pub mod flutter_sys;
 pub mod init;
+pub mod jump_list;
 pub mod key_event;
+pub mod locale;
 pub mod menu;
+pub mod native_view;
+pub mod permission;
+pub mod power;
+pub mod render_layer;
 pub mod run_loop;
+pub mod screen_capture;
+pub mod share;
+pub mod single_instance;
+pub mod system_theme;
+pub mod tablet;
+pub mod texture;
+pub mod user_activity;
 pub mod util;
 pub mod window;
 pub mod window_adapter;
 pub mod window_base;
+pub mod window_list;
 pub mod window_menu;
+pub mod workspace;
 
-#[allow(dead_code)]
+// Generated WinRT bindings trigger lints of newer compilers
+#[allow(
+    dead_code,
+    unknown_lints,
+    unused_must_use,
+    unexpected_cfgs,
+    static_mut_refs
+)]
 mod bindings {
     ::windows::include_bindings!();
 }
@@ -30,12 +58,12 @@ mod all_bindings {
         Graphics::{Dwm::*, Gdi::*},
         Storage::StructuredStorage::*,
         System::{
-            Com::*, DataExchange::*, Diagnostics::Debug::*, Memory::*, SystemServices::*,
-            Threading::*,
+            Com::*, DataExchange::*, Diagnostics::Debug::*, Memory::*, Registry::*,
+            SystemServices::*, Threading::*,
         },
         UI::{
-            Controls::*, DisplayDevices::*, KeyboardAndMouseInput::*, MenusAndResources::*,
-            Shell::*, WindowsAndMessaging::*,
+            Accessibility::*, Controls::*, DisplayDevices::*, KeyboardAndMouseInput::*,
+            MenusAndResources::*, PointerInput::*, Shell::*, WindowsAndMessaging::*,
         },
     };
     pub use windows::*;
@@ -0,0 +1,70 @@
+use std::rc::Rc;
+
+use crate::shell::Rect;
+
+use super::{all_bindings::*, error::PlatformResult, util::BoolResultExt, window::PlatformWindow};
+
+// Window handle; Ownership is transferred to PlatformNativeView, which destroys
+// the window when disposed
+pub type NativeViewType = isize;
+
+pub struct PlatformNativeView {
+    window: Rc<PlatformWindow>,
+    hwnd: HWND,
+}
+
+impl PlatformNativeView {
+    pub fn new(window: Rc<PlatformWindow>, view: NativeViewType) -> PlatformResult<Self> {
+        let hwnd = HWND(view);
+        unsafe {
+            // Child window on top of flutter view gets mouse and keyboard input directly
+            let mut style = WINDOW_STYLE(GetWindowLongW(hwnd, GWL_STYLE) as u32);
+            style &= WINDOW_STYLE(!WS_POPUP.0);
+            style |= WS_CHILD | WS_CLIPSIBLINGS;
+            SetWindowLongW(hwnd, GWL_STYLE, style.0 as i32);
+            SetParent(hwnd, window.hwnd());
+            ShowWindow(hwnd, SW_HIDE);
+        }
+        Ok(Self { window, hwnd })
+    }
+
+    pub fn update(&self, frame: &Rect, clip: &Rect, visible: bool) -> PlatformResult<()> {
+        let scale = self.window.get_scaling_factor();
+        let frame = frame.scaled(scale);
+        let clip = clip.scaled(scale);
+        let mut flags = SWP_NOACTIVATE;
+        flags |= if visible {
+            SWP_SHOWWINDOW
+        } else {
+            SWP_HIDEWINDOW
+        };
+        unsafe {
+            // Region is relative to window origin and owned by the system afterwards
+            let region = CreateRectRgn(
+                (clip.x - frame.x).round() as i32,
+                (clip.y - frame.y).round() as i32,
+                (clip.x2() - frame.x).round() as i32,
+                (clip.y2() - frame.y).round() as i32,
+            );
+            SetWindowRgn(self.hwnd, region, visible);
+            SetWindowPos(
+                self.hwnd,
+                HWND(0), // HWND_TOP
+                frame.x.round() as i32,
+                frame.y.round() as i32,
+                frame.width.round() as i32,
+                frame.height.round() as i32,
+                flags,
+            )
+            .as_platform_result()
+        }
+    }
+}
+
+impl Drop for PlatformNativeView {
+    fn drop(&mut self) {
+        unsafe {
+            DestroyWindow(self.hwnd);
+        }
+    }
+}
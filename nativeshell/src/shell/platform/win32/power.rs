@@ -0,0 +1,127 @@
+use std::{cell::Cell, rc::Rc};
+
+use crate::shell::{
+    api_model::{PowerSource, PowerStatus},
+    ActivityOptions, Context, SleepPreventionKind,
+};
+
+use super::{
+    all_bindings::*,
+    error::{PlatformError, PlatformResult},
+    util::HRESULT_FROM_WIN32,
+};
+
+// SYSTEM_POWER_STATUS field values
+const AC_LINE_OFFLINE: u8 = 0;
+const AC_LINE_ONLINE: u8 = 1;
+const BATTERY_FLAG_CHARGING: u8 = 8;
+const BATTERY_FLAG_NO_BATTERY: u8 = 128;
+const BATTERY_FLAG_UNKNOWN: u8 = 255;
+const BATTERY_PERCENTAGE_UNKNOWN: u8 = 255;
+const SYSTEM_STATUS_BATTERY_SAVER_ON: u8 = 1;
+
+// Execution state belongs to the calling thread, so guards are only counted here
+// and the state is updated whenever number of active guards changes
+thread_local! {
+    static SYSTEM_GUARDS: Cell<usize> = const { Cell::new(0) };
+    static DISPLAY_GUARDS: Cell<usize> = const { Cell::new(0) };
+}
+
+pub struct PlatformSleepGuard {
+    kind: SleepPreventionKind,
+}
+
+impl PlatformSleepGuard {
+    pub fn new(kind: SleepPreventionKind, _reason: &str) -> PlatformResult<Self> {
+        Self::counter(kind, |count| count + 1);
+        if !update_execution_state() {
+            Self::counter(kind, |count| count - 1);
+            return Err(PlatformError::UnknownError);
+        }
+        Ok(Self { kind })
+    }
+
+    fn counter<F: FnOnce(usize) -> usize>(kind: SleepPreventionKind, f: F) {
+        let counter = match kind {
+            SleepPreventionKind::System => &SYSTEM_GUARDS,
+            SleepPreventionKind::Display => &DISPLAY_GUARDS,
+        };
+        counter.with(|count| count.set(f(count.get())));
+    }
+}
+
+impl Drop for PlatformSleepGuard {
+    fn drop(&mut self) {
+        Self::counter(self.kind, |count| count - 1);
+        update_execution_state();
+    }
+}
+
+fn update_execution_state() -> bool {
+    let mut state = ES_CONTINUOUS;
+    if SYSTEM_GUARDS.with(Cell::get) > 0 {
+        state |= ES_SYSTEM_REQUIRED;
+    }
+    if DISPLAY_GUARDS.with(Cell::get) > 0 {
+        state |= ES_SYSTEM_REQUIRED | ES_DISPLAY_REQUIRED;
+    }
+    unsafe { SetThreadExecutionState(state).0 != 0 }
+}
+
+// Windows does not nap applications; Only idle sleep prevention has an effect
+pub struct PlatformActivityGuard {
+    _sleep_guard: Option<PlatformSleepGuard>,
+}
+
+impl PlatformActivityGuard {
+    pub fn new(options: ActivityOptions, reason: &str) -> PlatformResult<Self> {
+        let sleep_guard = if options.prevent_idle_sleep {
+            Some(PlatformSleepGuard::new(
+                SleepPreventionKind::System,
+                reason,
+            )?)
+        } else {
+            None
+        };
+        Ok(Self {
+            _sleep_guard: sleep_guard,
+        })
+    }
+}
+
+// Power status changes are reported through WM_POWERBROADCAST, which is
+// handled in PlatformWindow.
+pub struct PlatformPowerSource {}
+
+impl PlatformPowerSource {
+    pub fn new(_context: Rc<Context>) -> Self {
+        Self {}
+    }
+
+    pub fn get_status(&self) -> PlatformResult<PowerStatus> {
+        let mut status: SYSTEM_POWER_STATUS = unsafe { std::mem::zeroed() };
+        if !unsafe { GetSystemPowerStatus(&mut status) }.as_bool() {
+            return Err(PlatformError::HResult(HRESULT_FROM_WIN32(unsafe {
+                GetLastError().0
+            })));
+        }
+        let has_battery = status.BatteryFlag != BATTERY_FLAG_NO_BATTERY
+            && status.BatteryFlag != BATTERY_FLAG_UNKNOWN;
+        Ok(PowerStatus {
+            power_source: match status.ACLineStatus {
+                AC_LINE_OFFLINE => PowerSource::Battery,
+                AC_LINE_ONLINE => PowerSource::Ac,
+                _ => PowerSource::Unknown,
+            },
+            has_battery,
+            battery_level: if has_battery && status.BatteryLifePercent != BATTERY_PERCENTAGE_UNKNOWN
+            {
+                Some(status.BatteryLifePercent as f64 / 100.0)
+            } else {
+                None
+            },
+            charging: has_battery && status.BatteryFlag & BATTERY_FLAG_CHARGING != 0,
+            low_power_mode: status.SystemStatusFlag == SYSTEM_STATUS_BATTERY_SAVER_ON,
+        })
+    }
+}
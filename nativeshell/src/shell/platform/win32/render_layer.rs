@@ -0,0 +1,107 @@
+use std::{
+    cell::Cell,
+    rc::{Rc, Weak},
+};
+
+use crate::shell::{RenderLayerPosition, RenderLayerResizeCallback};
+
+use super::{
+    all_bindings::*,
+    error::{PlatformError, PlatformResult},
+    window::PlatformWindow,
+    window_adapter::WindowAdapter,
+};
+
+// Child window of top level window; Laid out by the platform window together
+// with flutter view
+pub struct PlatformRenderLayer {
+    window: Rc<PlatformWindow>,
+    layer_window: Box<LayerWindow>,
+}
+
+struct LayerWindow {
+    hwnd: Cell<HWND>,
+    window: Weak<PlatformWindow>,
+    on_resize: Box<RenderLayerResizeCallback>,
+}
+
+impl PlatformRenderLayer {
+    pub fn new(
+        window: Rc<PlatformWindow>,
+        position: RenderLayerPosition,
+        on_resize: Box<RenderLayerResizeCallback>,
+    ) -> PlatformResult<Self> {
+        let layer_window = Box::new(LayerWindow {
+            hwnd: Cell::new(HWND(0)),
+            window: Rc::downgrade(&window),
+            on_resize,
+        });
+        // Window must not be child when created (there is no parent yet)
+        let hwnd = layer_window.create_window_custom("", WS_POPUP, WS_EX_NOACTIVATE);
+        if hwnd.0 == 0 {
+            return Err(PlatformError::UnknownError);
+        }
+        layer_window.hwnd.set(hwnd);
+        unsafe {
+            SetWindowLongW(
+                hwnd,
+                GWL_STYLE,
+                (WS_CHILD | WS_CLIPSIBLINGS | WS_VISIBLE).0 as i32,
+            );
+            SetParent(hwnd, window.hwnd());
+            let insert_after = match position {
+                RenderLayerPosition::AboveContent => HWND(0), // HWND_TOP
+                RenderLayerPosition::BelowContent => HWND(1), // HWND_BOTTOM
+            };
+            SetWindowPos(
+                hwnd,
+                insert_after,
+                0,
+                0,
+                0,
+                0,
+                SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE,
+            );
+        }
+        window.add_render_layer(hwnd);
+        Ok(Self {
+            window,
+            layer_window,
+        })
+    }
+
+    pub fn native_handle(&self) -> usize {
+        self.layer_window.hwnd.get().0 as usize
+    }
+}
+
+impl Drop for PlatformRenderLayer {
+    fn drop(&mut self) {
+        let hwnd = self.layer_window.hwnd.get();
+        self.window.remove_render_layer(hwnd);
+        unsafe {
+            DestroyWindow(hwnd);
+        }
+    }
+}
+
+impl WindowAdapter for LayerWindow {
+    fn wnd_proc(&self, h_wnd: HWND, msg: u32, w_param: WPARAM, l_param: LPARAM) -> LRESULT {
+        match msg {
+            WM_SIZE => {
+                let scale = self
+                    .window
+                    .upgrade()
+                    .map(|w| w.get_scaling_factor())
+                    .unwrap_or(1.0);
+                let width = (l_param.0 & 0xFFFF) as usize;
+                let height = ((l_param.0 >> 16) & 0xFFFF) as usize;
+                (self.on_resize)(width, height, scale);
+                LRESULT(0)
+            }
+            // Mouse input goes to flutter view
+            WM_NCHITTEST => LRESULT(HTTRANSPARENT),
+            _ => self.default_wnd_proc(h_wnd, msg, w_param, l_param),
+        }
+    }
+}
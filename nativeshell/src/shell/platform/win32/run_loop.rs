@@ -1,32 +1,66 @@
 use std::{
     cell::{Cell, RefCell},
     collections::HashMap,
+    rc::Rc,
     sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
 
+use log::error;
+
 use super::all_bindings::*;
 
+use crate::shell::TaskPriority;
+
 use super::window_adapter::WindowAdapter;
 
 pub type HandleType = usize;
 pub const INVALID_HANDLE: HandleType = 0;
 
+// Waitable object HANDLE (event, process, waitable timer, ...); Callback is
+// invoked when the object is signaled. Auto-reset objects are reset by the wait
+// itself, manual-reset objects must be reset by the callback.
+pub type EventSourceType = isize;
+
+// MsgWaitForMultipleObjects limit is MAXIMUM_WAIT_OBJECTS - 1
+const MAX_EVENT_SOURCES: usize = 63;
+
+const INFINITE: u32 = 0xFFFFFFFF;
+const WAIT_TIMEOUT: u32 = 0x102;
+const QS_INPUT: QUEUE_STATUS_FLAGS = QUEUE_STATUS_FLAGS(0x0407);
+const QS_ALLINPUT: QUEUE_STATUS_FLAGS = QUEUE_STATUS_FLAGS(0x04FF);
+const PM_REMOVE: PEEK_MESSAGE_REMOVE_TYPE = PEEK_MESSAGE_REMOVE_TYPE(1);
+
 pub struct PlatformRunLoop {
     state: Box<State>,
 }
 
 struct Timer {
     scheduled: Instant,
+    priority: TaskPriority,
     callback: Box<dyn FnOnce()>,
 }
 
+impl Timer {
+    // Due idle timers are not processed on WM_TIMER but when the run loop is idle
+    fn is_idle_pending(&self, now: Instant) -> bool {
+        self.priority == TaskPriority::Idle && self.scheduled <= now
+    }
+}
+
 type SenderCallback = Box<dyn FnOnce() + Send>;
 
+struct EventSource {
+    handle: HandleType,
+    object: HANDLE,
+    callback: Rc<RefCell<dyn FnMut()>>,
+}
+
 struct State {
     next_handle: Cell<HandleType>,
     hwnd: Cell<HWND>,
     timers: RefCell<HashMap<HandleType, Timer>>,
+    event_sources: RefCell<Vec<EventSource>>,
 
     // Callbacks sent from other threads
     sender_callbacks: Arc<Mutex<Vec<SenderCallback>>>,
@@ -38,6 +72,7 @@ impl State {
             next_handle: Cell::new(INVALID_HANDLE + 1),
             hwnd: Cell::new(HWND(0)),
             timers: RefCell::new(HashMap::new()),
+            event_sources: RefCell::new(Vec::new()),
             sender_callbacks: Arc::new(Mutex::new(Vec::new())),
         }
     }
@@ -63,7 +98,14 @@ impl State {
     }
 
     fn next_timer(&self) -> Instant {
-        let min = self.timers.borrow().values().map(|x| x.scheduled).min();
+        let now = Instant::now();
+        let min = self
+            .timers
+            .borrow()
+            .values()
+            .filter(|x| !x.is_idle_pending(now))
+            .map(|x| x.scheduled)
+            .min();
         min.unwrap_or_else(|| Instant::now() + Duration::from_secs(60 * 60))
     }
 
@@ -73,7 +115,7 @@ impl State {
         r
     }
 
-    pub fn schedule<F>(&self, in_time: Duration, callback: F) -> HandleType
+    pub fn schedule<F>(&self, in_time: Duration, priority: TaskPriority, callback: F) -> HandleType
     where
         F: FnOnce() + 'static,
     {
@@ -83,6 +125,7 @@ impl State {
             handle,
             Timer {
                 scheduled: Instant::now() + in_time,
+                priority,
                 callback: Box::new(callback),
             },
         );
@@ -93,33 +136,110 @@ impl State {
     }
 
     pub fn unschedule(&self, handle: HandleType) {
-        self.timers.borrow_mut().remove(&handle);
+        let removed = self.timers.borrow_mut().remove(&handle);
+        // Do not wake up for timer that no longer exists
+        if removed.is_some() {
+            self.wake_up_at(self.next_timer());
+        }
     }
 
     fn process_timers(&self) -> Instant {
         loop {
             let now = Instant::now();
-            let pending: Vec<HandleType> = self
+            let mut pending: Vec<(TaskPriority, Instant, HandleType)> = self
                 .timers
                 .borrow()
                 .iter()
-                .filter(|v| v.1.scheduled <= now)
-                .map(|v| *v.0)
+                .filter(|v| v.1.scheduled <= now && v.1.priority != TaskPriority::Idle)
+                .map(|v| (v.1.priority, v.1.scheduled, *v.0))
                 .collect();
             if pending.is_empty() {
                 break;
             }
-            for handle in pending {
+            pending.sort();
+            for (priority, _, handle) in pending {
                 let timer = self.timers.borrow_mut().remove(&handle);
                 if let Some(timer) = timer {
                     (timer.callback)();
                 }
+                // Let pending input be processed before remaining timers; WM_TIMER
+                // is only generated when there are no other messages in queue
+                if priority != TaskPriority::UserInteractive && Self::has_pending_input() {
+                    return now;
+                }
             }
         }
 
         self.next_timer()
     }
 
+    fn has_pending_input() -> bool {
+        let status = unsafe { GetQueueStatus(QS_INPUT) };
+        (status >> 16) != 0
+    }
+
+    fn has_pending_idle(&self) -> bool {
+        let now = Instant::now();
+        self.timers
+            .borrow()
+            .values()
+            .any(|x| x.is_idle_pending(now))
+    }
+
+    // Runs single due idle callback
+    fn process_idle(&self) {
+        let now = Instant::now();
+        let handle = self
+            .timers
+            .borrow()
+            .iter()
+            .filter(|v| v.1.is_idle_pending(now))
+            .min_by_key(|v| v.1.scheduled)
+            .map(|v| *v.0);
+        let timer = handle.and_then(|handle| self.timers.borrow_mut().remove(&handle));
+        if let Some(timer) = timer {
+            (timer.callback)();
+        }
+    }
+
+    pub fn add_event_source<F>(&self, source: EventSourceType, callback: F) -> HandleType
+    where
+        F: FnMut() + 'static,
+    {
+        let mut event_sources = self.event_sources.borrow_mut();
+        if event_sources.len() >= MAX_EVENT_SOURCES {
+            error!(
+                "Can not register more than {} event sources",
+                MAX_EVENT_SOURCES
+            );
+            return INVALID_HANDLE;
+        }
+        let handle = self.next_handle();
+        event_sources.push(EventSource {
+            handle,
+            object: HANDLE(source),
+            callback: Rc::new(RefCell::new(callback)),
+        });
+        handle
+    }
+
+    pub fn remove_event_source(&self, handle: HandleType) {
+        self.event_sources
+            .borrow_mut()
+            .retain(|source| source.handle != handle);
+    }
+
+    fn process_event_source(&self, index: usize) {
+        let callback = self
+            .event_sources
+            .borrow()
+            .get(index)
+            .map(|source| source.callback.clone());
+        if let Some(callback) = callback {
+            (callback.borrow_mut())();
+        }
+    }
+
     fn process_callbacks(&self) {
         let callbacks: Vec<SenderCallback> = {
             let mut callbacks = self.sender_callbacks.lock().unwrap();
@@ -137,12 +257,44 @@ impl State {
         }
     }
 
+    // Event sources and idle callbacks are only processed here; They are not serviced
+    // while a nested modal loop (i.e. window move/resize, menu tracking) is running.
     fn run(&self) {
-        unsafe {
-            let mut message = MSG::default();
-            while GetMessageW(&mut message, HWND(0), 0, 0) == TRUE {
-                TranslateMessage(&message);
-                DispatchMessageW(&message);
+        let mut message = MSG::default();
+        loop {
+            let objects: Vec<HANDLE> = self
+                .event_sources
+                .borrow()
+                .iter()
+                .map(|source| source.object)
+                .collect();
+            let timeout = if self.has_pending_idle() { 0 } else { INFINITE };
+            let res = unsafe {
+                MsgWaitForMultipleObjects(
+                    objects.len() as u32,
+                    objects.as_ptr(),
+                    FALSE,
+                    timeout,
+                    QS_ALLINPUT,
+                )
+            };
+            if res == WAIT_TIMEOUT {
+                self.process_idle();
+                continue;
+            }
+            let res = res as usize;
+            if res < objects.len() {
+                self.process_event_source(res);
+                continue;
+            }
+            unsafe {
+                while PeekMessageW(&mut message, HWND(0), 0, 0, PM_REMOVE) == TRUE {
+                    if message.message == WM_QUIT {
+                        return;
+                    }
+                    TranslateMessage(&message);
+                    DispatchMessageW(&message);
+                }
             }
         }
     }
@@ -182,11 +334,22 @@ impl PlatformRunLoop {
     }
 
     #[must_use]
-    pub fn schedule<F>(&self, in_time: Duration, callback: F) -> HandleType
+    pub fn schedule<F>(&self, in_time: Duration, priority: TaskPriority, callback: F) -> HandleType
     where
         F: FnOnce() + 'static,
     {
-        self.state.schedule(in_time, callback)
+        self.state.schedule(in_time, priority, callback)
+    }
+
+    pub fn add_event_source<F>(&self, source: EventSourceType, callback: F) -> HandleType
+    where
+        F: FnMut() + 'static,
+    {
+        self.state.add_event_source(source, callback)
+    }
+
+    pub fn remove_event_source(&self, handle: HandleType) {
+        self.state.remove_event_source(handle);
     }
 
     pub fn run(&self) {
@@ -0,0 +1,237 @@
+use std::{
+    cell::RefCell,
+    ptr, slice,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use log::error;
+use windows::Interface;
+
+use crate::shell::{
+    ScreenCaptureFrame, ScreenCaptureOptions, ScreenCaptureSink, ScreenCaptureSource,
+};
+
+use super::{
+    bindings::Windows::Win32::Graphics::{Direct3D11::*, Dxgi::*},
+    error::{PlatformError, PlatformResult},
+    util::HRESULTExt,
+};
+
+struct CaptureThread {
+    stop: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+// Uses DXGI desktop duplication on a dedicated thread
+pub struct PlatformScreenCapture {
+    display: usize,
+    options: ScreenCaptureOptions,
+    sink: Arc<ScreenCaptureSink>,
+    thread: RefCell<Option<CaptureThread>>,
+}
+
+impl PlatformScreenCapture {
+    pub fn new(
+        source: ScreenCaptureSource,
+        options: ScreenCaptureOptions,
+        sink: Arc<ScreenCaptureSink>,
+    ) -> PlatformResult<Self> {
+        let ScreenCaptureSource::Display(display) = source;
+        if find_output(display).is_none() {
+            return Err(PlatformError::NotAvailable);
+        }
+        Ok(Self {
+            display,
+            options,
+            sink,
+            thread: RefCell::new(None),
+        })
+    }
+
+    pub fn start(&self) -> PlatformResult<()> {
+        if self.thread.borrow().is_some() {
+            return Ok(());
+        }
+        let stop = Arc::new(AtomicBool::new(false));
+        let display = self.display;
+        let interval = Duration::from_secs(1) / self.options.max_frame_rate.max(1);
+        let sink = self.sink.clone();
+        let stop_copy = stop.clone();
+        let handle = thread::spawn(move || {
+            if let Err(err) = capture_loop(display, interval, sink, stop_copy) {
+                error!("Screen capture failed: {}", err);
+            }
+        });
+        self.thread.replace(Some(CaptureThread { stop, handle }));
+        Ok(())
+    }
+
+    pub fn stop(&self) -> PlatformResult<()> {
+        if let Some(thread) = self.thread.take() {
+            thread.stop.store(true, Ordering::SeqCst);
+            thread.handle.join().ok();
+        }
+        Ok(())
+    }
+}
+
+impl Drop for PlatformScreenCapture {
+    fn drop(&mut self) {
+        self.stop().ok();
+    }
+}
+
+// Outputs of all adapters in enumeration order
+fn find_output(index: usize) -> Option<(IDXGIAdapter1, IDXGIOutput)> {
+    let factory: IDXGIFactory1 = unsafe { CreateDXGIFactory1() }.ok()?;
+    let mut remaining = index;
+    let mut adapter_index = 0;
+    loop {
+        let mut adapter = None;
+        if unsafe { factory.EnumAdapters1(adapter_index, &mut adapter) }.is_err() {
+            return None;
+        }
+        let adapter = adapter?;
+        let mut output_index = 0;
+        loop {
+            let mut output = None;
+            if unsafe { adapter.EnumOutputs(output_index, &mut output) }.is_err() {
+                break;
+            }
+            if remaining == 0 {
+                return output.map(|output| (adapter, output));
+            }
+            remaining -= 1;
+            output_index += 1;
+        }
+        adapter_index += 1;
+    }
+}
+
+fn capture_loop(
+    display: usize,
+    interval: Duration,
+    sink: Arc<ScreenCaptureSink>,
+    stop: Arc<AtomicBool>,
+) -> PlatformResult<()> {
+    let (adapter, output) = find_output(display).ok_or(PlatformError::NotAvailable)?;
+    let output = output
+        .cast::<IDXGIOutput1>()
+        .map_err(|e| PlatformError::HResult(e.code().0))?;
+    let mut device = None;
+    let mut context = None;
+    unsafe {
+        D3D11CreateDevice(
+            &adapter,
+            D3D_DRIVER_TYPE_UNKNOWN,
+            None,
+            D3D11_CREATE_DEVICE_FLAG(0),
+            ptr::null(),
+            0,
+            D3D11_SDK_VERSION,
+            &mut device,
+            ptr::null_mut(),
+            &mut context,
+        )
+        .as_platform_result()?;
+    }
+    let device = device.ok_or(PlatformError::UnknownError)?;
+    let context = context.ok_or(PlatformError::UnknownError)?;
+
+    let mut duplication: Option<IDXGIOutputDuplication> = None;
+    let mut staging: Option<ID3D11Texture2D> = None;
+    while !stop.load(Ordering::SeqCst) {
+        // Duplication is lost on mode change, desktop switch (UAC prompt), etc.
+        if duplication.is_none() {
+            let res = unsafe { output.DuplicateOutput(&device, &mut duplication) };
+            if res.is_err() {
+                thread::sleep(interval);
+                continue;
+            }
+        }
+        let start = Instant::now();
+        let mut info = DXGI_OUTDUPL_FRAME_INFO::default();
+        let mut resource = None;
+        let res = unsafe {
+            duplication
+                .as_ref()
+                .unwrap()
+                .AcquireNextFrame(100, &mut info, &mut resource)
+        };
+        if res == DXGI_ERROR_WAIT_TIMEOUT {
+            continue;
+        }
+        if res == DXGI_ERROR_ACCESS_LOST {
+            duplication = None;
+            continue;
+        }
+        res.as_platform_result()?;
+
+        // LastPresentTime is zero when only the pointer was updated
+        let texture = resource.and_then(|r| r.cast::<ID3D11Texture2D>().ok());
+        if let (true, Some(texture)) = (info.LastPresentTime != 0, texture) {
+            copy_frame(&device, &context, &texture, &mut staging, &sink)?;
+        }
+        unsafe { duplication.as_ref().unwrap().ReleaseFrame() }.ok_log();
+
+        let elapsed = start.elapsed();
+        if elapsed < interval {
+            thread::sleep(interval - elapsed);
+        }
+    }
+    Ok(())
+}
+
+fn copy_frame(
+    device: &ID3D11Device,
+    context: &ID3D11DeviceContext,
+    texture: &ID3D11Texture2D,
+    staging: &mut Option<ID3D11Texture2D>,
+    sink: &Arc<ScreenCaptureSink>,
+) -> PlatformResult<()> {
+    unsafe {
+        let mut desc = D3D11_TEXTURE2D_DESC::default();
+        texture.GetDesc(&mut desc);
+
+        // Staging texture must match the size of desktop texture
+        let reuse = staging.as_ref().is_some_and(|staging| {
+            let mut staging_desc = D3D11_TEXTURE2D_DESC::default();
+            staging.GetDesc(&mut staging_desc);
+            staging_desc.Width == desc.Width && staging_desc.Height == desc.Height
+        });
+        if !reuse {
+            let mut staging_desc = desc;
+            staging_desc.Usage = D3D11_USAGE_STAGING;
+            staging_desc.BindFlags = 0;
+            staging_desc.CPUAccessFlags = D3D11_CPU_ACCESS_READ.0 as u32;
+            staging_desc.MiscFlags = 0;
+            *staging = None;
+            device
+                .CreateTexture2D(&staging_desc, ptr::null(), staging)
+                .as_platform_result()?;
+        }
+        let staging = staging.as_ref().ok_or(PlatformError::UnknownError)?;
+        context.CopyResource(staging, texture);
+
+        let mut mapped = D3D11_MAPPED_SUBRESOURCE::default();
+        context
+            .Map(staging, 0, D3D11_MAP_READ, 0, &mut mapped)
+            .as_platform_result()?;
+        let height = desc.Height as usize;
+        let bytes_per_row = mapped.RowPitch as usize;
+        let data = slice::from_raw_parts(mapped.pData as *const u8, bytes_per_row * height);
+        sink(&ScreenCaptureFrame {
+            width: desc.Width as usize,
+            height,
+            bytes_per_row,
+            data,
+        });
+        context.Unmap(staging, 0);
+    }
+    Ok(())
+}
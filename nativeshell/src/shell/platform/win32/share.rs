@@ -0,0 +1,87 @@
+use std::cell::RefCell;
+
+use crate::shell::api_model::SharePickerRequest;
+
+use super::{
+    all_bindings::*,
+    bindings::Windows::{
+        ApplicationModel::DataTransfer::{DataRequestedEventArgs, DataTransferManager},
+        Foundation::{EventRegistrationToken, TypedEventHandler, Uri},
+    },
+    error::{PlatformError, PlatformResult},
+    util::HRESULTExt,
+};
+
+// Data requested handler of last share request; Removed before showing the
+// share UI again
+struct Registration {
+    manager: DataTransferManager,
+    token: EventRegistrationToken,
+}
+
+thread_local! {
+    static REGISTRATION: RefCell<Option<Registration>> = const { RefCell::new(None) };
+}
+
+// Shows share UI through DataTransferManager (Windows 10 and later). Sharing
+// files is not supported, as that requires StorageFile items.
+pub(super) fn show_share_picker(hwnd: HWND, request: SharePickerRequest) -> PlatformResult<()> {
+    if !request.files.is_empty() {
+        return Err(PlatformError::NotAvailable);
+    }
+    let error = |e: windows::Error| PlatformError::HResult(e.code().0);
+
+    let interop: IDataTransferManagerInterop =
+        windows::factory::<DataTransferManager, IDataTransferManagerInterop>().map_err(error)?;
+    let manager: DataTransferManager = unsafe { interop.GetForWindow(hwnd) }.map_err(error)?;
+
+    REGISTRATION.with(|registration| {
+        if let Some(registration) = registration.borrow_mut().take() {
+            registration
+                .manager
+                .RemoveDataRequested(registration.token)
+                .ok();
+        }
+    });
+
+    // Data package only holds single web link, remaining URLs are shared as text
+    let mut urls = request.urls.into_iter();
+    let web_link = urls.next();
+    let text: Vec<String> = request.text.into_iter().chain(urls).collect();
+    let text = if text.is_empty() {
+        None
+    } else {
+        Some(text.join("\n"))
+    };
+    // Share UI fails without title
+    let title = request
+        .title
+        .or_else(|| text.clone())
+        .or_else(|| web_link.clone())
+        .unwrap_or_default();
+
+    let handler = TypedEventHandler::<DataTransferManager, DataRequestedEventArgs>::new(
+        move |_manager, args| {
+            if let Some(args) = args {
+                let data = args.Request()?.Data()?;
+                data.Properties()?.SetTitle(title.as_str())?;
+                if let Some(text) = &text {
+                    data.SetText(text.as_str())?;
+                }
+                if let Some(web_link) = &web_link {
+                    data.SetWebLink(Uri::CreateUri(web_link.as_str())?)?;
+                }
+            }
+            Ok(())
+        },
+    );
+    let token = manager.DataRequested(handler).map_err(error)?;
+    REGISTRATION.with(|registration| {
+        registration.borrow_mut().replace(Registration {
+            manager: manager.clone(),
+            token,
+        });
+    });
+
+    unsafe { interop.ShowShareUIForWindow(hwnd) }.as_platform_result()
+}
@@ -0,0 +1,132 @@
+use std::{cell::Cell, ptr, rc::Rc, slice, thread, time::Duration};
+
+use log::warn;
+
+use crate::shell::{api_model::InstanceActivation, Context};
+
+use super::{
+    all_bindings::*,
+    bindings::Windows::Win32::System::WindowsProgramming::CloseHandle,
+    error::{PlatformError, PlatformResult},
+    window_adapter::WindowAdapter,
+};
+
+// Not included in generated bindings
+const ERROR_ALREADY_EXISTS: u32 = 183;
+const HWND_MESSAGE: HWND = HWND(-3);
+
+// Identifies WM_COPYDATA payload
+const ACTIVATION_DATA: usize = 0x4E53_4931;
+
+// First instance owns named mutex and message-only window; Later instances
+// send their activation to the window with WM_COPYDATA
+pub struct PlatformSingleInstance {
+    mutex: HANDLE,
+    message_window: Box<MessageWindow>,
+}
+
+struct MessageWindow {
+    hwnd: Cell<HWND>,
+    on_activation: Box<dyn Fn(InstanceActivation)>,
+}
+
+impl PlatformSingleInstance {
+    // Returns None if another instance is running (activation was forwarded to it)
+    pub fn acquire(
+        _context: &Rc<Context>,
+        id: &str,
+        activation: &InstanceActivation,
+        on_activation: Box<dyn Fn(InstanceActivation)>,
+    ) -> PlatformResult<Option<Self>> {
+        let window_title = format!("nativeshell_instance_{}", id);
+        unsafe {
+            let mutex = CreateMutexW(
+                ptr::null_mut(),
+                false,
+                format!("Local\\nativeshell_instance_{}", id),
+            );
+            if mutex.0 == 0 {
+                return Err(PlatformError::UnknownError);
+            }
+            if GetLastError().0 == ERROR_ALREADY_EXISTS {
+                CloseHandle(mutex);
+                forward_activation(&window_title, activation);
+                return Ok(None);
+            }
+
+            let message_window = Box::new(MessageWindow {
+                hwnd: Cell::new(HWND(0)),
+                on_activation,
+            });
+            let hwnd = message_window.create_window_custom(
+                &window_title,
+                WINDOW_STYLE(0),
+                WINDOW_EX_STYLE(0),
+            );
+            SetParent(hwnd, HWND_MESSAGE);
+            message_window.hwnd.set(hwnd);
+            Ok(Some(Self {
+                mutex,
+                message_window,
+            }))
+        }
+    }
+}
+
+fn forward_activation(window_title: &str, activation: &InstanceActivation) {
+    let data = serde_json::to_vec(activation).unwrap();
+    // First instance may still be starting up
+    for _ in 0..20 {
+        let hwnd = unsafe { FindWindowExW(HWND_MESSAGE, HWND(0), PWSTR::default(), window_title) };
+        if hwnd.0 != 0 {
+            unsafe {
+                // Running instance needs permission to bring its window to front
+                let mut process_id = 0u32;
+                GetWindowThreadProcessId(hwnd, &mut process_id);
+                AllowSetForegroundWindow(process_id);
+                let copy_data = COPYDATASTRUCT {
+                    dwData: ACTIVATION_DATA,
+                    cbData: data.len() as u32,
+                    lpData: data.as_ptr() as *mut _,
+                };
+                SendMessageW(
+                    hwnd,
+                    WM_COPYDATA,
+                    WPARAM(0),
+                    LPARAM(&copy_data as *const _ as isize),
+                );
+            }
+            return;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    warn!("Could not find running application instance");
+}
+
+impl Drop for PlatformSingleInstance {
+    fn drop(&mut self) {
+        unsafe {
+            DestroyWindow(self.message_window.hwnd.get());
+            CloseHandle(self.mutex);
+        }
+    }
+}
+
+impl WindowAdapter for MessageWindow {
+    fn wnd_proc(&self, h_wnd: HWND, msg: u32, w_param: WPARAM, l_param: LPARAM) -> LRESULT {
+        if msg == WM_COPYDATA {
+            let copy_data = unsafe { &*(l_param.0 as *const COPYDATASTRUCT) };
+            if copy_data.dwData == ACTIVATION_DATA && !copy_data.lpData.is_null() {
+                let data = unsafe {
+                    slice::from_raw_parts(copy_data.lpData as *const u8, copy_data.cbData as usize)
+                };
+                match serde_json::from_slice(data) {
+                    Ok(activation) => (self.on_activation)(activation),
+                    Err(_) => warn!("Received malformed instance activation"),
+                }
+                return LRESULT(1);
+            }
+        }
+        self.default_wnd_proc(h_wnd, msg, w_param, l_param)
+    }
+}
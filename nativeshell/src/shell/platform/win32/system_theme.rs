@@ -0,0 +1,121 @@
+use std::{ffi::c_void, mem::size_of, rc::Rc};
+
+use crate::shell::{
+    api_model::{SystemColors, SystemThemeSettings},
+    Context,
+};
+
+use super::{all_bindings::*, error::PlatformResult, util::get_user_registry_dword};
+
+pub struct PlatformSystemTheme {}
+
+// Theme and color changes are reported through WM_SETTINGCHANGE and
+// WM_SYSCOLORCHANGE, which are handled in PlatformWindow.
+impl PlatformSystemTheme {
+    pub fn new(_context: Rc<Context>) -> Self {
+        Self {}
+    }
+
+    pub fn get_settings(&self) -> PlatformResult<SystemThemeSettings> {
+        // Stored as 0xAABBGGRR
+        let accent_color =
+            get_user_registry_dword("Software\\Microsoft\\Windows\\DWM", "AccentColor")
+                .map(from_colorref);
+        let system_colors = system_colors();
+        let high_contrast_theme = high_contrast_theme();
+        // Registry value is ignored while high contrast theme is active
+        let dark_mode = match high_contrast_theme {
+            Some(_) => is_dark_color(system_colors.window),
+            None => is_dark_mode(),
+        };
+        let system_dark_mode = match high_contrast_theme {
+            Some(_) => dark_mode,
+            None => is_system_dark_mode(),
+        };
+        Ok(SystemThemeSettings {
+            dark_mode,
+            accent_color,
+            highlight_color: Some(system_colors.highlight),
+            high_contrast_theme,
+            system_dark_mode,
+            system_colors: Some(system_colors),
+        })
+    }
+}
+
+fn system_colors() -> SystemColors {
+    // COLORREF (0x00BBGGRR)
+    let color = |index| from_colorref(unsafe { GetSysColor(index) });
+    SystemColors {
+        window: color(COLOR_WINDOW),
+        window_text: color(COLOR_WINDOWTEXT),
+        highlight: color(COLOR_HIGHLIGHT),
+        highlight_text: color(COLOR_HIGHLIGHTTEXT),
+        button_face: color(COLOR_BTNFACE),
+        button_text: color(COLOR_BTNTEXT),
+        gray_text: color(COLOR_GRAYTEXT),
+        hotlight: color(COLOR_HOTLIGHT),
+    }
+}
+
+// Name of high contrast theme (i.e. "High Contrast Black") if enabled
+fn high_contrast_theme() -> Option<String> {
+    let mut high_contrast = HIGHCONTRASTW {
+        cbSize: size_of::<HIGHCONTRASTW>() as u32,
+        ..Default::default()
+    };
+    let res = unsafe {
+        SystemParametersInfoW(
+            SPI_GETHIGHCONTRAST,
+            high_contrast.cbSize,
+            &mut high_contrast as *mut HIGHCONTRASTW as *mut c_void,
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        )
+    };
+    if !res.as_bool() || high_contrast.dwFlags.0 & HCF_HIGHCONTRASTON.0 == 0 {
+        return None;
+    }
+    let scheme = high_contrast.lpszDefaultScheme.0;
+    if scheme.is_null() {
+        return Some(String::new());
+    }
+    let name = unsafe {
+        let len = (0..).take_while(|i| *scheme.offset(*i) != 0).count();
+        std::slice::from_raw_parts(scheme, len)
+    };
+    Some(String::from_utf16_lossy(name))
+}
+
+// Whether applications should use dark theme
+pub(super) fn is_dark_mode() -> bool {
+    let apps_use_light_theme = get_user_registry_dword(
+        "Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize",
+        "AppsUseLightTheme",
+    );
+    apps_use_light_theme == Some(0)
+}
+
+// Same setting as read by (undocumented) ShouldSystemUseDarkMode
+fn is_system_dark_mode() -> bool {
+    let system_uses_light_theme = get_user_registry_dword(
+        "Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize",
+        "SystemUsesLightTheme",
+    );
+    system_uses_light_theme == Some(0)
+}
+
+// Perceived brightness of 0xAARRGGBB color
+fn is_dark_color(color: u32) -> bool {
+    let r = (color >> 16) & 0xFF;
+    let g = (color >> 8) & 0xFF;
+    let b = color & 0xFF;
+    r * 299 + g * 587 + b * 114 < 128 * 1000
+}
+
+// Converts 0x??BBGGRR to opaque 0xAARRGGBB
+fn from_colorref(color: u32) -> u32 {
+    let r = color & 0xFF;
+    let g = (color >> 8) & 0xFF;
+    let b = (color >> 16) & 0xFF;
+    0xFF00_0000 | r << 16 | g << 8 | b
+}
@@ -0,0 +1,93 @@
+use crate::shell::{
+    api_model::{TabletEvent, TabletEventType, TabletPointerType},
+    IPoint, Point,
+};
+
+use super::{all_bindings::*, util::LOWORD};
+
+// Pen pressure is reported in range 0 - 1024
+const MAX_PRESSURE: f64 = 1024.0;
+
+// Tilt is reported in degrees (-90 - 90)
+const MAX_TILT: f64 = 90.0;
+
+// Converts WM_POINTER* message to tablet event if the pointer is a pen. Pointer
+// position is in physical screen coordinates, to_local converts it to
+// window coordinates.
+pub(super) fn tablet_event_for_message<F>(
+    msg: u32,
+    w_param: WPARAM,
+    to_local: F,
+) -> Option<TabletEvent>
+where
+    F: FnOnce(&IPoint) -> Point,
+{
+    let event_type = match msg {
+        WM_POINTERENTER => TabletEventType::ProximityEnter,
+        WM_POINTERLEAVE => TabletEventType::ProximityLeave,
+        WM_POINTERDOWN => TabletEventType::Down,
+        WM_POINTERUP => TabletEventType::Up,
+        WM_POINTERUPDATE => TabletEventType::Move,
+        _ => return None,
+    };
+
+    // GET_POINTERID_WPARAM
+    let pointer_id = LOWORD(w_param.0 as u32) as u32;
+
+    unsafe {
+        let mut pointer_type = POINTER_INPUT_TYPE::default();
+        if !GetPointerType(pointer_id, &mut pointer_type as *mut _).as_bool()
+            || pointer_type != PT_PEN
+        {
+            return None;
+        }
+
+        let mut info = POINTER_PEN_INFO::default();
+        if !GetPointerPenInfo(pointer_id, &mut info as *mut _).as_bool() {
+            return None;
+        }
+
+        let pointer_type = if info.penFlags & (PEN_FLAG_ERASER | PEN_FLAG_INVERTED) != 0 {
+            TabletPointerType::Eraser
+        } else {
+            TabletPointerType::Pen
+        };
+
+        let location = info.pointerInfo.ptPixelLocation;
+
+        Some(TabletEvent {
+            event_type,
+            pointer_type,
+            device_id: info.pointerInfo.pointerId as i64,
+            position: Some(to_local(&IPoint::xy(location.x, location.y))),
+            pressure: if info.penMask & PEN_MASK_PRESSURE != 0 {
+                info.pressure as f64 / MAX_PRESSURE
+            } else {
+                0.0
+            },
+            tilt: Point::xy(
+                if info.penMask & PEN_MASK_TILT_X != 0 {
+                    info.tiltX as f64 / MAX_TILT
+                } else {
+                    0.0
+                },
+                if info.penMask & PEN_MASK_TILT_Y != 0 {
+                    info.tiltY as f64 / MAX_TILT
+                } else {
+                    0.0
+                },
+            ),
+            rotation: if info.penMask & PEN_MASK_ROTATION != 0 {
+                info.rotation as f64
+            } else {
+                0.0
+            },
+            // Windows only reports single barrel button
+            buttons: if info.penFlags & PEN_FLAG_BARREL != 0 {
+                1
+            } else {
+                0
+            },
+        })
+    }
+}
@@ -0,0 +1,198 @@
+use std::{ffi::CString, mem::size_of, os::raw::c_void, ptr, sync::Mutex};
+
+use crate::shell::{GpuSurfaceKind, GpuSurfaceProvider, SharedPixelBuffer};
+
+use super::{
+    engine::PlatformEngine,
+    error::{PlatformError, PlatformResult},
+    flutter_sys::{
+        kFlutterDesktopGpuSurfaceTexture, kFlutterDesktopGpuSurfaceTypeD3d11Texture2D,
+        kFlutterDesktopGpuSurfaceTypeDxgiSharedHandle, kFlutterDesktopPixelBufferTexture,
+        kFlutterDesktopPixelFormatBGRA8888, FlutterDesktopEngineGetPluginRegistrar,
+        FlutterDesktopGpuSurfaceDescriptor, FlutterDesktopGpuSurfaceTextureConfig,
+        FlutterDesktopPixelBuffer, FlutterDesktopPixelBufferTextureConfig,
+        FlutterDesktopRegistrarGetTextureRegistrar, FlutterDesktopTextureInfo,
+        FlutterDesktopTextureInfoConfig,
+        FlutterDesktopTextureRegistrarMarkExternalTextureFrameAvailable,
+        FlutterDesktopTextureRegistrarRef, FlutterDesktopTextureRegistrarRegisterExternalTexture,
+        FlutterDesktopTextureRegistrarUnregisterExternalTexture,
+    },
+};
+
+pub struct PlatformTexture {
+    registrar: FlutterDesktopTextureRegistrarRef,
+    id: i64,
+    _state: Box<TextureState>,
+}
+
+enum TextureState {
+    PixelBuffer {
+        buffer: SharedPixelBuffer,
+        // Copy of the buffer handed to the engine; Must stay valid until next callback
+        front: Mutex<(Vec<u8>, FlutterDesktopPixelBuffer)>,
+    },
+    GpuSurface {
+        provider: Box<GpuSurfaceProvider>,
+        descriptor: Mutex<FlutterDesktopGpuSurfaceDescriptor>,
+    },
+}
+
+impl PlatformTexture {
+    pub fn new(engine: &PlatformEngine, buffer: SharedPixelBuffer) -> PlatformResult<Self> {
+        let state = Box::new(TextureState::PixelBuffer {
+            buffer,
+            front: Mutex::new((
+                Vec::new(),
+                FlutterDesktopPixelBuffer {
+                    buffer: ptr::null(),
+                    width: 0,
+                    height: 0,
+                },
+            )),
+        });
+        let info = FlutterDesktopTextureInfo {
+            type_: kFlutterDesktopPixelBufferTexture,
+            config: FlutterDesktopTextureInfoConfig {
+                pixel_buffer_config: FlutterDesktopPixelBufferTextureConfig {
+                    callback: Some(Self::copy_pixel_buffer),
+                    user_data: state.as_ref() as *const TextureState as *mut c_void,
+                },
+            },
+        };
+        Self::register(engine, info, state)
+    }
+
+    pub fn new_gpu_surface(
+        engine: &PlatformEngine,
+        kind: GpuSurfaceKind,
+        provider: Box<GpuSurfaceProvider>,
+    ) -> PlatformResult<Self> {
+        let surface_type = match kind {
+            GpuSurfaceKind::DxgiSharedHandle => kFlutterDesktopGpuSurfaceTypeDxgiSharedHandle,
+            GpuSurfaceKind::D3d11Texture2D => kFlutterDesktopGpuSurfaceTypeD3d11Texture2D,
+            _ => return Err(PlatformError::NotAvailable),
+        };
+        let state = Box::new(TextureState::GpuSurface {
+            provider,
+            descriptor: Mutex::new(FlutterDesktopGpuSurfaceDescriptor {
+                struct_size: size_of::<FlutterDesktopGpuSurfaceDescriptor>(),
+                handle: ptr::null_mut(),
+                width: 0,
+                height: 0,
+                visible_width: 0,
+                visible_height: 0,
+                format: kFlutterDesktopPixelFormatBGRA8888,
+                release_callback: None,
+                release_context: ptr::null_mut(),
+            }),
+        });
+        let info = FlutterDesktopTextureInfo {
+            type_: kFlutterDesktopGpuSurfaceTexture,
+            config: FlutterDesktopTextureInfoConfig {
+                gpu_surface_config: FlutterDesktopGpuSurfaceTextureConfig {
+                    struct_size: size_of::<FlutterDesktopGpuSurfaceTextureConfig>(),
+                    type_: surface_type,
+                    callback: Some(Self::get_gpu_surface),
+                    user_data: state.as_ref() as *const TextureState as *mut c_void,
+                },
+            },
+        };
+        Self::register(engine, info, state)
+    }
+
+    fn register(
+        engine: &PlatformEngine,
+        info: FlutterDesktopTextureInfo,
+        state: Box<TextureState>,
+    ) -> PlatformResult<Self> {
+        let name = CString::new("nativeshell_texture").unwrap();
+        let registrar = unsafe {
+            let registrar = FlutterDesktopEngineGetPluginRegistrar(engine.handle, name.as_ptr());
+            FlutterDesktopRegistrarGetTextureRegistrar(registrar)
+        };
+        let id = unsafe { FlutterDesktopTextureRegistrarRegisterExternalTexture(registrar, &info) };
+        if id < 0 {
+            return Err(PlatformError::UnknownError);
+        }
+        Ok(Self {
+            registrar,
+            id,
+            _state: state,
+        })
+    }
+
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    pub fn mark_frame_available(&self) -> PlatformResult<()> {
+        let res = unsafe {
+            FlutterDesktopTextureRegistrarMarkExternalTextureFrameAvailable(self.registrar, self.id)
+        };
+        if res {
+            Ok(())
+        } else {
+            Err(PlatformError::UnknownError)
+        }
+    }
+
+    // Invoked on raster thread
+    unsafe extern "C" fn copy_pixel_buffer(
+        _width: usize,
+        _height: usize,
+        user_data: *mut c_void,
+    ) -> *const FlutterDesktopPixelBuffer {
+        let (buffer, front) = match &*(user_data as *const TextureState) {
+            TextureState::PixelBuffer { buffer, front } => (buffer, front),
+            _ => return ptr::null(),
+        };
+        let mut front = front.lock().unwrap();
+        let front = &mut *front;
+        {
+            let buffer = buffer.lock().unwrap();
+            front.0.clear();
+            front.0.extend_from_slice(&buffer.data);
+            front.1.width = buffer.width;
+            front.1.height = buffer.height;
+        }
+        if front.0.is_empty() {
+            return ptr::null();
+        }
+        front.1.buffer = front.0.as_ptr();
+        &front.1
+    }
+
+    // Invoked on raster thread
+    unsafe extern "C" fn get_gpu_surface(
+        width: usize,
+        height: usize,
+        user_data: *mut c_void,
+    ) -> *const FlutterDesktopGpuSurfaceDescriptor {
+        let (provider, descriptor) = match &*(user_data as *const TextureState) {
+            TextureState::GpuSurface {
+                provider,
+                descriptor,
+            } => (provider, descriptor),
+            _ => return ptr::null(),
+        };
+        let surface = match provider(width, height) {
+            Some(surface) => surface,
+            None => return ptr::null(),
+        };
+        let mut descriptor = descriptor.lock().unwrap();
+        descriptor.handle = surface.handle as *mut c_void;
+        descriptor.width = surface.width;
+        descriptor.height = surface.height;
+        descriptor.visible_width = surface.width;
+        descriptor.visible_height = surface.height;
+        &*descriptor
+    }
+}
+
+impl Drop for PlatformTexture {
+    fn drop(&mut self) {
+        unsafe {
+            FlutterDesktopTextureRegistrarUnregisterExternalTexture(self.registrar, self.id);
+        }
+    }
+}
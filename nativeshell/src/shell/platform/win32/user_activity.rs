@@ -0,0 +1,24 @@
+use std::time::Duration;
+
+use super::{
+    all_bindings::*,
+    bindings::Windows::Win32::System::WindowsProgramming::GetTickCount,
+    error::{PlatformError, PlatformResult},
+    util::HRESULT_FROM_WIN32,
+};
+
+// Time since last input in current session
+pub fn idle_time() -> PlatformResult<Duration> {
+    let mut info = LASTINPUTINFO {
+        cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+        dwTime: 0,
+    };
+    unsafe {
+        if !GetLastInputInfo(&mut info).as_bool() {
+            return Err(PlatformError::HResult(HRESULT_FROM_WIN32(GetLastError().0)));
+        }
+        // Tick count wraps around after 49.7 days
+        let idle = GetTickCount().wrapping_sub(info.dwTime);
+        Ok(Duration::from_millis(idle as u64))
+    }
+}
@@ -5,15 +5,92 @@ use widestring::WideCString;
 
 use super::{
     all_bindings::*,
+    bindings::Windows::Win32::{
+        Media::Audio::DirectMusic::IPropertyStore, System::PropertiesSystem::PROPERTYKEY,
+    },
     error::{PlatformError, PlatformResult},
 };
 
+// Not included in generated bindings
+const VT_LPWSTR: u16 = 31;
+
 pub(super) fn to_utf16(string: &str) -> Vec<u16> {
     let mut res: Vec<u16> = string.encode_utf16().collect();
     res.push(0);
     res
 }
 
+// Reads REG_DWORD value from HKEY_CURRENT_USER
+pub(super) fn get_user_registry_dword(key: &str, value: &str) -> Option<u32> {
+    let mut key = to_utf16(key);
+    let mut value = to_utf16(value);
+    let mut data = 0u32;
+    let mut data_size = std::mem::size_of::<u32>() as u32;
+    let status = unsafe {
+        RegGetValueW(
+            HKEY_CURRENT_USER,
+            PWSTR(key.as_mut_ptr()),
+            PWSTR(value.as_mut_ptr()),
+            RRF_RT_DWORD,
+            std::ptr::null_mut(),
+            &mut data as *mut _ as *mut _,
+            &mut data_size as *mut _,
+        )
+    };
+    if status.0 == 0 {
+        Some(data)
+    } else {
+        None
+    }
+}
+
+// Reads REG_SZ value from HKEY_CLASSES_ROOT
+pub(super) fn get_class_registry_string(key: &str, value: &str) -> Option<String> {
+    get_registry_string(HKEY_CLASSES_ROOT, key, value)
+}
+
+pub(super) fn get_registry_string(root: HKEY, key: &str, value: &str) -> Option<String> {
+    let mut key = to_utf16(key);
+    let mut value = to_utf16(value);
+    let mut data = [0u16; 256];
+    let mut data_size = std::mem::size_of_val(&data) as u32;
+    let status = unsafe {
+        RegGetValueW(
+            root,
+            PWSTR(key.as_mut_ptr()),
+            PWSTR(value.as_mut_ptr()),
+            RRF_RT_REG_SZ,
+            std::ptr::null_mut(),
+            data.as_mut_ptr() as *mut _,
+            &mut data_size as *mut _,
+        )
+    };
+    if status.0 == 0 {
+        let len = data.iter().position(|c| *c == 0).unwrap_or(data.len());
+        Some(String::from_utf16_lossy(&data[..len]))
+    } else {
+        None
+    }
+}
+
+// Sets string property value; None removes the value
+pub(super) fn set_string_property(
+    store: &IPropertyStore,
+    key: &PROPERTYKEY,
+    value: Option<&str>,
+) -> PlatformResult<()> {
+    let mut string = value.map(to_utf16);
+    unsafe {
+        let mut variant: PROPVARIANT = std::mem::zeroed(); // VT_EMPTY
+        if let Some(string) = string.as_mut() {
+            variant.Anonymous.Anonymous.vt = VT_LPWSTR;
+            variant.Anonymous.Anonymous.Anonymous.pwszVal = PWSTR(string.as_mut_ptr());
+        }
+        store.SetValue(key, &variant).as_platform_result()?;
+        store.Commit().as_platform_result()
+    }
+}
+
 /// # Safety
 ///
 /// Data must be properly aligned (see slice::from_raw_parts)
@@ -96,7 +173,7 @@ pub trait BoolResultExt {
 }
 
 #[allow(non_snake_case)]
-fn HRESULT_FROM_WIN32(x: u32) -> u32 {
+pub(super) fn HRESULT_FROM_WIN32(x: u32) -> u32 {
     if x as i32 <= 0 {
         x as u32
     } else {
@@ -1,5 +1,6 @@
 use std::{
     cell::{Cell, Ref, RefCell},
+    mem::size_of,
     ptr::null_mut,
     rc::{Rc, Weak},
     time::Duration,
@@ -9,22 +10,31 @@ use crate::{
     codec::Value,
     shell::{
         api_model::{
-            DragEffect, DragRequest, PopupMenuRequest, PopupMenuResponse, WindowGeometry,
-            WindowGeometryFlags, WindowGeometryRequest, WindowStyle,
+            ActivationPolicy, DragEffect, DragRequest, KeyboardModifiers, NativeWindowContent,
+            PointerState, PopupMenuRequest, PopupMenuResponse, QuickLookRequest,
+            SharePickerRequest, TouchBar, WindowAppearance, WindowBackdrop, WindowGeometry,
+            WindowGeometryFlags, WindowGeometryRequest, WindowStyle, WindowType,
         },
-        Context, IPoint, PlatformWindowDelegate, Point,
+        Context, IPoint, PlatformWindowDelegate, Point, Rect, SyntheticEvent, SyntheticMouseButton,
     },
-    util::LateRefCell,
+    util::{LateRefCell, OkLog},
 };
 
 use super::{
     all_bindings::*,
+    bindings::Windows::Win32::{
+        Media::Audio::DirectMusic::IPropertyStore,
+        System::PropertiesSystem::{SHGetPropertyStoreForWindow, PROPERTYKEY},
+    },
     drag_context::DragContext,
     dxgi_hook::{set_override_parent_hwnd, take_override_parent_hwnd},
     engine::PlatformEngine,
     error::{PlatformError, PlatformResult},
     flutter_sys::*,
     menu::PlatformMenu,
+    share,
+    tablet::tablet_event_for_message,
+    util::{set_string_property, GET_X_LPARAM, GET_Y_LPARAM, HRESULT_FROM_WIN32, MAKELONG},
     window_adapter::{SetWindowLongPtrW, WindowAdapter},
     window_base::{WindowBaseState, WindowDelegate},
     window_menu::{WindowMenu, WindowMenuDelegate},
@@ -32,6 +42,19 @@ use super::{
 
 pub type PlatformWindowType = isize; // HWND
 
+// Not included in generated bindings
+const SIZE_MINIMIZED: usize = 1;
+const WA_INACTIVE: usize = 0;
+const PKEY_APP_USER_MODEL_ID: PROPERTYKEY = PROPERTYKEY {
+    fmtid: Guid::from_values(
+        0x9F4C2855,
+        0x9F79,
+        0x4B39,
+        [0xA8, 0xD0, 0xE1, 0xD4, 0x2D, 0xE1, 0xD5, 0xF3],
+    ),
+    pid: 5,
+};
+
 pub struct PlatformWindow {
     context: Rc<Context>,
     hwnd: Cell<HWND>,
@@ -46,13 +69,24 @@ pub struct PlatformWindow {
     delegate: Weak<dyn PlatformWindowDelegate>,
     modal_close_callback: RefCell<Option<Box<dyn FnOnce(PlatformResult<Value>)>>>,
     ready_to_show: Cell<bool>,
+    // COLORREF painted as window background if window has no Flutter view
+    native_background: Cell<Option<u32>>,
     show_when_ready: Cell<bool>,
     mouse_state: RefCell<MouseState>,
+    render_layers: RefCell<Vec<HWND>>,
 }
 
 struct MouseState {
     // last button down message used to synthetize button up when displaying menu
     last_button_down: Option<MSG>,
+
+    // WM_MOUSELEAVE of flutter view was suppressed because cursor moved over
+    // maximize button
+    over_maximize_button: bool,
+
+    // MK_* mask of buttons pressed by injected events; Posted messages don't
+    // update the thread key state
+    injected_buttons: usize,
 }
 
 impl PlatformWindow {
@@ -75,10 +109,14 @@ impl PlatformWindow {
             delegate,
             modal_close_callback: RefCell::new(None),
             ready_to_show: Cell::new(false),
+            native_background: Cell::new(None),
             show_when_ready: Cell::new(false),
             mouse_state: RefCell::new(MouseState {
                 last_button_down: None,
+                over_maximize_button: false,
+                injected_buttons: 0,
             }),
+            render_layers: RefCell::new(Vec::new()),
         }
     }
 
@@ -95,9 +133,29 @@ impl PlatformWindow {
                 rect.bottom - rect.top,
                 true,
             );
+            for layer in self.render_layers.borrow().iter() {
+                MoveWindow(
+                    *layer,
+                    rect.left,
+                    rect.top,
+                    rect.right - rect.left,
+                    rect.bottom - rect.top,
+                    true,
+                );
+            }
         }
     }
 
+    // Render layers are laid out together with flutter view
+    pub(super) fn add_render_layer(&self, hwnd: HWND) {
+        self.render_layers.borrow_mut().push(hwnd);
+        self.layout_child();
+    }
+
+    pub(super) fn remove_render_layer(&self, hwnd: HWND) {
+        self.render_layers.borrow_mut().retain(|h| *h != hwnd);
+    }
+
     pub fn get_platform_window(&self) -> PlatformWindowType {
         self.hwnd().0
     }
@@ -117,6 +175,8 @@ impl PlatformWindow {
             // the flutter view will not have parent set yet, so we need to provide it here
             set_override_parent_hwnd(win);
 
+            engine.run_custom_entrypoint().ok_log();
+
             self.flutter_controller
                 .set(FlutterDesktopViewControllerCreate(100, 100, engine.handle));
 
@@ -150,6 +210,62 @@ impl PlatformWindow {
     }
 }
 
+impl PlatformWindow {
+    pub fn assign_weak_self_native(
+        &self,
+        weak: Weak<PlatformWindow>,
+        content: &NativeWindowContent,
+    ) {
+        self.weak_self.set(weak.clone());
+
+        let win = self.create_window("");
+        self.hwnd.set(win);
+
+        self.state.set(WindowBaseState::new(win, weak.clone()));
+
+        let color = content.background_color;
+        // 0xAARRGGBB to 0x00BBGGRR
+        let color_ref = ((color & 0xFF) << 16) | (color & 0xFF00) | ((color >> 16) & 0xFF);
+        self.native_background.set(Some(color_ref));
+
+        let alpha = (color >> 24) as u8;
+        if alpha < 0xFF {
+            unsafe {
+                let style = GetWindowLongW(win, GWL_EXSTYLE);
+                SetWindowLongW(win, GWL_EXSTYLE, style | WS_EX_LAYERED.0 as i32);
+                SetLayeredWindowAttributes(win, 0, alpha, LWA_ALPHA);
+            }
+        }
+
+        // There is no Flutter view to forward keyboard and mouse messages to,
+        // so menu messages are sent to the window itself
+        self.window_menu.set(WindowMenu::new(
+            self.context.clone(),
+            win,
+            win,
+            weak.clone(),
+        ));
+
+        let drag_context = Rc::new(DragContext::new(self.context.clone(), weak));
+        self.drag_context.set(drag_context.clone());
+        drag_context.assign_weak_self(Rc::downgrade(&drag_context));
+    }
+
+    fn erase_native_background(&self, hdc: HDC) -> bool {
+        match self.native_background.get() {
+            Some(color) => unsafe {
+                let mut rect: RECT = RECT::default();
+                GetClientRect(self.hwnd(), &mut rect as *mut _);
+                let brush = CreateSolidBrush(color);
+                FillRect(hdc, &rect as *const _, brush);
+                DeleteObject(brush);
+                true
+            },
+            None => false,
+        }
+    }
+}
+
 impl WindowAdapter for PlatformWindow {
     fn wnd_proc(&self, h_wnd: HWND, msg: u32, w_param: WPARAM, l_param: LPARAM) -> LRESULT {
         let res = if self.state.is_set() {
@@ -165,7 +281,10 @@ impl WindowAdapter for PlatformWindow {
 }
 
 impl WindowDelegate for PlatformWindow {
-    fn displays_changed(&self) {}
+    // Sent to all top level windows; Display manager ignores duplicates
+    fn displays_changed(&self) {
+        self.context.display_manager.borrow().displays_changed();
+    }
 
     fn should_close(&self) {
         let u = self.delegate.upgrade();
@@ -226,6 +345,42 @@ impl PlatformWindow {
         }
     }
 
+    // Brings window to front (restores it if minimized); Only succeeds if the
+    // process is allowed to set foreground window
+    pub fn activate(&self) -> PlatformResult<()> {
+        unsafe {
+            if IsIconic(self.hwnd()).as_bool() {
+                ShowWindow(self.hwnd(), SW_RESTORE);
+            }
+            SetForegroundWindow(self.hwnd());
+        }
+        Ok(())
+    }
+
+    // Tool windows are excluded from taskbar and Alt+Tab; Taskbar only notices
+    // the change when window is shown again
+    pub fn set_activation_policy(&self, policy: ActivationPolicy) -> PlatformResult<()> {
+        let hwnd = self.hwnd();
+        unsafe {
+            let style = GetWindowLongW(hwnd, GWL_EXSTYLE) as u32;
+            let style = if policy == ActivationPolicy::Regular {
+                (style & !WS_EX_TOOLWINDOW.0) | WS_EX_APPWINDOW.0
+            } else {
+                (style & !WS_EX_APPWINDOW.0) | WS_EX_TOOLWINDOW.0
+            };
+            let visible = IsWindowVisible(hwnd).as_bool();
+            let flags = SWP_NOMOVE | SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE;
+            if visible {
+                SetWindowPos(hwnd, HWND(0), 0, 0, 0, 0, flags | SWP_HIDEWINDOW);
+            }
+            SetWindowLongW(hwnd, GWL_EXSTYLE, style as i32);
+            if visible {
+                SetWindowPos(hwnd, HWND(0), 0, 0, 0, 0, flags | SWP_SHOWWINDOW);
+            }
+        }
+        Ok(())
+    }
+
     pub fn hide(&self) -> PlatformResult<()> {
         if self.ready_to_show.get() {
             self.state.borrow().hide()?;
@@ -286,6 +441,72 @@ impl PlatformWindow {
         self.state.borrow().set_title(title)
     }
 
+    // Windows title bar has no subtitle
+    pub fn set_subtitle(&self, _subtitle: String) -> PlatformResult<()> {
+        Err(PlatformError::NotAvailable)
+    }
+
+    // There is no proxy icon on Windows
+    pub fn set_represented_file(&self, _path: Option<String>) -> PlatformResult<()> {
+        Ok(())
+    }
+
+    pub fn set_document_edited(&self, edited: bool) -> PlatformResult<()> {
+        self.state.borrow().set_document_edited(edited)
+    }
+
+    pub fn set_maximize_button_rect(&self, rect: Option<Rect>) -> PlatformResult<()> {
+        self.state.borrow().set_maximize_button_rect(rect)
+    }
+
+    // Windows with different id are grouped separately on taskbar; None reverts
+    // to process id
+    pub fn set_app_user_model_id(&self, id: Option<String>) -> PlatformResult<()> {
+        let store: IPropertyStore = unsafe { SHGetPropertyStoreForWindow(self.hwnd()) }
+            .map_err(|e| PlatformError::HResult(e.code().0))?;
+        set_string_property(&store, &PKEY_APP_USER_MODEL_ID, id.as_deref())
+    }
+
+    pub fn is_on_active_space(&self) -> PlatformResult<bool> {
+        Err(PlatformError::NotAvailable)
+    }
+
+    pub fn move_to_active_space(&self) -> PlatformResult<()> {
+        Err(PlatformError::NotAvailable)
+    }
+
+    pub fn show_share_picker(&self, request: SharePickerRequest) -> PlatformResult<()> {
+        share::show_share_picker(self.hwnd(), request)
+    }
+
+    // Quick Look is MacOS only
+    pub fn show_quick_look(&self, _request: QuickLookRequest) -> PlatformResult<()> {
+        Err(PlatformError::NotAvailable)
+    }
+
+    pub fn set_quick_look_index(&self, _index: usize) -> PlatformResult<()> {
+        Err(PlatformError::NotAvailable)
+    }
+
+    pub fn hide_quick_look(&self) -> PlatformResult<()> {
+        Err(PlatformError::NotAvailable)
+    }
+
+    pub fn set_appearance(&self, appearance: WindowAppearance) -> PlatformResult<()> {
+        self.state.borrow().set_appearance(appearance)
+    }
+
+    pub fn set_backdrop(&self, backdrop: WindowBackdrop) -> PlatformResult<()> {
+        self.state.borrow().set_backdrop(backdrop)
+    }
+
+    pub fn set_window_type(&self, window_type: WindowType) -> PlatformResult<()> {
+        match window_type {
+            WindowType::Normal => Ok(()),
+            _ => Err(PlatformError::NotAvailable),
+        }
+    }
+
     pub fn set_style(&self, style: WindowStyle) -> PlatformResult<()> {
         self.state.borrow().set_style(style)?;
         self.force_redraw();
@@ -393,6 +614,10 @@ impl PlatformWindow {
         Err(PlatformError::NotAvailable)
     }
 
+    pub fn set_touch_bar(&self, _touch_bar: Option<TouchBar>) -> PlatformResult<()> {
+        Err(PlatformError::NotAvailable)
+    }
+
     pub fn begin_drag_session(&self, request: DragRequest) -> PlatformResult<()> {
         self.drag_context.borrow().begin_drag_session(request)?;
 
@@ -400,6 +625,91 @@ impl PlatformWindow {
         Ok(())
     }
 
+    // Messages are posted to flutter view so that they go through the message loop
+    // (and its hooks) like real input; Posted messages don't update key state
+    pub fn inject_event(&self, event: &SyntheticEvent) -> PlatformResult<()> {
+        let scaling_factor = self.get_scaling_factor();
+        let position = |position: &Point| {
+            let x = (position.x * scaling_factor).round() as i32;
+            let y = (position.y * scaling_factor).round() as i32;
+            LPARAM(MAKELONG(x as u16, y as u16) as isize)
+        };
+        let mask = |button: &SyntheticMouseButton| match button {
+            SyntheticMouseButton::Left => MK_LBUTTON as usize,
+            SyntheticMouseButton::Right => MK_RBUTTON as usize,
+            SyntheticMouseButton::Middle => MK_MBUTTON as usize,
+        };
+        let mut mouse_state = self.mouse_state.borrow_mut();
+        let (msg, w_param, l_param) = match event {
+            SyntheticEvent::MouseDown {
+                position: p,
+                button,
+            } => {
+                let msg = match button {
+                    SyntheticMouseButton::Left => WM_LBUTTONDOWN,
+                    SyntheticMouseButton::Right => WM_RBUTTONDOWN,
+                    SyntheticMouseButton::Middle => WM_MBUTTONDOWN,
+                };
+                mouse_state.injected_buttons |= mask(button);
+                (msg, mouse_state.injected_buttons, position(p))
+            }
+            SyntheticEvent::MouseUp {
+                position: p,
+                button,
+            } => {
+                let msg = match button {
+                    SyntheticMouseButton::Left => WM_LBUTTONUP,
+                    SyntheticMouseButton::Right => WM_RBUTTONUP,
+                    SyntheticMouseButton::Middle => WM_MBUTTONUP,
+                };
+                mouse_state.injected_buttons &= !mask(button);
+                (msg, mouse_state.injected_buttons, position(p))
+            }
+            // Pressed buttons must be set, otherwise Flutter doesn't see a drag
+            SyntheticEvent::MouseMove { position: p } => {
+                (WM_MOUSEMOVE, mouse_state.injected_buttons, position(p))
+            }
+            // repeat count 1; key up has previous state and transition bits set
+            SyntheticEvent::KeyDown { key_code, .. } => (WM_KEYDOWN, *key_code as usize, LPARAM(1)),
+            SyntheticEvent::KeyUp { key_code, .. } => {
+                (WM_KEYUP, *key_code as usize, LPARAM(0xC0000001u32 as isize))
+            }
+        };
+        drop(mouse_state);
+        let res = unsafe { PostMessageW(self.child_hwnd(), msg, WPARAM(w_param), l_param) };
+        if res.as_bool() {
+            Ok(())
+        } else {
+            Err(PlatformError::HResult(HRESULT_FROM_WIN32(unsafe {
+                GetLastError().0
+            })))
+        }
+    }
+
+    // Key state of the thread message queue reflects input messages processed so far,
+    // which matches what the window has seen
+    pub fn pointer_state(&self) -> PointerState {
+        let pressed = |key: u32| unsafe { GetKeyState(key as i32) } < 0;
+        let mut point = POINT::default();
+        unsafe { GetCursorPos(&mut point as *mut _) };
+        let mut buttons = 0;
+        for (i, key) in [VK_LBUTTON, VK_RBUTTON, VK_MBUTTON].iter().enumerate() {
+            if pressed(*key) {
+                buttons |= 1 << i;
+            }
+        }
+        PointerState {
+            position: Some(self.global_to_local(&IPoint::xy(point.x, point.y))),
+            buttons,
+            modifiers: KeyboardModifiers {
+                shift: pressed(VK_SHIFT),
+                control: pressed(VK_CONTROL),
+                alt: pressed(VK_MENU),
+                meta: pressed(VK_LWIN) || pressed(VK_RWIN),
+            },
+        }
+    }
+
     pub fn set_pending_effect(&self, effect: DragEffect) {
         self.drag_context.borrow().set_pending_effect(effect);
     }
@@ -416,9 +726,15 @@ impl PlatformWindow {
         self.state.borrow().local_to_global(&offset)
     }
 
+    pub fn get_scaling_factor(&self) -> f64 {
+        self.state.borrow().get_scaling_factor()
+    }
+
     fn force_redraw(&self) {
-        unsafe {
-            FlutterDesktopViewControllerForceRedraw(*self.flutter_controller.borrow());
+        if self.flutter_controller.is_set() {
+            unsafe {
+                FlutterDesktopViewControllerForceRedraw(*self.flutter_controller.borrow());
+            }
         }
     }
 
@@ -435,6 +751,9 @@ impl PlatformWindow {
         match msg {
             WM_SIZE => {
                 self.layout_child();
+                if let Some(delegate) = self.delegate.upgrade() {
+                    delegate.occlusion_changed(w_param.0 == SIZE_MINIMIZED);
+                }
             }
             WM_SHOWWINDOW => {
                 self.layout_child();
@@ -458,6 +777,28 @@ impl PlatformWindow {
                     })
                     .detach();
             }
+            // WM_SETTINGCHANGE; Sent to all top level windows, managers ignore duplicates
+            WM_WININICHANGE => {
+                self.state.borrow().update_appearance();
+                self.context
+                    .accessibility_manager
+                    .borrow()
+                    .settings_changed();
+                self.context
+                    .system_theme_manager
+                    .borrow()
+                    .settings_changed();
+                self.context.locale_manager.borrow().settings_changed();
+            }
+            WM_SYSCOLORCHANGE => {
+                self.context
+                    .system_theme_manager
+                    .borrow()
+                    .settings_changed();
+            }
+            WM_POWERBROADCAST if w_param.0 == PBT_APMPOWERSTATUSCHANGE as usize => {
+                self.context.power_manager.borrow().status_changed();
+            }
             _ => {}
         }
         if self.flutter_controller.is_set() {
@@ -483,7 +824,11 @@ impl PlatformWindow {
             WM_SETFOCUS => unsafe {
                 SetFocus(self.child_hwnd());
             },
-            WM_ACTIVATE => {}
+            WM_ACTIVATE => {
+                if let Some(delegate) = self.delegate.upgrade() {
+                    delegate.activation_changed(w_param.0 & 0xFFFF != WA_INACTIVE);
+                }
+            }
             WM_NCCALCSIZE => unsafe {
                 // No redirection surface, or redireciton surface with removed border; In this case we
                 // need to resize child in WM_NCALCSIZE for better performance
@@ -516,6 +861,31 @@ impl PlatformWindow {
             WM_EXITSIZEMOVE => {
                 self.force_redraw();
             }
+            WM_ERASEBKGND if self.erase_native_background(HDC(w_param.0 as isize)) => {
+                return Some(LRESULT(1));
+            }
+            // Maximize button is drawn by flutter, but reported as non-client area
+            WM_NCMOUSEMOVE | WM_NCLBUTTONDOWN | WM_NCLBUTTONUP | WM_NCLBUTTONDBLCLK
+                if w_param.0 == HTMAXBUTTON as usize && self.get_state().remove_border() =>
+            {
+                self.forward_maximize_button_message(msg, l_param);
+                // Snap Layouts flyout is shown by default window procedure on hover
+                if msg != WM_NCMOUSEMOVE {
+                    return Some(LRESULT(0));
+                }
+            }
+            WM_NCMOUSELEAVE => unsafe {
+                let mut point = POINT::default();
+                GetCursorPos(&mut point as *mut _);
+                let left_to_view = WindowFromPoint(point) == self.child_hwnd();
+                let over_maximize_button = {
+                    let mut mouse_state = self.mouse_state.borrow_mut();
+                    std::mem::replace(&mut mouse_state.over_maximize_button, false)
+                };
+                if over_maximize_button && !left_to_view {
+                    SendMessageW(self.child_hwnd(), WM_MOUSELEAVE, WPARAM(0), LPARAM(0));
+                }
+            },
             _ => {}
         }
 
@@ -538,6 +908,36 @@ impl PlatformWindow {
         }
     }
 
+    fn forward_maximize_button_message(&self, msg: u32, l_param: LPARAM) {
+        let point = IPoint::xy(GET_X_LPARAM(l_param), GET_Y_LPARAM(l_param));
+        let point = self.get_state().global_to_local_physical(&point);
+        let l_param = LPARAM(MAKELONG(point.x as u16, point.y as u16) as isize);
+        let (msg, w_param) = match msg {
+            WM_NCMOUSEMOVE => {
+                // Flutter view is not notified when cursor leaves non-client area
+                let mut event = TRACKMOUSEEVENT {
+                    cbSize: size_of::<TRACKMOUSEEVENT>() as u32,
+                    dwFlags: TME_LEAVE | TME_NONCLIENT,
+                    hwndTrack: self.hwnd(),
+                    dwHoverTime: 0,
+                };
+                unsafe { TrackMouseEvent(&mut event as *mut _) };
+                (WM_MOUSEMOVE, 0)
+            }
+            WM_NCLBUTTONUP => (WM_LBUTTONUP, 0),
+            _ => (WM_LBUTTONDOWN, MK_LBUTTON as usize),
+        };
+        unsafe {
+            SendMessageW(self.child_hwnd(), msg, WPARAM(w_param), l_param);
+        }
+    }
+
+    fn is_cursor_over_maximize_button(&self) -> bool {
+        let mut point = POINT::default();
+        unsafe { GetCursorPos(&mut point as *mut _) };
+        self.get_state().is_over_maximize_button(point.x, point.y)
+    }
+
     pub fn synthetize_mouse_up(&self) {
         // synthetize mouse up / down event
         let mouse_msg = self.mouse_state.borrow_mut().last_button_down.take();
@@ -565,6 +965,28 @@ impl PlatformWindow {
         w_param: WPARAM,
         l_param: LPARAM,
     ) -> LRESULT {
+        // Flutter view is not hit while cursor is over maximize button; Leave is
+        // delivered when cursor leaves the button
+        if u_msg == WM_MOUSELEAVE && self.is_cursor_over_maximize_button() {
+            self.mouse_state.borrow_mut().over_maximize_button = true;
+            return LRESULT(0);
+        }
+        if u_msg == WM_MOUSEMOVE
+            && self.mouse_state.borrow().over_maximize_button
+            && !self.is_cursor_over_maximize_button()
+        {
+            // Cursor moved back to flutter view; Leave tracking was cancelled
+            // by suppressed WM_MOUSELEAVE
+            self.mouse_state.borrow_mut().over_maximize_button = false;
+            let mut event = TRACKMOUSEEVENT {
+                cbSize: size_of::<TRACKMOUSEEVENT>() as u32,
+                dwFlags: TME_LEAVE,
+                hwndTrack: h_wnd,
+                dwHoverTime: 0,
+            };
+            unsafe { TrackMouseEvent(&mut event as *mut _) };
+        }
+
         {
             let mut mouse_state = self.mouse_state.borrow_mut();
 
@@ -597,6 +1019,27 @@ impl PlatformWindow {
             }
         }
 
+        // WM_MOUSELEAVE messages fired while popup menu is active are filtered by window menu
+        if u_msg == WM_MOUSEMOVE || u_msg == WM_MOUSELEAVE {
+            if let Some(delegate) = self.delegate.upgrade() {
+                if u_msg == WM_MOUSEMOVE {
+                    delegate.pointer_entered();
+                } else {
+                    delegate.pointer_exited();
+                }
+            }
+        }
+
+        // Pen input is observed only; DefSubclassProc still produces mouse messages for flutter
+        let tablet_event = tablet_event_for_message(u_msg, w_param, |point| {
+            self.get_state().global_to_local(point)
+        });
+        if let Some(event) = tablet_event {
+            if let Some(delegate) = self.delegate.upgrade() {
+                delegate.tablet_event(event);
+            }
+        }
+
         unsafe { DefSubclassProc(h_wnd, u_msg, w_param, l_param) }
     }
 
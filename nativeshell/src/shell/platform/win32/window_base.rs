@@ -1,12 +1,13 @@
 use std::{
-    cell::RefCell,
+    cell::{Cell, RefCell},
     rc::{Rc, Weak},
 };
 
 use crate::{
     shell::{
         api_model::{
-            WindowFrame, WindowGeometry, WindowGeometryFlags, WindowGeometryRequest, WindowStyle,
+            WindowAppearance, WindowBackdrop, WindowFrame, WindowGeometry, WindowGeometryFlags,
+            WindowGeometryRequest, WindowStyle,
         },
         IPoint, IRect, ISize, Point, Rect, Size,
     },
@@ -18,6 +19,7 @@ use super::{
     display::Displays,
     error::PlatformResult,
     flutter_sys::{FlutterDesktopGetDpiForHWND, FlutterDesktopGetDpiForMonitor},
+    system_theme::is_dark_mode,
     util::{clamp, BoolResultExt, HRESULTExt, GET_X_LPARAM, GET_Y_LPARAM},
 };
 
@@ -29,10 +31,23 @@ pub struct WindowBaseState {
     max_content_size: RefCell<Size>,
     delegate: Weak<dyn WindowDelegate>,
     style: RefCell<WindowStyle>,
+    title: RefCell<String>,
+    document_edited: Cell<bool>,
+    appearance: Cell<WindowAppearance>,
+    backdrop: Cell<WindowBackdrop>,
+    maximize_button_rect: RefCell<Option<Rect>>,
 }
 
 const LARGE_SIZE: f64 = 64.0 * 1024.0;
 
+// Not included in generated bindings
+const DWMWA_USE_IMMERSIVE_DARK_MODE: u32 = 20;
+const DWMWA_USE_IMMERSIVE_DARK_MODE_BEFORE_20H1: u32 = 19;
+const DWMWA_SYSTEMBACKDROP_TYPE: u32 = 38;
+const DWMSBT_NONE: i32 = 1;
+const DWMSBT_MAINWINDOW: i32 = 2;
+const DWMSBT_TRANSIENTWINDOW: i32 = 3;
+
 impl WindowBaseState {
     pub fn new(hwnd: HWND, delegate: Weak<dyn WindowDelegate>) -> Self {
         Self {
@@ -43,6 +58,11 @@ impl WindowBaseState {
             min_content_size: RefCell::new(Size::wh(0.0, 0.0)),
             max_content_size: RefCell::new(Size::wh(LARGE_SIZE, LARGE_SIZE)),
             style: Default::default(),
+            title: RefCell::new(String::new()),
+            document_edited: Cell::new(false),
+            appearance: Cell::new(WindowAppearance::System),
+            backdrop: Cell::new(WindowBackdrop::None),
+            maximize_button_rect: RefCell::new(None),
         }
     }
 
@@ -371,11 +391,21 @@ impl WindowBaseState {
             WindowFrame::NoFrame => 0, // neede for transparency
         };
 
-        let margins = MARGINS {
-            cxLeftWidth: 0,
-            cxRightWidth: 0,
-            cyTopHeight: margin,
-            cyBottomHeight: 0,
+        let margins = if self.backdrop.get() != WindowBackdrop::None {
+            // backdrop is only drawn in frame area, so extend it over whole window
+            MARGINS {
+                cxLeftWidth: -1,
+                cxRightWidth: -1,
+                cyTopHeight: -1,
+                cyBottomHeight: -1,
+            }
+        } else {
+            MARGINS {
+                cxLeftWidth: 0,
+                cxRightWidth: 0,
+                cyTopHeight: margin,
+                cyBottomHeight: 0,
+            }
         };
         unsafe {
             DwmExtendFrameIntoClientArea(self.hwnd, &margins as *const _).as_platform_result()
@@ -383,10 +413,84 @@ impl WindowBaseState {
     }
 
     pub fn set_title(&self, title: String) -> PlatformResult<()> {
+        self.title.replace(title);
+        self.update_title();
+        Ok(())
+    }
+
+    pub fn set_document_edited(&self, edited: bool) -> PlatformResult<()> {
+        self.document_edited.set(edited);
+        self.update_title();
+        Ok(())
+    }
+
+    pub fn set_appearance(&self, appearance: WindowAppearance) -> PlatformResult<()> {
+        self.appearance.set(appearance);
+        self.update_appearance();
+        Ok(())
+    }
+
+    // Title bar of Win32 windows doesn't follow system theme on its own, so this
+    // is also called when system settings change
+    pub fn update_appearance(&self) {
+        let dark = match self.appearance.get() {
+            WindowAppearance::System => is_dark_mode(),
+            WindowAppearance::Light => false,
+            WindowAppearance::Dark => true,
+        };
+        let value = BOOL(dark as i32);
+        unsafe {
+            let res = DwmSetWindowAttribute(
+                self.hwnd,
+                DWMWA_USE_IMMERSIVE_DARK_MODE,
+                &value as *const _ as *const _,
+                std::mem::size_of::<BOOL>() as u32,
+            );
+            if res.is_err() {
+                // Windows 10 before 20H1 used undocumented attribute value
+                DwmSetWindowAttribute(
+                    self.hwnd,
+                    DWMWA_USE_IMMERSIVE_DARK_MODE_BEFORE_20H1,
+                    &value as *const _ as *const _,
+                    std::mem::size_of::<BOOL>() as u32,
+                )
+                .ok_log();
+            }
+        }
+    }
+
+    // Requires Windows 11 22H2; Backdrop is visible through transparent
+    // parts of Flutter content
+    pub fn set_backdrop(&self, backdrop: WindowBackdrop) -> PlatformResult<()> {
+        let value = match backdrop {
+            WindowBackdrop::None => DWMSBT_NONE,
+            WindowBackdrop::Mica => DWMSBT_MAINWINDOW,
+            WindowBackdrop::Acrylic => DWMSBT_TRANSIENTWINDOW,
+        };
+        unsafe {
+            DwmSetWindowAttribute(
+                self.hwnd,
+                DWMWA_SYSTEMBACKDROP_TYPE,
+                &value as *const _ as *const _,
+                std::mem::size_of::<i32>() as u32,
+            )
+            .as_platform_result()?;
+        }
+        self.backdrop.set(backdrop);
+        self.update_dwm_frame()
+    }
+
+    // Unsaved documents are indicated by asterisk before the title (like Notepad)
+    fn update_title(&self) {
+        let title = self.title.borrow();
+        let title = if self.document_edited.get() {
+            format!("*{}", title)
+        } else {
+            title.clone()
+        };
         unsafe {
             SetWindowTextW(self.hwnd, title);
         }
-        Ok(())
     }
 
     pub fn set_style(&self, style: WindowStyle) -> PlatformResult<()> {
@@ -455,6 +559,24 @@ impl WindowBaseState {
         Ok(())
     }
 
+    // Maximize button drawn by Dart in window without title bar; Hit test
+    // reports it as HTMAXBUTTON so that Windows 11 shows Snap Layouts
+    pub fn set_maximize_button_rect(&self, rect: Option<Rect>) -> PlatformResult<()> {
+        self.maximize_button_rect.replace(rect);
+        Ok(())
+    }
+
+    // Screen coordinates
+    pub fn is_over_maximize_button(&self, x: i32, y: i32) -> bool {
+        if !self.remove_border() {
+            return false;
+        }
+        match self.maximize_button_rect.borrow().as_ref() {
+            Some(rect) => rect.is_inside(&self.global_to_local(&IPoint::xy(x, y))),
+            None => false,
+        }
+    }
+
     pub fn has_redirection_surface(&self) -> bool {
         let style = WINDOW_EX_STYLE(unsafe { GetWindowLongW(self.hwnd, GWL_EXSTYLE) } as u32);
         (style & WS_EX_NOREDIRECTIONBITMAP).0 == 0
@@ -488,6 +610,8 @@ impl WindowBaseState {
             HTLEFT
         } else if x > win_rect.right - border_width {
             HTRIGHT
+        } else if self.is_over_maximize_button(x, y) {
+            HTMAXBUTTON
         } else {
             HTCLIENT
         }
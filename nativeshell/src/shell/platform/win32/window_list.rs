@@ -0,0 +1,229 @@
+use std::{ffi::c_void, mem::size_of, path::Path};
+
+use crate::shell::{
+    api_model::{ExternalWindow, ImageData, WindowListRequest},
+    IPoint, ISize, Rect, Size,
+};
+
+use super::{
+    all_bindings::*,
+    bindings::Windows::Win32::{
+        Storage::Xps::{PrintWindow, PRINT_WINDOW_FLAGS},
+        System::WindowsProgramming::CloseHandle,
+    },
+    display::Displays,
+    error::PlatformResult,
+};
+
+const DWMWA_CLOAKED: u32 = 14;
+
+// Also captures DirectComposition content (i.e. browsers and UWP
+// applications); Windows 8.1 and newer
+const PW_RENDERFULLCONTENT: PRINT_WINDOW_FLAGS = PRINT_WINDOW_FLAGS(2);
+
+extern "system" fn enum_windows(hwnd: HWND, lparam: LPARAM) -> BOOL {
+    unsafe {
+        let windows = &mut *(lparam.0 as *mut Vec<HWND>);
+        windows.push(hwnd);
+    }
+    TRUE
+}
+
+// Windows that would be shown in task switcher; Cloaked windows are on other
+// virtual desktops or suspended UWP applications
+fn is_application_window(hwnd: HWND) -> bool {
+    unsafe {
+        if !IsWindowVisible(hwnd).as_bool() || GetWindow(hwnd, GW_OWNER).0 != 0 {
+            return false;
+        }
+        let ex_style = GetWindowLongW(hwnd, GWL_EXSTYLE) as u32;
+        if ex_style & WS_EX_TOOLWINDOW.0 != 0 {
+            return false;
+        }
+        let mut cloaked = 0u32;
+        let res = DwmGetWindowAttribute(
+            hwnd,
+            DWMWA_CLOAKED,
+            &mut cloaked as *mut _ as *mut c_void,
+            size_of::<u32>() as u32,
+        );
+        res.is_err() || cloaked == 0
+    }
+}
+
+fn window_title(hwnd: HWND) -> Option<String> {
+    let mut buf = [0u16; 512];
+    let len = unsafe { GetWindowTextW(hwnd, PWSTR(buf.as_mut_ptr()), buf.len() as i32) };
+    if len > 0 {
+        Some(String::from_utf16_lossy(&buf[..len as usize]))
+    } else {
+        None
+    }
+}
+
+// Executable name without extension
+fn process_name(pid: u32) -> String {
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid);
+        if process.0 == 0 {
+            return String::new();
+        }
+        let mut buf = [0u16; 1024];
+        let mut size = buf.len() as u32;
+        let res = QueryFullProcessImageNameW(
+            process,
+            PROCESS_NAME_FORMAT(0),
+            PWSTR(buf.as_mut_ptr()),
+            &mut size,
+        );
+        CloseHandle(process);
+        if !res.as_bool() {
+            return String::new();
+        }
+        let path = String::from_utf16_lossy(&buf[..size as usize]);
+        Path::new(&path)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into())
+            .unwrap_or_default()
+    }
+}
+
+// Windows are enumerated in Z order (topmost first)
+pub fn get_windows(request: &WindowListRequest) -> PlatformResult<Vec<ExternalWindow>> {
+    let mut hwnds = Vec::<HWND>::new();
+    unsafe {
+        EnumWindows(Some(enum_windows), LPARAM(&mut hwnds as *mut _ as isize));
+    }
+    let displays = Displays::get_displays();
+    let current_pid = std::process::id();
+    let mut res = Vec::new();
+    for hwnd in hwnds {
+        if !is_application_window(hwnd) {
+            continue;
+        }
+        let mut pid = 0u32;
+        unsafe { GetWindowThreadProcessId(hwnd, &mut pid) };
+        if pid == current_pid {
+            continue;
+        }
+        let mut rect = RECT::default();
+        unsafe { GetWindowRect(hwnd, &mut rect as *mut _) };
+        let origin = IPoint::xy(rect.left, rect.top);
+        let size = ISize::wh(rect.right - rect.left, rect.bottom - rect.top);
+        let (logical_origin, scale) = match (
+            displays.convert_physical_to_logical(&origin),
+            displays.display_for_physical_point(&origin),
+        ) {
+            (Some(origin), Some(display)) => (origin, display.scale),
+            _ => continue,
+        };
+        res.push(ExternalWindow {
+            id: hwnd.0 as i64,
+            title: window_title(hwnd),
+            owner_name: process_name(pid),
+            owner_pid: pid as i64,
+            frame: Rect::origin_size(
+                &logical_origin,
+                &Size::from(size.clone()).scaled(1.0 / scale),
+            ),
+            thumbnail: request
+                .thumbnail_size
+                .as_ref()
+                .and_then(|max_size| thumbnail(hwnd, &size, max_size)),
+        });
+    }
+    Ok(res)
+}
+
+// Minimized windows can not be captured
+fn thumbnail(hwnd: HWND, size: &ISize, max_size: &Size) -> Option<ImageData> {
+    if size.width <= 0 || size.height <= 0 || unsafe { IsIconic(hwnd) }.as_bool() {
+        return None;
+    }
+    let scale = (max_size.width / size.width as f64)
+        .min(max_size.height / size.height as f64)
+        .min(1.0);
+    let width = ((size.width as f64 * scale).round() as i32).max(1);
+    let height = ((size.height as f64 * scale).round() as i32).max(1);
+
+    let info = BITMAPINFO {
+        bmiHeader: BITMAPINFOHEADER {
+            biSize: size_of::<BITMAPINFOHEADER>() as u32,
+            biWidth: width,
+            // Top-down
+            biHeight: -height,
+            biPlanes: 1,
+            biBitCount: 32,
+            biCompression: BI_RGB as u32,
+            biSizeImage: (width * height * 4) as u32,
+            biXPelsPerMeter: 0,
+            biYPelsPerMeter: 0,
+            biClrUsed: 0,
+            biClrImportant: 0,
+        },
+        bmiColors: Default::default(),
+    };
+
+    unsafe {
+        let screen_dc = GetDC(HWND(0));
+        let window_dc = CreateCompatibleDC(screen_dc);
+        let window_bitmap = CreateCompatibleBitmap(screen_dc, size.width, size.height);
+        let prev_window_bitmap = SelectObject(HDC(window_dc.0), HGDIOBJ(window_bitmap.0));
+
+        let thumbnail_dc = CreateCompatibleDC(screen_dc);
+        let mut bits: *mut c_void = std::ptr::null_mut();
+        let thumbnail_bitmap = CreateDIBSection(
+            screen_dc,
+            &info as *const _,
+            DIB_RGB_COLORS,
+            &mut bits,
+            HANDLE(0),
+            0,
+        );
+        let prev_thumbnail_bitmap = SelectObject(HDC(thumbnail_dc.0), HGDIOBJ(thumbnail_bitmap.0));
+
+        let res = PrintWindow(hwnd, HDC(window_dc.0), PW_RENDERFULLCONTENT).as_bool() && {
+            SetStretchBltMode(HDC(thumbnail_dc.0), HALFTONE);
+            StretchBlt(
+                HDC(thumbnail_dc.0),
+                0,
+                0,
+                width,
+                height,
+                HDC(window_dc.0),
+                0,
+                0,
+                size.width,
+                size.height,
+                SRCCOPY,
+            )
+            .as_bool()
+        };
+
+        let data = if res && !bits.is_null() {
+            let bits = std::slice::from_raw_parts(bits as *const u8, (width * height * 4) as usize);
+            let mut data = Vec::with_capacity(bits.len());
+            for pixel in bits.chunks_exact(4) {
+                data.extend_from_slice(&[pixel[2], pixel[1], pixel[0], 255]);
+            }
+            Some(data)
+        } else {
+            None
+        };
+
+        SelectObject(HDC(thumbnail_dc.0), prev_thumbnail_bitmap);
+        SelectObject(HDC(window_dc.0), prev_window_bitmap);
+        DeleteObject(HGDIOBJ(thumbnail_bitmap.0));
+        DeleteObject(HGDIOBJ(window_bitmap.0));
+        DeleteDC(thumbnail_dc);
+        DeleteDC(window_dc);
+        ReleaseDC(HWND(0), screen_dc);
+
+        data.map(|data| ImageData {
+            width,
+            height,
+            bytes_per_row: width * 4,
+            data,
+        })
+    }
+}
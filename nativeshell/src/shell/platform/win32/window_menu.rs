@@ -129,7 +129,12 @@ impl WindowMenu {
         self.mouse_state.borrow_mut().ignore_mouse_leave = true;
 
         let mut params = {
-            if let Some(item_rect) = request.item_rect.as_ref() {
+            // Menu opened from keyboard should not obscure the focused element
+            let exclude_rect = request
+                .item_rect
+                .as_ref()
+                .or(request.keyboard_anchor.as_ref());
+            if let Some(item_rect) = exclude_rect {
                 let top_left = self
                     .delegate()
                     .get_state()
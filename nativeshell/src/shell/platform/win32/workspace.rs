@@ -0,0 +1,156 @@
+use std::ptr::null_mut;
+
+use crate::shell::api_model::{DefaultHandler, DropFilter, HandlerTarget};
+
+use super::{
+    all_bindings::*,
+    error::{PlatformError, PlatformResult},
+    util::{get_class_registry_string, HRESULTExt, HRESULT_FROM_WIN32},
+};
+
+pub fn open_url(url: &str) -> PlatformResult<()> {
+    shell_execute(url)
+}
+
+// Opens file in default application or directory in Explorer
+pub fn open_path(path: &str) -> PlatformResult<()> {
+    shell_execute(path)
+}
+
+pub fn reveal_in_file_manager(path: &str) -> PlatformResult<()> {
+    unsafe {
+        let item = ILCreateFromPathW(path);
+        if item.is_null() {
+            return Err(PlatformError::HResult(HRESULT_FROM_WIN32(GetLastError().0)));
+        }
+        let res = SHOpenFolderAndSelectItems(item, 0, null_mut(), 0);
+        ILFree(item);
+        res.as_platform_result()
+    }
+}
+
+// Trash location is not reported by shell
+pub fn trash_items(paths: &[String]) -> PlatformResult<Vec<Option<String>>> {
+    if paths.is_empty() {
+        return Ok(Vec::new());
+    }
+    // Paths are separated by null character; List is terminated by two
+    let mut from: Vec<u16> = Vec::new();
+    for path in paths {
+        from.extend(path.encode_utf16());
+        from.push(0);
+    }
+    from.push(0);
+    let mut operation = SHFILEOPSTRUCTW {
+        hwnd: HWND(0),
+        wFunc: FO_DELETE,
+        pFrom: PWSTR(from.as_mut_ptr()),
+        pTo: PWSTR::NULL,
+        fFlags: (FOF_ALLOWUNDO | FOF_NOCONFIRMATION | FOF_NOERRORUI | FOF_SILENT) as u16,
+        fAnyOperationsAborted: false.into(),
+        hNameMappings: null_mut(),
+        lpszProgressTitle: PWSTR::NULL,
+    };
+    let res = unsafe { SHFileOperationW(&mut operation) };
+    if res != 0 {
+        return Err(PlatformError::HResult(HRESULT_FROM_WIN32(res as u32)));
+    }
+    if operation.fAnyOperationsAborted.as_bool() {
+        return Err(PlatformError::UnknownError);
+    }
+    Ok(vec![None; paths.len()])
+}
+
+// Matches MIME type registered for file extension; Supports "type/*" wildcards
+pub fn file_conforms_to(path: &str, filter: &DropFilter) -> bool {
+    let extension = match std::path::Path::new(path).extension() {
+        Some(extension) => extension.to_string_lossy(),
+        None => return false,
+    };
+    let mime_type = match get_class_registry_string(&format!(".{}", extension), "Content Type") {
+        Some(mime_type) => mime_type.to_lowercase(),
+        None => return false,
+    };
+    filter.mime_types.iter().any(|m| {
+        let m = m.to_lowercase();
+        match m.strip_suffix("/*") {
+            Some(prefix) => mime_type.split('/').next() == Some(prefix),
+            None => m == mime_type,
+        }
+    })
+}
+
+fn assoc_query_string(target: &HandlerTarget, string: ASSOCSTR) -> Option<String> {
+    let (flags, assoc) = match target {
+        HandlerTarget::UrlScheme(scheme) => (ASSOCF_IS_PROTOCOL as u32, scheme.clone()),
+        HandlerTarget::FileExtension(extension) => (0, format!(".{}", extension)),
+    };
+    let mut buf = [0u16; 1024];
+    let mut len = buf.len() as u32;
+    let res = unsafe {
+        AssocQueryStringW(
+            flags,
+            string,
+            assoc.as_str(),
+            PWSTR::NULL,
+            PWSTR(buf.as_mut_ptr()),
+            &mut len as *mut _,
+        )
+    };
+    if res.is_ok() {
+        let len = buf.iter().position(|c| *c == 0).unwrap_or(buf.len());
+        Some(String::from_utf16_lossy(&buf[..len]))
+    } else {
+        None
+    }
+}
+
+// Id is path of handler executable
+pub fn default_handler(target: &HandlerTarget) -> PlatformResult<Option<DefaultHandler>> {
+    let executable = match assoc_query_string(target, ASSOCSTR_EXECUTABLE) {
+        Some(executable) => executable,
+        None => return Ok(None),
+    };
+    let name = assoc_query_string(target, ASSOCSTR_FRIENDLYAPPNAME).unwrap_or_else(|| {
+        std::path::Path::new(&executable)
+            .file_stem()
+            .map(|s| s.to_string_lossy().into())
+            .unwrap_or_default()
+    });
+    let is_current_application = std::env::current_exe()
+        .map(|exe| exe.to_string_lossy().eq_ignore_ascii_case(&executable))
+        .unwrap_or(false);
+    Ok(Some(DefaultHandler {
+        name,
+        id: executable,
+        is_current_application,
+    }))
+}
+
+// Since Windows 8 default handlers can only be changed by user; Opens default
+// apps settings instead
+#[allow(unused_variables)]
+pub fn set_as_default_handler(target: &HandlerTarget) -> PlatformResult<()> {
+    shell_execute("ms-settings:defaultapps")
+}
+
+fn shell_execute(file: &str) -> PlatformResult<()> {
+    let instance = unsafe {
+        ShellExecuteW(
+            HWND(0),
+            "open",
+            file,
+            PWSTR::NULL,
+            PWSTR::NULL,
+            SW_SHOWNORMAL.0 as i32,
+        )
+    };
+    // Values greater than 32 indicate success
+    if instance.0 > 32 {
+        Ok(())
+    } else {
+        Err(PlatformError::HResult(HRESULT_FROM_WIN32(unsafe {
+            GetLastError().0
+        })))
+    }
+}
@@ -0,0 +1,53 @@
+use std::{collections::HashMap, rc::Rc};
+
+use crate::{codec::Value, Error, Result};
+
+use super::{platform::native_view::NativeViewType, WindowHandle};
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PlatformViewHandle(pub(crate) i64);
+
+// Invoked with window that will host the view and parameters passed from Dart;
+// Returned native view is owned by the window until disposed from Dart or
+// until the window is closed.
+pub type PlatformViewFactory = dyn Fn(WindowHandle, Value) -> Result<NativeViewType>;
+
+// Keeps track of factories for native views (NSView, HWND, GtkWidget) that can
+// be embedded in window content through the PlatformView widget.
+pub struct PlatformViewManager {
+    factories: HashMap<String, Rc<PlatformViewFactory>>,
+    next_handle: PlatformViewHandle,
+}
+
+impl PlatformViewManager {
+    pub(super) fn new() -> Self {
+        Self {
+            factories: HashMap::new(),
+            next_handle: PlatformViewHandle(1),
+        }
+    }
+
+    pub fn register_factory<F>(&mut self, view_type: &str, factory: F)
+    where
+        F: Fn(WindowHandle, Value) -> Result<NativeViewType> + 'static,
+    {
+        self.factories.insert(view_type.into(), Rc::new(factory));
+    }
+
+    pub fn unregister_factory(&mut self, view_type: &str) {
+        self.factories.remove(view_type);
+    }
+
+    pub(super) fn get_factory(&self, view_type: &str) -> Result<Rc<PlatformViewFactory>> {
+        self.factories
+            .get(view_type)
+            .cloned()
+            .ok_or(Error::UnknownPlatformViewType)
+    }
+
+    pub(super) fn next_handle(&mut self) -> PlatformViewHandle {
+        let res = self.next_handle;
+        self.next_handle.0 += 1;
+        res
+    }
+}
@@ -0,0 +1,49 @@
+use crate::Result;
+
+use super::platform::power::{PlatformActivityGuard, PlatformSleepGuard};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SleepPreventionKind {
+    // System stays awake, display may still turn off (i.e. long running export)
+    System,
+    // Display stays on and screen saver is not started (i.e. video playback)
+    Display,
+}
+
+// Keeps the system (or display) awake until dropped
+pub struct SleepGuard {
+    _platform_guard: PlatformSleepGuard,
+}
+
+// Prevents idle sleep; Reason may be shown to user by the system
+pub fn prevent_sleep(kind: SleepPreventionKind, reason: &str) -> Result<SleepGuard> {
+    let platform_guard = PlatformSleepGuard::new(kind, reason)?;
+    Ok(SleepGuard {
+        _platform_guard: platform_guard,
+    })
+}
+
+// Kind of work performed while activity is in progress (MacOS App Nap)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ActivityOptions {
+    // Application is not napped while activity is in progress
+    pub user_initiated: bool,
+    // Timers are not coalesced (i.e. real-time audio or input processing)
+    pub latency_critical: bool,
+    // System stays awake while activity is in progress
+    pub prevent_idle_sleep: bool,
+}
+
+// Ends the activity when dropped
+pub struct ActivityGuard {
+    _platform_guard: PlatformActivityGuard,
+}
+
+// Opts out of App Nap and timer coalescing for duration of latency sensitive
+// work; No-op on platforms without such throttling
+pub fn begin_activity(options: ActivityOptions, reason: &str) -> Result<ActivityGuard> {
+    let platform_guard = PlatformActivityGuard::new(options, reason)?;
+    Ok(ActivityGuard {
+        _platform_guard: platform_guard,
+    })
+}
@@ -0,0 +1,86 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    codec::{value::to_value, MethodCall, MethodCallReply, Value},
+    util::OkLog,
+    Result,
+};
+
+use super::{
+    api_constants::*, api_model::PowerStatus, platform::power::PlatformPowerSource, Context,
+    EngineHandle,
+};
+
+// Battery and power source status, so that applications can throttle
+// background work when running on battery or in low power mode
+#[cfg_attr(feature = "mock", allow(dead_code))]
+pub struct PowerManager {
+    context: Rc<Context>,
+    platform_power_source: PlatformPowerSource,
+    last_status: RefCell<Option<PowerStatus>>,
+}
+
+impl PowerManager {
+    pub(super) fn new(context: Rc<Context>) -> Self {
+        let context_copy = context.clone();
+        context
+            .message_manager
+            .borrow_mut()
+            .register_method_handler(channel::POWER, move |value, reply, engine| {
+                context_copy
+                    .power_manager
+                    .borrow()
+                    .on_method_call(value, reply, engine);
+            });
+
+        Self {
+            context: context.clone(),
+            platform_power_source: PlatformPowerSource::new(context),
+            last_status: RefCell::new(None),
+        }
+    }
+
+    pub fn get_status(&self) -> Result<PowerStatus> {
+        let status = self.platform_power_source.get_status()?;
+        self.last_status.replace(Some(status.clone()));
+        Ok(status)
+    }
+
+    // Called by platform when power status (possibly) changed; Engines are only
+    // notified if the status differs from last known value
+    #[cfg_attr(feature = "mock", allow(dead_code))]
+    pub(crate) fn status_changed(&self) {
+        let previous = self.last_status.borrow().clone();
+        let status = match self.get_status().ok_log() {
+            Some(status) => status,
+            None => return,
+        };
+        if previous.as_ref() == Some(&status) {
+            return;
+        }
+        let engines = self.context.engine_manager.borrow().get_all_engines();
+        let message_manager = self.context.message_manager.borrow();
+        for engine in engines {
+            if let Some(invoker) = message_manager.get_method_invoker(engine, channel::POWER) {
+                invoker
+                    .call_method(
+                        method::power::STATUS_CHANGED.into(),
+                        to_value(&status).unwrap(),
+                        |_| {},
+                    )
+                    .ok_log();
+            }
+        }
+    }
+
+    fn on_method_call(
+        &self,
+        call: MethodCall<Value>,
+        reply: MethodCallReply<Value>,
+        _engine: EngineHandle,
+    ) {
+        if call.method.as_str() == method::power::GET_STATUS {
+            reply.send_result(self.get_status());
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{Error, Result};
+
+use super::{platform::render_layer::PlatformRenderLayer, Context, WindowHandle};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RenderLayerPosition {
+    // Covers flutter content; Does not receive mouse input
+    AboveContent,
+    // Only visible where flutter content is transparent; Not supported on Linux
+    BelowContent,
+}
+
+// Invoked on main thread with layer size in physical pixels and scale factor;
+// Called synchronously during live resize so that the layer can render new frame
+// before window contents is presented.
+pub type RenderLayerResizeCallback = dyn Fn(usize, usize, f64);
+
+// Native layer covering whole window content for custom rendering from Rust.
+// Layer is resized together with window and removed when dropped.
+pub struct RenderLayer {
+    platform_layer: PlatformRenderLayer,
+}
+
+impl RenderLayer {
+    pub fn new<F>(
+        context: &Context,
+        window: WindowHandle,
+        position: RenderLayerPosition,
+        on_resize: F,
+    ) -> Result<Self>
+    where
+        F: Fn(usize, usize, f64) + 'static,
+    {
+        let window = context
+            .window_manager
+            .borrow()
+            .get_window(window)
+            .ok_or(Error::InvalidWindowHandle)?;
+        let platform_layer = PlatformRenderLayer::new(
+            window.platform_window.borrow().clone(),
+            position,
+            Box::new(on_resize),
+        )?;
+        Ok(Self { platform_layer })
+    }
+
+    // CAMetalLayer on macOS, HWND suitable for swap chain on Windows, GdkWindow
+    // with native surface on Linux
+    pub fn native_handle(&self) -> usize {
+        self.platform_layer.native_handle()
+    }
+}
@@ -1,18 +1,111 @@
-use std::{rc::Rc, time::Duration};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    rc::{Rc, Weak},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, ThreadId},
+    time::Duration,
+};
 
 use super::platform::run_loop::{
     HandleType, PlatformRunLoop, PlatformRunLoopSender, INVALID_HANDLE,
 };
 
+use super::{
+    run_loop_instrumentation::RunLoopInstrumentation, RunLoopStats, RunLoopTaskInfo,
+    RunLoopTaskKind, VirtualRunLoop, VirtualRunLoopSender, Watchdog, WatchdogOptions,
+};
+
+pub use super::platform::run_loop::EventSourceType;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TaskPriority {
+    // Work that user is waiting for, i.e. responding to input
+    UserInteractive,
+    #[default]
+    Default,
+    // Background work; Runs only when run loop would otherwise block waiting
+    // for events
+    Idle,
+}
+
+enum RunLoopBackend {
+    Platform(PlatformRunLoop),
+    Virtual(VirtualRunLoop),
+}
+
+impl RunLoopBackend {
+    fn schedule<F>(&self, in_time: Duration, priority: TaskPriority, callback: F) -> HandleType
+    where
+        F: FnOnce() + 'static,
+    {
+        match self {
+            RunLoopBackend::Platform(run_loop) => run_loop.schedule(in_time, priority, callback),
+            RunLoopBackend::Virtual(run_loop) => run_loop.schedule(in_time, priority, callback),
+        }
+    }
+
+    fn unschedule(&self, handle: HandleType) {
+        match self {
+            RunLoopBackend::Platform(run_loop) => run_loop.unschedule(handle),
+            RunLoopBackend::Virtual(run_loop) => run_loop.unschedule(handle),
+        }
+    }
+
+    fn add_event_source<F>(&self, source: EventSourceType, callback: F) -> HandleType
+    where
+        F: FnMut() + 'static,
+    {
+        match self {
+            RunLoopBackend::Platform(run_loop) => run_loop.add_event_source(source, callback),
+            RunLoopBackend::Virtual(run_loop) => run_loop.add_event_source(source, callback),
+        }
+    }
+
+    fn remove_event_source(&self, handle: HandleType) {
+        match self {
+            RunLoopBackend::Platform(run_loop) => run_loop.remove_event_source(handle),
+            RunLoopBackend::Virtual(run_loop) => run_loop.remove_event_source(handle),
+        }
+    }
+
+    fn run(&self) {
+        match self {
+            RunLoopBackend::Platform(run_loop) => run_loop.run(),
+            RunLoopBackend::Virtual(run_loop) => run_loop.run(),
+        }
+    }
+
+    fn stop(&self) {
+        match self {
+            RunLoopBackend::Platform(run_loop) => run_loop.stop(),
+            RunLoopBackend::Virtual(run_loop) => run_loop.stop(),
+        }
+    }
+
+    fn new_sender(&self) -> SenderBackend {
+        match self {
+            RunLoopBackend::Platform(run_loop) => SenderBackend::Platform(run_loop.new_sender()),
+            RunLoopBackend::Virtual(run_loop) => SenderBackend::Virtual(run_loop.new_sender()),
+        }
+    }
+}
+
 pub struct ScheduledCallback {
-    platform_run_loop: Rc<PlatformRunLoop>,
+    backend: Rc<RunLoopBackend>,
     handle: HandleType,
 }
 
 impl ScheduledCallback {
     pub fn cancel(&mut self) {
         if self.handle != INVALID_HANDLE {
-            self.platform_run_loop.unschedule(self.handle);
+            self.backend.unschedule(self.handle);
 
             self.handle = INVALID_HANDLE;
         }
@@ -29,14 +122,176 @@ impl Drop for ScheduledCallback {
     }
 }
 
+// Timer that invokes the callback repeatedly every interval until paused or dropped.
+// Timer can be paused, resumed and rescheduled (also from within the callback).
+pub struct RepeatingTimer {
+    state: Rc<RepeatingTimerState>,
+}
+
+struct RepeatingTimerState {
+    backend: Rc<RunLoopBackend>,
+    instrumentation: Arc<RunLoopInstrumentation>,
+    interval: Cell<Duration>,
+    handle: Cell<HandleType>,
+    callback: RefCell<Box<dyn FnMut()>>,
+}
+
+impl RepeatingTimer {
+    // Stops the timer; Callback will not be invoked until resumed or rescheduled
+    pub fn pause(&self) {
+        self.state.disarm();
+    }
+
+    // Resumes paused timer; Callback will be invoked after interval
+    pub fn resume(&self) {
+        if self.is_paused() {
+            self.state.arm(self.state.interval.get());
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state.handle.get() == INVALID_HANDLE
+    }
+
+    // Invokes the callback after in_time and then every interval. Replaces currently
+    // scheduled invocation; Resumes the timer if it was paused.
+    pub fn reschedule(&self, in_time: Duration) {
+        self.state.arm(in_time);
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.state.interval.get()
+    }
+
+    // New interval is used after next invocation; Use reschedule to apply it
+    // immediately.
+    pub fn set_interval(&self, interval: Duration) {
+        self.state.interval.set(interval);
+    }
+}
+
+impl Drop for RepeatingTimer {
+    fn drop(&mut self) {
+        self.state.disarm();
+    }
+}
+
+impl RepeatingTimerState {
+    fn arm(self: &Rc<Self>, in_time: Duration) {
+        self.disarm();
+        let weak = Rc::downgrade(self);
+        let task = self.instrumentation.task_queued(
+            RunLoopTaskKind::Scheduled,
+            TaskPriority::Default,
+            in_time,
+        );
+        let handle = self
+            .backend
+            .schedule(in_time, TaskPriority::Default, move || {
+                task.run(|| Self::fire(weak));
+            });
+        self.handle.set(handle);
+    }
+
+    fn disarm(&self) {
+        let handle = self.handle.replace(INVALID_HANDLE);
+        if handle != INVALID_HANDLE {
+            self.backend.unschedule(handle);
+        }
+    }
+
+    fn fire(weak: Weak<Self>) {
+        if let Some(state) = weak.upgrade() {
+            // handle has been consumed by platform run loop; re-arm before invoking
+            // the callback so that it can pause or reschedule the timer
+            state.handle.set(INVALID_HANDLE);
+            state.arm(state.interval.get());
+            let mut callback = state.callback.borrow_mut();
+            callback();
+        }
+    }
+}
+
+pub struct EventSource {
+    backend: Rc<RunLoopBackend>,
+    handle: HandleType,
+}
+
+impl EventSource {
+    pub fn remove(&mut self) {
+        if self.handle != INVALID_HANDLE {
+            self.backend.remove_event_source(self.handle);
+
+            self.handle = INVALID_HANDLE;
+        }
+    }
+
+    // Returns false if the source could not be registered with platform run loop
+    pub fn is_valid(&self) -> bool {
+        self.handle != INVALID_HANDLE
+    }
+}
+
+impl Drop for EventSource {
+    fn drop(&mut self) {
+        self.remove();
+    }
+}
+
 pub struct RunLoop {
-    platform_run_loop: Rc<PlatformRunLoop>,
+    backend: Rc<RunLoopBackend>,
+    instrumentation: Arc<RunLoopInstrumentation>,
 }
 
 impl RunLoop {
     pub fn new() -> Self {
         Self {
-            platform_run_loop: Rc::new(PlatformRunLoop::new()),
+            backend: Rc::new(RunLoopBackend::Platform(PlatformRunLoop::new())),
+            instrumentation: Arc::new(RunLoopInstrumentation::new()),
+        }
+    }
+
+    // Run loop with virtual time for unit tests; See VirtualRunLoop.
+    pub fn new_virtual() -> Self {
+        Self {
+            backend: Rc::new(RunLoopBackend::Virtual(VirtualRunLoop::new())),
+            instrumentation: Arc::new(RunLoopInstrumentation::new()),
+        }
+    }
+
+    // Statistics of tasks executed by this run loop (since creation or last reset)
+    pub fn stats(&self) -> RunLoopStats {
+        self.instrumentation.stats()
+    }
+
+    pub fn reset_stats(&self) {
+        self.instrumentation.reset_stats();
+    }
+
+    // Observer is invoked on run loop thread after every executed task (scheduled
+    // callbacks, repeating timers and callbacks sent from other threads). Can be
+    // used to find tasks that block the run loop thread.
+    pub fn add_task_observer<F>(&self, observer: F) -> i64
+    where
+        F: Fn(&RunLoopTaskInfo) + Send + Sync + 'static,
+    {
+        self.instrumentation.add_observer(observer)
+    }
+
+    pub fn remove_task_observer(&self, observer: i64) {
+        self.instrumentation.remove_observer(observer);
+    }
+
+    // Returns virtual run loop that can be used to advance time; None for
+    // platform run loop
+    pub fn as_virtual(&self) -> Option<&VirtualRunLoop> {
+        match self.backend.as_ref() {
+            // Mock platform run loop is virtual
+            #[cfg(feature = "mock")]
+            RunLoopBackend::Platform(run_loop) => Some(run_loop),
+            #[cfg(not(feature = "mock"))]
+            RunLoopBackend::Platform(_) => None,
+            RunLoopBackend::Virtual(run_loop) => Some(run_loop),
         }
     }
 
@@ -45,12 +300,42 @@ impl RunLoop {
     where
         F: FnOnce() + 'static,
     {
+        self.schedule_with_priority(in_time, TaskPriority::Default, callback)
+    }
+
+    // Callbacks with higher priority that are due are invoked first. Idle callbacks are
+    // only invoked once they are due and there are no other events pending. On macOS
+    // UserInteractive and Default priorities are equivalent.
+    #[must_use]
+    pub fn schedule_with_priority<F>(
+        &self,
+        in_time: Duration,
+        priority: TaskPriority,
+        callback: F,
+    ) -> ScheduledCallback
+    where
+        F: FnOnce() + 'static,
+    {
+        let task = self
+            .instrumentation
+            .task_queued(RunLoopTaskKind::Scheduled, priority, in_time);
         ScheduledCallback {
-            platform_run_loop: self.platform_run_loop.clone(),
-            handle: self.platform_run_loop.schedule(in_time, callback),
+            backend: self.backend.clone(),
+            handle: self
+                .backend
+                .schedule(in_time, priority, move || task.run(callback)),
         }
     }
 
+    // Convenience method to schedule callback to be invoked next time run loop is idle
+    #[must_use]
+    pub fn schedule_idle<F>(&self, callback: F) -> ScheduledCallback
+    where
+        F: FnOnce() + 'static,
+    {
+        self.schedule_with_priority(Duration::from_secs(0), TaskPriority::Idle, callback)
+    }
+
     // Convenience method to schedule callback on next run loop turn
     #[must_use]
     pub fn schedule_now<F>(&self, callback: F) -> ScheduledCallback
@@ -60,24 +345,195 @@ impl RunLoop {
         self.schedule(Duration::from_secs(0), callback)
     }
 
+    // Invokes the callback every interval (first invocation after interval) until
+    // returned timer is paused or dropped.
+    #[must_use]
+    pub fn schedule_repeating<F>(&self, interval: Duration, callback: F) -> RepeatingTimer
+    where
+        F: FnMut() + 'static,
+    {
+        let state = Rc::new(RepeatingTimerState {
+            backend: self.backend.clone(),
+            instrumentation: self.instrumentation.clone(),
+            interval: Cell::new(interval),
+            handle: Cell::new(INVALID_HANDLE),
+            callback: RefCell::new(Box::new(callback)),
+        });
+        state.arm(interval);
+        RepeatingTimer { state }
+    }
+
+    // Invokes the callback on run loop thread whenever the source is ready. Source is
+    // file descriptor on Linux and macOS (callback is invoked when it becomes readable)
+    // and waitable HANDLE on Windows (callback is invoked when it is signaled).
+    // Caller retains ownership of the source, which must outlive returned EventSource.
+    #[must_use]
+    pub fn add_event_source<F>(&self, source: EventSourceType, callback: F) -> EventSource
+    where
+        F: FnMut() + 'static,
+    {
+        EventSource {
+            backend: self.backend.clone(),
+            handle: self.backend.add_event_source(source, callback),
+        }
+    }
+
+    // Starts a thread that reports when run loop thread doesn't process events
+    // for options.timeout (i.e. because of a deadlock). Must be called on run
+    // loop thread; Watchdog is stopped when dropped.
+    pub fn start_watchdog(&self, options: WatchdogOptions) -> Watchdog {
+        Watchdog::new(self.new_sender(), options)
+    }
+
     pub fn run(&self) {
-        self.platform_run_loop.run()
+        self.backend.run()
     }
 
     pub fn stop(&self) {
-        self.platform_run_loop.stop()
+        self.backend.stop()
     }
 
     pub fn new_sender(&self) -> RunLoopSender {
         RunLoopSender {
-            platform_sender: self.platform_run_loop.new_sender(),
+            sender: self.backend.new_sender(),
+            instrumentation: self.instrumentation.clone(),
+            thread_id: thread::current().id(),
+        }
+    }
+
+    // Runs the future to completion on run loop thread. The future is polled
+    // immediately and then every time its waker is woken (which may happen
+    // from any thread). The future is dropped without completing when the
+    // returned handle is cancelled or dropped, unless it is detached.
+    #[must_use]
+    pub fn spawn<F>(&self, future: F) -> SpawnedTask
+    where
+        F: Future<Output = ()> + 'static,
+    {
+        let task_id = NEXT_TASK_ID.with(|id| id.replace(id.get() + 1));
+        let task = Rc::new(Task {
+            future: RefCell::new(Some(Box::pin(future))),
+            woken_while_polling: Cell::new(false),
+            waker: Arc::new(TaskWaker {
+                task_id,
+                sender: self.new_sender(),
+                scheduled: AtomicBool::new(false),
+            }),
+        });
+        TASKS.with(|tasks| tasks.borrow_mut().insert(task_id, task));
+        poll_task(task_id);
+        SpawnedTask { task_id }
+    }
+}
+
+pub struct SpawnedTask {
+    task_id: usize,
+}
+
+impl SpawnedTask {
+    pub fn cancel(&mut self) {
+        if self.task_id != INVALID_TASK_ID {
+            // Future may spawn or cancel tasks when dropped
+            let task = TASKS.with(|tasks| tasks.borrow_mut().remove(&self.task_id));
+            drop(task);
+            self.task_id = INVALID_TASK_ID;
+        }
+    }
+
+    pub fn detach(&mut self) {
+        self.task_id = INVALID_TASK_ID;
+    }
+
+    // Whether the future has neither completed nor been cancelled yet
+    pub fn is_pending(&self) -> bool {
+        TASKS.with(|tasks| tasks.borrow().contains_key(&self.task_id))
+    }
+}
+
+impl Drop for SpawnedTask {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+const INVALID_TASK_ID: usize = 0;
+
+struct Task {
+    // None while the future is being polled
+    future: RefCell<Option<Pin<Box<dyn Future<Output = ()>>>>>,
+    woken_while_polling: Cell<bool>,
+    waker: Arc<TaskWaker>,
+}
+
+struct TaskWaker {
+    task_id: usize,
+    sender: RunLoopSender,
+    scheduled: AtomicBool,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        if !self.scheduled.swap(true, Ordering::SeqCst) {
+            let task_id = self.task_id;
+            self.sender.send(move || poll_task(task_id));
+        }
+    }
+}
+
+thread_local! {
+    static TASKS: RefCell<HashMap<usize, Rc<Task>>> = RefCell::new(HashMap::new());
+    static NEXT_TASK_ID: Cell<usize> = const { Cell::new(1) };
+}
+
+fn poll_task(task_id: usize) {
+    let task = TASKS.with(|tasks| tasks.borrow().get(&task_id).cloned());
+    let task = match task {
+        Some(task) => task,
+        None => return,
+    };
+    let mut future = match task.future.borrow_mut().take() {
+        Some(future) => future,
+        // Waker invoked synchronously from within poll; let the outer poll
+        // take care of it
+        None => {
+            task.woken_while_polling.set(true);
+            return;
+        }
+    };
+    let waker = Waker::from(task.waker.clone());
+    let mut context = Context::from_waker(&waker);
+    loop {
+        task.waker.scheduled.store(false, Ordering::SeqCst);
+        task.woken_while_polling.set(false);
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(()) => {
+                TASKS.with(|tasks| tasks.borrow_mut().remove(&task_id));
+                return;
+            }
+            Poll::Pending => {
+                if !task.woken_while_polling.get() {
+                    break;
+                }
+            }
         }
     }
+    task.future.replace(Some(future));
 }
 
 // Can be used to send callbacks from other threads to be executed on run loop thread
 pub struct RunLoopSender {
-    platform_sender: PlatformRunLoopSender,
+    sender: SenderBackend,
+    instrumentation: Arc<RunLoopInstrumentation>,
+    thread_id: ThreadId,
+}
+
+enum SenderBackend {
+    Platform(PlatformRunLoopSender),
+    Virtual(VirtualRunLoopSender),
 }
 
 impl RunLoopSender {
@@ -85,6 +541,167 @@ impl RunLoopSender {
     where
         F: FnOnce() + 'static + Send,
     {
-        self.platform_sender.send(callback)
+        let task = self.instrumentation.task_queued(
+            RunLoopTaskKind::Sent,
+            TaskPriority::Default,
+            Duration::from_secs(0),
+        );
+        self.send_untracked(move || task.run(callback));
+    }
+
+    // Not counted in run loop statistics and not reported to task observers;
+    // Used for watchdog heartbeats
+    pub(super) fn send_untracked<F>(&self, callback: F)
+    where
+        F: FnOnce() + 'static + Send,
+    {
+        match &self.sender {
+            SenderBackend::Platform(sender) => sender.send(callback),
+            SenderBackend::Virtual(sender) => sender.send(callback),
+        }
+    }
+
+    // Executes the callback on run loop thread and blocks until it returns.
+    // Callback is invoked directly when called on run loop thread. Returns None
+    // if the callback was dropped without being invoked (i.e. run loop was
+    // terminated).
+    pub fn send_and_wait<F, R>(&self, callback: F) -> Option<R>
+    where
+        F: FnOnce() -> R + 'static + Send,
+        R: 'static + Send,
+    {
+        if thread::current().id() == self.thread_id {
+            return Some(callback());
+        }
+        let (sender, receiver) = mpsc::channel();
+        self.send(move || {
+            sender.send(callback()).ok();
+        });
+        receiver.recv().ok()
+    }
+
+    // Executes the callback on run loop thread; Returned future resolves to the
+    // result of the callback, or None if the callback was dropped without being
+    // invoked.
+    pub fn send_with_result<F, R>(&self, callback: F) -> SendResult<R>
+    where
+        F: FnOnce() -> R + 'static + Send,
+        R: 'static + Send,
+    {
+        let state = Arc::new(Mutex::new(SendResultState {
+            result: None,
+            completed: false,
+            waker: None,
+        }));
+        let completer = SendResultCompleter {
+            state: state.clone(),
+        };
+        self.send(move || completer.complete(callback()));
+        SendResult { state }
+    }
+}
+
+struct SendResultState<R> {
+    result: Option<R>,
+    completed: bool,
+    waker: Option<Waker>,
+}
+
+// Completes the result when dropped, even if the callback was never invoked
+struct SendResultCompleter<R> {
+    state: Arc<Mutex<SendResultState<R>>>,
+}
+
+impl<R> SendResultCompleter<R> {
+    fn complete(self, result: R) {
+        self.state.lock().unwrap().result.replace(result);
+    }
+}
+
+impl<R> Drop for SendResultCompleter<R> {
+    fn drop(&mut self) {
+        let waker = {
+            let mut state = self.state.lock().unwrap();
+            state.completed = true;
+            state.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+pub struct SendResult<R> {
+    state: Arc<Mutex<SendResultState<R>>>,
+}
+
+impl<R> Future for SendResult<R> {
+    type Output = Option<R>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap();
+        if state.completed {
+            Poll::Ready(state.result.take())
+        } else {
+            state.waker.replace(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, future::Future, pin::Pin, rc::Rc, task::Poll};
+
+    use super::RunLoop;
+
+    struct Pending {
+        dropped: Rc<Cell<bool>>,
+    }
+
+    impl Future for Pending {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, _cx: &mut std::task::Context<'_>) -> Poll<()> {
+            Poll::Pending
+        }
+    }
+
+    impl Drop for Pending {
+        fn drop(&mut self) {
+            self.dropped.set(true);
+        }
+    }
+
+    #[test]
+    fn test_cancel_spawned_task() {
+        let run_loop = RunLoop::new_virtual();
+        let dropped = Rc::new(Cell::new(false));
+        let mut task = run_loop.spawn(Pending {
+            dropped: dropped.clone(),
+        });
+        run_loop.as_virtual().unwrap().run_until_idle();
+        assert!(task.is_pending());
+        assert!(!dropped.get());
+
+        task.cancel();
+        assert!(!task.is_pending());
+        assert!(dropped.get());
+
+        // Dropping the handle cancels the task as well
+        let dropped = Rc::new(Cell::new(false));
+        drop(run_loop.spawn(Pending {
+            dropped: dropped.clone(),
+        }));
+        assert!(dropped.get());
+
+        let dropped = Rc::new(Cell::new(false));
+        run_loop
+            .spawn(Pending {
+                dropped: dropped.clone(),
+            })
+            .detach();
+        run_loop.as_virtual().unwrap().run_until_idle();
+        assert!(!dropped.get());
     }
 }
@@ -0,0 +1,157 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use super::TaskPriority;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RunLoopTaskKind {
+    // Callback scheduled on run loop thread (including repeating timers)
+    Scheduled,
+    // Callback sent through RunLoopSender
+    Sent,
+}
+
+#[derive(Clone, Debug)]
+pub struct RunLoopTaskInfo {
+    pub kind: RunLoopTaskKind,
+    pub priority: TaskPriority,
+    // How long after it was due the task started executing
+    pub delay: Duration,
+    pub execution_time: Duration,
+    // Number of tasks still waiting in queue after this task finished
+    pub queue_depth: usize,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct RunLoopStats {
+    pub queue_depth: usize,
+    pub tasks_executed: u64,
+    pub total_execution_time: Duration,
+    // Longest time single task blocked the run loop thread
+    pub longest_stall: Duration,
+}
+
+type TaskObserver = Arc<dyn Fn(&RunLoopTaskInfo) + Send + Sync>;
+
+pub(super) struct RunLoopInstrumentation {
+    queue_depth: AtomicUsize,
+    stats: Mutex<RunLoopStats>,
+    observers: Mutex<Vec<(i64, TaskObserver)>>,
+    next_observer: Mutex<i64>,
+}
+
+// Keeps the task counted in queue depth until the task is executed or dropped
+pub(super) struct QueuedTask {
+    instrumentation: Arc<RunLoopInstrumentation>,
+    kind: RunLoopTaskKind,
+    priority: TaskPriority,
+    due: Instant,
+}
+
+impl Drop for QueuedTask {
+    fn drop(&mut self) {
+        self.instrumentation
+            .queue_depth
+            .fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl QueuedTask {
+    pub(super) fn run<F: FnOnce()>(self, callback: F) {
+        let start = Instant::now();
+        let instrumentation = self.instrumentation.clone();
+        let kind = self.kind;
+        let priority = self.priority;
+        let delay = start.saturating_duration_since(self.due);
+        drop(self);
+        callback();
+        instrumentation.task_finished(RunLoopTaskInfo {
+            kind,
+            priority,
+            delay,
+            execution_time: start.elapsed(),
+            queue_depth: instrumentation.queue_depth.load(Ordering::SeqCst),
+        });
+    }
+}
+
+impl RunLoopInstrumentation {
+    pub(super) fn new() -> Self {
+        Self {
+            queue_depth: AtomicUsize::new(0),
+            stats: Mutex::new(Default::default()),
+            observers: Mutex::new(Vec::new()),
+            next_observer: Mutex::new(1),
+        }
+    }
+
+    pub(super) fn task_queued(
+        self: &Arc<Self>,
+        kind: RunLoopTaskKind,
+        priority: TaskPriority,
+        in_time: Duration,
+    ) -> QueuedTask {
+        self.queue_depth.fetch_add(1, Ordering::SeqCst);
+        QueuedTask {
+            instrumentation: self.clone(),
+            kind,
+            priority,
+            due: Instant::now() + in_time,
+        }
+    }
+
+    fn task_finished(&self, info: RunLoopTaskInfo) {
+        {
+            let mut stats = self.stats.lock().unwrap();
+            stats.tasks_executed += 1;
+            stats.total_execution_time += info.execution_time;
+            stats.longest_stall = stats.longest_stall.max(info.execution_time);
+        }
+        let observers: Vec<TaskObserver> = self
+            .observers
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|o| o.1.clone())
+            .collect();
+        for observer in observers {
+            observer(&info);
+        }
+    }
+
+    pub(super) fn stats(&self) -> RunLoopStats {
+        let mut stats = self.stats.lock().unwrap().clone();
+        stats.queue_depth = self.queue_depth.load(Ordering::SeqCst);
+        stats
+    }
+
+    pub(super) fn reset_stats(&self) {
+        *self.stats.lock().unwrap() = Default::default();
+    }
+
+    pub(super) fn add_observer<F>(&self, observer: F) -> i64
+    where
+        F: Fn(&RunLoopTaskInfo) + Send + Sync + 'static,
+    {
+        let id = {
+            let mut next_observer = self.next_observer.lock().unwrap();
+            let id = *next_observer;
+            *next_observer += 1;
+            id
+        };
+        self.observers
+            .lock()
+            .unwrap()
+            .push((id, Arc::new(observer)));
+        id
+    }
+
+    pub(super) fn remove_observer(&self, observer: i64) {
+        self.observers.lock().unwrap().retain(|o| o.0 != observer);
+    }
+}
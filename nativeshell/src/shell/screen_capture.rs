@@ -0,0 +1,119 @@
+use std::{
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use crate::{
+    util::{Capsule, OkLog},
+    Result,
+};
+
+use super::{
+    platform::screen_capture::PlatformScreenCapture, Context, FlutterEngine, PixelBufferTexture,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ScreenCaptureSource {
+    // Index of display in platform display list; 0 is the main (primary) display
+    Display(usize),
+}
+
+#[derive(Clone, Debug)]
+pub struct ScreenCaptureOptions {
+    pub max_frame_rate: u32,
+
+    // Not supported on Windows (DXGI desktop duplication does not include cursor)
+    pub show_cursor: bool,
+}
+
+impl Default for ScreenCaptureOptions {
+    fn default() -> Self {
+        Self {
+            max_frame_rate: 30,
+            show_cursor: true,
+        }
+    }
+}
+
+// Frame delivered by platform capture; 4 bytes per pixel in BGRA order
+pub struct ScreenCaptureFrame<'a> {
+    pub width: usize,
+    pub height: usize,
+    pub bytes_per_row: usize,
+    pub data: &'a [u8],
+}
+
+// Invoked on capture thread (or dispatch queue) for every captured frame
+pub type ScreenCaptureSink = dyn Fn(&ScreenCaptureFrame) + Send + Sync;
+
+// Captures display content into a pixel buffer texture, which can be used to
+// preview the display in Flutter (i.e. for screen sharing UI). Capture is
+// stopped when dropped. Only available on macOS and Windows; on other platforms
+// `new` fails with not implemented error.
+pub struct ScreenCapture {
+    platform_capture: PlatformScreenCapture,
+    texture: Rc<PixelBufferTexture>,
+}
+
+impl ScreenCapture {
+    pub fn new(
+        context: &Context,
+        engine: &FlutterEngine,
+        source: ScreenCaptureSource,
+        options: ScreenCaptureOptions,
+    ) -> Result<Self> {
+        let texture = Rc::new(PixelBufferTexture::new(engine)?);
+        let buffer = texture.buffer();
+        let sender = context.run_loop.borrow().new_sender();
+        let weak_texture = Arc::new(Mutex::new(Capsule::new_with_sender(
+            Rc::downgrade(&texture),
+            context.run_loop.borrow().new_sender(),
+        )));
+        // Frames captured before run loop gets to mark previous frame available
+        // only replace the pixels
+        let notification_pending = Arc::new(AtomicBool::new(false));
+        let sink = move |frame: &ScreenCaptureFrame| {
+            buffer.lock().unwrap().update_bgra(
+                frame.width,
+                frame.height,
+                frame.bytes_per_row,
+                frame.data,
+            );
+            if !notification_pending.swap(true, Ordering::SeqCst) {
+                let weak_texture = weak_texture.clone();
+                let notification_pending = notification_pending.clone();
+                sender.send(move || {
+                    notification_pending.store(false, Ordering::SeqCst);
+                    let texture = weak_texture
+                        .lock()
+                        .unwrap()
+                        .get_ref()
+                        .and_then(|t| t.upgrade());
+                    if let Some(texture) = texture {
+                        texture.mark_frame_available().ok_log();
+                    }
+                });
+            }
+        };
+        let platform_capture = PlatformScreenCapture::new(source, options, Arc::new(sink))?;
+        Ok(Self {
+            platform_capture,
+            texture,
+        })
+    }
+
+    pub fn texture_id(&self) -> i64 {
+        self.texture.id()
+    }
+
+    pub fn start(&self) -> Result<()> {
+        self.platform_capture.start().map_err(|e| e.into())
+    }
+
+    pub fn stop(&self) -> Result<()> {
+        self.platform_capture.stop().map_err(|e| e.into())
+    }
+}
@@ -0,0 +1,94 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{
+    codec::{value::to_value, MethodCall, MethodCallError, MethodCallReply, Value},
+    util::OkLog,
+    Result,
+};
+
+use super::{
+    api_constants::*, api_model::SystemThemeSettings, platform::system_theme::PlatformSystemTheme,
+    Context, EngineHandle, WindowMethodCallResult,
+};
+
+#[cfg_attr(feature = "mock", allow(dead_code))]
+pub struct SystemThemeManager {
+    context: Rc<Context>,
+    platform_system_theme: PlatformSystemTheme,
+    last_settings: RefCell<Option<SystemThemeSettings>>,
+}
+
+impl SystemThemeManager {
+    pub(super) fn new(context: Rc<Context>) -> Self {
+        let context_copy = context.clone();
+        context
+            .message_manager
+            .borrow_mut()
+            .register_method_handler(channel::SYSTEM_THEME, move |value, reply, engine| {
+                context_copy
+                    .system_theme_manager
+                    .borrow()
+                    .on_method_call(value, reply, engine);
+            });
+
+        Self {
+            context: context.clone(),
+            platform_system_theme: PlatformSystemTheme::new(context),
+            last_settings: RefCell::new(None),
+        }
+    }
+
+    pub fn get_settings(&self) -> Result<SystemThemeSettings> {
+        let settings = self.platform_system_theme.get_settings()?;
+        self.last_settings.replace(Some(settings.clone()));
+        Ok(settings)
+    }
+
+    // Called by platform when system appearance (possibly) changed; Engines are only
+    // notified if theme settings actually differ from last known values
+    #[cfg_attr(feature = "mock", allow(dead_code))]
+    pub(crate) fn settings_changed(&self) {
+        let previous = self.last_settings.borrow().clone();
+        let settings = match self.get_settings().ok_log() {
+            Some(settings) => settings,
+            None => return,
+        };
+        if previous.as_ref() == Some(&settings) {
+            return;
+        }
+        let engines = self.context.engine_manager.borrow().get_all_engines();
+        let message_manager = self.context.message_manager.borrow();
+        for engine in engines {
+            if let Some(invoker) = message_manager.get_method_invoker(engine, channel::SYSTEM_THEME)
+            {
+                invoker
+                    .call_method(
+                        method::system_theme::SETTINGS_CHANGED.into(),
+                        to_value(&settings).unwrap(),
+                        |_| {},
+                    )
+                    .ok_log();
+            }
+        }
+    }
+
+    fn map_result<T>(result: Result<T>) -> WindowMethodCallResult
+    where
+        T: serde::Serialize,
+    {
+        result
+            .map(|v| to_value(v).unwrap())
+            .map_err(MethodCallError::from_error)
+    }
+
+    fn on_method_call(
+        &self,
+        call: MethodCall<Value>,
+        reply: MethodCallReply<Value>,
+        _engine: EngineHandle,
+    ) {
+        if call.method.as_str() == method::system_theme::GET_SETTINGS {
+            reply.send(Self::map_result(self.get_settings()));
+        }
+    }
+}
@@ -0,0 +1,344 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use crate::{
+    codec::{MethodCall, MethodCodec, StandardMethodCodec, Value},
+    Error, Result,
+};
+
+use super::{Context, EngineHandle, EngineOptions, MessageDirection, Point, WindowHandle};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SyntheticMouseButton {
+    Left,
+    Right,
+    Middle,
+}
+
+// Input event delivered to window as if it came from the system. Positions are in
+// window content coordinates (logical pixels).
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyntheticEvent {
+    MouseDown {
+        position: Point,
+        button: SyntheticMouseButton,
+    },
+    MouseUp {
+        position: Point,
+        button: SyntheticMouseButton,
+    },
+    MouseMove {
+        position: Point,
+    },
+    // Key code is platform specific (virtual key code on macOS and Windows,
+    // GDK key value on Linux)
+    KeyDown {
+        key_code: i64,
+        characters: String,
+    },
+    KeyUp {
+        key_code: i64,
+        characters: String,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordedMessage {
+    pub engine: EngineHandle,
+    pub direction: MessageDirection,
+    pub channel: String,
+    pub data: Vec<u8>,
+}
+
+impl RecordedMessage {
+    // Decodes message as method call encoded by standard codec (which is used by
+    // all NativeShell channels); None for replies or other encodings
+    pub fn method_call(&self) -> Option<MethodCall<Value>> {
+        match self.direction {
+            MessageDirection::ToDart | MessageDirection::FromDart => {
+                StandardMethodCodec.decode_method_call(&self.data)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn is_method_call(&self, channel: &str, method: &str) -> bool {
+        self.channel == channel
+            && self
+                .method_call()
+                .map(|call| call.method == method)
+                .unwrap_or(false)
+    }
+}
+
+// Drives windows and engines from integration tests: creates windows, injects
+// synthetic input and records all platform channel traffic so that tests can
+// assert on messages exchanged with Dart.
+pub struct TestDriver {
+    context: Rc<Context>,
+    traffic: Rc<RefCell<Vec<RecordedMessage>>>,
+    observer: i64,
+}
+
+// Granularity of run loop pumping in wait_for
+const WAIT_STEP: Duration = Duration::from_millis(10);
+
+impl TestDriver {
+    pub fn new(context: Rc<Context>) -> Self {
+        let traffic = Rc::new(RefCell::new(Vec::new()));
+        let traffic_copy = traffic.clone();
+        let observer = context.engine_manager.borrow_mut().add_traffic_observer(
+            move |engine, direction, channel, data| {
+                traffic_copy.borrow_mut().push(RecordedMessage {
+                    engine,
+                    direction,
+                    channel: channel.into(),
+                    data: data.into(),
+                });
+            },
+        );
+        Self {
+            context,
+            traffic,
+            observer,
+        }
+    }
+
+    pub fn create_window(&self, init_data: Value, options: &EngineOptions) -> WindowHandle {
+        self.context
+            .window_manager
+            .borrow()
+            .create_window(init_data, None, options)
+    }
+
+    pub fn inject(&self, window: WindowHandle, event: SyntheticEvent) -> Result<()> {
+        let window = self
+            .context
+            .window_manager
+            .borrow()
+            .get_window(window)
+            .ok_or(Error::InvalidWindowHandle)?;
+        window.inject_event(&event)
+    }
+
+    // Processes run loop tasks for given duration; With virtual run loop the time
+    // is advanced without waiting
+    pub fn pump(&self, duration: Duration) {
+        let run_loop = self.context.run_loop.borrow();
+        match run_loop.as_virtual() {
+            Some(run_loop) => run_loop.advance(duration),
+            None => {
+                let context = self.context.clone();
+                run_loop
+                    .schedule(duration, move || context.run_loop.borrow().stop())
+                    .detach();
+                run_loop.run();
+            }
+        }
+    }
+
+    // Pumps run loop until predicate returns true or timeout expires; Returns
+    // whether the predicate was satisfied
+    pub fn wait_for<F>(&self, timeout: Duration, predicate: F) -> bool
+    where
+        F: Fn(&TestDriver) -> bool,
+    {
+        let mut elapsed = Duration::from_secs(0);
+        loop {
+            if predicate(self) {
+                return true;
+            }
+            if elapsed >= timeout {
+                return false;
+            }
+            self.pump(WAIT_STEP);
+            elapsed += WAIT_STEP;
+        }
+    }
+
+    // Waits until method call is recorded on given channel
+    pub fn wait_for_method_call(&self, channel: &str, method: &str, timeout: Duration) -> bool {
+        self.wait_for(timeout, |driver| {
+            driver
+                .traffic
+                .borrow()
+                .iter()
+                .any(|m| m.is_method_call(channel, method))
+        })
+    }
+
+    pub fn traffic(&self) -> Vec<RecordedMessage> {
+        self.traffic.borrow().clone()
+    }
+
+    // Returns messages recorded since last call
+    pub fn take_traffic(&self) -> Vec<RecordedMessage> {
+        self.traffic.take()
+    }
+}
+
+impl Drop for TestDriver {
+    fn drop(&mut self) {
+        self.context
+            .engine_manager
+            .borrow_mut()
+            .remove_traffic_observer(self.observer);
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use std::time::Duration;
+
+    use velcro::hash_map;
+
+    use crate::{
+        codec::{MethodCall, MethodCodec, StandardMethodCodec, Value},
+        shell::{
+            api_constants::*,
+            test_util::{create_window, mock_context, platform_window, window_engine},
+            MessageDirection, Point,
+        },
+    };
+
+    use super::{SyntheticEvent, SyntheticMouseButton, TestDriver};
+
+    #[test]
+    fn test_inject_input_and_record_traffic() {
+        let context = mock_context();
+        // Engine created before the driver is observed as well
+        let window = create_window(&context, None);
+        let driver = TestDriver::new(context.clone());
+
+        let events = vec![
+            SyntheticEvent::MouseDown {
+                position: Point::xy(10.0, 20.0),
+                button: SyntheticMouseButton::Left,
+            },
+            SyntheticEvent::MouseMove {
+                position: Point::xy(15.0, 25.0),
+            },
+        ];
+        for event in &events {
+            driver.inject(window, event.clone()).unwrap();
+        }
+        assert_eq!(
+            platform_window(&context, window).take_injected_events(),
+            events
+        );
+        let state = context
+            .window_manager
+            .borrow()
+            .get_pointer_state(window)
+            .unwrap();
+        assert_eq!(state.buttons, 1);
+        assert_eq!(state.position, Some(Point::xy(15.0, 25.0)));
+
+        let engine = window_engine(&context, window);
+        let call = MethodCall {
+            method: method::menu::SET_APP_MENU.into(),
+            args: Value::Map(hash_map! { "handle".into(): Value::Null }),
+        };
+        let engine_manager = context.engine_manager.borrow();
+        let platform_engine = &engine_manager.get_engine(engine).unwrap().platform_engine;
+        assert!(platform_engine.simulate_message(
+            channel::MENU_MANAGER,
+            &StandardMethodCodec.encode_method_call(&call),
+            |_| {},
+        ));
+        assert!(driver.wait_for_method_call(
+            channel::MENU_MANAGER,
+            method::menu::SET_APP_MENU,
+            Duration::from_millis(100),
+        ));
+
+        let traffic = driver.take_traffic();
+        let directions: Vec<_> = traffic.iter().map(|m| m.direction).collect();
+        assert_eq!(
+            directions,
+            vec![MessageDirection::FromDart, MessageDirection::ReplyToDart]
+        );
+        assert!(traffic.iter().all(|m| m.engine == engine));
+    }
+}
+
+// Runs real engine, so it needs Flutter assets of an application built with
+// nativeshell_dart laid out next to the test executable the same way as in
+// application bundle. Platform UI must run on main thread, run with
+// `cargo test -- --ignored --test-threads=1`.
+#[cfg(all(test, not(feature = "mock")))]
+mod engine_tests {
+    use std::time::Duration;
+
+    use crate::{
+        codec::Value,
+        shell::{
+            api_constants::*, Context, ContextOptions, EngineOptions, MessageDirection, Point,
+        },
+    };
+
+    use super::{SyntheticEvent, SyntheticMouseButton, TestDriver};
+
+    #[test]
+    #[ignore]
+    fn test_real_engine_input_and_traffic() {
+        let context = Context::new(ContextOptions {
+            on_last_engine_removed: Box::new(|_| {}),
+            ..Default::default()
+        })
+        .unwrap();
+        let driver = TestDriver::new(context.clone());
+        let window = driver.create_window(Value::Null, &EngineOptions::default());
+
+        // Window isolate initializes itself through dispatcher channel
+        assert!(driver.wait_for(Duration::from_secs(30), |driver| {
+            driver.traffic().iter().any(|m| {
+                m.channel == channel::DISPATCHER && m.direction == MessageDirection::FromDart
+            })
+        }));
+
+        let position = Point::xy(10.0, 10.0);
+        let button = SyntheticMouseButton::Left;
+        let events = vec![
+            SyntheticEvent::MouseDown {
+                position: position.clone(),
+                button,
+            },
+            SyntheticEvent::MouseMove {
+                position: Point::xy(20.0, 20.0),
+            },
+            SyntheticEvent::MouseUp { position, button },
+        ];
+        for event in events {
+            driver.inject(window, event).unwrap();
+        }
+
+        // Reply to flush confirms that the engine has processed the input
+        let engine = context
+            .window_manager
+            .borrow()
+            .get_window(window)
+            .unwrap()
+            .engine_handle
+            .unwrap();
+        context
+            .engine_manager
+            .borrow()
+            .get_engine(engine)
+            .unwrap()
+            .binary_messenger()
+            .send_message(channel::ENGINE_FLUSH, &[], |_| {})
+            .unwrap();
+        assert!(driver.wait_for(Duration::from_secs(5), |driver| {
+            driver.traffic().iter().any(|m| {
+                m.channel == channel::ENGINE_FLUSH && m.direction == MessageDirection::ReplyFromDart
+            })
+        }));
+        assert!(driver.traffic().iter().all(|m| m.engine == engine));
+
+        context
+            .window_manager
+            .borrow()
+            .close_window(window)
+            .unwrap();
+    }
+}
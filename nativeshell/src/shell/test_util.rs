@@ -0,0 +1,46 @@
+use std::rc::Rc;
+
+use crate::codec::Value;
+
+use super::{
+    platform::{recorder::take_operations, window::PlatformWindow},
+    Context, ContextOptions, EngineHandle, EngineOptions, WindowHandle,
+};
+
+// Context for tests running on mock platform; Unlike the default options the
+// run loop is kept running after last engine is removed. Operations recorded
+// while creating the context are discarded.
+pub(crate) fn mock_context() -> Rc<Context> {
+    mock_context_with(Default::default())
+}
+
+pub(crate) fn mock_context_with(options: ContextOptions) -> Rc<Context> {
+    let context = Context::new(ContextOptions {
+        on_last_engine_removed: Box::new(|_| {}),
+        ..options
+    })
+    .unwrap();
+    take_operations();
+    context
+}
+
+// Window with default engine options and no init data
+pub(crate) fn create_window(context: &Context, parent: Option<WindowHandle>) -> WindowHandle {
+    context
+        .window_manager
+        .borrow()
+        .create_window(Value::Null, parent, &EngineOptions::default())
+}
+
+pub(crate) fn platform_window(context: &Context, handle: WindowHandle) -> Rc<PlatformWindow> {
+    let manager = context.window_manager.borrow();
+    let window = manager.get_window(handle).unwrap();
+    let platform_window = window.platform_window.borrow().clone();
+    platform_window
+}
+
+pub(crate) fn window_engine(context: &Context, handle: WindowHandle) -> EngineHandle {
+    let manager = context.window_manager.borrow();
+    let window = manager.get_window(handle).unwrap();
+    window.engine_handle.unwrap()
+}
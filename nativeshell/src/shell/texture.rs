@@ -0,0 +1,201 @@
+use std::sync::{Arc, Mutex};
+
+use super::{platform::texture::PlatformTexture, FlutterEngine};
+use crate::{Error, Result};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PixelFormat {
+    // 4 bytes per pixel, rows are tightly packed
+    Rgba,
+    // Planar YUV 4:2:0; Y plane followed by U and V planes with half resolution
+    I420,
+    // Y plane followed by interleaved UV plane with half resolution
+    Nv12,
+}
+
+impl PixelFormat {
+    fn buffer_size(&self, width: usize, height: usize) -> usize {
+        match self {
+            PixelFormat::Rgba => width * height * 4,
+            PixelFormat::I420 | PixelFormat::Nv12 => {
+                width * height + 2 * width.div_ceil(2) * height.div_ceil(2)
+            }
+        }
+    }
+}
+
+// Most recent frame in RGBA format; Shared with platform texture, which may read it
+// from raster thread.
+#[derive(Default)]
+pub struct PixelBuffer {
+    pub width: usize,
+    pub height: usize,
+    pub data: Vec<u8>,
+}
+
+impl PixelBuffer {
+    // Replaces content with BGRA pixels that may have padded rows
+    pub(super) fn update_bgra(
+        &mut self,
+        width: usize,
+        height: usize,
+        bytes_per_row: usize,
+        data: &[u8],
+    ) {
+        self.width = width;
+        self.height = height;
+        self.data.resize(width * height * 4, 0);
+        for (dst, src) in self
+            .data
+            .chunks_exact_mut(width * 4)
+            .zip(data.chunks(bytes_per_row))
+        {
+            for (dst, src) in dst.chunks_exact_mut(4).zip(src.chunks_exact(4)) {
+                dst[0] = src[2];
+                dst[1] = src[1];
+                dst[2] = src[0];
+                dst[3] = src[3];
+            }
+        }
+    }
+}
+
+pub type SharedPixelBuffer = Arc<Mutex<PixelBuffer>>;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GpuSurfaceKind {
+    // macOS: IOSurfaceRef
+    IOSurface,
+    // Windows: HANDLE of shared DXGI resource (BGRA)
+    DxgiSharedHandle,
+    // Windows: ID3D11Texture2D* created on the same adapter as the engine (BGRA)
+    D3d11Texture2D,
+    // Linux: GL_TEXTURE_2D texture name in engine GL context, which is current when
+    // provider is invoked. DMA-BUF can be imported as EGLImage and bound to the texture.
+    GlTexture,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GpuSurface {
+    // IOSurfaceRef, HANDLE, ID3D11Texture2D* or GL texture name (see GpuSurfaceKind)
+    pub handle: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+// Invoked on raster thread with current texture size in pixels; Returned surface
+// must remain valid until next invocation or until the texture is dropped.
+pub type GpuSurfaceProvider = dyn Fn(usize, usize) -> Option<GpuSurface> + Send + Sync;
+
+// External texture backed by pixel buffers provided from Rust. Texture id can be
+// passed to Dart and used as textureId of Texture widget. Texture must be dropped
+// before the engine is shut down.
+pub struct PixelBufferTexture {
+    platform_texture: PlatformTexture,
+    buffer: SharedPixelBuffer,
+}
+
+impl PixelBufferTexture {
+    pub fn new(engine: &FlutterEngine) -> Result<Self> {
+        let buffer = SharedPixelBuffer::default();
+        let platform_texture = PlatformTexture::new(&engine.platform_engine, buffer.clone())?;
+        Ok(Self {
+            platform_texture,
+            buffer,
+        })
+    }
+
+    pub fn id(&self) -> i64 {
+        self.platform_texture.id()
+    }
+
+    // Replaces texture content with given pixels and notifies the engine that new
+    // frame is available. YUV pixels are converted to RGBA (BT.601).
+    pub fn push_frame(
+        &self,
+        width: usize,
+        height: usize,
+        format: PixelFormat,
+        data: &[u8],
+    ) -> Result<()> {
+        if data.len() < format.buffer_size(width, height) {
+            return Err(Error::InvalidPixelBufferSize);
+        }
+        {
+            let mut buffer = self.buffer.lock().unwrap();
+            buffer.width = width;
+            buffer.height = height;
+            buffer.data.resize(width * height * 4, 0);
+            match format {
+                PixelFormat::Rgba => buffer.data.copy_from_slice(&data[..width * height * 4]),
+                PixelFormat::I420 | PixelFormat::Nv12 => {
+                    yuv_to_rgba(width, height, format, data, &mut buffer.data)
+                }
+            }
+        }
+        self.mark_frame_available()
+    }
+
+    pub(super) fn buffer(&self) -> SharedPixelBuffer {
+        self.buffer.clone()
+    }
+
+    pub(super) fn mark_frame_available(&self) -> Result<()> {
+        self.platform_texture
+            .mark_frame_available()
+            .map_err(|e| e.into())
+    }
+}
+
+// External texture backed by GPU surfaces shared with the compositor without
+// copying pixels through CPU. Texture must be dropped before the engine is shut down.
+pub struct GpuSurfaceTexture {
+    platform_texture: PlatformTexture,
+}
+
+impl GpuSurfaceTexture {
+    pub fn new<F>(engine: &FlutterEngine, kind: GpuSurfaceKind, provider: F) -> Result<Self>
+    where
+        F: Fn(usize, usize) -> Option<GpuSurface> + Send + Sync + 'static,
+    {
+        let platform_texture =
+            PlatformTexture::new_gpu_surface(&engine.platform_engine, kind, Box::new(provider))?;
+        Ok(Self { platform_texture })
+    }
+
+    pub fn id(&self) -> i64 {
+        self.platform_texture.id()
+    }
+
+    // Notifies the engine that surface content changed and provider should be
+    // invoked again
+    pub fn mark_frame_available(&self) -> Result<()> {
+        self.platform_texture
+            .mark_frame_available()
+            .map_err(|e| e.into())
+    }
+}
+
+fn yuv_to_rgba(width: usize, height: usize, format: PixelFormat, src: &[u8], dst: &mut [u8]) {
+    let chroma_width = width.div_ceil(2);
+    let chroma_size = chroma_width * height.div_ceil(2);
+    let (y_plane, chroma) = src.split_at(width * height);
+    for row in 0..height {
+        for col in 0..width {
+            let chroma_index = (row / 2) * chroma_width + col / 2;
+            let (u, v) = match format {
+                PixelFormat::Nv12 => (chroma[chroma_index * 2], chroma[chroma_index * 2 + 1]),
+                _ => (chroma[chroma_index], chroma[chroma_size + chroma_index]),
+            };
+            let c = y_plane[row * width + col] as i32 - 16;
+            let d = u as i32 - 128;
+            let e = v as i32 - 128;
+            let clamp = |v: i32| (v >> 8).clamp(0, 255) as u8;
+            let offset = (row * width + col) * 4;
+            dst[offset] = clamp(298 * c + 409 * e + 128);
+            dst[offset + 1] = clamp(298 * c - 100 * d - 208 * e + 128);
+            dst[offset + 2] = clamp(298 * c + 516 * d + 128);
+            dst[offset + 3] = 255;
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use std::{
+    rc::{Rc, Weak},
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    codec::{MethodCallError, MethodCallResult, MethodInvoker, Value},
+    util::{Capsule, OkLog},
+    Error,
+};
+
+use super::{Context, EngineHandle, RunLoopSender};
+
+// Context reference that can be moved between threads but only accessed on
+// run loop thread
+#[derive(Clone)]
+struct ContextHandle {
+    context: Arc<Mutex<Capsule<Weak<Context>>>>,
+    sender: Arc<RunLoopSender>,
+}
+
+impl ContextHandle {
+    fn new(context: &Rc<Context>) -> Self {
+        let sender = Arc::new(context.run_loop.borrow().new_sender());
+        Self {
+            context: Arc::new(Mutex::new(Capsule::new_with_sender(
+                Rc::downgrade(context),
+                context.run_loop.borrow().new_sender(),
+            ))),
+            sender,
+        }
+    }
+
+    // Executes the callback on run loop thread, provided the context is still alive
+    fn perform<F>(&self, callback: F)
+    where
+        F: FnOnce(Rc<Context>) + 'static + Send,
+    {
+        let context = self.context.clone();
+        self.sender.send(move || {
+            let context = context
+                .lock()
+                .unwrap()
+                .get_ref()
+                .and_then(|context| context.upgrade());
+            if let Some(context) = context {
+                callback(context);
+            }
+        });
+    }
+}
+
+// Method invoker that can be used from any thread. Calls are marshalled to
+// run loop thread and reply callback is invoked on run loop thread.
+#[derive(Clone)]
+pub struct ThreadSafeMethodInvoker {
+    context: ContextHandle,
+    engine_handle: EngineHandle,
+    channel_name: String,
+}
+
+impl ThreadSafeMethodInvoker {
+    pub(super) fn new(context: &Rc<Context>, engine_handle: EngineHandle, channel: &str) -> Self {
+        Self {
+            context: ContextHandle::new(context),
+            engine_handle,
+            channel_name: channel.into(),
+        }
+    }
+
+    pub fn call_method<F>(&self, method: String, args: Value, reply: F)
+    where
+        F: FnOnce(MethodCallResult<Value>) + 'static + Send,
+    {
+        let engine_handle = self.engine_handle;
+        let channel = self.channel_name.clone();
+        self.context.perform(move |context| {
+            if context
+                .engine_manager
+                .borrow()
+                .get_engine(engine_handle)
+                .is_none()
+            {
+                reply(Err(MethodCallError::from_error(Error::InvalidEngineHandle)));
+                return;
+            }
+            let codec = context.message_manager.borrow().get_method_codec(&channel);
+            let invoker = MethodInvoker::new(context.clone(), engine_handle, &channel, codec);
+            invoker.call_method(method, args, reply).ok_log();
+        });
+    }
+}
+
+// Message sender that can be used from any thread. Messages are marshalled to
+// run loop thread and reply callback is invoked on run loop thread.
+#[derive(Clone)]
+pub struct ThreadSafeMessageSender {
+    context: ContextHandle,
+    engine_handle: EngineHandle,
+    channel_name: String,
+}
+
+impl ThreadSafeMessageSender {
+    pub(super) fn new(context: &Rc<Context>, engine_handle: EngineHandle, channel: &str) -> Self {
+        Self {
+            context: ContextHandle::new(context),
+            engine_handle,
+            channel_name: channel.into(),
+        }
+    }
+
+    pub fn send_message<F>(&self, message: Value, reply: F)
+    where
+        F: FnOnce(Value) + 'static + Send,
+    {
+        let engine_handle = self.engine_handle;
+        let channel = self.channel_name.clone();
+        self.context.perform(move |context| {
+            let sender = context
+                .message_manager
+                .borrow()
+                .get_message_sender(engine_handle, &channel);
+            if let Some(sender) = sender {
+                sender.send_message(&message, reply).ok_log();
+            }
+        });
+    }
+
+    pub fn post_message(&self, message: Value) {
+        let engine_handle = self.engine_handle;
+        let channel = self.channel_name.clone();
+        self.context.perform(move |context| {
+            let sender = context
+                .message_manager
+                .borrow()
+                .get_message_sender(engine_handle, &channel);
+            if let Some(sender) = sender {
+                sender.post_message(&message).ok_log();
+            }
+        });
+    }
+}
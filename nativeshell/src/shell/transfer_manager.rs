@@ -0,0 +1,425 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    io::Read,
+    rc::Rc,
+};
+
+use velcro::hash_map;
+
+use crate::{
+    codec::{value::to_value, MethodCall, MethodCallError, MethodCallReply, Value},
+    Error,
+};
+
+use super::{
+    api_constants::*,
+    api_model::{TransferBegin, TransferEnd},
+    Context, EngineHandle,
+};
+
+pub type TransferResult = std::result::Result<(), MethodCallError<Value>>;
+
+// Receives payload transferred from Dart in chunks.
+pub trait TransferReceiver {
+    // Called for every chunk. Dart will not send next chunk until the ack is sent,
+    // which allows the receiver to throttle the transfer.
+    fn on_chunk(&mut self, data: Vec<u8>, ack: TransferAck);
+
+    // Called after last chunk has been acknowledged
+    fn on_complete(&mut self);
+
+    // Called when the transfer has been cancelled by Dart or the engine is gone
+    fn on_cancel(&mut self);
+}
+
+pub struct TransferAck {
+    reply: MethodCallReply<Value>,
+}
+
+impl TransferAck {
+    pub fn ack(self) {
+        self.reply.send_ok(Value::Null);
+    }
+
+    // Fails the transfer; Dart side will cancel it
+    pub fn fail(self, error: MethodCallError<Value>) {
+        self.reply.send(Err(error));
+    }
+}
+
+type TransferReceiverFactory = dyn Fn(EngineHandle) -> Box<dyn TransferReceiver>;
+
+struct OutgoingTransfer {
+    engine: EngineHandle,
+    source: RefCell<Box<dyn Read>>,
+    chunk_size: usize,
+    cancelled: Cell<bool>,
+    on_done: RefCell<Option<Box<dyn FnOnce(TransferResult)>>>,
+}
+
+// Transfers large payloads between Dart and Rust in chunks with backpressure;
+// Neither side needs to hold entire payload in single contiguous buffer.
+pub struct TransferManager {
+    context: Rc<Context>,
+    receiver_factories: RefCell<HashMap<String, Box<TransferReceiverFactory>>>,
+    incoming: RefCell<HashMap<(EngineHandle, i64), Box<dyn TransferReceiver>>>,
+    outgoing: RefCell<HashMap<i64, Rc<OutgoingTransfer>>>,
+    next_transfer_id: Cell<i64>,
+}
+
+pub const DEFAULT_TRANSFER_CHUNK_SIZE: usize = 1024 * 1024;
+
+impl TransferManager {
+    pub(super) fn new(context: Rc<Context>) -> Self {
+        let context_copy = context.clone();
+        context
+            .message_manager
+            .borrow_mut()
+            .register_method_handler(channel::TRANSFER, move |value, reply, engine| {
+                context_copy
+                    .transfer_manager
+                    .borrow()
+                    .on_method_call(value, reply, engine);
+            });
+
+        Self {
+            context,
+            receiver_factories: RefCell::new(HashMap::new()),
+            incoming: RefCell::new(HashMap::new()),
+            outgoing: RefCell::new(HashMap::new()),
+            next_transfer_id: Cell::new(1),
+        }
+    }
+
+    // Registers factory for receivers of transfers with given name initiated from Dart
+    pub fn register_receiver<F, R>(&self, name: &str, factory: F)
+    where
+        F: Fn(EngineHandle) -> R + 'static,
+        R: TransferReceiver + 'static,
+    {
+        self.receiver_factories.borrow_mut().insert(
+            name.into(),
+            Box::new(move |engine| Box::new(factory(engine))),
+        );
+    }
+
+    pub fn unregister_receiver(&self, name: &str) {
+        self.receiver_factories.borrow_mut().remove(name);
+    }
+
+    // Sends content of source to Dart receiver with given name. Source is read
+    // in chunks of given size; Next chunk is only read after Dart acknowledged
+    // previous one. Returns transfer identifier that can be used to cancel the
+    // transfer.
+    pub fn send<R, F>(
+        &self,
+        engine: EngineHandle,
+        name: &str,
+        source: R,
+        chunk_size: usize,
+        on_done: F,
+    ) -> i64
+    where
+        R: Read + 'static,
+        F: FnOnce(TransferResult) + 'static,
+    {
+        let transfer_id = self.next_id();
+        let transfer = Rc::new(OutgoingTransfer {
+            engine,
+            source: RefCell::new(Box::new(source)),
+            chunk_size: chunk_size.max(1),
+            cancelled: Cell::new(false),
+            on_done: RefCell::new(Some(Box::new(on_done))),
+        });
+        self.outgoing
+            .borrow_mut()
+            .insert(transfer_id, transfer.clone());
+
+        let begin = TransferBegin {
+            transfer_id: Some(transfer_id),
+            name: name.into(),
+        };
+        let context = self.context.clone();
+        self.invoke(
+            transfer_id,
+            engine,
+            method::transfer::ON_BEGIN,
+            to_value(&begin).unwrap(),
+            move |result| Self::step(context, transfer_id, result),
+        );
+        transfer_id
+    }
+
+    // Cancels outgoing transfer; Completion callback is called with "cancelled" error
+    pub fn cancel(&self, transfer_id: i64) {
+        if let Some(transfer) = self.outgoing.borrow().get(&transfer_id) {
+            transfer.cancelled.set(true);
+        }
+    }
+
+    fn next_id(&self) -> i64 {
+        self.next_transfer_id
+            .replace(self.next_transfer_id.get() + 1)
+    }
+
+    fn invoke<F>(&self, transfer_id: i64, engine: EngineHandle, method: &str, args: Value, reply: F)
+    where
+        F: FnOnce(TransferResult) + 'static,
+    {
+        let invoker = self
+            .context
+            .message_manager
+            .borrow()
+            .get_method_invoker(engine, channel::TRANSFER);
+        let res = invoker
+            .ok_or(Error::InvalidEngineHandle)
+            .and_then(|invoker| {
+                invoker.call_method(method.into(), args, move |r| reply(r.map(|_| ())))
+            });
+        if let Err(error) = res {
+            // Reply callback was dropped with the failed call
+            let context = self.context.clone();
+            self.context
+                .run_loop
+                .borrow()
+                .schedule_now(move || Self::finish(context, transfer_id, Err(error.into())))
+                .detach();
+        }
+    }
+
+    // Called after Dart replied to previous message of outgoing transfer
+    fn step(context: Rc<Context>, transfer_id: i64, result: TransferResult) {
+        let manager = context.transfer_manager.borrow();
+        let transfer = match manager.outgoing.borrow().get(&transfer_id) {
+            Some(transfer) => transfer.clone(),
+            None => return,
+        };
+        if let Err(error) = result {
+            drop(manager);
+            Self::finish(context, transfer_id, Err(error));
+            return;
+        }
+        if transfer.cancelled.get() {
+            let end = TransferEnd { transfer_id };
+            manager.invoke(
+                transfer_id,
+                transfer.engine,
+                method::transfer::ON_CANCEL,
+                to_value(&end).unwrap(),
+                |_| {},
+            );
+            drop(manager);
+            Self::finish(
+                context,
+                transfer_id,
+                Err(MethodCallError::from_code_message(
+                    "cancelled",
+                    "Transfer was cancelled",
+                )),
+            );
+            return;
+        }
+
+        let mut buffer = vec![0u8; transfer.chunk_size];
+        let mut len = 0;
+        let mut read_error = None;
+        {
+            let mut source = transfer.source.borrow_mut();
+            while len < buffer.len() {
+                match source.read(&mut buffer[len..]) {
+                    Ok(0) => break,
+                    Ok(n) => len += n,
+                    Err(error) if error.kind() == std::io::ErrorKind::Interrupted => {}
+                    Err(error) => {
+                        read_error = Some(error);
+                        break;
+                    }
+                }
+            }
+        }
+
+        if let Some(error) = read_error {
+            let end = TransferEnd { transfer_id };
+            manager.invoke(
+                transfer_id,
+                transfer.engine,
+                method::transfer::ON_CANCEL,
+                to_value(&end).unwrap(),
+                |_| {},
+            );
+            drop(manager);
+            Self::finish(
+                context,
+                transfer_id,
+                Err(MethodCallError::from_code_message(
+                    "io-error",
+                    &error.to_string(),
+                )),
+            );
+        } else if len == 0 {
+            let end = TransferEnd { transfer_id };
+            let context_copy = context.clone();
+            manager.invoke(
+                transfer_id,
+                transfer.engine,
+                method::transfer::ON_END,
+                to_value(&end).unwrap(),
+                move |result| Self::finish(context_copy, transfer_id, result),
+            );
+        } else {
+            buffer.truncate(len);
+            let chunk = Value::Map(hash_map! {
+                "transferId".into(): transfer_id.into(),
+                "data".into(): Value::U8List(buffer),
+            });
+            let context_copy = context.clone();
+            manager.invoke(
+                transfer_id,
+                transfer.engine,
+                method::transfer::ON_CHUNK,
+                chunk,
+                move |result| Self::step(context_copy, transfer_id, result),
+            );
+        }
+    }
+
+    fn finish(context: Rc<Context>, transfer_id: i64, result: TransferResult) {
+        let transfer = context
+            .transfer_manager
+            .borrow()
+            .outgoing
+            .borrow_mut()
+            .remove(&transfer_id);
+        if let Some(transfer) = transfer {
+            let on_done = transfer.on_done.borrow_mut().take();
+            if let Some(on_done) = on_done {
+                on_done(result);
+            }
+        }
+    }
+
+    fn take_chunk(args: Value) -> Option<(i64, Vec<u8>)> {
+        if let Value::Map(mut map) = args {
+            let transfer_id = map.remove(&"transferId".into());
+            let data = map.remove(&"data".into());
+            if let (Some(Value::I64(transfer_id)), Some(Value::U8List(data))) = (transfer_id, data)
+            {
+                return Some((transfer_id, data));
+            }
+        }
+        None
+    }
+
+    fn take_receiver(
+        &self,
+        engine: EngineHandle,
+        transfer_id: i64,
+    ) -> Option<Box<dyn TransferReceiver>> {
+        self.incoming.borrow_mut().remove(&(engine, transfer_id))
+    }
+
+    fn on_method_call(
+        &self,
+        call: MethodCall<Value>,
+        reply: MethodCallReply<Value>,
+        engine: EngineHandle,
+    ) {
+        match call.method.as_str() {
+            method::transfer::BEGIN => {
+                let begin = match call.args_as::<TransferBegin>() {
+                    Ok(begin) => begin,
+                    Err(error) => {
+                        reply.send(Err(Error::from(error).into()));
+                        return;
+                    }
+                };
+                let receiver = self
+                    .receiver_factories
+                    .borrow()
+                    .get(&begin.name)
+                    .map(|factory| factory(engine));
+                match receiver {
+                    Some(receiver) => {
+                        let transfer_id = self.next_id();
+                        self.incoming
+                            .borrow_mut()
+                            .insert((engine, transfer_id), receiver);
+                        reply.send_ok(transfer_id.into());
+                    }
+                    None => reply.send_error(
+                        "no-receiver",
+                        Some(&format!("No receiver registered for '{}'", begin.name)),
+                        Value::Null,
+                    ),
+                }
+            }
+            method::transfer::CHUNK => {
+                let chunk = Self::take_chunk(call.args);
+                let receiver =
+                    chunk.and_then(|(id, data)| Some((id, self.take_receiver(engine, id)?, data)));
+                match receiver {
+                    Some((transfer_id, mut receiver, data)) => {
+                        receiver.on_chunk(data, TransferAck { reply });
+                        self.incoming
+                            .borrow_mut()
+                            .insert((engine, transfer_id), receiver);
+                    }
+                    None => reply.send_error("no-transfer", None, Value::Null),
+                }
+            }
+            method::transfer::END | method::transfer::CANCEL => {
+                let receiver = call
+                    .args_as::<TransferEnd>()
+                    .ok()
+                    .and_then(|end| self.take_receiver(engine, end.transfer_id));
+                if let Some(mut receiver) = receiver {
+                    if call.method == method::transfer::END {
+                        receiver.on_complete();
+                    } else {
+                        receiver.on_cancel();
+                    }
+                }
+                reply.send_ok(Value::Null);
+            }
+            _ => {}
+        }
+    }
+
+    // Cancels all incoming transfers from given engine
+    pub(crate) fn engine_removed(&self, engine: EngineHandle) {
+        let receivers: Vec<Box<dyn TransferReceiver>> = {
+            let mut incoming = self.incoming.borrow_mut();
+            let keys: Vec<(EngineHandle, i64)> =
+                incoming.keys().filter(|k| k.0 == engine).cloned().collect();
+            keys.iter().filter_map(|k| incoming.remove(k)).collect()
+        };
+        for mut receiver in receivers {
+            receiver.on_cancel();
+        }
+        let outgoing: Vec<i64> = self
+            .outgoing
+            .borrow()
+            .iter()
+            .filter(|(_, t)| t.engine == engine)
+            .map(|(id, _)| *id)
+            .collect();
+        for transfer_id in outgoing {
+            let context = self.context.clone();
+            self.context
+                .run_loop
+                .borrow()
+                .schedule_now(move || {
+                    Self::finish(
+                        context,
+                        transfer_id,
+                        Err(MethodCallError::from_code_message(
+                            "engine-removed",
+                            "Engine was removed during transfer",
+                        )),
+                    )
+                })
+                .detach();
+        }
+    }
+}
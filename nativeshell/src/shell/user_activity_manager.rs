@@ -0,0 +1,47 @@
+use std::{rc::Rc, time::Duration};
+
+use crate::{
+    codec::{MethodCall, MethodCallError, MethodCallReply, Value},
+    Result,
+};
+
+use super::{api_constants::*, platform::user_activity, Context, EngineHandle};
+
+pub struct UserActivityManager {}
+
+impl UserActivityManager {
+    pub(super) fn new(context: Rc<Context>) -> Self {
+        let context_copy = context.clone();
+        context
+            .message_manager
+            .borrow_mut()
+            .register_method_handler(channel::USER_ACTIVITY, move |value, reply, engine| {
+                context_copy
+                    .user_activity_manager
+                    .borrow()
+                    .on_method_call(value, reply, engine);
+            });
+        Self {}
+    }
+
+    // Time since last keyboard or mouse input anywhere in user session (i.e. to
+    // set away status or reset kiosk after inactivity)
+    pub fn idle_time(&self) -> Result<Duration> {
+        user_activity::idle_time().map_err(|e| e.into())
+    }
+
+    fn on_method_call(
+        &self,
+        call: MethodCall<Value>,
+        reply: MethodCallReply<Value>,
+        _engine: EngineHandle,
+    ) {
+        if call.method.as_str() == method::user_activity::GET_IDLE_TIME {
+            let res = self.idle_time();
+            reply.send(
+                res.map(|idle_time| Value::F64(idle_time.as_secs_f64()))
+                    .map_err(MethodCallError::from_error),
+            );
+        }
+    }
+}
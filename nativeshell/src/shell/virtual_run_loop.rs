@@ -0,0 +1,300 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use super::{
+    platform::run_loop::{EventSourceType, HandleType, INVALID_HANDLE},
+    TaskPriority,
+};
+
+struct VirtualTimer {
+    scheduled: Duration,
+    priority: TaskPriority,
+    callback: Box<dyn FnOnce()>,
+}
+
+type SenderCallback = Box<dyn FnOnce() + Send>;
+
+// Run loop with virtual time intended for unit tests. Nothing happens on its own;
+// Scheduled callbacks are only invoked from run_until_idle and advance, in
+// deterministic order (priority, scheduled time, order of scheduling).
+pub struct VirtualRunLoop {
+    now: Cell<Duration>,
+    next_handle: Cell<HandleType>,
+    timers: RefCell<HashMap<HandleType, VirtualTimer>>,
+    event_sources: RefCell<Vec<(HandleType, EventSourceType, Rc<RefCell<dyn FnMut()>>)>>,
+    stopped: Cell<bool>,
+
+    // Callbacks sent from other threads
+    sender_callbacks: Arc<Mutex<Vec<SenderCallback>>>,
+}
+
+impl VirtualRunLoop {
+    pub(super) fn new() -> Self {
+        Self {
+            now: Cell::new(Duration::from_secs(0)),
+            next_handle: Cell::new(INVALID_HANDLE + 1),
+            timers: RefCell::new(HashMap::new()),
+            event_sources: RefCell::new(Vec::new()),
+            stopped: Cell::new(false),
+            sender_callbacks: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    fn next_handle(&self) -> HandleType {
+        let r = self.next_handle.get();
+        self.next_handle.replace(r + 1);
+        r
+    }
+
+    // Virtual time elapsed since the run loop was created
+    pub fn now(&self) -> Duration {
+        self.now.get()
+    }
+
+    // Number of scheduled callbacks (including callbacks that are not due yet)
+    pub fn pending_count(&self) -> usize {
+        self.timers.borrow().len() + self.sender_callbacks.lock().unwrap().len()
+    }
+
+    // Invokes all callbacks that are due at current virtual time, including
+    // callbacks scheduled by these callbacks. Idle callbacks are invoked once
+    // there is nothing else to do.
+    pub fn run_until_idle(&self) {
+        while self.run_next() {}
+    }
+
+    // Advances virtual time by given duration, invoking callbacks as they become due
+    pub fn advance(&self, duration: Duration) {
+        let target = self.now.get() + duration;
+        loop {
+            self.run_until_idle();
+            match self.next_scheduled() {
+                Some(next) if next <= target => self.now.set(next),
+                _ => break,
+            }
+        }
+        self.now.set(target);
+        self.run_until_idle();
+    }
+
+    // Invokes callbacks of all event sources registered for given source
+    pub fn signal_event_source(&self, source: EventSourceType) {
+        let callbacks: Vec<_> = self
+            .event_sources
+            .borrow()
+            .iter()
+            .filter(|s| s.1 == source)
+            .map(|s| s.2.clone())
+            .collect();
+        for callback in callbacks {
+            (callback.borrow_mut())();
+        }
+    }
+
+    fn next_scheduled(&self) -> Option<Duration> {
+        self.timers.borrow().values().map(|t| t.scheduled).min()
+    }
+
+    // Invokes single callback; Returns false if there was nothing to invoke
+    fn run_next(&self) -> bool {
+        let now = self.now.get();
+        let handle = {
+            let timers = self.timers.borrow();
+            let due = |idle: bool| {
+                timers
+                    .iter()
+                    .filter(|t| {
+                        t.1.scheduled <= now && (t.1.priority == TaskPriority::Idle) == idle
+                    })
+                    .min_by_key(|t| (t.1.priority, t.1.scheduled, *t.0))
+                    .map(|t| *t.0)
+            };
+            due(false).or_else(|| {
+                if self.sender_callbacks.lock().unwrap().is_empty() {
+                    due(true)
+                } else {
+                    None
+                }
+            })
+        };
+        let timer = handle.and_then(|handle| self.timers.borrow_mut().remove(&handle));
+        if let Some(timer) = timer {
+            (timer.callback)();
+            return true;
+        }
+        let callbacks: Vec<SenderCallback> = {
+            let mut callbacks = self.sender_callbacks.lock().unwrap();
+            callbacks.drain(0..).collect()
+        };
+        let res = !callbacks.is_empty();
+        for c in callbacks {
+            c()
+        }
+        res
+    }
+
+    pub(super) fn schedule<F>(
+        &self,
+        in_time: Duration,
+        priority: TaskPriority,
+        callback: F,
+    ) -> HandleType
+    where
+        F: FnOnce() + 'static,
+    {
+        let handle = self.next_handle();
+        self.timers.borrow_mut().insert(
+            handle,
+            VirtualTimer {
+                scheduled: self.now.get() + in_time,
+                priority,
+                callback: Box::new(callback),
+            },
+        );
+        handle
+    }
+
+    pub(super) fn unschedule(&self, handle: HandleType) {
+        self.timers.borrow_mut().remove(&handle);
+    }
+
+    pub(super) fn add_event_source<F>(&self, source: EventSourceType, callback: F) -> HandleType
+    where
+        F: FnMut() + 'static,
+    {
+        let handle = self.next_handle();
+        self.event_sources
+            .borrow_mut()
+            .push((handle, source, Rc::new(RefCell::new(callback))));
+        handle
+    }
+
+    pub(super) fn remove_event_source(&self, handle: HandleType) {
+        self.event_sources.borrow_mut().retain(|s| s.0 != handle);
+    }
+
+    // Advances virtual time until there are no more callbacks or the run loop is stopped
+    pub(super) fn run(&self) {
+        self.stopped.set(false);
+        while !self.stopped.get() {
+            self.run_until_idle();
+            match self.next_scheduled() {
+                Some(next) if !self.stopped.get() => self.now.set(next),
+                _ => break,
+            }
+        }
+    }
+
+    pub(super) fn stop(&self) {
+        self.stopped.set(true);
+    }
+
+    pub(super) fn new_sender(&self) -> VirtualRunLoopSender {
+        VirtualRunLoopSender {
+            callbacks: self.sender_callbacks.clone(),
+        }
+    }
+}
+
+pub(super) struct VirtualRunLoopSender {
+    callbacks: Arc<Mutex<Vec<SenderCallback>>>,
+}
+
+impl VirtualRunLoopSender {
+    pub(super) fn send<F>(&self, callback: F)
+    where
+        F: FnOnce() + 'static + Send,
+    {
+        self.callbacks.lock().unwrap().push(Box::new(callback));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc, time::Duration};
+
+    use crate::shell::{RunLoop, TaskPriority};
+
+    fn recorder() -> (
+        Rc<RefCell<Vec<&'static str>>>,
+        impl Fn(&'static str) -> Box<dyn FnOnce()>,
+    ) {
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let log_copy = log.clone();
+        let record = move |name: &'static str| -> Box<dyn FnOnce()> {
+            let log = log_copy.clone();
+            Box::new(move || log.borrow_mut().push(name))
+        };
+        (log, record)
+    }
+
+    #[test]
+    fn test_order() {
+        let run_loop = RunLoop::new_virtual();
+        let (log, record) = recorder();
+        let ms = Duration::from_millis;
+        run_loop.schedule(ms(20), record("b")).detach();
+        run_loop.schedule(ms(10), record("a")).detach();
+        run_loop.schedule_idle(record("idle")).detach();
+        run_loop
+            .schedule_with_priority(ms(0), TaskPriority::UserInteractive, record("urgent"))
+            .detach();
+        run_loop.schedule_now(record("now")).detach();
+        let mut cancelled = run_loop.schedule(ms(15), record("cancelled"));
+        cancelled.cancel();
+
+        let virtual_loop = run_loop.as_virtual().unwrap();
+        virtual_loop.run_until_idle();
+        assert_eq!(*log.borrow(), vec!["urgent", "now", "idle"]);
+
+        virtual_loop.advance(ms(15));
+        assert_eq!(*log.borrow(), vec!["urgent", "now", "idle", "a"]);
+        assert_eq!(virtual_loop.now(), ms(15));
+        assert_eq!(virtual_loop.pending_count(), 1);
+
+        virtual_loop.advance(ms(5));
+        assert_eq!(*log.borrow(), vec!["urgent", "now", "idle", "a", "b"]);
+        assert_eq!(virtual_loop.pending_count(), 0);
+    }
+
+    #[test]
+    fn test_repeating() {
+        let run_loop = RunLoop::new_virtual();
+        let count = Rc::new(RefCell::new(0));
+        let count_copy = count.clone();
+        let timer = run_loop.schedule_repeating(Duration::from_millis(10), move || {
+            *count_copy.borrow_mut() += 1;
+        });
+        let virtual_loop = run_loop.as_virtual().unwrap();
+        virtual_loop.advance(Duration::from_millis(35));
+        assert_eq!(*count.borrow(), 3);
+
+        timer.pause();
+        virtual_loop.advance(Duration::from_millis(100));
+        assert_eq!(*count.borrow(), 3);
+
+        timer.reschedule(Duration::from_millis(1));
+        virtual_loop.advance(Duration::from_millis(1));
+        assert_eq!(*count.borrow(), 4);
+    }
+
+    #[test]
+    fn test_sender() {
+        let run_loop = RunLoop::new_virtual();
+        let sender = run_loop.new_sender();
+        let result = std::thread::spawn(move || {
+            sender.send(|| {});
+        })
+        .join();
+        assert!(result.is_ok());
+        let virtual_loop = run_loop.as_virtual().unwrap();
+        assert_eq!(virtual_loop.pending_count(), 1);
+        virtual_loop.run_until_idle();
+        assert_eq!(virtual_loop.pending_count(), 0);
+    }
+}
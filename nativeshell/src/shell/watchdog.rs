@@ -0,0 +1,331 @@
+use std::{
+    sync::{Arc, Condvar, Mutex},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use log::warn;
+
+use super::RunLoopSender;
+
+pub struct WatchdogOptions {
+    // Run loop thread is considered blocked when it doesn't process heartbeat
+    // callback within this time
+    pub timeout: Duration,
+
+    // Capture backtrace of the blocked run loop thread (macOS and Linux). The
+    // backtrace is captured by SIGUSR2 handler running on the blocked thread,
+    // which allocates; If the thread is blocked while holding allocator lock
+    // the handler deadlocks and the thread never recovers. Only meant for
+    // diagnosing hangs during development.
+    pub capture_backtrace: bool,
+
+    // Invoked on watchdog thread every time run loop thread gets blocked, in
+    // addition to logging the report
+    pub on_blocked: Option<Box<dyn Fn(&WatchdogReport) + Send>>,
+}
+
+impl Default for WatchdogOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(2),
+            capture_backtrace: false,
+            on_blocked: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct WatchdogReport {
+    // How long the run loop thread has been blocked when report was created
+    pub blocked_for: Duration,
+    pub backtrace: Option<String>,
+}
+
+struct WatchdogState {
+    // Last heartbeat processed by run loop thread
+    heartbeat: u64,
+    stopped: bool,
+}
+
+struct WatchdogShared {
+    state: Mutex<WatchdogState>,
+    condition: Condvar,
+}
+
+// Periodically sends heartbeat callback to run loop thread and reports when it
+// is not invoked in time. Each blocked period is reported once.
+pub struct Watchdog {
+    shared: Arc<WatchdogShared>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Watchdog {
+    pub(super) fn new(sender: RunLoopSender, options: WatchdogOptions) -> Self {
+        let shared = Arc::new(WatchdogShared {
+            state: Mutex::new(WatchdogState {
+                heartbeat: 0,
+                stopped: false,
+            }),
+            condition: Condvar::new(),
+        });
+        let run_loop_thread = thread_backtrace::current_thread();
+        let shared_copy = shared.clone();
+        let thread = thread::Builder::new()
+            .name("nativeshell-watchdog".into())
+            .spawn(move || Self::run(shared_copy, sender, options, run_loop_thread))
+            .ok();
+        Self { shared, thread }
+    }
+
+    fn run(
+        shared: Arc<WatchdogShared>,
+        sender: RunLoopSender,
+        options: WatchdogOptions,
+        run_loop_thread: thread_backtrace::ThreadHandle,
+    ) {
+        let mut heartbeat = 0;
+        loop {
+            heartbeat += 1;
+            let sent = Instant::now();
+            let shared_copy = shared.clone();
+            sender.send_untracked(move || {
+                let mut state = shared_copy.state.lock().unwrap();
+                state.heartbeat = state.heartbeat.max(heartbeat);
+                shared_copy.condition.notify_all();
+            });
+
+            let state = shared.state.lock().unwrap();
+            let (mut state, _) = shared
+                .condition
+                .wait_timeout_while(state, options.timeout, |s| {
+                    !s.stopped && s.heartbeat < heartbeat
+                })
+                .unwrap();
+            if !state.stopped && state.heartbeat < heartbeat {
+                drop(state);
+                let report = WatchdogReport {
+                    blocked_for: sent.elapsed(),
+                    backtrace: if options.capture_backtrace {
+                        thread_backtrace::capture(&run_loop_thread)
+                    } else {
+                        None
+                    },
+                };
+                warn!(
+                    "Run loop thread blocked for {:?}; Backtrace:\n{}",
+                    report.blocked_for,
+                    report.backtrace.as_deref().unwrap_or("(not available)")
+                );
+                if let Some(on_blocked) = &options.on_blocked {
+                    on_blocked(&report);
+                }
+                state = shared
+                    .condition
+                    .wait_while(shared.state.lock().unwrap(), |s| {
+                        !s.stopped && s.heartbeat < heartbeat
+                    })
+                    .unwrap();
+                if !state.stopped {
+                    warn!("Run loop thread unblocked after {:?}", sent.elapsed());
+                }
+            }
+
+            let (state, _) = shared
+                .condition
+                .wait_timeout_while(state, options.timeout / 2, |s| !s.stopped)
+                .unwrap();
+            if state.stopped {
+                return;
+            }
+        }
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        self.shared.state.lock().unwrap().stopped = true;
+        self.shared.condition.notify_all();
+        if let Some(thread) = self.thread.take() {
+            thread.join().ok();
+        }
+    }
+}
+
+// Backtrace of another thread is captured by signal handler executed on that
+// thread. Capturing backtrace is not async signal safe (see
+// WatchdogOptions::capture_backtrace). Signals not requested by watchdog are
+// passed to previously installed handler.
+#[cfg(unix)]
+mod thread_backtrace {
+    use std::{
+        backtrace::Backtrace,
+        mem,
+        ptr::null_mut,
+        sync::{
+            atomic::{AtomicBool, AtomicPtr, Ordering},
+            OnceLock,
+        },
+        thread,
+        time::{Duration, Instant},
+    };
+
+    const SIGNAL: libc::c_int = libc::SIGUSR2;
+
+    static CAPTURED: AtomicPtr<Backtrace> = AtomicPtr::new(null_mut());
+
+    // Set by watchdog right before signalling the thread
+    static REQUESTED: AtomicBool = AtomicBool::new(false);
+
+    // Action that was installed before on_signal; None if installing failed
+    static PREVIOUS: OnceLock<Option<libc::sigaction>> = OnceLock::new();
+
+    pub struct ThreadHandle(libc::pthread_t);
+
+    // pthread_t is a pointer on macOS
+    unsafe impl Send for ThreadHandle {}
+
+    pub fn current_thread() -> ThreadHandle {
+        ThreadHandle(unsafe { libc::pthread_self() })
+    }
+
+    extern "C" fn on_signal(
+        signal: libc::c_int,
+        info: *mut libc::siginfo_t,
+        context: *mut libc::c_void,
+    ) {
+        if !REQUESTED.swap(false, Ordering::SeqCst) {
+            if let Some(Some(previous)) = PREVIOUS.get() {
+                unsafe { chain(previous, signal, info, context) };
+            }
+            return;
+        }
+        // Symbols are resolved later on watchdog thread
+        let backtrace = Box::into_raw(Box::new(Backtrace::force_capture()));
+        let previous = CAPTURED.swap(backtrace, Ordering::SeqCst);
+        if !previous.is_null() {
+            drop(unsafe { Box::from_raw(previous) });
+        }
+    }
+
+    unsafe fn chain(
+        previous: &libc::sigaction,
+        signal: libc::c_int,
+        info: *mut libc::siginfo_t,
+        context: *mut libc::c_void,
+    ) {
+        match previous.sa_sigaction {
+            libc::SIG_IGN => {}
+            // Default action (terminate) is performed with previous action restored
+            libc::SIG_DFL => {
+                libc::sigaction(signal, previous, null_mut());
+                libc::raise(signal);
+            }
+            handler if previous.sa_flags & libc::SA_SIGINFO != 0 => {
+                let handler: extern "C" fn(libc::c_int, *mut libc::siginfo_t, *mut libc::c_void) =
+                    mem::transmute(handler);
+                handler(signal, info, context);
+            }
+            handler => {
+                let handler: extern "C" fn(libc::c_int) = mem::transmute(handler);
+                handler(signal);
+            }
+        }
+    }
+
+    fn install_handler() -> Option<libc::sigaction> {
+        unsafe {
+            let mut action: libc::sigaction = mem::zeroed();
+            action.sa_sigaction = on_signal
+                as extern "C" fn(libc::c_int, *mut libc::siginfo_t, *mut libc::c_void)
+                as libc::sighandler_t;
+            // Interrupted system calls of the blocked thread are resumed
+            action.sa_flags = libc::SA_SIGINFO | libc::SA_RESTART;
+            libc::sigemptyset(&mut action.sa_mask);
+            let mut previous: libc::sigaction = mem::zeroed();
+            if libc::sigaction(SIGNAL, &action, &mut previous) == 0 {
+                Some(previous)
+            } else {
+                None
+            }
+        }
+    }
+
+    pub fn capture(thread: &ThreadHandle) -> Option<String> {
+        PREVIOUS.get_or_init(install_handler).as_ref()?;
+        REQUESTED.store(true, Ordering::SeqCst);
+        if unsafe { libc::pthread_kill(thread.0, SIGNAL) } != 0 {
+            REQUESTED.store(false, Ordering::SeqCst);
+            return None;
+        }
+        let deadline = Instant::now() + Duration::from_secs(1);
+        while Instant::now() < deadline {
+            let backtrace = CAPTURED.swap(null_mut(), Ordering::SeqCst);
+            if !backtrace.is_null() {
+                let backtrace = unsafe { Box::from_raw(backtrace) };
+                return Some(backtrace.to_string());
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        None
+    }
+}
+
+// Would require suspending the thread and walking its stack with dbghelp
+#[cfg(not(unix))]
+mod thread_backtrace {
+    pub struct ThreadHandle;
+
+    pub fn current_thread() -> ThreadHandle {
+        ThreadHandle
+    }
+
+    pub fn capture(_thread: &ThreadHandle) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::mpsc, time::Duration};
+
+    use crate::shell::{RunLoop, WatchdogOptions};
+
+    #[test]
+    fn test_reports_blocked_run_loop() {
+        let run_loop = RunLoop::new_virtual();
+        let (sender, receiver) = mpsc::channel();
+        let _watchdog = run_loop.start_watchdog(WatchdogOptions {
+            timeout: Duration::from_millis(20),
+            capture_backtrace: false,
+            on_blocked: Some(Box::new(move |report| {
+                sender.send(report.blocked_for).ok();
+            })),
+        });
+        // Virtual run loop doesn't process heartbeat until told to
+        let blocked_for = receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(blocked_for >= Duration::from_millis(20));
+
+        // Only reported once per blocked period
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+        run_loop.as_virtual().unwrap().run_until_idle();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_capture_backtrace() {
+        use std::thread;
+
+        use super::thread_backtrace;
+
+        let (sender, receiver) = mpsc::channel();
+        let blocked = thread::spawn(move || {
+            sender.send(thread_backtrace::current_thread()).unwrap();
+            // Interrupted sleep is resumed after the handler returns
+            thread::sleep(Duration::from_millis(300));
+        });
+        let handle = receiver.recv().unwrap();
+        assert!(thread_backtrace::capture(&handle).is_some());
+        blocked.join().unwrap();
+    }
+}
@@ -1,39 +1,87 @@
 use std::{
-    cell::Cell,
+    cell::{Cell, RefCell},
+    collections::HashMap,
     rc::{Rc, Weak},
 };
 
 use crate::{
     codec::{
         value::{from_value, to_value},
-        Value,
+        MethodCallError, Value,
     },
-    util::{LateRefCell, OkLog},
-    Result,
+    util::{trace, LateRefCell, OkLog},
+    Error, Result,
 };
 
 use super::{
     api_constants::*,
     api_model::{
-        DragEffect, DragRequest, DragResult, DraggingInfo, HidePopupMenuRequest, PopupMenuRequest,
-        PopupMenuResponse, SetMenuRequest, WindowGeometry, WindowGeometryFlags,
-        WindowGeometryRequest, WindowStyle,
+        ActivationPolicy, AttachEngineViewRequest, CreatePlatformViewRequest,
+        DetachEngineViewRequest, DisposePlatformViewRequest, DragData, DragEffect, DragRequest,
+        DragResult, DraggingInfo, DropFilter, GeometryPreference, HidePopupMenuRequest,
+        PopoverEdge, PopupMenuRequest, PopupMenuResponse, QuickLookRequest, SetMenuRequest,
+        SetTouchBarRequest, SharePickerRequest, ShowPopoverRequest, TabletEvent, TouchBarAction,
+        UpdateEngineViewRequest, UpdatePlatformViewRequest, WindowAppearance, WindowBackdrop,
+        WindowGeometry, WindowGeometryFlags, WindowGeometryRequest, WindowGeometryUpdate,
+        WindowOptions, WindowStyle, WindowType,
     },
-    platform::window::PlatformWindow,
-    Context, EngineHandle, WindowMethodCallReply, WindowMethodCallResult, WindowMethodInvoker,
+    platform::{
+        native_view::{NativeViewType, PlatformNativeView},
+        window::PlatformWindow,
+        workspace,
+    },
+    window_method_channel::encode_error,
+    Context, EngineEvent, EngineHandle, EngineOptions, PlatformViewHandle, Point, Rect, Size,
+    SyntheticEvent, WindowMethodCallReply, WindowMethodCallResult, WindowMethodInvoker,
 };
 
 #[derive(serde::Serialize, serde::Deserialize, Debug, Copy, Clone, Hash, Eq, PartialEq)]
 pub struct WindowHandle(pub(super) i64);
 
+pub(super) type ModalCallback = Box<dyn FnOnce(Result<Value>)>;
+
+// Decodes value the modal window was closed with
+pub(super) fn decode_modal_result<T>(result: Result<Value>) -> Result<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    result.and_then(|value| from_value(&value).map_err(|e| e.into()))
+}
+
+// Flutter view of additional engine embedded in window content
+struct EngineView {
+    native_view: PlatformNativeView,
+    init_data: Value,
+}
+
 pub(super) struct Window {
     context: Rc<Context>,
     pub(super) window_handle: WindowHandle,
-    pub(super) engine_handle: EngineHandle,
+    // None for native windows, which have no Flutter content
+    pub(super) engine_handle: Option<EngineHandle>,
     pub(super) platform_window: LateRefCell<Rc<PlatformWindow>>,
     pub(super) init_data: Value,
     pub(super) parent: Option<WindowHandle>,
     pub(super) initialized: Cell<bool>,
+    pointer_inside: Cell<bool>,
+    visible: Cell<bool>,
+    active: Cell<bool>,
+    occluded: Cell<bool>,
+    // Shown in window menu
+    title: RefCell<String>,
+    lifecycle_state: Cell<Option<&'static str>>,
+    platform_views: RefCell<HashMap<PlatformViewHandle, PlatformNativeView>>,
+    engine_views: RefCell<HashMap<EngineHandle, EngineView>>,
+    // Dragging updates are sent one at a time; Updates arriving while waiting
+    // for reply are coalesced into the latest one
+    dragging_update_in_flight: Cell<bool>,
+    pending_dragging_update: RefCell<Option<DraggingInfo>>,
+    drop_filter: RefCell<Option<DropFilter>>,
+    // Window shown as transient popover is hidden when it loses focus
+    transient_popover: Cell<bool>,
+    // Callbacks waiting for result of modal session; None if window is not modal.
+    // Shared with the session callback, which may outlive the window.
+    modal_callbacks: Rc<RefCell<Option<Vec<ModalCallback>>>>,
     weak_self: LateRefCell<Weak<Self>>,
 }
 
@@ -41,7 +89,7 @@ impl Window {
     pub(crate) fn new(
         context: Rc<Context>,
         window_handle: WindowHandle,
-        engine_handle: EngineHandle,
+        engine_handle: Option<EngineHandle>,
         init_data: Value,
         parent: Option<WindowHandle>,
     ) -> Self {
@@ -53,6 +101,19 @@ impl Window {
             init_data,
             parent,
             initialized: Cell::new(false),
+            pointer_inside: Cell::new(false),
+            visible: Cell::new(false),
+            active: Cell::new(false),
+            occluded: Cell::new(false),
+            title: RefCell::new(String::new()),
+            lifecycle_state: Cell::new(None),
+            platform_views: RefCell::new(HashMap::new()),
+            engine_views: RefCell::new(HashMap::new()),
+            dragging_update_in_flight: Cell::new(false),
+            pending_dragging_update: RefCell::new(None),
+            drop_filter: RefCell::new(None),
+            transient_popover: Cell::new(false),
+            modal_callbacks: Rc::new(RefCell::new(None)),
             weak_self: LateRefCell::new(),
         }
     }
@@ -80,6 +141,39 @@ impl Window {
         broadcaster.broadcast_message(message, arguments);
     }
 
+    // Reports AppLifecycleState to the engine, so that WidgetsBindingObserver
+    // works as on mobile. Hidden (or minimized, occluded) window is reported as
+    // paused; Visible window is resumed when active and inactive otherwise.
+    fn update_lifecycle_state(&self) {
+        let state = if !self.visible.get() || self.occluded.get() {
+            "AppLifecycleState.paused"
+        } else if self.active.get() {
+            "AppLifecycleState.resumed"
+        } else {
+            "AppLifecycleState.inactive"
+        };
+        if self.lifecycle_state.replace(Some(state)) != Some(state) {
+            let engine_manager = self.context.engine_manager.borrow();
+            let engine = self
+                .engine_handle
+                .and_then(|engine| engine_manager.get_engine(engine));
+            if let Some(engine) = engine {
+                engine
+                    .binary_messenger()
+                    .post_message(channel::FLUTTER_LIFECYCLE, state.as_bytes())
+                    .ok_log();
+            }
+        }
+    }
+
+    // Restarted isolate needs to receive current lifecycle state again
+    pub(super) fn hot_restarted(&self) {
+        // Views created by previous isolate would never be disposed
+        self.platform_views.borrow_mut().clear();
+        self.lifecycle_state.set(None);
+        self.update_lifecycle_state();
+    }
+
     fn drop_target_invoker(&self) -> WindowMethodInvoker {
         self.context
             .window_method_channel
@@ -104,32 +198,132 @@ impl Window {
             .unwrap()
     }
 
+    fn touch_bar_invoker(&self) -> WindowMethodInvoker {
+        self.context
+            .window_method_channel
+            .borrow()
+            .get_method_invoker(
+                &self.context.window_manager.borrow(),
+                self.window_handle,
+                channel::win::TOUCH_BAR,
+            )
+            .unwrap()
+    }
+
+    fn tablet_invoker(&self) -> WindowMethodInvoker {
+        self.context
+            .window_method_channel
+            .borrow()
+            .get_method_invoker(
+                &self.context.window_manager.borrow(),
+                self.window_handle,
+                channel::win::TABLET,
+            )
+            .unwrap()
+    }
+
     fn platform_window(&self) -> Rc<PlatformWindow> {
         self.platform_window.borrow().clone()
     }
 
-    fn show(&self) -> Result<()> {
+    pub(super) fn show(&self) -> Result<()> {
         self.platform_window().show().map_err(|e| e.into())
     }
 
-    fn ready_to_show(&self) -> Result<()> {
+    pub(super) fn show_popover(&self, request: ShowPopoverRequest) -> Result<()> {
+        let frame_size = self
+            .get_geometry()?
+            .frame_size
+            .unwrap_or_else(|| Size::wh(0.0, 0.0));
+        let anchor = &request.anchor;
+        let x = anchor.center().x - frame_size.width / 2.0;
+        let y = match request.edge {
+            PopoverEdge::Below => anchor.y2(),
+            PopoverEdge::Above => anchor.y - frame_size.height,
+        };
+        self.set_geometry(WindowGeometryRequest {
+            geometry: WindowGeometry {
+                frame_origin: Some(Point::xy(x, y)),
+                ..Default::default()
+            },
+            preference: GeometryPreference::PreferFrame,
+        })?;
+        self.transient_popover.set(request.transient);
+        self.show()?;
+        self.activate()
+    }
+
+    // Callback is invoked with result of the modal session; Showing window that is
+    // already modal only waits for the result
+    pub(super) fn show_modal<F>(&self, on_done: F)
+    where
+        F: FnOnce(Result<Value>) + 'static,
+    {
+        if let Some(callbacks) = self.modal_callbacks.borrow_mut().as_mut() {
+            callbacks.push(Box::new(on_done));
+            return;
+        }
+        self.modal_callbacks
+            .replace(Some(vec![Box::new(on_done) as ModalCallback]));
+        let modal_callbacks = self.modal_callbacks.clone();
+        self.platform_window().show_modal(move |result| {
+            let result: Result<Value> = result.map_err(|e| e.into());
+            let callbacks = modal_callbacks.borrow_mut().take().unwrap_or_default();
+            for callback in callbacks {
+                callback(result.clone());
+            }
+        });
+    }
+
+    // Initial configuration; Visibility and modality are handled by caller
+    pub(super) fn apply_options(&self, options: WindowOptions) -> Result<()> {
+        if let Some(style) = options.style {
+            self.set_style(style)?;
+        }
+        if let Some(title) = options.title {
+            self.set_title(title)?;
+        }
+        if let Some(geometry) = options.geometry {
+            self.set_geometry(geometry)?;
+        }
+        Ok(())
+    }
+
+    pub(super) fn ready_to_show(&self) -> Result<()> {
+        if let Some(engine) = self.engine_handle {
+            self.context
+                .engine_manager
+                .borrow()
+                .notify_listeners(engine, EngineEvent::FirstFrame);
+        }
         self.platform_window().ready_to_show().map_err(|e| e.into())
     }
 
-    fn close(&self) -> Result<()> {
+    pub(super) fn close(&self) -> Result<()> {
         self.platform_window().close().map_err(|e| e.into())
     }
 
-    fn close_with_result(&self, result: Value) -> Result<()> {
+    pub(super) fn close_with_result(&self, result: Value) -> Result<()> {
         self.platform_window()
             .close_with_result(result)
             .map_err(|e| e.into())
     }
 
-    fn hide(&self) -> Result<()> {
+    pub(super) fn hide(&self) -> Result<()> {
         self.platform_window().hide().map_err(|e| e.into())
     }
 
+    pub(super) fn activate(&self) -> Result<()> {
+        self.platform_window().activate().map_err(|e| e.into())
+    }
+
+    // Shows or hides window in taskbar (Windows, Linux)
+    pub(super) fn set_activation_policy(&self, policy: ActivationPolicy) -> Result<()> {
+        self.platform_window()
+            .set_activation_policy(policy)
+            .map_err(|e| e.into())
+    }
+
     fn set_geometry(&self, geometry: WindowGeometryRequest) -> Result<WindowGeometryFlags> {
         self.platform_window()
             .set_geometry(geometry)
@@ -140,6 +334,13 @@ impl Window {
         self.platform_window().get_geometry().map_err(|e| e.into())
     }
 
+    fn update_geometry(&self, request: WindowGeometryRequest) -> Result<WindowGeometryUpdate> {
+        let platform_window = self.platform_window();
+        let applied = platform_window.set_geometry(request)?;
+        let geometry = platform_window.get_geometry()?;
+        Ok(WindowGeometryUpdate { applied, geometry })
+    }
+
     fn supported_geometry(&self) -> Result<WindowGeometryFlags> {
         self.platform_window()
             .supported_geometry()
@@ -153,8 +354,110 @@ impl Window {
     }
 
     fn set_title(&self, title: String) -> Result<()> {
+        self.platform_window().set_title(title.clone())?;
+        self.title.replace(title);
+        self.context.menu_manager.borrow().window_list_changed();
+        Ok(())
+    }
+
+    pub(super) fn title(&self) -> String {
+        self.title.borrow().clone()
+    }
+
+    pub(super) fn is_visible(&self) -> bool {
+        self.visible.get()
+    }
+
+    pub(super) fn is_active(&self) -> bool {
+        self.active.get()
+    }
+
+    fn set_subtitle(&self, subtitle: String) -> Result<()> {
+        self.platform_window()
+            .set_subtitle(subtitle)
+            .map_err(|e| e.into())
+    }
+
+    fn set_represented_file(&self, path: Option<String>) -> Result<()> {
+        self.platform_window()
+            .set_represented_file(path)
+            .map_err(|e| e.into())
+    }
+
+    fn set_document_edited(&self, edited: bool) -> Result<()> {
+        self.platform_window()
+            .set_document_edited(edited)
+            .map_err(|e| e.into())
+    }
+
+    fn set_appearance(&self, appearance: WindowAppearance) -> Result<()> {
         self.platform_window()
-            .set_title(title)
+            .set_appearance(appearance)
+            .map_err(|e| e.into())
+    }
+
+    fn set_backdrop(&self, backdrop: WindowBackdrop) -> Result<()> {
+        self.platform_window()
+            .set_backdrop(backdrop)
+            .map_err(|e| e.into())
+    }
+
+    pub(super) fn set_drop_filter(&self, filter: Option<DropFilter>) -> Result<()> {
+        self.drop_filter.replace(filter);
+        Ok(())
+    }
+
+    fn drop_filter_accepts(&self, data: &DragData) -> bool {
+        let filter = self.drop_filter.borrow();
+        let filter = match filter.as_ref() {
+            Some(filter) => filter,
+            None => return true,
+        };
+        let files = match data.properties.get(drag_data::key::FILES) {
+            Some(Value::List(files)) if !files.is_empty() => files,
+            _ => return filter.allow_non_file_data,
+        };
+        files.iter().all(|file| match file {
+            Value::String(path) => {
+                let extension = std::path::Path::new(path)
+                    .extension()
+                    .map(|e| e.to_string_lossy().to_lowercase());
+                let extension_matches = extension
+                    .map(|e| filter.extensions.iter().any(|f| f.to_lowercase() == e))
+                    .unwrap_or(false);
+                extension_matches || workspace::file_conforms_to(path, filter)
+            }
+            _ => false,
+        })
+    }
+
+    fn set_window_type(&self, window_type: WindowType) -> Result<()> {
+        self.platform_window()
+            .set_window_type(window_type)
+            .map_err(|e| e.into())
+    }
+
+    fn show_share_picker(&self, request: SharePickerRequest) -> Result<()> {
+        self.platform_window()
+            .show_share_picker(request)
+            .map_err(|e| e.into())
+    }
+
+    fn show_quick_look(&self, request: QuickLookRequest) -> Result<()> {
+        self.platform_window()
+            .show_quick_look(request)
+            .map_err(|e| e.into())
+    }
+
+    fn set_quick_look_index(&self, index: usize) -> Result<()> {
+        self.platform_window()
+            .set_quick_look_index(index)
+            .map_err(|e| e.into())
+    }
+
+    fn hide_quick_look(&self) -> Result<()> {
+        self.platform_window()
+            .hide_quick_look()
             .map_err(|e| e.into())
     }
 
@@ -165,8 +468,14 @@ impl Window {
     }
 
     fn begin_drag_session(&self, request: DragRequest) -> Result<()> {
+        self.platform_window().begin_drag_session(request)?;
+        trace::drag_session_started(self.window_handle.0);
+        Ok(())
+    }
+
+    pub(super) fn inject_event(&self, event: &SyntheticEvent) -> Result<()> {
         self.platform_window()
-            .begin_drag_session(request)
+            .inject_event(event)
             .map_err(|e| e.into())
     }
 
@@ -174,6 +483,14 @@ impl Window {
     where
         F: FnOnce(Result<PopupMenuResponse>) + 'static,
     {
+        let request = match request.keyboard_anchor.clone() {
+            Some(anchor) => PopupMenuRequest {
+                position: Point::xy(anchor.x, anchor.y2()),
+                preselect_first: true,
+                ..request
+            },
+            None => request,
+        };
         let menu = self
             .context
             .menu_manager
@@ -204,6 +521,30 @@ impl Window {
             .map_err(|e| e.into())
     }
 
+    fn set_maximize_button_rect(&self, rect: Option<Rect>) -> Result<()> {
+        self.platform_window()
+            .set_maximize_button_rect(rect)
+            .map_err(|e| e.into())
+    }
+
+    fn set_app_user_model_id(&self, id: Option<String>) -> Result<()> {
+        self.platform_window()
+            .set_app_user_model_id(id)
+            .map_err(|e| e.into())
+    }
+
+    fn is_on_active_space(&self) -> Result<bool> {
+        self.platform_window()
+            .is_on_active_space()
+            .map_err(|e| e.into())
+    }
+
+    fn move_to_active_space(&self) -> Result<()> {
+        self.platform_window()
+            .move_to_active_space()
+            .map_err(|e| e.into())
+    }
+
     fn set_window_menu(&self, request: SetMenuRequest) -> Result<()> {
         match request.handle {
             Some(handle) => {
@@ -223,11 +564,129 @@ impl Window {
         }
     }
 
+    fn set_touch_bar(&self, request: SetTouchBarRequest) -> Result<()> {
+        self.platform_window()
+            .set_touch_bar(request.touch_bar)
+            .map_err(|e| e.into())
+    }
+
+    fn create_platform_view(
+        &self,
+        request: CreatePlatformViewRequest,
+    ) -> Result<PlatformViewHandle> {
+        let (factory, handle) = {
+            let mut manager = self.context.platform_view_manager.borrow_mut();
+            (
+                manager.get_factory(&request.view_type)?,
+                manager.next_handle(),
+            )
+        };
+        let view = factory(self.window_handle, request.params)?;
+        let view = PlatformNativeView::new(self.platform_window(), view)?;
+        self.platform_views.borrow_mut().insert(handle, view);
+        Ok(handle)
+    }
+
+    fn update_platform_view(&self, request: UpdatePlatformViewRequest) -> Result<()> {
+        let views = self.platform_views.borrow();
+        let view = views
+            .get(&request.handle)
+            .ok_or(Error::InvalidPlatformViewHandle)?;
+        view.update(&request.frame, &request.clip, request.visible)
+            .map_err(|e| e.into())
+    }
+
+    fn dispose_platform_view(&self, request: DisposePlatformViewRequest) -> Result<()> {
+        self.platform_views
+            .borrow_mut()
+            .remove(&request.handle)
+            .map(|_| ())
+            .ok_or(Error::InvalidPlatformViewHandle)
+    }
+
+    pub(super) fn add_engine_view(
+        &self,
+        engine: EngineHandle,
+        view: NativeViewType,
+        init_data: Value,
+        frame: &Rect,
+    ) -> Result<()> {
+        let native_view = PlatformNativeView::new(self.platform_window(), view)?;
+        native_view.update(frame, frame, true)?;
+        self.engine_views.borrow_mut().insert(
+            engine,
+            EngineView {
+                native_view,
+                init_data,
+            },
+        );
+        Ok(())
+    }
+
+    pub(super) fn update_engine_view(
+        &self,
+        engine: EngineHandle,
+        frame: &Rect,
+        visible: bool,
+    ) -> Result<()> {
+        let views = self.engine_views.borrow();
+        let view = views.get(&engine).ok_or(Error::InvalidEngineHandle)?;
+        view.native_view
+            .update(frame, frame, visible)
+            .map_err(|e| e.into())
+    }
+
+    pub(super) fn remove_engine_view(&self, engine: EngineHandle) -> Result<()> {
+        self.engine_views
+            .borrow_mut()
+            .remove(&engine)
+            .map(|_| ())
+            .ok_or(Error::InvalidEngineHandle)
+    }
+
+    pub(super) fn engine_views(&self) -> Vec<EngineHandle> {
+        self.engine_views.borrow().keys().cloned().collect()
+    }
+
+    pub(super) fn engine_view_init_data(&self, engine: EngineHandle) -> Option<Value> {
+        self.engine_views
+            .borrow()
+            .get(&engine)
+            .map(|v| v.init_data.clone())
+    }
+
+    fn attach_engine_view(&self, request: AttachEngineViewRequest) -> Result<EngineHandle> {
+        let options = EngineOptions {
+            entrypoint: request.entrypoint,
+            entrypoint_args: request.entrypoint_args,
+            ..Default::default()
+        };
+        self.context.window_manager.borrow().attach_engine_view(
+            self.window_handle,
+            request.init_data,
+            &options,
+            request.frame,
+        )
+    }
+
+    fn update_engine_view_request(&self, request: UpdateEngineViewRequest) -> Result<()> {
+        self.update_engine_view(request.engine, &request.frame, request.visible)
+    }
+
+    fn detach_engine_view(&self, request: DetachEngineViewRequest) -> Result<()> {
+        self.context
+            .window_manager
+            .borrow()
+            .detach_engine_view(self.window_handle, request.engine)
+    }
+
     fn map_result<T>(result: Result<T>) -> WindowMethodCallResult
     where
         T: serde::Serialize,
     {
-        result.map(|v| to_value(v).unwrap()).map_err(|e| e.into())
+        result
+            .map(|v| to_value(v).unwrap())
+            .map_err(MethodCallError::from_error)
     }
 
     fn reply<'a, T, F, A>(reply: WindowMethodCallReply, arg: &'a Value, c: F)
@@ -254,10 +713,11 @@ impl Window {
             method::window::SHOW => {
                 return Self::reply(reply, &arg, |()| self.show());
             }
+            method::window::SHOW_POPOVER => {
+                return Self::reply(reply, &arg, |request| self.show_popover(request));
+            }
             method::window::SHOW_MODAL => {
-                return self.platform_window().show_modal(move |result| {
-                    reply.send(Self::map_result(result.map_err(|e| e.into())))
-                });
+                return self.show_modal(move |result| reply.send(Self::map_result(result)));
             }
             method::window::READY_TO_SHOW => {
                 return Self::reply(reply, &arg, |()| self.ready_to_show());
@@ -271,12 +731,18 @@ impl Window {
             method::window::HIDE => {
                 return Self::reply(reply, &arg, |()| self.hide());
             }
+            method::window::ACTIVATE => {
+                return Self::reply(reply, &arg, |()| self.activate());
+            }
             method::window::SET_GEOMETRY => {
                 return Self::reply(reply, &arg, |geometry| self.set_geometry(geometry));
             }
             method::window::GET_GEOMETRY => {
                 return Self::reply(reply, &arg, |()| self.get_geometry());
             }
+            method::window::UPDATE_GEOMETRY => {
+                return Self::reply(reply, &arg, |request| self.update_geometry(request));
+            }
             method::window::SUPPORTED_GEOMETRY => {
                 return Self::reply(reply, &arg, |()| self.supported_geometry());
             }
@@ -286,6 +752,27 @@ impl Window {
             method::window::SET_TITLE => {
                 return Self::reply(reply, &arg, |title| self.set_title(title));
             }
+            method::window::SET_SUBTITLE => {
+                return Self::reply(reply, &arg, |subtitle| self.set_subtitle(subtitle));
+            }
+            method::window::SET_REPRESENTED_FILE => {
+                return Self::reply(reply, &arg, |path| self.set_represented_file(path));
+            }
+            method::window::SET_DOCUMENT_EDITED => {
+                return Self::reply(reply, &arg, |edited| self.set_document_edited(edited));
+            }
+            method::window::SET_APPEARANCE => {
+                return Self::reply(reply, &arg, |appearance| self.set_appearance(appearance));
+            }
+            method::window::SET_BACKDROP => {
+                return Self::reply(reply, &arg, |backdrop| self.set_backdrop(backdrop));
+            }
+            method::window::SET_DROP_FILTER => {
+                return Self::reply(reply, &arg, |filter| self.set_drop_filter(filter));
+            }
+            method::window::SET_WINDOW_TYPE => {
+                return Self::reply(reply, &arg, |window_type| self.set_window_type(window_type));
+            }
             method::window::PERFORM_WINDOW_DRAG => {
                 return Self::reply(reply, &arg, |()| self.perform_window_drag());
             }
@@ -302,12 +789,57 @@ impl Window {
             method::window::HIDE_POPUP_MENU => {
                 return Self::reply(reply, &arg, |req| self.hide_popup_menu(req));
             }
+            method::window::SHOW_SHARE_PICKER => {
+                return Self::reply(reply, &arg, |req| self.show_share_picker(req));
+            }
+            method::window::SHOW_QUICK_LOOK => {
+                return Self::reply(reply, &arg, |req| self.show_quick_look(req));
+            }
+            method::window::SET_QUICK_LOOK_INDEX => {
+                return Self::reply(reply, &arg, |index| self.set_quick_look_index(index));
+            }
+            method::window::HIDE_QUICK_LOOK => {
+                return Self::reply(reply, &arg, |()| self.hide_quick_look());
+            }
             method::window::SHOW_SYSTEM_MENU => {
                 return Self::reply(reply, &arg, |()| self.show_system_menu());
             }
+            method::window::SET_MAXIMIZE_BUTTON_RECT => {
+                return Self::reply(reply, &arg, |rect| self.set_maximize_button_rect(rect));
+            }
+            method::window::SET_APP_USER_MODEL_ID => {
+                return Self::reply(reply, &arg, |id| self.set_app_user_model_id(id));
+            }
+            method::window::IS_ON_ACTIVE_SPACE => {
+                return Self::reply(reply, &arg, |()| self.is_on_active_space());
+            }
+            method::window::MOVE_TO_ACTIVE_SPACE => {
+                return Self::reply(reply, &arg, |()| self.move_to_active_space());
+            }
             method::window::SET_WINDOW_MENU => {
                 return Self::reply(reply, &arg, |req| self.set_window_menu(req));
             }
+            method::window::SET_TOUCH_BAR => {
+                return Self::reply(reply, &arg, |req| self.set_touch_bar(req));
+            }
+            method::window::CREATE_PLATFORM_VIEW => {
+                return Self::reply(reply, &arg, |req| self.create_platform_view(req));
+            }
+            method::window::UPDATE_PLATFORM_VIEW => {
+                return Self::reply(reply, &arg, |req| self.update_platform_view(req));
+            }
+            method::window::DISPOSE_PLATFORM_VIEW => {
+                return Self::reply(reply, &arg, |req| self.dispose_platform_view(req));
+            }
+            method::window::ATTACH_ENGINE_VIEW => {
+                return Self::reply(reply, &arg, |req| self.attach_engine_view(req));
+            }
+            method::window::UPDATE_ENGINE_VIEW => {
+                return Self::reply(reply, &arg, |req| self.update_engine_view_request(req));
+            }
+            method::window::DETACH_ENGINE_VIEW => {
+                return Self::reply(reply, &arg, |req| self.detach_engine_view(req));
+            }
             method::drag_source::BEGIN_DRAG_SESSION => {
                 return Self::reply(reply, &arg, |request| self.begin_drag_session(request));
             }
@@ -320,19 +852,55 @@ impl Window {
 
 pub trait PlatformWindowDelegate {
     fn visibility_changed(&self, visible: bool);
+    fn activation_changed(&self, active: bool);
+    // Minimized or (where supported) fully covered by other windows
+    fn occlusion_changed(&self, occluded: bool);
     fn did_request_close(&self);
     fn will_close(&self);
 
+    // Platform may report these redundantly; Window only broadcasts actual changes
+    fn pointer_entered(&self);
+    fn pointer_exited(&self);
+
     fn dragging_exited(&self);
     fn dragging_updated(&self, info: &DraggingInfo);
     fn perform_drop(&self, info: &DraggingInfo);
 
     fn drag_ended(&self, effect: DragEffect);
+
+    fn touch_bar_action(&self, action: TouchBarAction);
+
+    fn tablet_event(&self, event: TabletEvent);
+
+    fn represented_file_dragged(&self, path: String);
+
+    fn active_space_changed(&self, on_active_space: bool);
+
+    // Showing the window failed after the show request itself succeeded (i.e.
+    // while waiting for first frame)
+    fn show_failed(&self, error: Error);
 }
 
 impl PlatformWindowDelegate for Window {
     fn visibility_changed(&self, visible: bool) {
         self.broadcast_message(event::window::VISIBILITY_CHANGED, Value::Bool(visible));
+        self.visible.set(visible);
+        self.update_lifecycle_state();
+        self.context.menu_manager.borrow().window_list_changed();
+    }
+
+    fn activation_changed(&self, active: bool) {
+        self.active.set(active);
+        if !active && self.transient_popover.replace(false) {
+            self.hide().ok_log();
+        }
+        self.update_lifecycle_state();
+        self.context.menu_manager.borrow().window_list_changed();
+    }
+
+    fn occlusion_changed(&self, occluded: bool) {
+        self.occluded.set(occluded);
+        self.update_lifecycle_state();
     }
 
     fn did_request_close(&self) {
@@ -341,36 +909,73 @@ impl PlatformWindowDelegate for Window {
 
     fn will_close(&self) {
         self.broadcast_message(event::window::CLOSE, Value::Null);
-        self.context.window_manager.borrow_mut().remove_window(self);
+        self.platform_views.borrow_mut().clear();
+        // Engines of embedded views are shut down together with the window
+        self.context.window_manager.borrow().remove_window(self);
+        self.engine_views.borrow_mut().clear();
+    }
+
+    fn pointer_entered(&self) {
+        if !self.pointer_inside.replace(true) {
+            self.broadcast_message(event::window::POINTER_ENTERED, Value::Null);
+        }
+    }
+
+    fn pointer_exited(&self) {
+        if self.pointer_inside.replace(false) {
+            self.broadcast_message(event::window::POINTER_EXITED, Value::Null);
+        }
     }
 
     fn dragging_exited(&self) {
+        self.pending_dragging_update.replace(None);
         self.drop_target_invoker()
             .call_method(method::drop_target::DRAGGING_EXITED, Value::Null, |_| {})
             .ok_log();
     }
 
     fn dragging_updated(&self, info: &DraggingInfo) {
+        if !self.drop_filter_accepts(&info.data) {
+            self.platform_window().set_pending_effect(DragEffect::None);
+            return;
+        }
+        if self.dragging_update_in_flight.replace(true) {
+            self.pending_dragging_update.replace(Some(info.clone()));
+            return;
+        }
         let weak = self.weak_self.clone_value();
-        self.drop_target_invoker()
-            .call_method(
-                method::drop_target::DRAGGING_UPDATED,
-                to_value(info).unwrap(),
-                move |r| {
-                    let s = weak.upgrade();
-                    if let (Ok(result), Some(s)) = (r, s) {
-                        let result: DragResult =
-                            from_value(&result).ok_log().unwrap_or(DragResult {
-                                effect: DragEffect::None,
-                            });
-                        s.platform_window().set_pending_effect(result.effect);
-                    }
-                },
-            )
-            .ok_log();
+        let res = self.drop_target_invoker().call_method(
+            method::drop_target::DRAGGING_UPDATED,
+            to_value(info).unwrap(),
+            move |r| {
+                let s = match weak.upgrade() {
+                    Some(s) => s,
+                    None => return,
+                };
+                if let Ok(result) = r {
+                    let result: DragResult = from_value(&result).ok_log().unwrap_or(DragResult {
+                        effect: DragEffect::None,
+                    });
+                    s.platform_window().set_pending_effect(result.effect);
+                }
+                s.dragging_update_in_flight.set(false);
+                let pending = s.pending_dragging_update.take();
+                if let Some(pending) = pending {
+                    s.dragging_updated(&pending);
+                }
+            },
+        );
+        if res.ok_log().is_none() {
+            self.dragging_update_in_flight.set(false);
+        }
     }
 
     fn perform_drop(&self, info: &DraggingInfo) {
+        // Drop carries the latest position already
+        self.pending_dragging_update.replace(None);
+        if !self.drop_filter_accepts(&info.data) {
+            return;
+        }
         self.drop_target_invoker()
             .call_method(
                 method::drop_target::PERFORM_DROP,
@@ -381,6 +986,7 @@ impl PlatformWindowDelegate for Window {
     }
 
     fn drag_ended(&self, effect: DragEffect) {
+        trace::drag_session_ended(self.window_handle.0, &effect);
         self.drag_source_invoker()
             .call_method(
                 method::drag_source::DRAG_SESSION_ENDED,
@@ -389,4 +995,138 @@ impl PlatformWindowDelegate for Window {
             )
             .ok_log();
     }
+
+    fn touch_bar_action(&self, action: TouchBarAction) {
+        self.touch_bar_invoker()
+            .call_method(
+                method::touch_bar::ON_ACTION,
+                to_value(action).unwrap(),
+                |_| {},
+            )
+            .ok_log();
+    }
+
+    fn tablet_event(&self, event: TabletEvent) {
+        self.tablet_invoker()
+            .call_method(method::tablet::ON_EVENT, to_value(event).unwrap(), |_| {})
+            .ok_log();
+    }
+
+    fn represented_file_dragged(&self, path: String) {
+        self.broadcast_message(event::window::REPRESENTED_FILE_DRAGGED, Value::String(path));
+    }
+
+    fn active_space_changed(&self, on_active_space: bool) {
+        self.broadcast_message(
+            event::window::ACTIVE_SPACE_CHANGED,
+            Value::Bool(on_active_space),
+        );
+    }
+
+    fn show_failed(&self, error: Error) {
+        let error = MethodCallError::from_error(error);
+        self.broadcast_message(
+            event::window::SHOW_FAILED,
+            encode_error(&error.code, error.message.as_deref(), error.details),
+        );
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        codec::Value,
+        shell::{
+            api_constants::drag_data,
+            api_model::{
+                DragData, DraggingInfo, DropFilter, GeometryPreference, PopoverEdge,
+                ShowPopoverRequest, WindowGeometry, WindowGeometryRequest,
+            },
+            platform::recorder::take_operations,
+            test_util::{create_window, mock_context, platform_window},
+            Point, Rect, Size,
+        },
+    };
+
+    #[test]
+    fn test_drop_filter_rejects_without_dart_round_trip() {
+        let context = mock_context();
+        let handle = create_window(&context, None);
+        context
+            .window_manager
+            .borrow()
+            .get_window(handle)
+            .unwrap()
+            .set_drop_filter(Some(DropFilter {
+                extensions: vec!["PNG".into()],
+                ..Default::default()
+            }))
+            .unwrap();
+
+        let info = |file: &str| {
+            let mut properties = HashMap::new();
+            properties.insert(
+                drag_data::key::FILES.to_string(),
+                Value::List(vec![file.into()]),
+            );
+            DraggingInfo {
+                location: Default::default(),
+                data: DragData { properties },
+                allowed_effects: Vec::new(),
+            }
+        };
+        let pending_effects = || {
+            take_operations()
+                .into_iter()
+                .filter(|o| o.name == "set_pending_effect")
+                .count()
+        };
+
+        take_operations();
+        let platform_window = platform_window(&context, handle);
+        platform_window.simulate(|d| d.dragging_updated(&info("/tmp/notes.txt")));
+        assert_eq!(pending_effects(), 1);
+
+        // Accepted file is forwarded to Dart, which decides the effect
+        platform_window.simulate(|d| d.dragging_updated(&info("/tmp/image.png")));
+        assert_eq!(pending_effects(), 0);
+    }
+
+    #[test]
+    fn test_transient_popover() {
+        let context = mock_context();
+        let handle = create_window(&context, None);
+        let platform_window = platform_window(&context, handle);
+        platform_window
+            .set_geometry(WindowGeometryRequest {
+                geometry: WindowGeometry {
+                    frame_size: Some(Size::wh(200.0, 300.0)),
+                    ..Default::default()
+                },
+                preference: GeometryPreference::PreferFrame,
+            })
+            .unwrap();
+        platform_window.ready_to_show().unwrap();
+
+        context
+            .window_manager
+            .borrow()
+            .show_popover(
+                handle,
+                ShowPopoverRequest {
+                    anchor: Rect::xywh(500.0, 0.0, 20.0, 24.0),
+                    edge: PopoverEdge::Below,
+                    transient: true,
+                },
+            )
+            .unwrap();
+        let geometry = platform_window.get_geometry().unwrap();
+        assert_eq!(geometry.frame_origin, Some(Point::xy(410.0, 24.0)));
+        assert!(platform_window.is_visible());
+
+        platform_window.simulate(|d| d.activation_changed(false));
+        assert!(!platform_window.is_visible());
+    }
 }
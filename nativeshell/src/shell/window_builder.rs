@@ -0,0 +1,135 @@
+use crate::{codec::Value, Result};
+
+use super::{
+    api_model::{NativeWindowContent, WindowGeometryRequest, WindowOptions, WindowStyle},
+    decode_modal_result, Context, EngineOptions, ModalCallback, WindowHandle,
+};
+
+// Creates window with initial configuration. Style, title and geometry are applied
+// before the window is shown for the first time, so the window doesn't visibly
+// change after it appears and Dart code doesn't need to configure it.
+#[derive(Default)]
+pub struct WindowBuilder {
+    pub(super) init_data: Value,
+    pub(super) parent: Option<WindowHandle>,
+    pub(super) engine_options: EngineOptions,
+    pub(super) options: WindowOptions,
+    pub(super) on_modal_done: Option<ModalCallback>,
+    pub(super) native_content: Option<NativeWindowContent>,
+}
+
+impl WindowBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    // Available to Dart code of the window through WindowState.initData
+    pub fn with_init_data(mut self, init_data: Value) -> Self {
+        self.init_data = init_data;
+        self
+    }
+
+    pub fn with_parent(mut self, parent: WindowHandle) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    pub fn with_engine_options(mut self, engine_options: EngineOptions) -> Self {
+        self.engine_options = engine_options;
+        self
+    }
+
+    // Window without Flutter engine (i.e. overlay or screenshot flash); Init data
+    // and engine options are ignored
+    pub fn with_native_content(mut self, content: NativeWindowContent) -> Self {
+        self.native_content = Some(content);
+        self
+    }
+
+    pub fn with_options(mut self, options: WindowOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.options.title = Some(title.into());
+        self
+    }
+
+    pub fn with_style(mut self, style: WindowStyle) -> Self {
+        self.options.style = Some(style);
+        self
+    }
+
+    pub fn with_geometry(mut self, geometry: WindowGeometryRequest) -> Self {
+        self.options.geometry = Some(geometry);
+        self
+    }
+
+    // Window is shown as soon as Dart renders first frame
+    pub fn with_visible(mut self, visible: bool) -> Self {
+        self.options.visible = visible;
+        self
+    }
+
+    // Window is shown as modal for its parent as soon as Dart renders first frame;
+    // Callback is invoked with the value window was closed with
+    pub fn with_modal<F>(mut self, on_done: F) -> Self
+    where
+        F: FnOnce(Result<Value>) + 'static,
+    {
+        self.options.modal = true;
+        self.on_modal_done = Some(Box::new(on_done));
+        self
+    }
+
+    // Typed variant of with_modal; Result that can't be decoded as T is reported
+    // as Error::Value
+    pub fn with_modal_result<T, F>(self, on_done: F) -> Self
+    where
+        T: serde::de::DeserializeOwned,
+        F: FnOnce(Result<T>) + 'static,
+    {
+        self.with_modal(move |result| on_done(decode_modal_result(result)))
+    }
+
+    pub fn build(self, context: &Context) -> WindowHandle {
+        context.window_manager.borrow().open_window(self)
+    }
+}
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use crate::shell::{
+        api_model::WindowStyle,
+        platform::recorder::{take_operations, MockTarget},
+        test_util::{mock_context, platform_window},
+    };
+
+    use super::WindowBuilder;
+
+    #[test]
+    fn test_window_builder_configures_window_before_showing() {
+        let context = mock_context();
+        let handle = WindowBuilder::new()
+            .with_title("Title")
+            .with_style(WindowStyle::default())
+            .with_visible(true)
+            .build(&context);
+        let platform_window = platform_window(&context, handle);
+        assert!(!platform_window.is_visible());
+
+        let operations: Vec<_> = take_operations()
+            .into_iter()
+            .filter(|o| matches!(o.target, MockTarget::Window(_)))
+            .map(|o| o.name)
+            .collect();
+        assert_eq!(
+            operations,
+            vec!["new", "assign_engine", "set_style", "set_title", "show"]
+        );
+
+        platform_window.ready_to_show().unwrap();
+        assert!(platform_window.is_visible());
+    }
+}
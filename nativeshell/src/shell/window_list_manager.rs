@@ -0,0 +1,55 @@
+use std::rc::Rc;
+
+use crate::{
+    codec::{value::from_value, MethodCall, MethodCallReply, Value},
+    Result,
+};
+
+use super::{
+    api_constants::*,
+    api_model::{ExternalWindow, WindowListRequest},
+    platform::window_list,
+    Context, EngineHandle,
+};
+
+// Enumerates windows of other applications, so that screen sharing and window
+// capture utilities can present a window picker. On macOS window titles and
+// thumbnails require screen recording permission; Not supported on Wayland.
+pub struct WindowListManager {}
+
+impl WindowListManager {
+    pub(super) fn new(context: Rc<Context>) -> Self {
+        let context_copy = context.clone();
+        context
+            .message_manager
+            .borrow_mut()
+            .register_method_handler(channel::WINDOW_LIST, move |value, reply, engine| {
+                context_copy
+                    .window_list_manager
+                    .borrow()
+                    .on_method_call(value, reply, engine);
+            });
+        Self {}
+    }
+
+    // Windows are returned in front to back order
+    pub fn get_windows(&self, request: &WindowListRequest) -> Result<Vec<ExternalWindow>> {
+        window_list::get_windows(request).map_err(|e| e.into())
+    }
+
+    fn on_method_call(
+        &self,
+        call: MethodCall<Value>,
+        reply: MethodCallReply<Value>,
+        _engine: EngineHandle,
+    ) {
+        if call.method.as_str() == method::window_list::GET_WINDOWS {
+            let request = from_value::<WindowListRequest>(&call.args);
+            reply.send_result(
+                request
+                    .map_err(|e| e.into())
+                    .and_then(|request| self.get_windows(&request)),
+            );
+        }
+    }
+}
@@ -1,5 +1,11 @@
-use std::{borrow::Borrow, collections::HashMap, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::Rc,
+    time::Duration,
+};
 
+use log::warn;
 use velcro::hash_map;
 
 use crate::{
@@ -7,21 +13,40 @@ use crate::{
         value::{from_value, to_value},
         MessageCodec, MessageSender, MethodCallError, StandardMethodCodec, Value,
     },
-    util::OkLog,
+    util::{trace, OkLog, SlotKey, SlotMap},
+    Error, Result,
 };
 
 use super::{
     api_constants::*,
+    api_model::{
+        ActivationPolicy, NativeWindowContent, PointerState, ShowPopoverRequest, WindowOptions,
+    },
+    decode_modal_result,
     platform::window::{PlatformWindow, PlatformWindowType},
-    Context, EngineHandle, PlatformWindowDelegate, Window, WindowHandle, WindowMethodCall,
-    WindowMethodCallReply,
+    Context, EngineEvent, EngineHandle, EngineOptions, ModalCallback, PlatformWindowDelegate, Rect,
+    RepeatingTimer, Window, WindowBuilder, WindowHandle, WindowMethodCall, WindowMethodCallReply,
 };
 
+// All methods take shared reference; State is only borrowed for short lookups
+// and never while calling into windows, engines or platform, so that windows
+// can be created or closed from within callbacks of other windows.
 pub struct WindowManager {
     context: Rc<Context>,
-    windows: HashMap<WindowHandle, Rc<Window>>,
-    next_handle: WindowHandle,
-    engine_to_window: HashMap<EngineHandle, WindowHandle>,
+    // Window handle is raw key of the slot, so handles of closed windows never
+    // resolve to windows created later
+    windows: RefCell<SlotMap<Rc<Window>>>,
+    // Open windows in order of creation; First one is the main window
+    window_order: RefCell<Vec<WindowHandle>>,
+    engine_to_window: RefCell<HashMap<EngineHandle, WindowHandle>>,
+
+    // Launched headless engines waiting to be attached to new windows
+    engine_pool: RefCell<Vec<EngineHandle>>,
+    engine_pool_supported: Cell<bool>,
+    engine_pool_timer: RefCell<Option<RepeatingTimer>>,
+
+    // Init requests from engines that have no window yet
+    pending_init: RefCell<HashMap<EngineHandle, WindowMethodCallReply>>,
 }
 
 #[derive(serde::Deserialize)]
@@ -29,6 +54,14 @@ pub struct WindowManager {
 struct WindowCreateRequest {
     parent: WindowHandle,
     init_data: Value,
+    #[serde(default)]
+    entrypoint: Option<String>,
+    #[serde(default)]
+    entrypoint_args: Vec<String>,
+    #[serde(default)]
+    options: WindowOptions,
+    #[serde(default)]
+    native_content: Option<NativeWindowContent>,
 }
 
 #[derive(serde::Serialize)]
@@ -57,36 +90,328 @@ impl WindowManager {
 
         WindowManager {
             context,
-            windows: HashMap::new(),
-            next_handle: WindowHandle(1),
-            engine_to_window: HashMap::new(),
+            windows: RefCell::new(SlotMap::new()),
+            window_order: RefCell::new(Vec::new()),
+            engine_to_window: RefCell::new(HashMap::new()),
+            engine_pool: RefCell::new(Vec::new()),
+            engine_pool_supported: Cell::new(true),
+            engine_pool_timer: RefCell::new(None),
+            pending_init: RefCell::new(HashMap::new()),
         }
     }
 
     pub fn create_window(
-        &mut self,
+        &self,
         init_data: Value,
         parent: Option<WindowHandle>,
+        options: &EngineOptions,
     ) -> WindowHandle {
-        let window_handle = self.next_handle;
-        self.next_handle.0 += 1;
+        // Pooled engines are created with default options
+        let pool_empty = self.engine_pool.borrow().is_empty();
+        if !pool_empty && *options == EngineOptions::default() {
+            if let Some(window_handle) = self.create_window_from_pool(init_data.clone(), parent) {
+                return window_handle;
+            }
+        }
+
+        let engine_handle = self
+            .context
+            .engine_manager
+            .borrow_mut()
+            .create_engine(options);
+        let window_handle = self.create_window_with_engine(engine_handle, init_data, parent);
+
+        self.context
+            .engine_manager
+            .borrow_mut()
+            .launch_engine(engine_handle)
+            .ok_log();
+
+        window_handle
+    }
+
+    // Opens window configured by the builder. Doesn't require any other window or
+    // engine to exist, so it can be used from tray, hotkey or URL handlers.
+    pub fn open_window(&self, builder: WindowBuilder) -> WindowHandle {
+        let handle = match builder.native_content {
+            Some(content) => self.create_native_window(content, builder.parent),
+            None => self.create_window(builder.init_data, builder.parent, &builder.engine_options),
+        };
+        // Engine has not rendered first frame yet, so the window is not visible
+        self.apply_window_options(handle, builder.options, builder.on_modal_done)
+            .ok_log();
+        handle
+    }
+
+    // Applies options to existing window; Visible or modal options show the window
+    pub fn configure_window(&self, handle: WindowHandle, options: WindowOptions) -> Result<()> {
+        self.apply_window_options(handle, options, None)
+    }
+
+    pub fn show_window(&self, handle: WindowHandle) -> Result<()> {
+        self.existing_window(handle)?.show()
+    }
+
+    // Positions window next to anchor (i.e. tray icon) and shows it
+    pub fn show_popover(&self, handle: WindowHandle, request: ShowPopoverRequest) -> Result<()> {
+        self.existing_window(handle)?.show_popover(request)
+    }
+
+    pub fn hide_window(&self, handle: WindowHandle) -> Result<()> {
+        self.existing_window(handle)?.hide()
+    }
+
+    pub fn activate_window(&self, handle: WindowHandle) -> Result<()> {
+        self.existing_window(handle)?.activate()
+    }
 
-        let engine_handle = self.context.engine_manager.borrow_mut().create_engine();
+    // Shows window as modal for its parent; Callback receives the value window was
+    // closed with (from Dart or close_with_result) decoded as T
+    pub fn show_modal<T, F>(&self, handle: WindowHandle, on_done: F) -> Result<()>
+    where
+        T: serde::de::DeserializeOwned,
+        F: FnOnce(Result<T>) + 'static,
+    {
+        self.existing_window(handle)?
+            .show_modal(move |result| on_done(decode_modal_result(result)));
+        Ok(())
+    }
+
+    // Closes modal window; Result is delivered to all show_modal callbacks
+    pub fn close_with_result<T>(&self, handle: WindowHandle, result: &T) -> Result<()>
+    where
+        T: serde::Serialize,
+    {
+        self.existing_window(handle)?
+            .close_with_result(to_value(result)?)
+    }
+
+    // Closes window without asking Dart (close request event is not fired)
+    pub fn close_window(&self, handle: WindowHandle) -> Result<()> {
+        self.existing_window(handle)?.close()
+    }
+
+    // Handles of open windows in order of creation
+    pub fn get_all_windows(&self) -> Vec<WindowHandle> {
+        self.window_order.borrow().clone()
+    }
+
+    // Embeds Flutter view of a new engine in window content. The engine runs its
+    // own isolate with own channels and is identified by returned handle; Dart
+    // code of the view gets the window as current window and init data through
+    // WindowState.initData. Frame is in logical coordinates.
+    pub fn attach_engine_view(
+        &self,
+        window: WindowHandle,
+        init_data: Value,
+        options: &EngineOptions,
+        frame: Rect,
+    ) -> Result<EngineHandle> {
+        let window = self.existing_window(window)?;
+        let engine = self
+            .context
+            .engine_manager
+            .borrow_mut()
+            .create_engine(options);
+        let view = self
+            .context
+            .engine_manager
+            .borrow()
+            .get_engine(engine)
+            .ok_or(Error::InvalidEngineHandle)
+            .and_then(|e| e.embedded_view())
+            .and_then(|view| window.add_engine_view(engine, view, init_data, &frame));
+        if let Err(error) = view {
+            self.remove_engines(vec![engine]);
+            return Err(error);
+        }
+        self.engine_to_window
+            .borrow_mut()
+            .insert(engine, window.window_handle);
+        self.context
+            .engine_manager
+            .borrow_mut()
+            .launch_engine(engine)?;
+        Ok(engine)
+    }
+
+    pub fn update_engine_view(
+        &self,
+        window: WindowHandle,
+        engine: EngineHandle,
+        frame: Rect,
+        visible: bool,
+    ) -> Result<()> {
+        self.existing_window(window)?
+            .update_engine_view(engine, &frame, visible)
+    }
 
-        self.engine_to_window.insert(engine_handle, window_handle);
+    pub fn detach_engine_view(&self, window: WindowHandle, engine: EngineHandle) -> Result<()> {
+        self.existing_window(window)?.remove_engine_view(engine)?;
+        self.engine_to_window.borrow_mut().remove(&engine);
+        self.remove_engines(vec![engine]);
+        Ok(())
+    }
+
+    fn existing_window(&self, handle: WindowHandle) -> Result<Rc<Window>> {
+        self.get_window(handle).ok_or(Error::InvalidWindowHandle)
+    }
 
+    fn apply_window_options(
+        &self,
+        handle: WindowHandle,
+        options: WindowOptions,
+        on_modal_done: Option<ModalCallback>,
+    ) -> Result<()> {
+        let window = self.existing_window(handle)?;
+        let (visible, modal) = (options.visible, options.modal);
+        window.apply_options(options)?;
+        if modal {
+            window.show_modal(move |result| {
+                if let Some(on_modal_done) = on_modal_done {
+                    on_modal_done(result);
+                }
+            });
+            Ok(())
+        } else if visible {
+            window.show()
+        } else {
+            Ok(())
+        }
+    }
+
+    // Creates window for engine created through EngineManager::create_headless_engine
+    pub fn create_window_for_engine(
+        &self,
+        engine_handle: EngineHandle,
+        init_data: Value,
+        parent: Option<WindowHandle>,
+    ) -> Result<WindowHandle> {
+        if self.engine_to_window.borrow().contains_key(&engine_handle) {
+            return Err(Error::InvalidEngineHandle);
+        }
+        self.context
+            .engine_manager
+            .borrow()
+            .attach_view(engine_handle)?;
+        Ok(self.create_window_with_engine(engine_handle, init_data, parent))
+    }
+
+    fn create_window_from_pool(
+        &self,
+        init_data: Value,
+        parent: Option<WindowHandle>,
+    ) -> Option<WindowHandle> {
+        let engine_handle = self.engine_pool.borrow_mut().pop()?;
+        match self.create_window_for_engine(engine_handle, init_data, parent) {
+            Ok(window_handle) => Some(window_handle),
+            Err(error) => {
+                // Attaching engines to windows is not supported on all platforms
+                warn!("Could not use pooled engine: {}", error);
+                self.engine_pool_supported.set(false);
+                let mut engines = vec![engine_handle];
+                engines.append(&mut self.engine_pool.borrow_mut());
+                self.remove_engines(engines);
+                None
+            }
+        }
+    }
+
+    fn engine_pool_full(&self) -> bool {
+        !self.engine_pool_supported.get()
+            || self.engine_pool.borrow().len() >= self.context.options.engine_pool_size
+    }
+
+    fn schedule_engine_pool_refill(&self) {
+        if self.engine_pool_full() {
+            return;
+        }
+        // Give the window that triggered refill time to show first
+        let delay = Duration::from_millis(500);
+        if let Some(timer) = &*self.engine_pool_timer.borrow() {
+            timer.reschedule(delay);
+            return;
+        }
+        let context = self.context.clone();
+        let timer = self
+            .context
+            .run_loop
+            .borrow()
+            .schedule_repeating(delay, move || {
+                context.window_manager.borrow().refill_engine_pool();
+            });
+        self.engine_pool_timer.replace(Some(timer));
+    }
+
+    fn refill_engine_pool(&self) {
+        // Pool is drained when last window is closed
+        if self.windows.borrow().is_empty() || self.engine_pool_full() {
+            if let Some(timer) = &*self.engine_pool_timer.borrow() {
+                timer.pause();
+            }
+            return;
+        }
+        // Create one engine per timer tick to keep run loop responsive
+        let engine = self
+            .context
+            .engine_manager
+            .borrow_mut()
+            .create_headless_engine(&EngineOptions::default());
+        match engine {
+            Ok(engine) => self.engine_pool.borrow_mut().push(engine),
+            Err(error) => {
+                warn!("Could not create pooled engine: {}", error);
+                self.engine_pool_supported.set(false);
+            }
+        }
+    }
+
+    fn remove_engines(&self, engines: Vec<EngineHandle>) {
+        let context_copy = self.context.clone();
+
+        // This is a bit hacky; When engine destroy is triggered from flutter
+        // platform task runner, we need to schedule this on next run loop turn otherwise
+        // it may cause crashes. This particular hack could be avoided by scheduling
+        // every flutter message callback on run loop, but is probably not worth the
+        // overhead.
+        self.context
+            .run_loop
+            .borrow()
+            .schedule_now(move || {
+                let engine_manager = context_copy.engine_manager.borrow();
+                for engine in engines {
+                    engine_manager.shut_down_engine(engine, |res| {
+                        res.ok_log();
+                    });
+                }
+            })
+            .detach();
+    }
+
+    // Registers window and creates its platform window; Content is assigned by caller
+    fn insert_window(
+        &self,
+        engine_handle: Option<EngineHandle>,
+        init_data: Value,
+        parent: Option<WindowHandle>,
+    ) -> (Rc<Window>, Rc<PlatformWindow>) {
+        // Window must not be created while the map is borrowed
+        let key = self.windows.borrow_mut().reserve_key();
         let window = Rc::new(Window::new(
             self.context.clone(),
-            window_handle,
+            WindowHandle(key.to_raw()),
             engine_handle,
             init_data,
             parent,
         ));
-
+        self.windows
+            .borrow_mut()
+            .insert_reserved(key, window.clone());
         window.assign_weak_self(Rc::downgrade(&window));
+        self.window_order.borrow_mut().push(window.window_handle);
 
         let parent_platform_window = parent
-            .and_then(|h| self.windows.get(&h))
+            .and_then(|h| self.get_window(h))
             .map(|w| w.platform_window.borrow().clone());
 
         let platform_window = Rc::new(PlatformWindow::new(
@@ -94,8 +419,21 @@ impl WindowManager {
             Rc::downgrade(&(window.clone() as Rc<dyn PlatformWindowDelegate>)),
             parent_platform_window,
         ));
+        (window, platform_window)
+    }
 
-        self.windows.insert(window_handle, window.clone());
+    fn create_window_with_engine(
+        &self,
+        engine_handle: EngineHandle,
+        init_data: Value,
+        parent: Option<WindowHandle>,
+    ) -> WindowHandle {
+        let (window, platform_window) = self.insert_window(Some(engine_handle), init_data, parent);
+        let window_handle = window.window_handle;
+        trace::window_created(window_handle.0, engine_handle.0);
+        self.engine_to_window
+            .borrow_mut()
+            .insert(engine_handle, window_handle);
 
         platform_window.assign_weak_self(
             Rc::downgrade(&platform_window),
@@ -108,52 +446,173 @@ impl WindowManager {
                 .platform_engine,
         );
         window.platform_window.set(platform_window);
+        self.apply_activation_policy(&window);
 
+        // Engine launched before it had a window may be waiting for init
+        let pending_init = self.pending_init.borrow_mut().remove(&engine_handle);
+        if let Some(reply) = pending_init {
+            self.init_window(window_handle, engine_handle, reply);
+        }
+
+        self.schedule_engine_pool_refill();
+
+        window_handle
+    }
+
+    // Windows created while application is hidden from taskbar follow current
+    // activation policy
+    fn apply_activation_policy(&self, window: &Window) {
+        let policy = self
+            .context
+            .application_manager
+            .borrow()
+            .activation_policy();
+        if policy != ActivationPolicy::Regular {
+            window.set_activation_policy(policy).ok_log();
+        }
+    }
+
+    fn create_native_window(
+        &self,
+        content: NativeWindowContent,
+        parent: Option<WindowHandle>,
+    ) -> WindowHandle {
+        let (window, platform_window) = self.insert_window(None, Value::Null, parent);
+        let window_handle = window.window_handle;
+        trace::native_window_created(window_handle.0);
+
+        platform_window.assign_weak_self_native(Rc::downgrade(&platform_window), &content);
+        window.platform_window.set(platform_window);
+        self.apply_activation_policy(&window);
+
+        // There is no first frame to wait for and no Dart code to initialize the
+        // window, so other isolates can address it right away
+        window.ready_to_show().ok_log();
+        window.initialized.set(true);
         self.context
-            .engine_manager
-            .borrow_mut()
-            .launch_engine(engine_handle)
-            .ok_log();
+            .window_method_channel
+            .borrow()
+            .get_message_broadcaster(window_handle, channel::win::WINDOW_MANAGER)
+            .broadcast_message(event::window::INITIALIZE, Value::Null);
 
         window_handle
     }
 
     pub fn get_platform_window(&self, handle: WindowHandle) -> Option<PlatformWindowType> {
+        self.get_window(handle)
+            .map(|w| w.platform_window.borrow().get_platform_window())
+    }
+
+    // Mouse buttons, modifiers and pointer position as seen by the window; Meant for
+    // plugins that need current mouse state outside of Flutter pointer events
+    pub fn get_pointer_state(&self, handle: WindowHandle) -> Option<PointerState> {
+        self.get_window(handle)
+            .map(|w| w.platform_window.borrow().pointer_state())
+    }
+
+    pub(super) fn get_window(&self, handle: WindowHandle) -> Option<Rc<Window>> {
         self.windows
             .borrow()
-            .get(&handle)
-            .map(|w| w.platform_window.borrow().get_platform_window())
+            .get(SlotKey::from_raw(handle.0))
+            .cloned()
     }
 
-    pub(super) fn remove_window(&mut self, window: &Window) {
-        let engine_handle = window.engine_handle;
-        let context_copy = self.context.clone();
+    // Snapshot of open windows; Windows may be added or removed while iterating
+    fn all_windows(&self) -> Vec<Rc<Window>> {
+        self.windows.borrow().values().cloned().collect()
+    }
 
-        // This is a bit hacky; When engine destroy is triggered from flutter
-        // platform task runner, we need to schedule this on next run loop turn otherwise
-        // it may cause crashes. This particular hack could be avoided by scheduling
-        // every flutter message callback on run loop, but is probably not worth the
-        // overhead.
+    // Main window is the first window that is still open
+    pub(super) fn activate_main_window(&self) -> Result<()> {
+        let first = self.window_order.borrow().first().cloned();
+        let window = first.and_then(|h| self.get_window(h));
+        match window {
+            Some(window) => window.activate(),
+            None => Ok(()),
+        }
+    }
+
+    pub(super) fn remove_window(&self, window: &Window) {
+        trace::window_closed(window.window_handle.0);
+        self.windows
+            .borrow_mut()
+            .remove(SlotKey::from_raw(window.window_handle.0));
+        self.window_order
+            .borrow_mut()
+            .retain(|h| *h != window.window_handle);
+        self.context.menu_manager.borrow().window_list_changed();
         self.context
-            .run_loop
+            .window_method_channel
             .borrow()
-            .schedule_now(move || {
-                context_copy
-                    .engine_manager
-                    .borrow_mut()
-                    .remove_engine(engine_handle)
-                    .ok_log();
-            })
-            .detach();
+            .window_closed(window.window_handle);
+        let mut engines: Vec<_> = window.engine_handle.into_iter().collect();
+        engines.extend(window.engine_views());
+        for engine in &engines {
+            self.engine_to_window.borrow_mut().remove(engine);
+        }
+
+        // Pooled engines would otherwise prevent last engine removal
+        if self.windows.borrow().is_empty() {
+            engines.append(&mut self.engine_pool.borrow_mut());
+        }
+        self.remove_engines(engines);
+    }
+
+    fn init_window(
+        &self,
+        window: WindowHandle,
+        engine: EngineHandle,
+        reply: WindowMethodCallReply,
+    ) {
+        // Window initialized again means that Dart isolate has been hot restarted
+        if let Some(window) = self.get_window(window) {
+            if window.engine_handle != Some(engine) {
+                // Embedded view; Window itself is initialized by its own engine
+                reply.send(Ok(self.on_init_engine_view(&window, engine)));
+                return;
+            }
+            if window.initialized.get() {
+                self.engine_hot_restarted(&window);
+            }
+        }
+        reply.send(Ok(self.on_init(window)));
+        self.context
+            .window_method_channel
+            .borrow()
+            .get_message_broadcaster(window, channel::win::WINDOW_MANAGER)
+            .broadcast_message(event::window::INITIALIZE, Value::Null);
+    }
 
-        self.windows.remove(&window.window_handle);
+    fn engine_hot_restarted(&self, window: &Window) {
+        let engine = match window.engine_handle {
+            Some(engine) => engine,
+            None => return,
+        };
+        // Dart side listeners and transfers are gone with the isolate
+        self.context
+            .message_bus_manager
+            .borrow()
+            .engine_removed(engine);
+        self.context
+            .transfer_manager
+            .borrow()
+            .engine_removed(engine);
+        window.hot_restarted();
+        self.context
+            .engine_manager
+            .borrow()
+            .notify_listeners(engine, EngineEvent::HotRestart);
     }
 
     fn on_init(&self, window: WindowHandle) -> Value {
-        let all_handles = self.windows.keys().map(|h| Value::I64(h.0));
-        let all_handles: Vec<Value> = all_handles.collect();
-        let window = self.windows.get(&window).unwrap();
+        let window = self.get_window(window).unwrap();
         window.initialized.replace(true);
+        self.on_init_response(&window)
+    }
+
+    fn on_init_response(&self, window: &Window) -> Value {
+        let all_handles = self.window_order.borrow();
+        let all_handles: Vec<Value> = all_handles.iter().map(|h| Value::I64(h.0)).collect();
         let parent = window
             .parent
             .map(|h| h.0.into())
@@ -166,8 +625,33 @@ impl WindowManager {
         ))
     }
 
-    fn on_create_window(&mut self, argument: Value, parent: WindowHandle) -> Value {
-        let win = self.create_window(argument, Some(parent));
+    fn on_init_engine_view(&self, window: &Window, engine: EngineHandle) -> Value {
+        let mut res = self.on_init_response(window);
+        if let Value::Map(map) = &mut res {
+            map.insert(
+                "initData".into(),
+                window.engine_view_init_data(engine).unwrap_or(Value::Null),
+            );
+        }
+        res
+    }
+
+    fn on_create_window(&self, request: WindowCreateRequest) -> Value {
+        let engine_options = EngineOptions {
+            entrypoint: request.entrypoint,
+            entrypoint_args: request.entrypoint_args,
+            ..Default::default()
+        };
+        let mut builder = WindowBuilder::new()
+            .with_init_data(request.init_data)
+            .with_parent(request.parent)
+            .with_engine_options(engine_options)
+            .with_options(request.options);
+        if let Some(content) = request.native_content {
+            builder = builder.with_native_content(content);
+        }
+        // Result of window created as modal is delivered through Window.showModal
+        let win = self.open_window(builder);
         to_value(&WindowCreateResponse { window_handle: win }).unwrap()
     }
 
@@ -176,10 +660,16 @@ impl WindowManager {
         handle: WindowHandle,
         channel_name: &str,
     ) -> Option<MessageSender<Value>> {
-        let manager = self.context.message_manager.borrow();
-        self.windows
-            .get(&handle)
-            .and_then(|w| manager.get_message_sender(w.engine_handle, channel_name))
+        self.get_window(handle)
+            .and_then(|w| w.engine_handle)
+            .map(|engine| {
+                MessageSender::new(
+                    self.context.clone(),
+                    engine,
+                    channel_name,
+                    &StandardMethodCodec,
+                )
+            })
     }
 
     fn on_method_call(
@@ -197,40 +687,41 @@ impl WindowManager {
                     .window_manager
                     .borrow()
                     .engine_to_window
+                    .borrow()
                     .get(&engine)
                     .cloned();
                 match window {
-                    Some(window) => {
-                        reply.send(Ok(context.window_manager.borrow().on_init(window)));
+                    Some(window) => context
+                        .window_manager
+                        .borrow()
+                        .init_window(window, engine, reply),
+                    // Headless engine; Reply once the engine is attached to a window
+                    None => {
                         context
-                            .window_method_channel
+                            .window_manager
                             .borrow()
-                            .get_message_broadcaster(window, channel::win::WINDOW_MANAGER)
-                            .broadcast_message(event::window::INITIALIZE, Value::Null);
+                            .pending_init
+                            .borrow_mut()
+                            .insert(engine, reply);
                     }
-                    None => reply.send(Err(MethodCallError {
-                        code: "no-window".into(),
-                        message: Some("No window associated with engine".into()),
-                        details: Value::Null,
-                    })),
                 }
             }
             method::window_manager::CREATE_WINDOW => {
-                let create_request: WindowCreateRequest = from_value(&call.arguments).unwrap();
-                reply.send(Ok(context
-                    .window_manager
-                    .borrow_mut()
-                    .on_create_window(create_request.init_data, create_request.parent)));
-            }
-            _ => {
-                let window = {
-                    context
+                let create_request: std::result::Result<WindowCreateRequest, _> =
+                    from_value(&call.arguments);
+                match create_request {
+                    Ok(request) => reply.send(Ok(context
                         .window_manager
                         .borrow()
-                        .windows
-                        .get(&call.target_window_handle)
-                        .cloned()
-                };
+                        .on_create_window(request))),
+                    Err(err) => reply.send(Err(Error::from(err).into())),
+                }
+            }
+            _ => {
+                let window = context
+                    .window_manager
+                    .borrow()
+                    .get_window(call.target_window_handle);
                 if let Some(window) = window {
                     window.on_message(&call.method, call.arguments, reply);
                 } else {
@@ -248,13 +739,17 @@ impl WindowManager {
         let codec: &'static dyn MessageCodec<Value> = &StandardMethodCodec;
         // we use binary messenger directly to be able to encode the message only once
         let message = codec.encode_message(&message);
-        for window in self.windows.values() {
+        for window in self.all_windows() {
             if !window.initialized.get() {
                 continue;
             }
             let manager = self.context.engine_manager.borrow();
-            let engine = manager.get_engine(window.engine_handle);
-            if let Some(engine) = engine {
+            // Embedded views keep track of windows too
+            let engines = window
+                .engine_handle
+                .into_iter()
+                .chain(window.engine_views());
+            for engine in engines.filter_map(|engine| manager.get_engine(engine)) {
                 engine
                     .binary_messenger()
                     .post_message(channel::DISPATCHER, &message)
@@ -263,3 +758,177 @@ impl WindowManager {
         }
     }
 }
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use std::{cell::RefCell, rc::Rc, time::Duration};
+
+    use crate::{
+        codec::Value,
+        shell::{
+            api_model::NativeWindowContent,
+            platform::recorder::{take_operations, MockTarget},
+            test_util::{create_window, mock_context, platform_window, window_engine},
+            EngineOptions, Rect, WindowBuilder,
+        },
+        Error,
+    };
+
+    #[test]
+    fn test_create_and_close_window() {
+        let context = mock_context();
+        let handle = create_window(&context, None);
+        let manager = context.window_manager.borrow();
+        assert_eq!(manager.get_all_windows(), vec![handle]);
+
+        let engine = window_engine(&context, handle);
+        assert!(context.engine_manager.borrow().get_engine(engine).is_some());
+
+        let platform_window = platform_window(&context, handle);
+        platform_window.ready_to_show().unwrap();
+        platform_window.show().unwrap();
+        assert!(platform_window.is_visible());
+
+        platform_window.close().unwrap();
+        assert!(manager.get_window(handle).is_none());
+        assert!(manager.get_all_windows().is_empty());
+
+        // Engine is removed after Dart flush reply, which never comes from
+        // mock engine, or after flush timeout
+        let run_loop = context.run_loop.borrow();
+        run_loop
+            .as_virtual()
+            .unwrap()
+            .advance(Duration::from_secs(1));
+        assert!(context.engine_manager.borrow().get_engine(engine).is_none());
+    }
+
+    #[test]
+    fn test_manage_window_from_rust() {
+        let context = mock_context();
+        let manager = context.window_manager.borrow();
+        let handle = manager.open_window(WindowBuilder::new());
+        assert_eq!(manager.get_all_windows(), vec![handle]);
+
+        let platform_window = platform_window(&context, handle);
+        platform_window.ready_to_show().unwrap();
+        manager.show_window(handle).unwrap();
+        assert!(platform_window.is_visible());
+        manager.hide_window(handle).unwrap();
+        assert!(!platform_window.is_visible());
+
+        manager.close_window(handle).unwrap();
+        assert!(manager.get_all_windows().is_empty());
+        assert!(matches!(
+            manager.show_window(handle),
+            Err(Error::InvalidWindowHandle)
+        ));
+    }
+
+    #[test]
+    fn test_native_window_without_engine() {
+        let context = mock_context();
+        let manager = context.window_manager.borrow();
+        let handle = manager.open_window(
+            WindowBuilder::new()
+                .with_native_content(NativeWindowContent {
+                    background_color: 0x80000000,
+                })
+                .with_visible(true),
+        );
+        let window = manager.get_window(handle).unwrap();
+        assert!(window.engine_handle.is_none());
+        assert!(window.platform_window.borrow().is_visible());
+        assert!(take_operations()
+            .iter()
+            .all(|o| !matches!(o.target, MockTarget::Engine(_))));
+
+        manager.close_window(handle).unwrap();
+        assert!(manager.get_all_windows().is_empty());
+    }
+
+    #[test]
+    fn test_attach_engine_view() {
+        let context = mock_context();
+        let handle = create_window(&context, None);
+        let manager = context.window_manager.borrow();
+        let window_id = manager.get_platform_window(handle).unwrap();
+        take_operations();
+
+        let frame = Rect::xywh(0.0, 0.0, 200.0, 300.0);
+        let engine = manager
+            .attach_engine_view(
+                handle,
+                Value::I64(1),
+                &EngineOptions::default(),
+                frame.clone(),
+            )
+            .unwrap();
+        let window = manager.get_window(handle).unwrap();
+        assert_ne!(window.engine_handle, Some(engine));
+        assert_eq!(window.engine_views(), vec![engine]);
+        assert_eq!(window.engine_view_init_data(engine), Some(Value::I64(1)));
+
+        let names = |target| -> Vec<_> {
+            take_operations()
+                .into_iter()
+                .filter(|o| o.target == target)
+                .map(|o| o.name)
+                .collect()
+        };
+        assert_eq!(
+            names(MockTarget::Window(window_id)),
+            vec!["add_native_view", "update_native_view"]
+        );
+
+        manager
+            .update_engine_view(handle, engine, frame, false)
+            .unwrap();
+        manager.detach_engine_view(handle, engine).unwrap();
+        assert!(window.engine_views().is_empty());
+        assert_eq!(
+            names(MockTarget::Window(window_id)),
+            vec!["update_native_view", "remove_native_view"]
+        );
+        assert!(matches!(
+            manager.detach_engine_view(handle, engine),
+            Err(Error::InvalidEngineHandle)
+        ));
+    }
+
+    #[test]
+    fn test_typed_modal_result() {
+        #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+        struct Answer {
+            confirmed: bool,
+        }
+
+        let context = mock_context();
+        let parent = create_window(&context, None);
+        let dialog = create_window(&context, Some(parent));
+        let manager = context.window_manager.borrow();
+
+        let result = Rc::new(RefCell::new(None));
+        let result_copy = result.clone();
+        manager
+            .show_modal(dialog, move |res: crate::Result<Answer>| {
+                result_copy.replace(Some(res));
+            })
+            .unwrap();
+        manager
+            .close_with_result(dialog, &Answer { confirmed: true })
+            .unwrap();
+        assert_eq!(result.take().unwrap().unwrap(), Answer { confirmed: true });
+
+        // Unexpected value is reported as decode error
+        let dialog = create_window(&context, Some(parent));
+        let result_copy = result.clone();
+        manager
+            .show_modal(dialog, move |res: crate::Result<Answer>| {
+                result_copy.replace(Some(res));
+            })
+            .unwrap();
+        manager.close_with_result(dialog, &"yes").unwrap();
+        assert!(matches!(result.take(), Some(Err(Error::Value(_)))));
+    }
+}
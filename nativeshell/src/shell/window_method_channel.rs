@@ -1,10 +1,20 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    rc::{Rc, Weak},
+};
 
+use log::error;
 use velcro::hash_map;
 
 use crate::{
-    codec::{MessageReply, MessageSender, MethodCallError, Value},
-    Result,
+    codec::{
+        value::{from_value, to_value, ArenaValue},
+        BinaryMessageReply, MessageCodec, MessageSender, MethodCallError, StandardMethodCodec,
+        Value,
+    },
+    util::trace,
+    Error, Result,
 };
 
 use super::{api_constants::channel, Context, EngineHandle, WindowHandle, WindowManager};
@@ -12,6 +22,57 @@ use super::{api_constants::channel, Context, EngineHandle, WindowHandle, WindowM
 pub struct WindowMethodChannel {
     context: Rc<Context>,
     handlers: Rc<RefCell<HashMap<String, Box<WindowMethodCallback>>>>,
+    pending_calls: Rc<PendingCalls>,
+}
+
+// Invoked with encoded result
+type PendingReply = Box<dyn FnOnce(Value)>;
+
+// Method calls sent to windows that have not replied yet; Calls are failed with
+// Error::WindowClosed when the target window closes, as its engine will never
+// reply
+#[derive(Default)]
+struct PendingCalls {
+    next_id: Cell<i64>,
+    calls: RefCell<HashMap<i64, (WindowHandle, PendingReply)>>,
+}
+
+impl PendingCalls {
+    fn track<F>(self: &Rc<Self>, target: WindowHandle, reply: F) -> impl FnOnce(Value)
+    where
+        F: FnOnce(Value) + 'static,
+    {
+        let id = self.next_id.get() + 1;
+        self.next_id.set(id);
+        self.calls
+            .borrow_mut()
+            .insert(id, (target, Box::new(reply)));
+        let pending: Weak<Self> = Rc::downgrade(self);
+        move |value| {
+            let call = pending
+                .upgrade()
+                .and_then(|pending| pending.calls.borrow_mut().remove(&id));
+            if let Some((_, reply)) = call {
+                reply(value);
+            }
+        }
+    }
+
+    fn fail_calls_to(&self, window: WindowHandle) {
+        let ids: Vec<i64> = self
+            .calls
+            .borrow()
+            .iter()
+            .filter(|(_, (target, _))| *target == window)
+            .map(|(id, _)| *id)
+            .collect();
+        for id in ids {
+            let call = self.calls.borrow_mut().remove(&id);
+            if let Some((_, reply)) = call {
+                reply(encode_result(Err(Error::WindowClosed.into())));
+            }
+        }
+    }
 }
 
 pub struct WindowMethodCall {
@@ -22,14 +83,18 @@ pub struct WindowMethodCall {
 }
 
 pub struct WindowMethodCallReply {
-    reply: MessageReply<Value>,
+    reply: BinaryMessageReply,
 }
 
 pub type WindowMethodCallResult = std::result::Result<Value, MethodCallError<Value>>;
 
 impl WindowMethodCallReply {
     pub fn send(self, result: WindowMethodCallResult) {
-        self.reply.send(encode_result(result));
+        self.send_value(encode_result(result));
+    }
+
+    fn send_value(self, value: Value) {
+        self.reply.send(&StandardMethodCodec.encode_message(&value));
     }
 }
 
@@ -38,6 +103,7 @@ pub struct WindowMethodInvoker {
     sender: MessageSender<Value>,
     channel_name: String,
     target_window_handle: WindowHandle,
+    pending_calls: Rc<PendingCalls>,
 }
 
 #[derive(Clone)]
@@ -63,10 +129,17 @@ impl WindowMessageBroadcaster {
 }
 
 impl WindowMethodInvoker {
+    // Reply is invoked with Error::WindowClosed if target window closes before
+    // replying
     pub fn call_method<F>(&self, method: &str, arguments: Value, reply: F) -> Result<()>
     where
         F: FnOnce(WindowMethodCallResult) + 'static,
     {
+        let reply = self
+            .pending_calls
+            .track(self.target_window_handle, move |value| {
+                reply(decode_result(value))
+            });
         self.sender.send_message(
             &encode_method_call(WindowMethodCall {
                 target_window_handle: self.target_window_handle,
@@ -74,9 +147,23 @@ impl WindowMethodInvoker {
                 channel: self.channel_name.clone(),
                 arguments,
             }),
-            move |value| reply(decode_result(value)),
+            reply,
         )
     }
+
+    // Typed variant of call_method; Arguments are serialized and result
+    // deserialized through Value
+    pub fn invoke<A, R, F>(&self, method: &str, arguments: &A, reply: F) -> Result<()>
+    where
+        A: serde::Serialize,
+        R: serde::de::DeserializeOwned,
+        F: FnOnce(std::result::Result<R, MethodCallError<Value>>) + 'static,
+    {
+        let arguments = to_value(arguments)?;
+        self.call_method(method, arguments, move |result| {
+            reply(result.and_then(|value| from_value(&value).map_err(|e| Error::from(e).into())))
+        })
+    }
 }
 
 type WindowMethodCallback = dyn Fn(WindowMethodCall, WindowMethodCallReply, EngineHandle);
@@ -85,24 +172,38 @@ impl WindowMethodChannel {
     pub(super) fn new(context: Rc<Context>) -> Self {
         let handlers = Rc::new(RefCell::new(HashMap::new()));
 
+        let pending_calls = Rc::new(PendingCalls::default());
+
         let context_copy = context.clone();
         let handlers_copy = handlers.clone();
+        let pending_calls_copy = pending_calls.clone();
+        // Dispatcher messages are decoded into arena, so that handler lookup
+        // and call header don't need to allocate
         context
             .message_manager
             .borrow_mut()
-            .register_message_handler(
+            .register_binary_message_handler(
                 channel::DISPATCHER, //
                 move |message, reply, engine| {
                     Self::on_message(
                         context_copy.clone(),
                         handlers_copy.clone(),
+                        &pending_calls_copy,
                         message,
                         reply,
                         engine,
                     );
                 },
             );
-        Self { context, handlers }
+        Self {
+            context,
+            handlers,
+            pending_calls,
+        }
+    }
+
+    pub(super) fn window_closed(&self, window: WindowHandle) {
+        self.pending_calls.fail_calls_to(window);
     }
 
     pub fn register_method_handler<F>(&mut self, channel: &str, callback: F)
@@ -131,6 +232,7 @@ impl WindowMethodChannel {
                 sender,
                 channel_name: channel_name.into(),
                 target_window_handle: window,
+                pending_calls: self.pending_calls.clone(),
             })
     }
 
@@ -149,28 +251,67 @@ impl WindowMethodChannel {
     fn on_message(
         context: Rc<Context>,
         handlers: Rc<RefCell<HashMap<String, Box<WindowMethodCallback>>>>,
-        message: Value,
-        reply: MessageReply<Value>,
+        pending_calls: &Rc<PendingCalls>,
+        message: &[u8],
+        reply: BinaryMessageReply,
         engine: EngineHandle,
     ) {
-        let call = decode_method_call(message);
+        let reply = WindowMethodCallReply { reply };
+        let arena = StandardMethodCodec.decode_message_in_arena(message);
+        let header = arena
+            .as_ref()
+            .and_then(|arena| decode_call_header(arena.root()));
+        let (target_window_handle, method, channel, arguments) = match header {
+            Some(header) => header,
+            None => {
+                error!("Invalid window method call");
+                reply.send(Err(MethodCallError::from_code_message(
+                    "invalid-method-call",
+                    "Invalid window method call",
+                )));
+                return;
+            }
+        };
+        let arguments = arguments.map(|a| a.to_value()).unwrap_or(Value::Null);
         let handlers = handlers.as_ref().borrow();
-        let handler = handlers.get(&call.channel);
+        let handler = handlers.get(channel);
         match handler {
             // found handler for message
             Some(handler) => {
-                handler(call, WindowMethodCallReply { reply }, engine);
+                let _span = trace::window_method_call(target_window_handle.0, channel, method);
+                let call = WindowMethodCall {
+                    target_window_handle,
+                    method: method.into(),
+                    channel: channel.into(),
+                    arguments,
+                };
+                handler(call, reply, engine);
             }
             // no handler, forward message to target window
             None => {
+                let call = WindowMethodCall {
+                    target_window_handle,
+                    method: method.into(),
+                    channel: channel.into(),
+                    arguments,
+                };
+                let target = call.target_window_handle;
                 let sender = context
                     .window_manager
                     .borrow()
-                    .message_sender_for_window(call.target_window_handle, channel::DISPATCHER);
-                if let Some(sender) = sender {
-                    sender
-                        .send_message(&encode_method_call(call), |reply_in| reply.send(reply_in))
-                        .ok();
+                    .message_sender_for_window(target, channel::DISPATCHER);
+                match sender {
+                    Some(sender) => {
+                        let reply = pending_calls.track(target, |value| reply.send_value(value));
+                        if sender
+                            .send_message(&encode_method_call(call), reply)
+                            .is_err()
+                        {
+                            // Engine is going away and will not reply
+                            pending_calls.fail_calls_to(target);
+                        }
+                    }
+                    None => reply.send(Err(Error::InvalidWindowHandle.into())),
                 }
             }
         }
@@ -182,7 +323,7 @@ impl Drop for WindowMethodChannel {
         self.context
             .message_manager
             .borrow_mut()
-            .unregister_message_handler(channel::DISPATCHER);
+            .unregister_binary_message_handler(channel::DISPATCHER);
     }
 }
 
@@ -204,31 +345,21 @@ fn encode_message(handle: &WindowHandle, channel: &str, message: &str, arguments
     })
 }
 
-fn decode_method_call(call: Value) -> WindowMethodCall {
-    if let Value::Map(mut map) = call {
-        let target_window_handle = map.remove(&"targetWindowHandle".into());
-        let method = map.remove(&"method".into());
-        let channel = map.remove(&"channel".into());
-        let arguments = map.remove(&"arguments".into());
-
-        match (target_window_handle, method, channel) {
-            (
-                Some(Value::I64(target_window_handle)),
-                Some(Value::String(method)),
-                Some(Value::String(channel)),
-            ) => WindowMethodCall {
-                target_window_handle: WindowHandle(target_window_handle),
-                method,
-                channel,
-                arguments: arguments.unwrap_or(Value::Null),
-            },
-            _ => {
-                panic!("Invalid method call")
-            }
-        }
-    } else {
-        panic!("Invalid method call");
-    }
+// Target window handle, method, channel and arguments; Strings are borrowed
+// from the message
+fn decode_call_header<'r, 'a>(
+    call: ArenaValue<'r, 'a>,
+) -> Option<(WindowHandle, &'a str, &'a str, Option<ArenaValue<'r, 'a>>)> {
+    let target_window_handle = call.get("targetWindowHandle")?.as_i64()?;
+    let method = call.get("method")?.as_str()?;
+    let channel = call.get("channel")?.as_str()?;
+    let arguments = call.get("arguments");
+    Some((
+        WindowHandle(target_window_handle),
+        method,
+        channel,
+        arguments,
+    ))
 }
 
 fn decode_result(result: Value) -> WindowMethodCallResult {
@@ -258,7 +389,7 @@ fn decode_result(result: Value) -> WindowMethodCallResult {
     }
 }
 
-fn encode_error(code: &str, message: Option<&str>, details: Value) -> Value {
+pub(super) fn encode_error(code: &str, message: Option<&str>, details: Value) -> Value {
     let message = match message {
         Some(message) => message.into(),
         None => Value::Null,
@@ -278,3 +409,98 @@ fn encode_result(result: WindowMethodCallResult) -> Value {
         Err(error) => encode_error(&error.code, error.message.as_deref(), error.details),
     }
 }
+
+#[cfg(all(test, feature = "mock"))]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use velcro::hash_map;
+
+    use crate::{
+        codec::{MessageCodec, StandardMethodCodec, Value},
+        shell::{
+            api_constants::channel,
+            test_util::{create_window, mock_context, window_engine},
+        },
+        Error,
+    };
+
+    #[test]
+    fn test_method_call_fails_when_target_window_closes() {
+        let context = mock_context();
+        create_window(&context, None);
+        let target = create_window(&context, None);
+
+        let manager = context.window_manager.borrow();
+        let invoker = context
+            .window_method_channel
+            .borrow()
+            .get_method_invoker(&manager, target, "test-channel")
+            .unwrap();
+        let result = Rc::new(RefCell::new(None));
+        let result_copy = result.clone();
+        invoker
+            .invoke("request", &1, move |res: Result<i64, _>| {
+                result_copy.replace(Some(res.map_err(|e| e.code)));
+            })
+            .unwrap();
+        assert!(result.borrow().is_none());
+
+        manager.close_window(target).unwrap();
+        assert_eq!(
+            result.take(),
+            Some(Err(Error::WindowClosed.code().to_string()))
+        );
+    }
+
+    #[test]
+    fn test_dispatch_method_call() {
+        let context = mock_context();
+        let window = create_window(&context, None);
+        context
+            .window_method_channel
+            .borrow_mut()
+            .register_method_handler("test-channel", |call, reply, _| {
+                assert_eq!(call.method, "echo");
+                reply.send(Ok(call.arguments));
+            });
+
+        let codec = &StandardMethodCodec;
+        let call = |message: Value| {
+            let result = Rc::new(RefCell::new(None));
+            let result_copy = result.clone();
+            let engine_manager = context.engine_manager.borrow();
+            let engine = engine_manager
+                .get_engine(window_engine(&context, window))
+                .unwrap();
+            assert!(engine.platform_engine.simulate_message(
+                channel::DISPATCHER,
+                &codec.encode_message(&message),
+                move |reply| {
+                    result_copy.replace(codec.decode_message(reply));
+                },
+            ));
+            result.take().unwrap()
+        };
+
+        let arguments = Value::List(vec!["Hello".into(), Value::I32List(vec![1, 2])]);
+        let result = call(Value::Map(hash_map! {
+            "targetWindowHandle".into(): window.0.into(),
+            "method".into(): "echo".into(),
+            "channel".into(): "test-channel".into(),
+            "arguments".into(): arguments.clone(),
+        }));
+        assert_eq!(result, Value::Map(hash_map! { "result".into(): arguments }));
+
+        // Malformed call gets error reply
+        let result = call(Value::Map(hash_map! {
+            "method".into(): "echo".into(),
+        }));
+        match result {
+            Value::Map(map) => {
+                assert_eq!(map.get(&"code".into()), Some(&"invalid-method-call".into()));
+            }
+            other => panic!("Unexpected result {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use std::rc::Rc;
+
+use crate::{
+    codec::{
+        value::{from_value, to_value},
+        MethodCall, MethodCallError, MethodCallReply, Value,
+    },
+    Result,
+};
+
+use super::{
+    api_constants::*,
+    api_model::{DefaultHandler, HandlerTarget},
+    platform::workspace,
+    Context, EngineHandle,
+};
+
+// Opens URLs and files with default applications, so that these basic actions
+// don't require shelling out or additional plugins
+pub struct WorkspaceManager {}
+
+impl WorkspaceManager {
+    pub(super) fn new(context: Rc<Context>) -> Self {
+        let context_copy = context.clone();
+        context
+            .message_manager
+            .borrow_mut()
+            .register_method_handler(channel::WORKSPACE, move |value, reply, engine| {
+                context_copy
+                    .workspace_manager
+                    .borrow()
+                    .on_method_call(value, reply, engine);
+            });
+        Self {}
+    }
+
+    // Opens URL in default browser (or application registered for the scheme)
+    pub fn open_url(&self, url: &str) -> Result<()> {
+        workspace::open_url(url).map_err(|e| e.into())
+    }
+
+    // Opens file in default application or directory in file manager
+    pub fn open_path(&self, path: &str) -> Result<()> {
+        workspace::open_path(path).map_err(|e| e.into())
+    }
+
+    // Shows file manager with the item selected
+    pub fn reveal_in_file_manager(&self, path: &str) -> Result<()> {
+        workspace::reveal_in_file_manager(path).map_err(|e| e.into())
+    }
+
+    // Moves items to trash, so that deletion can be undone by user. Returns
+    // locations of trashed items where reported by the system (macOS).
+    pub fn trash_items(&self, paths: &[String]) -> Result<Vec<Option<String>>> {
+        workspace::trash_items(paths).map_err(|e| e.into())
+    }
+
+    // Returns None when no application is registered for the target
+    pub fn default_handler(&self, target: &HandlerTarget) -> Result<Option<DefaultHandler>> {
+        workspace::default_handler(target).map_err(|e| e.into())
+    }
+
+    // Not possible on Windows, where this opens default apps settings instead;
+    // On macOS the user is asked to confirm the change. Linux requires the
+    // application to have installed desktop file.
+    pub fn set_as_default_handler(&self, target: &HandlerTarget) -> Result<()> {
+        workspace::set_as_default_handler(target).map_err(|e| e.into())
+    }
+
+    fn on_method_call(
+        &self,
+        call: MethodCall<Value>,
+        reply: MethodCallReply<Value>,
+        _engine: EngineHandle,
+    ) {
+        match call.method.as_str() {
+            method::workspace::GET_DEFAULT_HANDLER => {
+                let res = from_value(&call.args)
+                    .map_err(|e| e.into())
+                    .and_then(|target| self.default_handler(&target));
+                reply.send_result(res);
+                return;
+            }
+            method::workspace::SET_AS_DEFAULT_HANDLER => {
+                let res = from_value(&call.args)
+                    .map_err(|e| e.into())
+                    .and_then(|target| self.set_as_default_handler(&target));
+                reply.send_result(res);
+                return;
+            }
+            _ => {}
+        }
+        if call.method.as_str() == method::workspace::TRASH_ITEMS {
+            let paths: Vec<String> = match from_value(&call.args) {
+                Ok(paths) => paths,
+                Err(_) => return,
+            };
+            let res = self.trash_items(&paths);
+            reply.send(
+                res.map(|v| to_value(v).unwrap())
+                    .map_err(MethodCallError::from_error),
+            );
+            return;
+        }
+        let argument: String = match from_value(&call.args) {
+            Ok(argument) => argument,
+            Err(_) => return,
+        };
+        let res = match call.method.as_str() {
+            method::workspace::OPEN_URL => self.open_url(&argument),
+            method::workspace::OPEN_PATH => self.open_path(&argument),
+            method::workspace::REVEAL_IN_FILE_MANAGER => self.reveal_in_file_manager(&argument),
+            _ => return,
+        };
+        reply.send(
+            res.map(|_| Value::Null)
+                .map_err(MethodCallError::from_error),
+        );
+    }
+}
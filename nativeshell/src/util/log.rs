@@ -1,5 +1,6 @@
 use std::{fmt::Display, panic::Location};
 
+#[cfg(not(feature = "tracing"))]
 use log::{Level, Record};
 
 pub trait OkLog<T> {
@@ -14,6 +15,15 @@ where
     fn ok_log(self) -> Option<T> {
         match self {
             Ok(value) => Some(value),
+            #[cfg(feature = "tracing")]
+            Err(err) => {
+                // Recorded within current span; Forwarded to log when there is
+                // no tracing subscriber
+                let location = Location::caller();
+                tracing::error!(error = %err, %location, "Unexpected error");
+                None
+            }
+            #[cfg(not(feature = "tracing"))]
             Err(err) => {
                 let location = Location::caller();
                 log::logger().log(
@@ -3,8 +3,11 @@ mod cell;
 mod diff;
 pub mod errno;
 mod log;
+mod slot_map;
+pub(crate) mod trace;
 
 pub use self::diff::*;
 pub use self::log::*;
 pub use capsule::*;
 pub use cell::*;
+pub use slot_map::*;
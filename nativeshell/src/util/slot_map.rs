@@ -0,0 +1,193 @@
+// Key of value stored in SlotMap; Combines slot index with generation of the
+// slot, so that key of removed value never resolves to value inserted later
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SlotKey {
+    index: u32,
+    generation: u32,
+}
+
+// Generations are kept below 2^31 so that raw keys are always positive
+const MAX_GENERATION: u32 = i32::MAX as u32;
+
+impl SlotKey {
+    // Raw value is always positive (non-zero)
+    pub fn to_raw(self) -> i64 {
+        (self.generation as i64) << 32 | self.index as i64
+    }
+
+    pub fn from_raw(raw: i64) -> Self {
+        Self {
+            index: raw as u32,
+            generation: (raw >> 32) as u32,
+        }
+    }
+}
+
+struct Slot<V> {
+    generation: u32,
+    value: Option<V>,
+}
+
+// Storage addressed by stable keys. Slots of removed values are reused with
+// incremented generation.
+pub struct SlotMap<V> {
+    slots: Vec<Slot<V>>,
+    free: Vec<u32>,
+    len: usize,
+}
+
+impl<V> SlotMap<V> {
+    pub fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    pub fn insert(&mut self, value: V) -> SlotKey {
+        self.insert_with_key(|_| value)
+    }
+
+    // Allows value to store its own key
+    pub fn insert_with_key<F>(&mut self, f: F) -> SlotKey
+    where
+        F: FnOnce(SlotKey) -> V,
+    {
+        let key = self.reserve_key();
+        self.insert_reserved(key, f(key));
+        key
+    }
+
+    // Reserves slot for value that needs its key before it can be created,
+    // without keeping the map borrowed in the meanwhile. Reserved slot is not
+    // reused until the value is inserted with insert_reserved and removed.
+    pub fn reserve_key(&mut self) -> SlotKey {
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                self.slots.push(Slot {
+                    generation: 0,
+                    value: None,
+                });
+                (self.slots.len() - 1) as u32
+            }
+        };
+        let slot = &mut self.slots[index as usize];
+        slot.generation += 1;
+        SlotKey {
+            index,
+            generation: slot.generation,
+        }
+    }
+
+    pub fn insert_reserved(&mut self, key: SlotKey, value: V) {
+        let slot = &mut self.slots[key.index as usize];
+        assert!(
+            slot.generation == key.generation && slot.value.is_none(),
+            "Key was not reserved"
+        );
+        slot.value = Some(value);
+        self.len += 1;
+    }
+
+    pub fn get(&self, key: SlotKey) -> Option<&V> {
+        self.slots
+            .get(key.index as usize)
+            .filter(|slot| slot.generation == key.generation)
+            .and_then(|slot| slot.value.as_ref())
+    }
+
+    pub fn contains_key(&self, key: SlotKey) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn remove(&mut self, key: SlotKey) -> Option<V> {
+        let slot = self
+            .slots
+            .get_mut(key.index as usize)
+            .filter(|slot| slot.generation == key.generation)?;
+        let value = slot.value.take()?;
+        // Slot with exhausted generations is never reused
+        if slot.generation < MAX_GENERATION {
+            self.free.push(key.index);
+        }
+        self.len -= 1;
+        Some(value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (SlotKey, &V)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            slot.value.as_ref().map(|value| {
+                let key = SlotKey {
+                    index: index as u32,
+                    generation: slot.generation,
+                };
+                (key, value)
+            })
+        })
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = SlotKey> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+impl<V> Default for SlotMap<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SlotKey, SlotMap};
+
+    #[test]
+    fn test_stale_key() {
+        let mut map = SlotMap::new();
+        let a = map.insert("a");
+        assert_eq!(map.remove(a), Some("a"));
+        let b = map.insert("b");
+        // Slot is reused, but the old key must not resolve
+        assert_eq!(map.get(a), None);
+        assert_eq!(map.remove(a), None);
+        assert_eq!(map.get(b), Some(&"b"));
+        assert_eq!(map.len(), 1);
+    }
+
+    #[test]
+    fn test_raw_key() {
+        let mut map = SlotMap::new();
+        let key = map.insert_with_key(|key| key.to_raw());
+        let raw = *map.get(key).unwrap();
+        assert!(raw > 0);
+        assert_eq!(SlotKey::from_raw(raw), key);
+    }
+
+    #[test]
+    fn test_reserved_key() {
+        let mut map = SlotMap::new();
+        let key = map.reserve_key();
+        assert_eq!(map.get(key), None);
+        assert_eq!(map.len(), 0);
+        // Reserved slot must not be handed out again
+        let other = map.insert("other");
+        assert_ne!(other, key);
+        map.insert_reserved(key, "reserved");
+        assert_eq!(map.get(key), Some(&"reserved"));
+        assert_eq!(map.len(), 2);
+    }
+}
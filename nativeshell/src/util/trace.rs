@@ -0,0 +1,86 @@
+// Spans and events describing what the shell is doing; Recorded through the
+// `tracing` crate when the "tracing" feature is enabled, otherwise the functions
+// do nothing and guards are zero sized.
+
+#[cfg(feature = "tracing")]
+mod imp {
+    use std::fmt::Debug;
+
+    use tracing::{debug, debug_span, span::EnteredSpan};
+
+    // Span is exited when the guard is dropped
+    pub struct SpanGuard {
+        _span: EnteredSpan,
+    }
+
+    pub fn method_call(channel: &str, method: &str) -> SpanGuard {
+        SpanGuard {
+            _span: debug_span!("method_call", channel, method).entered(),
+        }
+    }
+
+    pub fn window_method_call(window: i64, channel: &str, method: &str) -> SpanGuard {
+        SpanGuard {
+            _span: debug_span!("window_method_call", window, channel, method).entered(),
+        }
+    }
+
+    pub fn window_created(window: i64, engine: i64) {
+        debug!(window, engine, "window created");
+    }
+
+    pub fn native_window_created(window: i64) {
+        debug!(window, "native window created");
+    }
+
+    pub fn window_closed(window: i64) {
+        debug!(window, "window closed");
+    }
+
+    pub fn menu_updated(menu: i64) {
+        debug!(menu, "menu updated");
+    }
+
+    pub fn menu_destroyed(menu: i64) {
+        debug!(menu, "menu destroyed");
+    }
+
+    pub fn drag_session_started(window: i64) {
+        debug!(window, "drag session started");
+    }
+
+    pub fn drag_session_ended(window: i64, effect: &dyn Debug) {
+        debug!(window, ?effect, "drag session ended");
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+mod imp {
+    use std::fmt::Debug;
+
+    pub struct SpanGuard;
+
+    pub fn method_call(_channel: &str, _method: &str) -> SpanGuard {
+        SpanGuard
+    }
+
+    pub fn window_method_call(_window: i64, _channel: &str, _method: &str) -> SpanGuard {
+        SpanGuard
+    }
+
+    pub fn window_created(_window: i64, _engine: i64) {}
+
+    pub fn native_window_created(_window: i64) {}
+
+    pub fn window_closed(_window: i64) {}
+
+    pub fn menu_updated(_menu: i64) {}
+
+    pub fn menu_destroyed(_menu: i64) {}
+
+    pub fn drag_session_started(_window: i64) {}
+
+    pub fn drag_session_ended(_window: i64, _effect: &dyn Debug) {}
+}
+
+pub(crate) use imp::*;
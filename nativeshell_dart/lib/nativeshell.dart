@@ -1,9 +1,28 @@
 library nativeshell;
 
 export 'src/accelerator.dart';
+export 'src/accessibility.dart';
 export 'src/api_model.dart';
+export 'src/application.dart';
+export 'src/display.dart';
 export 'src/drag_drop.dart';
+export 'src/event_tap.dart';
+export 'src/feedback.dart';
+export 'src/jump_list.dart';
 export 'src/menu.dart';
 export 'src/menu_bar.dart';
+export 'src/message_bus.dart';
+export 'src/permission.dart';
+export 'src/platform_view.dart';
+export 'src/power.dart';
+export 'src/system_locale.dart';
+export 'src/system_theme.dart';
+export 'src/tablet.dart';
+export 'src/touch_bar.dart';
+export 'src/transfer.dart';
+export 'src/user_activity.dart';
+export 'src/window_method_channel.dart' show WindowRequestChannel;
+export 'src/window_list.dart';
 export 'src/window_widget.dart';
 export 'src/window.dart';
+export 'src/workspace.dart';
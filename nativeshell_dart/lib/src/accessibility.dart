@@ -0,0 +1,68 @@
+import 'package:flutter/services.dart';
+
+import 'api_constants.dart';
+import 'event.dart';
+
+class AccessibilitySettings {
+  AccessibilitySettings({
+    required this.fullKeyboardAccess,
+    required this.reduceMotion,
+    required this.reduceTransparency,
+    required this.highContrast,
+    required this.cursorSize,
+  });
+
+  // Whether keyboard navigation should move focus to all controls
+  // (macOS, Windows)
+  final bool fullKeyboardAccess;
+
+  final bool reduceMotion;
+  final bool reduceTransparency;
+  final bool highContrast;
+
+  // Cursor scale relative to default cursor size
+  final double cursorSize;
+
+  static AccessibilitySettings deserialize(dynamic value) {
+    final map = value as Map;
+    return AccessibilitySettings(
+      fullKeyboardAccess: map['fullKeyboardAccess'],
+      reduceMotion: map['reduceMotion'],
+      reduceTransparency: map['reduceTransparency'],
+      highContrast: map['highContrast'],
+      cursorSize: map['cursorSize'],
+    );
+  }
+
+  @override
+  String toString() => 'AccessibilitySettings('
+      'fullKeyboardAccess: $fullKeyboardAccess, reduceMotion: $reduceMotion, '
+      'reduceTransparency: $reduceTransparency, highContrast: $highContrast, '
+      'cursorSize: $cursorSize)';
+}
+
+// System accessibility preferences. Settings are not reported by Flutter
+// through MediaQuery on all platforms.
+class Accessibility {
+  Accessibility._() {
+    _channel.setMethodCallHandler(_onMethodCall);
+  }
+
+  static final instance = Accessibility._();
+
+  // Fired when any of the settings change
+  final settingsChanged = Event<AccessibilitySettings>();
+
+  Future<AccessibilitySettings> getSettings() async {
+    return AccessibilitySettings.deserialize(
+        await _channel.invokeMethod(Methods.accessibilityGetSettings));
+  }
+
+  Future<dynamic> _onMethodCall(MethodCall call) async {
+    if (call.method == Methods.accessibilitySettingsChanged) {
+      settingsChanged.fire(AccessibilitySettings.deserialize(call.arguments));
+    }
+  }
+
+  final _channel = MethodChannel(Channels.accessibility);
+}
@@ -5,8 +5,25 @@ class Channels {
   static final windowManager = '.window.window-manager';
   static final dropTarget = '.window.drop-target';
   static final dragSource = '.window.drag-source';
+  static final touchBar = '.window.touch-bar';
+  static final tablet = '.window.tablet';
+  static final quit = '.window.quit';
 
   static final menuManager = 'nativeshell/menu-manager';
+  static final eventTap = 'nativeshell/event-tap';
+  static final accessibility = 'nativeshell/accessibility';
+  static final systemTheme = 'nativeshell/system-theme';
+  static final display = 'nativeshell/display';
+  static final power = 'nativeshell/power';
+  static final windowList = 'nativeshell/window-list';
+  static final locale = 'nativeshell/locale';
+  static final userActivity = 'nativeshell/user-activity';
+  static final messageBus = 'nativeshell/message-bus';
+  static final transfer = 'nativeshell/transfer';
+  static final application = 'nativeshell/application';
+  static final workspace = 'nativeshell/workspace';
+  static final feedback = 'nativeshell/feedback';
+  static final permission = 'nativeshell/permission';
 }
 
 class Events {
@@ -14,6 +31,12 @@ class Events {
   static final windowVisibilityChanged = 'event:Window.visibilityChanged';
   static final windowCloseRequest = 'event:Window.closeRequest';
   static final windowClose = 'event:Window.close';
+  static final windowPointerEntered = 'event:Window.pointerEntered';
+  static final windowPointerExited = 'event:Window.pointerExited';
+  static final windowRepresentedFileDragged =
+      'event:Window.representedFileDragged';
+  static final windowActiveSpaceChanged = 'event:Window.activeSpaceChanged';
+  static final windowShowFailed = 'event:Window.showFailed';
 }
 
 const currentApiVersion = 1;
@@ -26,24 +49,49 @@ class Methods {
 
   // Window
   static final windowShow = 'Window.show';
+  static final windowShowPopover = 'Window.showPopover';
   static final windowShowModal = 'Window.showModal';
   static final windowReadyToShow = 'Window.readyToShow';
   static final windowHide = 'Window.hide';
+  static final windowActivate = 'Window.activate';
   static final windowClose = 'Window.close';
   static final windowCloseWithResult = 'Window.closeWithResult';
 
   static final windowSetGeometry = 'Window.setGeometry';
   static final windowGetGeometry = 'Window.getGeometry';
   static final windowSupportedGeometry = 'Window.supportedGeometry';
+  static final windowUpdateGeometry = 'Window.updateGeometry';
 
   static final windowSetStyle = 'Window.setStyle';
   static final windowSetTitle = 'Window.setTitle';
+  static final windowSetSubtitle = 'Window.setSubtitle';
+  static final windowSetRepresentedFile = 'Window.setRepresentedFile';
+  static final windowSetDocumentEdited = 'Window.setDocumentEdited';
+  static final windowSetAppearance = 'Window.setAppearance';
+  static final windowSetBackdrop = 'Window.setBackdrop';
+  static final windowSetWindowType = 'Window.setWindowType';
+  static final windowSetDropFilter = 'Window.setDropFilter';
   static final windowPerformWindowDrag = 'Window.performWindowDrag';
 
   static final windowShowPopupMenu = 'Window.showPopupMenu';
   static final windowHidePopupMenu = 'Window.hidePopupMenu';
+  static final windowShowSharePicker = 'Window.showSharePicker';
+  static final windowShowQuickLook = 'Window.showQuickLook';
+  static final windowSetQuickLookIndex = 'Window.setQuickLookIndex';
+  static final windowHideQuickLook = 'Window.hideQuickLook';
   static final windowShowSystemMenu = 'Window.showSystemMenu';
+  static final windowSetMaximizeButtonRect = 'Window.setMaximizeButtonRect';
+  static final windowSetAppUserModelId = 'Window.setAppUserModelId';
+  static final windowIsOnActiveSpace = 'Window.isOnActiveSpace';
+  static final windowMoveToActiveSpace = 'Window.moveToActiveSpace';
   static final windowSetWindowMenu = 'Window.setWindowMenu';
+  static final windowSetTouchBar = 'Window.setTouchBar';
+  static final windowCreatePlatformView = 'Window.createPlatformView';
+  static final windowUpdatePlatformView = 'Window.updatePlatformView';
+  static final windowDisposePlatformView = 'Window.disposePlatformView';
+  static final windowAttachEngineView = 'Window.attachEngineView';
+  static final windowUpdateEngineView = 'Window.updateEngineView';
+  static final windowDetachEngineView = 'Window.detachEngineView';
 
   // Drop Target
   static final dropTargetDraggingUpdated = 'DropTarget.draggingUpdated';
@@ -54,11 +102,122 @@ class Methods {
   static final dragSourceBeginDragSession = 'DragSource.beginDragSession';
   static final dragSourceDragSessionEnded = 'DragSource.dragSessionEnded';
 
+  // Touch Bar
+  static final touchBarOnAction = 'TouchBar.onAction';
+
+  // Tablet
+  static final tabletOnEvent = 'Tablet.onEvent';
+
+  // Quit
+  static final quitConfirm = 'Quit.confirm';
+
+  // Event Tap
+  static final eventTapGetPermission = 'EventTap.getPermission';
+  static final eventTapRequestPermission = 'EventTap.requestPermission';
+  static final eventTapStart = 'EventTap.start';
+  static final eventTapStop = 'EventTap.stop';
+  static final eventTapOnEvent = 'EventTap.onEvent';
+
+  // Accessibility
+  static final accessibilityGetSettings = 'Accessibility.getSettings';
+  static final accessibilitySettingsChanged = 'Accessibility.settingsChanged';
+
+  // SystemTheme
+  static final systemThemeGetSettings = 'SystemTheme.getSettings';
+  static final systemThemeSettingsChanged = 'SystemTheme.settingsChanged';
+
+  // Display
+  static final displayGetDisplays = 'Display.getDisplays';
+  static final displayDisplaysChanged = 'Display.displaysChanged';
+
+  // Power
+  static final powerGetStatus = 'Power.getStatus';
+  static final powerStatusChanged = 'Power.statusChanged';
+
+  // WindowList
+  static final windowListGetWindows = 'WindowList.getWindows';
+
+  // Locale
+  static final localeGetSettings = 'Locale.getSettings';
+  static final localeSettingsChanged = 'Locale.settingsChanged';
+
+  // UserActivity
+  static final userActivityGetIdleTime = 'UserActivity.getIdleTime';
+
+  // Application
+  static final applicationOnSecondInstance = 'Application.onSecondInstance';
+  static final applicationReady = 'Application.ready';
+  static final applicationOpenUrls = 'Application.openUrls';
+  static final applicationOpenFiles = 'Application.openFiles';
+  static final applicationOpenJumpListItem = 'Application.openJumpListItem';
+  static final applicationShouldTerminate = 'Application.shouldTerminate';
+  static final applicationSetPreventSuddenTermination =
+      'Application.setPreventSuddenTermination';
+  static final applicationGetLaunchAtLogin = 'Application.getLaunchAtLogin';
+  static final applicationSetLaunchAtLogin = 'Application.setLaunchAtLogin';
+  static final applicationStartedHidden = 'Application.startedHidden';
+  static final applicationNotifyStartupComplete =
+      'Application.notifyStartupComplete';
+  static final applicationSetJumpList = 'Application.setJumpList';
+  static final applicationSetAppUserModelId =
+      'Application.setAppUserModelId';
+  static final applicationSetUserActivity = 'Application.setUserActivity';
+  static final applicationContinueUserActivity =
+      'Application.continueUserActivity';
+  static final applicationGetActivationPolicy =
+      'Application.getActivationPolicy';
+  static final applicationSetActivationPolicy =
+      'Application.setActivationPolicy';
+  static final applicationHide = 'Application.hide';
+  static final applicationUnhide = 'Application.unhide';
+  static final applicationHideOtherApplications =
+      'Application.hideOtherApplications';
+  static final applicationOnHide = 'Application.onHide';
+  static final applicationOnUnhide = 'Application.onUnhide';
+  static final applicationRequestQuit = 'Application.requestQuit';
+
+  // Workspace
+  static final workspaceOpenUrl = 'Workspace.openUrl';
+  static final workspaceOpenPath = 'Workspace.openPath';
+  static final workspaceRevealInFileManager = 'Workspace.revealInFileManager';
+  static final workspaceTrashItems = 'Workspace.trashItems';
+  static final workspaceGetDefaultHandler = 'Workspace.getDefaultHandler';
+  static final workspaceSetAsDefaultHandler = 'Workspace.setAsDefaultHandler';
+
+  // Feedback
+  static final feedbackBeep = 'Feedback.beep';
+  static final feedbackPlaySystemSound = 'Feedback.playSystemSound';
+  static final feedbackPerformHapticFeedback = 'Feedback.performHapticFeedback';
+
+  // Permission
+  static final permissionGetStatus = 'Permission.getStatus';
+  static final permissionRequest = 'Permission.request';
+
+  // MessageBus
+  static final messageBusSubscribe = 'MessageBus.subscribe';
+  static final messageBusUnsubscribe = 'MessageBus.unsubscribe';
+  static final messageBusPublish = 'MessageBus.publish';
+  static final messageBusOnMessage = 'MessageBus.onMessage';
+
+  // Transfer
+  static final transferBegin = 'Transfer.begin';
+  static final transferChunk = 'Transfer.chunk';
+  static final transferEnd = 'Transfer.end';
+  static final transferCancel = 'Transfer.cancel';
+  static final transferOnBegin = 'Transfer.onBegin';
+  static final transferOnChunk = 'Transfer.onChunk';
+  static final transferOnEnd = 'Transfer.onEnd';
+  static final transferOnCancel = 'Transfer.onCancel';
+
   // Menu
   static final menuCreateOrUpdate = 'Menu.createOrUpdate';
   static final menuDestroy = 'Menu.destroy';
   static final menuOnAction = 'Menu.onAction';
   static final menuSetAppMenu = 'Menu.setAppMenu';
+  static final menuSelectNextWindow = 'Menu.selectNextWindow';
+  static final menuSelectPreviousWindow = 'Menu.selectPreviousWindow';
+  static final menuBringAllToFront = 'Menu.bringAllToFront';
+  static final menuOnDiagnostic = 'Menu.onDiagnostic';
 
   // Menubar
   static final menubarMoveToPreviousMenu = 'Menubar.moveToPreviousMenu';
@@ -68,4 +227,6 @@ class Methods {
 class Keys {
   static final dragDataFiles = 'drag-data:internal:files';
   static final dragDataURLs = 'drag-data:internal:urls';
+  static final dragDataText = 'drag-data:internal:text';
+  static final dragDataHtml = 'drag-data:internal:html';
 }
@@ -183,6 +183,57 @@ class GeometryFlags {
   }
 }
 
+// Result of LocalWindow.updateGeometry
+class GeometryUpdate {
+  GeometryUpdate({
+    required this.applied,
+    required this.geometry,
+  });
+
+  // Fields of the request that were applied
+  final GeometryFlags applied;
+  final Geometry geometry;
+
+  static GeometryUpdate deserialize(dynamic value) {
+    final map = value as Map;
+    return GeometryUpdate(
+        applied: GeometryFlags.deserialize(map['applied']),
+        geometry: Geometry.deserialize(map['geometry']));
+  }
+
+  @override
+  String toString() => 'GeometryUpdate(applied: $applied, geometry: $geometry)';
+}
+
+enum WindowAppearance {
+  // Follows system setting
+  system,
+  light,
+  dark,
+}
+
+enum WindowBackdrop {
+  none,
+  mica,
+  acrylic,
+}
+
+enum WindowType {
+  normal,
+  dialog,
+  splash,
+  dock,
+  tooltip,
+  notification,
+}
+
+enum PopoverEdge {
+  // Menu bar on macOS, top panel on Linux
+  below,
+  // Taskbar at bottom of screen on Windows
+  above,
+}
+
 enum WindowFrame {
   regular,
   noTitle,
@@ -197,6 +248,7 @@ class WindowStyle {
     this.canMinimize = true,
     this.canMaximize = true,
     this.canFullScreen = true,
+    this.headerBar = false,
   });
 
   final WindowFrame frame;
@@ -207,6 +259,10 @@ class WindowStyle {
   final bool canMaximize; // ignored on mac
   final bool canFullScreen;
 
+  // Linux only; Regular frame uses client-side GtkHeaderBar (with title and
+  // subtitle) instead of window manager decorations
+  final bool headerBar;
+
   dynamic serialize() => {
         'frame': enumToString(frame),
         'canResize': canResize,
@@ -214,6 +270,7 @@ class WindowStyle {
         'canMinimize': canMinimize,
         'canMaximize': canMaximize,
         'canFullScreen': canFullScreen,
+        'headerBar': headerBar,
       };
 
   static WindowStyle deserialize(dynamic value) {
@@ -225,7 +282,8 @@ class WindowStyle {
         canClose: map['canClose'],
         canMinimize: map['canMinimize'],
         canMaximize: map['canMaximize'],
-        canFullScreen: map['canFullScreen']);
+        canFullScreen: map['canFullScreen'],
+        headerBar: map['headerBar'] ?? false);
   }
 
   @override
@@ -234,6 +292,55 @@ class WindowStyle {
   }
 }
 
+// Initial window configuration, applied before the window is first shown
+class WindowOptions {
+  WindowOptions({
+    this.title,
+    this.style,
+    this.geometry,
+    this.geometryPreference = GeometryPreference.preferContent,
+    this.visible = false,
+    this.modal = false,
+  });
+
+  final String? title;
+  final WindowStyle? style;
+  final Geometry? geometry;
+  final GeometryPreference geometryPreference;
+
+  // Show window as soon as first frame is rendered
+  final bool visible;
+
+  // Show window as modal for its parent as soon as first frame is rendered;
+  // Result is available through Window.showModal()
+  final bool modal;
+
+  dynamic serialize() => {
+        'title': title,
+        'style': style?.serialize(),
+        'geometry': geometry != null
+            ? {
+                'geometry': geometry!.serialize(),
+                'preference': enumToString(geometryPreference),
+              }
+            : null,
+        'visible': visible,
+        'modal': modal,
+      };
+}
+
+// Content of window without Flutter engine (i.e. overlay or screenshot flash)
+class NativeWindowContent {
+  NativeWindowContent({required this.backgroundColor});
+
+  // Translucent background requires frameless window
+  final Color backgroundColor;
+
+  dynamic serialize() => {
+        'backgroundColor': backgroundColor.value,
+      };
+}
+
 class PopupMenuRequest {
   PopupMenuRequest({
     required this.handle,
@@ -241,6 +348,7 @@ class PopupMenuRequest {
     this.trackingRect,
     this.itemRect,
     this.preselectFirst = false,
+    this.keyboardAnchor,
   });
 
   final MenuHandle handle;
@@ -249,12 +357,17 @@ class PopupMenuRequest {
   final Rect? itemRect;
   final bool preselectFirst;
 
+  // Rect of focused element for menu opened from keyboard; Menu is placed
+  // below it (instead of at position) with first item selected
+  final Rect? keyboardAnchor;
+
   dynamic serialize() => {
         'handle': handle.value,
         'position': position.serialize(),
         'trackingRect': trackingRect?.serialize(),
         'itemRect': itemRect?.serialize(),
         'preselectFirst': preselectFirst,
+        'keyboardAnchor': keyboardAnchor?.serialize(),
       };
 }
 
@@ -289,3 +402,21 @@ class HidePopupMenuRequest {
         'handle': handle.value,
       };
 }
+
+class QuickLookItem {
+  QuickLookItem({
+    required this.path,
+    this.rect,
+  });
+
+  final String path;
+
+  // Position of item in window coordinates; Quick Look panel zooms from
+  // this rect
+  final Rect? rect;
+
+  dynamic serialize() => {
+        'path': path,
+        'rect': rect?.serialize(),
+      };
+}
@@ -0,0 +1,261 @@
+import 'package:flutter/services.dart';
+
+import 'dart:async';
+
+import 'api_constants.dart';
+import 'event.dart';
+import 'jump_list.dart';
+import 'util.dart';
+
+// Launch of the application that was redirected to running instance
+class InstanceActivation {
+  InstanceActivation({
+    required this.arguments,
+    required this.workingDirectory,
+  });
+
+  // Command line arguments without executable path
+  final List<String> arguments;
+  final String workingDirectory;
+
+  static InstanceActivation deserialize(dynamic value) {
+    final map = value as Map;
+    return InstanceActivation(
+      arguments: (map['arguments'] as List).cast<String>(),
+      workingDirectory: map['workingDirectory'],
+    );
+  }
+
+  @override
+  String toString() => 'InstanceActivation(arguments: $arguments, '
+      'workingDirectory: $workingDirectory)';
+}
+
+// Activity that can be continued on user's other devices (Handoff, macOS only)
+class UserActivity {
+  UserActivity({
+    required this.activityType,
+    this.title,
+    this.payload,
+    this.webpageUrl,
+  });
+
+  // Must be listed in NSUserActivityTypes of Info.plist
+  final String activityType;
+  final String? title;
+
+  // Any value supported by StandardMessageCodec; Only available when activity
+  // is continued by this application
+  final dynamic payload;
+
+  // Opened in browser when continued on device without the application
+  final String? webpageUrl;
+
+  dynamic serialize() => {
+        'activityType': activityType,
+        'title': title,
+        'payload': payload,
+        'webpageUrl': webpageUrl,
+      };
+
+  static UserActivity deserialize(dynamic value) {
+    final map = value as Map;
+    return UserActivity(
+      activityType: map['activityType'],
+      title: map['title'],
+      payload: map['payload'],
+      webpageUrl: map['webpageUrl'],
+    );
+  }
+
+  @override
+  String toString() => 'UserActivity(activityType: $activityType, '
+      'title: $title, webpageUrl: $webpageUrl)';
+}
+
+enum TerminationReason {
+  // Application was asked to quit (i.e. from application menu or dock)
+  quit,
+  // User is logging out or the system is shutting down
+  sessionEnd,
+}
+
+enum ActivationPolicy {
+  // Application has Dock icon (macOS) and windows are shown in taskbar
+  regular,
+  // No Dock icon or menu bar, windows are not shown in taskbar; Windows can
+  // still be activated (i.e. tray utilities)
+  accessory,
+  // Same as accessory, but application can not be activated (macOS only)
+  prohibited,
+}
+
+// Returns false to cancel termination
+typedef ShouldTerminateHandler = FutureOr<bool> Function(
+    TerminationReason reason);
+
+// Application wide events
+class Application {
+  Application._() {
+    _channel.setMethodCallHandler(_onMethodCall);
+  }
+
+  static final instance = Application._();
+
+  // Fired when the application was launched again while single instance is
+  // enforced (see ContextOptions.single_instance); Main window is activated
+  // before the event is fired.
+  final secondInstanceEvent = Event<InstanceActivation>();
+
+  // Fired with URLs (using one of application URL schemes) that the application
+  // was asked to open; Only fired after calling ready().
+  final openUrlsEvent = Event<List<String>>();
+
+  // Fired with paths of documents that the application was asked to open (i.e.
+  // associated files opened from file manager); Only fired after calling ready().
+  final openFilesEvent = Event<List<String>>();
+
+  // Fired when the application was launched (or activated) from jump list
+  // item; Only fired after calling ready().
+  final openJumpListItemEvent = Event<JumpListActivation>();
+
+  // Fired with activity continued from user's other device (macOS only); Only
+  // fired after calling ready().
+  final continueUserActivityEvent = Event<UserActivity>();
+
+  // Fired after application was hidden (i.e. from application menu or using
+  // Cmd+H) or unhidden (macOS only).
+  final hideEvent = VoidEvent();
+  final unhideEvent = VoidEvent();
+
+  // Invoked (in ready isolates) when application or user session is about to
+  // end; Termination proceeds after all isolates replied. On Windows the reply
+  // can not cancel logout or shutdown, use setPreventSuddenTermination instead.
+  ShouldTerminateHandler? shouldTerminateHandler;
+
+  // While set, application can not be terminated without being asked
+  // (macOS) and logout or shutdown is blocked (Windows, Linux). Reason is
+  // shown to user by the system.
+  Future<void> setPreventSuddenTermination(bool prevent, {String? reason}) {
+    return _channel.invokeMethod(Methods.applicationSetPreventSuddenTermination,
+        {'prevent': prevent, 'reason': reason});
+  }
+
+  // Whether the application is registered to start when user logs in
+  Future<bool> getLaunchAtLogin() async {
+    return await _channel.invokeMethod(Methods.applicationGetLaunchAtLogin);
+  }
+
+  // Registers the application to start when user logs in (login item on macOS
+  // 13+, Run registry key on Windows, XDG autostart entry on Linux). Application
+  // started hidden should not show any window (see startedHidden).
+  Future<void> setLaunchAtLogin(bool enabled, {bool startHidden = false}) {
+    return _channel.invokeMethod(Methods.applicationSetLaunchAtLogin,
+        {'enabled': enabled, 'startHidden': startHidden});
+  }
+
+  // Whether the application was started at login with startHidden flag; Tray
+  // utilities should not show main window in that case.
+  Future<bool> startedHidden() async {
+    return await _channel.invokeMethod(Methods.applicationStartedHidden);
+  }
+
+  // Ends startup notification (Linux launch feedback) when application does
+  // not show any window after launch; Otherwise it ends when first window maps.
+  Future<void> notifyStartupComplete() {
+    return _channel.invokeMethod(Methods.applicationNotifyStartupComplete);
+  }
+
+  // Replaces taskbar jump list (Windows only)
+  Future<void> setJumpList(JumpList jumpList) {
+    return _channel.invokeMethod(
+        Methods.applicationSetJumpList, jumpList.serialize());
+  }
+
+  // Sets explicit AppUserModelID of the process, which determines taskbar
+  // grouping and notification identity (Windows only). Should be called before
+  // any window is shown.
+  Future<void> setAppUserModelId(String id) {
+    return _channel.invokeMethod(Methods.applicationSetAppUserModelId, id);
+  }
+
+  // Publishes activity that can be continued on user's other devices; Null
+  // stops publishing current activity (macOS only).
+  Future<void> setUserActivity(UserActivity? activity) {
+    return _channel.invokeMethod(
+        Methods.applicationSetUserActivity, activity?.serialize());
+  }
+
+  Future<ActivationPolicy> getActivationPolicy() async {
+    final policy =
+        await _channel.invokeMethod(Methods.applicationGetActivationPolicy);
+    return enumFromString(ActivationPolicy.values, policy,
+        ActivationPolicy.regular);
+  }
+
+  // Hides or shows Dock icon (macOS) or taskbar entries of all windows
+  // (Windows, Linux). Tray only applications can switch to regular policy
+  // while a window is open and back to accessory when it is closed.
+  Future<void> setActivationPolicy(ActivationPolicy policy) {
+    return _channel.invokeMethod(
+        Methods.applicationSetActivationPolicy, enumToString(policy));
+  }
+
+  // Hides all application windows and activates next application; Intended
+  // for 'Hide' item of application menu (macOS only).
+  Future<void> hide() {
+    return _channel.invokeMethod(Methods.applicationHide);
+  }
+
+  Future<void> unhide() {
+    return _channel.invokeMethod(Methods.applicationUnhide);
+  }
+
+  // Intended for 'Hide Others' item of application menu (macOS only)
+  Future<void> hideOtherApplications() {
+    return _channel.invokeMethod(Methods.applicationHideOtherApplications);
+  }
+
+  // Asks every open window (one at a time, see WindowState.confirmQuit) and
+  // then shouldTerminateHandler of each isolate whether application may quit;
+  // Quits if all agree. Returns false if quit was cancelled.
+  Future<bool> requestQuit() async {
+    return await _channel.invokeMethod(Methods.applicationRequestQuit);
+  }
+
+  // Signals that this isolate handles open requests. Requests received before
+  // (including the one that launched the application) are queued until first
+  // isolate is ready; Usually only called from main window.
+  Future<void> ready() {
+    return _channel.invokeMethod(Methods.applicationReady);
+  }
+
+  Future<dynamic> _onMethodCall(MethodCall call) async {
+    if (call.method == Methods.applicationOnSecondInstance) {
+      secondInstanceEvent.fire(InstanceActivation.deserialize(call.arguments));
+    } else if (call.method == Methods.applicationOpenUrls) {
+      openUrlsEvent.fire((call.arguments as List).cast<String>());
+    } else if (call.method == Methods.applicationOpenFiles) {
+      openFilesEvent.fire((call.arguments as List).cast<String>());
+    } else if (call.method == Methods.applicationOpenJumpListItem) {
+      openJumpListItemEvent
+          .fire(JumpListActivation.deserialize(call.arguments));
+    } else if (call.method == Methods.applicationContinueUserActivity) {
+      continueUserActivityEvent.fire(UserActivity.deserialize(call.arguments));
+    } else if (call.method == Methods.applicationOnHide) {
+      hideEvent.fire();
+    } else if (call.method == Methods.applicationOnUnhide) {
+      unhideEvent.fire();
+    } else if (call.method == Methods.applicationShouldTerminate) {
+      final handler = shouldTerminateHandler;
+      if (handler == null) {
+        return true;
+      }
+      final reason = enumFromString(TerminationReason.values,
+          call.arguments as String, TerminationReason.quit);
+      return await handler(reason);
+    }
+  }
+
+  final _channel = MethodChannel(Channels.application);
+}
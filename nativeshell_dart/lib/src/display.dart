@@ -0,0 +1,91 @@
+import 'dart:typed_data';
+import 'dart:ui';
+
+import 'package:flutter/services.dart';
+
+import 'api_constants.dart';
+import 'api_model.dart';
+import 'event.dart';
+
+class DisplayInfo {
+  DisplayInfo({
+    required this.index,
+    required this.frame,
+    required this.scaleFactor,
+    this.colorSpace,
+    this.iccProfile,
+    required this.maxEdrHeadroom,
+    required this.currentEdrHeadroom,
+    required this.hdr,
+  });
+
+  // Display index for screen capture; 0 is the main (primary) display
+  final int index;
+
+  // Logical coordinates, top left origin
+  final Rect frame;
+  final double scaleFactor;
+
+  // Localized name of display color space (i.e. 'Display P3'), if known
+  final String? colorSpace;
+  final Uint8List? iccProfile;
+
+  // Largest color component value display can show, where 1.0 is SDR white;
+  // Greater than 1.0 for HDR (EDR) capable displays
+  final double maxEdrHeadroom;
+
+  // Currently available headroom; On macOS this changes with display
+  // brightness without change notification
+  final double currentEdrHeadroom;
+
+  final bool hdr;
+
+  static DisplayInfo deserialize(dynamic value) {
+    final map = value as Map;
+    return DisplayInfo(
+      index: map['index'],
+      frame: RectExt.deserialize(map['frame']),
+      scaleFactor: map['scaleFactor'],
+      colorSpace: map['colorSpace'],
+      iccProfile: map['iccProfile'],
+      maxEdrHeadroom: map['maxEdrHeadroom'],
+      currentEdrHeadroom: map['currentEdrHeadroom'],
+      hdr: map['hdr'],
+    );
+  }
+
+  @override
+  String toString() => 'DisplayInfo($index, frame: $frame, '
+      'scaleFactor: $scaleFactor, colorSpace: $colorSpace, '
+      'maxEdrHeadroom: $maxEdrHeadroom, hdr: $hdr)';
+}
+
+// Display geometry and color capabilities, so that color critical
+// applications can adapt rendering
+class Displays {
+  Displays._() {
+    _channel.setMethodCallHandler(_onMethodCall);
+  }
+
+  static final instance = Displays._();
+
+  // Fired when displays are added, removed or their configuration (including
+  // color space) changes
+  final displaysChanged = Event<List<DisplayInfo>>();
+
+  Future<List<DisplayInfo>> getDisplays() async {
+    return _deserialize(
+        await _channel.invokeMethod(Methods.displayGetDisplays));
+  }
+
+  static List<DisplayInfo> _deserialize(dynamic value) =>
+      (value as List).map(DisplayInfo.deserialize).toList();
+
+  Future<dynamic> _onMethodCall(MethodCall call) async {
+    if (call.method == Methods.displayDisplaysChanged) {
+      displaysChanged.fire(_deserialize(call.arguments));
+    }
+  }
+
+  final _channel = MethodChannel(Channels.display);
+}
@@ -76,6 +76,12 @@ class DragData {
   static final uris =
       DragDataKey<List<Uri>>(Keys.dragDataURLs, _encodeURLs, _decodeURLs);
 
+  // Plain text; Other applications receive it as regular string data
+  static final text = DragDataKey<String>(Keys.dragDataText);
+
+  // Rich text as HTML fragment; Usually provided alongside plain text
+  static final html = DragDataKey<String>(Keys.dragDataHtml);
+
   // Usage
   //
   // final data = DragData([
@@ -117,6 +123,36 @@ class DragData {
   final Map<String, dynamic> _properties;
 }
 
+// File types accepted by drop targets in window; Drags with other files are
+// rejected natively and never reach drop regions.
+class DropFilter {
+  DropFilter({
+    this.extensions = const [],
+    this.mimeTypes = const [],
+    this.utis = const [],
+    this.allowNonFileData = false,
+  });
+
+  // Case insensitive, without leading dot
+  final List<String> extensions;
+
+  // i.e. 'image/png' or 'image/*'; Linux and Windows only
+  final List<String> mimeTypes;
+
+  // Uniform type identifiers, i.e. 'public.image'; macOS only
+  final List<String> utis;
+
+  // Whether drags without files (text, URLs) are accepted
+  final bool allowNonFileData;
+
+  Map serialize() => {
+        'extensions': extensions,
+        'mimeTypes': mimeTypes,
+        'utis': utis,
+        'allowNonFileData': allowNonFileData,
+      };
+}
+
 class DropEvent {
   DropEvent({
     required this.info,
@@ -0,0 +1,107 @@
+import 'dart:ui';
+
+import 'package:flutter/services.dart';
+
+import 'api_constants.dart';
+import 'api_model.dart';
+import 'event.dart';
+import 'util.dart';
+
+enum EventTapPermission {
+  granted,
+  denied,
+}
+
+enum EventTapEventType {
+  keyDown,
+  keyUp,
+  mouseDown,
+  mouseUp,
+  mouseMove,
+  scrollWheel,
+}
+
+class EventTapEvent {
+  EventTapEvent({
+    required this.type,
+    this.keyCode,
+    this.button,
+    this.position,
+    this.scrollDelta,
+  });
+
+  final EventTapEventType type;
+
+  // Platform specific key code (virtual key code on Windows, kVK on macOS)
+  final int? keyCode;
+
+  // 0 - primary, 1 - secondary, 2 - middle
+  final int? button;
+
+  // Screen position in logical coordinates
+  final Offset? position;
+
+  final Offset? scrollDelta;
+
+  static EventTapEvent deserialize(dynamic value) {
+    final map = value as Map;
+    return EventTapEvent(
+      type: enumFromString(EventTapEventType.values, map['eventType'],
+          EventTapEventType.mouseMove),
+      keyCode: map['keyCode'],
+      button: map['button'],
+      position: OffsetExt.maybeDeserialize(map['position']),
+      scrollDelta: OffsetExt.maybeDeserialize(map['scrollDelta']),
+    );
+  }
+
+  @override
+  String toString() => 'EventTapEvent($type, keyCode: $keyCode, '
+      'button: $button, position: $position)';
+}
+
+// Delivers keyboard and mouse events that happen outside of the application.
+// Nothing is monitored until start() is called. On macOS key events require
+// the application to be trusted for accessibility (see requestPermission).
+class EventTap {
+  EventTap._() {
+    _channel.setMethodCallHandler(_onMethodCall);
+  }
+
+  static final instance = EventTap._();
+
+  final eventReceived = Event<EventTapEvent>();
+
+  Future<EventTapPermission> getPermission() async {
+    return _permissionFromString(
+        await _channel.invokeMethod(Methods.eventTapGetPermission));
+  }
+
+  Future<EventTapPermission> requestPermission() async {
+    return _permissionFromString(
+        await _channel.invokeMethod(Methods.eventTapRequestPermission));
+  }
+
+  Future<void> start({bool keyEvents = true, bool mouseEvents = true}) {
+    return _channel.invokeMethod(Methods.eventTapStart, {
+      'keyEvents': keyEvents,
+      'mouseEvents': mouseEvents,
+    });
+  }
+
+  Future<void> stop() {
+    return _channel.invokeMethod(Methods.eventTapStop);
+  }
+
+  EventTapPermission _permissionFromString(dynamic value) =>
+      enumFromString(EventTapPermission.values, value as String,
+          EventTapPermission.denied);
+
+  Future<dynamic> _onMethodCall(MethodCall call) async {
+    if (call.method == Methods.eventTapOnEvent) {
+      eventReceived.fire(EventTapEvent.deserialize(call.arguments));
+    }
+  }
+
+  final _channel = MethodChannel(Channels.eventTap);
+}
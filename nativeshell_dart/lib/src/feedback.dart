@@ -0,0 +1,39 @@
+import 'package:flutter/services.dart';
+
+import 'api_constants.dart';
+import 'util.dart';
+
+// Force touch trackpad feedback (macOS only)
+enum HapticFeedbackPattern {
+  generic,
+  // Dragged object snapped to guide or another object
+  alignment,
+  // Value crossed discrete level (i.e. slider detent)
+  levelChange,
+}
+
+// System sounds and haptic feedback
+class SystemFeedback {
+  SystemFeedback._();
+
+  static final instance = SystemFeedback._();
+
+  // Default alert sound
+  Future<void> beep() {
+    return _channel.invokeMethod(Methods.feedbackBeep);
+  }
+
+  // Name is platform specific, i.e. 'Glass' (macOS), 'SystemAsterisk'
+  // (Windows) or 'message-new-instant' (Linux sound theme).
+  Future<void> playSystemSound(String name) {
+    return _channel.invokeMethod(Methods.feedbackPlaySystemSound, name);
+  }
+
+  // Ignored when there is no force touch trackpad; fails on other platforms.
+  Future<void> performHapticFeedback(HapticFeedbackPattern pattern) {
+    return _channel.invokeMethod(
+        Methods.feedbackPerformHapticFeedback, enumToString(pattern));
+  }
+
+  final _channel = MethodChannel(Channels.feedback);
+}
@@ -0,0 +1,95 @@
+// Item of Windows taskbar jump list; Activating the item launches the
+// application (or activates running instance if single instance is enforced)
+// and fires Application.openJumpListItemEvent.
+class JumpListItem {
+  JumpListItem({
+    required this.id,
+    required this.title,
+    this.description,
+    this.arguments = const [],
+    this.iconPath,
+    this.iconIndex = 0,
+  });
+
+  // Reported in JumpListActivation when the item is activated
+  final String id;
+  final String title;
+
+  // Shown as tooltip
+  final String? description;
+
+  // Additional command line arguments of launched instance
+  final List<String> arguments;
+
+  // Path of .ico, .exe or .dll file; Application icon is used if not set
+  final String? iconPath;
+  final int iconIndex;
+
+  dynamic serialize() => {
+        'id': id,
+        'title': title,
+        'description': description,
+        'arguments': arguments,
+        'iconPath': iconPath,
+        'iconIndex': iconIndex,
+      };
+}
+
+class JumpListCategory {
+  JumpListCategory({
+    required this.title,
+    required this.items,
+  });
+
+  final String title;
+  final List<JumpListItem> items;
+
+  dynamic serialize() => {
+        'title': title,
+        'items': items.map((e) => e.serialize()).toList(),
+      };
+}
+
+class JumpList {
+  JumpList({
+    this.tasks = const [],
+    this.categories = const [],
+    this.showRecent = false,
+    this.showFrequent = false,
+  });
+
+  final List<JumpListItem> tasks;
+  final List<JumpListCategory> categories;
+
+  // Categories maintained by the system
+  final bool showRecent;
+  final bool showFrequent;
+
+  dynamic serialize() => {
+        'tasks': tasks.map((e) => e.serialize()).toList(),
+        'categories': categories.map((e) => e.serialize()).toList(),
+        'showRecent': showRecent,
+        'showFrequent': showFrequent,
+      };
+}
+
+class JumpListActivation {
+  JumpListActivation({
+    required this.id,
+    required this.arguments,
+  });
+
+  final String id;
+  final List<String> arguments;
+
+  static JumpListActivation deserialize(dynamic value) {
+    final map = value as Map;
+    return JumpListActivation(
+      id: map['id'],
+      arguments: (map['arguments'] as List).cast<String>(),
+    );
+  }
+
+  @override
+  String toString() => 'JumpListActivation(id: $id, arguments: $arguments)';
+}
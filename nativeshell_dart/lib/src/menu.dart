@@ -1,7 +1,9 @@
 import 'package:flutter/material.dart';
 
 import 'accelerator.dart';
+import 'event.dart';
 import 'menu_internal.dart';
+import 'util.dart';
 
 enum MenuItemRole {
   // macOS specific
@@ -22,12 +24,12 @@ enum MenuItemRole {
   // macOS specific
   zoomWindow,
 
-  // macOS specific
   bringAllToFront,
 }
 
 enum MenuRole {
-  // macOS specific; Menus marked with window will have additional Window specific items in it
+  // Menus marked with window will have list of open windows appended (with
+  // active window checked)
   window,
 
   // macOS specific; Services menu
@@ -167,13 +169,74 @@ class Menu {
     state.update();
   }
 
-  // macOS specific. Sets this menu as application menu. It will be shown
-  // for every window that doesn't have window specific menu.
+  // Fired for duplicate accelerators and accelerators that collide with system
+  // shortcuts, shortly after menu (or any of its submenus) is updated.
+  static final diagnosticEvent = Event<MenuDiagnostic>();
+
+  // Sets this menu as application menu. On macOS it will be shown for every
+  // window that doesn't have window specific menu. On Linux it is exported to
+  // desktops with global menu bar (KDE, Unity); Not supported on Windows.
   Future<void> setAsAppMenu() {
     return state.setAsAppMenu();
   }
 }
 
+enum MenuDiagnosticKind {
+  // Multiple items in same menu tree use the accelerator
+  duplicateAccelerator,
+  // Accelerator is handled by the system and may never reach the application
+  systemShortcut,
+}
+
+class MenuDiagnostic {
+  MenuDiagnostic({
+    required this.kind,
+    required this.items,
+    this.systemShortcut,
+    required this.message,
+  });
+
+  final MenuDiagnosticKind kind;
+
+  // Affected items as paths of titles (i.e. 'File > Save')
+  final List<String> items;
+
+  // Name of colliding system shortcut
+  final String? systemShortcut;
+
+  final String message;
+
+  static MenuDiagnostic deserialize(dynamic value) {
+    final map = value as Map;
+    return MenuDiagnostic(
+      kind: enumFromString(MenuDiagnosticKind.values, map['kind'],
+          MenuDiagnosticKind.duplicateAccelerator),
+      items: (map['items'] as List).cast<String>(),
+      systemShortcut: map['systemShortcut'],
+      message: map['message'],
+    );
+  }
+
+  @override
+  String toString() => message;
+}
+
+// Standard window menu actions; Windows are cycled in order in which they are
+// listed in window menu.
+class WindowMenu {
+  static Future<void> selectNextWindow() {
+    return MenuManager.instance().selectNextWindow();
+  }
+
+  static Future<void> selectPreviousWindow() {
+    return MenuManager.instance().selectPreviousWindow();
+  }
+
+  static Future<void> bringAllToFront() {
+    return MenuManager.instance().bringAllToFront();
+  }
+}
+
 class MenuHandle {
   const MenuHandle(this.value);
 
@@ -37,8 +37,9 @@ class MenuState {
   // fired when replacing app menu; used to release handle in materialize
   static MenuState? _currentAppMenu;
 
-  // macOS specific. Sets this menu as application menu. It will be shown
-  // for every window that doesn't have window specific menu.
+  // Sets this menu as application menu. On macOS it will be shown for every
+  // window that doesn't have window specific menu. On Linux it is exported to
+  // desktops with global menu bar (KDE, Unity); Not supported on Windows.
   Future<void> setAsAppMenu() async {
     if (_currentAppMenu != null) {
       await _currentAppMenu!.unmaterialize();
@@ -414,6 +415,8 @@ class MenuManager {
       if (menu != null) {
         menu.onAction(id);
       }
+    } else if (call.method == Methods.menuOnDiagnostic) {
+      Menu.diagnosticEvent.fire(MenuDiagnostic.deserialize(call.arguments));
     } else if (call.method == Methods.menubarMoveToPreviousMenu) {
       for (final d in _delegates) {
         d.moveToPreviousMenu();
@@ -431,6 +434,18 @@ class MenuManager {
     });
   }
 
+  Future<void> selectNextWindow() {
+    return _menuChannel.invokeMethod(Methods.menuSelectNextWindow);
+  }
+
+  Future<void> selectPreviousWindow() {
+    return _menuChannel.invokeMethod(Methods.menuSelectPreviousWindow);
+  }
+
+  Future<void> bringAllToFront() {
+    return _menuChannel.invokeMethod(Methods.menuBringAllToFront);
+  }
+
   void registerDelegate(MenuManagerDelegate delegate) {
     _delegates.add(delegate);
   }
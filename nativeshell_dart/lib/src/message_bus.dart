@@ -0,0 +1,61 @@
+import 'package:flutter/services.dart';
+
+import 'api_constants.dart';
+
+typedef MessageBusListener = void Function(dynamic message);
+
+// Publish/subscribe messaging between engines (windows). Messages published
+// to a topic are delivered to all other engines that have listeners for the
+// topic, as well as to Rust listeners.
+class MessageBus {
+  MessageBus._() {
+    _channel.setMethodCallHandler(_onMethodCall);
+  }
+
+  static final instance = MessageBus._();
+
+  Future<void> publish(String topic, dynamic message) {
+    return _channel.invokeMethod(Methods.messageBusPublish, {
+      'topic': topic,
+      'message': message,
+    });
+  }
+
+  Future<void> addListener(String topic, MessageBusListener listener) async {
+    final listeners = _listeners.putIfAbsent(topic, () => []);
+    listeners.add(listener);
+    if (listeners.length == 1) {
+      await _channel.invokeMethod(Methods.messageBusSubscribe, {
+        'topic': topic,
+      });
+    }
+  }
+
+  Future<void> removeListener(String topic, MessageBusListener listener) async {
+    final listeners = _listeners[topic];
+    if (listeners == null || !listeners.remove(listener)) {
+      return;
+    }
+    if (listeners.isEmpty) {
+      _listeners.remove(topic);
+      await _channel.invokeMethod(Methods.messageBusUnsubscribe, {
+        'topic': topic,
+      });
+    }
+  }
+
+  Future<dynamic> _onMethodCall(MethodCall call) async {
+    if (call.method == Methods.messageBusOnMessage) {
+      final map = call.arguments as Map;
+      final listeners = _listeners[map['topic'] as String];
+      if (listeners != null) {
+        for (final l in List<MessageBusListener>.from(listeners)) {
+          l(map['message']);
+        }
+      }
+    }
+  }
+
+  final _listeners = <String, List<MessageBusListener>>{};
+  final _channel = MethodChannel(Channels.messageBus);
+}
@@ -0,0 +1,52 @@
+import 'package:flutter/services.dart';
+
+import 'api_constants.dart';
+import 'util.dart';
+
+enum PermissionKind {
+  screenRecording,
+  // Required for monitoring key events and controlling other applications
+  accessibility,
+  inputMonitoring,
+  camera,
+  microphone,
+}
+
+enum PermissionStatus {
+  granted,
+  denied,
+  // User has not been asked yet
+  notDetermined,
+  // Blocked by system policy; User can not change this
+  restricted,
+}
+
+// Privacy permissions required by capture and input monitoring features.
+// Platforms without permission model report everything as granted.
+class Permissions {
+  Permissions._();
+
+  static final instance = Permissions._();
+
+  // Screen recording permission on macOS is reported as denied until granted
+  // (it is not possible to tell whether user has been asked).
+  Future<PermissionStatus> getStatus(PermissionKind kind) async {
+    final res = await _channel.invokeMethod(
+        Methods.permissionGetStatus, enumToString(kind));
+    return _status(res);
+  }
+
+  // Shows system prompt if user has not been asked yet. Camera and microphone
+  // requests complete once user responds; Other prompts only direct user to
+  // System Preferences and changes take effect after application restart.
+  Future<PermissionStatus> request(PermissionKind kind) async {
+    final res = await _channel.invokeMethod(
+        Methods.permissionRequest, enumToString(kind));
+    return _status(res);
+  }
+
+  PermissionStatus _status(dynamic value) => enumFromString(
+      PermissionStatus.values, value, PermissionStatus.notDetermined);
+
+  final _channel = MethodChannel(Channels.permission);
+}
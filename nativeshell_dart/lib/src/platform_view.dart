@@ -0,0 +1,192 @@
+import 'dart:async';
+import 'dart:ui' show hashValues;
+
+import 'package:flutter/rendering.dart';
+import 'package:flutter/scheduler.dart';
+import 'package:flutter/widgets.dart';
+
+import 'window_manager.dart';
+
+// Embeds native view (NSView, HWND, GtkWidget) created by factory registered
+// for viewType on Rust side (PlatformViewManager::register_factory).
+//
+// Native view is positioned over the area occupied by this widget and clipped
+// by ancestor clips (i.e. scroll views). It receives mouse and keyboard input
+// directly. Flutter content can not be painted on top of native view.
+class PlatformView extends StatefulWidget {
+  const PlatformView({
+    Key? key,
+    required this.viewType,
+    this.params,
+  }) : super(key: key);
+
+  final String viewType;
+  final dynamic params;
+
+  @override
+  State<StatefulWidget> createState() => _PlatformViewState();
+}
+
+class _PlatformViewState extends State<PlatformView> {
+  @override
+  void initState() {
+    super.initState();
+    _create();
+  }
+
+  @override
+  void didUpdateWidget(covariant PlatformView oldWidget) {
+    super.didUpdateWidget(oldWidget);
+    if (oldWidget.viewType != widget.viewType ||
+        oldWidget.params != widget.params) {
+      _dispose();
+      _create();
+    }
+  }
+
+  @override
+  void dispose() {
+    _dispose();
+    super.dispose();
+  }
+
+  void _create() {
+    final completer = Completer<int?>();
+    _handle = completer.future;
+    _window.createPlatformView(widget.viewType, widget.params).then((handle) {
+      completer.complete(handle);
+      _lastGeometry = null;
+      _update(_pendingGeometry);
+    }, onError: (e) {
+      completer.complete(null);
+    });
+  }
+
+  void _dispose() {
+    final handle = _handle;
+    _handle = null;
+    handle?.then((handle) {
+      if (handle != null) {
+        _window.disposePlatformView(handle);
+      }
+    });
+  }
+
+  void _update(_Geometry? geometry) async {
+    _pendingGeometry = geometry;
+    final handle = _handle;
+    if (geometry == null || geometry == _lastGeometry || handle == null) {
+      return;
+    }
+    _lastGeometry = geometry;
+    final h = await handle;
+    if (h != null && _handle == handle) {
+      await _window.updatePlatformView(h,
+          frame: geometry.frame, clip: geometry.clip, visible: geometry.visible);
+    }
+  }
+
+  @override
+  Widget build(BuildContext context) {
+    return _PlatformViewPlaceholder(onGeometryChanged: _update);
+  }
+
+  final _window = WindowManager.instance.currentWindow;
+  Future<int?>? _handle;
+  _Geometry? _lastGeometry;
+  _Geometry? _pendingGeometry;
+}
+
+class _Geometry {
+  _Geometry(this.frame, this.clip, this.visible);
+
+  final Rect frame;
+  final Rect clip;
+  final bool visible;
+
+  @override
+  bool operator ==(Object other) =>
+      other is _Geometry &&
+      other.frame == frame &&
+      other.clip == clip &&
+      other.visible == visible;
+
+  @override
+  int get hashCode => hashValues(frame, clip, visible);
+}
+
+typedef _GeometryCallback = void Function(_Geometry);
+
+class _PlatformViewPlaceholder extends LeafRenderObjectWidget {
+  const _PlatformViewPlaceholder({
+    required this.onGeometryChanged,
+  });
+
+  final _GeometryCallback onGeometryChanged;
+
+  @override
+  RenderObject createRenderObject(BuildContext context) {
+    return _RenderPlatformView(onGeometryChanged);
+  }
+
+  @override
+  void updateRenderObject(
+      BuildContext context, covariant _RenderPlatformView renderObject) {
+    renderObject.onGeometryChanged = onGeometryChanged;
+  }
+}
+
+class _RenderPlatformView extends RenderBox {
+  _RenderPlatformView(this.onGeometryChanged);
+
+  _GeometryCallback onGeometryChanged;
+
+  @override
+  bool get sizedByParent => true;
+
+  @override
+  Size computeDryLayout(BoxConstraints constraints) => constraints.biggest;
+
+  @override
+  bool hitTestSelf(Offset position) => true;
+
+  @override
+  void paint(PaintingContext context, Offset offset) {
+    // Geometry can only be determined after all ancestors are painted
+    if (!_updateScheduled) {
+      _updateScheduled = true;
+      SchedulerBinding.instance!.addPostFrameCallback((_) {
+        _updateScheduled = false;
+        if (attached) {
+          onGeometryChanged(_computeGeometry());
+        }
+      });
+    }
+  }
+
+  @override
+  void detach() {
+    super.detach();
+    onGeometryChanged(_Geometry(Rect.zero, Rect.zero, false));
+  }
+
+  _Geometry _computeGeometry() {
+    final frame = MatrixUtils.transformRect(
+        getTransformTo(null), Offset.zero & size);
+    var clip = frame;
+    RenderObject child = this;
+    var parent = child.parent;
+    while (parent is RenderObject) {
+      final parentClip = parent.describeApproximatePaintClip(child);
+      if (parentClip != null) {
+        clip = clip.intersect(MatrixUtils.transformRect(
+            parent.getTransformTo(null), parentClip));
+      }
+      child = parent;
+      parent = child.parent;
+    }
+    return _Geometry(frame, clip, !clip.isEmpty);
+  }
+
+  bool _updateScheduled = false;
+}
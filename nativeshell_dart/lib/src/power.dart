@@ -0,0 +1,77 @@
+import 'package:flutter/services.dart';
+
+import 'api_constants.dart';
+import 'event.dart';
+import 'util.dart';
+
+enum PowerSource {
+  ac,
+  battery,
+  unknown,
+}
+
+class PowerStatus {
+  PowerStatus({
+    required this.powerSource,
+    required this.hasBattery,
+    this.batteryLevel,
+    required this.charging,
+    required this.lowPowerMode,
+  });
+
+  final PowerSource powerSource;
+
+  // False for desktop computers without battery
+  final bool hasBattery;
+
+  // 0.0 to 1.0; null if there is no battery or the level is not known
+  final double? batteryLevel;
+
+  final bool charging;
+
+  // Low Power Mode (macOS), battery saver (Windows) or power saver profile
+  // (Linux)
+  final bool lowPowerMode;
+
+  static PowerStatus deserialize(dynamic value) {
+    final map = value as Map;
+    return PowerStatus(
+      powerSource: enumFromString(
+          PowerSource.values, map['powerSource'], PowerSource.unknown),
+      hasBattery: map['hasBattery'],
+      batteryLevel: map['batteryLevel'],
+      charging: map['charging'],
+      lowPowerMode: map['lowPowerMode'],
+    );
+  }
+
+  @override
+  String toString() => 'PowerStatus($powerSource, batteryLevel: $batteryLevel, '
+      'charging: $charging, lowPowerMode: $lowPowerMode)';
+}
+
+// Battery and power source status, so that applications can throttle
+// background work when running on battery or in low power mode
+class Power {
+  Power._() {
+    _channel.setMethodCallHandler(_onMethodCall);
+  }
+
+  static final instance = Power._();
+
+  // Fired when power source, battery level or low power mode changes
+  final statusChanged = Event<PowerStatus>();
+
+  Future<PowerStatus> getStatus() async {
+    return PowerStatus.deserialize(
+        await _channel.invokeMethod(Methods.powerGetStatus));
+  }
+
+  Future<dynamic> _onMethodCall(MethodCall call) async {
+    if (call.method == Methods.powerStatusChanged) {
+      statusChanged.fire(PowerStatus.deserialize(call.arguments));
+    }
+  }
+
+  final _channel = MethodChannel(Channels.power);
+}
@@ -0,0 +1,80 @@
+import 'package:flutter/services.dart';
+
+import 'api_constants.dart';
+import 'event.dart';
+import 'util.dart';
+
+enum MeasurementSystem {
+  metric,
+  // United States customary units
+  us,
+  // Metric with imperial units for some quantities
+  uk,
+}
+
+class LocaleSettings {
+  LocaleSettings({
+    required this.locale,
+    this.region,
+    required this.firstDayOfWeek,
+    required this.uses24HourClock,
+    required this.measurementSystem,
+  });
+
+  // BCP 47 language tag (i.e. "en-US")
+  final String locale;
+
+  // ISO 3166 country code, if known
+  final String? region;
+
+  // DateTime.monday (1) to DateTime.sunday (7)
+  final int firstDayOfWeek;
+
+  // Includes user override of locale time format
+  final bool uses24HourClock;
+
+  final MeasurementSystem measurementSystem;
+
+  static LocaleSettings deserialize(dynamic value) {
+    final map = value as Map;
+    return LocaleSettings(
+      locale: map['locale'],
+      region: map['region'],
+      firstDayOfWeek: map['firstDayOfWeek'],
+      uses24HourClock: map['uses24HourClock'],
+      measurementSystem: enumFromString(MeasurementSystem.values,
+          map['measurementSystem'], MeasurementSystem.metric),
+    );
+  }
+
+  @override
+  String toString() => 'LocaleSettings(locale: $locale, region: $region, '
+      'firstDayOfWeek: $firstDayOfWeek, uses24HourClock: $uses24HourClock, '
+      'measurementSystem: $measurementSystem)';
+}
+
+// Regional preferences of the user; Allows reformatting dates and units
+// immediately when system settings change.
+class SystemLocale {
+  SystemLocale._() {
+    _channel.setMethodCallHandler(_onMethodCall);
+  }
+
+  static final instance = SystemLocale._();
+
+  // Fired when any of the settings change
+  final settingsChanged = Event<LocaleSettings>();
+
+  Future<LocaleSettings> getSettings() async {
+    return LocaleSettings.deserialize(
+        await _channel.invokeMethod(Methods.localeGetSettings));
+  }
+
+  Future<dynamic> _onMethodCall(MethodCall call) async {
+    if (call.method == Methods.localeSettingsChanged) {
+      settingsChanged.fire(LocaleSettings.deserialize(call.arguments));
+    }
+  }
+
+  final _channel = MethodChannel(Channels.locale);
+}
@@ -0,0 +1,126 @@
+import 'dart:ui';
+
+import 'package:flutter/services.dart';
+
+import 'api_constants.dart';
+import 'event.dart';
+
+// Windows system colors, which define the palette of high contrast themes
+class SystemColors {
+  SystemColors({
+    required this.window,
+    required this.windowText,
+    required this.highlight,
+    required this.highlightText,
+    required this.buttonFace,
+    required this.buttonText,
+    required this.grayText,
+    required this.hotlight,
+  });
+
+  final Color window;
+  final Color windowText;
+  final Color highlight;
+  final Color highlightText;
+  final Color buttonFace;
+  final Color buttonText;
+  final Color grayText;
+  // Hyperlinks
+  final Color hotlight;
+
+  static SystemColors deserialize(dynamic value) {
+    final map = value as Map;
+    return SystemColors(
+      window: Color(map['window']),
+      windowText: Color(map['windowText']),
+      highlight: Color(map['highlight']),
+      highlightText: Color(map['highlightText']),
+      buttonFace: Color(map['buttonFace']),
+      buttonText: Color(map['buttonText']),
+      grayText: Color(map['grayText']),
+      hotlight: Color(map['hotlight']),
+    );
+  }
+
+  @override
+  String toString() => 'SystemColors(window: $window, windowText: '
+      '$windowText, highlight: $highlight, highlightText: $highlightText)';
+}
+
+class SystemThemeSettings {
+  SystemThemeSettings({
+    required this.darkMode,
+    this.accentColor,
+    this.highlightColor,
+    this.highContrastTheme,
+    required this.systemDarkMode,
+    this.systemColors,
+  });
+
+  final bool darkMode;
+
+  // Null if not available on current system. On Linux both colors are the
+  // selection color of GTK theme.
+  final Color? accentColor;
+
+  // Background color of selected text
+  final Color? highlightColor;
+
+  // Name of active high contrast theme (Windows, Linux); Null if high contrast
+  // is off. When set, UI should be drawn using systemColors (Windows).
+  final String? highContrastTheme;
+
+  // Whether taskbar and start menu use dark theme; Only differs from darkMode
+  // on Windows
+  final bool systemDarkMode;
+
+  // Windows only
+  final SystemColors? systemColors;
+
+  static SystemThemeSettings deserialize(dynamic value) {
+    final map = value as Map;
+    final accentColor = map['accentColor'] as int?;
+    final highlightColor = map['highlightColor'] as int?;
+    final systemColors = map['systemColors'];
+    return SystemThemeSettings(
+      darkMode: map['darkMode'],
+      accentColor: accentColor != null ? Color(accentColor) : null,
+      highlightColor: highlightColor != null ? Color(highlightColor) : null,
+      highContrastTheme: map['highContrastTheme'],
+      systemDarkMode: map['systemDarkMode'],
+      systemColors: systemColors != null
+          ? SystemColors.deserialize(systemColors)
+          : null,
+    );
+  }
+
+  @override
+  String toString() => 'SystemThemeSettings(darkMode: $darkMode, '
+      'accentColor: $accentColor, highlightColor: $highlightColor, '
+      'highContrastTheme: $highContrastTheme)';
+}
+
+// System appearance; Allows Flutter theme to follow dark mode and accent color.
+class SystemTheme {
+  SystemTheme._() {
+    _channel.setMethodCallHandler(_onMethodCall);
+  }
+
+  static final instance = SystemTheme._();
+
+  // Fired when any of the settings change
+  final settingsChanged = Event<SystemThemeSettings>();
+
+  Future<SystemThemeSettings> getSettings() async {
+    return SystemThemeSettings.deserialize(
+        await _channel.invokeMethod(Methods.systemThemeGetSettings));
+  }
+
+  Future<dynamic> _onMethodCall(MethodCall call) async {
+    if (call.method == Methods.systemThemeSettingsChanged) {
+      settingsChanged.fire(SystemThemeSettings.deserialize(call.arguments));
+    }
+  }
+
+  final _channel = MethodChannel(Channels.systemTheme);
+}
@@ -0,0 +1,74 @@
+import 'dart:ui';
+
+import 'api_model.dart';
+import 'util.dart';
+
+enum TabletEventType {
+  proximityEnter,
+  proximityLeave,
+  down,
+  move,
+  up,
+}
+
+enum TabletPointerType {
+  unknown,
+  pen,
+  eraser,
+  cursor,
+}
+
+class TabletEvent {
+  TabletEvent({
+    required this.type,
+    required this.pointerType,
+    required this.deviceId,
+    this.position,
+    required this.pressure,
+    required this.tilt,
+    required this.rotation,
+    required this.buttons,
+  });
+
+  final TabletEventType type;
+  final TabletPointerType pointerType;
+
+  // Identifies the tool for the duration of its proximity
+  final int deviceId;
+
+  // Position in window coordinates; Not available for proximity events on
+  // some platforms
+  final Offset? position;
+
+  // 0.0 - 1.0
+  final double pressure;
+
+  // -1.0 - 1.0 for each axis; positive values tilt right and towards the user
+  final Offset tilt;
+
+  // Rotation in degrees
+  final double rotation;
+
+  // Barrel buttons (1 - lower button, 2 - upper button)
+  final int buttons;
+
+  static TabletEvent deserialize(dynamic value) {
+    final map = value as Map;
+    return TabletEvent(
+      type: enumFromString(
+          TabletEventType.values, map['eventType'], TabletEventType.move),
+      pointerType: enumFromString(TabletPointerType.values, map['pointerType'],
+          TabletPointerType.unknown),
+      deviceId: map['deviceId'],
+      position: OffsetExt.maybeDeserialize(map['position']),
+      pressure: map['pressure'],
+      tilt: OffsetExt.deserialize(map['tilt']),
+      rotation: map['rotation'],
+      buttons: map['buttons'],
+    );
+  }
+
+  @override
+  String toString() => 'TabletEvent($type, $pointerType, position: $position, '
+      'pressure: $pressure, tilt: $tilt)';
+}
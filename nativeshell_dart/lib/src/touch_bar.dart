@@ -0,0 +1,138 @@
+import 'package:flutter/foundation.dart';
+
+import 'util.dart';
+
+enum _TouchBarItemType {
+  button,
+  slider,
+  scrubber,
+}
+
+abstract class TouchBarItem {
+  TouchBarItem({
+    required this.title,
+    this.enabled = true,
+  });
+
+  final String title;
+  final bool enabled;
+
+  _TouchBarItemType get _type;
+
+  void _onAction(double? value, int? selectedIndex);
+
+  Map _serialize(int id) => {
+        'id': id,
+        'itemType': enumToString(_type),
+        'title': title,
+        'enabled': enabled,
+        'minValue': 0.0,
+        'maxValue': 0.0,
+        'value': 0.0,
+        'scrubberItems': <String>[],
+        'selectedIndex': null,
+      };
+}
+
+class TouchBarButton extends TouchBarItem {
+  TouchBarButton({
+    required String title,
+    bool enabled = true,
+    this.onPressed,
+  }) : super(title: title, enabled: enabled);
+
+  final VoidCallback? onPressed;
+
+  @override
+  _TouchBarItemType get _type => _TouchBarItemType.button;
+
+  @override
+  void _onAction(double? value, int? selectedIndex) {
+    onPressed?.call();
+  }
+}
+
+class TouchBarSlider extends TouchBarItem {
+  TouchBarSlider({
+    String title = '',
+    bool enabled = true,
+    this.minValue = 0.0,
+    this.maxValue = 1.0,
+    required this.value,
+    this.onChanged,
+  }) : super(title: title, enabled: enabled);
+
+  final double minValue;
+  final double maxValue;
+  final double value;
+  final ValueChanged<double>? onChanged;
+
+  @override
+  _TouchBarItemType get _type => _TouchBarItemType.slider;
+
+  @override
+  Map _serialize(int id) => super._serialize(id)
+    ..['minValue'] = minValue
+    ..['maxValue'] = maxValue
+    ..['value'] = value;
+
+  @override
+  void _onAction(double? value, int? selectedIndex) {
+    if (value != null) {
+      onChanged?.call(value);
+    }
+  }
+}
+
+class TouchBarScrubber extends TouchBarItem {
+  TouchBarScrubber({
+    bool enabled = true,
+    required this.items,
+    this.selectedIndex,
+    this.onSelected,
+  }) : super(title: '', enabled: enabled);
+
+  final List<String> items;
+  final int? selectedIndex;
+  final ValueChanged<int>? onSelected;
+
+  @override
+  _TouchBarItemType get _type => _TouchBarItemType.scrubber;
+
+  @override
+  Map _serialize(int id) => super._serialize(id)
+    ..['scrubberItems'] = items
+    ..['selectedIndex'] = selectedIndex;
+
+  @override
+  void _onAction(double? value, int? selectedIndex) {
+    if (selectedIndex != null) {
+      onSelected?.call(selectedIndex);
+    }
+  }
+}
+
+// Touch Bar displayed while window is active (macOS only). Set using
+// LocalWindow.setTouchBar.
+class TouchBar {
+  TouchBar(this.items);
+
+  final List<TouchBarItem> items;
+
+  dynamic serialize() => {
+        'items': items
+            .asMap()
+            .entries
+            .map((e) => e.value._serialize(e.key))
+            .toList(),
+      };
+
+  void onAction(dynamic arguments) {
+    final map = arguments as Map;
+    final id = map['id'] as int;
+    if (id >= 0 && id < items.length) {
+      items[id]._onAction(
+          map['value'] as double?, map['selectedIndex'] as int?);
+    }
+  }
+}
@@ -0,0 +1,103 @@
+import 'dart:async';
+import 'dart:typed_data';
+
+import 'package:flutter/services.dart';
+
+import 'api_constants.dart';
+
+// Receives payload sent from Rust in chunks. Next chunk is not sent until
+// the future returned from onChunk completes.
+abstract class TransferReceiver {
+  Future<void> onChunk(Uint8List data);
+  void onComplete();
+  void onCancel();
+}
+
+typedef TransferReceiverFactory = TransferReceiver Function();
+
+// Transfers large payloads between Dart and Rust in chunks with backpressure.
+class Transfer {
+  Transfer._() {
+    _channel.setMethodCallHandler(_onMethodCall);
+  }
+
+  static final instance = Transfer._();
+
+  static const defaultChunkSize = 1024 * 1024;
+
+  // Sends the data to Rust receiver registered under given name. Each chunk
+  // is only sent after Rust receiver acknowledged previous one.
+  Future<void> send(String name, Stream<List<int>> data) async {
+    final transferId = await _channel.invokeMethod(Methods.transferBegin, {
+      'name': name,
+    }) as int;
+    try {
+      await for (final chunk in data) {
+        await _channel.invokeMethod(Methods.transferChunk, {
+          'transferId': transferId,
+          'data': chunk is Uint8List ? chunk : Uint8List.fromList(chunk),
+        });
+      }
+    } catch (e) {
+      await _channel.invokeMethod(Methods.transferCancel, {
+        'transferId': transferId,
+      });
+      rethrow;
+    }
+    await _channel.invokeMethod(Methods.transferEnd, {
+      'transferId': transferId,
+    });
+  }
+
+  // Convenience method for sending existing buffer; Chunks are views into the
+  // buffer, no copy is made on Dart side.
+  Future<void> sendBytes(String name, Uint8List data,
+      {int chunkSize = defaultChunkSize}) {
+    return send(name, _chunks(data, chunkSize));
+  }
+
+  // Registers factory for receivers of transfers sent from Rust
+  void registerReceiver(String name, TransferReceiverFactory? factory) {
+    if (factory != null) {
+      _factories[name] = factory;
+    } else {
+      _factories.remove(name);
+    }
+  }
+
+  Stream<Uint8List> _chunks(Uint8List data, int chunkSize) async* {
+    for (var offset = 0; offset < data.length; offset += chunkSize) {
+      final end =
+          offset + chunkSize < data.length ? offset + chunkSize : data.length;
+      yield Uint8List.sublistView(data, offset, end);
+    }
+  }
+
+  Future<dynamic> _onMethodCall(MethodCall call) async {
+    final map = call.arguments as Map;
+    final transferId = map['transferId'] as int;
+    if (call.method == Methods.transferOnBegin) {
+      final factory = _factories[map['name'] as String];
+      if (factory == null) {
+        throw PlatformException(
+            code: 'no-receiver',
+            message: 'No receiver registered for "${map['name']}"');
+      }
+      _receivers[transferId] = factory();
+    } else if (call.method == Methods.transferOnChunk) {
+      final receiver = _receivers[transferId];
+      if (receiver == null) {
+        throw PlatformException(code: 'no-transfer');
+      }
+      await receiver.onChunk(map['data'] as Uint8List);
+    } else if (call.method == Methods.transferOnEnd) {
+      _receivers.remove(transferId)?.onComplete();
+    } else if (call.method == Methods.transferOnCancel) {
+      _receivers.remove(transferId)?.onCancel();
+    }
+  }
+
+  final _factories = <String, TransferReceiverFactory>{};
+  final _receivers = <int, TransferReceiver>{};
+  final _channel = MethodChannel(Channels.transfer);
+}
@@ -0,0 +1,21 @@
+import 'package:flutter/services.dart';
+
+import 'api_constants.dart';
+
+class UserActivity {
+  UserActivity._();
+
+  static final instance = UserActivity._();
+
+  // Time since last keyboard or mouse input anywhere in user session (i.e. to
+  // set away status or reset kiosk after inactivity). On Wayland only
+  // supported on GNOME.
+  Future<Duration> getIdleTime() async {
+    final double seconds =
+        await _channel.invokeMethod(Methods.userActivityGetIdleTime);
+    return Duration(
+        microseconds: (seconds * Duration.microsecondsPerSecond).round());
+  }
+
+  final _channel = MethodChannel(Channels.userActivity);
+}
@@ -6,8 +6,12 @@ import 'package:flutter/services.dart';
 import 'package:pedantic/pedantic.dart';
 
 import 'api_model.dart';
+import 'application.dart';
+import 'drag_drop.dart';
 import 'event.dart';
 import 'menu.dart';
+import 'tablet.dart';
+import 'touch_bar.dart';
 import 'util.dart';
 import 'window_manager.dart';
 import 'window_method_channel.dart';
@@ -46,6 +50,20 @@ class Window {
     return _showCompleter!.future;
   }
 
+  // Shows window centered next to anchor in screen coordinates (i.e. frame of
+  // tray icon). Transient popover is hidden when it loses focus.
+  Future<void> showPopover(
+    Rect anchor, {
+    PopoverEdge edge = PopoverEdge.below,
+    bool transient = true,
+  }) {
+    return _invokeMethod(Methods.windowShowPopover, {
+      'anchor': anchor.serialize(),
+      'edge': enumToString(edge),
+      'transient': transient,
+    });
+  }
+
   Future<dynamic> showModal() async {
     final res = await _invokeMethod(Methods.windowShowModal);
     return res;
@@ -60,6 +78,11 @@ class Window {
     await _invokeMethod(Methods.windowHide);
   }
 
+  // Brings window to front (restores it if minimized)
+  Future<void> activate() {
+    return _invokeMethod(Methods.windowActivate);
+  }
+
   Future<GeometryFlags> setGeometry(Geometry request,
       [GeometryPreference preference =
           GeometryPreference.preferContent]) async {
@@ -70,6 +93,17 @@ class Window {
     }));
   }
 
+  // Same as setGeometry followed by getGeometry, in single call
+  Future<GeometryUpdate> updateGeometry(Geometry request,
+      [GeometryPreference preference =
+          GeometryPreference.preferContent]) async {
+    return GeometryUpdate.deserialize(await _invokeMethod(
+        Methods.windowUpdateGeometry, {
+      'geometry': request.serialize(),
+      'preference': enumToString(preference)
+    }));
+  }
+
   Future<Geometry> getGeometry() async {
     return Geometry.deserialize(await _invokeMethod(Methods.windowGetGeometry));
   }
@@ -83,6 +117,46 @@ class Window {
     return _invokeMethod(Methods.windowSetTitle, title);
   }
 
+  // Shown below title on macOS 11+ and in header bar on Linux (see
+  // WindowStyle.headerBar); Not available on Windows.
+  Future<void> setSubtitle(String subtitle) {
+    return _invokeMethod(Methods.windowSetSubtitle, subtitle);
+  }
+
+  // macOS only; Shows proxy icon for the file in title bar (null removes it)
+  Future<void> setRepresentedFile(String? path) {
+    return _invokeMethod(Methods.windowSetRepresentedFile, path);
+  }
+
+  // Indicates unsaved changes (dot in close button on macOS, asterisk in title
+  // on other platforms)
+  Future<void> setDocumentEdited(bool edited) {
+    return _invokeMethod(Methods.windowSetDocumentEdited, edited);
+  }
+
+  // Forces light or dark window chrome regardless of system setting. On Linux
+  // this only affects window decorations provided by X11 window manager.
+  Future<void> setAppearance(WindowAppearance appearance) {
+    return _invokeMethod(Methods.windowSetAppearance, enumToString(appearance));
+  }
+
+  // Shows Mica or Acrylic material behind transparent parts of window content.
+  // Requires Windows 11; Not available on other platforms.
+  Future<void> setBackdrop(WindowBackdrop backdrop) {
+    return _invokeMethod(Methods.windowSetBackdrop, enumToString(backdrop));
+  }
+
+  // Restricts files that can be dropped in window; null accepts everything.
+  Future<void> setDropFilter(DropFilter? filter) {
+    return _invokeMethod(Methods.windowSetDropFilter, filter?.serialize());
+  }
+
+  // Sets _NET_WM_WINDOW_TYPE so that window manager treats special purpose
+  // windows correctly; Should be called before window is shown. Linux only.
+  Future<void> setWindowType(WindowType type) {
+    return _invokeMethod(Methods.windowSetWindowType, enumToString(type));
+  }
+
   Future<void> setStyle(WindowStyle style) {
     return _invokeMethod(Methods.windowSetStyle, style.serialize());
   }
@@ -97,14 +171,54 @@ class Window {
     return WindowManager.instance.getWindow(handle);
   }
 
-  static Future<Window> create(dynamic initData) {
-    return WindowManager.instance.createWindow(initData);
+  // Entrypoint, if specified, is the name of top level function to run instead
+  // of main in new window. The function must be annotated with
+  // @pragma('vm:entry-point'). Options configure the window before it is
+  // first shown.
+  static Future<Window> create(dynamic initData,
+      {String? entrypoint,
+      List<String>? entrypointArgs,
+      WindowOptions? options}) {
+    return WindowManager.instance.createWindow(initData,
+        entrypoint: entrypoint,
+        entrypointArgs: entrypointArgs,
+        options: options);
+  }
+
+  // Creates window without Flutter engine; Window can be configured and
+  // closed, but it doesn't run any Dart code.
+  static Future<Window> createNative(NativeWindowContent content,
+      {WindowOptions? options}) {
+    return WindowManager.instance
+        .createWindow(null, options: options, nativeContent: content);
   }
 
   final visibilityChangedEvent = Event<bool>();
   final closeRequestEvent = VoidEvent();
   final closeEvent = VoidEvent();
 
+  // Pointer entered or left window content area; Unlike MouseRegion these are
+  // not affected by popup menus
+  final pointerEnteredEvent = VoidEvent();
+  final pointerExitedEvent = VoidEvent();
+
+  // macOS only; Fired when user starts dragging title bar proxy icon of file
+  // set with setRepresentedFile (argument is the file path)
+  final representedFileDraggedEvent = Event<String>();
+
+  // macOS only; Fired when user switches Space (argument is whether the window
+  // is on the newly active Space)
+  final activeSpaceChangedEvent = Event<bool>();
+
+  // Fired when window could not be shown after the show request succeeded
+  // (i.e. while waiting for first frame); Pending show() completes with the
+  // same error
+  final showFailedEvent = Event<PlatformException>();
+
+  bool _pointerInside = false;
+
+  bool get isPointerInside => _pointerInside;
+
   void onMessage(String message, dynamic arguments) {
     if (message == Events.windowInitialize) {
       _initialized = true;
@@ -119,6 +233,26 @@ class Window {
     } else if (message == Events.windowClose) {
       WindowManager.instance.windowClosed(this);
       closeEvent.fire();
+    } else if (message == Events.windowPointerEntered) {
+      _pointerInside = true;
+      pointerEnteredEvent.fire();
+    } else if (message == Events.windowPointerExited) {
+      _pointerInside = false;
+      pointerExitedEvent.fire();
+    } else if (message == Events.windowRepresentedFileDragged) {
+      representedFileDraggedEvent.fire(arguments as String);
+    } else if (message == Events.windowActiveSpaceChanged) {
+      activeSpaceChangedEvent.fire(arguments as bool);
+    } else if (message == Events.windowShowFailed) {
+      final error = PlatformException(
+          code: arguments['code'] as String,
+          message: arguments['message'] as String?,
+          details: arguments['details']);
+      showFailedEvent.fire(error);
+      if (_showCompleter != null) {
+        _showCompleter!.completeError(error);
+        _showCompleter = null;
+      }
     }
   }
 
@@ -185,22 +319,34 @@ class LocalWindow extends Window {
     Rect? trackingRect,
     Rect? itemRect,
     bool preselectFirst = false,
+    Rect? keyboardAnchor,
   }) async {
     final handle = await menu.state.materialize();
     final res = await showPopupMenuWithHandle(handle, globalPosition,
         trackingRect: trackingRect,
         itemRect: itemRect,
-        preselectFirst: preselectFirst);
+        preselectFirst: preselectFirst,
+        keyboardAnchor: keyboardAnchor);
     await menu.state.unmaterialize();
     return res;
   }
 
+  // Shows context menu invoked from keyboard (i.e. context menu key) for
+  // focused element with given rect (in window coordinates); First item is
+  // selected so that the menu can be navigated with arrow keys
+  Future<PopupMenuResponse> showPopupMenuForKeyboard(
+      Menu menu, Rect focusedRect) {
+    return showPopupMenu(menu, focusedRect.bottomLeft,
+        keyboardAnchor: focusedRect);
+  }
+
   Future<PopupMenuResponse> showPopupMenuWithHandle(
     MenuHandle handle,
     Offset globalPosition, {
     Rect? trackingRect,
     Rect? itemRect,
     bool preselectFirst = false,
+    Rect? keyboardAnchor,
   }) async {
     final value = await _invokeMethod(
         Methods.windowShowPopupMenu,
@@ -209,7 +355,8 @@ class LocalWindow extends Window {
                 position: globalPosition,
                 trackingRect: trackingRect,
                 itemRect: itemRect,
-                preselectFirst: preselectFirst)
+                preselectFirst: preselectFirst,
+                keyboardAnchor: keyboardAnchor)
             .serialize());
     return PopupMenuResponse.deserialize(value);
   }
@@ -219,10 +366,71 @@ class LocalWindow extends Window {
         HidePopupMenuRequest(handle: handle).serialize());
   }
 
+  // Shows system share UI next to anchor (in window coordinates; macOS).
+  // Windows requires title and doesn't support sharing files; Not available
+  // on Linux.
+  Future<void> showSharePicker({
+    String? text,
+    List<String> urls = const [],
+    List<String> files = const [],
+    String? title,
+    required Rect anchor,
+  }) async {
+    await _invokeMethod(Methods.windowShowSharePicker, {
+      'text': text,
+      'urls': urls,
+      'files': files,
+      'title': title,
+      'anchor': anchor.serialize(),
+    });
+  }
+
+  // MacOS only; Shows Quick Look panel for given items or updates items of
+  // panel already shown for this window.
+  Future<void> showQuickLook(List<QuickLookItem> items,
+      {int currentIndex = 0}) async {
+    await _invokeMethod(Methods.windowShowQuickLook, {
+      'items': items.map((e) => e.serialize()).toList(),
+      'currentIndex': currentIndex,
+    });
+  }
+
+  Future<void> setQuickLookIndex(int index) async {
+    await _invokeMethod(Methods.windowSetQuickLookIndex, index);
+  }
+
+  Future<void> hideQuickLook() async {
+    await _invokeMethod(Methods.windowHideQuickLook);
+  }
+
   Future<void> showSystemMenu() async {
     await _invokeMethod(Methods.windowShowSystemMenu);
   }
 
+  // Windows only; Declares rect of maximize button drawn in window without
+  // title bar, so that Windows 11 shows Snap Layouts when hovering it. Button
+  // still receives pointer events.
+  Future<void> setMaximizeButtonRect(Rect? rect) async {
+    await _invokeMethod(Methods.windowSetMaximizeButtonRect, rect?.serialize());
+  }
+
+  // Windows only; Windows with different AppUserModelID are grouped separately
+  // on taskbar. Null reverts to AppUserModelID of the process.
+  Future<void> setAppUserModelId(String? id) async {
+    await _invokeMethod(Methods.windowSetAppUserModelId, id);
+  }
+
+  // macOS only; Whether the window is on currently active Space
+  Future<bool> isOnActiveSpace() async {
+    return await _invokeMethod(Methods.windowIsOnActiveSpace);
+  }
+
+  // macOS only; Moves window to currently active Space, i.e. to follow user
+  // when activeSpaceChangedEvent fires
+  Future<void> moveToActiveSpace() async {
+    await _invokeMethod(Methods.windowMoveToActiveSpace);
+  }
+
   Menu? _currentWindowMenu;
 
   Menu? get currentWindowMenu => _currentWindowMenu;
@@ -240,6 +448,91 @@ class LocalWindow extends Window {
     return res;
   }
 
+  TouchBar? _currentTouchBar;
+
+  TouchBar? get currentTouchBar => _currentTouchBar;
+
+  // MacOS only; Pass null to remove the touch bar from window
+  Future<void> setTouchBar(TouchBar? touchBar) async {
+    _currentTouchBar = touchBar;
+    await _invokeMethod(Methods.windowSetTouchBar, {
+      'touchBar': touchBar?.serialize(),
+    });
+  }
+
+  // Called when application is about to quit; Windows are asked one at a time
+  // so that each can activate itself before prompting the user. Set by
+  // WindowWidget to WindowState.confirmQuit.
+  ShouldTerminateHandler? confirmQuitHandler;
+
+  // Low level platform view API; Use PlatformView widget instead
+  Future<int> createPlatformView(String viewType, dynamic params) async {
+    return await _invokeMethod(Methods.windowCreatePlatformView, {
+      'viewType': viewType,
+      'params': params,
+    });
+  }
+
+  Future<void> updatePlatformView(
+    int handle, {
+    required Rect frame,
+    required Rect clip,
+    required bool visible,
+  }) async {
+    await _invokeMethod(Methods.windowUpdatePlatformView, {
+      'handle': handle,
+      'frame': frame.serialize(),
+      'clip': clip.serialize(),
+      'visible': visible,
+    });
+  }
+
+  Future<void> disposePlatformView(int handle) async {
+    await _invokeMethod(Methods.windowDisposePlatformView, {
+      'handle': handle,
+    });
+  }
+
+  // Embeds Flutter view running in a separate engine (and isolate) in window
+  // content. Returns handle of the engine, which identifies the view. Dart code
+  // of the view gets this window as current window.
+  Future<int> attachEngineView({
+    required Rect frame,
+    dynamic initData,
+    String? entrypoint,
+    List<String>? entrypointArgs,
+  }) async {
+    return await _invokeMethod(Methods.windowAttachEngineView, {
+      'frame': frame.serialize(),
+      'initData': initData,
+      'entrypoint': entrypoint,
+      'entrypointArgs': entrypointArgs ?? <String>[],
+    });
+  }
+
+  Future<void> updateEngineView(
+    int engine, {
+    required Rect frame,
+    bool visible = true,
+  }) async {
+    await _invokeMethod(Methods.windowUpdateEngineView, {
+      'engine': engine,
+      'frame': frame.serialize(),
+      'visible': visible,
+    });
+  }
+
+  // Shuts down the engine of the view
+  Future<void> detachEngineView(int engine) async {
+    await _invokeMethod(Methods.windowDetachEngineView, {
+      'engine': engine,
+    });
+  }
+
+  // Pen events within window content (proximity, contact and movement);
+  // Fired in addition to regular pointer events
+  final tabletEvent = Event<TabletEvent>();
+
   Future<void> performDrag() async {
     await _invokeMethod(Methods.windowPerformWindowDrag);
   }
@@ -0,0 +1,87 @@
+import 'dart:async';
+import 'dart:typed_data';
+import 'dart:ui' as ui;
+
+import 'package:flutter/services.dart';
+
+import 'api_constants.dart';
+import 'api_model.dart';
+
+// Top level window of another application
+class ExternalWindow {
+  ExternalWindow({
+    required this.id,
+    this.title,
+    required this.ownerName,
+    required this.ownerPid,
+    required this.frame,
+    this.thumbnail,
+  });
+
+  // CGWindowID (macOS), HWND (Windows) or X11 window
+  final int id;
+
+  // Not available on macOS without screen recording permission
+  final String? title;
+  final String ownerName;
+  final int ownerPid;
+
+  // Logical coordinates, top left origin
+  final ui.Rect frame;
+
+  final ui.Image? thumbnail;
+
+  static Future<ExternalWindow> deserialize(dynamic value) async {
+    final map = value as Map;
+    return ExternalWindow(
+      id: map['id'],
+      title: map['title'],
+      ownerName: map['ownerName'],
+      ownerPid: map['ownerPid'],
+      frame: RectExt.deserialize(map['frame']),
+      thumbnail: await _decodeThumbnail(map['thumbnail']),
+    );
+  }
+
+  static Future<ui.Image?> _decodeThumbnail(dynamic value) {
+    if (value == null) {
+      return Future.value(null);
+    }
+    final map = value as Map;
+    final completer = Completer<ui.Image?>();
+    ui.decodeImageFromPixels(
+      map['data'] as Uint8List,
+      map['width'],
+      map['height'],
+      ui.PixelFormat.rgba8888,
+      completer.complete,
+      rowBytes: map['bytesPerRow'],
+    );
+    return completer.future;
+  }
+
+  @override
+  String toString() =>
+      'ExternalWindow($id, title: $title, owner: $ownerName, frame: $frame)';
+}
+
+// Enumerates windows of other applications, so that screen sharing and
+// window capture utilities can present a window picker. On macOS titles and
+// thumbnails require screen recording permission (see Permissions). Not
+// supported on Wayland.
+class WindowList {
+  WindowList._();
+
+  static final instance = WindowList._();
+
+  // Windows are returned in front to back order. Thumbnails are only captured
+  // when thumbnailSize (maximum size in pixels) is specified.
+  Future<List<ExternalWindow>> getWindows({ui.Size? thumbnailSize}) async {
+    final res = await _channel.invokeMethod(Methods.windowListGetWindows, {
+      'thumbnailSize': thumbnailSize?.serialize(),
+    }) as List;
+    return Future.wait(res.map(ExternalWindow.deserialize));
+  }
+
+  final _channel = MethodChannel(Channels.windowList);
+}
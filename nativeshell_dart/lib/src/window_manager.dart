@@ -2,7 +2,11 @@ import 'package:flutter/widgets.dart';
 
 import 'key_interceptor.dart';
 import 'api_constants.dart';
+import 'api_model.dart';
+import 'application.dart';
 import 'drag_drop.dart';
+import 'tablet.dart';
+import 'util.dart';
 import 'event.dart';
 import 'window.dart';
 import 'window_method_channel.dart';
@@ -61,9 +65,16 @@ class WindowManager {
 
     dispatcher.registerMessageHandler(Channels.windowManager, _onMessage);
     dispatcher.registerMethodHandler(Channels.dropTarget, _onDropTargetCall);
+    dispatcher.registerMethodHandler(Channels.touchBar, _onTouchBarCall);
+    dispatcher.registerMethodHandler(Channels.tablet, _onTabletCall);
+    dispatcher.registerMethodHandler(Channels.quit, _onQuitCall);
   }
 
-  Future<Window> createWindow(dynamic initData) async {
+  Future<Window> createWindow(dynamic initData,
+      {String? entrypoint,
+      List<String>? entrypointArgs,
+      WindowOptions? options,
+      NativeWindowContent? nativeContent}) async {
     final dispatcher = WindowMethodDispatcher.instance;
     final result = await dispatcher.invokeMethod(
         channel: Channels.windowManager,
@@ -72,6 +83,10 @@ class WindowManager {
         arguments: {
           'parent': currentWindow.handle.value,
           'initData': initData,
+          'entrypoint': entrypoint,
+          'entrypointArgs': entrypointArgs ?? <String>[],
+          'options': options?.serialize(),
+          'nativeContent': nativeContent?.serialize(),
         });
     final handle = WindowHandle(result['windowHandle'] as int);
     final res = _windows.putIfAbsent(handle, () => Window(handle));
@@ -109,6 +124,33 @@ class WindowManager {
     }
   }
 
+  Future<dynamic> _onTouchBarCall(WindowMethodCall call) async {
+    final window = _windows[call.targetWindowHandle];
+    if (window is LocalWindow && call.method == Methods.touchBarOnAction) {
+      window.currentTouchBar?.onAction(call.arguments);
+    }
+    return null;
+  }
+
+  Future<dynamic> _onTabletCall(WindowMethodCall call) async {
+    final window = _windows[call.targetWindowHandle];
+    if (window is LocalWindow && call.method == Methods.tabletOnEvent) {
+      window.tabletEvent.fire(TabletEvent.deserialize(call.arguments));
+    }
+    return null;
+  }
+
+  Future<dynamic> _onQuitCall(WindowMethodCall call) async {
+    final window = _windows[call.targetWindowHandle];
+    final handler = window is LocalWindow ? window.confirmQuitHandler : null;
+    if (handler != null && call.method == Methods.quitConfirm) {
+      final reason = enumFromString(TerminationReason.values,
+          call.arguments as String, TerminationReason.quit);
+      return await handler(reason);
+    }
+    return true;
+  }
+
   final windowAddedEvent = Event<Window>();
 }
 
@@ -67,7 +67,8 @@ class WindowMethodDispatcher {
     final code = decoded['code'] as String?;
     final message = decoded['message'] as String?;
     if (code != null) {
-      throw PlatformException(code: code, message: message);
+      throw PlatformException(
+          code: code, message: message, details: decoded['details']);
     }
     return decoded['result'];
   }
@@ -141,17 +142,18 @@ class WindowMethodDispatcher {
       return _encodeError('error', 'Malformed message');
     } on PlatformException catch (e) {
       print(e);
-      return _encodeError(e.code, e.message);
+      return _encodeError(e.code, e.message, e.details);
     } catch (e) {
       print(e);
       return _encodeError('error', e.toString());
     }
   }
 
-  ByteData _encodeError(String code, String? message) {
+  ByteData _encodeError(String code, String? message, [dynamic details]) {
     return _codec.encodeMessage({
       'code': code,
       'message': message,
+      'details': details,
     })!;
   }
 
@@ -178,3 +180,50 @@ class WindowMethodChannel {
     _dispatcher.registerMethodHandler(name, handler);
   }
 }
+
+// Typed request/reply between windows. Requests and replies are converted
+// to and from values supported by StandardMessageCodec with given functions.
+// Sending request to window that doesn't exist (or that closes before
+// replying) fails with PlatformException with code 'invalid-window-handle'
+// ('window-closed' respectively).
+class WindowRequestChannel<Request, Reply> {
+  const WindowRequestChannel(
+    this.name, {
+    required this.encodeRequest,
+    required this.decodeRequest,
+    required this.encodeReply,
+    required this.decodeReply,
+  });
+
+  final String name;
+  final dynamic Function(Request request) encodeRequest;
+  final Request Function(dynamic value) decodeRequest;
+  final dynamic Function(Reply reply) encodeReply;
+  final Reply Function(dynamic value) decodeReply;
+
+  Future<Reply> sendRequest(WindowHandle target, Request request) async {
+    final res = await _dispatcher.invokeMethod(
+        channel: name,
+        method: _requestMethod,
+        arguments: encodeRequest(request),
+        targetWindowHandle: target);
+    return decodeReply(res);
+  }
+
+  // Handles requests sent to current window on this channel
+  void setRequestHandler(FutureOr<Reply> Function(Request request)? handler) {
+    if (handler == null) {
+      _dispatcher.registerMethodHandler(name, null);
+    } else {
+      _dispatcher.registerMethodHandler(name, (call) async {
+        if (call.method != _requestMethod) {
+          throw PlatformException(
+              code: 'error', message: 'Unexpected method ${call.method}');
+        }
+        return encodeReply(await handler(decodeRequest(call.arguments)));
+      });
+    }
+  }
+
+  static const _requestMethod = 'request';
+}
@@ -6,6 +6,7 @@ import 'package:flutter/scheduler.dart';
 import 'package:flutter/widgets.dart';
 
 import 'api_model.dart';
+import 'application.dart';
 import 'window.dart';
 import 'window_manager.dart';
 
@@ -55,6 +56,11 @@ abstract class WindowState {
     await window.setGeometry(Geometry(contentSize: contentSize));
   }
 
+  // Called before application quits. Windows are asked one at a time; If you
+  // need to prompt the user (i.e. unsaved changes), call window.activate()
+  // first. Return false to cancel quitting.
+  Future<bool> confirmQuit(TerminationReason reason) async => true;
+
   // Convenience function to calculate initial geometry for centered windows
   Future<Geometry> centerInParent(Size contentSize) async {
     final parent = window.parentWindow;
@@ -156,6 +162,7 @@ class _WindowWidgetState extends State<WindowWidget> {
       final window = WindowManager.instance.currentWindow;
       _windowContext ??= widget.onCreateState(window.initData);
       _windowContext!._requestUpdateConstraints = requestUpdateConstraints;
+      window.confirmQuitHandler = _windowContext!.confirmQuit;
 
       return Listener(
         onPointerDown: _onWindowTap,
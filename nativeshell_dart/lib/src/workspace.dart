@@ -0,0 +1,97 @@
+import 'package:flutter/services.dart';
+
+import 'api_constants.dart';
+
+// URL scheme (i.e. 'mailto') or file extension without leading dot
+class HandlerTarget {
+  HandlerTarget.urlScheme(String scheme)
+      : _kind = 'urlScheme',
+        value = scheme;
+
+  HandlerTarget.fileExtension(String extension)
+      : _kind = 'fileExtension',
+        value = extension;
+
+  final String _kind;
+  final String value;
+
+  dynamic serialize() => {_kind: value};
+
+  @override
+  String toString() => 'HandlerTarget($_kind: $value)';
+}
+
+// Application registered as default handler for URL scheme or file type
+class DefaultHandler {
+  DefaultHandler({
+    required this.name,
+    required this.id,
+    required this.isCurrentApplication,
+  });
+
+  final String name;
+
+  // Bundle identifier (macOS), executable path (Windows) or desktop file id
+  // (Linux)
+  final String id;
+
+  final bool isCurrentApplication;
+
+  static DefaultHandler deserialize(dynamic value) {
+    final map = value as Map;
+    return DefaultHandler(
+      name: map['name'],
+      id: map['id'],
+      isCurrentApplication: map['isCurrentApplication'],
+    );
+  }
+
+  @override
+  String toString() => 'DefaultHandler($name, id: $id)';
+}
+
+// Opens URLs and files with default applications
+class Workspace {
+  Workspace._();
+
+  static final instance = Workspace._();
+
+  // Opens URL in default browser (or application registered for the scheme)
+  Future<void> openUrl(String url) {
+    return _channel.invokeMethod(Methods.workspaceOpenUrl, url);
+  }
+
+  // Opens file in default application or directory in file manager
+  Future<void> openPath(String path) {
+    return _channel.invokeMethod(Methods.workspaceOpenPath, path);
+  }
+
+  // Shows file manager (Finder, Explorer) with the item selected
+  Future<void> revealInFileManager(String path) {
+    return _channel.invokeMethod(Methods.workspaceRevealInFileManager, path);
+  }
+
+  // Moves items to trash so that deletion can be undone. Returns locations of
+  // trashed items, which are only known on macOS (null otherwise).
+  Future<List<String?>> trashItems(List<String> paths) async {
+    final res = await _channel.invokeMethod(Methods.workspaceTrashItems, paths);
+    return (res as List).cast<String?>();
+  }
+
+  // Returns null when there is no application registered for the target
+  Future<DefaultHandler?> getDefaultHandler(HandlerTarget target) async {
+    final res = await _channel.invokeMethod(
+        Methods.workspaceGetDefaultHandler, target.serialize());
+    return res != null ? DefaultHandler.deserialize(res) : null;
+  }
+
+  // Asks system to make this application default handler. User is asked to
+  // confirm the change on macOS; Windows only opens default apps settings.
+  // Linux requires installed desktop file named after the program.
+  Future<void> setAsDefaultHandler(HandlerTarget target) {
+    return _channel.invokeMethod(
+        Methods.workspaceSetAsDefaultHandler, target.serialize());
+  }
+
+  final _channel = MethodChannel(Channels.workspace);
+}